@@ -1,64 +1,326 @@
+use crate::bot::health::HealthState;
 use crate::config::Settings;
-use crate::data::models::{GuildPrefix, ModerationAction, ModerationCase};
-use crate::utils::BotError;
+use crate::data::models::{
+    GuildCommandChannel, GuildCommandCooldown, GuildDisabledCommand, GuildEmbedTheme, GuildLocale, GuildPrefix, ModerationAction,
+    ModerationCase,
+};
+use crate::data::repos::{RepoError, Repos};
+use crate::utils::command_channels::CommandChannels;
+use crate::utils::command_cooldowns::{cooldown_for, CommandCooldownTracker, CommandCooldowns};
+use crate::utils::content_filter::{ContentFilter, GlobalContentFilter, NameFilters};
+use crate::utils::disabled_commands::{disabled_by, DisabledCommands};
+use crate::utils::embed_theme::EmbedTheme;
+use crate::utils::i18n::Locale;
+use crate::utils::name_severity::NameCheck;
+use crate::utils::{
+    error::{error_code, ErrorCode},
+    BoostStateCache, BotError, CheckFailure, CommandRegistry, CommandUsageCounters,
+    EmbedPermissionCache, ExperimentCounters, FailureTracker, GuildAvailability,
+    InFlightRoleChanges, InviteTracker, PerformanceTracker, PermissionFailureLog, PrefixCache,
+};
 use serenity::all::{GuildId, UserId};
 use sqlx::SqlitePool;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
 
 /// Bot application data that will be accessible in all commands
 #[derive(Debug, Clone)]
 pub struct Data {
     pub settings: Settings,
     pub db_pool: SqlitePool,
-    pub prefix_cache: Arc<RwLock<HashMap<u64, String>>>,
+    /// Booster roles and their guild settings, behind traits
+    pub repos: Repos,
+    pub prefix_cache: PrefixCache,
+    pub command_channel_cache: Arc<RwLock<HashMap<u64, CommandChannels>>>,
+    pub disabled_command_cache: Arc<RwLock<HashMap<u64, DisabledCommands>>>,
+    pub command_cooldown_cache: Arc<RwLock<HashMap<u64, CommandCooldowns>>>,
+    pub cooldown_tracker: CommandCooldownTracker,
+    pub content_filters: Arc<RwLock<HashMap<u64, Arc<ContentFilter>>>>,
+    pub embed_themes: Arc<RwLock<HashMap<u64, EmbedTheme>>>,
+    pub guild_locales: Arc<RwLock<HashMap<u64, Option<Locale>>>>,
+    pub global_filter: Arc<GlobalContentFilter>,
+    pub command_registry: Arc<RwLock<CommandRegistry>>,
+    pub embed_permission_cache: EmbedPermissionCache,
+    pub failure_tracker: FailureTracker,
+    pub experiments: ExperimentCounters,
+    pub command_usage: CommandUsageCounters,
+    pub permission_failures: PermissionFailureLog,
+    pub guild_availability: GuildAvailability,
+    pub invite_tracker: InviteTracker,
+    pub role_changes: InFlightRoleChanges,
+    pub boost_states: BoostStateCache,
+    pub performance: PerformanceTracker,
+    /// Gateway readiness for the health server; the framework swaps in the
+    /// one `main` started the server with
+    pub health: HealthState,
+    /// Cancelled when the bot starts shutting down; background loops watch it
+    pub shutdown: CancellationToken,
+    /// When the bot process started, for uptime reporting
+    pub started_at: Instant,
 }
 
 impl Data {
     pub fn new(settings: Settings, db_pool: SqlitePool) -> Self {
+        let global_filter = Arc::new(GlobalContentFilter::new(db_pool.clone()));
         Self {
             settings,
+            repos: Repos::sqlite(db_pool.clone()),
             db_pool,
-            prefix_cache: Arc::new(RwLock::new(HashMap::new())),
+            prefix_cache: PrefixCache::new(),
+            command_channel_cache: Arc::new(RwLock::new(HashMap::new())),
+            disabled_command_cache: Arc::new(RwLock::new(HashMap::new())),
+            command_cooldown_cache: Arc::new(RwLock::new(HashMap::new())),
+            cooldown_tracker: CommandCooldownTracker::new(),
+            content_filters: Arc::new(RwLock::new(HashMap::new())),
+            embed_themes: Arc::new(RwLock::new(HashMap::new())),
+            guild_locales: Arc::new(RwLock::new(HashMap::new())),
+            global_filter,
+            command_registry: Arc::new(RwLock::new(CommandRegistry::new())),
+            embed_permission_cache: EmbedPermissionCache::new(),
+            failure_tracker: FailureTracker::new(),
+            experiments: ExperimentCounters::new(),
+            command_usage: CommandUsageCounters::new(),
+            permission_failures: PermissionFailureLog::new(),
+            guild_availability: GuildAvailability::new(),
+            invite_tracker: InviteTracker::new(),
+            role_changes: InFlightRoleChanges::new(),
+            boost_states: BoostStateCache::new(),
+            performance: PerformanceTracker::new(),
+            health: HealthState::new(),
+            shutdown: CancellationToken::new(),
+            started_at: Instant::now(),
         }
     }
 
+    /// Custom prefix for a guild; the database is only asked once per guild
     pub async fn get_guild_prefix(&self, guild_id: u64) -> Result<Option<String>, Error> {
-        let cache = self.prefix_cache.read().await;
-        if let Some(prefix) = cache.get(&guild_id) {
-            return Ok(Some(prefix.clone()));
+        if let Some(prefix) = self.prefix_cache.get(guild_id).await {
+            return Ok(prefix);
         }
-        drop(cache);
 
         let prefix = GuildPrefix::get(&self.db_pool, guild_id).await?;
-
-        if let Some(ref p) = prefix {
-            let mut cache = self.prefix_cache.write().await;
-            cache.insert(guild_id, p.clone());
-        }
+        self.prefix_cache.store(guild_id, prefix.clone()).await;
 
         Ok(prefix)
     }
 
     pub async fn set_guild_prefix(&self, guild_id: u64, prefix: &str) -> Result<(), Error> {
         GuildPrefix::set(&self.db_pool, guild_id, prefix).await?;
-
-        let mut cache = self.prefix_cache.write().await;
-        cache.insert(guild_id, prefix.to_string());
+        self.prefix_cache
+            .store(guild_id, Some(prefix.to_string()))
+            .await;
 
         Ok(())
     }
 
     pub async fn remove_guild_prefix(&self, guild_id: u64) -> Result<bool, Error> {
         let removed = GuildPrefix::remove(&self.db_pool, guild_id).await?;
+        self.prefix_cache.store(guild_id, None).await;
+
+        Ok(removed)
+    }
 
-        if removed {
-            let mut cache = self.prefix_cache.write().await;
-            cache.remove(&guild_id);
+    /// Channels each command group is confined to; checked on every command
+    pub async fn get_command_channels(
+        &self,
+        guild_id: GuildId,
+    ) -> Result<CommandChannels, Error> {
+        let cache = self.command_channel_cache.read().await;
+        if let Some(rows) = cache.get(&guild_id.get()) {
+            return Ok(rows.clone());
         }
+        drop(cache);
 
-        Ok(removed)
+        let rows = GuildCommandChannel::list(&self.db_pool, guild_id).await?;
+
+        let mut cache = self.command_channel_cache.write().await;
+        cache.insert(guild_id.get(), rows.clone());
+
+        Ok(rows)
+    }
+
+    pub async fn invalidate_command_channels(&self, guild_id: GuildId) {
+        let mut cache = self.command_channel_cache.write().await;
+        cache.remove(&guild_id.get());
+    }
+
+    /// The disabled entry covering a command, if any; checked on every command
+    pub async fn disabled_command(
+        &self,
+        guild_id: GuildId,
+        qualified_name: &str,
+    ) -> Result<Option<String>, Error> {
+        let cache = self.disabled_command_cache.read().await;
+        if let Some(disabled) = cache.get(&guild_id.get()) {
+            return Ok(disabled_by(qualified_name, disabled).map(str::to_string));
+        }
+        drop(cache);
+
+        let disabled: DisabledCommands = GuildDisabledCommand::list(&self.db_pool, guild_id)
+            .await?
+            .into_iter()
+            .collect();
+        let entry = disabled_by(qualified_name, &disabled).map(str::to_string);
+
+        let mut cache = self.disabled_command_cache.write().await;
+        cache.insert(guild_id.get(), disabled);
+
+        Ok(entry)
+    }
+
+    pub async fn invalidate_disabled_commands(&self, guild_id: GuildId) {
+        let mut cache = self.disabled_command_cache.write().await;
+        cache.remove(&guild_id.get());
+    }
+
+    /// The cooldown entry covering a command and its seconds, if any;
+    /// checked on every command
+    pub async fn command_cooldown(
+        &self,
+        guild_id: GuildId,
+        qualified_name: &str,
+    ) -> Result<Option<(String, u64)>, Error> {
+        let cache = self.command_cooldown_cache.read().await;
+        if let Some(cooldowns) = cache.get(&guild_id.get()) {
+            return Ok(cooldown_for(qualified_name, cooldowns)
+                .map(|(command, seconds)| (command.to_string(), seconds)));
+        }
+        drop(cache);
+
+        let cooldowns: CommandCooldowns = GuildCommandCooldown::list(&self.db_pool, guild_id)
+            .await?
+            .into_iter()
+            .collect();
+        let entry = cooldown_for(qualified_name, &cooldowns)
+            .map(|(command, seconds)| (command.to_string(), seconds));
+
+        let mut cache = self.command_cooldown_cache.write().await;
+        cache.insert(guild_id.get(), cooldowns);
+
+        Ok(entry)
+    }
+
+    pub async fn invalidate_command_cooldowns(&self, guild_id: GuildId) {
+        let mut cache = self.command_cooldown_cache.write().await;
+        cache.remove(&guild_id.get());
+    }
+
+    /// The guild's embed colors; a failed lookup falls back to the default
+    /// palette rather than failing the reply
+    pub async fn embed_theme(&self, guild_id: GuildId) -> EmbedTheme {
+        if let Some(theme) = self.embed_themes.read().await.get(&guild_id.get()) {
+            return *theme;
+        }
+
+        match GuildEmbedTheme::get(&self.db_pool, guild_id).await {
+            Ok(theme) => {
+                self.embed_themes.write().await.insert(guild_id.get(), theme);
+                theme
+            }
+            Err(e) => {
+                tracing::warn!(
+                    guild_id = %guild_id,
+                    error = ?e,
+                    "Failed to load embed theme, using defaults"
+                );
+                EmbedTheme::default()
+            }
+        }
+    }
+
+    pub async fn invalidate_embed_theme(&self, guild_id: GuildId) {
+        self.embed_themes.write().await.remove(&guild_id.get());
+    }
+
+    /// The guild's chosen reply language, if any; a failed lookup reads as
+    /// unset so replies fall back to en-US rather than failing
+    pub async fn guild_locale(&self, guild_id: GuildId) -> Option<Locale> {
+        if let Some(locale) = self.guild_locales.read().await.get(&guild_id.get()) {
+            return *locale;
+        }
+
+        match GuildLocale::get(&self.db_pool, guild_id).await {
+            Ok(locale) => {
+                self.guild_locales.write().await.insert(guild_id.get(), locale);
+                locale
+            }
+            Err(e) => {
+                tracing::warn!(
+                    guild_id = %guild_id,
+                    error = ?e,
+                    "Failed to load guild locale, using en-US"
+                );
+                None
+            }
+        }
+    }
+
+    pub async fn set_guild_locale(
+        &self,
+        guild_id: GuildId,
+        locale: Locale,
+        set_by: UserId,
+    ) -> Result<(), Error> {
+        GuildLocale::set(&self.db_pool, guild_id, locale, set_by).await?;
+        self.guild_locales
+            .write()
+            .await
+            .insert(guild_id.get(), Some(locale));
+
+        Ok(())
+    }
+
+    /// The guild's cached role name blacklist, created on first use
+    pub async fn content_filter(&self, guild_id: GuildId) -> Arc<ContentFilter> {
+        let filters = self.content_filters.read().await;
+        if let Some(filter) = filters.get(&guild_id.get()) {
+            return filter.clone();
+        }
+        drop(filters);
+
+        let mut filters = self.content_filters.write().await;
+        filters
+            .entry(guild_id.get())
+            .or_insert_with(|| Arc::new(ContentFilter::new(guild_id, self.db_pool.clone())))
+            .clone()
+    }
+
+    /// Check a role name against the cached global and guild blacklists,
+    /// recording the attempt whenever a word matches
+    pub async fn check_role_name(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        name: &str,
+        command: &str,
+    ) -> Result<NameCheck, RepoError> {
+        let filters = self.name_filters(guild_id).await?;
+        self.repos
+            .guild_settings
+            .check_role_name(guild_id, user_id, name, Some(command), &filters)
+            .await
+    }
+
+    /// The cached bot-wide and guild blacklists a role name is checked against
+    pub async fn name_filters(&self, guild_id: GuildId) -> Result<NameFilters, sqlx::Error> {
+        Ok(NameFilters {
+            global: self.global_filter.entries().await?,
+            guild: self.content_filter(guild_id).await.entries().await?,
+        })
+    }
+
+    /// Drop cached settings for a guild the bot has left
+    pub async fn forget_guild(&self, guild_id: GuildId) {
+        self.prefix_cache.forget(guild_id.get()).await;
+        self.invalidate_command_channels(guild_id).await;
+        self.invalidate_disabled_commands(guild_id).await;
+        self.content_filters.write().await.remove(&guild_id.get());
+        self.guild_locales.write().await.remove(&guild_id.get());
+        self.invite_tracker.forget(guild_id).await;
+        self.boost_states.forget_guild(guild_id).await;
     }
 
     /// Create a moderation case (F1 store).
@@ -101,24 +363,56 @@ impl Data {
 #[derive(Debug)]
 pub enum Error {
     Serenity(serenity::Error),
+    /// A utility error with no direct counterpart here, kept whole so its
+    /// code survives to the error handler
+    Bot(BotError),
     Config(String),
     Command(String),
     Database(sqlx::Error),
+    /// A repository failure, which doesn't say what store it came from
+    Repo(RepoError),
+    Check(CheckFailure),
 }
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Error::Serenity(e) => write!(f, "Serenity error: {}", e),
+            Error::Bot(e) => write!(f, "{}", e),
             Error::Config(e) => write!(f, "Configuration error: {}", e),
             Error::Command(e) => write!(f, "Command error: {}", e),
             Error::Database(e) => write!(f, "Database error: {}", e),
+            Error::Repo(e) => write!(f, "Database error: {}", e),
+            Error::Check(reason) => write!(f, "Permission check failed: {}", reason),
         }
     }
 }
 
 impl std::error::Error for Error {}
 
+impl Error {
+    /// Stable code shown in the footer of error replies
+    pub fn code(&self) -> &'static ErrorCode {
+        match self {
+            Error::Bot(e) => e.code(),
+            Error::Serenity(_) => error_code("DSC-001"),
+            Error::Config(_) => error_code("CFG-001"),
+            Error::Command(_) => error_code("CMD-001"),
+            Error::Database(sqlx::Error::RowNotFound) => error_code("DB-003"),
+            Error::Database(sqlx::Error::Database(e))
+                if e.kind() != sqlx::error::ErrorKind::Other =>
+            {
+                error_code("DB-002")
+            }
+            Error::Database(_) => error_code("DB-001"),
+            Error::Repo(RepoError::NotFound) => error_code("DB-003"),
+            Error::Repo(RepoError::Conflict(_)) => error_code("DB-002"),
+            Error::Repo(RepoError::Unavailable(_)) => error_code("DB-001"),
+            Error::Check(_) => error_code("PRM-001"),
+        }
+    }
+}
+
 impl From<serenity::Error> for Error {
     fn from(error: serenity::Error) -> Self {
         Self::Serenity(error)
@@ -131,15 +425,19 @@ impl From<sqlx::Error> for Error {
     }
 }
 
+impl From<RepoError> for Error {
+    fn from(error: RepoError) -> Self {
+        Self::Repo(error)
+    }
+}
+
 impl From<BotError> for Error {
     fn from(error: BotError) -> Self {
         match error {
             BotError::Config(msg) => Error::Config(msg),
             BotError::Discord(e) => Error::Serenity(e),
-            BotError::Io(e) => Error::Command(format!("IO error: {}", e)),
             BotError::Command(msg) => Error::Command(msg),
-            BotError::InvalidColor(color) => Error::Command(format!("Invalid color: {}", color)),
-            BotError::Other(msg) => Error::Command(msg),
+            other => Error::Bot(other),
         }
     }
 }
@@ -159,3 +457,126 @@ impl From<String> for Error {
 /// Type aliases for easier usage throughout the codebase
 pub type Context<'a> = poise::Context<'a, Data, Error>;
 pub type Framework = poise::Framework<Data, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::database::DEFAULT_MAX_CONNECTIONS;
+    use crate::data::init_database;
+    use crate::data::models::RoleNameBlacklist;
+
+    fn settings() -> Settings {
+        Settings {
+            discord_token: String::new(),
+            debug_mode: false,
+            command_prefix: "!".to_string(),
+            development_guild_id: None,
+            auto_sync_commands: false,
+            slash_commands_global: false,
+            always_use_embeds: false,
+            slow_query_threshold_ms: 100,
+            table_growth_multiplier: 2.0,
+            orphan_cleanup_interval_hours: 24,
+            database_max_connections: 5,
+            owners: Vec::new(),
+            private_reply_fallback: Default::default(),
+            health_port: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prefix_cache_follows_set_and_remove() {
+        let path =
+            std::env::temp_dir().join(format!("data_prefix_test_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let pool = init_database(&path.to_string_lossy(), DEFAULT_MAX_CONNECTIONS)
+            .await
+            .unwrap();
+        let data = Data::new(settings(), pool);
+        const GUILD: u64 = 42;
+
+        // First lookup misses, the second is answered from the cache
+        assert_eq!(data.get_guild_prefix(GUILD).await.unwrap(), None);
+        assert_eq!(data.get_guild_prefix(GUILD).await.unwrap(), None);
+
+        data.set_guild_prefix(GUILD, "?").await.unwrap();
+        assert_eq!(
+            data.get_guild_prefix(GUILD).await.unwrap(),
+            Some("?".to_string())
+        );
+
+        assert!(data.remove_guild_prefix(GUILD).await.unwrap());
+        assert_eq!(data.get_guild_prefix(GUILD).await.unwrap(), None);
+
+        let stats = data.prefix_cache.stats().await;
+        assert_eq!((stats.hits, stats.misses), (3, 1));
+        assert_eq!((stats.custom, stats.default), (0, 1));
+
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(format!("{}-wal", path.display()));
+        let _ = std::fs::remove_file(format!("{}-shm", path.display()));
+    }
+
+    #[tokio::test]
+    async fn test_global_blacklist_blocks_names_in_every_guild() {
+        let path =
+            std::env::temp_dir().join(format!("data_global_blacklist_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let pool = init_database(&path.to_string_lossy(), DEFAULT_MAX_CONNECTIONS)
+            .await
+            .unwrap();
+        let data = Data::new(settings(), pool.clone());
+        let guild = GuildId::new(42);
+        let member = UserId::new(7);
+
+        crate::data::models::GlobalRoleNameBlacklist::add_word(
+            &pool,
+            "Slur",
+            crate::utils::name_severity::MatchType::Substring,
+            UserId::new(1),
+        )
+        .await
+        .unwrap();
+
+        // The guild has no words of its own
+        assert!(data
+            .content_filter(guild)
+            .await
+            .entries()
+            .await
+            .unwrap()
+            .is_empty());
+        let check = data
+            .check_role_name(guild, member, "Big SLUR Energy", "boosterrole color")
+            .await
+            .unwrap();
+        assert_eq!(
+            check,
+            NameCheck::Reject {
+                word: "slur".to_string(),
+                alert: false
+            }
+        );
+
+        // Guild admins can't lift it from their own list
+        assert!(!RoleNameBlacklist::remove_word(&pool, guild, "slur")
+            .await
+            .unwrap());
+        assert!(matches!(
+            data.check_role_name(guild, member, "slur", "boosterrole rename")
+                .await
+                .unwrap(),
+            NameCheck::Reject { .. }
+        ));
+        assert_eq!(
+            data.check_role_name(guild, member, "Fine Name", "boosterrole color")
+                .await
+                .unwrap(),
+            NameCheck::Clean
+        );
+
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(format!("{}-wal", path.display()));
+        let _ = std::fs::remove_file(format!("{}-shm", path.display()));
+    }
+}
@@ -1,69 +1,101 @@
-use crate::bot::{Data, Error, Framework};
-use crate::commands::{boosterrole, cache_status, help, info, ping, prefix, settings, test_responses};
+use crate::bot::{Data, Error, Framework, HealthState, Shutdown};
+use crate::commands;
 use crate::config::Settings;
 use crate::data::init_database;
-use crate::handlers::{BoostHandler, MemberHandler};
-use crate::utils::{EmbedBuilder, ResponseHelper};
-use serenity::all::{Context, FullEvent, GuildId};
+use crate::handlers::support_ticket::offer_staff_help;
+use crate::handlers::{
+    AnnouncementHandler, BoostHandler, CleanupHandler, ColorRotationHandler, MaintenanceHandler,
+    MemberHandler, OrphanCleanupHandler, ScheduledChangeHandler, ShareExpiryHandler,
+    ShareRevalidationHandler, SpotlightHandler,
+};
+use crate::utils::failure_streak::FailedAttempt;
+use crate::utils::query_metrics::query_metrics;
+use crate::utils::command_channels::channel_check;
+use crate::utils::command_cooldowns::cooldown_check;
+use crate::utils::disabled_commands::disabled_check;
+use crate::utils::guild_availability::AvailabilityChange;
+use crate::utils::i18n::{locale_for, translate};
+use crate::utils::invite_tracker::TrackedInvite;
+use crate::utils::prefix_rules::ADDITIONAL_PREFIXES;
+use crate::utils::rename_cooldown::format_remaining;
+use crate::utils::usage_example::command_examples;
+use crate::utils::{
+    record_check_failure, BotError, CheckFailure, CommandRegistry, EmbedBuilder, ResponseHelper,
+};
+use poise::CreateReply;
+use serenity::all::{ChannelId, Command, Context, CreateEmbedFooter, FullEvent, GuildId};
 use std::sync::Arc;
 
+/// How long a prefix command's wrong-channel notice stays up
+const WRONG_CHANNEL_NOTICE_LIFETIME: std::time::Duration = std::time::Duration::from_secs(15);
+
 /// Create and configure the Poise framework
-pub async fn create_framework(settings: Settings) -> Framework {
-    let mut commands = vec![
-        ping::ping(),
-        help::help(),
-        info::info(),
-        prefix::prefix(),
-        cache_status::cache_status(),
-        boosterrole::boosterrole(),
-        settings::settings(),
-    ];
-    
-    #[cfg(debug_assertions)]
-    commands.push(test_responses::test_responses());
-    
+pub async fn create_framework(
+    settings: Settings,
+    shutdown: Shutdown,
+    health: HealthState,
+) -> Framework {
+    let commands = commands::all();
+    for violation in commands::meta::convention_violations(&commands) {
+        tracing::warn!(violation = %violation, "Command metadata breaks conventions");
+    }
+
     let options = poise::FrameworkOptions {
         commands,
-        // Add performance tracking hooks here
+        // Drops commands a guild has disabled, keeps command groups to the
+        // channels the guild allows them in, then applies the guild's
+        // cooldowns so refused commands never start one
+        command_check: Some(|ctx| {
+            Box::pin(async move {
+                Ok(disabled_check(ctx).await?
+                    && channel_check(ctx).await?
+                    && cooldown_check(ctx).await?)
+            })
+        }),
+        // Every execution is timed for `/metrics` and counted for
+        // `/settings usage`; failures are recorded in on_error
         pre_command: |ctx| {
             Box::pin(async move {
-                // Store start time - we'll use a simple approach
-                // In production, you'd use a concurrent HashMap with command ID
-                let _start = std::time::Instant::now();
-                
-                // For now, we'll track in the post_command
                 tracing::debug!("Command '{}' starting", ctx.command().name);
+                ctx.data().performance.start_timer(ctx.id()).await;
+                if let Some(guild_id) = ctx.guild_id() {
+                    ctx.data().command_usage.record(
+                        guild_id,
+                        &ctx.command().qualified_name,
+                        chrono::Utc::now().date_naive(),
+                    );
+                }
             })
         },
         post_command: |ctx| {
             Box::pin(async move {
-                // For simplicity, we'll measure the entire command duration here
-                // In a real implementation, you'd retrieve the start time from pre_command
-                
-                // Log that command completed
+                // A success ends any failure streak for this command
+                if let Some(guild_id) = ctx.guild_id() {
+                    ctx.data().failure_tracker.clear(
+                        guild_id,
+                        ctx.author().id,
+                        &ctx.command().qualified_name,
+                    );
+                }
+
+                let elapsed = ctx
+                    .data()
+                    .performance
+                    .end_timer(
+                        ctx.id(),
+                        &ctx.command().qualified_name,
+                        ctx.author().id.get(),
+                        ctx.guild_id().map(|id| id.get()),
+                        None,
+                    )
+                    .await;
+
                 tracing::info!(
-                    "Command '{}' completed for user {}",
-                    ctx.command().name,
-                    ctx.author().id
+                    "Command '{}' completed for user {} in {:.1}ms",
+                    ctx.command().qualified_name,
+                    ctx.author().id,
+                    elapsed.unwrap_or_default()
                 );
-                
-                // Simple metric logging (you could enhance this)
-                let metric = serde_json::json!({
-                    "command": ctx.command().name,
-                    "user_id": ctx.author().id.get(),
-                    "guild_id": ctx.guild_id().map(|id| id.get()),
-                    "timestamp": chrono::Utc::now().to_rfc3339(),
-                });
-                
-                // Append to metrics file
-                if let Ok(mut file) = std::fs::OpenOptions::new()
-                    .create(true)
-                    .append(true)
-                    .open("test_results/command_executions.jsonl")
-                {
-                    use std::io::Write;
-                    let _ = writeln!(file, "{}", metric);
-                }
             })
         },
         prefix_options: poise::PrefixFrameworkOptions {
@@ -89,10 +121,10 @@ pub async fn create_framework(settings: Settings) -> Framework {
             edit_tracker: Some(std::sync::Arc::new(poise::EditTracker::for_timespan(
                 std::time::Duration::from_secs(3600),
             ))),
-            additional_prefixes: vec![
-                poise::Prefix::Literal("death"),
-                poise::Prefix::Literal("d!"),
-            ],
+            additional_prefixes: ADDITIONAL_PREFIXES
+                .iter()
+                .map(|prefix| poise::Prefix::Literal(prefix))
+                .collect(),
             ..Default::default()
         },
         // The global error handler for all commands
@@ -109,25 +141,98 @@ pub async fn create_framework(settings: Settings) -> Framework {
                             error
                         );
 
+                        ctx.data()
+                            .performance
+                            .end_timer(
+                                ctx.id(),
+                                &ctx.command().qualified_name,
+                                ctx.author().id.get(),
+                                ctx.guild_id().map(|id| id.get()),
+                                Some(error.to_string()),
+                            )
+                            .await;
+
+                        // The reference in the footer ties a member's screenshot to this log line
+                        let code = error.code().code;
+                        let reference = uuid::Uuid::new_v4();
+                        tracing::error!(
+                            code,
+                            correlation_id = %reference,
+                            command = %ctx.command().qualified_name,
+                            error = %error,
+                            "Command failed"
+                        );
+
+                        let locale = locale_for(ctx).await;
                         let (error_title, error_description) = match error {
-                            Error::Serenity(e) => ("Discord API Error", format!("{}", e)),
-                            Error::Command(e) => ("Command Error", format!("{}", e)),
-                            Error::Config(e) => ("Configuration Error", format!("{}", e)),
-                            Error::Database(e) => ("Database Error", format!("{}", e)),
+                            Error::Serenity(e) => ("error.discord", format!("{}", e)),
+                            Error::Bot(BotError::DiscordUnavailable(_)) => (
+                                "error.discord_unavailable.title",
+                                translate(locale, "error.discord_unavailable.body", &[]),
+                            ),
+                            Error::Bot(BotError::Io(e)) => ("error.command", format!("IO error: {}", e)),
+                            Error::Bot(
+                                BotError::InvalidColor(reason) | BotError::InvalidColorValue(reason),
+                            ) => ("error.command", format!("Invalid color: {}", reason)),
+                            Error::Bot(
+                                BotError::Config(msg) | BotError::Command(msg) | BotError::Other(msg),
+                            ) => ("error.command", msg),
+                            Error::Bot(BotError::Discord(e)) => ("error.discord", format!("{}", e)),
+                            Error::Command(e) => ("error.command", format!("{}", e)),
+                            Error::Config(e) => ("error.config", format!("{}", e)),
+                            Error::Database(e) => ("error.database", format!("{}", e)),
+                            Error::Repo(e) => ("error.database", format!("{}", e)),
+                            Error::Check(reason) => {
+                                record_check_failure(ctx, reason.clone());
+                                ("error.not_allowed", format!("{}", reason))
+                            }
                         };
 
-                        // Send error as embed - maintain embed-only policy
-                        let error_embed = EmbedBuilder::error(error_title, &error_description);
-                        if let Err(e) = ResponseHelper::send_embed(ctx, error_embed).await {
+                        let error_embed = EmbedBuilder::error(
+                            translate(locale, error_title, &[]),
+                            &error_description,
+                        )
+                        .footer(CreateEmbedFooter::new(translate(
+                            locale,
+                            "error.footer",
+                            &[("code", &code), ("reference", &reference)],
+                        )));
+
+                        // Repeated failures of the same command offer a staff help thread
+                        let escalation = ctx.guild_id().and_then(|guild_id| {
+                            ctx.data().failure_tracker.record_failure(
+                                guild_id,
+                                ctx.author().id,
+                                FailedAttempt {
+                                    command: ctx.command().qualified_name.clone(),
+                                    error: error_description.clone(),
+                                    correlation_id: ctx.id(),
+                                    at: std::time::Instant::now(),
+                                },
+                            )
+                        });
+                        if let Some(attempts) = escalation {
+                            match offer_staff_help(ctx, error_embed.clone(), attempts).await {
+                                Ok(true) => return,
+                                Ok(false) => {}
+                                Err(e) => println!("Failed to offer staff help: {:?}", e),
+                            }
+                        }
+
+                        // Errors are shown only to the member; send_embed_with degrades to
+                        // text only when Embed Links is missing
+                        if let Err(e) = ResponseHelper::send_embed_with(ctx, error_embed, true).await {
                             println!("Failed to send error embed: {:?}", e);
                             // Try a simpler embed format if the first fails
                             let simple_embed = EmbedBuilder::error(
-                                "Error",
-                                "An error occurred processing your command.",
+                                translate(locale, "error.generic.title", &[]),
+                                translate(locale, "error.generic.body", &[]),
                             );
-                            if let Err(e) = ResponseHelper::send_embed(ctx, simple_embed).await {
+                            if let Err(e) =
+                                ResponseHelper::send_embed_with(ctx, simple_embed, true).await
+                            {
                                 println!("Failed to send fallback error embed: {:?}", e);
-                                // Log but don't fall back to text - maintain embed-only policy
+                                // Log only - missing Embed Links was already handled by send_embed
                             }
                         }
                     }
@@ -138,17 +243,39 @@ pub async fn create_framework(settings: Settings) -> Framework {
                             error
                         );
 
+                        // Show a correct invocation of the command that just failed
+                        let mut parent: Vec<&str> =
+                            ctx.command().qualified_name.split(' ').collect();
+                        parent.pop();
+                        let example = command_examples(ctx.command(), &parent, ctx.prefix())
+                            .into_iter()
+                            .next()
+                            .map(|example| match (ctx, example.prefix) {
+                                (poise::Context::Prefix(_), Some(prefix)) => prefix,
+                                _ => example.slash,
+                            });
+                        let locale = locale_for(ctx).await;
+                        let example = example
+                            .map(|line| {
+                                translate(locale, "error.arguments.example", &[("example", &line)])
+                            })
+                            .unwrap_or_default();
+
                         // Handle argument parsing errors with embeds
                         let error_embed = EmbedBuilder::error(
-                            "Invalid Arguments",
-                            &format!(
-                                "{}.\n\nUse `/help {}` for usage information.",
-                                error,
-                                ctx.command().name
+                            translate(locale, "error.arguments.title", &[]),
+                            translate(
+                                locale,
+                                "error.arguments.body",
+                                &[
+                                    ("error", &error),
+                                    ("example", &example),
+                                    ("command", &ctx.command().name),
+                                ],
                             ),
                         );
 
-                        if let Err(e) = ResponseHelper::send_embed(ctx, error_embed).await {
+                        if let Err(e) = ResponseHelper::send_embed_with(ctx, error_embed, true).await {
                             println!("Failed to send argument error embed: {:?}", e);
                         }
                     }
@@ -159,15 +286,113 @@ pub async fn create_framework(settings: Settings) -> Framework {
                             error
                         );
 
+                        if let Some(Error::Check(CheckFailure::WrongChannel(channel))) = error {
+                            record_check_failure(ctx, CheckFailure::WrongChannel(channel));
+                            send_wrong_channel_notice(ctx, channel).await;
+                            return;
+                        }
+
+                        let locale = locale_for(ctx).await;
+
+                        // A cooldown isn't a permission problem, so it isn't recorded
+                        if let Some(Error::Check(CheckFailure::Cooldown(command, seconds))) = &error {
+                            let remaining =
+                                format_remaining(chrono::Duration::seconds(*seconds as i64));
+                            let error_embed = EmbedBuilder::error(
+                                translate(locale, "error.slow_down", &[]),
+                                translate(
+                                    locale,
+                                    "error.cooldown.command",
+                                    &[("command", command), ("remaining", &remaining)],
+                                ),
+                            );
+
+                            if let Err(e) = ResponseHelper::send_embed_with(ctx, error_embed, true).await {
+                                println!("Failed to send cooldown embed: {:?}", e);
+                            }
+                            return;
+                        }
+
+                        let description = match &error {
+                            Some(Error::Check(CheckFailure::NotStaff)) => {
+                                translate(locale, "error.not_staff", &[])
+                            }
+                            Some(Error::Check(CheckFailure::Disabled(command))) => {
+                                translate(locale, "error.disabled", &[("command", command)])
+                            }
+                            _ => translate(locale, "error.not_permitted", &[]),
+                        };
+
+                        if let Some(Error::Check(reason)) = error {
+                            record_check_failure(ctx, reason);
+                        }
+
+                        let error_embed = EmbedBuilder::error(
+                            translate(locale, "error.not_allowed", &[]),
+                            &description,
+                        );
+
+                        if let Err(e) = ResponseHelper::send_embed_with(ctx, error_embed, true).await {
+                            println!("Failed to send permission error embed: {:?}", e);
+                        }
+                    }
+                    poise::FrameworkError::NotAnOwner { ctx, .. } => {
+                        record_check_failure(ctx, CheckFailure::NotOwner);
+
+                        let locale = locale_for(ctx).await;
+                        let error_embed = EmbedBuilder::error(
+                            translate(locale, "error.not_allowed", &[]),
+                            translate(locale, "error.not_owner", &[]),
+                        );
+
+                        if let Err(e) = ResponseHelper::send_embed_with(ctx, error_embed, true).await {
+                            println!("Failed to send permission error embed: {:?}", e);
+                        }
+                    }
+                    poise::FrameworkError::MissingUserPermissions {
+                        missing_permissions,
+                        ctx,
+                        ..
+                    } => {
+                        println!(
+                            "Missing user permissions for `{}`: {:?}",
+                            ctx.command().qualified_name,
+                            missing_permissions
+                        );
+
+                        if let Some(missing) = missing_permissions {
+                            record_check_failure(ctx, CheckFailure::from_missing(missing));
+                        }
+
+                        let locale = locale_for(ctx).await;
                         let error_embed = EmbedBuilder::error(
-                            "Command Not Allowed",
-                            "You don't have permission to use this command or it can't be used here."
+                            translate(locale, "error.not_allowed", &[]),
+                            translate(locale, "error.missing_permissions", &[]),
                         );
 
-                        if let Err(e) = ResponseHelper::send_embed(ctx, error_embed).await {
+                        if let Err(e) = ResponseHelper::send_embed_with(ctx, error_embed, true).await {
                             println!("Failed to send permission error embed: {:?}", e);
                         }
                     }
+                    poise::FrameworkError::CooldownHit {
+                        remaining_cooldown,
+                        ctx,
+                        ..
+                    } => {
+                        let locale = locale_for(ctx).await;
+                        let error_embed = EmbedBuilder::error(
+                            translate(locale, "error.slow_down", &[]),
+                            translate(
+                                locale,
+                                "error.cooldown.any",
+                                &[("seconds", &remaining_cooldown.as_secs().max(1))],
+                            ),
+                        );
+
+                        if let Err(e) = ResponseHelper::send_embed_with(ctx, error_embed, true).await {
+                            println!("Failed to send cooldown embed: {:?}", e);
+                        }
+                    }
                     error => {
                         // For any other framework errors, try to send a generic embed
                         println!("Other framework error: {:?}", error);
@@ -181,6 +406,8 @@ pub async fn create_framework(settings: Settings) -> Framework {
         event_handler: |ctx, event, framework, data| {
             Box::pin(event_handler(ctx, event, framework, data))
         },
+        // Joined with the application's owner when the framework starts
+        owners: settings.owners.iter().map(|&id| serenity::all::UserId::new(id)).collect(),
         ..Default::default()
     };
 
@@ -189,21 +416,36 @@ pub async fn create_framework(settings: Settings) -> Framework {
             Box::pin(async move {
                 println!("Logged in as {}", _ready.user.name);
 
+                query_metrics().set_slow_threshold_ms(settings.slow_query_threshold_ms);
+
                 // Register slash commands
                 let guild_id = settings.development_guild_id.map(GuildId::new);
 
                 let commands = &framework.options().commands;
 
-                if settings.auto_sync_commands {
+                // Register manually rather than via poise::builtins so we keep the
+                // command IDs Discord hands back for clickable </command:id> mentions
+                let create_commands = poise::builtins::create_application_commands(commands);
+                let registered = if settings.auto_sync_commands {
                     println!("🔄 Syncing {} slash commands to guild...", commands.len());
-                    poise::builtins::register_in_guild(&ctx.http, commands, guild_id.unwrap())
-                        .await?;
+                    guild_id
+                        .unwrap()
+                        .set_commands(&ctx.http, create_commands)
+                        .await?
                 } else {
                     println!(
                         "🚀 Registering {} slash commands globally...",
                         commands.len()
                     );
-                    poise::builtins::register_globally(&ctx.http, commands).await?;
+                    Command::set_global_commands(&ctx.http, create_commands).await?
+                };
+
+                let mut command_registry = CommandRegistry::new();
+                command_registry.capture(&registered);
+                if command_registry.is_empty() {
+                    println!("⚠️ No command IDs captured; suggestions will use plain text");
+                } else {
+                    println!("🔗 Captured {} command IDs", command_registry.len());
                 }
 
                 println!("✅ Commands registered successfully!");
@@ -225,10 +467,63 @@ pub async fn create_framework(settings: Settings) -> Framework {
                 }
 
                 println!("🗄️ Initializing database...");
-                let db_pool = init_database("data/bot.db").await?;
+                let db_pool =
+                    init_database("data/bot.db", settings.database_max_connections).await?;
                 println!("✅ Database initialized successfully!");
 
-                Ok(Data::new(settings, db_pool))
+                let orphan_cleanup_every = std::time::Duration::from_secs(
+                    settings.orphan_cleanup_interval_hours * 60 * 60,
+                );
+                let growth_multiplier = settings.table_growth_multiplier;
+                let data = Data {
+                    health,
+                    ..Data::new(settings, db_pool.clone())
+                };
+                shutdown.attach(&data);
+                data.health.attach(&data);
+                let stop = &data.shutdown;
+
+                SpotlightHandler::spawn_scheduler(ctx.http.clone(), db_pool.clone(), stop.clone());
+                MemberHandler::spawn_quiet_summary_scheduler(
+                    ctx.http.clone(),
+                    db_pool.clone(),
+                    stop.clone(),
+                );
+                ShareRevalidationHandler::spawn_scheduler(
+                    ctx.http.clone(),
+                    db_pool.clone(),
+                    stop.clone(),
+                );
+                ShareExpiryHandler::spawn_scheduler(
+                    ctx.http.clone(),
+                    db_pool.clone(),
+                    stop.clone(),
+                );
+                ScheduledChangeHandler::spawn_scheduler(
+                    ctx.http.clone(),
+                    db_pool.clone(),
+                    stop.clone(),
+                );
+                ColorRotationHandler::spawn_scheduler(ctx.clone(), db_pool.clone(), stop.clone());
+                AnnouncementHandler::spawn_resume(ctx.http.clone(), db_pool.clone());
+                CleanupHandler::spawn_resume(ctx.clone(), db_pool.clone());
+                OrphanCleanupHandler::spawn_scheduler(
+                    ctx.clone(),
+                    db_pool.clone(),
+                    orphan_cleanup_every,
+                    stop.clone(),
+                );
+                MaintenanceHandler::spawn_scheduler(
+                    ctx.http.clone(),
+                    db_pool.clone(),
+                    growth_multiplier,
+                    stop.clone(),
+                );
+                data.experiments.spawn_flusher(db_pool.clone(), stop.clone());
+                data.command_usage.spawn_flusher(db_pool, stop.clone());
+                *data.command_registry.write().await = command_registry;
+
+                Ok(data)
             })
         })
         .options(options)
@@ -249,17 +544,96 @@ async fn event_handler(
     match event {
         FullEvent::Ready { data_about_bot, .. } => {
             println!("🤖 {} is connected and ready!", data_about_bot.user.name);
+            data.health.shard_ready(
+                ctx.shard_id,
+                data_about_bot.shard.map_or(1, |shard| shard.total),
+            );
 
             // Handle ready event for boost handler
-            boost_handler.on_ready(ctx, data_about_bot).await;
+            boost_handler
+                .on_ready(ctx, data_about_bot, &data.guild_availability)
+                .await;
+        }
+        FullEvent::ShardStageUpdate { event } => {
+            data.health.shard_stage(event.shard_id, event.new);
+        }
+        FullEvent::GuildCreate { guild, is_new } => {
+            data.boost_states
+                .seed(guild.id, guild.members.values())
+                .await;
+            match data
+                .guild_availability
+                .guild_create(guild.id, is_new.unwrap_or(false))
+            {
+                change @ (AvailabilityChange::RunStartup | AvailabilityChange::Recovered) => {
+                    tracing::info!(
+                        guild_id = %guild.id,
+                        change = ?change,
+                        "Guild available, running startup work"
+                    );
+                    let ctx = ctx.clone();
+                    let availability = data.guild_availability.clone();
+                    let invites = data.invite_tracker.clone();
+                    let guild_id = guild.id;
+                    tokio::spawn(async move {
+                        invites.refresh(&ctx.http, guild_id).await;
+                        boost_handler
+                            .run_guild_startup(&ctx, guild_id, &availability)
+                            .await;
+                    });
+                }
+                AvailabilityChange::Joined => {
+                    tracing::info!(guild_id = %guild.id, "Joined guild");
+                    let http = ctx.http.clone();
+                    let invites = data.invite_tracker.clone();
+                    let guild_id = guild.id;
+                    tokio::spawn(async move {
+                        invites.refresh(&http, guild_id).await;
+                    });
+                }
+                _ => {}
+            }
+        }
+        FullEvent::GuildDelete { incomplete, .. } => {
+            match data
+                .guild_availability
+                .guild_delete(incomplete.id, incomplete.unavailable)
+            {
+                AvailabilityChange::Outage => {
+                    // Outages keep every setting and record; the guild comes back as it was
+                    tracing::warn!(guild_id = %incomplete.id, "Guild unavailable (outage)");
+                }
+                AvailabilityChange::Removed => {
+                    tracing::info!(
+                        guild_id = %incomplete.id,
+                        "Removed from guild, dropping cached settings and stored data"
+                    );
+                    data.forget_guild(incomplete.id).await;
+                    boost_handler.on_guild_removed(incomplete.id).await;
+                }
+                _ => {}
+            }
         }
         FullEvent::GuildMemberUpdate {
-            old_if_available: _,
-            new: _,
+            old_if_available,
+            new,
             event,
         } => {
-            // Handle member updates for boost status changes - simplified approach
-            boost_handler.handle_boost_change(ctx, event).await;
+            // Handle member updates for boost status changes
+            boost_handler
+                .handle_boost_change(
+                    ctx,
+                    &data.boost_states,
+                    old_if_available.as_ref(),
+                    new.as_ref(),
+                    event,
+                )
+                .await;
+        }
+        FullEvent::GuildMembersChunk { chunk } => {
+            data.boost_states
+                .seed(chunk.guild_id, chunk.members.values())
+                .await;
         }
         FullEvent::GuildRoleDelete {
             guild_id,
@@ -275,17 +649,69 @@ async fn event_handler(
                 )
                 .await;
         }
+        FullEvent::GuildRoleUpdate { new, .. } => {
+            // Keep locked booster roles in their locked state
+            boost_handler.on_guild_role_update(ctx, new).await;
+        }
         FullEvent::GuildMemberAddition { new_member } => {
             // Handle member join events
-            member_handler.handle_member_join(ctx, new_member).await;
+            member_handler
+                .handle_member_join(ctx, new_member, &data.invite_tracker)
+                .await;
         }
         FullEvent::GuildMemberRemoval {
             guild_id, user, ..
         } => {
             // Handle member leave events
+            data.boost_states.forget_member(*guild_id, user.id).await;
+            boost_handler.on_member_leave(ctx, *guild_id, user.id).await;
             member_handler.handle_member_leave(ctx, *guild_id, user).await;
         }
+        FullEvent::InviteCreate { data: invite } => {
+            // Keep invite counts current for join attribution
+            if let Some(guild_id) = invite.guild_id {
+                data.invite_tracker
+                    .invite_created(guild_id, TrackedInvite::from(invite))
+                    .await;
+            }
+        }
+        FullEvent::InviteDelete { data: invite } => {
+            if let Some(guild_id) = invite.guild_id {
+                data.invite_tracker
+                    .invite_deleted(guild_id, &invite.code)
+                    .await;
+            }
+        }
         _ => {}
     }
     Ok(())
 }
+
+/// Point a member at the channel a command belongs in. Slash replies are
+/// ephemeral; prefix replies are removed shortly after so they don't pile up.
+async fn send_wrong_channel_notice(ctx: crate::bot::Context<'_>, channel: ChannelId) {
+    let embed = EmbedBuilder::warning(
+        "Wrong Channel",
+        format!("Use this command in <#{}>.", channel),
+    );
+    let handle = match ctx
+        .send(CreateReply::default().embed(embed).ephemeral(true))
+        .await
+    {
+        Ok(handle) => handle,
+        Err(e) => {
+            println!("Failed to send wrong channel notice: {:?}", e);
+            return;
+        }
+    };
+
+    if let poise::Context::Prefix(_) = ctx {
+        if let Ok(message) = handle.into_message().await {
+            let http = ctx.serenity_context().http.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(WRONG_CHANNEL_NOTICE_LIFETIME).await;
+                let _ = message.delete(&http).await;
+            });
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use crate::bot::Data;
+use crate::utils::performance::{PerformanceSummary, PerformanceTracker};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use serenity::all::{ConnectionStage, ShardId};
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+/// Which shards have a live gateway connection
+#[derive(Debug, Default)]
+struct ShardStatus {
+    total: u32,
+    connected: HashSet<ShardId>,
+}
+
+/// What the health endpoint reports: the gateway connection from shard
+/// events, and command timings once the framework hands over its [`Data`]
+#[derive(Debug, Clone)]
+pub struct HealthState {
+    shards: Arc<Mutex<ShardStatus>>,
+    performance: Arc<OnceLock<PerformanceTracker>>,
+    started_at: Instant,
+}
+
+impl Default for HealthState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HealthState {
+    pub fn new() -> Self {
+        Self {
+            shards: Arc::new(Mutex::new(ShardStatus::default())),
+            performance: Arc::new(OnceLock::new()),
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Remember the command timings to serve from `/metrics`
+    pub fn attach(&self, data: &Data) {
+        let _ = self.performance.set(data.performance.clone());
+    }
+
+    /// A shard received Ready; `total` is how many shards the bot runs
+    pub fn shard_ready(&self, shard_id: ShardId, total: u32) {
+        let mut shards = self.shards.lock().unwrap_or_else(|e| e.into_inner());
+        shards.total = total.max(1);
+        shards.connected.insert(shard_id);
+    }
+
+    /// A shard's connection changed; anything short of connected means it's
+    /// dropped or reconnecting
+    pub fn shard_stage(&self, shard_id: ShardId, stage: ConnectionStage) {
+        let mut shards = self.shards.lock().unwrap_or_else(|e| e.into_inner());
+        if stage == ConnectionStage::Connected {
+            shards.connected.insert(shard_id);
+        } else {
+            shards.connected.remove(&shard_id);
+        }
+    }
+
+    /// Every shard has a gateway connection
+    pub fn is_ready(&self) -> bool {
+        let shards = self.shards.lock().unwrap_or_else(|e| e.into_inner());
+        shards.total > 0 && shards.connected.len() as u32 >= shards.total
+    }
+
+    /// Serve `/healthz`, `/readyz` and `/metrics` on `port` until `stop` is
+    /// cancelled. A port that can't be bound is logged and the bot carries on.
+    pub fn spawn_server(&self, port: u16, stop: CancellationToken) {
+        let state = self.clone();
+        let addr = SocketAddr::from(([0, 0, 0, 0], port));
+
+        tokio::spawn(async move {
+            let builder = match Server::try_bind(&addr) {
+                Ok(builder) => builder,
+                Err(e) => {
+                    tracing::error!(error = %e, port, "Couldn't start the health server");
+                    return;
+                }
+            };
+
+            let make_service = make_service_fn(move |_| {
+                let state = state.clone();
+                async move {
+                    Ok::<_, Infallible>(service_fn(move |request| {
+                        let state = state.clone();
+                        async move { Ok::<_, Infallible>(state.respond(request).await) }
+                    }))
+                }
+            });
+
+            tracing::info!(port, "Health server listening");
+            let server = builder
+                .serve(make_service)
+                .with_graceful_shutdown(stop.cancelled_owned());
+            if let Err(e) = server.await {
+                tracing::error!(error = %e, "Health server stopped");
+            }
+        });
+    }
+
+    async fn respond(&self, request: Request<Body>) -> Response<Body> {
+        if request.method() != Method::GET {
+            return plain(StatusCode::METHOD_NOT_ALLOWED, "method not allowed\n");
+        }
+
+        match request.uri().path() {
+            "/healthz" => plain(StatusCode::OK, "ok\n"),
+            "/readyz" if self.is_ready() => plain(StatusCode::OK, "ready\n"),
+            "/readyz" => plain(StatusCode::SERVICE_UNAVAILABLE, "gateway not connected\n"),
+            "/metrics" => {
+                let summary = match self.performance.get() {
+                    Some(tracker) => tracker.summary().await,
+                    None => PerformanceSummary::default(),
+                };
+                let body = render_metrics(
+                    &summary,
+                    self.is_ready(),
+                    self.started_at.elapsed().as_secs(),
+                );
+                Response::builder()
+                    .header("Content-Type", "text/plain; version=0.0.4")
+                    .body(Body::from(body))
+                    .unwrap_or_default()
+            }
+            _ => plain(StatusCode::NOT_FOUND, "not found\n"),
+        }
+    }
+}
+
+fn plain(status: StatusCode, body: &'static str) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header("Content-Type", "text/plain")
+        .body(Body::from(body))
+        .unwrap_or_default()
+}
+
+/// Prometheus text exposition of the command timings kept in memory, along
+/// with gateway readiness and uptime
+pub fn render_metrics(summary: &PerformanceSummary, ready: bool, uptime_secs: u64) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "# HELP death_bot_up Whether the gateway is connected.");
+    let _ = writeln!(out, "# TYPE death_bot_up gauge");
+    let _ = writeln!(out, "death_bot_up {}", u8::from(ready));
+
+    let _ = writeln!(out, "# HELP death_bot_uptime_seconds Seconds since the process started.");
+    let _ = writeln!(out, "# TYPE death_bot_uptime_seconds gauge");
+    let _ = writeln!(out, "death_bot_uptime_seconds {}", uptime_secs);
+
+    let _ = writeln!(
+        out,
+        "# HELP death_bot_command_executions Recent command executions by outcome."
+    );
+    let _ = writeln!(out, "# TYPE death_bot_command_executions gauge");
+    for command in &summary.commands {
+        let name = escape_label(&command.name);
+        let _ = writeln!(
+            out,
+            "death_bot_command_executions{{command=\"{}\",outcome=\"success\"}} {}",
+            name, command.successful
+        );
+        let _ = writeln!(
+            out,
+            "death_bot_command_executions{{command=\"{}\",outcome=\"failure\"}} {}",
+            name,
+            command.count - command.successful
+        );
+    }
+
+    let _ = writeln!(
+        out,
+        "# HELP death_bot_command_duration_milliseconds Execution time of recent commands."
+    );
+    let _ = writeln!(out, "# TYPE death_bot_command_duration_milliseconds summary");
+    for command in &summary.commands {
+        let name = escape_label(&command.name);
+        let _ = writeln!(
+            out,
+            "death_bot_command_duration_milliseconds{{command=\"{}\",quantile=\"0.95\"}} {}",
+            name, command.p95_ms
+        );
+        let _ = writeln!(
+            out,
+            "death_bot_command_duration_milliseconds_sum{{command=\"{}\"}} {}",
+            name,
+            command.avg_ms * command.count as f64
+        );
+        let _ = writeln!(
+            out,
+            "death_bot_command_duration_milliseconds_count{{command=\"{}\"}} {}",
+            name, command.count
+        );
+    }
+
+    out
+}
+
+/// Label values escape backslashes, quotes and line breaks
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::performance::CommandStats;
+
+    #[test]
+    fn test_metrics_render_counts_and_latencies() {
+        let summary = PerformanceSummary {
+            total: 5,
+            successful: 4,
+            commands: vec![CommandStats {
+                name: "boosterrole color".to_string(),
+                count: 5,
+                successful: 4,
+                avg_ms: 20.0,
+                p95_ms: 42.5,
+            }],
+        };
+
+        let text = render_metrics(&summary, true, 90);
+        for line in [
+            "death_bot_up 1",
+            "death_bot_uptime_seconds 90",
+            "death_bot_command_executions{command=\"boosterrole color\",outcome=\"success\"} 4",
+            "death_bot_command_executions{command=\"boosterrole color\",outcome=\"failure\"} 1",
+            "death_bot_command_duration_milliseconds{command=\"boosterrole color\",quantile=\"0.95\"} 42.5",
+            "death_bot_command_duration_milliseconds_sum{command=\"boosterrole color\"} 100",
+            "death_bot_command_duration_milliseconds_count{command=\"boosterrole color\"} 5",
+        ] {
+            assert!(text.lines().any(|l| l == line), "missing {:?} in\n{}", line, text);
+        }
+
+        // Every sample belongs to a metric declared with a TYPE line
+        for sample in text.lines().filter(|l| !l.starts_with('#')) {
+            let metric = sample.split(['{', ' ']).next().unwrap();
+            let family = metric
+                .trim_end_matches("_sum")
+                .trim_end_matches("_count");
+            assert!(
+                text.contains(&format!("# TYPE {} ", family)),
+                "{} has no TYPE",
+                metric
+            );
+        }
+    }
+
+    #[test]
+    fn test_metrics_escape_labels_and_handle_no_commands() {
+        assert_eq!(escape_label("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+
+        let text = render_metrics(&PerformanceSummary::default(), false, 0);
+        assert!(text.contains("death_bot_up 0\n"));
+        assert!(!text.contains("command=\""));
+    }
+
+    #[test]
+    fn test_ready_tracks_every_shard() {
+        let health = HealthState::new();
+        assert!(!health.is_ready());
+
+        health.shard_ready(ShardId(0), 2);
+        assert!(!health.is_ready());
+        health.shard_ready(ShardId(1), 2);
+        assert!(health.is_ready());
+
+        health.shard_stage(ShardId(1), ConnectionStage::Resuming);
+        assert!(!health.is_ready());
+        health.shard_stage(ShardId(1), ConnectionStage::Connected);
+        assert!(health.is_ready());
+    }
+}
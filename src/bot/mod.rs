@@ -1,7 +1,11 @@
 pub mod data;
 pub mod framework;
+pub mod health;
 pub mod intents;
 pub mod poise_client;
+pub mod shutdown;
 
 pub use data::{Context, Data, Error, Framework};
+pub use health::HealthState;
 pub use poise_client::create_poise_client;
+pub use shutdown::Shutdown;
@@ -1,12 +1,14 @@
-use crate::bot::{framework::create_framework, intents::get_bot_intents};
+use crate::bot::{framework::create_framework, intents::get_bot_intents, HealthState, Shutdown};
 use crate::config::Settings;
 use serenity::{cache::Settings as CacheSettings, Client};
 
 /// Create a Poise-based client
 pub async fn create_poise_client(
     settings: &Settings,
+    shutdown: Shutdown,
+    health: HealthState,
 ) -> Result<Client, Box<dyn std::error::Error + Send + Sync>> {
-    let framework = create_framework(settings.clone()).await;
+    let framework = create_framework(settings.clone(), shutdown, health).await;
     let intents = get_bot_intents();
 
     // Configure cache settings
@@ -0,0 +1,95 @@
+use crate::bot::Data;
+use serenity::all::ShardManager;
+use std::sync::{Arc, OnceLock};
+use tokio_util::sync::CancellationToken;
+
+/// Coordinates a clean exit on SIGINT/SIGTERM.
+///
+/// The framework hands over its [`Data`] once setup finishes; until then only
+/// the gateway connections need closing.
+#[derive(Clone, Default)]
+pub struct Shutdown {
+    data: Arc<OnceLock<Data>>,
+    /// For tasks started before the framework, such as the health server
+    stop: CancellationToken,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember the bot data so background tasks and the pool can be closed
+    pub fn attach(&self, data: &Data) {
+        let _ = self.data.set(data.clone());
+    }
+
+    /// Cancelled alongside the bot data's token
+    pub fn token(&self) -> CancellationToken {
+        self.stop.clone()
+    }
+
+    /// Stop background loops and close the shards on the first signal,
+    /// which makes `Client::start` return
+    pub fn spawn_signal_listener(&self, shard_manager: Arc<ShardManager>) {
+        let shutdown = self.clone();
+
+        tokio::spawn(async move {
+            let signal = wait_for_signal().await;
+            println!("🛑 Received {}, shutting down...", signal);
+            tracing::info!(signal, "Shutdown requested");
+
+            shutdown.stop.cancel();
+            if let Some(data) = shutdown.data.get() {
+                data.shutdown.cancel();
+            }
+            shard_manager.shutdown_all().await;
+        });
+    }
+
+    /// Flush what's still in memory and close the database once the client
+    /// has stopped
+    pub async fn finish(&self) {
+        self.stop.cancel();
+        if let Some(data) = self.data.get() {
+            data.shutdown.cancel();
+
+            if let Err(e) = data.experiments.flush(&data.db_pool).await {
+                tracing::error!(error = ?e, "Failed to flush experiment counters on shutdown");
+            }
+            if let Err(e) = data.command_usage.flush(&data.db_pool).await {
+                tracing::error!(error = ?e, "Failed to flush command usage on shutdown");
+            }
+            data.db_pool.close().await;
+        }
+
+        println!("👋 Shut down cleanly");
+        tracing::info!("Shut down cleanly");
+    }
+}
+
+/// Resolves with the name of the first SIGINT or SIGTERM received
+async fn wait_for_signal() -> &'static str {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => tokio::select! {
+                _ = tokio::signal::ctrl_c() => "SIGINT",
+                _ = terminate.recv() => "SIGTERM",
+            },
+            Err(e) => {
+                tracing::warn!(error = %e, "Couldn't listen for SIGTERM");
+                let _ = tokio::signal::ctrl_c().await;
+                "SIGINT"
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+        "SIGINT"
+    }
+}
@@ -0,0 +1,403 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{BoosterRole, BoosterRoleShare, SettingsAuditLog};
+use crate::handlers::support_ticket::alert_blocked_name;
+use crate::utils::args::MemberArg;
+use crate::utils::audit::{before_after, booster_audit_embed, send_booster_audit};
+use crate::utils::confirm::{ask_confirmation, finish_prompt, Confirmation};
+use crate::utils::name_severity::NameCheck;
+use crate::utils::{ColorParser, EmbedBuilder, ResponseHelper, RoleManager};
+use poise::serenity_prelude as serenity;
+use serenity::all::{CreateEmbed, EditRole, GuildId, RoleId, UserId};
+use serenity::prelude::Mentionable;
+use tracing::{info, instrument, warn};
+
+/// Manage another member's booster role (Staff only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    check = "super::staff_check",
+    category = "Booster Roles",
+    subcommands("admin_rename", "admin_recolor", "admin_delete"),
+    description_localized("en-US", "Manage another member's booster role")
+)]
+pub async fn admin(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Rename a member's booster role (Staff only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "rename",
+    check = "super::staff_check",
+    category = "Booster Roles",
+    required_bot_permissions = "MANAGE_ROLES",
+    broadcast_typing
+)]
+#[instrument(
+    skip(ctx, user),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.admin.rename"
+    )
+)]
+pub async fn admin_rename(
+    ctx: Context<'_>,
+    #[description = "Member whose role to rename"] user: MemberArg,
+    #[description = "New name for the role"]
+    #[min_length = 1]
+    #[max_length = 100]
+    name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+    let target = user.user.id;
+
+    let Some(record) = target_role(ctx, guild_id, &user).await? else {
+        return Ok(());
+    };
+
+    // Staff are held to the same naming rules as members
+    let name_check = ctx
+        .data()
+        .check_role_name(guild_id, ctx.author().id, &name, "boosterrole admin rename")
+        .await?;
+    if let NameCheck::Reject { word, alert } = &name_check {
+        if *alert {
+            alert_blocked_name(
+                &ctx.serenity_context().http,
+                pool,
+                guild_id,
+                ctx.author().id,
+                &name,
+                word,
+            )
+            .await;
+        }
+        ResponseHelper::send_error(
+            ctx,
+            "🚫 Name Not Allowed",
+            "This name contains blacklisted words and cannot be used.",
+        )
+        .await?;
+        return Ok(());
+    }
+    let name = match RoleManager::validate_role_name(&name) {
+        Ok(name) => name,
+        Err(e) => {
+            ResponseHelper::send_error(ctx, "❌ Invalid Role Name", &e.to_string()).await?;
+            return Ok(());
+        }
+    };
+
+    let role_id = RoleId::new(record.role_id as u64);
+    guild_id
+        .edit_role(ctx.http(), role_id, EditRole::new().name(&name))
+        .await?;
+
+    let repos = &ctx.data().repos;
+    repos
+        .booster_roles
+        .update(
+            guild_id,
+            target,
+            &name,
+            &record.primary_color,
+            record.secondary_color.as_deref(),
+        )
+        .await?;
+    repos
+        .booster_roles
+        .record_rename(guild_id, target, &record.role_name, &name)
+        .await?;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "booster_role_admin_rename",
+        Some(&format!(
+            "Member: {}, name: {} → {}",
+            target, record.role_name, name
+        )),
+    )
+    .await?;
+    send_booster_audit(
+        ctx.http(),
+        pool,
+        guild_id,
+        booster_audit_embed(
+            "✏️ Booster Role Renamed by Staff",
+            ctx.author().id,
+            format!("<@&{}>", role_id),
+        )
+        .field("Owner", target.mention().to_string(), true)
+        .field("Name", before_after(&record.role_name, &name), false),
+    )
+    .await;
+
+    info!(target_id = %target, role_id = %role_id, "Staff renamed booster role");
+
+    ResponseHelper::send_success(
+        ctx,
+        "✅ Role Renamed",
+        &format!(
+            "{}'s booster role is now **{}** (was **{}**).",
+            target.mention(),
+            name,
+            record.role_name
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Change the color of a member's booster role (Staff only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "recolor",
+    check = "super::staff_check",
+    category = "Booster Roles",
+    required_bot_permissions = "MANAGE_ROLES",
+    broadcast_typing
+)]
+#[instrument(
+    skip(ctx, user),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.admin.recolor"
+    )
+)]
+pub async fn admin_recolor(
+    ctx: Context<'_>,
+    #[description = "Member whose role to recolor"] user: MemberArg,
+    #[description = "New color (hex code like #FF0000 or a color name)"] color: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+    let target = user.user.id;
+
+    let Some(record) = target_role(ctx, guild_id, &user).await? else {
+        return Ok(());
+    };
+
+    let primary = match ColorParser::parse(&color) {
+        Ok(primary) => primary,
+        Err(e) => {
+            ResponseHelper::send_error(ctx, "❌ Invalid Color", &e.to_string()).await?;
+            return Ok(());
+        }
+    };
+    let hex = ColorParser::to_hex_string(primary);
+
+    let role_id = RoleId::new(record.role_id as u64);
+    guild_id
+        .edit_role(ctx.http(), role_id, EditRole::new().colour(primary))
+        .await?;
+
+    ctx.data()
+        .repos
+        .booster_roles
+        .update(
+            guild_id,
+            target,
+            &record.role_name,
+            &hex,
+            record.secondary_color.as_deref(),
+        )
+        .await?;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "booster_role_admin_recolor",
+        Some(&format!(
+            "Member: {}, color: {} → {}",
+            target, record.primary_color, hex
+        )),
+    )
+    .await?;
+    send_booster_audit(
+        ctx.http(),
+        pool,
+        guild_id,
+        booster_audit_embed(
+            "🎨 Booster Role Recolored by Staff",
+            ctx.author().id,
+            format!("<@&{}>", role_id),
+        )
+        .field("Owner", target.mention().to_string(), true)
+        .field("Color", before_after(&record.primary_color, &hex), false),
+    )
+    .await;
+
+    info!(target_id = %target, role_id = %role_id, color = %hex, "Staff recolored booster role");
+
+    ResponseHelper::send_success(
+        ctx,
+        "✅ Role Recolored",
+        &format!(
+            "{}'s booster role is now `{}` (was `{}`).",
+            target.mention(),
+            hex,
+            record.primary_color
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Delete a member's booster role (Staff only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "delete",
+    check = "super::staff_check",
+    category = "Booster Roles",
+    required_bot_permissions = "MANAGE_ROLES"
+)]
+#[instrument(
+    skip(ctx, user),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.admin.delete"
+    )
+)]
+pub async fn admin_delete(
+    ctx: Context<'_>,
+    #[description = "Member whose role to delete"] user: MemberArg,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+    let target = user.user.id;
+
+    let Some(record) = target_role(ctx, guild_id, &user).await? else {
+        return Ok(());
+    };
+
+    let role_id = RoleId::new(record.role_id as u64);
+    let shares = BoosterRoleShare::get_role_shares(pool, guild_id, role_id).await?;
+
+    let mut preview = format!(
+        "This will permanently delete {}'s booster role **{}** (<@&{}>).",
+        target.mention(),
+        record.role_name,
+        role_id
+    );
+    if !shares.is_empty() {
+        preview.push_str(&format!(
+            "\n\nIt's shared with {} member(s), who will lose it too.",
+            shares.len()
+        ));
+    }
+
+    let (answer, prompt) = ask_confirmation(
+        ctx,
+        EmbedBuilder::warning("⚠️ Delete Booster Role?", &preview),
+        "Delete Role",
+    )
+    .await?;
+    if answer != Confirmation::Confirmed {
+        finish_prompt(
+            ctx,
+            prompt,
+            EmbedBuilder::info("Deletion Cancelled", "The booster role was kept."),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    for share in &shares {
+        let shared_user_id = UserId::new(share.shared_with_id as u64);
+        if let Err(e) = ctx
+            .http()
+            .remove_member_role(
+                guild_id,
+                shared_user_id,
+                role_id,
+                Some("Booster role deleted by staff"),
+            )
+            .await
+        {
+            warn!(
+                "Failed to remove role {} from user {}: {}",
+                role_id, shared_user_id, e
+            );
+        }
+    }
+
+    // The record goes even if Discord already lost the role
+    if let Err(e) = guild_id.delete_role(ctx.http(), role_id).await {
+        warn!(role_id = %role_id, error = ?e, "Failed to delete role from Discord");
+    }
+    ctx.data().repos.booster_roles.delete(guild_id, target).await?;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "booster_role_admin_delete",
+        Some(&format!(
+            "Member: {}, role: {} ({})",
+            target, record.role_name, role_id
+        )),
+    )
+    .await?;
+    send_booster_audit(
+        ctx.http(),
+        pool,
+        guild_id,
+        booster_audit_embed(
+            "🗑️ Booster Role Deleted by Staff",
+            ctx.author().id,
+            format!("**{}** (`{}`)", record.role_name, role_id),
+        )
+        .field("Owner", target.mention().to_string(), true)
+        .field("Shares Removed", shares.len().to_string(), true),
+    )
+    .await;
+
+    info!(target_id = %target, role_id = %role_id, "Staff deleted booster role");
+
+    finish_prompt(ctx, prompt, deleted_embed(target, &record.role_name)).await?;
+    Ok(())
+}
+
+/// Confirmation for staff that a member's role was deleted
+pub(crate) fn deleted_embed(target: UserId, role_name: &str) -> CreateEmbed {
+    EmbedBuilder::success(
+        "Role Deleted",
+        format!(
+            "{}'s booster role **{}** has been deleted.",
+            target.mention(),
+            role_name
+        ),
+    )
+}
+
+/// The member's booster role record, or an error reply when they have none
+async fn target_role(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    user: &MemberArg,
+) -> Result<Option<BoosterRole>, Error> {
+    let record = ctx.data().repos.booster_roles.get(guild_id, user.user.id).await?;
+    if record.is_none() {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ No Booster Role",
+            &format!("{} doesn't have a booster role.", user.mention()),
+        )
+        .await?;
+    }
+    Ok(record)
+}
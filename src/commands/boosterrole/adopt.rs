@@ -0,0 +1,389 @@
+use crate::bot::{Context, Data, Error};
+use crate::data::models::{
+    BoosterRole, GuildBoosterAward, GuildPremiumRole, SettingsAuditLog,
+};
+use crate::handlers::cleanup::{IncompleteMembers, MemberPages};
+use crate::utils::role_adoption::{
+    find_adoption_candidates, AdoptionCandidate, MemberSnapshot, RoleSnapshot, TrackedRecords,
+};
+use crate::utils::{ColorParser, EmbedBuilder, ResponseHelper};
+use poise::serenity_prelude as serenity;
+use serenity::all::{
+    ComponentInteractionCollector, ComponentInteractionDataKind, CreateActionRow,
+    CreateInteractionResponse, CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption,
+    EditRole, GuildId, Permissions, RoleId, UserId,
+};
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::instrument;
+
+/// Discord caps select menus at 25 options
+const MAX_SELECT_OPTIONS: usize = 25;
+const SELECTION_TIMEOUT_SECS: u64 = 120;
+
+/// Adopt hand-made roles as booster roles
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    category = "Booster Roles",
+    required_permissions = "MANAGE_GUILD",
+    subcommands("adopt_scan"),
+    description_localized("en-US", "Adopt manually created roles as booster roles")
+)]
+pub async fn adopt(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Scan for manually created roles that look like booster roles
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "scan",
+    category = "Booster Roles",
+    required_permissions = "MANAGE_GUILD",
+    required_bot_permissions = "MANAGE_ROLES",
+    description_localized(
+        "en-US",
+        "Find untracked roles held by a single booster and adopt them"
+    )
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.adopt.scan"
+    )
+)]
+async fn adopt_scan(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+
+    ctx.defer().await?;
+
+    let tracked = load_tracked_records(ctx.data(), guild_id).await?;
+
+    let (mut candidates, colors) = match scan_candidates(ctx, guild_id, &tracked).await? {
+        Ok(scan) => scan,
+        Err(incomplete) => {
+            ResponseHelper::send_warning(
+                ctx,
+                "⚠️ Scan Aborted",
+                &format!(
+                    "{}, so a shared role could have looked like it had a single holder. \
+                    Nothing was adopted; try again later.",
+                    incomplete.shortfall()
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    if candidates.is_empty() {
+        ResponseHelper::send_info(
+            ctx,
+            "🔍 No Candidates",
+            "No untracked roles held by a single booster were found.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let total_found = candidates.len();
+    candidates.truncate(MAX_SELECT_OPTIONS);
+
+    let listing = candidates
+        .iter()
+        .map(|c| format!("• <@&{}> → <@{}>", c.role_id, c.user_id))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut description = format!(
+        "Found **{}** role(s) that look like hand-made booster roles:\n\n{}\n\nSelect the roles to adopt below.",
+        total_found, listing
+    );
+    if total_found > MAX_SELECT_OPTIONS {
+        description.push_str(&format!(
+            "\n\n*Showing the first {} — run the scan again after adopting to see the rest.*",
+            MAX_SELECT_OPTIONS
+        ));
+    }
+
+    let options = candidates
+        .iter()
+        .map(|c| {
+            CreateSelectMenuOption::new(
+                c.role_name.chars().take(100).collect::<String>(),
+                c.role_id.to_string(),
+            )
+            .description(format!("Held by user {}", c.user_id))
+        })
+        .collect();
+
+    let custom_id = format!("adopt_select_{}", ctx.id());
+    let menu = CreateSelectMenu::new(&custom_id, CreateSelectMenuKind::String { options })
+        .placeholder("Choose roles to adopt")
+        .min_values(1)
+        .max_values(candidates.len() as u8);
+
+    let reply = ctx
+        .send(
+            poise::CreateReply::default()
+                .embed(EmbedBuilder::info("🔍 Adoption Candidates", description))
+                .components(vec![CreateActionRow::SelectMenu(menu)]),
+        )
+        .await?;
+
+    let interaction = ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
+        .channel_id(ctx.channel_id())
+        .filter(move |i| i.data.custom_id == custom_id)
+        .timeout(Duration::from_secs(SELECTION_TIMEOUT_SECS))
+        .await;
+
+    let Some(interaction) = interaction else {
+        reply
+            .edit(
+                ctx,
+                poise::CreateReply::default()
+                    .embed(EmbedBuilder::warning(
+                        "⏱️ Selection Timed Out",
+                        "No roles were adopted. Run the scan again when you're ready.",
+                    ))
+                    .components(vec![]),
+            )
+            .await?;
+        return Ok(());
+    };
+
+    interaction
+        .create_response(ctx, CreateInteractionResponse::Acknowledge)
+        .await?;
+
+    let selected: Vec<RoleId> = match &interaction.data.kind {
+        ComponentInteractionDataKind::StringSelect { values } => values
+            .iter()
+            .filter_map(|v| v.parse::<u64>().ok())
+            .map(RoleId::new)
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let mut adopted = Vec::new();
+    let mut failed = Vec::new();
+
+    for candidate in candidates.iter().filter(|c| selected.contains(&c.role_id)) {
+        let color = colors.get(&candidate.role_id).copied().unwrap_or(0);
+        match adopt_role(ctx, guild_id, candidate, color).await {
+            Ok(true) => adopted.push(candidate),
+            Ok(false) => failed.push((candidate, "already tracked".to_string())),
+            Err(e) => {
+                tracing::error!(
+                    guild_id = %guild_id,
+                    role_id = %candidate.role_id,
+                    error = ?e,
+                    "Failed to adopt booster role"
+                );
+                failed.push((candidate, e.to_string()));
+            }
+        }
+    }
+
+    let mut summary = String::new();
+    if !adopted.is_empty() {
+        summary.push_str("**Adopted:**\n");
+        for c in &adopted {
+            summary.push_str(&format!("• <@&{}> → <@{}>\n", c.role_id, c.user_id));
+        }
+    }
+    if !failed.is_empty() {
+        summary.push_str("\n**Not adopted:**\n");
+        for (c, reason) in &failed {
+            summary.push_str(&format!("• <@&{}> — {}\n", c.role_id, reason));
+        }
+    }
+
+    let embed = if failed.is_empty() {
+        EmbedBuilder::success("✅ Roles Adopted", summary)
+    } else {
+        EmbedBuilder::warning("⚠️ Adoption Partially Complete", summary)
+    };
+
+    reply
+        .edit(
+            ctx,
+            poise::CreateReply::default()
+                .embed(embed)
+                .components(vec![]),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Find adoption candidates from every page of the member list, so a role
+/// shared by members the cache hasn't seen is never taken for a single
+/// member's. Refuses to judge when the pages fall well short of the member
+/// count, like the cleanup scan.
+async fn scan_candidates(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    tracked: &TrackedRecords,
+) -> Result<Result<(Vec<AdoptionCandidate>, HashMap<RoleId, u32>), IncompleteMembers>, Error> {
+    let http = ctx.http();
+    let bot_id = ctx.framework().bot_id;
+    let guild = guild_id.to_partial_guild_with_counts(http).await?;
+
+    let mut members: Vec<MemberSnapshot> = Vec::new();
+    let mut bot_role_ids: Vec<RoleId> = Vec::new();
+    let mut pages = MemberPages::new(guild_id);
+    while let Some(page) = pages.next(http).await? {
+        for member in &page {
+            if member.user.id == bot_id {
+                bot_role_ids = member.roles.clone();
+            }
+            // Members without roles can't hold a candidate
+            if member.user.bot || member.roles.is_empty() {
+                continue;
+            }
+            members.push(MemberSnapshot {
+                user_id: member.user.id,
+                role_ids: member.roles.clone(),
+                is_booster: member.premium_since.is_some(),
+            });
+        }
+    }
+
+    if let Some(incomplete) =
+        IncompleteMembers::check(pages.fetched, guild.approximate_member_count)
+    {
+        tracing::warn!(
+            guild_id = %guild_id,
+            fetched = incomplete.fetched,
+            expected = incomplete.expected,
+            "Member fetch incomplete, aborting adoption scan"
+        );
+        return Ok(Err(incomplete));
+    }
+
+    let roles: Vec<RoleSnapshot> = guild
+        .roles
+        .values()
+        .map(|r| RoleSnapshot {
+            id: r.id,
+            name: r.name.clone(),
+            position: r.position,
+            managed: r.managed,
+            permissions: r.permissions,
+        })
+        .collect();
+
+    let bot_top_position = bot_role_ids
+        .iter()
+        .filter_map(|id| guild.roles.get(id))
+        .map(|r| r.position)
+        .max()
+        .unwrap_or(0);
+
+    let colors = guild.roles.values().map(|r| (r.id, r.colour.0)).collect();
+
+    Ok(Ok((
+        find_adoption_candidates(
+            guild_id.everyone_role(),
+            &roles,
+            &members,
+            tracked,
+            bot_top_position,
+        ),
+        colors,
+    )))
+}
+
+/// Adopt one role: record it, then strip its permissions to match
+/// bot-created booster roles. The record is committed before the Discord
+/// call so no write lock is held across it, and removed again if the edit
+/// fails.
+async fn adopt_role(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    candidate: &AdoptionCandidate,
+    color: u32,
+) -> Result<bool, Error> {
+    let pool = &ctx.data().db_pool;
+    let mut tx = pool.begin().await?;
+
+    let inserted = BoosterRole::insert_claimed(
+        &mut tx,
+        guild_id,
+        candidate.user_id,
+        candidate.role_id,
+        &candidate.role_name,
+        &ColorParser::to_hex_string(color),
+    )
+    .await?;
+
+    if !inserted {
+        return Ok(false);
+    }
+    tx.commit().await?;
+
+    if let Err(e) = guild_id
+        .edit_role(
+            &ctx.serenity_context().http,
+            candidate.role_id,
+            EditRole::new().permissions(Permissions::empty()),
+        )
+        .await
+    {
+        ctx.data()
+            .repos
+            .booster_roles
+            .delete_by_role(guild_id, candidate.role_id)
+            .await?;
+        return Err(e.into());
+    }
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "booster_role_adopted",
+        Some(&format!(
+            "Role: <@&{}> Owner: <@{}>",
+            candidate.role_id, candidate.user_id
+        )),
+    )
+    .await?;
+
+    Ok(true)
+}
+
+/// Roles and owners already known to the bot, plus the guild's special roles
+async fn load_tracked_records(
+    data: &Data,
+    guild_id: GuildId,
+) -> Result<TrackedRecords, Error> {
+    let pool = &data.db_pool;
+    let mut tracked = TrackedRecords::default();
+
+    for record in data.repos.booster_roles.get_all_for_guild(guild_id).await? {
+        tracked.role_ids.insert(RoleId::new(record.role_id as u64));
+        tracked.user_ids.insert(UserId::new(record.user_id as u64));
+    }
+
+    if let Some(role_id) = GuildBoosterAward::get(pool, guild_id).await? {
+        tracked.role_ids.insert(role_id);
+    }
+    if let Some(role_id) = data.repos.guild_settings.base_role(guild_id).await? {
+        tracked.role_ids.insert(role_id);
+    }
+    if let Some(premium) = GuildPremiumRole::get(pool, guild_id).await? {
+        tracked.role_ids.insert(RoleId::new(premium.role_id as u64));
+    }
+
+    Ok(tracked)
+}
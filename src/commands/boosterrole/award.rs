@@ -1,18 +1,65 @@
-use crate::data::models::GuildBoosterAward;
-use crate::utils::embed_builder::EmbedBuilder;
+use crate::data::models::award_grants::MAX_LISTED_GRANTS;
+use crate::handlers::cleanup::MemberPages;
+use crate::utils::embed_builder::{DescriptionBuilder, EmbedBuilder, EmbedColor};
+use crate::utils::args::RoleArg;
+use crate::utils::paginator::paginate_embeds;
+use crate::utils::progress::ProgressReporter;
+use crate::utils::ResponseHelper;
 use crate::bot::{Context, Error};
-use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, Mentionable, Role};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, Mentionable, Timestamp};
+use std::time::Duration;
 
+/// Grants shown per page of `/boosterrole award list`
+const GRANTS_PER_PAGE: usize = 15;
+
+/// Pause between role assignments during a backfill, to stay under rate limits
+const BACKFILL_DELAY: Duration = Duration::from_millis(250);
+
+/// Boosters handled between progress updates during a backfill
+const BACKFILL_PROGRESS_EVERY: usize = 25;
+
+/// Tally of a backfill run
+#[derive(Debug, Default)]
+struct BackfillOutcome {
+    /// Boosters given the role, or who would be on a dry run
+    granted: usize,
+    /// Boosters who already had the role
+    skipped: usize,
+    failed: usize,
+}
+
+impl BackfillOutcome {
+    fn processed(&self) -> usize {
+        self.granted + self.skipped + self.failed
+    }
+
+    fn describe(&self, dry_run: bool) -> String {
+        if dry_run {
+            format!(
+                "• Would be granted: {}\n• Already have it: {}",
+                self.granted, self.skipped
+            )
+        } else {
+            format!(
+                "• Granted: {}\n• Already had it: {}\n• Failed: {}",
+                self.granted, self.skipped, self.failed
+            )
+        }
+    }
+}
+
+/// Give new boosters a role automatically
 #[poise::command(
     slash_command,
     guild_only,
-    subcommands("set", "unset", "view"),
+    subcommands("set", "unset", "view", "list", "backfill"),
     subcommand_required
 )]
 pub async fn award(_ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// Set the role awarded to new boosters (Admin only)
 #[poise::command(
     slash_command,
     guild_only,
@@ -21,8 +68,14 @@ pub async fn award(_ctx: Context<'_>) -> Result<(), Error> {
 )]
 async fn set(
     ctx: Context<'_>,
-    #[description = "Role to award to new boosters"] role: Role,
+    #[description = "Role to award to new boosters"] role: RoleArg,
+    #[description = "Take the role back when a member stops boosting (default: yes)"]
+    remove_on_unboost: Option<bool>,
+    #[description = "Congratulate new boosters in the join-log channel (default: no)"]
+    announce: Option<bool>,
 ) -> Result<(), Error> {
+    let remove_on_unboost = remove_on_unboost.unwrap_or(true);
+    let announce = announce.unwrap_or(false);
     let guild_id = ctx.guild_id().ok_or(Error::Command("This command can only be used in a guild".to_string()))?;
 
     tracing::info!(
@@ -54,7 +107,11 @@ async fn set(
         return Ok(());
     }
 
-    GuildBoosterAward::set(&ctx.data().db_pool, guild_id, role.id, ctx.author().id).await?;
+    ctx.data()
+        .repos
+        .guild_settings
+        .set_award(guild_id, role.id, remove_on_unboost, announce, ctx.author().id)
+        .await?;
 
     let embed = EmbedBuilder::success(
         "✅ Award Role Set",
@@ -64,7 +121,10 @@ async fn set(
         ),
     )
     .field("Role Details", &format!("Name: {}\nID: {}", role.name, role.id), false)
+    .field("When Boosting Stops", unboost_behavior(remove_on_unboost), false)
+    .field("Announcements", announce_behavior(announce), false)
     .footer(CreateEmbedFooter::new(format!("Set by {}", ctx.author().mention())));
+    let embed = ResponseHelper::with_next_steps(ctx, embed).await;
 
     ctx.send(poise::CreateReply::default().embed(CreateEmbed::from(embed)))
         .await?;
@@ -79,6 +139,7 @@ async fn set(
     Ok(())
 }
 
+/// Stop awarding a role to new boosters (Admin only)
 #[poise::command(
     slash_command,
     guild_only,
@@ -94,7 +155,7 @@ async fn unset(ctx: Context<'_>) -> Result<(), Error> {
         "Removing booster award role"
     );
 
-    let current_award = GuildBoosterAward::get(&ctx.data().db_pool, guild_id).await?;
+    let current_award = ctx.data().repos.guild_settings.award(guild_id).await?;
 
     if current_award.is_none() {
         let embed = EmbedBuilder::info(
@@ -107,7 +168,7 @@ async fn unset(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     }
 
-    let removed = GuildBoosterAward::remove(&ctx.data().db_pool, guild_id).await?;
+    let removed = ctx.data().repos.guild_settings.remove_award(guild_id).await?;
 
     if removed {
         let embed = EmbedBuilder::success(
@@ -137,6 +198,7 @@ async fn unset(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// Show the role awarded to new boosters
 #[poise::command(slash_command, guild_only)]
 async fn view(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or(Error::Command("This command can only be used in a guild".to_string()))?;
@@ -147,10 +209,11 @@ async fn view(ctx: Context<'_>) -> Result<(), Error> {
         "Viewing booster award role"
     );
 
-    let award_role_id = GuildBoosterAward::get(&ctx.data().db_pool, guild_id).await?;
+    let award = ctx.data().repos.guild_settings.award(guild_id).await?;
 
-    match award_role_id {
-        Some(role_id) => {
+    match award {
+        Some(award) => {
+            let role_id = award.role_id();
             let guild = guild_id.to_partial_guild(&ctx.serenity_context().http).await?;
             
             if let Some(role) = guild.roles.get(&role_id) {
@@ -162,6 +225,8 @@ async fn view(ctx: Context<'_>) -> Result<(), Error> {
                     ),
                 )
                 .field("Role Details", &format!("Name: {}\nID: {}\nColor: #{:06X}", role.name, role.id, role.colour.0), false)
+                .field("When Boosting Stops", unboost_behavior(award.remove_on_unboost), false)
+                .field("Announcements", announce_behavior(award.announce_boosts), false)
                 .footer(CreateEmbedFooter::new("Use /boosterrole award set to change or unset to remove"));
 
                 ctx.send(poise::CreateReply::default().embed(CreateEmbed::from(embed)))
@@ -192,4 +257,229 @@ async fn view(ctx: Context<'_>) -> Result<(), Error> {
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Show who has been given the award role, newest first
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD | MANAGE_ROLES",
+    default_member_permissions = "MANAGE_GUILD | MANAGE_ROLES"
+)]
+async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(Error::Command("This command can only be used in a guild".to_string()))?;
+
+    let grants = ctx
+        .data()
+        .repos
+        .booster_roles
+        .recent_award_grants(guild_id, MAX_LISTED_GRANTS)
+        .await?;
+
+    if grants.is_empty() {
+        let embed = EmbedBuilder::info(
+            "🎁 Award Grants",
+            "Nobody has been given the award role yet.\n\nGrants are recorded when a member starts boosting while an award role is set.",
+        );
+
+        ctx.send(poise::CreateReply::default().embed(CreateEmbed::from(embed)))
+            .await?;
+        return Ok(());
+    }
+
+    let lines = grants.iter().map(|grant| {
+        let when = format!("<t:{}:R>", grant.granted_at.timestamp());
+        format!("<@{}> • <@&{}> • {}", grant.user_id, grant.role_id, when)
+    });
+
+    let pages = DescriptionBuilder::new()
+        .max_lines(GRANTS_PER_PAGE)
+        .lines(lines)
+        .build(|description, page, total_pages| {
+            CreateEmbed::new()
+                .title("🎁 Award Grants")
+                .description(description)
+                .color(EmbedColor::Primary.value())
+                .footer(CreateEmbedFooter::new(format!(
+                    "Page {} of {} • {} most recent grant(s)",
+                    page,
+                    total_pages,
+                    grants.len()
+                )))
+                .timestamp(Timestamp::now())
+        });
+
+    paginate_embeds(ctx, pages).await?;
+    Ok(())
+}
+
+fn unboost_behavior(remove_on_unboost: bool) -> &'static str {
+    if remove_on_unboost {
+        "The award role is removed."
+    } else {
+        "Members keep the award role."
+    }
+}
+
+fn announce_behavior(announce: bool) -> &'static str {
+    if announce {
+        "New boosters are congratulated in the join-log channel."
+    } else {
+        "New boosters aren't announced."
+    }
+}
+
+/// Give the award role to members who were already boosting (Admin only)
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD | MANAGE_ROLES",
+    default_member_permissions = "MANAGE_GUILD | MANAGE_ROLES",
+    required_bot_permissions = "MANAGE_ROLES"
+)]
+async fn backfill(
+    ctx: Context<'_>,
+    #[description = "Only count who would get the role (default: no)"] dry_run: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(Error::Command("This command can only be used in a guild".to_string()))?;
+    let dry_run = dry_run.unwrap_or(false);
+    let repos = &ctx.data().repos;
+
+    tracing::info!(
+        guild_id = %guild_id,
+        admin_id = %ctx.author().id,
+        dry_run = dry_run,
+        "Booster award backfill initiated"
+    );
+
+    let Some(award) = repos.guild_settings.award(guild_id).await? else {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ No Award Role Set",
+            "Configure one with `/boosterrole award set` before backfilling.",
+        )
+        .await?;
+        return Ok(());
+    };
+    let award_role_id = award.role_id();
+
+    ctx.defer().await?;
+
+    let http = &ctx.serenity_context().http;
+    let guild = guild_id.to_partial_guild(http).await?;
+    if !guild.roles.contains_key(&award_role_id) {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Award Role Missing",
+            &format!(
+                "The configured award role (ID: {}) no longer exists. Set a new one with `/boosterrole award set`.",
+                award_role_id
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let title = if dry_run {
+        "🔍 Award Backfill Preview (Dry Run)"
+    } else {
+        "🎁 Award Backfill"
+    };
+    let mut progress = ProgressReporter::new(ctx);
+    progress
+        .update(EmbedBuilder::info(
+            title,
+            format!("Checking boosters for {}…", award_role_id.mention()),
+        ))
+        .await?;
+
+    let mut outcome = BackfillOutcome::default();
+    let mut pages = MemberPages::new(guild_id);
+    while let Some(members) = pages.next(http).await? {
+        for member in members.iter().filter(|m| m.premium_since.is_some()) {
+            if member.roles.contains(&award_role_id) {
+                outcome.skipped += 1;
+            } else if dry_run {
+                outcome.granted += 1;
+            } else {
+                match member.add_role(http, award_role_id).await {
+                    Ok(()) => {
+                        outcome.granted += 1;
+                        if let Err(e) = repos
+                            .booster_roles
+                            .record_award_grant(guild_id, member.user.id, award_role_id)
+                            .await
+                        {
+                            tracing::error!(
+                                user_id = %member.user.id,
+                                guild_id = %guild_id,
+                                error = ?e,
+                                "Failed to record backfilled award grant"
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        outcome.failed += 1;
+                        tracing::warn!(
+                            user_id = %member.user.id,
+                            guild_id = %guild_id,
+                            award_role_id = %award_role_id,
+                            error = ?e,
+                            "Failed to backfill award role"
+                        );
+                    }
+                }
+                tokio::time::sleep(BACKFILL_DELAY).await;
+            }
+
+            if outcome.processed().is_multiple_of(BACKFILL_PROGRESS_EVERY) {
+                progress
+                    .update(
+                        EmbedBuilder::info(
+                            title,
+                            format!("Checked **{}** booster(s) so far…", outcome.processed()),
+                        )
+                        .field("So Far", outcome.describe(dry_run), false),
+                    )
+                    .await?;
+            }
+        }
+    }
+
+    tracing::info!(
+        guild_id = %guild_id,
+        dry_run = dry_run,
+        granted = outcome.granted,
+        skipped = outcome.skipped,
+        failed = outcome.failed,
+        "Booster award backfill completed"
+    );
+
+    let summary = if outcome.processed() == 0 {
+        EmbedBuilder::info(title, "Nobody in this server is boosting right now.")
+    } else if dry_run {
+        EmbedBuilder::info(
+            title,
+            format!(
+                "Checked **{}** booster(s) for {}.",
+                outcome.processed(),
+                award_role_id.mention()
+            ),
+        )
+        .field("Results", outcome.describe(true), false)
+        .footer(CreateEmbedFooter::new("Run without dry_run to grant the role"))
+    } else {
+        EmbedBuilder::success(
+            "✅ Award Backfill Complete",
+            format!(
+                "Checked **{}** booster(s) for {}.",
+                outcome.processed(),
+                award_role_id.mention()
+            ),
+        )
+        .field("Results", outcome.describe(false), false)
+    };
+    progress.update(summary).await?;
+
+    Ok(())
+}
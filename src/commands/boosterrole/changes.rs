@@ -0,0 +1,1072 @@
+//! The database side of the booster role commands: everything that decides
+//! whether a change may go ahead and what gets stored afterwards. The poise
+//! commands around these only talk to Discord, so the rules can be tested
+//! against any [`Repos`], in-memory ones included.
+
+use crate::bot::Error;
+use crate::data::models::{BoosterLimitUsage, BoosterRole, GuildBoosterOptions};
+use crate::data::repos::Repos;
+use crate::utils::content_filter::NameFilters;
+use crate::utils::i18n::{translate, Arg, Locale};
+use crate::utils::name_severity::NameCheck;
+use crate::utils::rename_cooldown::{cooldown_remaining, effective_cooldown, format_remaining};
+use crate::utils::role_name::normalize_role_name;
+use crate::utils::{ColorParser, EmbedBuilder, RequestedChange, RoleManager};
+use chrono::{DateTime, Utc};
+use serenity::all::{CreateEmbed, GuildId, RoleId, UserId};
+
+/// Members a role can be shared with when the guild hasn't set a limit
+const DEFAULT_MAX_MEMBERS_PER_ROLE: i32 = 5;
+
+/// Shared roles a member can hold when the guild hasn't set a limit
+const DEFAULT_MAX_SHARED_ROLES_PER_MEMBER: i32 = 3;
+
+/// Why a booster role change was turned down before anything changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The name breaks Discord's role name rules
+    InvalidName(String),
+    /// The name matches a blacklisted word; `alert` asks for a staff alert
+    BlockedName {
+        word: String,
+        alert: bool,
+    },
+    InvalidColor(String),
+    InvalidSecondColor(String),
+    /// The name is longer than the guild allows booster role names to be
+    NameTooLong {
+        length: usize,
+        max: i64,
+    },
+    /// The guild doesn't allow gradient roles
+    SecondColorDisabled,
+    /// The member has no role yet and the guild has no room for another
+    LimitReached(BoosterLimitUsage),
+    /// The member has no booster role to change
+    NoRole,
+    /// The member renamed too recently
+    RenameCooldown {
+        remaining: chrono::Duration,
+        old_name: String,
+        new_name: String,
+    },
+    ShareWithSelf,
+    /// Bots have no use for a shared role
+    ShareWithBot,
+    /// Discord says the member isn't in the guild
+    NotInGuild(UserId),
+    /// The role is on record but was deleted in Discord; the stale record is
+    /// cleared so the color command can make a new one
+    RoleMissing,
+    /// The role is already shared with as many members as the guild allows
+    RoleShareLimit(i32),
+    /// The member already holds as many shared roles as the guild allows
+    MemberShareLimit {
+        user_id: UserId,
+        max: i32,
+    },
+    AlreadyShared(UserId),
+}
+
+impl Refusal {
+    /// The reply explaining the refusal to the member, in their language
+    pub fn embed(&self, locale: Locale) -> CreateEmbed {
+        let tr = |key: &str, args: &[Arg]| translate(locale, key, args);
+        match self {
+            Refusal::InvalidName(reason) => {
+                EmbedBuilder::error(tr("booster.invalid_name", &[]), reason)
+            }
+            Refusal::BlockedName { .. } => EmbedBuilder::error(
+                tr("booster.blocked_name.title", &[]),
+                tr("booster.blocked_name.body", &[]),
+            ),
+            Refusal::InvalidColor(reason) => EmbedBuilder::error(
+                tr("booster.invalid_color.title", &[]),
+                tr("booster.invalid_color.body", &[("reason", reason)]),
+            ),
+            Refusal::InvalidSecondColor(reason) => {
+                EmbedBuilder::error(tr("booster.invalid_second_color", &[]), reason)
+            }
+            Refusal::NameTooLong { length, max } => EmbedBuilder::error(
+                tr("booster.name_too_long.title", &[]),
+                tr(
+                    "booster.name_too_long.body",
+                    &[("max", max), ("length", length)],
+                ),
+            ),
+            Refusal::SecondColorDisabled => EmbedBuilder::error(
+                tr("booster.second_color_disabled.title", &[]),
+                tr("booster.second_color_disabled.body", &[]),
+            ),
+            Refusal::LimitReached(usage) => EmbedBuilder::error(
+                tr("booster.limit.title", &[]),
+                tr(
+                    "booster.limit.body",
+                    &[("max", &usage.max), ("current", &usage.current)],
+                ),
+            ),
+            Refusal::NoRole => EmbedBuilder::error(
+                tr("booster.no_role.title", &[]),
+                tr("booster.no_role.body", &[]),
+            ),
+            Refusal::RenameCooldown {
+                remaining,
+                old_name,
+                new_name,
+            } => EmbedBuilder::error(
+                tr("booster.rename_cooldown.title", &[]),
+                tr(
+                    "booster.rename_cooldown.body",
+                    &[
+                        ("remaining", &format_remaining(*remaining)),
+                        ("old_name", old_name),
+                        ("new_name", new_name),
+                    ],
+                ),
+            ),
+            Refusal::ShareWithSelf => EmbedBuilder::error(
+                tr("booster.share_self.title", &[]),
+                tr("booster.share_self.body", &[]),
+            ),
+            Refusal::ShareWithBot => EmbedBuilder::error(
+                tr("booster.share_bot.title", &[]),
+                tr("booster.share_bot.body", &[]),
+            ),
+            Refusal::NotInGuild(user_id) => EmbedBuilder::error(
+                tr("booster.not_in_guild.title", &[]),
+                tr("booster.not_in_guild.body", &[("user", user_id)]),
+            ),
+            Refusal::RoleMissing => EmbedBuilder::error(
+                tr("booster.role_missing.title", &[]),
+                tr("booster.role_missing.body", &[]),
+            ),
+            Refusal::RoleShareLimit(max) => EmbedBuilder::error(
+                tr("booster.role_share_limit.title", &[]),
+                tr("booster.role_share_limit.body", &[("max", max)]),
+            ),
+            Refusal::MemberShareLimit { user_id, max } => EmbedBuilder::error(
+                tr("booster.member_share_limit.title", &[]),
+                tr(
+                    "booster.member_share_limit.body",
+                    &[("user", user_id), ("max", max)],
+                ),
+            ),
+            Refusal::AlreadyShared(user_id) => EmbedBuilder::error(
+                tr("booster.already_shared.title", &[]),
+                tr("booster.already_shared.body", &[("user", user_id)]),
+            ),
+        }
+    }
+}
+
+/// Check a name against the blacklists, recording matches against
+/// `command` when there is one. A failed lookup is logged and lets the name
+/// through rather than blocking every change.
+async fn check_name(
+    repos: &Repos,
+    guild_id: GuildId,
+    user_id: UserId,
+    name: &str,
+    command: Option<&str>,
+    filters: &NameFilters,
+) -> NameCheck {
+    let check = repos
+        .guild_settings
+        .check_role_name(guild_id, user_id, name, command, filters)
+        .await;
+    match check {
+        Ok(check) => check,
+        Err(e) => {
+            tracing::error!(
+                error = ?e,
+                guild_id = %guild_id,
+                "Failed to check role name blacklist"
+            );
+            NameCheck::Clean
+        }
+    }
+}
+
+/// Refuse a name longer than the guild's own cap on booster role names
+fn name_length_refusal(options: &GuildBoosterOptions, name: &str) -> Option<Refusal> {
+    let length = options.name_too_long(name)?;
+    Some(Refusal::NameTooLong {
+        length,
+        max: options.max_name_length.unwrap_or_default(),
+    })
+}
+
+/// A validated `/boosterrole color` request, ready to apply on Discord
+#[derive(Debug, Clone)]
+pub struct ColorPlan {
+    pub name: String,
+    pub primary: u32,
+    pub secondary: Option<u32>,
+    /// The member's current role, which is updated rather than replaced
+    pub existing: Option<BoosterRole>,
+    pub name_check: NameCheck,
+}
+
+impl ColorPlan {
+    pub fn primary_hex(&self) -> String {
+        ColorParser::to_hex_string(self.primary)
+    }
+
+    pub fn secondary_hex(&self) -> Option<String> {
+        self.secondary.map(ColorParser::to_hex_string)
+    }
+
+    /// What the plan changes about the member's existing role, for checking
+    /// staff locks. Either color differing counts as a color change.
+    pub fn requested_change(&self) -> RequestedChange {
+        let Some(existing) = &self.existing else {
+            return RequestedChange::default();
+        };
+        let same_hex = |a: &str, b: &str| a.eq_ignore_ascii_case(b);
+        let secondary_changed = match (&existing.secondary_color, self.secondary_hex()) {
+            (Some(old), Some(new)) => !same_hex(old, &new),
+            (None, None) => false,
+            _ => true,
+        };
+        RequestedChange {
+            name: existing.role_name != self.name,
+            color: !same_hex(&existing.primary_color, &self.primary_hex()) || secondary_changed,
+        }
+    }
+}
+
+/// Decide whether a member may create or update their role with this name
+/// and these colors
+#[allow(clippy::too_many_arguments)]
+pub async fn color_core(
+    repos: &Repos,
+    guild_id: GuildId,
+    user_id: UserId,
+    name: &str,
+    color: &str,
+    second_color: Option<&str>,
+    filters: &NameFilters,
+    command: &str,
+) -> Result<Result<ColorPlan, Refusal>, Error> {
+    plan_color(
+        repos,
+        guild_id,
+        user_id,
+        name,
+        color,
+        second_color,
+        filters,
+        Some(command),
+    )
+    .await
+}
+
+/// Run every check `color_core` does without writing anything, not even a
+/// blacklist match, so members can try a look before committing to it
+pub async fn preview_core(
+    repos: &Repos,
+    guild_id: GuildId,
+    user_id: UserId,
+    name: &str,
+    color: &str,
+    second_color: Option<&str>,
+    filters: &NameFilters,
+) -> Result<Result<ColorPlan, Refusal>, Error> {
+    plan_color(
+        repos,
+        guild_id,
+        user_id,
+        name,
+        color,
+        second_color,
+        filters,
+        None,
+    )
+    .await
+}
+
+/// The checks behind `color_core` and `preview_core`; blacklist matches are
+/// only recorded when there's a `command` to record them against
+#[allow(clippy::too_many_arguments)]
+async fn plan_color(
+    repos: &Repos,
+    guild_id: GuildId,
+    user_id: UserId,
+    name: &str,
+    color: &str,
+    second_color: Option<&str>,
+    filters: &NameFilters,
+    command: Option<&str>,
+) -> Result<Result<ColorPlan, Refusal>, Error> {
+    // Blacklist checks see the name without the invisible characters used to
+    // slip words past them
+    let name = normalize_role_name(name);
+    let name_check = check_name(repos, guild_id, user_id, &name, command, filters).await;
+    if let NameCheck::Reject { word, alert } = &name_check {
+        return Ok(Err(Refusal::BlockedName {
+            word: word.clone(),
+            alert: *alert,
+        }));
+    }
+
+    let name = match RoleManager::validate_role_name(&name) {
+        Ok(name) => name,
+        Err(e) => return Ok(Err(Refusal::InvalidName(e.to_string()))),
+    };
+
+    let options = repos.guild_settings.booster_options(guild_id).await?;
+    if let Some(refusal) = name_length_refusal(&options, &name) {
+        return Ok(Err(refusal));
+    }
+    if second_color.is_some() && !options.allow_secondary_color {
+        return Ok(Err(Refusal::SecondColorDisabled));
+    }
+
+    let primary = match ColorParser::parse(color) {
+        Ok(primary) => primary,
+        Err(e) => return Ok(Err(Refusal::InvalidColor(e.to_string()))),
+    };
+    let secondary = match second_color.map(ColorParser::parse).transpose() {
+        Ok(secondary) => secondary,
+        Err(e) => return Ok(Err(Refusal::InvalidSecondColor(e.to_string()))),
+    };
+
+    let existing = repos.booster_roles.get(guild_id, user_id).await?;
+    if existing.is_none() {
+        if let Some(usage) = repos.limit_usage(guild_id).await? {
+            if usage.is_full() {
+                return Ok(Err(Refusal::LimitReached(usage)));
+            }
+        }
+    }
+
+    Ok(Ok(ColorPlan {
+        name,
+        primary,
+        secondary,
+        existing,
+        name_check,
+    }))
+}
+
+/// Store the role a color plan was applied to
+pub async fn record_color(
+    repos: &Repos,
+    guild_id: GuildId,
+    user_id: UserId,
+    role_id: RoleId,
+    plan: &ColorPlan,
+) -> Result<(), Error> {
+    repos
+        .booster_roles
+        .create(
+            guild_id,
+            user_id,
+            role_id,
+            &plan.name,
+            &plan.primary_hex(),
+            plan.secondary_hex().as_deref(),
+        )
+        .await?;
+    Ok(())
+}
+
+/// A validated `/boosterrole rename set` request
+#[derive(Debug, Clone)]
+pub struct RenamePlan {
+    pub role: BoosterRole,
+    /// The requested name as it will be applied, after normalization
+    pub new_name: String,
+    /// The guild's cooldown, for telling the member when they can rename next
+    pub cooldown_minutes: i64,
+    pub name_check: NameCheck,
+}
+
+/// Decide whether a member may rename their role now. Members who manage
+/// the server pass `bypass_cooldown`.
+#[allow(clippy::too_many_arguments)]
+pub async fn rename_core(
+    repos: &Repos,
+    guild_id: GuildId,
+    user_id: UserId,
+    new_name: &str,
+    bypass_cooldown: bool,
+    filters: &NameFilters,
+    now: DateTime<Utc>,
+) -> Result<Result<RenamePlan, Refusal>, Error> {
+    let Some(role) = repos.booster_roles.get(guild_id, user_id).await? else {
+        return Ok(Err(Refusal::NoRole));
+    };
+
+    let cooldown_minutes = effective_cooldown(repos.guild_settings.rename_cooldown(guild_id).await?);
+    if !bypass_cooldown {
+        if let Some(last) = repos.booster_roles.last_rename(guild_id, user_id).await? {
+            if let Some(remaining) = cooldown_remaining(last.renamed_at(), cooldown_minutes, now) {
+                return Ok(Err(Refusal::RenameCooldown {
+                    remaining,
+                    old_name: last.old_name,
+                    new_name: last.new_name,
+                }));
+            }
+        }
+    }
+
+    let new_name = normalize_role_name(new_name);
+    let name_check = check_name(
+        repos,
+        guild_id,
+        user_id,
+        &new_name,
+        Some("boosterrole rename"),
+        filters,
+    )
+    .await;
+    if let NameCheck::Reject { word, alert } = &name_check {
+        return Ok(Err(Refusal::BlockedName {
+            word: word.clone(),
+            alert: *alert,
+        }));
+    }
+
+    let new_name = match RoleManager::validate_role_name(&new_name) {
+        Ok(name) => name,
+        Err(e) => return Ok(Err(Refusal::InvalidName(e.to_string()))),
+    };
+    let options = repos.guild_settings.booster_options(guild_id).await?;
+    if let Some(refusal) = name_length_refusal(&options, &new_name) {
+        return Ok(Err(refusal));
+    }
+
+    Ok(Ok(RenamePlan {
+        role,
+        new_name,
+        cooldown_minutes,
+        name_check,
+    }))
+}
+
+/// Store a rename once Discord has applied it
+pub async fn record_rename(
+    repos: &Repos,
+    guild_id: GuildId,
+    user_id: UserId,
+    plan: &RenamePlan,
+    new_name: &str,
+) -> Result<(), Error> {
+    repos
+        .booster_roles
+        .update(
+            guild_id,
+            user_id,
+            new_name,
+            &plan.role.primary_color,
+            plan.role.secondary_color.as_deref(),
+        )
+        .await?;
+
+    repos
+        .booster_roles
+        .record_rename(guild_id, user_id, &plan.role.role_name, new_name)
+        .await?;
+    Ok(())
+}
+
+/// Decide whether a member may share their role with another. Returns the
+/// role being shared.
+pub async fn share_core(
+    repos: &Repos,
+    guild_id: GuildId,
+    owner_id: UserId,
+    target_id: UserId,
+) -> Result<Result<BoosterRole, Refusal>, Error> {
+    if target_id == owner_id {
+        return Ok(Err(Refusal::ShareWithSelf));
+    }
+
+    let Some(role) = repos.booster_roles.get(guild_id, owner_id).await? else {
+        return Ok(Err(Refusal::NoRole));
+    };
+    let role_id = RoleId::new(role.role_id as u64);
+
+    let (max_members_per_role, max_shared_roles_per_member) =
+        match repos.guild_settings.sharing_limits(guild_id).await? {
+            Some(limits) => (
+                limits.max_members_per_role,
+                limits.max_shared_roles_per_member,
+            ),
+            None => (
+                DEFAULT_MAX_MEMBERS_PER_ROLE,
+                DEFAULT_MAX_SHARED_ROLES_PER_MEMBER,
+            ),
+        };
+
+    if repos.booster_roles.count_role_shares(guild_id, role_id).await?
+        >= max_members_per_role as i64
+    {
+        return Ok(Err(Refusal::RoleShareLimit(max_members_per_role)));
+    }
+
+    if repos.booster_roles.count_user_shares(guild_id, target_id).await?
+        >= max_shared_roles_per_member as i64
+    {
+        return Ok(Err(Refusal::MemberShareLimit {
+            user_id: target_id,
+            max: max_shared_roles_per_member,
+        }));
+    }
+
+    if repos
+        .booster_roles
+        .is_shared_with(guild_id, role_id, target_id)
+        .await?
+    {
+        return Ok(Err(Refusal::AlreadyShared(target_id)));
+    }
+
+    Ok(Ok(role))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::database::init_memory_database;
+    use crate::data::models::GuildRenameCooldown;
+    use crate::data::repos::memory::MemoryRepo;
+    use crate::utils::name_severity::{BlacklistEntry, MatchType, Severity};
+    use crate::utils::role_lock::LockedAspect;
+    use crate::utils::{check_lock, LockFlags};
+
+    const GUILD: GuildId = GuildId::new(10);
+    const MEMBER: UserId = UserId::new(20);
+    const ADMIN: UserId = UserId::new(99);
+
+    fn no_filters() -> NameFilters {
+        NameFilters::default()
+    }
+
+    async fn plan(
+        repos: &Repos,
+        user_id: UserId,
+        name: &str,
+        color: &str,
+    ) -> Result<ColorPlan, Refusal> {
+        color_core(
+            repos,
+            GUILD,
+            user_id,
+            name,
+            color,
+            None,
+            &no_filters(),
+            "boosterrole color",
+        )
+        .await
+        .unwrap()
+    }
+
+    /// Run a color change through to the database, as the command would
+    /// after Discord created the role
+    async fn give_role(repos: &Repos, user_id: UserId, role_id: u64) {
+        let plan = plan(repos, user_id, "Mine", "red").await.unwrap();
+        record_color(repos, GUILD, user_id, RoleId::new(role_id), &plan)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_color_creates_then_updates() {
+        let pool = init_memory_database().await.unwrap();
+        let repos = Repos::sqlite(pool.clone());
+
+        let first = plan(&repos, MEMBER, "Sunset", "#FF8800").await.unwrap();
+        assert!(first.existing.is_none());
+        assert_eq!(first.primary, 0xFF8800);
+        record_color(&repos, GUILD, MEMBER, RoleId::new(500), &first)
+            .await
+            .unwrap();
+
+        let second = color_core(
+            &repos,
+            GUILD,
+            MEMBER,
+            "Ocean",
+            "blue",
+            Some("#00FFFF"),
+            &no_filters(),
+            "boosterrole color",
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        let existing = second.existing.clone().unwrap();
+        assert_eq!(existing.role_name, "Sunset");
+        assert_eq!(existing.role_id, 500);
+        record_color(&repos, GUILD, MEMBER, RoleId::new(500), &second)
+            .await
+            .unwrap();
+
+        let stored = BoosterRole::get(&pool, GUILD, MEMBER)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.role_name, "Ocean");
+        assert_eq!(stored.primary_color, second.primary_hex());
+        assert_eq!(stored.secondary_color.as_deref(), Some("#00FFFF"));
+    }
+
+    #[tokio::test]
+    async fn test_second_color_change_counts_against_a_color_lock() {
+        async fn with_second(repos: &Repos, second: Option<&str>) -> ColorPlan {
+            color_core(
+                repos,
+                GUILD,
+                MEMBER,
+                "Mine",
+                "red",
+                second,
+                &no_filters(),
+                "boosterrole color",
+            )
+            .await
+            .unwrap()
+            .unwrap()
+        }
+
+        let (_, repos) = MemoryRepo::repos();
+        give_role(&repos, MEMBER, 500).await;
+        let color_locked = LockFlags {
+            name: false,
+            color: true,
+        };
+
+        // Same name and colors: nothing a lock could block
+        let unchanged = with_second(&repos, None).await.requested_change();
+        assert_eq!(unchanged, RequestedChange::default());
+
+        // Only adding a second color is still a color change
+        let added = with_second(&repos, Some("blue")).await.requested_change();
+        assert_eq!(added, RequestedChange::color());
+        assert_eq!(check_lock(color_locked, added), Err(LockedAspect::Color));
+
+        // As are changing and removing one
+        let plan = with_second(&repos, Some("blue")).await;
+        record_color(&repos, GUILD, MEMBER, RoleId::new(500), &plan)
+            .await
+            .unwrap();
+        for second in [Some("green"), None] {
+            let change = with_second(&repos, second).await.requested_change();
+            assert_eq!(change, RequestedChange::color(), "{:?}", second);
+        }
+        let same = with_second(&repos, Some("#0000ff")).await.requested_change();
+        assert_eq!(same, RequestedChange::default());
+    }
+
+    #[tokio::test]
+    async fn test_color_rejects_bad_names_and_colors() {
+        let pool = init_memory_database().await.unwrap();
+        let repos = Repos::sqlite(pool.clone());
+
+        assert!(matches!(
+            plan(&repos, MEMBER, "@everyone", "red").await,
+            Err(Refusal::InvalidName(_))
+        ));
+        assert!(matches!(
+            plan(&repos, MEMBER, "Fine", "not a color").await,
+            Err(Refusal::InvalidColor(_))
+        ));
+        assert!(BoosterRole::get(&pool, GUILD, MEMBER)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_blacklisted_names_are_refused() {
+        let pool = init_memory_database().await.unwrap();
+        let repos = Repos::sqlite(pool.clone());
+        let filters = NameFilters {
+            global: Vec::new(),
+            guild: vec![BlacklistEntry::new(
+                "slur",
+                Severity::High,
+                MatchType::Substring,
+            )],
+        };
+
+        let refusal = color_core(
+            &repos,
+            GUILD,
+            MEMBER,
+            "Big Slur Energy",
+            "red",
+            None,
+            &filters,
+            "boosterrole color",
+        )
+        .await
+        .unwrap()
+        .unwrap_err();
+        assert!(matches!(refusal, Refusal::BlockedName { ref word, .. } if word == "slur"));
+
+        give_role(&repos, MEMBER, 500).await;
+        let refusal = rename_core(&repos, GUILD, MEMBER, "slurred", false, &filters, Utc::now())
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(refusal, Refusal::BlockedName { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_preview_writes_nothing() {
+        let pool = init_memory_database().await.unwrap();
+        let repos = Repos::sqlite(pool.clone());
+        let filters = NameFilters {
+            global: Vec::new(),
+            guild: vec![BlacklistEntry::new(
+                "slur",
+                Severity::High,
+                MatchType::Substring,
+            )],
+        };
+        let rows = |table: &'static str| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM {}", table))
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let preview = preview_core(
+            &repos,
+            GUILD,
+            MEMBER,
+            "Dusk",
+            "#FF8800",
+            Some("purple"),
+            &filters,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(preview.primary, 0xFF8800);
+        assert_eq!(preview.secondary_hex().as_deref(), Some("#800080"));
+        assert!(preview.existing.is_none());
+
+        let refusal = preview_core(&repos, GUILD, MEMBER, "Slur", "red", None, &filters)
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(refusal, Refusal::BlockedName { .. }));
+
+        assert_eq!(rows("booster_roles").await, 0);
+        assert_eq!(rows("name_violations").await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_only_commands_record_blacklist_matches() {
+        let (store, repos) = MemoryRepo::repos();
+        let filters = NameFilters {
+            global: Vec::new(),
+            guild: vec![BlacklistEntry::new(
+                "slur",
+                Severity::High,
+                MatchType::Substring,
+            )],
+        };
+
+        let refusal = preview_core(&repos, GUILD, MEMBER, "Slur", "red", None, &filters)
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(refusal, Refusal::BlockedName { .. }));
+        assert!(store.violations().is_empty());
+
+        let refusal = color_core(
+            &repos,
+            GUILD,
+            MEMBER,
+            "Slur",
+            "red",
+            None,
+            &filters,
+            "boosterrole color",
+        )
+        .await
+        .unwrap()
+        .unwrap_err();
+        assert!(matches!(refusal, Refusal::BlockedName { .. }));
+        assert_eq!(store.violations(), vec![(MEMBER, "Slur".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_guild_limit_blocks_only_new_roles() {
+        let (_, repos) = MemoryRepo::repos();
+        repos
+            .guild_settings
+            .set_booster_limit(GUILD, 1, ADMIN)
+            .await
+            .unwrap();
+
+        give_role(&repos, MEMBER, 500).await;
+
+        let refusal = plan(&repos, UserId::new(21), "Second", "red")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            refusal,
+            Refusal::LimitReached(BoosterLimitUsage { current: 1, max: 1 })
+        );
+
+        // The member who already has a role can still change it
+        assert!(plan(&repos, MEMBER, "Renamed", "green").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_guild_name_length_cap() {
+        let pool = init_memory_database().await.unwrap();
+        let repos = Repos::sqlite(pool.clone());
+        give_role(&repos, MEMBER, 500).await;
+        let options = GuildBoosterOptions {
+            max_name_length: Some(10),
+            ..GuildBoosterOptions::default()
+        };
+        GuildBoosterOptions::set(&pool, GUILD, options, ADMIN)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            plan(&repos, MEMBER, "Much Too Long", "red").await.unwrap_err(),
+            Refusal::NameTooLong { length: 13, max: 10 }
+        );
+        // Measured after normalization, so padding doesn't count
+        assert!(plan(&repos, MEMBER, "  Ten chars!  ", "red").await.is_ok());
+
+        let rename = rename_core(
+            &repos,
+            GUILD,
+            MEMBER,
+            "Eleven Char",
+            true,
+            &no_filters(),
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            rename.unwrap_err(),
+            Refusal::NameTooLong { length: 11, max: 10 }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_second_color_can_be_disallowed() {
+        let (_, repos) = MemoryRepo::repos();
+        let options = GuildBoosterOptions {
+            allow_secondary_color: false,
+            ..GuildBoosterOptions::default()
+        };
+        repos
+            .guild_settings
+            .set_booster_options(GUILD, options, ADMIN)
+            .await
+            .unwrap();
+
+        let gradient = color_core(
+            &repos,
+            GUILD,
+            MEMBER,
+            "Sunset",
+            "red",
+            Some("blue"),
+            &no_filters(),
+            "boosterrole color",
+        )
+        .await
+        .unwrap();
+        assert_eq!(gradient.unwrap_err(), Refusal::SecondColorDisabled);
+
+        assert!(plan(&repos, MEMBER, "Sunset", "red").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rename_cooldown() {
+        let pool = init_memory_database().await.unwrap();
+        let repos = Repos::sqlite(pool.clone());
+        GuildRenameCooldown::set(&pool, GUILD, 30, ADMIN)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            rename_core(
+                &repos,
+                GUILD,
+                MEMBER,
+                "New",
+                false,
+                &no_filters(),
+                Utc::now()
+            )
+            .await
+            .unwrap()
+            .unwrap_err(),
+            Refusal::NoRole
+        );
+
+        give_role(&repos, MEMBER, 500).await;
+        let first = rename_core(
+            &repos,
+            GUILD,
+            MEMBER,
+            "Second",
+            false,
+            &no_filters(),
+            Utc::now(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(first.cooldown_minutes, 30);
+        record_rename(&repos, GUILD, MEMBER, &first, "Second")
+            .await
+            .unwrap();
+        assert_eq!(
+            BoosterRole::get(&pool, GUILD, MEMBER)
+                .await
+                .unwrap()
+                .unwrap()
+                .role_name,
+            "Second"
+        );
+
+        let refusal = rename_core(
+            &repos,
+            GUILD,
+            MEMBER,
+            "Third",
+            false,
+            &no_filters(),
+            Utc::now(),
+        )
+        .await
+        .unwrap()
+        .unwrap_err();
+        assert!(matches!(
+            refusal,
+            Refusal::RenameCooldown { ref old_name, ref new_name, .. }
+                if old_name == "Mine" && new_name == "Second"
+        ));
+
+        // Staff skip the wait, and everyone is through once it has passed
+        assert!(rename_core(
+            &repos,
+            GUILD,
+            MEMBER,
+            "Third",
+            true,
+            &no_filters(),
+            Utc::now()
+        )
+        .await
+        .unwrap()
+        .is_ok());
+        let later = Utc::now() + chrono::Duration::minutes(31);
+        assert!(
+            rename_core(&repos, GUILD, MEMBER, "Third", false, &no_filters(), later)
+                .await
+                .unwrap()
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_share_limits() {
+        let (store, repos) = MemoryRepo::repos();
+        repos
+            .guild_settings
+            .set_sharing_limits(GUILD, 1, 1, ADMIN)
+            .await
+            .unwrap();
+        let (friend, other, second_owner) = (UserId::new(30), UserId::new(31), UserId::new(32));
+
+        assert_eq!(
+            share_core(&repos, GUILD, MEMBER, friend)
+                .await
+                .unwrap()
+                .unwrap_err(),
+            Refusal::NoRole
+        );
+
+        give_role(&repos, MEMBER, 500).await;
+        give_role(&repos, second_owner, 501).await;
+        assert_eq!(
+            share_core(&repos, GUILD, MEMBER, MEMBER)
+                .await
+                .unwrap()
+                .unwrap_err(),
+            Refusal::ShareWithSelf
+        );
+
+        let role = share_core(&repos, GUILD, MEMBER, friend)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(role.role_id, 500);
+        store.share(GUILD, RoleId::new(500), friend);
+
+        // One member per role
+        assert_eq!(
+            share_core(&repos, GUILD, MEMBER, other)
+                .await
+                .unwrap()
+                .unwrap_err(),
+            Refusal::RoleShareLimit(1)
+        );
+        // One shared role per member
+        assert_eq!(
+            share_core(&repos, GUILD, second_owner, friend)
+                .await
+                .unwrap()
+                .unwrap_err(),
+            Refusal::MemberShareLimit {
+                user_id: friend,
+                max: 1
+            }
+        );
+
+        repos
+            .guild_settings
+            .set_sharing_limits(GUILD, 5, 5, ADMIN)
+            .await
+            .unwrap();
+        assert_eq!(
+            share_core(&repos, GUILD, MEMBER, friend)
+                .await
+                .unwrap()
+                .unwrap_err(),
+            Refusal::AlreadyShared(friend)
+        );
+        assert!(share_core(&repos, GUILD, MEMBER, other)
+            .await
+            .unwrap()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_share_target_refusals_are_translated() {
+        for locale in [Locale::EnUs, Locale::EsEs] {
+            for refusal in [
+                Refusal::ShareWithBot,
+                Refusal::NotInGuild(MEMBER),
+                Refusal::RoleMissing,
+            ] {
+                let embed = serde_json::to_value(refusal.embed(locale)).unwrap();
+                let text = format!("{} {}", embed["title"], embed["description"]);
+                assert!(!text.contains("booster."), "{:?}: {}", refusal, text);
+            }
+        }
+        let embed = serde_json::to_value(Refusal::NotInGuild(MEMBER).embed(Locale::EnUs)).unwrap();
+        assert!(embed["description"].as_str().unwrap().contains("<@20>"));
+    }
+}
@@ -1,9 +1,15 @@
-use crate::data::models::BoosterRole;
+use crate::data::models::{BulkOperation, CleanupRun};
+use crate::handlers::cleanup::{
+    CleanupHandler, CleanupOutcome, CleanupScan, CLEANUP_OPERATION, CLEANUP_ROLES_OPERATION,
+    PROGRESS_EVERY,
+};
+use crate::utils::audit::{booster_audit_embed, send_booster_audit};
 use crate::utils::embed_builder::EmbedBuilder;
+use crate::utils::progress::ProgressReporter;
 use crate::bot::{Context, Error};
-use poise::serenity_prelude::{self as serenity, CreateEmbed, RoleId};
-use std::collections::HashSet;
+use poise::serenity_prelude as serenity;
 
+/// Delete booster roles whose owners stopped boosting or left (Admin only)
 #[poise::command(
     slash_command,
     guild_only,
@@ -13,57 +19,42 @@ use std::collections::HashSet;
 pub async fn cleanup(
     ctx: Context<'_>,
     #[description = "Preview changes without deleting (dry run)"] dry_run: Option<bool>,
+    #[description = "Also delete the Discord roles, not just the records (default: false)"]
+    delete_roles: Option<bool>,
 ) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or(Error::Command("This command can only be used in a guild".to_string()))?;
     let dry_run = dry_run.unwrap_or(false);
+    let delete_roles = delete_roles.unwrap_or(false);
 
     tracing::info!(
         guild_id = %guild_id,
         admin_id = %ctx.author().id,
         dry_run = dry_run,
+        delete_roles = delete_roles,
         "Boosterrole cleanup initiated"
     );
 
     ctx.defer().await?;
 
-    let guild = guild_id.to_partial_guild(&ctx.serenity_context().http).await?;
-    let members = guild.members(&ctx.serenity_context().http, None, None).await?;
+    let http = &ctx.serenity_context().http;
+    let pool = &ctx.data().db_pool;
+    let mut progress = ProgressReporter::new(ctx);
 
-    let booster_member_ids: HashSet<u64> = members
-        .iter()
-        .filter(|m| m.premium_since.is_some())
-        .map(|m| m.user.id.get())
-        .collect();
+    let last_run = CleanupRun::describe(CleanupRun::latest(pool, guild_id).await?.as_ref());
 
-    let all_roles = BoosterRole::get_all_for_guild(&ctx.data().db_pool, guild_id).await?;
-
-    let mut orphaned_roles = Vec::new();
-    let mut stats = CleanupStats::default();
-
-    for role_record in &all_roles {
-        let user_id = serenity::UserId::new(role_record.user_id as u64);
-        let role_id = RoleId::new(role_record.role_id as u64);
-
-        let is_orphaned = if !booster_member_ids.contains(&user_id.get()) {
-            stats.no_boost_count += 1;
-            true
-        } else if !guild.roles.contains_key(&role_id) {
-            stats.role_deleted_count += 1;
-            true
-        } else {
-            let member = guild.member(&ctx.serenity_context().http, user_id).await;
-            if member.is_err() {
-                stats.member_left_count += 1;
-                true
-            } else {
-                false
-            }
-        };
-
-        if is_orphaned {
-            orphaned_roles.push((user_id, role_id, role_record.role_name.clone()));
+    let scan = match CleanupHandler::scan(http, pool, guild_id, Some(&mut progress)).await? {
+        Ok(scan) => scan,
+        Err(incomplete) => {
+            progress.update(incomplete.embed()).await?;
+            return Ok(());
         }
-    }
+    };
+    let existing_roles = scan.existing_roles();
+    let CleanupScan {
+        guild,
+        orphaned: orphaned_roles,
+        stats,
+    } = scan;
 
     tracing::debug!(
         orphaned_count = orphaned_roles.len(),
@@ -71,13 +62,7 @@ pub async fn cleanup(
     );
 
     if orphaned_roles.is_empty() {
-        let embed = EmbedBuilder::success(
-            "✨ No Cleanup Needed",
-            "All booster roles are properly assigned. No orphaned roles found.",
-        );
-
-        ctx.send(poise::CreateReply::default().embed(CreateEmbed::from(embed)))
-            .await?;
+        progress.update(nothing_to_clean_embed(&last_run)).await?;
         return Ok(());
     }
 
@@ -103,80 +88,94 @@ pub async fn cleanup(
             ),
         )
         .field("Orphaned Roles", &format!("{}{}", role_list, more_text), false)
+        .field("Breakdown", stats.describe(), false)
         .field(
-            "Breakdown",
-            &format!(
-                "• No longer boosting: {}\n• Role deleted: {}\n• Member left: {}",
-                stats.no_boost_count, stats.role_deleted_count, stats.member_left_count
-            ),
+            "Discord Roles",
+            if delete_roles {
+                format!("**{}** role(s) still in Discord would be deleted.", existing_roles)
+            } else {
+                format!(
+                    "**{}** role(s) still in Discord would be kept. Set `delete_roles` to delete them too.",
+                    existing_roles
+                )
+            },
             false,
         )
+        .field("Automatic Cleanup", &last_run, false)
         .footer(serenity::CreateEmbedFooter::new("Run without dry_run to actually remove these roles"));
 
-        ctx.send(poise::CreateReply::default().embed(CreateEmbed::from(embed)))
-            .await?;
+        progress.update(embed).await?;
     } else {
-        let mut removed_count = 0;
-        let mut failed_count = 0;
-
-        for (user_id, role_id, _) in &orphaned_roles {
-            if guild.roles.contains_key(role_id) {
-                if let Err(e) = guild_id
-                    .delete_role(&ctx.serenity_context().http, *role_id)
-                    .await
-                {
-                    tracing::error!(
-                        "Failed to delete role {} in guild {}: {}",
-                        role_id,
-                        guild_id,
-                        e
-                    );
-                    failed_count += 1;
-                } else {
-                    removed_count += 1;
-                }
-            }
+        // Recorded so the outcome is still posted if the bot restarts mid-cleanup
+        let operation_id = BulkOperation::start(
+            pool,
+            guild_id,
+            ctx.channel_id(),
+            if delete_roles {
+                CLEANUP_ROLES_OPERATION
+            } else {
+                CLEANUP_OPERATION
+            },
+            ctx.author().id,
+        )
+        .await?;
 
-            if let Err(e) = BoosterRole::delete(&ctx.data().db_pool, guild_id, *user_id).await {
-                tracing::error!(
-                    "Failed to delete database record for user {} in guild {}: {}",
-                    user_id,
-                    guild_id,
-                    e
-                );
+        let total = orphaned_roles.len();
+        let mut outcome = CleanupOutcome::default();
+
+        progress
+            .update(CleanupHandler::progress_embed(0, total))
+            .await?;
+        for (done, (user_id, role_id, _)) in orphaned_roles.iter().enumerate() {
+            outcome.add(
+                CleanupHandler::remove(
+                    ctx.serenity_context(),
+                    pool,
+                    &guild,
+                    *user_id,
+                    *role_id,
+                    delete_roles,
+                )
+                .await,
+            );
+
+            if (done + 1).is_multiple_of(PROGRESS_EVERY) && done + 1 < total {
+                progress
+                    .update(CleanupHandler::progress_embed(done + 1, total))
+                    .await?;
             }
         }
 
-        let embed = if failed_count > 0 {
-            EmbedBuilder::warning(
-                "⚠️ Cleanup Partially Complete",
-                &format!(
-                    "Removed **{}** orphaned role(s), but **{}** failed to delete.",
-                    removed_count, failed_count
-                ),
+        BulkOperation::finish(pool, operation_id).await?;
+        send_booster_audit(
+            http,
+            pool,
+            guild_id,
+            booster_audit_embed(
+                "🧹 Orphaned Booster Roles Cleaned Up",
+                ctx.author().id,
+                format!("{} orphaned role(s)", total),
             )
-        } else {
-            EmbedBuilder::success(
-                "✅ Cleanup Complete",
-                &format!("Successfully removed **{}** orphaned role(s).", removed_count),
+            .field("Records Removed", outcome.records_removed.to_string(), true)
+            .field("Discord Roles Deleted", outcome.roles_deleted.to_string(), true)
+            .field("Failed", outcome.failed.to_string(), true),
+        )
+        .await;
+        progress
+            .update(
+                CleanupHandler::summary_embed(&outcome, &stats).field(
+                    "Automatic Cleanup",
+                    &last_run,
+                    false,
+                ),
             )
-        }
-        .field(
-            "Statistics",
-            &format!(
-                "• No longer boosting: {}\n• Role deleted: {}\n• Member left: {}",
-                stats.no_boost_count, stats.role_deleted_count, stats.member_left_count
-            ),
-            false,
-        );
-
-        ctx.send(poise::CreateReply::default().embed(CreateEmbed::from(embed)))
             .await?;
 
         tracing::info!(
             guild_id = %guild_id,
-            removed_count = removed_count,
-            failed_count = failed_count,
+            records_removed = outcome.records_removed,
+            roles_deleted = outcome.roles_deleted,
+            failed_count = outcome.failed,
             "Cleanup operation completed"
         );
     }
@@ -184,9 +183,11 @@ pub async fn cleanup(
     Ok(())
 }
 
-#[derive(Default)]
-struct CleanupStats {
-    no_boost_count: usize,
-    role_deleted_count: usize,
-    member_left_count: usize,
-}
\ No newline at end of file
+/// Reply when the scan found no orphaned roles
+pub(crate) fn nothing_to_clean_embed(last_run: &str) -> serenity::CreateEmbed {
+    EmbedBuilder::success(
+        "✨ No Cleanup Needed",
+        "All booster roles are properly assigned. No orphaned roles found.",
+    )
+    .field("Automatic Cleanup", last_run, false)
+}
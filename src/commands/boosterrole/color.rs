@@ -1,6 +1,8 @@
 use crate::bot::{Context, Error};
-use crate::data::models::{BoosterRole, GuildBoosterLimit, RoleNameBlacklist};
-use crate::utils::{ColorParser, EmbedBuilder, RoleManager};
+use crate::utils::audit::{before_after, booster_audit_embed, send_booster_audit};
+use crate::utils::i18n::{locale_for, translate};
+use crate::utils::role_icon::{guild_supports_role_icons, update_role_icon, IconSource};
+use crate::utils::{ColorParser, EmbedBuilder, ResponseHelper, RoleManager};
 use poise::serenity_prelude as serenity;
 use serenity::prelude::Mentionable;
 
@@ -24,6 +26,8 @@ pub async fn color(
     #[description = "Optional second color for future gradient features"] second_color: Option<
         String,
     >,
+    #[description = "Only show how the role would look, without creating or changing it"]
+    preview: Option<bool>,
 ) -> Result<(), Error> {
     let guild_id = ctx
         .guild_id()
@@ -40,102 +44,151 @@ pub async fn color(
         "Booster role color command invoked"
     );
 
-    // Defer response to give us more time to process
-    ctx.defer().await?;
+    if preview.unwrap_or(false) {
+        return show_preview(ctx, guild_id, name, color, second_color).await;
+    }
+    apply(ctx, guild_id, name, color, second_color, None).await
+}
+
+/// Run every check the real change would and show the result, without
+/// touching the database or the member's role
+async fn show_preview(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    name: String,
+    color: String,
+    second_color: Option<String>,
+) -> Result<(), Error> {
+    let user_id = ctx.author().id;
+    ctx.defer_ephemeral().await?;
 
-    // Get member object to check booster status
     let member = guild_id
         .member(&ctx.serenity_context().http, user_id)
         .await
         .map_err(|e| Error::Command(format!("Failed to get member information: {}", e)))?;
-
-    // Check if user is a booster
-    if !RoleManager::is_booster(&member) {
-        tracing::warn!(
-            user_id = %user_id,
-            guild_id = %guild_id,
-            "Non-booster attempted boosterrole color command"
-        );
-
-        let embed = EmbedBuilder::error(
-            "❌ Server Booster Required",
-            "This command is only available to server boosters! Please boost this server to use this feature."
-        );
-
-        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    if !super::ensure_eligible(ctx, guild_id, &member).await? {
         return Ok(());
     }
 
-    // Check if role name contains blacklisted words
-    if let Err(e) = RoleNameBlacklist::is_blacklisted(&ctx.data().db_pool, guild_id, &name).await {
-        tracing::error!(
-            error = ?e,
-            guild_id = %guild_id,
-            "Failed to check role name blacklist"
-        );
-    } else if RoleNameBlacklist::is_blacklisted(&ctx.data().db_pool, guild_id, &name)
-        .await
-        .unwrap_or(false)
+    let locale = locale_for(ctx).await;
+    let filters = ctx.data().name_filters(guild_id).await?;
+    let plan = match super::changes::preview_core(
+        &ctx.data().repos,
+        guild_id,
+        user_id,
+        &name,
+        &color,
+        second_color.as_deref(),
+        &filters,
+    )
+    .await?
     {
-        tracing::warn!(
-            user_id = %user_id,
-            guild_id = %guild_id,
-            role_name = %name,
-            "Attempted to use blacklisted word in role name"
-        );
+        Ok(plan) => plan,
+        Err(refusal) => {
+            // A blocked name in a preview doesn't alert staff; nothing was attempted
+            ResponseHelper::send_embed_with(ctx, refusal.embed(locale), true).await?;
+            return Ok(());
+        }
+    };
+    if !super::ensure_not_staff_lookalike(ctx, guild_id, &plan.name).await? {
+        return Ok(());
+    }
 
-        let embed = EmbedBuilder::error(
-            "❌ Inappropriate Role Name",
-            "The role name contains words that are not allowed. Please choose a different name.",
+    let mut embed = serenity::CreateEmbed::new()
+        .title(translate(locale, "booster.preview.title", &[]))
+        .description(translate(
+            locale,
+            "booster.preview.body",
+            &[
+                ("name", &plan.name),
+                ("color", &plan.primary_hex()),
+                ("color_name", &ColorParser::nearest_named(plan.primary)),
+            ],
+        ))
+        .color(plan.primary)
+        .footer(serenity::CreateEmbedFooter::new(translate(
+            locale,
+            "booster.preview.footer",
+            &[],
+        )));
+
+    if let Some(second_color_hex) = plan.secondary_hex() {
+        embed = embed.field(
+            translate(locale, "booster.second_color", &[]),
+            format!("`{}`", second_color_hex),
+            true,
         );
+    }
 
-        ctx.send(poise::CreateReply::default().embed(embed)).await?;
-        return Ok(());
+    if let Some(note) = plan.name_check.caution_note() {
+        embed = embed.field(translate(locale, "booster.caution", &[]), note, false);
     }
 
-    // Validate role name
-    if let Err(e) = RoleManager::validate_role_name(&name) {
-        let embed = EmbedBuilder::error("❌ Invalid Role Name", &format!("{}", e));
+    ctx.send(super::reply_with_gradient(embed, plan.primary, plan.secondary).ephemeral(true))
+        .await?;
+    Ok(())
+}
 
-        ctx.send(poise::CreateReply::default().embed(embed)).await?;
-        return Ok(());
-    }
 
-    // Parse primary color
-    let primary_color = match ColorParser::parse(&color) {
-        Ok(c) => c,
-        Err(e) => {
-            let embed = EmbedBuilder::error(
-                "❌ Invalid Color",
-                &format!("{}\n\nSupported formats:\n• Hex codes: `#FF0000`, `FF0000`, `0xFF0000`\n• Color names: `red`, `blue`, `green`, etc.\n• Short hex: `#F00` (expands to `#FF0000`)", e)
-            );
+/// Create or update the author's booster role with the given name and colors,
+/// and optionally an icon. Shared by `color` and `template use`.
+pub(crate) async fn apply(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    name: String,
+    color: String,
+    second_color: Option<String>,
+    icon: Option<String>,
+) -> Result<(), Error> {
+    let user_id = ctx.author().id;
 
-            ctx.send(poise::CreateReply::default().embed(embed)).await?;
-            return Ok(());
-        }
-    };
+    // Defer response to give us more time to process
+    ctx.defer().await?;
 
-    // Parse secondary color if provided
-    let secondary_color_parsed = if let Some(ref second_color) = second_color {
-        match ColorParser::parse(second_color) {
-            Ok(c) => Some(c),
-            Err(e) => {
-                let embed = EmbedBuilder::error("❌ Invalid Second Color", &format!("{}", e));
+    // Get member object to check eligibility
+    let member = guild_id
+        .member(&ctx.serenity_context().http, user_id)
+        .await
+        .map_err(|e| Error::Command(format!("Failed to get member information: {}", e)))?;
 
-                ctx.send(poise::CreateReply::default().embed(embed)).await?;
-                return Ok(());
-            }
+    if !super::ensure_eligible(ctx, guild_id, &member).await? {
+        return Ok(());
+    }
+    let Some(_in_flight) = super::begin_role_change(ctx, guild_id).await? else {
+        return Ok(());
+    };
+
+    let filters = ctx.data().name_filters(guild_id).await?;
+    let plan = match super::changes::color_core(
+        &ctx.data().repos,
+        guild_id,
+        user_id,
+        &name,
+        &color,
+        second_color.as_deref(),
+        &filters,
+        &ctx.command().qualified_name,
+    )
+    .await?
+    {
+        Ok(plan) => plan,
+        Err(refusal) => {
+            super::refuse(ctx, guild_id, &name, refusal).await?;
+            return Ok(());
         }
-    } else {
-        None
     };
+    let name = plan.name.clone();
+    if !super::ensure_not_staff_lookalike(ctx, guild_id, &name).await? {
+        return Ok(());
+    }
+    let primary_color = plan.primary;
 
-    // Check if user already has a booster role
-    let existing_role = BoosterRole::get(&ctx.data().db_pool, guild_id, user_id)
-        .await
-        .map_err(|e| Error::Database(e))?;
+    let previous = plan
+        .existing
+        .as_ref()
+        .map(|r| (r.role_name.clone(), r.primary_color.clone()));
 
-    let role = if let Some(existing) = existing_role {
+    let role = if let Some(existing) = &plan.existing {
         // Update existing role
         tracing::info!(
             user_id = %user_id,
@@ -146,6 +199,11 @@ pub async fn color(
 
         let role_id = serenity::RoleId::new(existing.role_id as u64);
 
+        let change = plan.requested_change();
+        if !super::lock::ensure_unlocked(ctx, guild_id, role_id, change).await? {
+            return Ok(());
+        }
+
         match RoleManager::update_booster_role(
             ctx.serenity_context(),
             guild_id,
@@ -175,27 +233,6 @@ pub async fn color(
             }
         }
     } else {
-        // Check limit before creating new role
-        let (can_create, limit) = GuildBoosterLimit::check_limit(&ctx.data().db_pool, guild_id)
-            .await
-            .map_err(|e| Error::Database(e))?;
-
-        if !can_create {
-            let limit_text = match limit {
-                Some(0) => "Role creation is currently disabled".to_string(),
-                Some(l) => format!("This server has reached the maximum limit of {} booster roles", l),
-                None => "Role creation limit exceeded".to_string(),
-            };
-
-            let embed = EmbedBuilder::error(
-                "❌ Role Limit Reached",
-                &format!("{}\n\nPlease contact an administrator.", limit_text),
-            );
-
-            ctx.send(poise::CreateReply::default().embed(embed)).await?;
-            return Ok(());
-        }
-
         // Create new role
         tracing::info!(
             user_id = %user_id,
@@ -267,19 +304,8 @@ pub async fn color(
     }
 
     // Update database
-    let secondary_color_str = secondary_color_parsed
-        .as_ref()
-        .map(|c| ColorParser::to_hex_string(*c));
-    if let Err(e) = BoosterRole::create(
-        &ctx.data().db_pool,
-        guild_id,
-        user_id,
-        role.id,
-        &name,
-        &ColorParser::to_hex_string(primary_color),
-        secondary_color_str.as_deref(),
-    )
-    .await
+    if let Err(e) =
+        super::changes::record_color(&ctx.data().repos, guild_id, user_id, role.id, &plan).await
     {
         tracing::error!(
             user_id = %user_id,
@@ -299,28 +325,73 @@ pub async fn color(
         );
     }
 
+    let icon_note = match &icon {
+        Some(icon) => Some(apply_icon(ctx, guild_id, role.id, icon).await),
+        None => None,
+    };
+
+    let new_color = ColorParser::to_hex_string(primary_color);
+    let audit = match &previous {
+        Some((old_name, old_color)) => {
+            booster_audit_embed("✏️ Booster Role Updated", user_id, role.mention().to_string())
+                .field("Name", before_after(old_name, &name), false)
+                .field("Color", before_after(old_color, &new_color), false)
+        }
+        None => {
+            booster_audit_embed("🆕 Booster Role Created", user_id, role.mention().to_string())
+                .field("Name", name.clone(), false)
+                .field("Color", new_color, false)
+        }
+    };
+    send_booster_audit(ctx.http(), &ctx.data().db_pool, guild_id, audit).await;
+
     // Create success response
+    let locale = locale_for(ctx).await;
     let mut embed = serenity::CreateEmbed::new()
-        .title("✅ Booster Role Created!")
-        .description(format!(
-            "Your custom role **{}** has been created and assigned!\n\nRole: {}\nColor: `{}`",
-            role.name,
-            role.mention(),
-            ColorParser::to_hex_string(primary_color)
+        .title(translate(locale, "booster.created.title", &[]))
+        .description(translate(
+            locale,
+            "booster.created.body",
+            &[
+                ("name", &role.name),
+                ("role", &role.mention()),
+                ("color", &ColorParser::to_hex_string(primary_color)),
+                ("color_name", &ColorParser::nearest_named(primary_color)),
+            ],
         ))
         .color(primary_color)
         .thumbnail(ctx.author().avatar_url().unwrap_or_default())
-        .footer(serenity::CreateEmbedFooter::new(format!(
-            "Created by {}",
-            ctx.author().name
+        .footer(serenity::CreateEmbedFooter::new(translate(
+            locale,
+            "booster.created.footer",
+            &[("user", &ctx.author().name)],
         )))
         .timestamp(serenity::Timestamp::now());
 
-    if let Some(second_color_hex) = secondary_color_str {
-        embed = embed.field("Second Color", format!("`{}`", second_color_hex), true);
+    if let Some(second_color_hex) = plan.secondary_hex() {
+        embed = embed.field(
+            translate(locale, "booster.second_color", &[]),
+            format!("`{}`", second_color_hex),
+            true,
+        );
+    }
+
+    if let Some(note) = icon_note {
+        embed = embed.field(translate(locale, "booster.icon", &[]), note, true);
     }
 
-    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    if let Some(note) = plan.name_check.caution_note() {
+        embed = embed.field(translate(locale, "booster.caution", &[]), note, false);
+    }
+
+    let embed = ResponseHelper::with_next_steps(ctx, embed).await;
+
+    ctx.send(super::reply_with_gradient(
+        embed,
+        primary_color,
+        plan.secondary,
+    ))
+    .await?;
 
     tracing::info!(
         user_id = %user_id,
@@ -332,3 +403,47 @@ pub async fn color(
 
     Ok(())
 }
+
+/// Set a stored icon on the role, returning a line for the success embed.
+/// Failures are reported there rather than undoing the name and color.
+async fn apply_icon(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    role_id: serenity::RoleId,
+    icon: &str,
+) -> String {
+    let source = match IconSource::parse(icon) {
+        Ok(source) => source,
+        Err(reason) => return format!("Skipped: {}", reason),
+    };
+
+    let supported = guild_id
+        .to_guild_cached(&ctx.serenity_context().cache)
+        .map(|guild| guild_supports_role_icons(&guild))
+        .unwrap_or(false);
+    if !supported {
+        return "Skipped: this server needs boost level 2 for role icons".to_string();
+    }
+
+    if let Err(e) = update_role_icon(ctx.http(), guild_id, role_id, &source).await {
+        tracing::warn!(
+            guild_id = %guild_id,
+            role_id = %role_id,
+            error = ?e,
+            "Failed to apply template icon"
+        );
+        return format!("Couldn't be applied: {}", e);
+    }
+
+    let stored = source.stored_value();
+    if let Err(e) = ctx
+        .data()
+        .repos
+        .booster_roles
+        .set_icon(guild_id, ctx.author().id, Some(&stored))
+        .await
+    {
+        tracing::error!(error = ?e, "Failed to record role icon source");
+    }
+    "Applied".to_string()
+}
@@ -1,6 +1,5 @@
 use crate::bot::{Context, Error};
-use crate::data::models::{BoosterRole, GuildBoosterLimit, RoleNameBlacklist};
-use crate::utils::{ColorParser, ResponseHelper, RoleManager};
+use crate::utils::{ResponseHelper, RoleManager};
 use poise::serenity_prelude as serenity;
 use serenity::prelude::Mentionable;
 use tracing::{error, info, instrument, warn};
@@ -43,29 +42,25 @@ pub async fn create(
 
     ctx.defer().await?;
 
-    // Check if user is a booster
+    // Same eligibility gate as every other customization command
     let member = guild_id
         .member(&ctx.serenity_context().http, user_id)
         .await
         .map_err(|e| Error::Command(format!("Failed to get member information: {}", e)))?;
 
-    if !RoleManager::is_booster(&member) {
-        warn!(
-            guild_id = %guild_id,
-            "Non-booster attempted boosterrole create command"
-        );
-
-        ResponseHelper::send_error(
-            ctx,
-            "❌ Server Booster Required",
-            "This command is only available to server boosters! Please boost this server to use this feature.",
-        )
-        .await?;
+    if !super::ensure_eligible(ctx, guild_id, &member).await? {
         return Ok(());
     }
 
+    let Some(_in_flight) = super::begin_role_change(ctx, guild_id).await? else {
+        return Ok(());
+    };
+
     // Check if user already has a booster role
-    let existing_role = BoosterRole::get(&data.db_pool, guild_id, user_id)
+    let existing_role = data
+        .repos
+        .booster_roles
+        .get(guild_id, user_id)
         .await
         .map_err(|e| Error::Database(e))?;
 
@@ -79,98 +74,36 @@ pub async fn create(
         return Ok(());
     }
 
-    // Check if role name contains blacklisted words
-    match RoleNameBlacklist::is_blacklisted(&data.db_pool, guild_id, &name).await {
-        Ok(true) => {
-            warn!(
-                guild_id = %guild_id,
-                role_name = %name,
-                "Attempted to use blacklisted word in role name"
-            );
-
-            ResponseHelper::send_error(
-                ctx,
-                "❌ Inappropriate Role Name",
-                "The role name contains words that are not allowed. Please choose a different name.",
-            )
-            .await?;
-            return Ok(());
-        }
-        Err(e) => {
-            error!(
-                error = ?e,
-                guild_id = %guild_id,
-                "Failed to check role name blacklist"
-            );
-        }
-        _ => {}
-    }
-
-    // Validate role name
-    if let Err(e) = RoleManager::validate_role_name(&name) {
-        ResponseHelper::send_error(ctx, "❌ Invalid Role Name", &format!("{}", e)).await?;
-        return Ok(());
-    }
-
-    // Parse primary color
-    let primary_color = match ColorParser::parse(&color) {
-        Ok(c) => c,
-        Err(e) => {
-            ResponseHelper::send_error(
-                ctx,
-                "❌ Invalid Color",
-                &format!(
-                    "{}\n\nSupported formats:\n• Hex codes: `#FF0000`, `FF0000`, `0xFF0000`\n• Color names: `red`, `blue`, `green`, etc.\n• Short hex: `#F00` (expands to `#FF0000`)",
-                    e
-                ),
-            )
-            .await?;
+    // Normalize the name and run the same checks `/boosterrole color` does:
+    // blacklists, Discord's naming rules, the guild's length cap and limit
+    let filters = data.name_filters(guild_id).await?;
+    let plan = match super::changes::color_core(
+        &data.repos,
+        guild_id,
+        user_id,
+        &name,
+        &color,
+        second_color.as_deref(),
+        &filters,
+        &ctx.command().qualified_name,
+    )
+    .await?
+    {
+        Ok(plan) => plan,
+        Err(refusal) => {
+            super::refuse(ctx, guild_id, &name, refusal).await?;
             return Ok(());
         }
     };
-
-    // Parse secondary color if provided
-    let secondary_color_parsed = if let Some(ref second_color) = second_color {
-        match ColorParser::parse(second_color) {
-            Ok(c) => Some(c),
-            Err(e) => {
-                ResponseHelper::send_error(ctx, "❌ Invalid Second Color", &format!("{}", e))
-                    .await?;
-                return Ok(());
-            }
-        }
-    } else {
-        None
-    };
-
-    // Check role limit before creating
-    let (can_create, limit) = GuildBoosterLimit::check_limit(&data.db_pool, guild_id)
-        .await
-        .map_err(|e| Error::Database(e))?;
-
-    if !can_create {
-        let limit_text = match limit {
-            Some(0) => "Role creation is currently disabled".to_string(),
-            Some(l) => format!(
-                "This server has reached the maximum limit of {} booster roles",
-                l
-            ),
-            None => "Role creation limit exceeded".to_string(),
-        };
-
-        ResponseHelper::send_error(
-            ctx,
-            "❌ Role Limit Reached",
-            &format!("{}\n\nPlease contact an administrator.", limit_text),
-        )
-        .await?;
+    if !super::ensure_not_staff_lookalike(ctx, guild_id, &plan.name).await? {
         return Ok(());
     }
+    let primary_color = plan.primary;
 
     // Create the role
     info!(
         guild_id = %guild_id,
-        role_name = %name,
+        role_name = %plan.name,
         color = %color,
         "Creating new booster role"
     );
@@ -179,7 +112,7 @@ pub async fn create(
         ctx.serenity_context(),
         guild_id,
         user_id,
-        &name,
+        &plan.name,
         primary_color,
         &data.db_pool,
     )
@@ -236,20 +169,8 @@ pub async fn create(
     }
 
     // Save to database
-    let secondary_color_str = secondary_color_parsed
-        .as_ref()
-        .map(|c| ColorParser::to_hex_string(*c));
-
-    if let Err(e) = BoosterRole::create(
-        &data.db_pool,
-        guild_id,
-        user_id,
-        role.id,
-        &name,
-        &ColorParser::to_hex_string(primary_color),
-        secondary_color_str.as_deref(),
-    )
-    .await
+    if let Err(e) =
+        super::changes::record_color(&data.repos, guild_id, user_id, role.id, &plan).await
     {
         error!(
             role_id = %role.id,
@@ -272,7 +193,7 @@ pub async fn create(
             "Your custom role **{}** has been created and assigned!\n\nRole: {}\nColor: `{}`",
             role.name,
             role.mention(),
-            ColorParser::to_hex_string(primary_color)
+            plan.primary_hex()
         ))
         .color(primary_color)
         .thumbnail(ctx.author().avatar_url().unwrap_or_default())
@@ -282,7 +203,7 @@ pub async fn create(
         )))
         .timestamp(serenity::Timestamp::now());
 
-    if let Some(second_color_hex) = secondary_color_str {
+    if let Some(second_color_hex) = plan.secondary_hex() {
         embed = embed.field("Second Color", format!("`{}`", second_color_hex), true);
     }
 
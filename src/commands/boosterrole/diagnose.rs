@@ -0,0 +1,95 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{
+    GuildAccountAgeSetting, GuildExemption, GuildNamingRules, RulesAcknowledgment,
+};
+use crate::utils::eligibility::{load_eligibility_config, EligibilityMode, ACCOUNT_AGE_EXEMPTION};
+use crate::utils::EmbedColor;
+use poise::serenity_prelude::{CreateEmbed, Timestamp};
+use tracing::instrument;
+
+/// Show the rules that decide who can customize a booster role (Staff only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    check = "super::staff_check",
+    category = "Booster Roles",
+    description_localized("en-US", "Show the eligibility rules applied to booster role commands")
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.diagnose"
+    )
+)]
+pub async fn diagnose(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or(Error::Command("Not in a guild".to_string()))?;
+    let pool = &ctx.data().db_pool;
+
+    let eligibility = load_eligibility_config(pool, guild_id).await?;
+    let age = GuildAccountAgeSetting::get(pool, guild_id).await?;
+    let exemptions = GuildExemption::list(pool, guild_id, ACCOUNT_AGE_EXEMPTION).await?;
+    let limit = ctx.data().repos.guild_settings.booster_limit(guild_id).await?;
+
+    let naming_rules = match GuildNamingRules::get(pool, guild_id).await? {
+        Some(rules) => {
+            let agreed = RulesAcknowledgment::count_current(pool, guild_id, rules.version).await?;
+            format!(
+                "Version {} • agreement {} • {} member(s) agreed to this version",
+                rules.version,
+                if rules.require_ack {
+                    "required"
+                } else {
+                    "not required"
+                },
+                agreed
+            )
+        }
+        None => "None written".to_string(),
+    };
+
+    let mode = match eligibility.effective_mode() {
+        EligibilityMode::BoostersOnly => "Boosters only",
+        EligibilityMode::PremiumRoleHolders => "Premium role holders",
+        EligibilityMode::Either => "Boosters or premium role holders",
+    };
+    let mut mode = mode.to_string();
+    if eligibility.mode != eligibility.effective_mode() {
+        mode.push_str(" (no premium role set, so only boosters qualify)");
+    }
+
+    let premium_role = eligibility
+        .premium_role_id
+        .map(|role| format!("<@&{}>", role))
+        .unwrap_or_else(|| "None configured".to_string());
+
+    let limit = match limit {
+        Some(max) if max > 0 => max.to_string(),
+        _ => "Unlimited".to_string(),
+    };
+
+    let embed = CreateEmbed::new()
+        .title("🩺 Booster Role Diagnostics")
+        .color(EmbedColor::Info.value())
+        .field("Eligibility", mode, false)
+        .field("Premium Role", premium_role, true)
+        .field("Role Limit", limit, true)
+        .field("Minimum Age", age.describe(), false)
+        .field("Naming Rules", naming_rules, false)
+        .field(
+            "Age Exemptions",
+            format!(
+                "{} member(s) • `/settings accountage view`",
+                exemptions.len()
+            ),
+            false,
+        )
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
@@ -1,19 +1,63 @@
 use crate::bot::{Context, Error};
-use crate::data::models::GuildBoosterBaseRole;
-use crate::utils::EmbedBuilder;
-use poise::serenity_prelude::{self as serenity, Colour, CreateEmbed, EditRole, Member};
+use crate::data::repos::Repos;
+use crate::utils::image_processor::{
+    self, fetch_image_capped, fetch_remote_image, parse_image_url, validate_image_attachment,
+    MAX_ATTACHMENT_BYTES,
+};
+use crate::utils::{ColorParser, EmbedBuilder, RequestedChange, ResponseHelper};
+use poise::serenity_prelude::{
+    self as serenity, Attachment, Colour, CreateEmbed, EditRole, GuildId, Member, UserId,
+};
 use tracing::{debug, error, info, warn};
 
+/// Where `/boosterrole dominant` takes its colors from
+pub(crate) enum ColorSource {
+    Avatar(String),
+    Attachment(Attachment),
+    Link(reqwest::Url),
+}
+
+impl ColorSource {
+    /// How the success embed refers to the image
+    fn label(&self) -> &'static str {
+        match self {
+            ColorSource::Avatar(_) => "your avatar",
+            ColorSource::Attachment(_) | ColorSource::Link(_) => "your image",
+        }
+    }
+
+    /// The image, shown as the success embed's thumbnail
+    fn thumbnail(&self) -> String {
+        match self {
+            ColorSource::Avatar(url) => url.clone(),
+            ColorSource::Attachment(attachment) => attachment.url.clone(),
+            ColorSource::Link(url) => url.to_string(),
+        }
+    }
+}
+
+/// Set your booster role color from the dominant colors of your avatar or any image
 #[poise::command(
     slash_command,
     prefix_command,
     guild_only,
-    category = "Booster",
-    description_localized("en-US", "Set your booster role color to your avatar's dominant color"),
+    category = "Booster Roles",
+    description_localized(
+        "en-US",
+        "Set your booster role color from your avatar, or from an image link or upload"
+    ),
     aliases("dom", "avatar", "auto"),
     broadcast_typing
 )]
-pub async fn dominant(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn dominant(
+    ctx: Context<'_>,
+    #[description = "Image link to take the colors from instead of your avatar"] url: Option<
+        String,
+    >,
+    #[description = "Image to take the colors from instead of your avatar"] image: Option<
+        Attachment,
+    >,
+) -> Result<(), Error> {
     let guild_id = ctx
         .guild_id()
         .ok_or(Error::Command("Not in a guild".to_string()))?;
@@ -28,66 +72,86 @@ pub async fn dominant(ctx: Context<'_>) -> Result<(), Error> {
         guild_id
     );
 
-    if !is_booster(&member) {
-        warn!(
-            "Non-booster {} attempted dominant color command",
-            ctx.author().id
-        );
-        let embed = EmbedBuilder::error(
-            "❌ Booster Required",
-            "This command is only available to server boosters.",
-        );
-        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
-            .await?;
+    if !super::ensure_eligible(ctx, guild_id, &member).await? {
         return Ok(());
     }
 
-    debug!("User {} confirmed as booster", ctx.author().id);
+    let source = match (url, image) {
+        (Some(_), Some(_)) => {
+            ResponseHelper::send_error_ephemeral(
+                ctx,
+                "❌ Too Many Images",
+                "Give either an image link or an upload, not both.",
+            )
+            .await?;
+            return Ok(());
+        }
+        (Some(url), None) => match parse_image_url(&url) {
+            Ok(url) => ColorSource::Link(url),
+            Err(e) => {
+                ResponseHelper::send_error_ephemeral(ctx, "❌ Invalid Link", &e.to_string())
+                    .await?;
+                return Ok(());
+            }
+        },
+        (None, Some(image)) => {
+            if let Err(e) = validate_image_attachment(
+                image.content_type.as_deref(),
+                &image.filename,
+                image.size,
+            ) {
+                ResponseHelper::send_error_ephemeral(ctx, "❌ Invalid Image", &e.to_string())
+                    .await?;
+                return Ok(());
+            }
+            ColorSource::Attachment(image)
+        }
+        (None, None) => {
+            let avatar_url = ctx.author().avatar_url().ok_or_else(|| {
+                Error::Command("You need to have an avatar set to use this command".to_string())
+            })?;
+            ColorSource::Avatar(avatar_url)
+        }
+    };
 
-    ctx.defer().await?;
+    let Some(_in_flight) = super::begin_role_change(ctx, guild_id).await? else {
+        return Ok(());
+    };
 
-    let avatar_url = ctx.author().avatar_url().ok_or_else(|| {
-        Error::Command("You need to have an avatar set to use this command".to_string())
-    })?;
+    debug!("User {} confirmed as eligible", ctx.author().id);
 
-    debug!(
-        "Fetching avatar for user {}: {}",
-        ctx.author().id,
-        avatar_url
-    );
+    ctx.defer().await?;
 
-    let (primary_color, secondary_color) = match extract_dual_colors(&avatar_url).await {
-        Ok(colors) => {
-            colors
-        }
+    let colors = match &source {
+        ColorSource::Avatar(avatar_url) => avatar_colors(ctx, avatar_url).await,
+        ColorSource::Attachment(image) => upload_colors(&image.url).await,
+        ColorSource::Link(url) => link_colors(url).await,
+    };
+    let (primary_color, secondary_color) = match colors {
+        Ok(colors) => colors,
         Err(e) => {
-            warn!(
-                "Dual color extraction failed for user {}: {}, falling back to single color",
+            error!(
+                "Color extraction failed for user {} from {}: {}",
                 ctx.author().id,
+                source.label(),
                 e
             );
-            match extract_dominant_color(&avatar_url).await {
-                Ok(color) => {
-                    (color, color)
-                }
-                Err(fallback_e) => {
-                    error!(
-                        "Avatar processing completely failed for user {}: {}",
-                        ctx.author().id,
-                        fallback_e
-                    );
-                    let embed = EmbedBuilder::error(
-                        "❌ Processing Failed",
-                        &format!("Failed to process your avatar: {}", fallback_e),
-                    );
-                    ctx.send(poise::CreateReply::default().embed(embed)).await?;
-                    return Ok(());
-                }
-            }
+            let embed = EmbedBuilder::error(
+                "❌ Processing Failed",
+                &format!("Failed to process {}: {}", source.label(), e),
+            );
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+            return Ok(());
         }
     };
 
-    let booster_role = find_or_create_booster_role(ctx, &member).await?;
+    let Some(booster_role) = find_or_create_booster_role(ctx, &member, primary_color).await? else {
+        return Ok(());
+    };
+
+    if !super::lock::ensure_unlocked(ctx, guild_id, booster_role, RequestedChange::color()).await? {
+        return Ok(());
+    }
 
     let color = Colour::from(primary_color);
 
@@ -103,8 +167,24 @@ pub async fn dominant(ctx: Context<'_>) -> Result<(), Error> {
                 secondary_color
             );
 
-            let embed = create_dual_color_success_embed(primary_color, secondary_color, color);
-            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+            store_colors(
+                &ctx.data().repos,
+                guild_id,
+                ctx.author().id,
+                primary_color,
+                secondary_color,
+            )
+            .await?;
+
+            let embed =
+                create_dual_color_success_embed(primary_color, secondary_color, color, &source);
+            let embed = ResponseHelper::with_next_steps(ctx, embed).await;
+            ctx.send(super::reply_with_gradient(
+                embed,
+                primary_color,
+                Some(secondary_color),
+            ))
+            .await?;
         }
         Err(e) => {
             error!("Failed to update role for user {}: {}", ctx.author().id, e);
@@ -119,33 +199,26 @@ pub async fn dominant(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
-fn is_booster(member: &Member) -> bool {
-    member.premium_since.is_some()
-}
-
-async fn find_or_create_booster_role(
+/// The member's booster role, creating one in `color` if the guild's limit allows.
+/// Returns `None` after telling the member the guild is full.
+pub(crate) async fn find_or_create_booster_role(
     ctx: Context<'_>,
     member: &Member,
-) -> Result<serenity::RoleId, Error> {
-    use sqlx::Row;
-
+    color: u32,
+) -> Result<Option<serenity::RoleId>, Error> {
     let guild_id = ctx
         .guild_id()
         .ok_or(Error::Command("Not in a guild".to_string()))?;
     let user_id = member.user.id;
 
-    let pool = &ctx.data().db_pool;
+    let repos = &ctx.data().repos;
 
-    let existing =
-        sqlx::query("SELECT role_id FROM booster_roles WHERE guild_id = ? AND user_id = ?")
-            .bind(guild_id.get() as i64)
-            .bind(user_id.get() as i64)
-            .fetch_optional(pool)
-            .await?;
+    if let Some(existing) = repos.booster_roles.get(guild_id, user_id).await? {
+        return Ok(Some(serenity::RoleId::new(existing.role_id as u64)));
+    }
 
-    if let Some(row) = existing {
-        let role_id: i64 = row.get("role_id");
-        return Ok(serenity::RoleId::new(role_id as u64));
+    if !super::ensure_under_limit(ctx, guild_id).await? {
+        return Ok(None);
     }
 
     let role_name = format!("{}'s Booster Role", member.user.name);
@@ -154,13 +227,14 @@ async fn find_or_create_booster_role(
             &ctx.http(),
             EditRole::new()
                 .name(&role_name)
+                .colour(Colour::new(color))
                 .mentionable(false)
                 .hoist(false),
         )
         .await?;
 
     // Position the role above base role if configured
-    if let Ok(Some(base_role_id)) = GuildBoosterBaseRole::get(pool, guild_id).await {
+    if let Ok(Some(base_role_id)) = repos.guild_settings.base_role(guild_id).await {
         let base_position = {
             let guild = guild_id
                 .to_guild_cached(&ctx.serenity_context().cache)
@@ -186,40 +260,103 @@ async fn find_or_create_booster_role(
         }
     }
 
-    sqlx::query("INSERT INTO booster_roles (guild_id, user_id, role_id) VALUES (?, ?, ?)")
-        .bind(guild_id.get() as i64)
-        .bind(user_id.get() as i64)
-        .bind(new_role.id.get() as i64)
-        .execute(pool)
+    repos
+        .booster_roles
+        .create(
+            guild_id,
+            user_id,
+            new_role.id,
+            &role_name,
+            &ColorParser::to_hex_string(color),
+            None,
+        )
         .await?;
 
     member.add_role(&ctx.http(), new_role.id).await?;
 
-    Ok(new_role.id)
+    Ok(Some(new_role.id))
 }
 
-async fn extract_dual_colors(avatar_url: &str) -> Result<(u32, u32), Error> {
-    use crate::utils::image_processor;
+/// Record the colors now on the member's role. Matching colors (the
+/// single-color fallback) are stored as a solid primary color.
+pub(crate) async fn store_colors(
+    repos: &Repos,
+    guild_id: GuildId,
+    user_id: UserId,
+    primary: u32,
+    secondary: u32,
+) -> Result<(), Error> {
+    let secondary = (secondary != primary).then(|| ColorParser::to_hex_string(secondary));
+    repos
+        .booster_roles
+        .update_color(
+            guild_id,
+            user_id,
+            &ColorParser::to_hex_string(primary),
+            secondary.as_deref(),
+        )
+        .await?;
+    Ok(())
+}
+
+/// The avatar's two strongest colors, or its single dominant color when
+/// the dual analysis fails
+async fn avatar_colors(ctx: Context<'_>, avatar_url: &str) -> Result<(u32, u32), Error> {
+    debug!(
+        "Fetching avatar for user {}: {}",
+        ctx.author().id,
+        avatar_url
+    );
+
+    match extract_dual_colors(avatar_url).await {
+        Ok(colors) => Ok(colors),
+        Err(e) => {
+            warn!(
+                "Dual color extraction failed for user {}: {}, falling back to single color",
+                ctx.author().id,
+                e
+            );
+            let color = extract_dominant_color(avatar_url).await?;
+            Ok((color, color))
+        }
+    }
+}
+
+/// Colors from an image uploaded to Discord's CDN
+async fn upload_colors(attachment_url: &str) -> Result<(u32, u32), Error> {
+    let image_data = fetch_image_capped(attachment_url, MAX_ATTACHMENT_BYTES).await?;
+    let colors = image_processor::extract_dual_colors_limited(image_data).await?;
+
+    Ok(colors)
+}
+
+/// Colors from a member's link, fetched with the checks for arbitrary hosts
+async fn link_colors(url: &reqwest::Url) -> Result<(u32, u32), Error> {
+    let image_data = fetch_remote_image(url, MAX_ATTACHMENT_BYTES).await?;
+    let colors = image_processor::extract_dual_colors_limited(image_data).await?;
+
+    Ok(colors)
+}
 
+async fn extract_dual_colors(avatar_url: &str) -> Result<(u32, u32), Error> {
     let image_data = image_processor::fetch_avatar(avatar_url).await?;
-    let colors = image_processor::extract_dual_colors(&image_data)?;
+    let colors = image_processor::extract_dual_colors_limited(image_data).await?;
 
     Ok(colors)
 }
 
 async fn extract_dominant_color(avatar_url: &str) -> Result<u32, Error> {
-    use crate::utils::image_processor;
-
     let image_data = image_processor::fetch_avatar(avatar_url).await?;
-    let color = image_processor::extract_dominant_color(&image_data)?;
+    let color = image_processor::extract_dominant_color_limited(image_data).await?;
 
     Ok(color)
 }
 
-fn create_dual_color_success_embed(
+pub(crate) fn create_dual_color_success_embed(
     primary: u32,
     secondary: u32,
     discord_color: Colour,
+    source: &ColorSource,
 ) -> CreateEmbed {
     let primary_r = (primary >> 16) & 0xFF;
     let primary_g = (primary >> 8) & 0xFF;
@@ -233,19 +370,20 @@ fn create_dual_color_success_embed(
 
     let description = if is_same_color {
         format!(
-            "Your booster role color has been set to your avatar's dominant color!\n\n\
+            "Your booster role color has been set to {}'s dominant color!\n\n\
             **🎨 Color:** #{:06X}\n\
             **RGB:** ({}, {}, {})",
-            primary, primary_r, primary_g, primary_b
+            source.label(), primary, primary_r, primary_g, primary_b
         )
     } else {
         format!(
-            "Your booster role color has been set based on your avatar's dual color analysis!\n\n\
+            "Your booster role color has been set based on {}'s dual color analysis!\n\n\
             **🎨 Primary Color** (Applied to Role)\n\
             **Hex:** #{:06X} | **RGB:** ({}, {}, {})\n\n\
             **🎨 Secondary Color** (Reference)\n\
             **Hex:** #{:06X} | **RGB:** ({}, {}, {})\n\n\
             💡 *Discord roles support only one color, so the primary color was applied to your role.*",
+            source.label(),
             primary, primary_r, primary_g, primary_b,
             secondary, secondary_r, secondary_g, secondary_b
         )
@@ -261,5 +399,47 @@ fn create_dual_color_success_embed(
         .title(title)
         .description(description)
         .color(discord_color)
-        .thumbnail("https://via.placeholder.com/150/".to_string() + &format!("{:06X}", primary))
+        .thumbnail(source.thumbnail())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::repos::memory::MemoryRepo;
+
+    #[tokio::test]
+    async fn test_dominant_run_leaves_a_complete_record() {
+        let (_, repos) = MemoryRepo::repos();
+        let (guild, user) = (GuildId::new(1), UserId::new(2));
+
+        // What find_or_create_booster_role stores for a new role
+        repos
+            .booster_roles
+            .create(
+                guild,
+                user,
+                serenity::RoleId::new(3),
+                "alice's Booster Role",
+                &ColorParser::to_hex_string(0x112233),
+                None,
+            )
+            .await
+            .unwrap();
+        store_colors(&repos, guild, user, 0x112233, 0x445566)
+            .await
+            .unwrap();
+
+        let role = repos.booster_roles.get(guild, user).await.unwrap().unwrap();
+        assert_eq!(role.role_name, "alice's Booster Role");
+        assert_eq!(role.primary_color, "#112233");
+        assert_eq!(role.secondary_color.as_deref(), Some("#445566"));
+
+        // A later single-color run clears the stale secondary color
+        store_colors(&repos, guild, user, 0xABCDEF, 0xABCDEF)
+            .await
+            .unwrap();
+        let role = repos.booster_roles.get(guild, user).await.unwrap().unwrap();
+        assert_eq!(role.primary_color, "#ABCDEF");
+        assert_eq!(role.secondary_color, None);
+    }
 }
@@ -0,0 +1,185 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{BoosterRole, BoosterRoleShare, SettingsAuditLog};
+use crate::handlers::cleanup::MemberPages;
+use crate::utils::role_export::{
+    build_csv, build_json, RoleExport, RoleExportRow, MAX_EXPORT_BYTES,
+};
+use crate::utils::EmbedBuilder;
+use poise::serenity_prelude::{CreateAttachment, CreateEmbed, GuildId, UserId};
+use std::collections::{HashMap, HashSet};
+use tracing::instrument;
+
+#[derive(Debug, Clone, Copy, Default, poise::ChoiceParameter)]
+pub enum RoleExportFormat {
+    #[default]
+    #[name = "csv"]
+    Csv,
+    #[name = "json"]
+    Json,
+}
+
+/// Download every booster role in the server as a spreadsheet or JSON file (Admin only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    category = "Booster Roles",
+    description_localized(
+        "en-US",
+        "Download every booster role in the server as a CSV or JSON file"
+    )
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.export"
+    )
+)]
+pub async fn export(
+    ctx: Context<'_>,
+    #[description = "File format (default csv)"] format: Option<RoleExportFormat>,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or(Error::Command("Not in a guild".to_string()))?;
+    let pool = &ctx.data().db_pool;
+
+    ctx.defer_ephemeral().await?;
+
+    let roles = ctx.data().repos.booster_roles.get_all_for_guild(guild_id).await?;
+    let mut share_counts: HashMap<i64, i64> = HashMap::new();
+    for share in BoosterRoleShare::get_active_for_guild(pool, guild_id).await? {
+        *share_counts.entry(share.role_id).or_default() += 1;
+    }
+    let names = member_names(ctx, guild_id, &roles).await;
+
+    let rows: Vec<RoleExportRow> = roles
+        .iter()
+        .map(|role| RoleExportRow {
+            user_id: role.user_id.to_string(),
+            username: names.get(&role.user_id).cloned().unwrap_or_default(),
+            role_id: role.role_id.to_string(),
+            role_name: role.role_name.clone(),
+            primary_color: role.primary_color.clone(),
+            secondary_color: role.secondary_color.clone(),
+            created_at: role
+                .created_at
+                .map(|at| at.format("%Y-%m-%d %H:%M:%S").to_string()),
+            shared_with_count: share_counts.get(&role.role_id).copied().unwrap_or(0),
+        })
+        .collect();
+
+    let format = format.unwrap_or_default();
+    let (export, extension) = match format {
+        RoleExportFormat::Csv => (build_csv(&rows, MAX_EXPORT_BYTES), "csv"),
+        RoleExportFormat::Json => (build_json(&rows, MAX_EXPORT_BYTES), "json"),
+    };
+    let filename = format!("booster_roles_{}.{}", guild_id, extension);
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "booster_roles_exported",
+        Some(&format!(
+            "Format: {}, rows: {}",
+            extension, export.rows_written
+        )),
+    )
+    .await?;
+
+    if export.rows_dropped > 0 {
+        tracing::warn!(
+            rows_dropped = export.rows_dropped,
+            "Booster role export truncated to fit the upload limit"
+        );
+    }
+    let unresolved = rows.iter().filter(|row| row.username.is_empty()).count();
+    let embed = export_embed(&export, unresolved);
+
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(embed)
+            .attachment(CreateAttachment::bytes(
+                export.contents.into_bytes(),
+                filename,
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Rows written, owners without a name, and a warning when rows were dropped
+pub(crate) fn export_embed(export: &RoleExport, unresolved: usize) -> CreateEmbed {
+    let mut description = format!("**{}** booster roles exported.", export.rows_written);
+    if unresolved > 0 {
+        description.push_str(&format!(
+            "\n{} owners couldn't be found in the server, so their username is blank.",
+            unresolved
+        ));
+    }
+
+    if export.rows_dropped > 0 {
+        EmbedBuilder::warning(
+            "Export Truncated",
+            format!(
+                "{}\n**{}** roles were left out to keep the file under Discord's 8MB limit.",
+                description, export.rows_dropped
+            ),
+        )
+    } else {
+        EmbedBuilder::info("📤 Booster Role Export", description)
+    }
+}
+
+/// Display names for role owners, from the cache when every owner is there and
+/// otherwise by paging through the member list. Owners who left are missing.
+async fn member_names(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    roles: &[BoosterRole],
+) -> HashMap<i64, String> {
+    let cached: Option<HashMap<i64, String>> = ctx.cache().guild(guild_id).and_then(|guild| {
+        roles
+            .iter()
+            .map(|role| {
+                guild
+                    .members
+                    .get(&UserId::new(role.user_id as u64))
+                    .map(|member| (role.user_id, member.display_name().to_string()))
+            })
+            .collect()
+    });
+    if let Some(names) = cached {
+        return names;
+    }
+
+    let owners: HashSet<i64> = roles.iter().map(|role| role.user_id).collect();
+    let mut names = HashMap::new();
+    let mut pages = MemberPages::new(guild_id);
+    loop {
+        let members = match pages.next(ctx.http()).await {
+            Ok(Some(members)) => members,
+            Ok(None) => break,
+            Err(e) => {
+                tracing::warn!(error = ?e, "Failed to fetch members for booster role export");
+                break;
+            }
+        };
+
+        for member in members
+            .iter()
+            .filter(|member| owners.contains(&(member.user.id.get() as i64)))
+        {
+            names.insert(
+                member.user.id.get() as i64,
+                member.display_name().to_string(),
+            );
+        }
+    }
+    names
+}
@@ -1,9 +1,12 @@
 use crate::bot::{Context, Error};
-use crate::data::models::RoleNameBlacklist;
-use crate::utils::{EmbedBuilder, EmbedColor};
+use crate::data::models::{GuildFilterEnforcement, RoleNameBlacklist, SettingsAuditLog};
+use crate::utils::name_severity::{compile_pattern, FilterAction, MatchType, Severity};
+use crate::utils::embed_builder::DescriptionBuilder;
+use crate::utils::paginator::paginate_embeds;
+use crate::utils::{EmbedBuilder, EmbedColor, ResponseHelper};
 use poise::serenity_prelude as serenity;
 
-/// Manage role name blacklist filters (Administrator only)
+/// Manage role name blacklist filters (Admin only)
 #[poise::command(
     slash_command,
     prefix_command,
@@ -13,16 +16,18 @@ use poise::serenity_prelude as serenity;
         "en-US",
         "Manage blacklisted words that cannot be used in booster role names"
     ),
-    subcommands("add", "remove", "list"),
+    subcommands("add", "remove", "list", "enforcement", "reload"),
     broadcast_typing
 )]
 pub async fn filter(ctx: Context<'_>) -> Result<(), Error> {
     let embed = EmbedBuilder::info(
         "🚫 Role Name Filter Commands",
         "**Available subcommands:**\n\n\
-        `/boosterrole filter add <word>` - Add word to blacklist\n\
+        `/boosterrole filter add <word> [severity] [match_type]` - Add word or pattern to blacklist\n\
         `/boosterrole filter remove <word>` - Remove word from blacklist\n\
-        `/boosterrole filter list` - View all blacklisted words",
+        `/boosterrole filter list` - View all blacklisted words\n\
+        `/boosterrole filter enforcement <severity> <action>` - Choose what each severity does\n\
+        `/boosterrole filter reload` - Reload the cached blacklist from the database",
     );
 
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
@@ -44,7 +49,13 @@ pub async fn filter(ctx: Context<'_>) -> Result<(), Error> {
 pub async fn add(
     ctx: Context<'_>,
     #[description = "The word to add to the blacklist"] word: String,
+    #[description = "low warns, medium rejects, high rejects and alerts staff (default medium)"]
+    severity: Option<Severity>,
+    #[description = "exact word, substring (default), wildcard with *, or regex"]
+    match_type: Option<MatchType>,
 ) -> Result<(), Error> {
+    let severity = severity.unwrap_or_default();
+    let match_type = match_type.unwrap_or_default();
     let guild_id = ctx
         .guild_id()
         .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
@@ -80,18 +91,46 @@ pub async fn add(
         return Ok(());
     }
 
+    if let Err(reason) = compile_pattern(word.trim(), match_type) {
+        let embed = EmbedBuilder::error("❌ Invalid Pattern", &reason);
+
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
     // Add word to blacklist
-    match RoleNameBlacklist::add_word(&ctx.data().db_pool, guild_id, &word.trim(), admin_id).await {
+    match RoleNameBlacklist::add_word(
+        &ctx.data().db_pool,
+        guild_id,
+        word.trim(),
+        severity,
+        match_type,
+        admin_id,
+    )
+    .await
+    {
         Ok(true) => {
+            ctx.data()
+                .content_filter(guild_id)
+                .await
+                .add_word_to_cache(word.trim(), severity, match_type)
+                .await;
+
             let embed = serenity::CreateEmbed::new()
                 .title("✅ Word Added to Blacklist")
-                .description(format!("boosterrole name blacklisted: **{}**", word.trim()))
+                .description(format!(
+                    "boosterrole name blacklisted: **{}** ({} severity, {} match)",
+                    word.trim(),
+                    severity.as_str(),
+                    match_type.as_str()
+                ))
                 .color(EmbedColor::Success.value())
                 .footer(serenity::CreateEmbedFooter::new(format!(
                     "Added by {}",
                     ctx.author().name
                 )))
                 .timestamp(serenity::Timestamp::now());
+            let embed = ResponseHelper::with_next_steps(ctx, embed).await;
 
             ctx.send(poise::CreateReply::default().embed(embed)).await?;
 
@@ -164,6 +203,12 @@ pub async fn remove(
     // Remove word from blacklist
     match RoleNameBlacklist::remove_word(&ctx.data().db_pool, guild_id, &word.trim()).await {
         Ok(true) => {
+            ctx.data()
+                .content_filter(guild_id)
+                .await
+                .remove_word_from_cache(word.trim())
+                .await;
+
             let embed = serenity::CreateEmbed::new()
                 .title("✅ Word Removed from Blacklist")
                 .description(format!(
@@ -176,6 +221,7 @@ pub async fn remove(
                     ctx.author().name
                 )))
                 .timestamp(serenity::Timestamp::now());
+            let embed = ResponseHelper::with_next_steps(ctx, embed).await;
 
             ctx.send(poise::CreateReply::default().embed(embed)).await?;
 
@@ -187,10 +233,24 @@ pub async fn remove(
             );
         }
         Ok(false) => {
-            let embed = EmbedBuilder::warning(
-                "⚠️ Word Not Found",
-                &format!("The word **{}** is not in the blacklist.", word.trim()),
-            );
+            let global = ctx.data().global_filter.entries().await?;
+            let embed = if global
+                .iter()
+                .any(|entry| entry.word == word.trim() || entry.word == word.trim().to_lowercase())
+            {
+                EmbedBuilder::warning(
+                    "🌐 Enforced Globally",
+                    format!(
+                        "**{}** is blocked in every server by the bot owners and can't be removed here.",
+                        word.trim()
+                    ),
+                )
+            } else {
+                EmbedBuilder::warning(
+                    "⚠️ Word Not Found",
+                    &format!("The word **{}** is not in the blacklist.", word.trim()),
+                )
+            };
 
             ctx.send(poise::CreateReply::default().embed(embed)).await?;
         }
@@ -241,7 +301,7 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
 
     // Get all blacklisted words
     let blacklisted_words =
-        match RoleNameBlacklist::get_all_for_guild(&ctx.data().db_pool, guild_id).await {
+        match RoleNameBlacklist::get_entries(&ctx.data().db_pool, guild_id).await {
             Ok(words) => words,
             Err(e) => {
                 tracing::error!(
@@ -261,7 +321,9 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
             }
         };
 
-    if blacklisted_words.is_empty() {
+    let global_words = ctx.data().global_filter.entries().await?;
+
+    if blacklisted_words.is_empty() && global_words.is_empty() {
         let embed = EmbedBuilder::primary(
             "📝 Role Name Blacklist",
             "No words are currently blacklisted for role names.\n\nUse `/boosterrole filter add <word>` to add words to the blacklist."
@@ -271,39 +333,84 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     }
 
-    // Create paginated response for large lists
     const WORDS_PER_PAGE: usize = 20;
-    let total_pages = (blacklisted_words.len() + WORDS_PER_PAGE - 1) / WORDS_PER_PAGE;
-    let current_page = 1; // For now, just show the first page
 
-    let start_idx = (current_page - 1) * WORDS_PER_PAGE;
-    let end_idx = std::cmp::min(start_idx + WORDS_PER_PAGE, blacklisted_words.len());
-    let page_words = &blacklisted_words[start_idx..end_idx];
+    // Shown on every page so it's clear why a word outside the list is blocked
+    let global_section = (!global_words.is_empty()).then(|| {
+        let mut section = String::from("Blocked in every server by the bot owners:\n");
+        for entry in &global_words {
+            let word = format!("`{}` ", entry.word);
+            if section.len() + word.len() > 1000 {
+                section.push('…');
+                break;
+            }
+            section.push_str(&word);
+        }
+        section
+    });
 
-    let word_list = page_words
+    let policy = GuildFilterEnforcement::get(&ctx.data().db_pool, guild_id).await?;
+    let enforcement = [Severity::Low, Severity::Medium, Severity::High]
         .iter()
-        .enumerate()
-        .map(|(i, word)| format!("{}. **{}**", start_idx + i + 1, word))
+        .map(|severity| {
+            format!(
+                "**{}**: {}",
+                severity.as_str(),
+                policy.action_for(*severity).describe()
+            )
+        })
         .collect::<Vec<_>>()
         .join("\n");
 
-    let embed = serenity::CreateEmbed::new()
-        .title("🚫 Role Name Blacklist")
-        .description(format!(
-            "**Blacklisted words ({} total):**\n\n{}\n\n*Role names containing these words will be rejected.*",
-            blacklisted_words.len(),
-            word_list
-        ))
-        .color(EmbedColor::Warning.value())
-        .footer(serenity::CreateEmbedFooter::new(format!(
-            "Page {} of {} • Requested by {}",
-            current_page,
-            total_pages,
-            ctx.author().name
-        )))
-        .timestamp(serenity::Timestamp::now());
+    // Every page repeats these fields, so the word list gets what's left
+    let reserved = 512
+        + enforcement.chars().count()
+        + global_section
+            .as_ref()
+            .map_or(0, |section| section.chars().count());
+
+    let builder = if blacklisted_words.is_empty() {
+        DescriptionBuilder::new().lines(["No words are blacklisted by this server."])
+    } else {
+        DescriptionBuilder::new()
+            .header(format!(
+                "**Blacklisted words ({} total):**",
+                blacklisted_words.len()
+            ))
+            .lines(blacklisted_words.iter().enumerate().map(|(i, entry)| {
+                format!(
+                    "{}. `{}` ({}, {})",
+                    i + 1,
+                    entry.word,
+                    entry.severity.as_str(),
+                    entry.match_type.as_str()
+                )
+            }))
+    };
+
+    let pages = builder
+        .max_lines(WORDS_PER_PAGE)
+        .reserve(reserved)
+        .build(|description, page, total_pages| {
+            let mut embed = serenity::CreateEmbed::new()
+                .title("🚫 Role Name Blacklist")
+                .description(description)
+                .field("Enforcement", enforcement.clone(), false);
+            if let Some(section) = &global_section {
+                embed = embed.field("Enforced globally", section.clone(), false);
+            }
+            embed
+                .color(EmbedColor::Warning.value())
+                .footer(serenity::CreateEmbedFooter::new(format!(
+                    "Page {} of {} • Requested by {}",
+                    page,
+                    total_pages,
+                    ctx.author().name
+                )))
+                .timestamp(serenity::Timestamp::now())
+        });
 
-    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    paginate_embeds(ctx, pages).await?;
 
     tracing::info!(
         admin_id = %admin_id,
@@ -314,3 +421,95 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
 
     Ok(())
 }
+
+/// Choose what happens when a role name matches a word of each severity
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Choose whether each blacklist severity warns, rejects, or rejects and alerts staff"
+    )
+)]
+pub async fn enforcement(
+    ctx: Context<'_>,
+    #[description = "Severity to configure"] severity: Severity,
+    #[description = "What happens when a name matches a word of this severity"]
+    action: FilterAction,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+    let pool = &ctx.data().db_pool;
+
+    GuildFilterEnforcement::set(pool, guild_id, severity, action, ctx.author().id).await?;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "filter_enforcement_set",
+        Some(&format!("{}: {}", severity.as_str(), action.as_str())),
+    )
+    .await?;
+
+    let note = match action {
+        FilterAction::Alert => {
+            "\n\nAlerts are posted in the support channel set with `/settings supportchannel`."
+        }
+        _ => "",
+    };
+    ResponseHelper::send_success(
+        ctx,
+        "🚫 Filter Enforcement Updated",
+        &format!(
+            "Names matching **{}** severity words will now **{}**.{}",
+            severity.as_str(),
+            action.describe(),
+            note
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Reload the cached role name blacklist from the database
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Reload this server's cached role name blacklist from the database"
+    )
+)]
+pub async fn reload(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+
+    let filter = ctx.data().content_filter(guild_id).await;
+    filter.refresh_cache().await?;
+    let stats = filter.get_cache_stats().await;
+
+    tracing::info!(
+        admin_id = %ctx.author().id,
+        guild_id = %guild_id,
+        word_count = stats.word_count,
+        "Role name filter cache reloaded"
+    );
+
+    ResponseHelper::send_success(
+        ctx,
+        "🔄 Filter Reloaded",
+        &format!(
+            "Loaded **{}** blacklisted word(s) for role name checks.",
+            stats.word_count
+        ),
+    )
+    .await?;
+    Ok(())
+}
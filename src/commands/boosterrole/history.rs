@@ -0,0 +1,113 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{compile_user_history, SettingsAuditLog};
+use crate::utils::args::UserArg;
+use crate::utils::history_export::UserHistoryReport;
+use crate::utils::EmbedBuilder;
+use poise::serenity_prelude::{CreateAttachment, CreateEmbed, UserId};
+use tracing::instrument;
+
+#[derive(Debug, Clone, Copy, Default, poise::ChoiceParameter)]
+pub enum ExportFormat {
+    #[default]
+    #[name = "json"]
+    Json,
+    #[name = "text"]
+    Text,
+}
+
+/// Moderation evidence for a member's booster role customization
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    category = "Booster Roles",
+    subcommands("history_export"),
+    description_localized("en-US", "Export a member's booster role history")
+)]
+pub async fn history(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Export a member's renames, shares, locks, rejected names and audit entries (Staff only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    check = "super::staff_check",
+    rename = "export",
+    category = "Booster Roles",
+    description_localized(
+        "en-US",
+        "Export a member's booster role history as a file for moderation evidence"
+    )
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.history.export"
+    )
+)]
+pub async fn history_export(
+    ctx: Context<'_>,
+    #[description = "Member to export history for"] user: UserArg,
+    #[description = "File format (default json)"] format: Option<ExportFormat>,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or(Error::Command("Not in a guild".to_string()))?;
+    let pool = &ctx.data().db_pool;
+
+    ctx.defer_ephemeral().await?;
+
+    let report = compile_user_history(pool, guild_id, user.id).await?;
+    let (contents, extension) = match format.unwrap_or_default() {
+        ExportFormat::Json => (report.to_json(), "json"),
+        ExportFormat::Text => (report.to_text(), "txt"),
+    };
+    let filename = format!("history_{}_{}.{}", guild_id, user.id, extension);
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "history_exported",
+        Some(&format!("User: <@{}>", user.id)),
+    )
+    .await?;
+
+    let embed = export_embed(&report, user.id);
+
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(embed)
+            .attachment(CreateAttachment::bytes(contents.into_bytes(), filename))
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Entry counts per source for an exported history
+pub(crate) fn export_embed(report: &UserHistoryReport, user_id: UserId) -> CreateEmbed {
+    let summary = if report.entries.is_empty() {
+        "Nothing has been recorded for this member.".to_string()
+    } else {
+        report
+            .counts_by_source()
+            .iter()
+            .map(|(source, count)| format!("`{}`: {}", source, count))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    EmbedBuilder::info(
+        "📜 History Export",
+        format!(
+            "**{}** entries for <@{}>.\n{}",
+            report.entries.len(),
+            user_id,
+            summary
+        ),
+    )
+}
@@ -0,0 +1,228 @@
+use crate::bot::{Context, Error};
+use crate::utils::image_processor::{
+    extract_dual_colors_limited, fetch_image_capped, validate_image_attachment,
+    MAX_ATTACHMENT_BYTES,
+};
+use crate::utils::{EmbedBuilder, RequestedChange, ResponseHelper};
+use poise::serenity_prelude::{
+    Attachment, ButtonStyle, Colour, ComponentInteractionCollector, CreateActionRow, CreateButton,
+    CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage, EditRole,
+};
+use poise::CreateReply;
+use std::time::Duration;
+use tracing::instrument;
+
+/// How long the primary/secondary buttons stay usable
+const CHOICE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Set your role color from an uploaded image
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "imagecolor",
+    category = "Booster Roles",
+    description_localized("en-US", "Pick your booster role color from an image you upload"),
+    aliases("imgcolor", "artcolor"),
+    broadcast_typing
+)]
+#[instrument(
+    skip(ctx, image),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.imagecolor"
+    )
+)]
+pub async fn imagecolor(
+    ctx: Context<'_>,
+    #[description = "Image to take the color from (PNG, JPEG, GIF, or WebP)"] image: Attachment,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or(Error::Command("Not in a guild".to_string()))?;
+    let member = ctx
+        .author_member()
+        .await
+        .ok_or(Error::Command("Not in a guild".to_string()))?
+        .into_owned();
+
+    if !super::ensure_eligible(ctx, guild_id, &member).await? {
+        return Ok(());
+    }
+
+    let Some(_in_flight) = super::begin_role_change(ctx, guild_id).await? else {
+        return Ok(());
+    };
+
+    if let Err(e) =
+        validate_image_attachment(image.content_type.as_deref(), &image.filename, image.size)
+    {
+        ResponseHelper::send_error(ctx, "❌ Invalid Image", &e.to_string()).await?;
+        return Ok(());
+    }
+
+    ctx.defer().await?;
+
+    let colors = match fetch_image_capped(&image.url, MAX_ATTACHMENT_BYTES).await {
+        Ok(data) => extract_dual_colors_limited(data).await,
+        Err(e) => Err(e),
+    };
+    let (primary, secondary) = match colors {
+        Ok(colors) => colors,
+        Err(e) => {
+            tracing::warn!(
+                filename = %image.filename,
+                error = %e,
+                "Image color extraction failed"
+            );
+            ResponseHelper::send_error(
+                ctx,
+                "❌ Processing Failed",
+                &format!("Failed to process your image: {}", e),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let Some(role_id) =
+        super::dominant::find_or_create_booster_role(ctx, &member, primary).await?
+    else {
+        return Ok(());
+    };
+
+    if !super::lock::ensure_unlocked(ctx, guild_id, role_id, RequestedChange::color()).await? {
+        return Ok(());
+    }
+
+    let chosen = if primary == secondary {
+        Some(primary)
+    } else {
+        choose_color(ctx, primary, secondary).await?
+    };
+
+    let Some(color) = chosen else {
+        return Ok(());
+    };
+
+    guild_id
+        .edit_role(
+            ctx.http(),
+            role_id,
+            EditRole::new().colour(Colour::new(color)),
+        )
+        .await?;
+    super::dominant::store_colors(&ctx.data().repos, guild_id, ctx.author().id, color, color)
+        .await?;
+
+    tracing::info!(
+        role_id = %role_id,
+        color = %format!("#{:06X}", color),
+        "Booster role color set from uploaded image"
+    );
+
+    let embed = EmbedBuilder::success(
+        "✅ Color Updated",
+        format!(
+            "Your booster role color is now **#{:06X}**, taken from `{}`.",
+            color, image.filename
+        ),
+    )
+    .color(Colour::new(color));
+    let embed = ResponseHelper::with_next_steps(ctx, embed).await;
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// The two colors found in the image, offered as a choice
+pub(crate) fn pick_color_embed(primary: u32, secondary: u32) -> CreateEmbed {
+    CreateEmbed::new()
+        .title("🎨 Pick a Color")
+        .description(format!(
+            "Two colors stand out in your image:\n\n\
+            **Primary:** #{:06X}\n\
+            **Secondary:** #{:06X}\n\n\
+            Choose one for your booster role.",
+            primary, secondary
+        ))
+        .color(Colour::new(primary))
+}
+
+/// Offer the primary and secondary colors as buttons. Returns `None` when
+/// the choice times out.
+async fn choose_color(
+    ctx: Context<'_>,
+    primary: u32,
+    secondary: u32,
+) -> Result<Option<u32>, Error> {
+    let primary_id = format!("imagecolor:{}:primary", ctx.id());
+    let secondary_id = format!("imagecolor:{}:secondary", ctx.id());
+
+    let prompt = pick_color_embed(primary, secondary);
+    let buttons = |disabled: bool| {
+        vec![CreateActionRow::Buttons(vec![
+            CreateButton::new(primary_id.clone())
+                .label(format!("Primary #{:06X}", primary))
+                .style(ButtonStyle::Primary)
+                .disabled(disabled),
+            CreateButton::new(secondary_id.clone())
+                .label(format!("Secondary #{:06X}", secondary))
+                .style(ButtonStyle::Secondary)
+                .disabled(disabled),
+        ])]
+    };
+
+    let reply = ctx
+        .send(
+            CreateReply::default()
+                .embed(prompt.clone())
+                .components(buttons(false)),
+        )
+        .await?;
+
+    let author_id = ctx.author().id;
+    let (filter_primary, filter_secondary) = (primary_id.clone(), secondary_id.clone());
+    let interaction = ComponentInteractionCollector::new(ctx)
+        .author_id(author_id)
+        .channel_id(ctx.channel_id())
+        .filter(move |i| i.data.custom_id == filter_primary || i.data.custom_id == filter_secondary)
+        .timeout(CHOICE_TIMEOUT)
+        .await;
+
+    let Some(interaction) = interaction else {
+        reply
+            .edit(
+                ctx,
+                CreateReply::default()
+                    .embed(
+                        prompt.footer(poise::serenity_prelude::CreateEmbedFooter::new(
+                            "No color chosen — your role was not changed",
+                        )),
+                    )
+                    .components(buttons(true)),
+            )
+            .await?;
+        return Ok(None);
+    };
+
+    let color = if interaction.data.custom_id == primary_id {
+        primary
+    } else {
+        secondary
+    };
+
+    interaction
+        .create_response(
+            ctx,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .embed(prompt.description(format!("Applying **#{:06X}**…", color)))
+                    .components(vec![]),
+            ),
+        )
+        .await?;
+
+    Ok(Some(color))
+}
@@ -1,8 +1,11 @@
-use crate::data::models::{BoosterRole, GuildBoosterLimit};
+use crate::data::models::GuildBoosterOptions;
 use crate::utils::embed_builder::EmbedBuilder;
+use crate::utils::role_name::MAX_ROLE_NAME_CHARS;
+use crate::utils::ResponseHelper;
 use crate::bot::{Context, Error};
 use poise::serenity_prelude::{CreateEmbed, Mentionable};
 
+/// Set how many booster roles this server allows and what each may use (Admin only)
 #[poise::command(
     slash_command,
     guild_only,
@@ -15,85 +18,147 @@ pub async fn limit(
     #[min = 0]
     #[max = 100]
     max_roles: Option<i32>,
+    #[description = "Longest booster role name, in characters (0 = Discord's limit)"]
+    #[min = 0]
+    #[max = 100]
+    max_name_length: Option<i64>,
+    #[description = "Whether boosters may give their role a second color"]
+    allow_secondary_color: Option<bool>,
 ) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or(Error::Command("This command can only be used in a guild".to_string()))?;
+    let repos = &ctx.data().repos;
 
     tracing::info!(
         guild_id = %guild_id,
         admin_id = %ctx.author().id,
         max_roles = ?max_roles,
+        max_name_length = ?max_name_length,
+        allow_secondary_color = ?allow_secondary_color,
         "Boosterrole limit command invoked"
     );
 
+    if max_roles.is_none() && max_name_length.is_none() && allow_secondary_color.is_none() {
+        let current_limit = repos.guild_settings.booster_limit(guild_id).await?;
+        let current_count = repos.booster_roles.count_for_guild(guild_id).await? as usize;
+        let options = repos.guild_settings.booster_options(guild_id).await?;
+
+        let embed = usage_embed(current_count, current_limit, &options);
+        ctx.send(poise::CreateReply::default().embed(embed))
+            .await?;
+        return Ok(());
+    }
+
+    let mut changes = Vec::new();
+    let mut warnings = Vec::new();
+
     if let Some(limit) = max_roles {
-        GuildBoosterLimit::set(&ctx.data().db_pool, guild_id, limit, ctx.author().id).await?;
+        repos
+            .guild_settings
+            .set_booster_limit(guild_id, limit, ctx.author().id)
+            .await?;
 
-        let current_count = BoosterRole::get_all_for_guild(&ctx.data().db_pool, guild_id)
-            .await?
-            .len();
+        let current_count = repos.booster_roles.count_for_guild(guild_id).await? as usize;
 
-        let description = if limit == 0 {
-            "Booster roles are now **unlimited** for this server.".to_string()
+        if limit == 0 {
+            changes.push("Booster roles are now **unlimited** for this server.".to_string());
         } else {
-            format!(
-                "Maximum booster roles set to **{}**.\nCurrent booster roles: **{}**",
-                limit, current_count
-            )
-        };
-
-        let mut embed = EmbedBuilder::success("✅ Limit Updated", &description);
+            changes.push(format!(
+                "Maximum booster roles set to **{}**.\nRoles used: **{}/{}**",
+                limit, current_count, limit
+            ));
+        }
 
         if limit > 0 && current_count > limit as usize {
-            embed = embed.field(
-                "⚠️ Warning",
-                format!(
-                    "There are currently {} booster roles, which exceeds the new limit of {}.\nExisting roles will remain, but no new roles can be created until below the limit.",
-                    current_count, limit
-                ),
-                false,
-            );
+            warnings.push(format!(
+                "There are currently {} booster roles, which exceeds the new limit of {}.\nExisting roles will remain, but no new roles can be created until below the limit.",
+                current_count, limit
+            ));
         }
+    }
 
-        embed = embed.footer(poise::serenity_prelude::CreateEmbedFooter::new(format!("Set by {}", ctx.author().mention())));
-
-        ctx.send(poise::CreateReply::default().embed(CreateEmbed::from(embed)))
+    if max_name_length.is_some() || allow_secondary_color.is_some() {
+        let mut options = repos.guild_settings.booster_options(guild_id).await?;
+        if let Some(length) = max_name_length {
+            options.max_name_length = (length > 0).then_some(length);
+            changes.push(format!("Name length: **{}**", describe_name_length(&options)));
+        }
+        if let Some(allow) = allow_secondary_color {
+            options.allow_secondary_color = allow;
+            changes.push(format!(
+                "Second color: **{}**",
+                if allow { "allowed" } else { "not allowed" }
+            ));
+            if !allow {
+                warnings.push(
+                    "Roles that already have a second color keep it until their owner changes colors."
+                        .to_string(),
+                );
+            }
+        }
+        repos
+            .guild_settings
+            .set_booster_options(guild_id, options, ctx.author().id)
             .await?;
-    } else {
-        let current_limit = GuildBoosterLimit::get(&ctx.data().db_pool, guild_id).await?;
-        let current_count = BoosterRole::get_all_for_guild(&ctx.data().db_pool, guild_id)
-            .await?
-            .len();
-
-        let (description, status_text) = match current_limit {
-            Some(0) | None => (
-                format!(
-                    "Booster roles are currently **unlimited**.\nActive booster roles: **{}**",
-                    current_count
-                ),
-                "No limit set".to_string(),
-            ),
-            Some(limit) => (
-                format!(
-                    "Maximum booster roles: **{}**\nActive booster roles: **{}**\nAvailable slots: **{}**",
-                    limit,
-                    current_count,
-                    if current_count >= limit as usize {
-                        0
-                    } else {
-                        limit as usize - current_count
-                    }
-                ),
-                format!("Limit: {}", limit),
-            ),
-        };
+    }
 
-        let embed = EmbedBuilder::info("📊 Booster Role Limit", &description)
-            .field("Status", &status_text, true)
-            .footer(poise::serenity_prelude::CreateEmbedFooter::new("Use /boosterrole limit <number> to set a new limit"));
+    let mut embed = EmbedBuilder::success("Limit Updated", changes.join("\n"));
 
-        ctx.send(poise::CreateReply::default().embed(CreateEmbed::from(embed)))
-            .await?;
+    if !warnings.is_empty() {
+        embed = embed.field("⚠️ Warning", warnings.join("\n\n"), false);
     }
 
+    embed = embed.footer(poise::serenity_prelude::CreateEmbedFooter::new(format!("Set by {}", ctx.author().mention())));
+    embed = ResponseHelper::with_next_steps(ctx, embed).await;
+
+    ctx.send(poise::CreateReply::default().embed(embed))
+        .await?;
+
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Current usage and per-role options, shown when the command is run without changes
+pub(crate) fn usage_embed(
+    current_count: usize,
+    current_limit: Option<i32>,
+    options: &GuildBoosterOptions,
+) -> CreateEmbed {
+    let (description, status_text) = match current_limit {
+        Some(0) | None => (
+            format!(
+                "Booster roles are currently **unlimited**.\nRoles used: **{}**",
+                current_count
+            ),
+            "No limit set".to_string(),
+        ),
+        Some(limit) => (
+            format!(
+                "Roles used: **{}/{}**\nAvailable slots: **{}**",
+                current_count,
+                limit,
+                (limit as usize).saturating_sub(current_count)
+            ),
+            if current_count >= limit as usize {
+                "Full".to_string()
+            } else {
+                format!("Limit: {}", limit)
+            },
+        ),
+    };
+
+    EmbedBuilder::info("📊 Booster Role Limit", &description)
+        .field("Status", &status_text, true)
+        .field("Name Length", describe_name_length(options), true)
+        .field(
+            "Second Color",
+            if options.allow_secondary_color { "Allowed" } else { "Not allowed" },
+            true,
+        )
+        .footer(poise::serenity_prelude::CreateEmbedFooter::new("Use /boosterrole limit with any option to change these"))
+}
+
+fn describe_name_length(options: &GuildBoosterOptions) -> String {
+    match options.max_name_length {
+        Some(max) => format!("Up to {} characters", max),
+        None => format!("Up to {} characters (Discord's limit)", MAX_ROLE_NAME_CHARS),
+    }
+}
@@ -1,11 +1,11 @@
 use crate::bot::{Context, Error};
-use crate::data::models::BoosterRoleLink;
-use crate::utils::{EmbedBuilder, EmbedColor};
+use crate::utils::args::{MemberArg, RoleArg};
+use crate::utils::audit::{before_after, booster_audit_embed, send_booster_audit};
+use crate::utils::{EmbedBuilder, EmbedColor, ResponseHelper, RoleManager};
 use poise::serenity_prelude as serenity;
-use serenity::all::{Member, Role};
 use serenity::prelude::Mentionable;
 
-/// Link an existing role to a booster (Administrator only)
+/// Link an existing role to a booster (Admin only)
 #[poise::command(
     slash_command,
     prefix_command,
@@ -19,8 +19,8 @@ use serenity::prelude::Mentionable;
 )]
 pub async fn link(
     ctx: Context<'_>,
-    #[description = "The booster member to link the role to"] member: Member,
-    #[description = "The existing role to link to the booster"] role: Role,
+    #[description = "The booster member to link the role to"] member: MemberArg,
+    #[description = "The existing role to link to the booster"] role: RoleArg,
 ) -> Result<(), Error> {
     let guild_id = ctx
         .guild_id()
@@ -40,31 +40,51 @@ pub async fn link(
     // Defer response to give us more time to process
     ctx.defer().await?;
 
-    // Check if the role is a system role that shouldn't be linked
-    if role.managed || role.id.get() == guild_id.get() {
-        let embed = EmbedBuilder::error(
-            "❌ Invalid Role",
-            "Cannot link system roles, managed roles, or the @everyone role.",
-        );
+    // Roles the bot could never assign get a specific explanation up front
+    let rejection = if role.id.get() == guild_id.get() {
+        Some((
+            "❌ Cannot Link @everyone",
+            "Every member already has @everyone, so it can't be linked as a booster role."
+                .to_string(),
+        ))
+    } else if role.managed {
+        Some((
+            "❌ Managed Role",
+            format!(
+                "{} is managed by an integration or bot and can't be assigned by hand.",
+                role.mention()
+            ),
+        ))
+    } else if role.position
+        >= RoleManager::bot_top_position(ctx.serenity_context(), guild_id).await?
+    {
+        Some((
+            "❌ Role Above Bot",
+            format!(
+                "{} is at or above my highest role. Move my role above it and try again.",
+                role.mention()
+            ),
+        ))
+    } else {
+        None
+    };
+    if let Some((title, description)) = rejection {
+        let embed = EmbedBuilder::error(title, description);
 
         ctx.send(poise::CreateReply::default().embed(embed)).await?;
         return Ok(());
     }
 
     // Check if the member already has a linked role
-    let existing_link = BoosterRoleLink::get(&ctx.data().db_pool, guild_id, member.user.id)
-        .await
-        .map_err(|e| Error::Database(e))?;
+    let existing_link = ctx.data().repos.booster_roles.link(guild_id, member.user.id).await?;
 
     // Create or update the link
-    if let Err(e) = BoosterRoleLink::create(
-        &ctx.data().db_pool,
-        guild_id,
-        member.user.id,
-        role.id,
-        admin_id,
-    )
-    .await
+    if let Err(e) = ctx
+        .data()
+        .repos
+        .booster_roles
+        .set_link(guild_id, member.user.id, role.id, admin_id)
+        .await
     {
         tracing::error!(
             admin_id = %admin_id,
@@ -100,6 +120,30 @@ pub async fn link(
         }
     }
 
+    let mut audit = booster_audit_embed(
+        "🔗 Booster Role Linked",
+        admin_id,
+        role.mention().to_string(),
+    )
+    .field("Member", member.mention().to_string(), true);
+    if let Some(previous) = &existing_link {
+        audit = audit.field(
+            "Linked Role",
+            before_after(
+                &format!("<@&{}>", previous.linked_role_id),
+                &role.mention().to_string(),
+            ),
+            false,
+        );
+    }
+    send_booster_audit(
+        &ctx.serenity_context().http,
+        &ctx.data().db_pool,
+        guild_id,
+        audit,
+    )
+    .await;
+
     // Create success response
     let action_text = if existing_link.is_some() {
         "updated"
@@ -107,22 +151,145 @@ pub async fn link(
         "created"
     };
 
-    let embed = serenity::CreateEmbed::new()
+    let embed = link_created_embed(
+        action_text,
+        &member.user,
+        member.avatar_url(),
+        role.id,
+        ctx.author(),
+    );
+    let embed = ResponseHelper::with_next_steps(ctx, embed).await;
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    tracing::info!(
+        admin_id = %admin_id,
+        guild_id = %guild_id,
+        user_id = %member.user.id,
+        role_id = %role.id,
+        "Booster role link created successfully"
+    );
+
+    Ok(())
+}
+
+/// Confirmation that a role is now linked to a booster
+pub(crate) fn link_created_embed(
+    action_text: &str,
+    member: &serenity::User,
+    avatar_url: Option<String>,
+    role_id: serenity::RoleId,
+    admin: &serenity::User,
+) -> serenity::CreateEmbed {
+    serenity::CreateEmbed::new()
         .title("✅ Booster Role Link Created!")
         .description(format!(
             "Successfully {} role link:\n\n**Member:** {}\n**Role:** {}\n**Linked by:** {}",
             action_text,
             member.mention(),
-            role.mention(),
-            ctx.author().mention()
+            role_id.mention(),
+            admin.mention()
         ))
         .color(EmbedColor::Success.value())
-        .thumbnail(member.avatar_url().unwrap_or_default())
+        .thumbnail(avatar_url.unwrap_or_default())
         .footer(serenity::CreateEmbedFooter::new(format!(
             "Linked by {}",
-            ctx.author().name
+            admin.name
         )))
-        .timestamp(serenity::Timestamp::now());
+        .timestamp(serenity::Timestamp::now())
+}
+
+/// Remove a role link from a booster (Admin only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    description_localized("en-US", "Remove the role linked to a booster member"),
+    broadcast_typing
+)]
+pub async fn unlink(
+    ctx: Context<'_>,
+    #[description = "The member whose role link to remove"] member: MemberArg,
+    #[description = "Also take the linked role away from them (default: no)"] remove_role: Option<
+        bool,
+    >,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+
+    let admin_id = ctx.author().id;
+
+    tracing::info!(
+        admin_id = %admin_id,
+        guild_id = %guild_id,
+        command = "boosterrole.unlink",
+        user_id = %member.user.id,
+        "Booster role unlink command invoked"
+    );
+
+    let Some(link) = ctx.data().repos.booster_roles.link(guild_id, member.user.id).await?
+    else {
+        let embed = EmbedBuilder::error(
+            "❌ No Linked Role",
+            format!("{} doesn't have a linked role.", member.mention()),
+        );
+
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    ctx.data().repos.booster_roles.remove_link(guild_id, member.user.id).await?;
+
+    let role_id = serenity::RoleId::new(link.linked_role_id as u64);
+    let mut role_removed = false;
+    if remove_role.unwrap_or(false) && member.roles.contains(&role_id) {
+        match member
+            .remove_role(&ctx.serenity_context().http, role_id)
+            .await
+        {
+            Ok(()) => role_removed = true,
+            Err(e) => {
+                tracing::warn!(
+                    admin_id = %admin_id,
+                    guild_id = %guild_id,
+                    user_id = %member.user.id,
+                    role_id = %role_id,
+                    error = ?e,
+                    "Failed to remove unlinked role from member"
+                );
+            }
+        }
+    }
+
+    send_booster_audit(
+        &ctx.serenity_context().http,
+        &ctx.data().db_pool,
+        guild_id,
+        booster_audit_embed(
+            "⛓️‍💥 Booster Role Unlinked",
+            admin_id,
+            role_id.mention().to_string(),
+        )
+        .field("Member", member.mention().to_string(), true)
+        .field(
+            "Role Removed",
+            if role_removed { "Yes" } else { "No" },
+            true,
+        ),
+    )
+    .await;
+
+    let mut description = format!(
+        "{} is no longer linked to {}.",
+        role_id.mention(),
+        member.mention()
+    );
+    if role_removed {
+        description.push_str("\nThe role was also removed from them.");
+    }
+    let embed = EmbedBuilder::success("✅ Role Unlinked", description);
 
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
 
@@ -130,8 +297,9 @@ pub async fn link(
         admin_id = %admin_id,
         guild_id = %guild_id,
         user_id = %member.user.id,
-        role_id = %role.id,
-        "Booster role link created successfully"
+        role_id = %role_id,
+        role_removed,
+        "Booster role link removed"
     );
 
     Ok(())
@@ -1,11 +1,12 @@
 use crate::bot::{Context, Error};
-use crate::data::models::BoosterRole;
-use crate::utils::{EmbedBuilder, EmbedColor};
+use crate::data::models::{BoosterRoleLink, BoosterRoleLock, UserPreference};
+use crate::utils::list_presenter::{render_role_list, ListLayout, RoleListEntry};
+use crate::utils::paginator::paginate_embeds;
+use crate::utils::{ColorParser, EmbedBuilder, EmbedColor};
 use poise::serenity_prelude as serenity;
-use serenity::all::UserId;
-use serenity::prelude::Mentionable;
+use std::collections::HashMap;
 
-/// View all booster roles in the server (Administrator only)
+/// View all booster roles in the server (Admin only)
 #[poise::command(
     slash_command,
     prefix_command,
@@ -33,7 +34,7 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
     ctx.defer().await?;
 
     // Get all booster roles for the guild
-    let booster_roles = match BoosterRole::get_all_for_guild(&ctx.data().db_pool, guild_id).await {
+    let booster_roles = match ctx.data().repos.booster_roles.get_all_for_guild(guild_id).await {
         Ok(roles) => roles,
         Err(e) => {
             tracing::error!(
@@ -53,7 +54,12 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
         }
     };
 
-    if booster_roles.is_empty() {
+    // Existing roles linked by an admin show up alongside created ones
+    let links = BoosterRoleLink::get_all_for_guild(&ctx.data().db_pool, guild_id)
+        .await
+        .unwrap_or_default();
+
+    if booster_roles.is_empty() && links.is_empty() {
         let embed = EmbedBuilder::primary(
             "📝 Booster Roles",
             "No booster roles have been created in this server yet.\n\nBoosters can use `/boosterrole color <color> <name>` to create their custom roles."
@@ -63,70 +69,99 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     }
 
-    // Create paginated response for large lists
-    const ROLES_PER_PAGE: usize = 10;
-    let total_pages = (booster_roles.len() + ROLES_PER_PAGE - 1) / ROLES_PER_PAGE;
-    let current_page = 1; // For now, just show the first page
-
-    let start_idx = (current_page - 1) * ROLES_PER_PAGE;
-    let end_idx = std::cmp::min(start_idx + ROLES_PER_PAGE, booster_roles.len());
-    let page_roles = &booster_roles[start_idx..end_idx];
-
-    let mut role_descriptions = Vec::new();
-
-    for (i, role) in page_roles.iter().enumerate() {
-        let user_mention = UserId::new(role.user_id as u64).mention();
-        let role_mention = format!("<@&{}>", role.role_id);
-
-        let created_at = role
-            .created_at
-            .as_ref()
-            .map(|dt| {
-                format!(
-                    "<t:{}:R>",
-                    chrono::DateTime::parse_from_str(dt, "%Y-%m-%d %H:%M:%S")
-                        .map(|dt| dt.timestamp())
-                        .unwrap_or(0)
-                )
+    let locks = BoosterRoleLock::get_all_for_guild(&ctx.data().db_pool, guild_id)
+        .await
+        .unwrap_or_default();
+
+    let mut entries: Vec<RoleListEntry> = booster_roles
+        .iter()
+        .map(|role| RoleListEntry {
+            role_id: role.role_id,
+            owner_id: role.user_id,
+            color: role.primary_color.clone(),
+            created_at: role
+                .created_at
+                .map(|dt| dt.timestamp()),
+            icon: role.icon_url.clone(),
+            lock_label: locks.iter().find(|l| l.role_id == role.role_id).map(|l| {
+                match (l.name_locked, l.color_locked) {
+                    (true, true) => "Name & color locked",
+                    (true, false) => "Name locked",
+                    _ => "Color locked",
+                }
+            }),
+            linked_by: links
+                .iter()
+                .find(|link| link.linked_role_id == role.role_id)
+                .map(|link| link.linked_by),
+        })
+        .collect();
+
+    let role_colors: HashMap<i64, String> = ctx
+        .guild()
+        .map(|guild| {
+            guild
+                .roles
+                .values()
+                .map(|role| {
+                    (
+                        role.id.get() as i64,
+                        ColorParser::to_hex_string(role.colour.0),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    entries.extend(
+        links
+            .iter()
+            .filter(|link| {
+                !booster_roles
+                    .iter()
+                    .any(|r| r.role_id == link.linked_role_id)
             })
-            .unwrap_or_else(|| "Unknown".to_string());
-
-        let description = format!(
-            "**{}. {}** by {}\n└ Color: `{}` • Created: {}",
-            start_idx + i + 1,
-            role_mention,
-            user_mention,
-            role.primary_color,
-            created_at
-        );
-
-        role_descriptions.push(description);
-    }
+            .map(|link| RoleListEntry {
+                role_id: link.linked_role_id,
+                owner_id: link.user_id,
+                color: role_colors
+                    .get(&link.linked_role_id)
+                    .cloned()
+                    .unwrap_or_else(|| "Unknown".to_string()),
+                created_at: link
+                    .created_at
+                    .map(|dt| dt.timestamp()),
+                icon: None,
+                lock_label: None,
+                linked_by: Some(link.linked_by),
+            }),
+    );
 
-    let role_list = role_descriptions.join("\n\n");
-
-    let embed = serenity::CreateEmbed::new()
-        .title("🎨 Server Booster Roles")
-        .description(format!(
-            "**All booster roles ({} total):**\n\n{}",
-            booster_roles.len(),
-            role_list
-        ))
-        .color(EmbedColor::Primary.value())
-        .footer(serenity::CreateEmbedFooter::new(format!(
-            "Page {} of {} • Requested by {}",
-            current_page,
-            total_pages,
-            ctx.author().name
-        )))
-        .timestamp(serenity::Timestamp::now());
-
-    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    let compact = UserPreference::compact_mode(&ctx.data().db_pool, admin_id)
+        .await
+        .unwrap_or(false);
+    let layout = ListLayout::from_compact(compact);
+    let pages = (1..=layout.page_count(entries.len()))
+        .map(|number| {
+            let page = render_role_list(&entries, number, layout, &ctx.author().name);
+            let embed = serenity::CreateEmbed::new()
+                .title("🎨 Server Booster Roles")
+                .description(page.description)
+                .color(EmbedColor::Primary.value())
+                .footer(serenity::CreateEmbedFooter::new(page.footer));
+            if compact {
+                embed
+            } else {
+                embed.timestamp(serenity::Timestamp::now())
+            }
+        })
+        .collect();
+
+    paginate_embeds(ctx, pages).await?;
 
     tracing::info!(
         admin_id = %admin_id,
         guild_id = %guild_id,
-        role_count = booster_roles.len(),
+        role_count = entries.len(),
         "Booster role list displayed successfully"
     );
 
@@ -0,0 +1,247 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{BoosterRoleLock, SettingsAuditLog};
+use crate::utils::args::MemberArg;
+use crate::utils::role_lock::LockedAspect;
+use crate::utils::{check_lock, EmbedBuilder, RequestedChange, ResponseHelper};
+use poise::serenity_prelude as serenity;
+use serenity::all::{CreateEmbed, GuildId, RoleId};
+use serenity::prelude::Mentionable;
+use tracing::instrument;
+
+/// Lock a booster's role name and/or color against changes (Staff only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    check = "super::staff_check",
+    category = "Booster Roles",
+    description_localized("en-US", "Lock a booster role's name and/or color against changes"),
+    broadcast_typing
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.lock"
+    )
+)]
+pub async fn lock(
+    ctx: Context<'_>,
+    #[description = "The booster whose role should be locked"] user: MemberArg,
+    #[description = "Lock the role name (defaults to locking both)"] name: Option<bool>,
+    #[description = "Lock the role color (defaults to locking both)"] color: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+
+    // With neither flag given, lock everything
+    let (name_locked, color_locked) = match (name, color) {
+        (None, None) => (true, true),
+        (name, color) => (name.unwrap_or(false), color.unwrap_or(false)),
+    };
+
+    if !name_locked && !color_locked {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Nothing to Lock",
+            "Choose at least one of `name` or `color` to lock, or use `/boosterrole unlock` to remove a lock.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let pool = &ctx.data().db_pool;
+
+    let Some(booster_role) = ctx.data().repos.booster_roles.get(guild_id, user.user.id).await? else {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ No Booster Role",
+            &format!("{} doesn't have a booster role to lock.", user.mention()),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let role_id = RoleId::new(booster_role.role_id as u64);
+
+    // Snapshot the current Discord-side values so manual edits can be reverted
+    let guild = guild_id.to_partial_guild(&ctx.serenity_context().http).await?;
+    let (current_name, current_color) = match guild.roles.get(&role_id) {
+        Some(role) => (role.name.clone(), role.colour.0),
+        None => {
+            ResponseHelper::send_error(
+                ctx,
+                "❌ Role Not Found",
+                "The booster role no longer exists on Discord. Try `/boosterrole cleanup` first.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    BoosterRoleLock::set(
+        pool,
+        guild_id,
+        role_id,
+        name_locked,
+        color_locked,
+        name_locked.then_some(current_name.as_str()),
+        color_locked.then_some(current_color),
+        ctx.author().id,
+    )
+    .await?;
+
+    let aspects = describe_aspects(name_locked, color_locked);
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "booster_role_locked",
+        Some(&format!(
+            "Role: <@&{}> Owner: <@{}> Locked: {}",
+            role_id, user.user.id, aspects
+        )),
+    )
+    .await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "🔒 Role Locked",
+        &format!(
+            "The **{}** of {}'s booster role {} is now locked.\n\nThe owner can no longer change it, and manual edits will be reverted.",
+            aspects,
+            user.mention(),
+            role_id.mention()
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Remove a lock from a booster's role (Staff only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    check = "super::staff_check",
+    category = "Booster Roles",
+    description_localized("en-US", "Remove the name/color lock from a booster role"),
+    broadcast_typing
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.unlock"
+    )
+)]
+pub async fn unlock(
+    ctx: Context<'_>,
+    #[description = "The booster whose role should be unlocked"] user: MemberArg,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+
+    let pool = &ctx.data().db_pool;
+
+    let Some(booster_role) = ctx.data().repos.booster_roles.get(guild_id, user.user.id).await? else {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ No Booster Role",
+            &format!("{} doesn't have a booster role.", user.mention()),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let role_id = RoleId::new(booster_role.role_id as u64);
+
+    if !BoosterRoleLock::remove(pool, guild_id, role_id).await? {
+        ResponseHelper::send_info(
+            ctx,
+            "ℹ️ Not Locked",
+            &format!("{}'s booster role isn't locked.", user.mention()),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "booster_role_unlocked",
+        Some(&format!("Role: <@&{}> Owner: <@{}>", role_id, user.user.id)),
+    )
+    .await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "🔓 Role Unlocked",
+        &format!(
+            "{}'s booster role {} can be changed again.",
+            user.mention(),
+            role_id.mention()
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Check a requested change against the role's lock, replying with an
+/// explanation when it is blocked. Returns `true` when the change may proceed.
+pub(crate) async fn ensure_unlocked(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    role_id: RoleId,
+    change: RequestedChange,
+) -> Result<bool, Error> {
+    let Some(role_lock) = BoosterRoleLock::get(&ctx.data().db_pool, guild_id, role_id).await?
+    else {
+        return Ok(true);
+    };
+
+    match check_lock(role_lock.flags(), change) {
+        Ok(()) => Ok(true),
+        Err(aspect) => {
+            tracing::warn!(
+                user_id = %ctx.author().id,
+                guild_id = %guild_id,
+                role_id = %role_id,
+                locked = aspect.label(),
+                "Blocked change to locked booster role"
+            );
+
+            let embed = locked_embed(aspect, role_lock.locked_by);
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+            Ok(false)
+        }
+    }
+}
+
+/// Why a change to a locked role was refused
+pub(crate) fn locked_embed(aspect: LockedAspect, locked_by: i64) -> CreateEmbed {
+    EmbedBuilder::error(
+        "🔒 Role Locked",
+        format!(
+            "Your role's **{}** has been locked by <@{}> and can't be changed.\n\nAsk a staff member if you'd like it unlocked.",
+            aspect.label(),
+            locked_by
+        ),
+    )
+}
+
+fn describe_aspects(name_locked: bool, color_locked: bool) -> &'static str {
+    match (name_locked, color_locked) {
+        (true, true) => "name and color",
+        (true, false) => "name",
+        _ => "color",
+    }
+}
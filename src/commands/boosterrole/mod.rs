@@ -1,76 +1,527 @@
+pub mod admin;
+pub mod adopt;
 pub mod award;
 pub mod base;
+pub mod changes;
 pub mod cleanup;
 pub mod color;
+pub mod diagnose;
 pub mod dominant;
+pub mod export;
 pub mod filter;
+pub mod history;
 pub mod icon;
+pub mod imagecolor;
 pub mod limit;
 pub mod link;
 pub mod list;
+pub mod notifications;
+pub mod lock;
 pub mod random;
 pub mod remove;
 pub mod rename;
+pub mod restore;
+pub mod rotate;
+pub mod rules;
+#[cfg(debug_assertions)]
+pub mod samples;
+pub mod schedule;
 pub mod share;
+pub mod spotlight;
+pub mod stats;
+pub mod template;
 
 use crate::bot::{Context, Error};
+use crate::data::models::{GuildNamingRules, GuildStaffLookalikeSetting, RulesAcknowledgment};
+use crate::utils::eligibility::{check_age, load_age_requirement, to_utc, AgeCheck, EligibilityMode};
+use crate::utils::embed_builder::DescriptionBuilder;
+use crate::utils::experiments;
+use crate::utils::i18n::{locale_for, tr};
+use crate::utils::image_processor::render_gradient;
+use crate::utils::in_flight::InFlightGuard;
+use crate::utils::paginator::paginate_embeds;
+use crate::utils::role_name::{imitated_role, RoleNameError};
+use crate::utils::rules_ack::{ack_decision, AckDecision};
+use crate::utils::{is_eligible, load_eligibility_config, load_staff_role_ids, CheckFailure};
+use serenity::all::{
+    ButtonStyle, ComponentInteractionCollector, CreateActionRow, CreateAttachment, CreateButton,
+    CreateEmbed, CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage,
+    GuildId, Member, Permissions,
+};
+use std::time::Duration;
+
+use admin::admin;
+use adopt::adopt;
 use award::award;
 use base::base;
 use cleanup::cleanup;
 use color::color;
+use diagnose::diagnose;
 use dominant::dominant;
+use export::export;
 use filter::filter;
+use history::history;
 use icon::icon;
+use imagecolor::imagecolor;
 use limit::limit;
-use link::link;
+use link::{link, unlink};
 use list::list;
+use lock::{lock, unlock};
+use notifications::notifications;
 use random::random;
 use remove::remove;
 use rename::rename;
+use restore::restore;
+use rotate::rotate;
+use rules::rules;
+use schedule::schedule;
 use share::share;
+use spotlight::spotlight;
+use stats::stats;
+use template::template;
+
+/// How long a member has to agree to the naming rules
+const RULES_ACK_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Body of the `/boosterrole` overview
+const HELP_TEXT: &str = "**Booster Commands:**\n\
+    `/boosterrole color <color> <name>` - Create/update your custom role\n\
+    `/boosterrole dominant [url] [image]` - Set role color from your avatar, an image link, or an upload\n\
+    `/boosterrole imagecolor <image>` - Set role color from an uploaded image\n\
+    `/boosterrole rename set <name>` - Rename your booster role (cooldown set by the server)\n\
+    `/boosterrole rename history [user]` - See past renames (staff can view anyone's)\n\
+    `/boosterrole rules view` - Read the server's naming rules\n\
+    `/boosterrole schedule once <when> [name] [color] [revert_after]` - Schedule a one-time role change\n\
+    `/boosterrole schedule list` - View your scheduled changes\n\
+    `/boosterrole schedule cancel <id>` - Cancel a scheduled change\n\
+    `/boosterrole rotate enable <color1> <color2> [color3..5] [every_hours]` - Cycle your role through a palette\n\
+    `/boosterrole rotate disable` - Stop cycling colors\n\
+    `/boosterrole icon [emoji or url] [image]` - Set custom icon for your role\n\
+    `/boosterrole random [style]` - Generate random color for your role\n\
+    `/boosterrole remove` - Delete your custom booster role\n\
+    `/boosterrole restore` - Bring back the role you had before your boost lapsed\n\
+    `/boosterrole template use <name>` - Apply one of the server's role templates\n\
+    `/boosterrole stats [user]` - See your booster role's history and stats\n\
+    `/boosterrole notifications <category> [on|off]` - Opt in to DMs like feature updates\n\n\
+    **Sharing Commands:**\n\
+    `/boosterrole share role <user> [duration]` - Share your role with another member\n\
+    `/boosterrole share remove <role>` - Remove yourself from shared role\n\
+    `/boosterrole share revoke <user>` - Take your role back from a member\n\
+    `/boosterrole share mine` - See your shares\n\n\
+    **Admin Commands:**\n\
+    `/boosterrole link <user> <role>` - Link existing role to booster\n\
+    `/boosterrole unlink <user> [remove_role]` - Remove a booster's role link\n\
+    `/boosterrole cleanup [dry_run] [delete_roles]` - Remove orphaned booster roles\n\
+    `/boosterrole limit [max] [max_name_length] [allow_secondary_color]` - Set/view role limits and usage\n\
+    `/boosterrole base set <role>` - Set base role for hierarchy positioning\n\
+    `/boosterrole base sync` - Move booster roles back above the base role\n\
+    `/boosterrole lock <user> [name] [color]` - Lock a booster's role name/color\n\
+    `/boosterrole unlock <user>` - Remove a booster role lock\n\
+    `/boosterrole admin rename <user> <name>` - Rename a member's booster role\n\
+    `/boosterrole admin recolor <user> <color>` - Change a member's booster role color\n\
+    `/boosterrole admin delete <user>` - Delete a member's booster role\n\
+    `/boosterrole template add <name> <color> [icon]` - Offer a name/color template\n\
+    `/boosterrole template remove <name>` - Remove a template\n\
+    `/boosterrole template list` - View the server's templates\n\
+    `/boosterrole adopt scan` - Adopt hand-made roles held by boosters\n\
+    `/boosterrole spotlight set <channel> <weekly|off>` - Weekly booster role highlight\n\
+    `/boosterrole spotlight now` - Post the spotlight right away\n\
+    `/boosterrole schedule all` - View every scheduled role change\n\
+    `/boosterrole rotate settings [min_interval_hours] [max_members]` - Limit color rotation\n\
+    `/boosterrole award set <role>` - Set role to award new boosters\n\
+    `/boosterrole award unset` - Remove award role\n\
+    `/boosterrole award view` - View current award role\n\
+    `/boosterrole award list` - See who received the award role\n\
+    `/boosterrole award backfill [dry_run]` - Give the award role to current boosters\n\
+    `/boosterrole filter add <word> [severity] [match_type]` - Add word or pattern to blacklist\n\
+    `/boosterrole filter remove <word>` - Remove word from blacklist\n\
+    `/boosterrole filter list` - View blacklisted words\n\
+    `/boosterrole filter enforcement <severity> <action>` - Choose what each severity does\n\
+    `/boosterrole filter reload` - Reload the cached blacklist\n\
+    `/boosterrole rules set` - Write the naming rules members agree to\n\
+    `/boosterrole rules require <on|off>` - Require agreement before customization\n\
+    `/boosterrole share max <num>` - Set max members per shared role\n\
+    `/boosterrole share limit <num>` - Set max shared roles per member\n\
+    `/boosterrole share list` - View all role shares\n\
+    `/boosterrole share revalidate <on|off>` - Revoke shares from recipients who left or stopped qualifying\n\
+    `/boosterrole list` - View all booster roles\n\
+    `/boosterrole export [csv|json]` - Download every booster role as a file\n\
+    `/boosterrole diagnose` - Show who is eligible for booster roles\n\
+    `/boosterrole history export <user> [format]` - Export a member's customization history\n\
+    `/boosterrole rename clear <user>` - Wipe a member's rename history\n\n\
+    **Aliases:** `!br`, `!booster`";
 
 /// Booster role management commands for server boosters and administrators
 #[poise::command(
     slash_command,
     prefix_command,
     guild_only,
-    category = "Booster",
+    category = "Booster Roles",
     description_localized("en-US", "Comprehensive booster role management with custom colors, filters, and admin controls"),
-    subcommands("color", "dominant", "rename", "link", "filter", "list", "cleanup", "limit", "award", "icon", "random", "remove", "base", "share"),
+    subcommands("color", "dominant", "imagecolor", "rename", "link", "unlink", "filter", "list", "export", "cleanup", "limit", "award", "icon", "random", "remove", "restore", "base", "share", "lock", "unlock", "adopt", "spotlight", "diagnose", "history", "rules", "schedule", "rotate", "notifications", "stats", "template", "admin"),
     aliases("br", "booster"),
     broadcast_typing
 )]
 pub async fn boosterrole(ctx: Context<'_>) -> Result<(), Error> {
-    let embed = crate::utils::EmbedBuilder::info(
-        "🎨 Booster Role Commands",
-        "**Booster Commands:**\n\
-        `/boosterrole color <color> <name>` - Create/update your custom role\n\
-        `/boosterrole dominant` - Set role color to your avatar's dominant color\n\
-        `/boosterrole rename <name>` - Rename your booster role (1hr cooldown)\n\
-        `/boosterrole icon <url>` - Set custom icon for your role\n\
-        `/boosterrole random [style]` - Generate random color for your role\n\
-        `/boosterrole remove` - Delete your custom booster role\n\n\
-        **Sharing Commands:**\n\
-        `/boosterrole share role <user>` - Share your role with another member\n\
-        `/boosterrole share remove <role>` - Remove yourself from shared role\n\n\
-        **Admin Commands:**\n\
-        `/boosterrole link <user> <role>` - Link existing role to booster\n\
-        `/boosterrole cleanup [dry_run]` - Remove orphaned booster roles\n\
-        `/boosterrole limit [max]` - Set/view max booster roles allowed\n\
-        `/boosterrole base [role]` - Set base role for hierarchy positioning\n\
-        `/boosterrole award set <role>` - Set role to award new boosters\n\
-        `/boosterrole award unset` - Remove award role\n\
-        `/boosterrole award view` - View current award role\n\
-        `/boosterrole filter add <word>` - Add word to blacklist\n\
-        `/boosterrole filter remove <word>` - Remove word from blacklist\n\
-        `/boosterrole filter list` - View blacklisted words\n\
-        `/boosterrole share max <num>` - Set max members per shared role\n\
-        `/boosterrole share limit <num>` - Set max shared roles per member\n\
-        `/boosterrole share list` - View all role shares\n\
-        `/boosterrole list` - View all booster roles\n\n\
-        **Aliases:** `!br`, `!booster`",
+    // Long enough to outgrow one embed, so it's paged like any other list
+    let pages = DescriptionBuilder::new()
+        .lines(HELP_TEXT.lines())
+        .build(|description, page, total| {
+            crate::utils::EmbedBuilder::info("🎨 Booster Role Commands", description)
+                .footer(CreateEmbedFooter::new(format!("Page {} of {}", page, total)))
+        });
+
+    paginate_embeds(ctx, pages).await
+}
+
+/// Reply with the embed, plus a gradient preview when the role has a second color.
+/// A preview that fails to render is left out rather than failing the command.
+pub(crate) fn reply_with_gradient(
+    embed: CreateEmbed,
+    primary: u32,
+    secondary: Option<u32>,
+) -> poise::CreateReply {
+    let Some(secondary) = secondary.filter(|&secondary| secondary != primary) else {
+        return poise::CreateReply::default().embed(embed);
+    };
+
+    match render_gradient(primary, secondary) {
+        Ok(png) => poise::CreateReply::default()
+            .embed(embed.image("attachment://gradient.png"))
+            .attachment(CreateAttachment::bytes(png, "gradient.png")),
+        Err(e) => {
+            tracing::warn!(error = ?e, "Skipping gradient preview");
+            poise::CreateReply::default().embed(embed)
+        }
+    }
+}
+
+/// Poise check: the invoking member is staff (owner, Manage Server, or a configured staff role)
+pub(crate) async fn staff_check(ctx: Context<'_>) -> Result<bool, Error> {
+    if !crate::commands::settings::is_staff(&ctx).await? {
+        return Err(Error::Check(CheckFailure::NotStaff));
+    }
+    Ok(true)
+}
+
+/// Check the member may own a custom booster role under the guild's eligibility mode.
+/// Sends an embed explaining what grants eligibility and returns `false` when they can't.
+pub(crate) async fn ensure_eligible(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    member: &Member,
+) -> Result<bool, Error> {
+    let pool = &ctx.data().db_pool;
+    let config = load_eligibility_config(pool, guild_id).await?;
+
+    if is_eligible(member.premium_since, &member.roles, &config) {
+        ctx.data()
+            .experiments
+            .report_conversion(pool, experiments::NON_BOOSTER_REJECTION, member.user.id)
+            .await;
+        if !ensure_old_enough(ctx, guild_id, member).await? {
+            return Ok(false);
+        }
+        return ensure_rules_acknowledged(ctx, guild_id, member).await;
+    }
+
+    tracing::warn!(
+        user_id = %member.user.id,
+        guild_id = %guild_id,
+        mode = config.effective_mode().as_str(),
+        command = %ctx.command().qualified_name,
+        "Ineligible member attempted booster role command"
     );
 
-    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    let (title, description) = match config.effective_mode() {
+        EligibilityMode::BoostersOnly => {
+            let description =
+                match experiments::select(experiments::NON_BOOSTER_REJECTION, member.user.id) {
+                    Some(variant) => {
+                        ctx.data()
+                            .experiments
+                            .show(pool, variant, member.user.id)
+                            .await;
+                        variant.text.to_string()
+                    }
+                    None => config.requirement_text(),
+                };
+            ("booster.not_booster", description)
+        }
+        _ => ("booster.not_eligible", config.requirement_text()),
+    };
+    let embed = crate::utils::EmbedBuilder::error(tr(ctx, title, &[]).await, description);
+    crate::utils::ResponseHelper::send_embed_with(ctx, embed, true).await?;
+
+    Ok(false)
+}
+
+/// Claim the member's booster role change slot for the rest of the command.
+/// Sends an embed and returns `None` while another of their changes is running.
+pub(crate) async fn begin_role_change(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+) -> Result<Option<InFlightGuard>, Error> {
+    if let Some(guard) = ctx.data().role_changes.try_begin(guild_id, ctx.author().id) {
+        return Ok(Some(guard));
+    }
+
+    tracing::info!(
+        guild_id = %guild_id,
+        user_id = %ctx.author().id,
+        command = %ctx.command().qualified_name,
+        "Booster role change refused while another is in progress"
+    );
+
+    let embed = crate::utils::EmbedBuilder::warning(
+        tr(ctx, "booster.in_progress.title", &[]).await,
+        tr(ctx, "booster.in_progress.body", &[]).await,
+    );
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(None)
+}
+
+/// Explain a refused booster role change to the member, alerting staff first
+/// when a blocked name calls for it
+pub(crate) async fn refuse(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    name: &str,
+    refusal: changes::Refusal,
+) -> Result<(), Error> {
+    if let changes::Refusal::BlockedName { word, alert } = &refusal {
+        tracing::warn!(
+            user_id = %ctx.author().id,
+            guild_id = %guild_id,
+            role_name = %name,
+            "Attempted to use blacklisted word in role name"
+        );
+        if *alert {
+            crate::handlers::support_ticket::alert_blocked_name(
+                &ctx.serenity_context().http,
+                &ctx.data().db_pool,
+                guild_id,
+                ctx.author().id,
+                name,
+                word,
+            )
+            .await;
+        }
+    }
+
+    let embed = refusal.embed(locale_for(ctx).await);
+    crate::utils::ResponseHelper::send_embed_with(ctx, embed, true).await?;
     Ok(())
 }
+
+/// Refuse a name that passes for one of the guild's staff roles, or a role
+/// with Manage Server, when the guild blocks lookalikes. Sends an embed and
+/// returns `false` for a lookalike.
+pub(crate) async fn ensure_not_staff_lookalike(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    name: &str,
+) -> Result<bool, Error> {
+    let pool = &ctx.data().db_pool;
+    if !GuildStaffLookalikeSetting::is_enabled(pool, guild_id).await? {
+        return Ok(true);
+    }
+
+    let staff_role_ids = load_staff_role_ids(pool, guild_id).await?;
+    let guild = guild_id
+        .to_partial_guild(&ctx.serenity_context().http)
+        .await?;
+    let staff_names = guild
+        .roles
+        .values()
+        .filter(|role| {
+            staff_role_ids.contains(&role.id)
+                || role.permissions.contains(Permissions::ADMINISTRATOR)
+                || role.permissions.contains(Permissions::MANAGE_GUILD)
+        })
+        .map(|role| role.name.as_str());
+    let Some(imitated) = imitated_role(name, staff_names) else {
+        return Ok(true);
+    };
+
+    tracing::warn!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        role_name = %name,
+        staff_role = %imitated,
+        "Booster role name imitates a staff role"
+    );
+
+    let reason = RoleNameError::ImitatesStaffRole(imitated.to_string()).to_string();
+    refuse(ctx, guild_id, name, changes::Refusal::InvalidName(reason)).await?;
+    Ok(false)
+}
+
+/// Check the guild has room for another booster role. Only call this when the
+/// member is about to get a new role; updating an existing one is never blocked.
+/// Sends an embed showing current usage and returns `false` when the guild is full.
+pub(crate) async fn ensure_under_limit(ctx: Context<'_>, guild_id: GuildId) -> Result<bool, Error> {
+    let Some(usage) = ctx.data().repos.limit_usage(guild_id).await? else {
+        return Ok(true);
+    };
+    if !usage.is_full() {
+        return Ok(true);
+    }
+
+    tracing::info!(
+        guild_id = %guild_id,
+        user_id = %ctx.author().id,
+        current = usage.current,
+        max = usage.max,
+        "Booster role creation refused at guild limit"
+    );
+
+    let locale = locale_for(ctx).await;
+    let embed = changes::Refusal::LimitReached(usage).embed(locale);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(false)
+}
+
+/// Check the guild's minimum account age and membership thresholds, unless
+/// staff exempted the member. Sends an embed saying when they'll become
+/// eligible and returns `false` when they aren't yet.
+async fn ensure_old_enough(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    member: &Member,
+) -> Result<bool, Error> {
+    let pool = &ctx.data().db_pool;
+    let Some(requirement) = load_age_requirement(pool, guild_id, member.user.id).await? else {
+        return Ok(true);
+    };
+
+    let mut joined_at = member.joined_at;
+    if joined_at.is_none() && requirement.min_membership_days > 0 {
+        // Gateway members sometimes lack joined_at; the REST member has it
+        joined_at = guild_id
+            .member(ctx.http(), member.user.id)
+            .await
+            .ok()
+            .and_then(|m| m.joined_at);
+    }
+
+    let check = check_age(
+        &requirement,
+        to_utc(member.user.created_at()),
+        joined_at.map(to_utc),
+        chrono::Utc::now(),
+    );
+
+    let description = match check {
+        AgeCheck::Met => return Ok(true),
+        AgeCheck::NotUntil(at) => format!(
+            "New accounts and members need to wait before customizing a booster role here.\n\n\
+            You'll be eligible <t:{0}:R> (<t:{0}:f>).",
+            at.timestamp()
+        ),
+        AgeCheck::JoinDateUnknown => "New members need to wait before customizing a booster role here, \
+            and I couldn't confirm when you joined. Please try again in a moment."
+            .to_string(),
+    };
+
+    tracing::warn!(
+        user_id = %member.user.id,
+        guild_id = %guild_id,
+        requirement = %requirement.describe(),
+        command = %ctx.command().qualified_name,
+        "Member below account age threshold attempted booster role command"
+    );
+
+    let embed = crate::utils::EmbedBuilder::error("⏳ Not Eligible Yet", description).field(
+        "Requirement",
+        requirement.describe(),
+        false,
+    );
+    crate::utils::ResponseHelper::send_embed_with(ctx, embed, true).await?;
+
+    Ok(false)
+}
+
+/// Ask the member to agree to the guild's naming rules when the guild requires
+/// it and they haven't agreed to the current version. The command carries on
+/// once they agree; returns `false` if they don't answer in time.
+async fn ensure_rules_acknowledged(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    member: &Member,
+) -> Result<bool, Error> {
+    let pool = &ctx.data().db_pool;
+    let Some(rules) = GuildNamingRules::get(pool, guild_id).await? else {
+        return Ok(true);
+    };
+    let acknowledged = RulesAcknowledgment::get_version(pool, guild_id, member.user.id).await?;
+
+    let AckDecision::Prompt { version } =
+        ack_decision(rules.require_ack, Some(rules.version), acknowledged)
+    else {
+        return Ok(true);
+    };
+
+    let agree_id = format!("rules:{}:agree", ctx.id());
+    let prompt = rules::rules_embed(&rules.rules_text);
+    let button = |disabled: bool| {
+        vec![CreateActionRow::Buttons(vec![CreateButton::new(
+            agree_id.clone(),
+        )
+        .label("I agree")
+        .style(ButtonStyle::Success)
+        .disabled(disabled)])]
+    };
+
+    let reply = ctx
+        .send(
+            poise::CreateReply::default()
+                .embed(prompt.clone().footer(CreateEmbedFooter::new(
+                    "Agree to these rules to continue customizing your role",
+                )))
+                .components(button(false)),
+        )
+        .await?;
+
+    let filter_id = agree_id.clone();
+    let interaction = ComponentInteractionCollector::new(ctx)
+        .author_id(member.user.id)
+        .channel_id(ctx.channel_id())
+        .filter(move |i| i.data.custom_id == filter_id)
+        .timeout(RULES_ACK_TIMEOUT)
+        .await;
+
+    let Some(interaction) = interaction else {
+        reply
+            .edit(
+                ctx,
+                poise::CreateReply::default()
+                    .embed(prompt.footer(CreateEmbedFooter::new(
+                        "Not agreed — run the command again when you're ready",
+                    )))
+                    .components(button(true)),
+            )
+            .await?;
+        return Ok(false);
+    };
+
+    RulesAcknowledgment::record(pool, guild_id, member.user.id, version).await?;
+
+    interaction
+        .create_response(
+            ctx,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .embed(prompt.footer(CreateEmbedFooter::new(
+                        "✅ Agreed — continuing with your command",
+                    )))
+                    .components(button(true)),
+            ),
+        )
+        .await?;
+
+    Ok(true)
+}
@@ -0,0 +1,77 @@
+use crate::bot::{Context, Error};
+use crate::data::models::NotificationSubscription;
+use crate::utils::announcements::NotificationCategory;
+use crate::utils::ResponseHelper;
+use poise::ChoiceParameter;
+use tracing::instrument;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum NotificationSetting {
+    #[name = "on"]
+    On,
+    #[name = "off"]
+    Off,
+}
+
+/// Choose which optional DMs you receive from the bot
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    category = "Booster Roles",
+    description_localized("en-US", "Opt in or out of optional DMs such as feature updates")
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.notifications"
+    )
+)]
+pub async fn notifications(
+    ctx: Context<'_>,
+    #[description = "Kind of notification"] category: NotificationCategory,
+    #[description = "Turn it on or off (leave empty to see your setting)"] setting: Option<
+        NotificationSetting,
+    >,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+    let user_id = ctx.author().id;
+    let pool = &ctx.data().db_pool;
+
+    let Some(setting) = setting else {
+        let subscribed =
+            NotificationSubscription::is_subscribed(pool, guild_id, user_id, category).await?;
+        ResponseHelper::send_info(
+            ctx,
+            "🔔 Notifications",
+            &format!(
+                "**{}** DMs are **{}** for you in this server.",
+                category.name(),
+                if subscribed { "on" } else { "off" }
+            ),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let subscribed = matches!(setting, NotificationSetting::On);
+    NotificationSubscription::set(pool, guild_id, user_id, category, subscribed).await?;
+
+    let description = if subscribed {
+        format!(
+            "You'll get a DM about **{}**. You'll only get one copy even if you opt in from several servers.",
+            category.name()
+        )
+    } else {
+        format!(
+            "You won't get **{}** DMs through this server anymore. Opt out in any other server you opted in from as well.",
+            category.name()
+        )
+    };
+    ResponseHelper::send_success(ctx, "🔔 Notifications Updated", &description).await?;
+    Ok(())
+}
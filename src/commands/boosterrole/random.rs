@@ -1,6 +1,6 @@
 use crate::bot::{Context, Error};
-use crate::data::models::{BoosterRole, BoosterRoleLink};
-use crate::utils::{ColorGenerator, ResponseHelper};
+use crate::data::models::BoosterRoleLink;
+use crate::utils::{ColorGenerator, RequestedChange, ResponseHelper};
 use serenity::all::{EditRole, Permissions, RoleId};
 use tracing::{info, instrument};
 
@@ -32,23 +32,15 @@ pub async fn random(
     let guild_id = ctx.guild_id().ok_or(Error::Command("This command must be used in a guild".to_string()))?;
     let user_id = ctx.author().id;
     
-    // Check if user is a booster
+    // Check if user may own a booster role
     let member = guild_id.member(&ctx.http(), user_id).await?;
     
-    let is_booster = {
-        let guild = guild_id.to_guild_cached(&ctx.serenity_context().cache)
-            .ok_or(Error::Command("Guild not found in cache".to_string()))?;
-        member.roles.iter().any(|r| guild.roles.get(r).map_or(false, |role| role.name.contains("Booster") || role.tags.premium_subscriber))
-    };
-    
-    if !is_booster {
-        ResponseHelper::send_error(
-            ctx,
-            "Not a Booster",
-            "You must be a server booster to use this command."
-        ).await?;
+    if !super::ensure_eligible(ctx, guild_id, &member).await? {
         return Ok(());
     }
+    let Some(_in_flight) = super::begin_role_change(ctx, guild_id).await? else {
+        return Ok(());
+    };
     
     // Check if user has a linked role (admin-assigned)
     let data = ctx.data();
@@ -72,20 +64,28 @@ pub async fn random(
     let hex_color = ColorGenerator::to_hex_string(color);
     
     // Get or create booster role
-    let existing_role = BoosterRole::get(&data.db_pool, guild_id, user_id).await?;
+    let existing_role = data.repos.booster_roles.get(guild_id, user_id).await?;
     
     let (role_id, role_name) = if let Some(role) = existing_role {
         // Update existing role
         let role_id = RoleId::new(role.role_id as u64);
         
+        if !super::lock::ensure_unlocked(ctx, guild_id, role_id, RequestedChange::color()).await? {
+            return Ok(());
+        }
+        
         // Update role color
         guild_id.edit_role(&ctx.http(), role_id, EditRole::new().colour(color.0 as u64)).await?;
         
         // Update database
-        BoosterRole::update_color(&data.db_pool, guild_id, user_id, &hex_color, None).await?;
+        data.repos.booster_roles.update_color(guild_id, user_id, &hex_color, None).await?;
         
         (role_id, role.role_name)
     } else {
+        if !super::ensure_under_limit(ctx, guild_id).await? {
+            return Ok(());
+        }
+
         // Create new role with random color
         let default_name = format!("{}'s Booster Role", member.user.name);
         
@@ -104,7 +104,7 @@ pub async fn random(
         member.add_role(&ctx.http(), new_role.id).await?;
         
         // Position the role above base role if configured
-        if let Some(base_role_id) = crate::data::models::GuildBoosterBaseRole::get(&data.db_pool, guild_id).await? {
+        if let Some(base_role_id) = data.repos.guild_settings.base_role(guild_id).await? {
             let base_position = {
                 let guild = guild_id.to_guild_cached(&ctx.serenity_context().cache)
                     .ok_or(Error::Command("Guild not found in cache".to_string()))?;
@@ -122,8 +122,7 @@ pub async fn random(
         }
         
         // Store in database
-        BoosterRole::create(
-            &data.db_pool,
+        data.repos.booster_roles.create(
             guild_id,
             user_id,
             new_role.id,
@@ -157,6 +156,7 @@ pub async fn random(
         "🎲 Random Color Applied",
         &description
     );
+    let embed = crate::utils::ResponseHelper::with_next_steps(ctx, embed).await;
     
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     
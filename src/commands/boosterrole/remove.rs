@@ -1,7 +1,9 @@
 use crate::bot::{Context, Error};
-use crate::data::models::{BoosterRole, BoosterRoleLink, BoosterRoleShare};
-use crate::utils::ResponseHelper;
-use serenity::all::{RoleId, UserId};
+use crate::data::models::{BoosterRoleLink, BoosterRoleShare};
+use crate::utils::audit::{booster_audit_embed, send_booster_audit};
+use crate::utils::confirm::{ask_confirmation, finish_prompt, Confirmation};
+use crate::utils::{EmbedBuilder, ResponseHelper};
+use serenity::all::{CreateEmbed, RoleId, UserId};
 use tracing::{error, info, instrument, warn};
 
 /// Remove your custom booster role
@@ -22,87 +24,98 @@ use tracing::{error, info, instrument, warn};
         command = "boosterrole.remove"
     )
 )]
-pub async fn remove(
-    ctx: Context<'_>,
-    #[description = "Also remove all shares of this role"] 
-    remove_shares: Option<bool>,
-) -> Result<(), Error> {
+pub async fn remove(ctx: Context<'_>) -> Result<(), Error> {
     info!("Remove booster role command invoked");
-    
-    let guild_id = ctx.guild_id().ok_or(Error::Command("This command must be used in a guild".to_string()))?;
+
+    let guild_id = ctx.guild_id().ok_or(Error::Command(
+        "This command must be used in a guild".to_string(),
+    ))?;
     let user_id = ctx.author().id;
-    
+
     // Check if user has a booster role
     let data = ctx.data();
-    let existing_role = BoosterRole::get(&data.db_pool, guild_id, user_id).await?;
-    
-    if existing_role.is_none() {
+    let Some(role_data) = data.repos.booster_roles.get(guild_id, user_id).await? else {
         ResponseHelper::send_error(
             ctx,
             "No Booster Role",
-            "You don't have a custom booster role to remove."
-        ).await?;
+            "You don't have a custom booster role to remove.",
+        )
+        .await?;
         return Ok(());
-    }
-    
-    let role_data = existing_role.unwrap();
+    };
+
     let role_id = RoleId::new(role_data.role_id as u64);
     let role_name = role_data.role_name.clone();
-    
+
     // Check if role is linked (admin-managed)
-    if let Some(_linked_role) = BoosterRoleLink::get(&data.db_pool, guild_id, user_id).await? {
+    if BoosterRoleLink::get(&data.db_pool, guild_id, user_id)
+        .await?
+        .is_some()
+    {
         ResponseHelper::send_error(
             ctx,
             "Role is Linked",
-            "Your booster role is managed by an administrator and cannot be removed."
-        ).await?;
+            "Your booster role is managed by an administrator and cannot be removed.",
+        )
+        .await?;
         return Ok(());
     }
-    
-    // Handle role shares if they exist
+
+    // Dry run: show exactly what will be removed before doing it
     let shares = BoosterRoleShare::get_role_shares(&data.db_pool, guild_id, role_id).await?;
     let share_count = shares.len();
-    
-    if share_count > 0 && !remove_shares.unwrap_or(false) {
-        ResponseHelper::send_error(
-            ctx,
-            "Role Has Active Shares",
-            &format!(
-                "This role is currently shared with {} member(s). \
-                Use `/boosterrole remove remove_shares:true` to remove the role and all shares.",
-                share_count
-            )
-        ).await?;
+
+    let mut preview = format!(
+        "This will permanently delete your booster role **{}** (<@&{}>).",
+        role_name, role_id
+    );
+    if share_count > 0 {
+        preview.push_str(&format!(
+            "\n\nIt's shared with {} member(s), who will lose it too.",
+            share_count
+        ));
+    }
+    preview.push_str("\n\nThis can't be undone.");
+
+    let (answer, prompt) = ask_confirmation(
+        ctx,
+        EmbedBuilder::warning("⚠️ Remove Booster Role?", &preview),
+        "Remove Role",
+    )
+    .await?;
+
+    if answer != Confirmation::Confirmed {
+        let reason = if answer == Confirmation::TimedOut {
+            "No answer in time, so your booster role was kept."
+        } else {
+            "Your booster role was kept."
+        };
+        info!(user_id = %user_id, guild_id = %guild_id, answer = ?answer, "Role removal aborted");
+        finish_prompt(ctx, prompt, EmbedBuilder::info("Removal Cancelled", reason)).await?;
         return Ok(());
     }
-    
-    // Remove shares if requested
-    if remove_shares.unwrap_or(false) && share_count > 0 {
-        for share in shares {
-            let shared_user_id = UserId::new(share.shared_with_id as u64);
-            
-            // Remove role from shared user
-            if let Ok(member) = guild_id.member(&ctx.http(), shared_user_id).await {
-                if let Err(e) = member.remove_role(&ctx.http(), role_id).await {
-                    warn!(
-                        "Failed to remove role {} from user {}: {}",
-                        role_id, shared_user_id, e
-                    );
-                }
-            }
-            
-            // Remove share from database
-            BoosterRoleShare::remove(&data.db_pool, guild_id, role_id, shared_user_id).await?;
+
+    // Take the role back from shared members first, so they don't keep it
+    // if the Discord deletion below fails
+    for share in &shares {
+        let shared_user_id = UserId::new(share.shared_with_id as u64);
+        if let Err(e) = ctx
+            .http()
+            .remove_member_role(
+                guild_id,
+                shared_user_id,
+                role_id,
+                Some("Booster role removed by its owner"),
+            )
+            .await
+        {
+            warn!(
+                "Failed to remove role {} from user {}: {}",
+                role_id, shared_user_id, e
+            );
         }
-        
-        info!(
-            guild_id = %guild_id,
-            role_id = %role_id,
-            shares_removed = share_count,
-            "Removed all role shares"
-        );
     }
-    
+
     // Remove the role from Discord
     match guild_id.delete_role(&ctx.http(), role_id).await {
         Ok(_) => {
@@ -124,10 +137,10 @@ pub async fn remove(
             // Continue with database cleanup even if Discord deletion fails
         }
     }
-    
-    // Remove from database
-    BoosterRole::delete(&data.db_pool, guild_id, user_id).await?;
-    
+
+    // Remove from database; active shares are deactivated in the same transaction
+    data.repos.booster_roles.delete(guild_id, user_id).await?;
+
     info!(
         user_id = %user_id,
         guild_id = %guild_id,
@@ -135,17 +148,36 @@ pub async fn remove(
         shares_removed = share_count,
         "Booster role removed successfully"
     );
-    
-    // Send success response
-    let mut description = format!("Your booster role **{}** has been successfully removed.", role_name);
-    if share_count > 0 && remove_shares.unwrap_or(false) {
-        description.push_str(&format!("\n\n{} role share(s) were also removed.", share_count));
-    }
-    
-    ResponseHelper::send_success(
-        ctx,
-        "✅ Role Removed",
-        &description
-    ).await?;
+
+    send_booster_audit(
+        ctx.http(),
+        &data.db_pool,
+        guild_id,
+        booster_audit_embed(
+            "🗑️ Booster Role Deleted",
+            user_id,
+            format!("**{}** (`{}`)", role_name, role_id),
+        )
+        .field("Shares Removed", share_count.to_string(), true),
+    )
+    .await;
+
+    finish_prompt(ctx, prompt, removed_embed(&role_name, share_count)).await?;
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Confirmation that the member's role and its shares are gone
+pub(crate) fn removed_embed(role_name: &str, share_count: usize) -> CreateEmbed {
+    let mut description = format!(
+        "Your booster role **{}** has been successfully removed.",
+        role_name
+    );
+    if share_count > 0 {
+        description.push_str(&format!(
+            "\n\n{} role share(s) were also removed.",
+            share_count
+        ));
+    }
+
+    EmbedBuilder::success("Role Removed", description)
+}
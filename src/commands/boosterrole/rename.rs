@@ -1,13 +1,35 @@
-use crate::data::models::{BoosterRole, BoosterRenameHistory, RoleNameBlacklist};
-use crate::utils::embed_builder::EmbedBuilder;
+use crate::data::models::{BoosterRenameHistory, SettingsAuditLog};
+use crate::utils::args::UserArg;
+use crate::utils::audit::{before_after, booster_audit_embed, send_booster_audit};
+use crate::utils::embed_builder::{DescriptionBuilder, EmbedBuilder};
+use crate::utils::i18n::{locale_for, translate};
+use crate::utils::paginator::paginate_embeds;
+use crate::utils::rename_cooldown::describe_cooldown;
+use crate::utils::{RequestedChange, ResponseHelper};
 use crate::bot::{Context, Error};
-use chrono::{DateTime, Duration, Utc};
-use poise::serenity_prelude::{CreateEmbed, EditRole, RoleId};
+use chrono::Utc;
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, EditRole, Permissions, RoleId};
+use poise::serenity_prelude::Mentionable;
 
-const RENAME_COOLDOWN_MINUTES: i64 = 60;
+/// Renames shown per page of `/boosterrole rename history`
+const HISTORY_PAGE_SIZE: usize = 10;
 
-#[poise::command(slash_command, guild_only)]
-pub async fn rename(
+/// Oldest renames past this many aren't shown
+const MAX_HISTORY_ENTRIES: i64 = 100;
+
+/// Rename your booster role or review past renames
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("rename_set", "rename_history", "rename_clear")
+)]
+pub async fn rename(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Rename your booster role (the server sets how often)
+#[poise::command(slash_command, guild_only, rename = "set")]
+pub async fn rename_set(
     ctx: Context<'_>,
     #[description = "New name for your booster role"]
     #[min_length = 1]
@@ -28,90 +50,65 @@ pub async fn rename(
         .member(&ctx.serenity_context().http, user_id)
         .await?;
 
-    if member.premium_since.is_none() {
-        let embed = EmbedBuilder::error(
-            "❌ Not a Booster",
-            "You must be actively boosting this server to rename your booster role.",
-        );
-
-        ctx.send(poise::CreateReply::default().embed(CreateEmbed::from(embed)))
-            .await?;
+    if !super::ensure_eligible(ctx, guild_id, &member).await? {
         return Ok(());
     }
-
-    let role_record = BoosterRole::get(&ctx.data().db_pool, guild_id, user_id).await?;
-
-    let role_record = match role_record {
-        Some(r) => r,
-        None => {
-            let embed = EmbedBuilder::error(
-                "❌ No Booster Role",
-                "You don't have a booster role yet. Use `/boosterrole color` to create one first.",
-            );
-
-            ctx.send(poise::CreateReply::default().embed(CreateEmbed::from(embed)))
-                .await?;
-            return Ok(());
-        }
+    let Some(_in_flight) = super::begin_role_change(ctx, guild_id).await? else {
+        return Ok(());
     };
 
-    let can_rename = BoosterRenameHistory::check_rate_limit(
-        &ctx.data().db_pool,
+    // Members who can manage the server rename without waiting
+    let guild = guild_id
+        .to_partial_guild(&ctx.serenity_context().http)
+        .await?;
+    let bypass_cooldown = guild.owner_id == user_id
+        || guild
+            .member_permissions(&member)
+            .contains(Permissions::MANAGE_GUILD);
+
+    let filters = ctx.data().name_filters(guild_id).await?;
+    let plan = match super::changes::rename_core(
+        &ctx.data().repos,
         guild_id,
         user_id,
-        RENAME_COOLDOWN_MINUTES,
+        &new_name,
+        bypass_cooldown,
+        &filters,
+        Utc::now(),
     )
-    .await?;
-
-    if !can_rename {
-        let last_rename =
-            BoosterRenameHistory::get_last_rename(&ctx.data().db_pool, guild_id, user_id).await?;
-
-        if let Some(last) = last_rename {
-            if let Ok(last_time) = DateTime::parse_from_rfc3339(&last.renamed_at) {
-                let last_utc: DateTime<Utc> = last_time.with_timezone(&Utc);
-                let cooldown_end = last_utc + Duration::minutes(RENAME_COOLDOWN_MINUTES);
-                let now = Utc::now();
-
-                if cooldown_end > now {
-                    let remaining = cooldown_end - now;
-                    let minutes_left = remaining.num_minutes();
-
-                    let embed = EmbedBuilder::error(
-                        "⏱️ Cooldown Active",
-                        &format!(
-                            "You can rename your role again in **{} minutes**.\n\nLast rename: {} → {}",
-                            minutes_left, last.old_name, last.new_name
-                        ),
-                    );
-
-                    tracing::warn!(
-                        user_id = %user_id,
-                        cooldown_remaining = ?remaining,
-                        "Rename rate limit hit"
-                    );
-
-                    ctx.send(poise::CreateReply::default().embed(CreateEmbed::from(embed)))
-                        .await?;
-                    return Ok(());
-                }
+    .await?
+    {
+        Ok(plan) => plan,
+        Err(refusal) => {
+            if let super::changes::Refusal::RenameCooldown { remaining, .. } = &refusal {
+                tracing::warn!(
+                    user_id = %user_id,
+                    cooldown_remaining = ?remaining,
+                    "Rename rate limit hit"
+                );
             }
+            super::refuse(ctx, guild_id, &new_name, refusal).await?;
+            return Ok(());
         }
+    };
+    let new_name = plan.new_name.clone();
+    if !super::ensure_not_staff_lookalike(ctx, guild_id, &new_name).await? {
+        return Ok(());
     }
 
-    if RoleNameBlacklist::is_blacklisted(&ctx.data().db_pool, guild_id, &new_name).await? {
-        let embed = EmbedBuilder::error(
-            "🚫 Name Not Allowed",
-            "This name contains blacklisted words and cannot be used.",
-        );
-
-        ctx.send(poise::CreateReply::default().embed(CreateEmbed::from(embed)))
-            .await?;
+    if !super::lock::ensure_unlocked(
+        ctx,
+        guild_id,
+        RoleId::new(plan.role.role_id as u64),
+        RequestedChange::name(),
+    )
+    .await?
+    {
         return Ok(());
     }
 
-    let role_id = RoleId::new(role_record.role_id as u64);
-    let old_name = role_record.role_name.clone();
+    let role_id = RoleId::new(plan.role.role_id as u64);
+    let old_name = plan.role.role_name.clone();
 
     if let Err(e) = guild_id
         .edit_role(&ctx.serenity_context().http, role_id, EditRole::new().name(&new_name))
@@ -134,33 +131,37 @@ pub async fn rename(
         return Ok(());
     }
 
-    BoosterRole::update(
-        &ctx.data().db_pool,
-        guild_id,
-        user_id,
-        &new_name,
-        &role_record.primary_color,
-        role_record.secondary_color.as_deref(),
-    )
-    .await?;
+    super::changes::record_rename(&ctx.data().repos, guild_id, user_id, &plan, &new_name).await?;
 
-    BoosterRenameHistory::add(
+    send_booster_audit(
+        &ctx.serenity_context().http,
         &ctx.data().db_pool,
         guild_id,
-        user_id,
-        &old_name,
-        &new_name,
+        booster_audit_embed("✏️ Booster Role Renamed", user_id, format!("<@&{}>", role_id))
+            .field("Name", before_after(&old_name, &new_name), false),
     )
-    .await?;
+    .await;
 
-    let embed = EmbedBuilder::success(
-        "✅ Role Renamed",
-        &format!("Your booster role has been renamed from **{}** to **{}**.", old_name, new_name),
-    )
-    .footer(poise::serenity_prelude::CreateEmbedFooter::new(format!(
-        "You can rename again in {} minutes",
-        RENAME_COOLDOWN_MINUTES
-    )));
+    let locale = locale_for(ctx).await;
+    let mut embed = EmbedBuilder::success(
+        translate(locale, "booster.renamed.title", &[]),
+        translate(
+            locale,
+            "booster.renamed.body",
+            &[("old_name", &old_name), ("new_name", &new_name)],
+        ),
+    );
+    if !bypass_cooldown && plan.cooldown_minutes > 0 {
+        embed = embed.footer(poise::serenity_prelude::CreateEmbedFooter::new(translate(
+            locale,
+            "booster.renamed.footer",
+            &[("cooldown", &describe_cooldown(plan.cooldown_minutes))],
+        )));
+    }
+    if let Some(note) = plan.name_check.caution_note() {
+        embed = embed.field(translate(locale, "booster.caution", &[]), note, false);
+    }
+    let embed = ResponseHelper::with_next_steps(ctx, embed).await;
 
     ctx.send(poise::CreateReply::default().embed(CreateEmbed::from(embed)))
         .await?;
@@ -174,4 +175,105 @@ pub async fn rename(
     );
 
     Ok(())
-}
\ No newline at end of file
+}
+/// See past renames of your booster role (staff can view anyone's)
+#[poise::command(slash_command, guild_only, rename = "history")]
+pub async fn rename_history(
+    ctx: Context<'_>,
+    #[description = "Member whose renames to show (staff only; default: you)"] user: Option<
+        UserArg,
+    >,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+    let target = user.map(|u| u.0).unwrap_or_else(|| ctx.author().clone());
+
+    if target.id != ctx.author().id && !crate::commands::settings::is_staff(&ctx).await? {
+        ResponseHelper::send_error_ephemeral(
+            ctx,
+            "❌ Staff Only",
+            "Only staff can view another member's rename history.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let total = BoosterRenameHistory::count_for_user(pool, guild_id, target.id).await?;
+    if total == 0 {
+        ResponseHelper::send_info(
+            ctx,
+            "📜 No Renames",
+            &format!("{} hasn't renamed a booster role yet.", target.mention()),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let shown = total.min(MAX_HISTORY_ENTRIES);
+    let entries =
+        BoosterRenameHistory::get_history(pool, guild_id, target.id, shown, 0).await?;
+
+    let lines = entries.iter().map(|entry| {
+        let when = format!("<t:{}:R>", entry.renamed_at().timestamp());
+        format!("**{}** → **{}** · {}", entry.old_name, entry.new_name, when)
+    });
+
+    let pages = DescriptionBuilder::new()
+        .max_lines(HISTORY_PAGE_SIZE)
+        .lines(lines)
+        .build(|description, page, page_count| {
+            let mut footer = format!("Page {}/{} · {} rename(s)", page, page_count, total);
+            if total > shown {
+                footer.push_str(&format!(" · showing the latest {}", shown));
+            }
+            EmbedBuilder::primary(format!("📜 Rename History for {}", target.name), description)
+                .footer(CreateEmbedFooter::new(footer))
+        });
+
+    paginate_embeds(ctx, pages).await
+}
+
+/// Wipe a member's rename history (Manage Server)
+#[poise::command(
+    slash_command,
+    guild_only,
+    rename = "clear",
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn rename_clear(
+    ctx: Context<'_>,
+    #[description = "Member whose rename history to clear"] user: UserArg,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    let removed = BoosterRenameHistory::clear_for_user(pool, guild_id, user.id).await?;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "rename_history_cleared",
+        Some(&format!("Member: {}, entries removed: {}", user.id, removed)),
+    )
+    .await?;
+
+    tracing::info!(
+        guild_id = %guild_id,
+        target_id = %user.id,
+        removed,
+        "Rename history cleared"
+    );
+
+    ResponseHelper::send_success(
+        ctx,
+        "✅ Rename History Cleared",
+        &format!(
+            "Removed {} rename(s) from {}'s history.",
+            removed,
+            user.mention()
+        ),
+    )
+    .await?;
+    Ok(())
+}
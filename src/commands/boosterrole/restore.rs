@@ -0,0 +1,158 @@
+use crate::bot::{Context, Error};
+use crate::data::models::BoosterRoleArchive;
+use crate::utils::audit::{booster_audit_embed, send_booster_audit};
+use crate::utils::role_archive::restore_archived_role;
+use crate::utils::{ColorParser, ResponseHelper};
+use serenity::all::{CreateEmbed, Mentionable, Timestamp};
+use tracing::{error, info, instrument};
+
+/// Bring back the booster role you had before your boost lapsed
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    category = "Booster Roles",
+    required_bot_permissions = "MANAGE_ROLES",
+    description_localized(
+        "en-US",
+        "Bring back the booster role you had before your boost lapsed"
+    )
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.restore"
+    )
+)]
+pub async fn restore(ctx: Context<'_>) -> Result<(), Error> {
+    info!("Restore booster role command invoked");
+
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+    let user_id = ctx.author().id;
+    let data = ctx.data();
+
+    ctx.defer().await?;
+
+    let member = guild_id
+        .member(&ctx.serenity_context().http, user_id)
+        .await
+        .map_err(|e| Error::Command(format!("Failed to get member information: {}", e)))?;
+
+    if !super::ensure_eligible(ctx, guild_id, &member).await? {
+        return Ok(());
+    }
+    let Some(_in_flight) = super::begin_role_change(ctx, guild_id).await? else {
+        return Ok(());
+    };
+
+    let Some(archive) = BoosterRoleArchive::get(&data.db_pool, guild_id, user_id).await? else {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Nothing to Restore",
+            "There's no saved booster role for you in this server. Roles are kept for a while after a boost lapses, then removed.",
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if data
+        .repos
+        .booster_roles
+        .get(guild_id, user_id)
+        .await?
+        .is_some()
+    {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Role Already Exists",
+            "You already have a custom booster role. Use `/boosterrole remove` first if you'd rather have your old one back.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if !super::ensure_under_limit(ctx, guild_id).await? {
+        return Ok(());
+    }
+
+    let restored = match restore_archived_role(
+        ctx.serenity_context(),
+        &data.db_pool,
+        guild_id,
+        user_id,
+        &archive,
+    )
+    .await
+    {
+        Ok(restored) => restored,
+        Err(e) => {
+            error!(
+                guild_id = %guild_id,
+                error = ?e,
+                "Failed to restore archived booster role"
+            );
+
+            ResponseHelper::send_error(
+                ctx,
+                "❌ Restore Failed",
+                &format!(
+                    "Your role couldn't be restored: {}\n\nYour saved role is still kept, so you can try again.",
+                    e
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    send_booster_audit(
+        ctx.http(),
+        &data.db_pool,
+        guild_id,
+        booster_audit_embed(
+            "♻️ Booster Role Restored",
+            user_id,
+            restored.role.mention().to_string(),
+        )
+        .field("Name", &restored.name, true)
+        .field("Color", &restored.primary_color, true)
+        .field("Icon", restored.icon.describe(), true),
+    )
+    .await;
+
+    let color = ColorParser::parse(&restored.primary_color).unwrap_or_default();
+    let mut embed = CreateEmbed::new()
+        .title("♻️ Booster Role Restored")
+        .description(format!(
+            "Welcome back! Your role {} has been recreated and assigned.",
+            restored.role.mention()
+        ))
+        .color(color)
+        .field("Name", &restored.name, true)
+        .field("Color", format!("`{}`", restored.primary_color), true);
+
+    if let Some(secondary) = &restored.secondary_color {
+        embed = embed.field("Second Color", format!("`{}`", secondary), true);
+    }
+    embed = embed.field("Icon", restored.icon.describe(), false);
+    if let Some(saved) = archive
+        .archived_at
+    {
+        embed = embed.field("Saved", format!("<t:{}:R>", saved.timestamp()), true);
+    }
+    embed = embed.timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    info!(
+        role_id = %restored.role.id,
+        guild_id = %guild_id,
+        "Booster role restored by member"
+    );
+
+    Ok(())
+}
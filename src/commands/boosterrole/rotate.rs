@@ -0,0 +1,280 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{ColorRotation, GuildRotationSettings};
+use crate::utils::color_rotation::{
+    check_rotation, step, RotationSettings, MAX_INTERVAL_HOURS, MIN_INTERVAL_FLOOR_HOURS,
+};
+use crate::utils::scheduled_change::to_stored;
+use crate::utils::{ColorParser, RequestedChange, ResponseHelper, RoleManager};
+use poise::serenity_prelude::RoleId;
+use tracing::instrument;
+
+/// Cycle your booster role through a palette of colors
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    category = "Booster Roles",
+    subcommands("rotate_enable", "rotate_disable", "rotate_settings"),
+    description_localized("en-US", "Cycle your booster role through a palette of colors")
+)]
+pub async fn rotate(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Start cycling your role through 2 to 5 colors on a schedule
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "enable",
+    category = "Booster Roles",
+    required_bot_permissions = "MANAGE_ROLES",
+    description_localized("en-US", "Start cycling your role through 2 to 5 colors on a schedule")
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.rotate.enable"
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+pub async fn rotate_enable(
+    ctx: Context<'_>,
+    #[description = "First color (hex code or color name)"] color1: String,
+    #[description = "Second color"] color2: String,
+    #[description = "Third color"] color3: Option<String>,
+    #[description = "Fourth color"] color4: Option<String>,
+    #[description = "Fifth color"] color5: Option<String>,
+    #[description = "Hours between changes (defaults to the server's minimum)"]
+    #[min = 1]
+    #[max = 168]
+    every_hours: Option<i64>,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+    let user_id = ctx.author().id;
+    let pool = &ctx.data().db_pool;
+
+    let mut palette = Vec::new();
+    for color in [Some(color1), Some(color2), color3, color4, color5]
+        .into_iter()
+        .flatten()
+    {
+        match ColorParser::parse(&color) {
+            Ok(parsed) => palette.push(parsed),
+            Err(e) => {
+                ResponseHelper::send_error(ctx, "❌ Invalid Color", &e.to_string()).await?;
+                return Ok(());
+            }
+        }
+    }
+
+    let member = guild_id
+        .member(&ctx.serenity_context().http, user_id)
+        .await?;
+    if !super::ensure_eligible(ctx, guild_id, &member).await? {
+        return Ok(());
+    }
+    let Some(_in_flight) = super::begin_role_change(ctx, guild_id).await? else {
+        return Ok(());
+    };
+
+    let Some(role) = ctx.data().repos.booster_roles.get(guild_id, user_id).await? else {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ No Booster Role",
+            "You don't have a booster role yet. Use `/boosterrole color` to create one first.",
+        )
+        .await?;
+        return Ok(());
+    };
+    let role_id = RoleId::new(role.role_id as u64);
+    if !super::lock::ensure_unlocked(ctx, guild_id, role_id, RequestedChange::color()).await? {
+        return Ok(());
+    }
+
+    let settings = GuildRotationSettings::get(pool, guild_id).await?;
+    let interval_hours = every_hours.unwrap_or(settings.min_interval_hours);
+    let rotating = ColorRotation::count_for_guild(pool, guild_id).await?;
+    let already_rotating = ColorRotation::get(pool, guild_id, user_id).await?.is_some();
+    if let Err(refusal) = check_rotation(settings, rotating, already_rotating, interval_hours) {
+        ResponseHelper::send_error(ctx, "❌ Can't Rotate Colors", &refusal.to_string()).await?;
+        return Ok(());
+    }
+
+    // The first color goes on now; the schedule picks up from the second
+    let Some((first, next_index)) = step(&palette, 0) else {
+        return Ok(());
+    };
+    RoleManager::update_booster_role(
+        ctx.serenity_context(),
+        guild_id,
+        role_id,
+        &role.role_name,
+        first,
+    )
+    .await?;
+    ctx.data()
+        .repos
+        .booster_roles
+        .update(
+            guild_id,
+            user_id,
+            &role.role_name,
+            &ColorParser::to_hex_string(first),
+            role.secondary_color.as_deref(),
+        )
+        .await?;
+
+    let next_run_at = chrono::Utc::now() + chrono::Duration::hours(interval_hours);
+    ColorRotation::enable(
+        pool,
+        guild_id,
+        user_id,
+        &palette,
+        interval_hours * 3600,
+        next_index,
+        &to_stored(next_run_at),
+    )
+    .await?;
+
+    let colors: Vec<String> = palette
+        .iter()
+        .map(|&color| format!("`{}`", ColorParser::to_hex_string(color)))
+        .collect();
+    ResponseHelper::send_success(
+        ctx,
+        "🎉 Party Mode On",
+        &format!(
+            "Your role will cycle through {} every **{}h**.\nNext change <t:{}:R>. Stop any time with `/boosterrole rotate disable`.",
+            colors.join(" → "),
+            interval_hours,
+            next_run_at.timestamp()
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Stop cycling your role's color; it keeps the color it has now
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "disable",
+    category = "Booster Roles",
+    description_localized("en-US", "Stop cycling your role's color; it keeps the color it has now")
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.rotate.disable"
+    )
+)]
+pub async fn rotate_disable(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+
+    if ColorRotation::disable(&ctx.data().db_pool, guild_id, ctx.author().id).await? {
+        ResponseHelper::send_success(
+            ctx,
+            "✅ Party Mode Off",
+            "Your role will keep its current color.",
+        )
+        .await?;
+    } else {
+        ResponseHelper::send_info(
+            ctx,
+            "ℹ️ Not Rotating",
+            "Your role isn't cycling colors. Start with `/boosterrole rotate enable`.",
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Set how often roles may change color and how many members may rotate (Admin only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "settings",
+    category = "Booster Roles",
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Set how often roles may change color and how many members may rotate"
+    )
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.rotate.settings"
+    )
+)]
+pub async fn rotate_settings(
+    ctx: Context<'_>,
+    #[description = "Shortest time between color changes, in hours (at least 1)"]
+    #[min = 1]
+    #[max = 168]
+    min_interval_hours: Option<i64>,
+    #[description = "Members who may rotate at once (0 turns rotation off)"]
+    #[min = 0]
+    #[max = 100]
+    max_members: Option<i64>,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+    let pool = &ctx.data().db_pool;
+
+    let current = GuildRotationSettings::get(pool, guild_id).await?;
+    let rotating = ColorRotation::count_for_guild(pool, guild_id).await?;
+
+    if min_interval_hours.is_none() && max_members.is_none() {
+        ResponseHelper::send_info(
+            ctx,
+            "🎉 Color Rotation Settings",
+            &describe(current, rotating),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let updated = RotationSettings {
+        min_interval_hours: min_interval_hours
+            .unwrap_or(current.min_interval_hours)
+            .clamp(MIN_INTERVAL_FLOOR_HOURS, MAX_INTERVAL_HOURS),
+        max_members: max_members.unwrap_or(current.max_members),
+    };
+    GuildRotationSettings::set(pool, guild_id, updated, ctx.author().id).await?;
+
+    let mut description = describe(updated, rotating);
+    if rotating > updated.max_members {
+        description.push_str(
+            "\n\nMembers already rotating keep going; nobody new can start until there's room.",
+        );
+    }
+    ResponseHelper::send_success(ctx, "✅ Color Rotation Updated", &description).await?;
+    Ok(())
+}
+
+/// The guild's rotation limits and how much of the cap is used
+fn describe(settings: RotationSettings, rotating: i64) -> String {
+    if settings.max_members == 0 {
+        return "Color rotation is **off**; members who were rotating stop at their next change."
+            .to_string();
+    }
+    format!(
+        "Minimum interval: **{}h**\nMembers rotating: **{}/{}**",
+        settings.min_interval_hours, rotating, settings.max_members
+    )
+}
@@ -0,0 +1,226 @@
+use crate::bot::{Context, Data, Error};
+use crate::data::models::{GuildNamingRules, RulesAcknowledgment, SettingsAuditLog};
+use crate::utils::rules_ack::MAX_RULES_LENGTH;
+use crate::utils::{EmbedColor, ResponseHelper};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, Timestamp};
+use std::time::Duration;
+use tracing::instrument;
+
+/// How long the rules modal stays open
+const MODAL_TIMEOUT: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum RulesRequirement {
+    #[name = "on"]
+    On,
+    #[name = "off"]
+    Off,
+}
+
+#[derive(Debug, poise::Modal)]
+#[name = "Booster Role Naming Rules"]
+struct RulesModal {
+    #[name = "Rules members must agree to"]
+    #[placeholder = "e.g. No slurs, no impersonating staff, keep names readable"]
+    #[paragraph]
+    #[min_length = 1]
+    #[max_length = 2000]
+    rules: String,
+}
+
+/// Naming rules boosters must agree to before customizing their role
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    category = "Booster Roles",
+    subcommands("rules_set", "rules_require", "rules_view"),
+    description_localized("en-US", "Manage the naming rules boosters agree to")
+)]
+pub async fn rules(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Write or update the naming rules (Admin only)
+#[poise::command(
+    slash_command,
+    guild_only,
+    rename = "set",
+    category = "Booster Roles",
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Write the naming rules; members agree again after every update"
+    )
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.rules.set"
+    )
+)]
+pub async fn rules_set(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+    let pool = &ctx.data().db_pool;
+
+    // Prefill the modal so small edits don't mean retyping everything
+    let current = GuildNamingRules::get(pool, guild_id).await?;
+    let defaults = current.as_ref().map(|r| RulesModal {
+        rules: r.rules_text.clone(),
+    });
+
+    let Some(modal) = poise::execute_modal(ctx, defaults, Some(MODAL_TIMEOUT)).await? else {
+        return Ok(());
+    };
+    let text = modal.rules.trim();
+    if text.is_empty() || text.len() > MAX_RULES_LENGTH {
+        ResponseHelper::send_error(
+            ctx.into(),
+            "❌ Invalid Rules",
+            &format!("Rules must be 1–{} characters.", MAX_RULES_LENGTH),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let version = GuildNamingRules::set_text(pool, guild_id, text, ctx.author().id).await?;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "naming_rules_set",
+        Some(&format!("Version {}", version)),
+    )
+    .await?;
+
+    let required = current.is_some_and(|r| r.require_ack);
+    let follow_up = if required {
+        "Members will be asked to agree to this version the next time they customize their role."
+    } else {
+        "Turn on `/boosterrole rules require on` to make members agree before customizing."
+    };
+    ResponseHelper::send_success(
+        ctx.into(),
+        "📜 Naming Rules Saved",
+        &format!(
+            "Saved version **{}** of the naming rules.\n{}",
+            version, follow_up
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Require members to agree to the rules before customizing (Admin only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "require",
+    category = "Booster Roles",
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Require agreement to the naming rules before role customization"
+    )
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.rules.require"
+    )
+)]
+pub async fn rules_require(
+    ctx: Context<'_>,
+    #[description = "Require agreement before customization"] requirement: RulesRequirement,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+    let pool = &ctx.data().db_pool;
+
+    let required = matches!(requirement, RulesRequirement::On);
+    if !GuildNamingRules::set_required(pool, guild_id, required, ctx.author().id).await? {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ No Rules Yet",
+            "Write the naming rules with `/boosterrole rules set` first.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "naming_rules_required",
+        Some(if required { "On" } else { "Off" }),
+    )
+    .await?;
+
+    let description = if required {
+        "Members must agree to the naming rules before customizing their booster role."
+    } else {
+        "Members can customize their booster role without agreeing to the naming rules."
+    };
+    ResponseHelper::send_success(ctx, "📜 Naming Rules", description).await?;
+    Ok(())
+}
+
+/// Show the current naming rules
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "view",
+    category = "Booster Roles",
+    description_localized("en-US", "Show the server's booster role naming rules")
+)]
+pub async fn rules_view(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+    let pool = &ctx.data().db_pool;
+
+    let Some(rules) = GuildNamingRules::get(pool, guild_id).await? else {
+        ResponseHelper::send_info(
+            ctx,
+            "📜 Naming Rules",
+            "This server hasn't written any naming rules.",
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let agreed = RulesAcknowledgment::get_version(pool, guild_id, ctx.author().id)
+        .await?
+        .is_some_and(|version| version >= rules.version);
+    let footer = match (rules.require_ack, agreed) {
+        (true, true) => format!("Version {} • You have agreed to these rules", rules.version),
+        (true, false) => format!(
+            "Version {} • You'll be asked to agree before customizing",
+            rules.version
+        ),
+        (false, _) => format!("Version {}", rules.version),
+    };
+
+    let embed = rules_embed(&rules.rules_text).footer(CreateEmbedFooter::new(footer));
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+pub(crate) fn rules_embed(text: &str) -> CreateEmbed {
+    CreateEmbed::new()
+        .title("📜 Booster Role Naming Rules")
+        .description(text)
+        .color(EmbedColor::Info.value())
+        .timestamp(Timestamp::now())
+}
@@ -0,0 +1,139 @@
+//! One representative reply from each subcommand, built from placeholder data
+//! so `/test_responses` can check them without a live server.
+
+use super::changes::Refusal;
+use super::dominant::ColorSource;
+use crate::data::models::{BoosterRole, GuildBoosterOptions};
+use crate::utils::history_export::{HistoryEntry, UserHistoryReport};
+use crate::utils::i18n::Locale;
+use crate::utils::role_export::RoleExport;
+use crate::utils::role_lock::LockedAspect;
+use chrono::TimeZone;
+use poise::serenity_prelude::{Colour, CreateEmbed, RoleId, User, UserId};
+
+const MEMBER_ID: u64 = 200_000_000_000_000_001;
+const ADMIN_ID: u64 = 200_000_000_000_000_002;
+const ROLE_ID: u64 = 300_000_000_000_000_001;
+
+/// `(command, embed)` for every subcommand with a context-free builder
+pub fn response_samples() -> Vec<(&'static str, CreateEmbed)> {
+    let locale = Locale::default();
+    let member = sample_user(MEMBER_ID, "booster");
+    let admin = sample_user(ADMIN_ID, "admin");
+    let role = BoosterRole {
+        id: 1,
+        guild_id: 1,
+        user_id: MEMBER_ID as i64,
+        role_id: ROLE_ID as i64,
+        role_name: "Sunset".to_string(),
+        primary_color: "#FF7F50".to_string(),
+        secondary_color: Some("#8A2BE2".to_string()),
+        created_at: chrono::Utc.with_ymd_and_hms(2024, 5, 1, 9, 0, 0).single(),
+        updated_at: None,
+        icon_url: None,
+    };
+    let history = UserHistoryReport::new(
+        1,
+        MEMBER_ID,
+        chrono::Utc::now(),
+        vec![HistoryEntry {
+            at: "2024-05-02 10:00:00".to_string(),
+            source: "booster_rename_history",
+            event: "rename".to_string(),
+            detail: "Dawn -> Sunset".to_string(),
+        }],
+    );
+    let export = RoleExport {
+        contents: String::new(),
+        rows_written: 42,
+        rows_dropped: 0,
+    };
+
+    vec![
+        (
+            "boosterrole admin",
+            super::admin::deleted_embed(member.id, &role.role_name),
+        ),
+        (
+            "boosterrole base",
+            super::base::current_base_embed(RoleId::new(ROLE_ID), "Server Booster", 12),
+        ),
+        (
+            "boosterrole cleanup",
+            super::cleanup::nothing_to_clean_embed("Ran <t:1714550400:R>"),
+        ),
+        (
+            "boosterrole color",
+            Refusal::InvalidColor("`#GGGGGG` is not a hex color".to_string()).embed(locale),
+        ),
+        (
+            "boosterrole dominant",
+            super::dominant::create_dual_color_success_embed(
+                0xFF7F50,
+                0x8A2BE2,
+                Colour::new(0xFF7F50),
+                &ColorSource::Avatar(member.face()),
+            ),
+        ),
+        (
+            "boosterrole export",
+            super::export::export_embed(&export, 1),
+        ),
+        (
+            "boosterrole history",
+            super::history::export_embed(&history, member.id),
+        ),
+        (
+            "boosterrole imagecolor",
+            super::imagecolor::pick_color_embed(0xFF7F50, 0x8A2BE2),
+        ),
+        (
+            "boosterrole limit",
+            super::limit::usage_embed(7, Some(10), &GuildBoosterOptions::default()),
+        ),
+        (
+            "boosterrole link",
+            super::link::link_created_embed("created", &member, None, RoleId::new(ROLE_ID), &admin),
+        ),
+        (
+            "boosterrole lock",
+            super::lock::locked_embed(LockedAspect::Name, admin.id.get() as i64),
+        ),
+        (
+            "boosterrole remove",
+            super::remove::removed_embed(&role.role_name, 2),
+        ),
+        (
+            "boosterrole rename",
+            Refusal::RenameCooldown {
+                remaining: chrono::Duration::minutes(42),
+                old_name: "Dawn".to_string(),
+                new_name: role.role_name.clone(),
+            }
+            .embed(locale),
+        ),
+        (
+            "boosterrole rules",
+            super::rules::rules_embed("Keep names friendly and readable."),
+        ),
+        (
+            "boosterrole share",
+            super::share::shares_embed(&[format!("<@&{}> from <@{}>", ROLE_ID, MEMBER_ID)], &[]),
+        ),
+        (
+            "boosterrole stats",
+            super::stats::stats_embed(&member, &role, 3, 1, "Not boosting".to_string()),
+        ),
+        (
+            "boosterrole template",
+            super::template::template_help_embed(),
+        ),
+    ]
+}
+
+fn sample_user(id: u64, name: &str) -> User {
+    let mut user = User::default();
+    user.id = UserId::new(id);
+    user.name = name.to_string();
+    user
+}
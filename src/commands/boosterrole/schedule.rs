@@ -0,0 +1,338 @@
+use crate::bot::{Context, Error};
+use crate::data::models::ScheduledRoleChange;
+use crate::handlers::scheduled_change::validate_change;
+use crate::utils::scheduled_change::{
+    parse_duration, parse_when, to_stored, ScheduleStatus, MAX_ACTIVE_PER_USER,
+    MAX_REVERT_AFTER_DAYS, MISSED_GRACE_HOURS,
+};
+use crate::utils::{ColorParser, EmbedColor, ResponseHelper};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, Timestamp};
+use tracing::instrument;
+
+/// Schedule a one-time change to your booster role
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    category = "Booster Roles",
+    subcommands("schedule_once", "schedule_list", "schedule_cancel", "schedule_all"),
+    description_localized("en-US", "Schedule a one-time change to your booster role")
+)]
+pub async fn schedule(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Change your role's name and/or color at a future time, optionally switching back later
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "once",
+    category = "Booster Roles",
+    description_localized(
+        "en-US",
+        "Change your role's name or color later, optionally switching back afterwards"
+    )
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.schedule.once"
+    )
+)]
+pub async fn schedule_once(
+    ctx: Context<'_>,
+    #[description = "When to change: a delay like 3d or 'in 2h', or a UTC time like 2025-06-14 18:00"]
+    when: String,
+    #[description = "Name to switch to"]
+    #[min_length = 1]
+    #[max_length = 100]
+    name: Option<String>,
+    #[description = "Color to switch to (hex code or color name)"] color: Option<String>,
+    #[description = "Switch back after this long, e.g. 2d or 12h"] revert_after: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+    let user_id = ctx.author().id;
+    let pool = &ctx.data().db_pool;
+
+    if name.is_none() && color.is_none() {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Nothing To Change",
+            "Give a `name`, a `color`, or both.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let member = guild_id
+        .member(&ctx.serenity_context().http, user_id)
+        .await?;
+    if !super::ensure_eligible(ctx, guild_id, &member).await? {
+        return Ok(());
+    }
+
+    let Some(role) = ctx.data().repos.booster_roles.get(guild_id, user_id).await? else {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ No Booster Role",
+            "You don't have a booster role yet. Use `/boosterrole color` to create one first.",
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let now = chrono::Utc::now();
+    let at = match parse_when(&when, now) {
+        Ok(at) => at,
+        Err(e) => {
+            ResponseHelper::send_error(ctx, "❌ Invalid Time", &e).await?;
+            return Ok(());
+        }
+    };
+
+    let revert_after = match revert_after.as_deref().map(parse_duration).transpose() {
+        Ok(Some(after)) if after > chrono::Duration::days(MAX_REVERT_AFTER_DAYS) => {
+            ResponseHelper::send_error(
+                ctx,
+                "❌ Invalid Revert Time",
+                &format!(
+                    "Changes can switch back at most {} days after they apply.",
+                    MAX_REVERT_AFTER_DAYS
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+        Ok(after) => after,
+        Err(e) => {
+            ResponseHelper::send_error(ctx, "❌ Invalid Revert Time", &e).await?;
+            return Ok(());
+        }
+    };
+
+    let active = ScheduledRoleChange::active_for_user(pool, guild_id, user_id).await?;
+    if active.len() as i64 >= MAX_ACTIVE_PER_USER {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Too Many Scheduled Changes",
+            &format!(
+                "You can have {} scheduled changes at once. Cancel one with `/boosterrole schedule cancel` first.",
+                MAX_ACTIVE_PER_USER
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let target = match validate_change(
+        &ctx.serenity_context().http,
+        pool,
+        guild_id,
+        &role,
+        name.as_deref(),
+        color.as_deref(),
+        "boosterrole schedule once",
+    )
+    .await?
+    {
+        Ok(target) => target,
+        Err(reason) => {
+            ResponseHelper::send_error(ctx, "❌ Change Not Allowed", &reason).await?;
+            return Ok(());
+        }
+    };
+
+    // Store the normalized color so execution doesn't depend on parser changes
+    let color_hex = color.map(|_| ColorParser::to_hex_string(target.color));
+    let id = ScheduledRoleChange::create(
+        pool,
+        guild_id,
+        user_id,
+        name.as_deref(),
+        color_hex.as_deref(),
+        &to_stored(at),
+        revert_after.map(|after| after.num_seconds()),
+    )
+    .await?;
+
+    let mut description = format!(
+        "Change **#{}**: your role becomes **{}** in `{}` <t:{}:F> (<t:{}:R>).",
+        id,
+        target.name,
+        ColorParser::to_hex_string(target.color),
+        at.timestamp(),
+        at.timestamp()
+    );
+    if let Some(after) = revert_after {
+        description.push_str(&format!(
+            "\nIt switches back to what you have at that moment {} later.",
+            describe_duration(after)
+        ));
+    }
+    description.push_str(&format!(
+        "\n\nThe name and color are checked again when the change runs. If the bot is offline for more than {} hours past the time, the change is dropped.",
+        MISSED_GRACE_HOURS
+    ));
+
+    ResponseHelper::send_success(ctx, "🗓️ Change Scheduled", &description).await?;
+    Ok(())
+}
+
+/// List your scheduled role changes
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "list",
+    category = "Booster Roles",
+    description_localized("en-US", "List your scheduled booster role changes")
+)]
+pub async fn schedule_list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+
+    let changes =
+        ScheduledRoleChange::active_for_user(&ctx.data().db_pool, guild_id, ctx.author().id)
+            .await?;
+    if changes.is_empty() {
+        ResponseHelper::send_info(
+            ctx,
+            "🗓️ Scheduled Changes",
+            "You have no scheduled changes. Add one with `/boosterrole schedule once`.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let lines: Vec<String> = changes.iter().map(|c| describe_change(c, false)).collect();
+    send_change_list(ctx, "🗓️ Your Scheduled Changes", lines).await
+}
+
+/// Cancel a scheduled change, or the switch-back of one that already applied
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "cancel",
+    category = "Booster Roles",
+    description_localized(
+        "en-US",
+        "Cancel a scheduled change or the switch-back of an applied one"
+    )
+)]
+pub async fn schedule_cancel(
+    ctx: Context<'_>,
+    #[description = "Change number from /boosterrole schedule list"] id: i64,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+
+    if ScheduledRoleChange::cancel(&ctx.data().db_pool, guild_id, ctx.author().id, id).await? {
+        ResponseHelper::send_success(
+            ctx,
+            "🗓️ Change Cancelled",
+            &format!("Scheduled change **#{}** was cancelled.", id),
+        )
+        .await?;
+    } else {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Not Found",
+            &format!(
+                "You have no active scheduled change **#{}**. See `/boosterrole schedule list`.",
+                id
+            ),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// List every scheduled role change in the server (Admin only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "all",
+    category = "Booster Roles",
+    required_permissions = "MANAGE_GUILD",
+    description_localized("en-US", "List every scheduled booster role change in the server")
+)]
+pub async fn schedule_all(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+
+    let changes = ScheduledRoleChange::active_for_guild(&ctx.data().db_pool, guild_id).await?;
+    if changes.is_empty() {
+        ResponseHelper::send_info(
+            ctx,
+            "🗓️ Scheduled Changes",
+            "No booster role changes are scheduled in this server.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let lines: Vec<String> = changes.iter().map(|c| describe_change(c, true)).collect();
+    send_change_list(ctx, "🗓️ Scheduled Changes", lines).await
+}
+
+async fn send_change_list(ctx: Context<'_>, title: &str, lines: Vec<String>) -> Result<(), Error> {
+    let total = lines.len();
+    let embed = CreateEmbed::new()
+        .title(title)
+        .description(lines.into_iter().take(20).collect::<Vec<_>>().join("\n"))
+        .color(EmbedColor::Info.value())
+        .footer(CreateEmbedFooter::new(format!(
+            "{} active • cancel with /boosterrole schedule cancel <id>",
+            total
+        )))
+        .timestamp(Timestamp::now());
+
+    ResponseHelper::send_embed(ctx, embed).await?;
+    Ok(())
+}
+
+fn describe_change(change: &ScheduledRoleChange, with_owner: bool) -> String {
+    let mut what = Vec::new();
+    if let Some(name) = &change.new_name {
+        what.push(format!("**{}**", name));
+    }
+    if let Some(color) = &change.new_color {
+        what.push(format!("`{}`", color));
+    }
+
+    let when = match change.status() {
+        ScheduleStatus::Applied => match change.revert_at {
+            Some(at) => format!("applied • switches back <t:{}:R>", at.timestamp()),
+            None => "applied".to_string(),
+        },
+        _ => format!("<t:{}:F>", change.scheduled_at.timestamp()),
+    };
+
+    let owner = if with_owner {
+        format!("<@{}> • ", change.user_id)
+    } else {
+        String::new()
+    };
+    format!("`#{}` {}{} • {}", change.id, owner, what.join(" in "), when)
+}
+
+fn describe_duration(duration: chrono::Duration) -> String {
+    let minutes = duration.num_minutes();
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    let parts: Vec<String> = [(days, "d"), (hours, "h"), (minutes, "m")]
+        .iter()
+        .filter(|(amount, _)| *amount > 0)
+        .map(|(amount, unit)| format!("{}{}", amount, unit))
+        .collect();
+    parts.join(" ")
+}
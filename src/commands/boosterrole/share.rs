@@ -1,16 +1,34 @@
 use crate::bot::{Context, Error};
-use crate::data::models::{BoosterRole, BoosterRoleShare, GuildSharingLimit};
+use crate::data::models::{BoosterRoleShare, GuildSharingLimit, SettingsAuditLog, UserPreference};
+use crate::utils::args::{RoleArg, UserArg};
+use crate::utils::audit::{booster_audit_embed, send_booster_audit};
+use crate::utils::i18n::{locale_for, translate};
+use crate::utils::list_presenter::{render_share_list, ListLayout, ShareListEntry, ShareRecipient};
+use crate::utils::paginator::paginate_embeds;
+use crate::utils::share_revalidation::{
+    recipient_state_from_error, share_expiry, RecipientState, RevalidationPolicy,
+    MAX_REVOCATIONS_PER_RUN,
+};
 use crate::utils::{EmbedBuilder, ResponseHelper};
-use serenity::all::{Role, RoleId, User, UserId};
+use super::changes::Refusal;
+use serenity::all::{CreateEmbed, GuildId, RoleId};
 use tracing::{info, instrument, warn};
 
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum RevalidateMode {
+    #[name = "on"]
+    On,
+    #[name = "off"]
+    Off,
+}
+
 /// Share your booster role with other members
 #[poise::command(
     slash_command,
     prefix_command,
     guild_only,
     category = "Booster Roles",
-    subcommands("share_role", "share_remove", "share_max", "share_list", "share_limit"),
+    subcommands("share_role", "share_remove", "share_revoke", "share_mine", "share_max", "share_list", "share_limit", "share_revalidate"),
     description_localized("en-US", "Manage booster role sharing")
 )]
 pub async fn share(_: Context<'_>) -> Result<(), Error> {
@@ -38,7 +56,9 @@ pub async fn share(_: Context<'_>) -> Result<(), Error> {
 async fn share_role(
     ctx: Context<'_>,
     #[description = "Member to share your role with"] 
-    user: User,
+    user: UserArg,
+    #[description = "How long the share lasts, e.g. 7d, 12h or 30m (default: until removed)"]
+    duration: Option<String>,
 ) -> Result<(), Error> {
     info!(target_user = %user.id, "Share role command invoked");
     
@@ -46,101 +66,136 @@ async fn share_role(
     let owner_id = ctx.author().id;
     let data = ctx.data();
     
-    // Prevent self-sharing
-    if user.id == owner_id {
-        ResponseHelper::send_error(
-            ctx,
-            "Invalid Target",
-            "You cannot share your role with yourself."
-        ).await?;
-        return Ok(());
+    if user.bot {
+        return refuse(ctx, Refusal::ShareWithBot).await;
     }
     
-    // Check if owner has a booster role
-    let booster_role = BoosterRole::get(&data.db_pool, guild_id, owner_id).await?
-        .ok_or(Error::Command("You don't have a booster role to share.".to_string()))?;
-    
-    let role_id = RoleId::new(booster_role.role_id as u64);
-    
-    // Check sharing limits
-    let limits = GuildSharingLimit::get(&data.db_pool, guild_id).await?
-        .unwrap_or(GuildSharingLimit {
-            id: 0,
-            guild_id: guild_id.get() as i64,
-            max_members_per_role: 5,
-            max_shared_roles_per_member: 3,
-            set_by: 0,
-            created_at: None,
-            updated_at: None,
-        });
+    let expires_at = match duration.as_deref().map(|d| share_expiry(d, chrono::Utc::now())) {
+        Some(Err(e)) => {
+            ResponseHelper::send_error(ctx, "Invalid Duration", &e).await?;
+            return Ok(());
+        }
+        Some(Ok(at)) => Some(at),
+        None => None,
+    };
     
-    // Check role share count
-    let current_shares = BoosterRoleShare::count_role_shares(&data.db_pool, guild_id, role_id).await?;
-    if current_shares >= limits.max_members_per_role as i64 {
-        ResponseHelper::send_error(
-            ctx,
-            "Share Limit Reached",
-            &format!(
-                "This role has reached the maximum share limit of {} members.",
-                limits.max_members_per_role
-            )
-        ).await?;
+    // Only members who may own a booster role can share one
+    let owner = guild_id.member(&ctx.http(), owner_id).await?;
+    if !super::ensure_eligible(ctx, guild_id, &owner).await? {
         return Ok(());
     }
     
-    // Check target user's shared role count
-    let user_shares = BoosterRoleShare::count_user_shares(&data.db_pool, guild_id, user.id).await?;
-    if user_shares >= limits.max_shared_roles_per_member as i64 {
-        ResponseHelper::send_error(
-            ctx,
-            "User Share Limit Reached",
-            &format!(
-                "{} has reached the maximum limit of {} shared roles.",
-                user.name,
-                limits.max_shared_roles_per_member
-            )
-        ).await?;
-        return Ok(());
-    }
+    let booster_role =
+        match super::changes::share_core(&data.repos, guild_id, owner_id, user.id).await? {
+            Ok(role) => role,
+            Err(refusal) => return refuse(ctx, refusal).await,
+        };
+    let role_id = RoleId::new(booster_role.role_id as u64);
     
-    // Check if already shared
-    let existing_shares = BoosterRoleShare::get_role_shares(&data.db_pool, guild_id, role_id).await?;
-    if existing_shares.iter().any(|s| s.shared_with_id == user.id.get() as i64 && s.is_active) {
-        ResponseHelper::send_error(
-            ctx,
-            "Already Shared",
-            &format!("Your role is already shared with {}.", user.name)
-        ).await?;
-        return Ok(());
+    let member = match guild_id.member(&ctx.http(), user.id).await {
+        Ok(member) => member,
+        Err(e) if recipient_state_from_error(&e) == RecipientState::NotMember => {
+            return refuse(ctx, Refusal::NotInGuild(user.id)).await;
+        }
+        Err(e) => return Err(e.into()),
+    };
+    
+    if !role_exists(ctx, guild_id, role_id).await? {
+        warn!(
+            role_id = %role_id,
+            guild_id = %guild_id,
+            "Booster role was deleted in Discord, clearing its record"
+        );
+        data.repos.booster_roles.delete_by_role(guild_id, role_id).await?;
+        return refuse(ctx, Refusal::RoleMissing).await;
     }
     
-    // Add role to target user
-    let member = guild_id.member(&ctx.http(), user.id).await?;
     member.add_role(&ctx.http(), role_id).await?;
     
-    // Create share record
-    BoosterRoleShare::create(&data.db_pool, guild_id, role_id, owner_id, user.id).await?;
+    // A share the database doesn't know about would never expire or be
+    // revoked, so take the role back if the record can't be written
+    if let Err(e) =
+        data.repos.booster_roles.create_share(guild_id, role_id, owner_id, user.id, expires_at).await
+    {
+        if let Err(remove_error) = member.remove_role(&ctx.http(), role_id).await {
+            warn!(
+                error = ?remove_error,
+                role_id = %role_id,
+                shared_with = %user.id,
+                "Failed to take back a share that couldn't be recorded"
+            );
+        }
+        return Err(e.into());
+    }
     
     info!(
         owner_id = %owner_id,
         shared_with = %user.id,
         role_id = %role_id,
         guild_id = %guild_id,
+        expires_at = ?expires_at,
         "Role shared successfully"
     );
     
-    ResponseHelper::send_success(
+    send_booster_audit(
+        ctx.http(),
+        &data.db_pool,
+        guild_id,
+        booster_audit_embed("🤝 Booster Role Shared", owner_id, format!("<@&{}>", role_id))
+            .field("Shared With", format!("<@{}>", user.id), true)
+            .field(
+                "Expires",
+                expires_at
+                    .map(|at| format!("<t:{}:R>", at.timestamp()))
+                    .unwrap_or_else(|| "Never".to_string()),
+                true,
+            ),
+    )
+    .await;
+
+    let locale = locale_for(ctx).await;
+    let until = expires_at
+        .map(|at| {
+            translate(
+                locale,
+                "booster.shared.until",
+                &[("when", &format!("<t:{}:R>", at.timestamp()))],
+            )
+        })
+        .unwrap_or_default();
+    ResponseHelper::send_success_with_next_steps(
         ctx,
-        "✅ Role Shared",
-        &format!(
-            "Your booster role **{}** has been shared with <@{}>.",
-            booster_role.role_name,
-            user.id
-        )
+        translate(locale, "booster.shared.title", &[]),
+        translate(
+            locale,
+            "booster.shared.body",
+            &[
+                ("name", &booster_role.role_name),
+                ("user", &user.id),
+                ("until", &until),
+            ],
+        ),
     ).await?;
     Ok(())
 }
 
+/// Reply privately with why the share didn't happen
+async fn refuse(ctx: Context<'_>, refusal: Refusal) -> Result<(), Error> {
+    let embed = refusal.embed(locale_for(ctx).await);
+    ResponseHelper::send_embed_with(ctx, embed, true).await?;
+    Ok(())
+}
+
+/// Whether the role is still in the guild, from the cache when it has the
+/// guild and otherwise from Discord
+async fn role_exists(ctx: Context<'_>, guild_id: GuildId, role_id: RoleId) -> Result<bool, Error> {
+    let cached = ctx.guild().map(|guild| guild.roles.contains_key(&role_id));
+    match cached {
+        Some(exists) => Ok(exists),
+        None => Ok(guild_id.roles(ctx.http()).await?.contains_key(&role_id)),
+    }
+}
+
 /// Remove yourself from a shared booster role
 #[poise::command(
     slash_command,
@@ -162,7 +217,7 @@ async fn share_role(
 async fn share_remove(
     ctx: Context<'_>,
     #[description = "The shared role to remove yourself from"] 
-    role: Role,
+    role: RoleArg,
 ) -> Result<(), Error> {
     info!(role_id = %role.id, "Remove share command invoked");
     
@@ -171,9 +226,9 @@ async fn share_remove(
     let data = ctx.data();
     
     // Check if user has this shared role
-    let shares = BoosterRoleShare::get_shared_with_user(&data.db_pool, guild_id, user_id).await?;
+    let shares = data.repos.booster_roles.shares_received(guild_id, user_id).await?;
     
-    let _share = shares.iter()
+    let share = shares.iter()
         .find(|s| s.role_id == role.id.get() as i64 && s.is_active)
         .ok_or(Error::Command("You don't have access to this shared role.".to_string()))?;
     
@@ -187,7 +242,7 @@ async fn share_remove(
     }
     
     // Remove share record
-    BoosterRoleShare::remove(&data.db_pool, guild_id, role.id, user_id).await?;
+    data.repos.booster_roles.remove_share(guild_id, role.id, user_id).await?;
     
     info!(
         user_id = %user_id,
@@ -196,6 +251,15 @@ async fn share_remove(
         "Share removed successfully"
     );
     
+    send_booster_audit(
+        ctx.http(),
+        &data.db_pool,
+        guild_id,
+        booster_audit_embed("👋 Left Shared Role", user_id, format!("<@&{}>", role.id))
+            .field("Owner", format!("<@{}>", share.owner_id), true),
+    )
+    .await;
+
     ResponseHelper::send_success(
         ctx,
         "✅ Share Removed",
@@ -204,6 +268,196 @@ async fn share_remove(
     Ok(())
 }
 
+/// Take your booster role back from a member you shared it with
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "revoke",
+    category = "Booster Roles",
+    required_bot_permissions = "MANAGE_ROLES",
+    description_localized("en-US", "Revoke your booster role from a member you shared it with")
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.share.revoke"
+    )
+)]
+async fn share_revoke(
+    ctx: Context<'_>,
+    #[description = "Member to take your role back from"] 
+    user: UserArg,
+) -> Result<(), Error> {
+    info!(target_user = %user.id, "Revoke share command invoked");
+    
+    let guild_id = ctx.guild_id().ok_or(Error::Command("This command must be used in a guild".to_string()))?;
+    let owner_id = ctx.author().id;
+    let data = ctx.data();
+    
+    let Some(booster_role) = data.repos.booster_roles.get(guild_id, owner_id).await? else {
+        ResponseHelper::send_error(
+            ctx,
+            "No Booster Role",
+            "You don't have a booster role, so there is nothing to revoke."
+        ).await?;
+        return Ok(());
+    };
+    
+    let role_id = RoleId::new(booster_role.role_id as u64);
+    
+    let shares = data.repos.booster_roles.shares_given(guild_id, owner_id).await?;
+    if !shares
+        .iter()
+        .any(|s| s.role_id == booster_role.role_id && s.shared_with_id == user.id.get() as i64)
+    {
+        ResponseHelper::send_error(
+            ctx,
+            "Nothing To Revoke",
+            &format!("Your role isn't shared with {}.", user.name)
+        ).await?;
+        return Ok(());
+    }
+    
+    // The member may have left; the share still needs ending
+    match guild_id.member(&ctx.http(), user.id).await {
+        Ok(member) => {
+            if let Err(e) = member.remove_role(&ctx.http(), role_id).await {
+                warn!(
+                    "Failed to remove role {} from user {}: {}",
+                    role_id, user.id, e
+                );
+            }
+        }
+        Err(e) => warn!(
+            "Failed to fetch member {} to revoke role {}: {}",
+            user.id, role_id, e
+        ),
+    }
+    
+    data.repos.booster_roles.remove_share(guild_id, role_id, user.id).await?;
+    
+    info!(
+        owner_id = %owner_id,
+        revoked_from = %user.id,
+        role_id = %role_id,
+        guild_id = %guild_id,
+        "Share revoked by owner"
+    );
+    
+    send_booster_audit(
+        ctx.http(),
+        &data.db_pool,
+        guild_id,
+        booster_audit_embed("🚫 Booster Role Share Revoked", owner_id, format!("<@&{}>", role_id))
+            .field("Revoked From", format!("<@{}>", user.id), true),
+    )
+    .await;
+
+    ResponseHelper::send_success(
+        ctx,
+        "✅ Share Revoked",
+        &format!(
+            "<@{}> no longer has your booster role **{}**.",
+            user.id,
+            booster_role.role_name
+        )
+    ).await?;
+    Ok(())
+}
+
+/// See roles shared with you and who your role is shared with
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "mine",
+    category = "Booster Roles",
+    description_localized("en-US", "See roles shared with you and who your role is shared with")
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.share.mine"
+    )
+)]
+async fn share_mine(ctx: Context<'_>) -> Result<(), Error> {
+    info!("Share mine command invoked");
+    
+    let guild_id = ctx.guild_id().ok_or(Error::Command("This command must be used in a guild".to_string()))?;
+    let user_id = ctx.author().id;
+    let data = ctx.data();
+    
+    let received = data.repos.booster_roles.shares_received(guild_id, user_id).await?;
+    let given = data.repos.booster_roles.shares_given(guild_id, user_id).await?;
+    
+    if received.is_empty() && given.is_empty() {
+        ResponseHelper::send_info(
+            ctx,
+            "No Shares",
+            "No booster roles are shared with you, and your role isn't shared with anyone. \
+            Use `/boosterrole share role <user>` to share yours."
+        ).await?;
+        return Ok(());
+    }
+    
+    let received_lines = received
+        .iter()
+        .map(|s| format!("<@&{}> from <@{}>{}", s.role_id, s.owner_id, expiry_suffix(s)))
+        .collect::<Vec<_>>();
+    let given_lines = given
+        .iter()
+        .map(|s| format!("<@{}>{}", s.shared_with_id, expiry_suffix(s)))
+        .collect::<Vec<_>>();
+    
+    ResponseHelper::send_embed(ctx, shares_embed(&received_lines, &given_lines)).await?;
+    Ok(())
+}
+
+/// Shares the member received and gave, one line each
+pub(crate) fn shares_embed(received_lines: &[String], given_lines: &[String]) -> CreateEmbed {
+    EmbedBuilder::info(
+        "🤝 Your Shares",
+        "Leave a shared role with `/boosterrole share remove`, or take yours back with `/boosterrole share revoke`."
+    )
+    .field("Shared With You", field_lines(received_lines, "None"), false)
+    .field("Your Role Is Shared With", field_lines(given_lines, "Nobody"), false)
+}
+
+/// Relative expiry for a share line, empty for shares without one
+fn expiry_suffix(share: &BoosterRoleShare) -> String {
+    share
+        .expires_at
+        .map(|at| format!(", expires <t:{}:R>", at.timestamp()))
+        .unwrap_or_default()
+}
+
+/// Join lines into an embed field value, cutting off before Discord's 1024 character limit
+fn field_lines(lines: &[String], empty: &str) -> String {
+    const FIELD_LIMIT: usize = 1024;
+    // Room for the "… and N more" line
+    const RESERVED: usize = 24;
+
+    if lines.is_empty() {
+        return empty.to_string();
+    }
+
+    let mut value = String::new();
+    for (shown, line) in lines.iter().enumerate() {
+        if value.len() + line.len() + 1 > FIELD_LIMIT - RESERVED {
+            value.push_str(&format!("… and {} more", lines.len() - shown));
+            return value;
+        }
+        value.push_str(line);
+        value.push('\n');
+    }
+    value.trim_end().to_string()
+}
+
 /// Set maximum members per shared role (Admin only)
 #[poise::command(
     slash_command,
@@ -236,7 +490,7 @@ async fn share_max(
     let data = ctx.data();
     
     // Get current limits or defaults
-    let current_limits = GuildSharingLimit::get(&data.db_pool, guild_id).await?
+    let current_limits = data.repos.guild_settings.sharing_limits(guild_id).await?
         .unwrap_or(GuildSharingLimit {
             id: 0,
             guild_id: guild_id.get() as i64,
@@ -248,8 +502,7 @@ async fn share_max(
         });
     
     // Update limits
-    GuildSharingLimit::set(
-        &data.db_pool,
+    data.repos.guild_settings.set_sharing_limits(
         guild_id,
         max_members,
         current_limits.max_shared_roles_per_member,
@@ -296,12 +549,7 @@ async fn share_list(ctx: Context<'_>) -> Result<(), Error> {
     let data = ctx.data();
     
     // Get all booster roles
-    let booster_roles = sqlx::query_as::<_, BoosterRole>(
-        "SELECT * FROM booster_roles WHERE guild_id = ?"
-    )
-    .bind(guild_id.get() as i64)
-    .fetch_all(&data.db_pool)
-    .await?;
+    let booster_roles = data.repos.booster_roles.get_all_for_guild(guild_id).await?;
     
     if booster_roles.is_empty() {
         ResponseHelper::send_info(
@@ -312,39 +560,41 @@ async fn share_list(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     }
     
-    let mut description = String::from("👥 **Booster Role Shares**\n\n");
-    
-    for role in booster_roles.iter().take(10) {
-        let role_id = RoleId::new(role.role_id as u64);
-        let owner_id = UserId::new(role.user_id as u64);
-        
-        // Get shares for this role
-        let shares = BoosterRoleShare::get_role_shares(&data.db_pool, guild_id, role_id).await?;
-        
-        description.push_str(&format!("**{}**\n", role.role_name));
-        description.push_str(&format!("Owner: <@{}>\n", owner_id));
-        
-        if shares.is_empty() {
-            description.push_str("No shares\n");
-        } else {
-            description.push_str("Shared with: ");
-            for share in shares.iter().take(5) {
-                description.push_str(&format!("<@{}> ", share.shared_with_id));
-            }
-            if shares.len() > 5 {
-                description.push_str(&format!("... and {} more", shares.len() - 5));
-            }
-            description.push_str("\n");
-        }
-        description.push_str("\n");
+    let compact = UserPreference::compact_mode(&data.db_pool, ctx.author().id)
+        .await
+        .unwrap_or(false);
+    let layout = ListLayout::from_compact(compact);
+    
+    // One query for every share rather than one per role
+    let shares = data.repos.booster_roles.active_shares(guild_id).await?;
+    
+    let mut entries = Vec::new();
+    for role in &booster_roles {
+        entries.push(ShareListEntry {
+            role_name: role.role_name.clone(),
+            owner_id: role.user_id,
+            recipients: shares
+                .iter()
+                .filter(|s| s.role_id == role.role_id)
+                .map(|s| ShareRecipient {
+                    user_id: s.shared_with_id,
+                    expires_at: s
+                        .expires_at
+                        .map(|at| at.timestamp()),
+                })
+                .collect(),
+        });
     }
     
-    if booster_roles.len() > 10 {
-        description.push_str(&format!("\n_Showing 10 of {} booster roles_", booster_roles.len()));
-    }
+    let pages = render_share_list(&entries, layout)
+        .into_iter()
+        .map(|page| {
+            EmbedBuilder::info("👥 Booster Role Shares", &page.description)
+                .footer(serenity::all::CreateEmbedFooter::new(page.footer))
+        })
+        .collect();
     
-    let embed = EmbedBuilder::info("👥 Booster Role Shares", &description);
-    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    paginate_embeds(ctx, pages).await?;
     
     Ok(())
 }
@@ -381,7 +631,7 @@ async fn share_limit(
     let data = ctx.data();
     
     // Get current limits or defaults
-    let current_limits = GuildSharingLimit::get(&data.db_pool, guild_id).await?
+    let current_limits = data.repos.guild_settings.sharing_limits(guild_id).await?
         .unwrap_or(GuildSharingLimit {
             id: 0,
             guild_id: guild_id.get() as i64,
@@ -393,8 +643,7 @@ async fn share_limit(
         });
     
     // Update limits
-    GuildSharingLimit::set(
-        &data.db_pool,
+    data.repos.guild_settings.set_sharing_limits(
         guild_id,
         current_limits.max_members_per_role,
         max_roles,
@@ -416,3 +665,85 @@ async fn share_limit(
     Ok(())
 }
 
+/// Periodically re-check share recipients and revoke lapsed shares (Admin only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "revalidate",
+    category = "Booster Roles",
+    required_permissions = "MANAGE_GUILD",
+    description_localized("en-US", "Revoke shares whose recipients left or stopped qualifying")
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.share.revalidate"
+    )
+)]
+async fn share_revalidate(
+    ctx: Context<'_>,
+    #[description = "Re-check share recipients periodically"]
+    mode: RevalidateMode,
+    #[description = "Also revoke shares from recipients who aren't boosters or premium members (default off)"]
+    require_eligibility: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or(Error::Command("This command must be used in a guild".to_string()))?;
+    let pool = &ctx.data().db_pool;
+
+    let policy = RevalidationPolicy {
+        enabled: matches!(mode, RevalidateMode::On),
+        require_eligibility: require_eligibility.unwrap_or(false),
+    };
+    ctx.data()
+        .repos
+        .guild_settings
+        .set_share_policy(guild_id, policy, ctx.author().id)
+        .await?;
+
+    let details = if !policy.enabled {
+        "Off".to_string()
+    } else if policy.require_eligibility {
+        "Membership and eligibility".to_string()
+    } else {
+        "Membership only".to_string()
+    };
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "share_revalidation_set",
+        Some(&details),
+    )
+    .await?;
+
+    if !policy.enabled {
+        ResponseHelper::send_success(
+            ctx,
+            "✅ Share Revalidation Off",
+            "Shares are no longer re-checked. Existing shares stay as they are.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let checks = if policy.require_eligibility {
+        "have left the server or no longer boost or hold the premium role"
+    } else {
+        "have left the server"
+    };
+    ResponseHelper::send_success(
+        ctx,
+        "✅ Share Revalidation On",
+        &format!(
+            "Every few hours, shares are revoked from recipients who {}. \
+            Recipients are notified by DM and each revocation is recorded in the settings audit log. \
+            At most **{}** shares are revoked per sweep.",
+            checks, MAX_REVOCATIONS_PER_RUN
+        ),
+    )
+    .await?;
+    Ok(())
+}
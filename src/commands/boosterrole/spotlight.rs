@@ -0,0 +1,198 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{GuildSpotlightSetting, SettingsAuditLog};
+use crate::handlers::{SpotlightHandler, SpotlightOutcome};
+use crate::utils::spotlight::MIN_ROLE_AGE_DAYS;
+use crate::utils::ResponseHelper;
+use serenity::all::{ChannelId, GuildChannel, Mentionable};
+use std::sync::Arc;
+use tracing::instrument;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum SpotlightMode {
+    #[name = "weekly"]
+    Weekly,
+    #[name = "off"]
+    Off,
+}
+
+/// Feature a booster role every week
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    category = "Booster Roles",
+    required_permissions = "MANAGE_GUILD",
+    subcommands("spotlight_set", "spotlight_now"),
+    description_localized("en-US", "Feature a booster role of the week in a channel")
+)]
+pub async fn spotlight(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Configure the weekly booster role spotlight
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "set",
+    category = "Booster Roles",
+    required_permissions = "MANAGE_GUILD",
+    description_localized(
+        "en-US",
+        "Post a booster role of the week in a channel, or turn it off"
+    )
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.spotlight.set"
+    )
+)]
+pub async fn spotlight_set(
+    ctx: Context<'_>,
+    #[description = "Channel to post the spotlight in"]
+    #[channel_types("Text", "News")]
+    channel: GuildChannel,
+    #[description = "Post weekly, or turn the spotlight off"] mode: SpotlightMode,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+    let pool = &ctx.data().db_pool;
+
+    match mode {
+        SpotlightMode::Weekly => {
+            GuildSpotlightSetting::set(pool, guild_id, channel.id, true, ctx.author().id).await?;
+
+            SettingsAuditLog::log(
+                pool,
+                guild_id,
+                ctx.author().id,
+                "spotlight_enabled",
+                Some(&format!("Channel: <#{}>", channel.id)),
+            )
+            .await?;
+
+            ResponseHelper::send_success(
+                ctx,
+                "🌟 Spotlight Enabled",
+                &format!(
+                    "A booster role of the week will be posted in {} every week.\n\n\
+                    Locked roles and roles younger than {} days are skipped. Use `/boosterrole spotlight now` to post one right away.",
+                    channel.id.mention(),
+                    MIN_ROLE_AGE_DAYS
+                ),
+            )
+            .await?;
+        }
+        SpotlightMode::Off => {
+            if !GuildSpotlightSetting::disable(pool, guild_id).await? {
+                ResponseHelper::send_info(
+                    ctx,
+                    "ℹ️ Spotlight Not Enabled",
+                    "The booster role spotlight isn't set up for this server.",
+                )
+                .await?;
+                return Ok(());
+            }
+
+            SettingsAuditLog::log(pool, guild_id, ctx.author().id, "spotlight_disabled", None)
+                .await?;
+
+            ResponseHelper::send_success(
+                ctx,
+                "🌙 Spotlight Disabled",
+                "The weekly booster role spotlight has been turned off.",
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Post a booster role spotlight immediately
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "now",
+    category = "Booster Roles",
+    required_permissions = "MANAGE_GUILD",
+    description_localized("en-US", "Post the booster role spotlight right now")
+)]
+#[instrument(
+    skip(ctx),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.spotlight.now"
+    )
+)]
+pub async fn spotlight_now(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+    let pool = &ctx.data().db_pool;
+
+    let Some(setting) = GuildSpotlightSetting::get(pool, guild_id)
+        .await?
+        .filter(|s| s.enabled)
+    else {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Spotlight Not Enabled",
+            "Set a channel first with `/boosterrole spotlight set <channel> weekly`.",
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let channel_id = ChannelId::new(setting.channel_id as u64);
+    let handler = SpotlightHandler::new(Arc::new(pool.clone()));
+
+    match handler
+        .post(&ctx.serenity_context().http, guild_id, channel_id)
+        .await?
+    {
+        SpotlightOutcome::Posted { role_id, user_id } => {
+            GuildSpotlightSetting::mark_posted(pool, guild_id).await?;
+
+            ResponseHelper::send_success(
+                ctx,
+                "🌟 Spotlight Posted",
+                &format!(
+                    "Featured {} by {} in {}.",
+                    role_id.mention(),
+                    user_id.mention(),
+                    channel_id.mention()
+                ),
+            )
+            .await?;
+        }
+        SpotlightOutcome::NoCandidates => {
+            ResponseHelper::send_info(
+                ctx,
+                "ℹ️ Nothing to Spotlight",
+                &format!(
+                    "There are no eligible booster roles yet. Roles must be unlocked and at least {} days old.",
+                    MIN_ROLE_AGE_DAYS
+                ),
+            )
+            .await?;
+        }
+        SpotlightOutcome::ChannelGone => {
+            ResponseHelper::send_error(
+                ctx,
+                "❌ Channel Missing",
+                "The spotlight channel no longer exists, so the spotlight has been turned off.\n\n\
+                Set a new channel with `/boosterrole spotlight set <channel> weekly`.",
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{BoosterRenameHistory, BoosterRole};
+use crate::utils::args::UserArg;
+use crate::utils::{ColorParser, EmbedColor, ResponseHelper};
+use serenity::all::{CreateEmbed, RoleId, Timestamp, User};
+use tracing::{info, instrument};
+
+/// Show a booster role's history and stats
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    category = "Booster Roles",
+    description_localized("en-US", "Show a booster role's history and stats")
+)]
+#[instrument(
+    skip(ctx, user),
+    fields(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        command = "boosterrole.stats"
+    )
+)]
+pub async fn stats(
+    ctx: Context<'_>,
+    #[description = "Member to look up (staff only, defaults to you)"] user: Option<UserArg>,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or(Error::Command("Not in a guild".to_string()))?;
+    let pool = &ctx.data().db_pool;
+    let repos = &ctx.data().repos;
+
+    let target = user.map(|u| u.0).unwrap_or_else(|| ctx.author().clone());
+    let is_self = target.id == ctx.author().id;
+    if !is_self {
+        super::staff_check(ctx).await?;
+    }
+
+    info!(target_id = %target.id, "Booster role stats requested");
+
+    let Some(role) = repos.booster_roles.get(guild_id, target.id).await? else {
+        let message = if is_self {
+            "You don't have a custom booster role yet. Create one with `/boosterrole color`."
+                .to_string()
+        } else {
+            format!("<@{}> doesn't have a custom booster role.", target.id)
+        };
+        ResponseHelper::send_info(ctx, "No Booster Role", &message).await?;
+        return Ok(());
+    };
+
+    let role_id = RoleId::new(role.role_id as u64);
+    let renames = BoosterRenameHistory::count_for_user(pool, guild_id, target.id).await?;
+    let shares = repos.booster_roles.count_role_shares(guild_id, role_id).await?;
+
+    let boosting = guild_id
+        .member(ctx, target.id)
+        .await
+        .ok()
+        .and_then(|member| member.premium_since)
+        .map(boosting_since)
+        .unwrap_or_else(|| "Not boosting".to_string());
+
+    let embed = stats_embed(&target, &role, renames, shares, boosting);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// A member's role with its rename and share counts
+pub(crate) fn stats_embed(
+    target: &User,
+    role: &BoosterRole,
+    renames: i64,
+    shares: i64,
+    boosting: String,
+) -> CreateEmbed {
+    let created = role
+        .created_at
+        .map(|at| format!("<t:{0}:D> (<t:{0}:R>)", at.timestamp()))
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let colors = match &role.secondary_color {
+        Some(secondary) => format!("`{}` → `{}`", role.primary_color, secondary),
+        None => format!("`{}`", role.primary_color),
+    };
+
+    let color =
+        ColorParser::parse(&role.primary_color).unwrap_or_else(|_| EmbedColor::Primary.value());
+
+    CreateEmbed::new()
+        .title(format!("📊 {}'s Booster Role", target.name))
+        .thumbnail(target.face())
+        .field("Role", format!("<@&{}>", role.role_id), true)
+        .field("Created", created, true)
+        .field("Colors", colors, true)
+        .field("Renames", renames.to_string(), true)
+        .field("Shared with", format!("{} member(s)", shares), true)
+        .field("Boosting since", boosting, true)
+        .color(color)
+        .timestamp(Timestamp::now())
+}
+
+fn boosting_since(since: Timestamp) -> String {
+    let days = (Timestamp::now().unix_timestamp() - since.unix_timestamp()).max(0) / 86_400;
+    format!(
+        "<t:{}:D> ({} day{})",
+        since.unix_timestamp(),
+        days,
+        if days == 1 { "" } else { "s" }
+    )
+}
@@ -0,0 +1,307 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{BoosterRoleTemplate, SettingsAuditLog};
+use crate::utils::name_severity::NameCheck;
+use crate::utils::paginator::paginate_embeds;
+use crate::utils::role_icon::IconSource;
+use crate::utils::{ColorParser, EmbedBuilder, EmbedColor, ResponseHelper, RoleManager};
+use poise::serenity_prelude as serenity;
+
+const TEMPLATES_PER_PAGE: usize = 10;
+
+/// Pick from name and color combos the server has prepared
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    subcommands("template_use", "template_add", "template_remove", "template_list")
+)]
+pub async fn template(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.send(poise::CreateReply::default().embed(template_help_embed()))
+        .await?;
+    Ok(())
+}
+
+/// The template subcommands, shown when `/boosterrole template` is run alone
+pub(crate) fn template_help_embed() -> serenity::CreateEmbed {
+    EmbedBuilder::info(
+        "📋 Booster Role Templates",
+        "**Available subcommands:**\n\n\
+        `/boosterrole template use <name>` - Apply a template to your booster role\n\
+        `/boosterrole template add <name> <color> [icon]` - Add a template (Admin)\n\
+        `/boosterrole template remove <name>` - Remove a template (Admin)\n\
+        `/boosterrole template list` - View the server's templates (Admin)",
+    )
+}
+
+/// Suggest the guild's template names containing what's been typed
+async fn autocomplete_template(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Vec::new();
+    };
+
+    BoosterRoleTemplate::search(&ctx.data().db_pool, guild_id, partial)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = ?e, guild_id = %guild_id, "Template autocomplete failed");
+            Vec::new()
+        })
+}
+
+/// Apply one of the server's templates to your booster role
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "use",
+    broadcast_typing
+)]
+pub async fn template_use(
+    ctx: Context<'_>,
+    #[description = "The template to apply"]
+    #[autocomplete = "autocomplete_template"]
+    name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        command = "boosterrole.template.use",
+        template = %name,
+        "Booster role template use invoked"
+    );
+
+    let Some(template) = BoosterRoleTemplate::get(&ctx.data().db_pool, guild_id, &name).await?
+    else {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Template Not Found",
+            &format!(
+                "This server has no template called **{}**. Start typing to see the available ones.",
+                name
+            ),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    super::color::apply(
+        ctx,
+        guild_id,
+        template.name,
+        template.color,
+        None,
+        template.icon_url,
+    )
+    .await
+}
+
+/// Add a booster role template (Admin only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "add"
+)]
+pub async fn template_add(
+    ctx: Context<'_>,
+    #[description = "Role name members get from this template"] name: String,
+    #[description = "Hex code like #FF0000, a color name, or rgb()/hsl()"] color: String,
+    #[description = "Optional emoji or image URL for the role icon"] icon: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+    let name = match RoleManager::validate_role_name(&name) {
+        Ok(name) => name,
+        Err(e) => {
+            ResponseHelper::send_error(ctx, "❌ Invalid Role Name", &e.to_string()).await?;
+            return Ok(());
+        }
+    };
+
+    let name_check = ctx
+        .data()
+        .check_role_name(guild_id, ctx.author().id, &name, "boosterrole template add")
+        .await?;
+    if let NameCheck::Reject { .. } = name_check {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Inappropriate Role Name",
+            "The template name contains words that are not allowed in role names.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let color = match ColorParser::parse(&color) {
+        Ok(c) => ColorParser::to_hex_string(c),
+        Err(e) => {
+            ResponseHelper::send_error(ctx, "❌ Invalid Color", &e.to_string()).await?;
+            return Ok(());
+        }
+    };
+
+    let icon = match icon.as_deref().map(IconSource::parse).transpose() {
+        Ok(source) => source.map(|source| source.stored_value()),
+        Err(reason) => {
+            ResponseHelper::send_error(ctx, "❌ Invalid Icon", &reason).await?;
+            return Ok(());
+        }
+    };
+
+    let pool = &ctx.data().db_pool;
+    let added = BoosterRoleTemplate::add(
+        pool,
+        guild_id,
+        &name,
+        &color,
+        icon.as_deref(),
+        ctx.author().id,
+    )
+    .await?;
+    if !added {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Template Exists",
+            &format!(
+                "There's already a template called **{}**. Remove it first to replace it.",
+                name
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "booster_template_add",
+        Some(&format!("{} {}", name, color)),
+    )
+    .await?;
+
+    let mut description = format!(
+        "Members can now pick **{}** (`{}`) with `/boosterrole template use`.",
+        name, color
+    );
+    if let Some(note) = name_check.caution_note() {
+        description.push_str(&format!("\n\n{}", note));
+    }
+    ResponseHelper::send_success(ctx, "✅ Template Added", &description).await?;
+    Ok(())
+}
+
+/// Remove a booster role template (Admin only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "remove"
+)]
+pub async fn template_remove(
+    ctx: Context<'_>,
+    #[description = "The template to remove"]
+    #[autocomplete = "autocomplete_template"]
+    name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+    let pool = &ctx.data().db_pool;
+
+    if !BoosterRoleTemplate::remove(pool, guild_id, &name).await? {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Template Not Found",
+            &format!("This server has no template called **{}**.", name),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "booster_template_remove",
+        Some(&name),
+    )
+    .await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "🗑️ Template Removed",
+        &format!(
+            "**{}** is no longer offered. Roles already made from it are unchanged.",
+            name
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// View the server's booster role templates (Admin only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "list"
+)]
+pub async fn template_list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
+
+    let templates = BoosterRoleTemplate::list(&ctx.data().db_pool, guild_id).await?;
+    if templates.is_empty() {
+        ResponseHelper::send_info(
+            ctx,
+            "📋 Booster Role Templates",
+            "This server has no templates yet. Add one with `/boosterrole template add`.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let total_pages = templates.len().div_ceil(TEMPLATES_PER_PAGE);
+    let pages = templates
+        .chunks(TEMPLATES_PER_PAGE)
+        .enumerate()
+        .map(|(page, chunk)| {
+            let lines = chunk
+                .iter()
+                .map(|t| {
+                    let icon = t
+                        .icon_url
+                        .as_ref()
+                        .map(|_| " • with icon")
+                        .unwrap_or_default();
+                    format!(
+                        "**{}** `{}`{} • added by <@{}>",
+                        t.name, t.color, icon, t.created_by
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            serenity::CreateEmbed::new()
+                .title("📋 Booster Role Templates")
+                .description(format!("**{} templates:**\n\n{}", templates.len(), lines))
+                .color(EmbedColor::Info.value())
+                .footer(serenity::CreateEmbedFooter::new(format!(
+                    "Page {} of {}",
+                    page + 1,
+                    total_pages
+                )))
+        })
+        .collect();
+
+    paginate_embeds(ctx, pages).await
+}
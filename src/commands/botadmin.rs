@@ -0,0 +1,330 @@
+use crate::bot::{Context, Data, Error};
+use crate::data::models::{
+    Announcement, AnnouncementDelivery, ExperimentCounter, NotificationSubscription, TableGrowth,
+};
+use crate::handlers::announcements::{announcement_embed, AnnouncementHandler};
+use crate::utils::announcements::{
+    dedup_recipients, sanitize_mentions, NotificationCategory, MAX_BODY_LENGTH, MAX_TITLE_LENGTH,
+};
+use crate::utils::experiments::{variant_label, EXPERIMENTS};
+use crate::utils::table_growth::TRAILING_DAYS;
+use crate::utils::{EmbedColor, ResponseHelper};
+use poise::serenity_prelude::{
+    ButtonStyle, ComponentInteractionCollector, CreateActionRow, CreateButton, CreateEmbed,
+    CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage, Timestamp,
+};
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// How long the announcement modal stays open
+const MODAL_TIMEOUT: Duration = Duration::from_secs(900);
+
+/// How long the preview waits for confirmation
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Debug, poise::Modal)]
+#[name = "Feature Announcement"]
+struct AnnouncementModal {
+    #[name = "Title"]
+    #[placeholder = "e.g. Gradient roles are here"]
+    #[min_length = 1]
+    #[max_length = 100]
+    title: String,
+    #[name = "Announcement"]
+    #[placeholder = "What's new and how to use it"]
+    #[paragraph]
+    #[min_length = 1]
+    #[max_length = 2000]
+    body: String,
+}
+
+/// Bot administration tools (Owner only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    category = "Owner",
+    subcommands("experiments", "growth", "announce")
+)]
+pub async fn botadmin(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Show impressions and conversions for each embed copy variant
+#[poise::command(slash_command, prefix_command, owners_only, hide_in_help)]
+pub async fn experiments(ctx: Context<'_>) -> Result<(), Error> {
+    let pool = &ctx.data().db_pool;
+
+    // Include counts still waiting for the periodic flush
+    ctx.data().experiments.flush(pool).await?;
+    let counters = ExperimentCounter::all(pool).await?;
+
+    let mut embed = CreateEmbed::new()
+        .title("🧪 Copy Experiments")
+        .color(EmbedColor::Primary.value())
+        .footer(CreateEmbedFooter::new(
+            "Variant A is the control • conversions within 7 days of an impression",
+        ))
+        .timestamp(Timestamp::now());
+
+    for experiment in EXPERIMENTS {
+        let lines = (0..experiment.variants.len())
+            .map(|index| {
+                let counts = counters
+                    .iter()
+                    .find(|c| c.experiment_key == experiment.key && c.variant == index as i64);
+                let (impressions, conversions) = counts
+                    .map(|c| (c.impressions, c.conversions))
+                    .unwrap_or((0, 0));
+                let rate = if impressions > 0 {
+                    format!("{:.1}%", conversions as f64 / impressions as f64 * 100.0)
+                } else {
+                    "—".to_string()
+                };
+                format!(
+                    "**{}** • {} impressions • {} conversions • {}",
+                    variant_label(index),
+                    impressions,
+                    conversions,
+                    rate
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        embed = embed.field(format!("`{}`", experiment.key), lines, false);
+    }
+
+    if EXPERIMENTS.is_empty() {
+        embed = embed.description("No experiments are enrolled.");
+    }
+
+    ResponseHelper::send_embed(ctx, embed).await?;
+    Ok(())
+}
+
+/// Show booster tables that grew unusually fast in the last week
+#[poise::command(slash_command, prefix_command, owners_only, hide_in_help)]
+pub async fn growth(ctx: Context<'_>) -> Result<(), Error> {
+    let pool = &ctx.data().db_pool;
+    let since = chrono::Utc::now().date_naive() - chrono::Duration::days(TRAILING_DAYS as i64);
+    let flagged = TableGrowth::flagged_since(pool, since).await?;
+
+    let description = if flagged.is_empty() {
+        "No table grew unusually fast in the last week.".to_string()
+    } else {
+        flagged
+            .iter()
+            .take(20)
+            .map(|sample| {
+                format!(
+                    "`{}` • guild `{}` • `{}` ({} rows): {}",
+                    sample.day,
+                    sample.guild_id,
+                    sample.table_name,
+                    sample.row_count,
+                    sample.detail.as_deref().unwrap_or("flagged")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = CreateEmbed::new()
+        .title("📈 Table Growth Warnings")
+        .description(description)
+        .color(if flagged.is_empty() {
+            EmbedColor::Success.value()
+        } else {
+            EmbedColor::Warning.value()
+        })
+        .footer(CreateEmbedFooter::new(format!(
+            "{} warning(s) in the last {} days • sampled nightly",
+            flagged.len(),
+            TRAILING_DAYS
+        )))
+        .timestamp(Timestamp::now());
+
+    ResponseHelper::send_embed(ctx, embed).await?;
+    Ok(())
+}
+
+/// Feature update announcements for members who opted in
+#[poise::command(
+    slash_command,
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    subcommands("announce_create", "announce_status")
+)]
+pub async fn announce(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Write a feature announcement, preview it, and DM it to subscribers
+#[poise::command(slash_command, owners_only, hide_in_help, rename = "create")]
+pub async fn announce_create(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let Some(modal) =
+        poise::execute_modal::<_, _, AnnouncementModal>(ctx, None, Some(MODAL_TIMEOUT)).await?
+    else {
+        return Ok(());
+    };
+
+    let title = sanitize_mentions(modal.title.trim());
+    let body = sanitize_mentions(modal.body.trim());
+    if title.is_empty()
+        || body.is_empty()
+        || title.len() > MAX_TITLE_LENGTH
+        || body.len() > MAX_BODY_LENGTH
+    {
+        ResponseHelper::send_error(
+            ctx.into(),
+            "❌ Invalid Announcement",
+            "The title and announcement text can't be empty or too long.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    // Only members of guilds the bot is still in can be reached
+    let guilds: HashSet<u64> = ctx
+        .serenity_context()
+        .cache
+        .guilds()
+        .into_iter()
+        .map(|g| g.get())
+        .collect();
+    let subscriptions =
+        NotificationSubscription::all(&ctx.data().db_pool, NotificationCategory::FeatureUpdates)
+            .await?;
+    let recipients = dedup_recipients(
+        subscriptions
+            .into_iter()
+            .filter(|(guild, _)| guilds.contains(guild)),
+    );
+
+    let confirm_id = format!("announce:{}:confirm", ctx.id());
+    let cancel_id = format!("announce:{}:cancel", ctx.id());
+    let buttons = |disabled: bool| {
+        vec![CreateActionRow::Buttons(vec![
+            CreateButton::new(confirm_id.clone())
+                .label(format!("Send to {}", recipients.len()))
+                .style(ButtonStyle::Success)
+                .disabled(disabled || recipients.is_empty()),
+            CreateButton::new(cancel_id.clone())
+                .label("Cancel")
+                .style(ButtonStyle::Secondary)
+                .disabled(disabled),
+        ])]
+    };
+
+    let reply = ctx
+        .send(
+            poise::CreateReply::default()
+                .content(format!(
+                    "**Preview** • {} subscriber(s) will receive this DM.",
+                    recipients.len()
+                ))
+                .embed(announcement_embed(&title, &body))
+                .components(buttons(false))
+                .ephemeral(true),
+        )
+        .await?;
+
+    let (filter_confirm, filter_cancel) = (confirm_id.clone(), cancel_id.clone());
+    let interaction = ComponentInteractionCollector::new(ctx.serenity_context())
+        .author_id(ctx.author().id)
+        .filter(move |i| i.data.custom_id == filter_confirm || i.data.custom_id == filter_cancel)
+        .timeout(CONFIRM_TIMEOUT)
+        .await;
+
+    let confirmed = interaction
+        .as_ref()
+        .is_some_and(|i| i.data.custom_id == confirm_id);
+    let outcome = if !confirmed {
+        "Announcement discarded.".to_string()
+    } else {
+        let id = Announcement::create(
+            &ctx.data().db_pool,
+            &title,
+            &body,
+            ctx.author().id,
+            &recipients,
+        )
+        .await?;
+        AnnouncementHandler::spawn_delivery(
+            ctx.serenity_context().http.clone(),
+            ctx.data().db_pool.clone(),
+            id,
+        );
+        format!(
+            "Announcement #{} is being delivered to {} subscriber(s). You'll get a DM with the results.",
+            id,
+            recipients.len()
+        )
+    };
+
+    let update = poise::CreateReply::default()
+        .content(outcome.clone())
+        .embed(announcement_embed(&title, &body))
+        .components(buttons(true));
+    match interaction {
+        Some(interaction) => {
+            interaction
+                .create_response(
+                    ctx.serenity_context(),
+                    CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new()
+                            .content(outcome)
+                            .embed(announcement_embed(&title, &body))
+                            .components(buttons(true)),
+                    ),
+                )
+                .await?;
+        }
+        None => reply.edit(ctx.into(), update).await?,
+    }
+    Ok(())
+}
+
+/// Delivery results for recent announcements
+#[poise::command(
+    slash_command,
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    rename = "status"
+)]
+pub async fn announce_status(ctx: Context<'_>) -> Result<(), Error> {
+    let pool = &ctx.data().db_pool;
+    let announcements = Announcement::recent(pool, 5).await?;
+
+    let mut embed = CreateEmbed::new()
+        .title("📣 Announcements")
+        .color(EmbedColor::Primary.value())
+        .timestamp(Timestamp::now());
+
+    if announcements.is_empty() {
+        embed = embed.description("No announcements have been sent.");
+    }
+    for announcement in announcements {
+        let tally = AnnouncementDelivery::tally(pool, announcement.id).await?;
+        embed = embed.field(
+            format!("#{} {}", announcement.id, announcement.title),
+            format!(
+                "{} • {}",
+                if tally.is_complete() {
+                    "Delivered"
+                } else {
+                    "In progress"
+                },
+                tally.describe()
+            ),
+            false,
+        );
+    }
+
+    ResponseHelper::send_embed(ctx, embed).await?;
+    Ok(())
+}
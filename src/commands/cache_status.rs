@@ -1,7 +1,12 @@
 use crate::bot::{Context, Error};
+use crate::data::models::DatabaseStats;
+use crate::utils::process_stats::{format_bytes, format_uptime, resident_memory_bytes};
 use crate::utils::{EmbedColor, ResponseHelper};
 use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, Timestamp};
 
+/// Guilds listed by name before the rest are summarised
+const LISTED_GUILDS: usize = 5;
+
 /// Display cache statistics and information
 #[poise::command(
     slash_command,
@@ -9,7 +14,10 @@ use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, Timestamp};
     aliases("cache", "c", "status"),
     broadcast_typing
 )]
-pub async fn cache_status(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn cache_status(
+    ctx: Context<'_>,
+    #[description = "Also count rows in the booster tables (slower)"] detailed: Option<bool>,
+) -> Result<(), Error> {
     // Extract all cache data before any await points to avoid Send issues
     let cache_data = {
         let cache = ctx.cache();
@@ -25,7 +33,7 @@ pub async fn cache_status(ctx: Context<'_>) -> Result<(), Error> {
                     .guild(*guild_id)
                     .map(|guild| format!("{} ({})", guild.name, guild.id))
             })
-            .take(5) // Show first 5 guilds to avoid spam
+            .take(LISTED_GUILDS) // Avoid spam in large deployments
             .collect();
 
         (guild_count, user_count, channel_count, cached_guilds)
@@ -35,23 +43,120 @@ pub async fn cache_status(ctx: Context<'_>) -> Result<(), Error> {
 
     let guilds_display = if cached_guilds.is_empty() {
         "No guilds cached".to_string()
-    } else if guild_count > 5 {
+    } else if guild_count > LISTED_GUILDS {
         format!(
             "{}\n... and {} more",
             cached_guilds.join("\n"),
-            guild_count - 5
+            guild_count - LISTED_GUILDS
         )
     } else {
         cached_guilds.join("\n")
     };
 
-    let embed = CreateEmbed::new()
+    let data = ctx.data();
+    let prefix_stats = data.prefix_cache.stats().await;
+    let prefix_display = format!(
+        "{} hits / {} misses ({})\n{} custom, {} using the default",
+        prefix_stats.hits,
+        prefix_stats.misses,
+        prefix_stats
+            .hit_rate()
+            .map(|rate| format!("{:.1}% hit rate", rate))
+            .unwrap_or_else(|| "no lookups yet".to_string()),
+        prefix_stats.custom,
+        prefix_stats.default
+    );
+
+    let boost_stats = data.boost_states.stats().await;
+    let boost_display = format!(
+        "{} hits / {} misses ({})\n{} members tracked, {} boosting",
+        boost_stats.hits,
+        boost_stats.misses,
+        boost_stats
+            .hit_rate()
+            .map(|rate| format!("{:.1}% hit rate", rate))
+            .unwrap_or_else(|| "no updates yet".to_string()),
+        boost_stats.tracked,
+        boost_stats.boosting
+    );
+
+    let filters: Vec<_> = data
+        .content_filters
+        .read()
+        .await
+        .values()
+        .cloned()
+        .collect();
+    let mut filter_sizes = Vec::with_capacity(filters.len());
+    for filter in filters {
+        filter_sizes.push(filter.get_cache_stats().await);
+    }
+    filter_sizes.sort_by_key(|stats| std::cmp::Reverse(stats.word_count));
+    let filter_display = if filter_sizes.is_empty() {
+        "No guild filters loaded".to_string()
+    } else {
+        let total: usize = filter_sizes.iter().map(|stats| stats.word_count).sum();
+        let mut lines = vec![format!(
+            "{} guilds, {} words cached",
+            filter_sizes.len(),
+            total
+        )];
+        lines.extend(
+            filter_sizes
+                .iter()
+                .take(LISTED_GUILDS)
+                .map(|stats| format!("`{}`: {} words", stats.guild_id, stats.word_count)),
+        );
+        lines.join("\n")
+    };
+
+    let memory = resident_memory_bytes()
+        .map(format_bytes)
+        .unwrap_or_else(|| "unavailable".to_string());
+    let process_display = format!(
+        "Uptime: {}\nMemory: {}",
+        format_uptime(data.started_at.elapsed()),
+        memory
+    );
+
+    let database_display = match DatabaseStats::file_size(&data.db_pool).await {
+        Ok(size) => format!("File size: {}", format_bytes(size.max(0) as u64)),
+        Err(e) => {
+            tracing::warn!(error = ?e, "Failed to read database size");
+            "File size: unavailable".to_string()
+        }
+    };
+
+    let mut embed = CreateEmbed::new()
         .title("📊 Cache Status")
         .color(EmbedColor::Info.value())
         .field("🏰 Cached Guilds", guild_count.to_string(), true)
         .field("👥 Cached Users", user_count.to_string(), true)
         .field("💬 Cached Channels", channel_count.to_string(), true)
         .field("📋 Guild Details", guilds_display, false)
+        .field("🔤 Prefix Cache", prefix_display, false)
+        .field("💎 Boost State Cache", boost_display, false)
+        .field("🚫 Content Filters", filter_display, false)
+        .field("⚙️ Process", process_display, true)
+        .field("🗄️ Database", database_display, true);
+
+    // Counting rows scans every booster table, so it's opt-in
+    if detailed.unwrap_or(false) {
+        let rows_display = match DatabaseStats::booster_row_counts(&data.db_pool).await {
+            Ok(counts) => counts
+                .iter()
+                .map(|(table, count)| format!("`{}`: {}", table, count))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Err(e) => {
+                tracing::warn!(error = ?e, "Failed to count booster table rows");
+                "Row counts unavailable".to_string()
+            }
+        };
+        embed = embed.field("📈 Booster Table Rows", rows_display, false);
+    }
+
+    let embed = embed
         .footer(CreateEmbedFooter::new(format!(
             "Requested by {}",
             ctx.author().name
@@ -0,0 +1,43 @@
+use crate::bot::{Context, Error};
+use crate::data::models::UserPreference;
+use crate::utils::ResponseHelper;
+
+/// Toggle compact, mobile-friendly list output for yourself
+#[poise::command(
+    slash_command,
+    prefix_command,
+    aliases("mobile"),
+    description_localized("en-US", "Toggle compact, mobile-friendly list output")
+)]
+pub async fn compact(
+    ctx: Context<'_>,
+    #[description = "Turn compact mode on or off (toggles when omitted)"] enabled: Option<bool>,
+) -> Result<(), Error> {
+    let pool = &ctx.data().db_pool;
+    let user_id = ctx.author().id;
+
+    let enabled = match enabled {
+        Some(enabled) => enabled,
+        None => !UserPreference::compact_mode(pool, user_id).await?,
+    };
+
+    UserPreference::set_compact_mode(pool, user_id, enabled).await?;
+
+    if enabled {
+        ResponseHelper::send_success(
+            ctx,
+            "📱 Compact Mode On",
+            "Lists will now show one line per entry with larger pages.",
+        )
+        .await?;
+    } else {
+        ResponseHelper::send_success(
+            ctx,
+            "🖥️ Compact Mode Off",
+            "Lists will use the full, detailed layout again.",
+        )
+        .await?;
+    }
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
 use crate::bot::{Context, Error};
+use crate::utils::error::{lookup_error_code, ERROR_CODES};
+use crate::utils::usage_example::{command_examples, render_examples};
 use crate::utils::{EmbedColor, ResponseHelper};
 use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, Timestamp};
 
@@ -14,7 +16,14 @@ pub async fn help(
     #[description = "Specific command to show help about"]
     #[autocomplete = "poise::builtins::autocomplete_command"]
     command: Option<String>,
+    #[description = "Error code from the footer of an error message, e.g. DB-002"]
+    #[rename = "errors"]
+    error_code: Option<String>,
 ) -> Result<(), Error> {
+    if let Some(code) = error_code {
+        return explain_error_code(ctx, &code).await;
+    }
+
     match command {
         Some(cmd_name) => {
             // Show help for specific command
@@ -44,14 +53,8 @@ pub async fn help(
                 } else {
                     ctx.data().settings.command_prefix.clone()
                 };
-                embed = embed.field(
-                    "💬 Usage",
-                    format!(
-                        "**Slash:** `/{}`\n**Prefix:** `{}{}`",
-                        cmd.name, actual_prefix, cmd.name
-                    ),
-                    false,
-                );
+                let examples = command_examples(cmd, &[], &actual_prefix);
+                embed = embed.field("💬 Examples", render_examples(&examples), false);
 
                 // Add parameters if any
                 let params: Vec<String> = cmd
@@ -181,3 +184,32 @@ pub async fn help(
 
     Ok(())
 }
+
+/// Explain a code from an error message's footer
+async fn explain_error_code(ctx: Context<'_>, code: &str) -> Result<(), Error> {
+    let Some(entry) = lookup_error_code(code) else {
+        let known: Vec<&str> = ERROR_CODES.iter().map(|entry| entry.code).collect();
+        ResponseHelper::send_error(
+            ctx,
+            "Unknown Error Code",
+            format!(
+                "No error has the code '{}'. Known codes: {}",
+                code,
+                known.join(", ")
+            ),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let embed = CreateEmbed::new()
+        .title(format!("📚 {}: {}", entry.code, entry.title))
+        .description(entry.description)
+        .color(EmbedColor::Info.value())
+        .footer(CreateEmbedFooter::new(
+            "Share the Ref from the error's footer when reporting a problem",
+        ));
+
+    ResponseHelper::send_embed(ctx, embed).await?;
+    Ok(())
+}
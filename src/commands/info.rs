@@ -1,7 +1,135 @@
 use crate::bot::{Context, Error};
 use crate::utils::{EmbedColor, ResponseHelper};
 use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, Timestamp};
-use serenity::all::{PremiumTier, User};
+use serenity::all::{
+    Guild, GuildId, PartialGuild, PremiumTier, User, UserId, VerificationLevel,
+};
+
+/// Where server information was read from, noted in the footer for debugging
+#[derive(Debug, Clone, Copy)]
+enum ServerInfoSource {
+    Cache,
+    Api,
+}
+
+/// Everything `/info server` shows, from either the cache or the API
+struct ServerInfo {
+    name: String,
+    id: GuildId,
+    owner_id: UserId,
+    member_count: Option<u64>,
+    online_count: Option<u64>,
+    /// Not part of the API's guild payload
+    channel_count: Option<usize>,
+    role_count: usize,
+    emoji_count: usize,
+    sticker_count: usize,
+    premium_tier: PremiumTier,
+    boost_count: u64,
+    icon_url: Option<String>,
+    banner_url: Option<String>,
+    verification_level: VerificationLevel,
+    source: ServerInfoSource,
+}
+
+impl ServerInfo {
+    fn from_cache(guild: &Guild) -> Self {
+        Self {
+            name: guild.name.clone(),
+            id: guild.id,
+            owner_id: guild.owner_id,
+            member_count: Some(guild.member_count),
+            online_count: None,
+            channel_count: Some(guild.channels.len()),
+            role_count: guild.roles.len(),
+            emoji_count: guild.emojis.len(),
+            sticker_count: guild.stickers.len(),
+            premium_tier: guild.premium_tier,
+            boost_count: guild.premium_subscription_count.unwrap_or(0),
+            icon_url: guild.icon_url(),
+            banner_url: guild.banner_url(),
+            verification_level: guild.verification_level,
+            source: ServerInfoSource::Cache,
+        }
+    }
+
+    fn from_partial(guild: &PartialGuild) -> Self {
+        Self {
+            name: guild.name.clone(),
+            id: guild.id,
+            owner_id: guild.owner_id,
+            member_count: guild.approximate_member_count,
+            online_count: guild.approximate_presence_count,
+            channel_count: None,
+            role_count: guild.roles.len(),
+            emoji_count: guild.emojis.len(),
+            sticker_count: guild.stickers.len(),
+            premium_tier: guild.premium_tier,
+            boost_count: guild.premium_subscription_count.unwrap_or(0),
+            icon_url: guild.icon_url(),
+            banner_url: guild.banner_url(),
+            verification_level: guild.verification_level,
+            source: ServerInfoSource::Api,
+        }
+    }
+
+    fn embed(self, requested_by: &str) -> CreateEmbed {
+        let boost_tier = match self.premium_tier {
+            PremiumTier::Tier0 => "0",
+            PremiumTier::Tier1 => "1",
+            PremiumTier::Tier2 => "2",
+            PremiumTier::Tier3 => "3",
+            _ => "Unknown",
+        };
+        let members = match (self.member_count, self.online_count) {
+            (Some(total), Some(online)) => format!("{} ({} online)", total, online),
+            (Some(total), None) => total.to_string(),
+            (None, _) => "Unknown".to_string(),
+        };
+        let source = match self.source {
+            ServerInfoSource::Cache => "cache",
+            ServerInfoSource::Api => "API",
+        };
+
+        let mut embed = CreateEmbed::new()
+            .title("🏰 Server Information")
+            .color(EmbedColor::Primary.value())
+            .thumbnail(self.icon_url.unwrap_or_default())
+            .field("📛 Name", self.name, true)
+            .field("🆔 Server ID", self.id.to_string(), true)
+            .field("👑 Owner", format!("<@{}>", self.owner_id), true)
+            .field("👥 Members", members, true);
+        if let Some(channel_count) = self.channel_count {
+            embed = embed.field("💬 Channels", channel_count.to_string(), true);
+        }
+        embed = embed
+            .field("🎭 Roles", self.role_count.to_string(), true)
+            .field("😀 Emojis", self.emoji_count.to_string(), true)
+            .field("🏷️ Stickers", self.sticker_count.to_string(), true)
+            .field("🚀 Boost Level", format!("Level {}", boost_tier), true)
+            .field("✨ Boosts", self.boost_count.to_string(), true)
+            .field(
+                "📅 Created",
+                format!("<t:{}:R>", self.id.created_at().unix_timestamp()),
+                true,
+            )
+            .field(
+                "🔐 Verification Level",
+                format!("{:?}", self.verification_level),
+                true,
+            );
+        if let Some(banner_url) = self.banner_url {
+            embed = embed.image(banner_url);
+        }
+
+        embed
+            .footer(CreateEmbedFooter::new(format!(
+                "Requested by {} • Data from {}",
+                requested_by, source
+            )))
+            .timestamp(Timestamp::now())
+    }
+}
 
 /// Get information about the server, a user, or the bot
 #[poise::command(
@@ -17,90 +145,7 @@ pub async fn info(
 ) -> Result<(), Error> {
     let embed = match info_type.as_deref() {
         Some("server") => {
-            if let Some(_guild_id) = ctx.guild_id() {
-                // Extract all guild data before any await points to avoid Send issues
-                let guild_data = ctx.guild().map(|guild| {
-                    let channel_count = guild.channels.len();
-                    let role_count = guild.roles.len();
-                    let boost_tier = match guild.premium_tier {
-                        PremiumTier::Tier0 => "0",
-                        PremiumTier::Tier1 => "1",
-                        PremiumTier::Tier2 => "2",
-                        PremiumTier::Tier3 => "3",
-                        _ => "Unknown",
-                    };
-                    let boost_count = guild.premium_subscription_count.unwrap_or(0);
-                    let guild_name = guild.name.clone();
-                    let guild_id = guild.id;
-                    let owner_id = guild.owner_id;
-                    let member_count = guild.member_count;
-                    let icon_url = guild.icon_url().unwrap_or_default();
-                    let verification_level = guild.verification_level;
-
-                    (
-                        guild_name,
-                        guild_id,
-                        owner_id,
-                        member_count,
-                        channel_count,
-                        role_count,
-                        boost_tier.to_string(),
-                        boost_count,
-                        icon_url,
-                        verification_level,
-                    )
-                });
-
-                if let Some((
-                    guild_name,
-                    guild_id,
-                    owner_id,
-                    member_count,
-                    channel_count,
-                    role_count,
-                    boost_tier,
-                    boost_count,
-                    icon_url,
-                    verification_level,
-                )) = guild_data
-                {
-                    CreateEmbed::new()
-                        .title("🏰 Server Information")
-                        .color(EmbedColor::Primary.value())
-                        .thumbnail(icon_url)
-                        .field("📛 Name", guild_name, true)
-                        .field("🆔 Server ID", guild_id.to_string(), true)
-                        .field("👑 Owner", format!("<@{}>", owner_id), true)
-                        .field("👥 Members", member_count.to_string(), true)
-                        .field("💬 Channels", channel_count.to_string(), true)
-                        .field("🎭 Roles", role_count.to_string(), true)
-                        .field("🚀 Boost Level", format!("Level {}", boost_tier), true)
-                        .field("✨ Boosts", boost_count.to_string(), true)
-                        .field(
-                            "📅 Created",
-                            format!("<t:{}:R>", guild_id.created_at().unix_timestamp()),
-                            true,
-                        )
-                        .field(
-                            "🔐 Verification Level",
-                            format!("{:?}", verification_level),
-                            true,
-                        )
-                        .footer(CreateEmbedFooter::new(format!(
-                            "Requested by {}",
-                            ctx.author().name
-                        )))
-                        .timestamp(Timestamp::now())
-                } else {
-                    return ResponseHelper::send_error(
-                        ctx,
-                        "Cache Error",
-                        "Server information is not available in cache.",
-                    )
-                    .await
-                    .map(|_| ());
-                }
-            } else {
+            let Some(guild_id) = ctx.guild_id() else {
                 return ResponseHelper::send_error(
                     ctx,
                     "Server Only",
@@ -108,7 +153,20 @@ pub async fn info(
                 )
                 .await
                 .map(|_| ());
-            }
+            };
+
+            // The cache is preferred; it's only missing right after startup
+            // or with limited cache settings
+            let cached = ctx.guild().map(|guild| ServerInfo::from_cache(&guild));
+            let server = match cached {
+                Some(server) => server,
+                None => {
+                    let guild = guild_id.to_partial_guild_with_counts(ctx.http()).await?;
+                    ServerInfo::from_partial(&guild)
+                }
+            };
+
+            server.embed(&ctx.author().name)
         }
         Some("user") => {
             let user = target.as_ref().unwrap_or_else(|| ctx.author());
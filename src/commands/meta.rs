@@ -0,0 +1,162 @@
+//! Conventions shared by every command's poise metadata.
+//!
+//! Poise attributes only take literals, so these constants can't be spliced
+//! into `#[poise::command]`; instead the test below walks the registered
+//! command tree and fails when a command drifts from them.
+
+use crate::bot::{Data, Error};
+
+pub const BOOSTER_ROLES: &str = "Booster Roles";
+pub const ADMINISTRATION: &str = "Administration";
+pub const CONFIGURATION: &str = "Configuration";
+pub const OWNER: &str = "Owner";
+pub const DEVELOPMENT: &str = "Development";
+
+/// Every category a command may declare
+pub const APPROVED_CATEGORIES: &[&str] = &[
+    BOOSTER_ROLES,
+    ADMINISTRATION,
+    CONFIGURATION,
+    OWNER,
+    DEVELOPMENT,
+];
+
+/// Description suffixes for restricted commands
+pub const ADMIN_ONLY: &str = "(Admin only)";
+pub const STAFF_ONLY: &str = "(Staff only)";
+pub const OWNER_ONLY: &str = "(Owner only)";
+
+const RESTRICTION_MARKERS: &[&str] = &[ADMIN_ONLY, STAFF_ONLY, OWNER_ONLY];
+
+/// Root commands whose whole tree must be guild-only
+const GUILD_ONLY_SUITES: &[&str] = &["boosterrole"];
+
+type Command = poise::Command<Data, Error>;
+
+/// Every way `commands` breaks the conventions, one line per problem
+pub fn convention_violations(commands: &[Command]) -> Vec<String> {
+    let mut violations = Vec::new();
+    for command in commands {
+        let guild_suite = GUILD_ONLY_SUITES.contains(&command.name.as_str());
+        check_command(command, "", guild_suite, false, &mut violations);
+    }
+    violations
+}
+
+fn check_command(
+    command: &Command,
+    parent: &str,
+    guild_suite: bool,
+    parent_gated: bool,
+    violations: &mut Vec<String>,
+) {
+    // qualified_name is only filled in once the framework starts
+    let name = if parent.is_empty() {
+        command.name.clone()
+    } else {
+        format!("{} {}", parent, command.name)
+    };
+
+    if let Some(category) = &command.category {
+        if !APPROVED_CATEGORIES.contains(&category.as_str()) {
+            violations.push(format!(
+                "`{}` uses unapproved category \"{}\"",
+                name, category
+            ));
+        }
+    }
+
+    if guild_suite && !command.guild_only {
+        violations.push(format!("`{}` is missing guild_only", name));
+    }
+
+    // Poise runs a parent's permission checks before its subcommands'
+    let gated = parent_gated
+        || command.owners_only
+        || !command.required_permissions.is_empty()
+        || !command.checks.is_empty();
+
+    match command.description.as_deref() {
+        None | Some("") => violations.push(format!("`{}` has no description", name)),
+        Some(description) => {
+            let marker = RESTRICTION_MARKERS
+                .iter()
+                .find(|marker| description.ends_with(*marker));
+            if marker.is_none() && description.ends_with("only)") {
+                violations.push(format!(
+                    "`{}` has a non-standard restriction marker in \"{}\"",
+                    name, description
+                ));
+            }
+            if marker == Some(&OWNER_ONLY) && !command.owners_only {
+                violations.push(format!(
+                    "`{}` says {} but isn't owners_only",
+                    name, OWNER_ONLY
+                ));
+            } else if marker.is_some() && !gated {
+                violations.push(format!(
+                    "`{}` is marked restricted but declares no required_permissions or check",
+                    name
+                ));
+            }
+        }
+    }
+
+    for subcommand in &command.subcommands {
+        check_command(subcommand, &name, guild_suite, gated, violations);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registered_commands_follow_conventions() {
+        let violations = convention_violations(&super::super::all());
+        assert!(violations.is_empty(), "{}", violations.join("\n"));
+    }
+
+    #[test]
+    fn test_drift_is_reported() {
+        let command = |name: &str, description: &str| Command {
+            name: name.to_string(),
+            description: Some(description.to_string()).filter(|d| !d.is_empty()),
+            guild_only: true,
+            ..Default::default()
+        };
+
+        let mut root = command("boosterrole", "Booster roles");
+        root.category = Some("Booster".to_string());
+        let mut open_sub = command("open", "Anyone can run this");
+        open_sub.guild_only = false;
+        root.subcommands = vec![
+            open_sub,
+            command("wipe", "Delete every role (Administrator only)"),
+            command("purge", "Delete every role (Admin only)"),
+            command("quiet", ""),
+        ];
+
+        let violations = convention_violations(&[root]);
+        assert_eq!(violations.len(), 5, "{:#?}", violations);
+        for expected in [
+            "`boosterrole` uses unapproved category",
+            "`boosterrole open` is missing guild_only",
+            "`boosterrole wipe` has a non-standard restriction marker",
+            "`boosterrole purge` is marked restricted",
+            "`boosterrole quiet` has no description",
+        ] {
+            assert!(
+                violations.iter().any(|v| v.starts_with(expected)),
+                "missing {}",
+                expected
+            );
+        }
+
+        // A gated parent covers its subcommands
+        let mut settings = command("settings", "Guild settings");
+        settings.required_permissions = serenity::all::Permissions::MANAGE_GUILD;
+        settings.subcommands = vec![command("reset", "Reset everything (Admin only)")];
+        assert!(convention_violations(&[settings]).is_empty());
+    }
+}
@@ -0,0 +1,65 @@
+use crate::bot::{Context, Error};
+use crate::utils::{EmbedColor, ResponseHelper};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, Timestamp};
+
+/// Commands listed in the breakdown, busiest first
+const MAX_COMMANDS_SHOWN: usize = 15;
+
+/// Show command timings since the bot started (Owner only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    category = "Owner"
+)]
+pub async fn metrics(ctx: Context<'_>) -> Result<(), Error> {
+    let summary = ctx.data().performance.summary().await;
+
+    if summary.total == 0 {
+        ResponseHelper::send_info(
+            ctx,
+            "📈 Command Metrics",
+            "No commands have finished since the bot started.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let breakdown = summary
+        .commands
+        .iter()
+        .take(MAX_COMMANDS_SHOWN)
+        .map(|c| {
+            format!(
+                "`{}` — {} runs • avg **{:.1}ms** • p95 **{:.1}ms** • {} failed",
+                c.name,
+                c.count,
+                c.avg_ms,
+                c.p95_ms,
+                c.count - c.successful
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let embed = CreateEmbed::new()
+        .title("📈 Command Metrics")
+        .color(EmbedColor::Primary.value())
+        .field("Commands Run", summary.total.to_string(), true)
+        .field(
+            "Success Rate",
+            format!("{:.1}%", summary.success_rate()),
+            true,
+        )
+        .field("By Command", breakdown, false)
+        .footer(CreateEmbedFooter::new(format!(
+            "Covers the last {} executions • {} distinct commands",
+            summary.total,
+            summary.commands.len()
+        )))
+        .timestamp(Timestamp::now());
+
+    ResponseHelper::send_embed(ctx, embed).await?;
+    Ok(())
+}
@@ -1,8 +1,41 @@
 pub mod boosterrole;
+pub mod botadmin;
 pub mod cache_status;
+pub mod compact;
 pub mod help;
 pub mod info;
+pub mod meta;
+pub mod metrics;
+pub mod owner;
+pub mod perf;
 pub mod ping;
 pub mod prefix;
 pub mod settings;
+#[cfg(debug_assertions)]
 pub mod test_responses;
+
+use crate::bot::{Data, Error};
+
+/// Every command the framework registers
+pub fn all() -> Vec<poise::Command<Data, Error>> {
+    #[allow(unused_mut)]
+    let mut commands = vec![
+        ping::ping(),
+        help::help(),
+        info::info(),
+        prefix::prefix(),
+        cache_status::cache_status(),
+        boosterrole::boosterrole(),
+        settings::settings(),
+        perf::perf(),
+        metrics::metrics(),
+        botadmin::botadmin(),
+        owner::owner(),
+        compact::compact(),
+    ];
+
+    #[cfg(debug_assertions)]
+    commands.push(test_responses::test_responses());
+
+    commands
+}
@@ -0,0 +1,441 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{BoosterRole, GlobalRoleNameBlacklist};
+use crate::utils::confirm::{ask_confirmation, finish_prompt, Confirmation};
+use crate::utils::name_severity::{compile_pattern, MatchType};
+use crate::utils::paginator::paginate_embeds;
+use crate::utils::{EmbedBuilder, EmbedColor};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, GuildId};
+
+/// Words shown per page of the global blacklist
+const WORDS_PER_PAGE: usize = 20;
+
+/// Servers shown per page of `/owner guilds`
+const GUILDS_PER_PAGE: usize = 10;
+
+/// Whether the invoking user is one of the bot's owners: the application's
+/// owner or anyone in `BOT_OWNERS`
+pub(crate) fn is_bot_owner(ctx: Context<'_>) -> bool {
+    ctx.framework().options().owners.contains(&ctx.author().id)
+}
+
+/// Bot-wide moderation tools (Owner only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    category = "Owner",
+    subcommands("blacklist", "guilds")
+)]
+pub async fn owner(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Manage the role name blacklist enforced in every server (Owner only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    subcommands("blacklist_add", "blacklist_remove", "blacklist_list")
+)]
+pub async fn blacklist(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Block a word in booster role names in every server (Owner only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    rename = "add"
+)]
+pub async fn blacklist_add(
+    ctx: Context<'_>,
+    #[description = "The word or pattern to block everywhere"] word: String,
+    #[description = "exact word, substring (default), wildcard with *, or regex"]
+    match_type: Option<MatchType>,
+) -> Result<(), Error> {
+    let match_type = match_type.unwrap_or_default();
+    let word = word.trim();
+
+    if word.is_empty() || word.len() > 50 {
+        let embed = EmbedBuilder::error(
+            "❌ Invalid Word",
+            "Global blacklist words must be 1 to 50 characters long.",
+        );
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    if let Err(reason) = compile_pattern(word, match_type) {
+        let embed = EmbedBuilder::error("❌ Invalid Pattern", reason);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let pool = &ctx.data().db_pool;
+    let added = GlobalRoleNameBlacklist::add_word(pool, word, match_type, ctx.author().id).await?;
+    ctx.data().global_filter.refresh_cache().await?;
+
+    let embed = if added {
+        EmbedBuilder::success(
+            "✅ Globally Blacklisted",
+            format!(
+                "**{}** ({} match) is now blocked in booster role names in every server.",
+                word,
+                match_type.as_str()
+            ),
+        )
+    } else {
+        EmbedBuilder::warning(
+            "⚠️ Already Blacklisted",
+            format!("**{}** is already on the global blacklist.", word),
+        )
+    };
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+/// Stop blocking a word in every server (Owner only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    rename = "remove"
+)]
+pub async fn blacklist_remove(
+    ctx: Context<'_>,
+    #[description = "The word or pattern to unblock"] word: String,
+) -> Result<(), Error> {
+    let word = word.trim();
+    let removed = GlobalRoleNameBlacklist::remove_word(&ctx.data().db_pool, word).await?;
+    ctx.data().global_filter.refresh_cache().await?;
+
+    let embed = if removed {
+        EmbedBuilder::success(
+            "✅ Removed from Global Blacklist",
+            format!(
+                "**{}** is no longer blocked everywhere. Server blacklists still apply.",
+                word
+            ),
+        )
+    } else {
+        EmbedBuilder::warning(
+            "⚠️ Word Not Found",
+            format!("**{}** isn't on the global blacklist.", word),
+        )
+    };
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+/// View the blacklist enforced in every server (Owner only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    rename = "list"
+)]
+pub async fn blacklist_list(ctx: Context<'_>) -> Result<(), Error> {
+    let entries = ctx.data().global_filter.entries().await?;
+
+    if entries.is_empty() {
+        let embed = EmbedBuilder::info(
+            "🌐 Global Blacklist",
+            "No words are blacklisted globally. Use `/owner blacklist add <word>` to add one.",
+        );
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    let total_pages = entries.len().div_ceil(WORDS_PER_PAGE);
+    let pages = entries
+        .chunks(WORDS_PER_PAGE)
+        .enumerate()
+        .map(|(page, words)| {
+            let word_list = words
+                .iter()
+                .enumerate()
+                .map(|(i, entry)| {
+                    format!(
+                        "{}. `{}` ({})",
+                        page * WORDS_PER_PAGE + i + 1,
+                        entry.word,
+                        entry.match_type.as_str()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            CreateEmbed::new()
+                .title("🌐 Global Blacklist")
+                .description(format!(
+                    "**Blocked in every server ({} total):**\n\n{}",
+                    entries.len(),
+                    word_list
+                ))
+                .color(EmbedColor::Warning.value())
+                .footer(CreateEmbedFooter::new(format!(
+                    "Page {} of {}",
+                    page + 1,
+                    total_pages
+                )))
+        })
+        .collect();
+
+    paginate_embeds(ctx, pages).await
+}
+
+/// One server the bot is in, as `/owner guilds` lists it
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GuildDiagnostic {
+    id: GuildId,
+    /// `None` while the guild is unavailable and missing from the cache
+    name: Option<String>,
+    member_count: u64,
+    booster_roles: i64,
+    /// `None` when the bot's own member isn't cached
+    manage_roles: Option<bool>,
+}
+
+impl GuildDiagnostic {
+    fn line(&self) -> String {
+        let manage_roles = match self.manage_roles {
+            Some(true) => "✅ Manage Roles",
+            Some(false) => "❌ No Manage Roles",
+            None => "❔ Permissions unknown",
+        };
+        format!(
+            "**{}** (`{}`)\n{} members · {} booster role(s) · {}",
+            self.name.as_deref().unwrap_or("Unavailable"),
+            self.id,
+            self.member_count,
+            self.booster_roles,
+            manage_roles
+        )
+    }
+}
+
+/// Most booster roles first, then largest, so heavy installations lead
+fn sort_diagnostics(guilds: &mut [GuildDiagnostic]) {
+    guilds.sort_by(|a, b| {
+        b.booster_roles
+            .cmp(&a.booster_roles)
+            .then(b.member_count.cmp(&a.member_count))
+            .then(a.id.cmp(&b.id))
+    });
+}
+
+fn diagnostic_pages(guilds: &[GuildDiagnostic]) -> Vec<CreateEmbed> {
+    let total_roles: i64 = guilds.iter().map(|g| g.booster_roles).sum();
+    let total_pages = guilds.len().div_ceil(GUILDS_PER_PAGE).max(1);
+
+    let summary = format!(
+        "**{}** server(s) · **{}** booster role(s) on record",
+        guilds.len(),
+        total_roles
+    );
+    let page = |number: usize, lines: String| {
+        CreateEmbed::new()
+            .title("🛰️ Guilds")
+            .description(format!("{}\n\n{}", summary, lines))
+            .color(EmbedColor::Info.value())
+            .footer(CreateEmbedFooter::new(format!(
+                "Page {} of {}",
+                number + 1,
+                total_pages
+            )))
+    };
+
+    if guilds.is_empty() {
+        return vec![page(0, "The bot isn't in any servers.".to_string())];
+    }
+    guilds
+        .chunks(GUILDS_PER_PAGE)
+        .enumerate()
+        .map(|(number, chunk)| {
+            let lines = chunk
+                .iter()
+                .map(GuildDiagnostic::line)
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            page(number, lines)
+        })
+        .collect()
+}
+
+/// Inspect the servers the bot is in (Owner only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    subcommands("guilds_list", "guilds_leave")
+)]
+pub async fn guilds(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Member counts, booster roles and permissions in every server (Owner only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    rename = "list"
+)]
+pub async fn guilds_list(ctx: Context<'_>) -> Result<(), Error> {
+    let counts = BoosterRole::count_by_guild(&ctx.data().db_pool).await?;
+    let bot_id = ctx.framework().bot_id;
+    let cache = ctx.cache();
+
+    let mut diagnostics: Vec<GuildDiagnostic> = cache
+        .guilds()
+        .into_iter()
+        .map(|guild_id| {
+            let booster_roles = counts.get(&guild_id.get()).copied().unwrap_or(0);
+            match cache.guild(guild_id) {
+                Some(guild) => GuildDiagnostic {
+                    id: guild_id,
+                    name: Some(guild.name.clone()),
+                    member_count: guild.member_count,
+                    booster_roles,
+                    manage_roles: guild
+                        .members
+                        .get(&bot_id)
+                        .map(|member| guild.member_permissions(member).manage_roles()),
+                },
+                None => GuildDiagnostic {
+                    id: guild_id,
+                    name: None,
+                    member_count: 0,
+                    booster_roles,
+                    manage_roles: None,
+                },
+            }
+        })
+        .collect();
+    sort_diagnostics(&mut diagnostics);
+
+    paginate_embeds(ctx, diagnostic_pages(&diagnostics)).await
+}
+
+/// Remove the bot from a server, after confirming (Owner only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    rename = "leave"
+)]
+pub async fn guilds_leave(
+    ctx: Context<'_>,
+    #[description = "ID of the server to leave"] guild_id: String,
+) -> Result<(), Error> {
+    let Some(guild_id) = guild_id
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|&id| id != 0)
+        .map(GuildId::new)
+    else {
+        let embed = EmbedBuilder::error("❌ Invalid Server ID", "Give the server's numeric ID.");
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    };
+
+    if !ctx.cache().guilds().contains(&guild_id) {
+        let embed = EmbedBuilder::warning(
+            "⚠️ Not in Server",
+            format!("The bot isn't in a server with ID `{}`.", guild_id),
+        );
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    let name = ctx
+        .cache()
+        .guild(guild_id)
+        .map(|guild| guild.name.clone())
+        .unwrap_or_else(|| "Unavailable server".to_string());
+    let (answer, prompt) = ask_confirmation(
+        ctx,
+        EmbedBuilder::warning(
+            "⚠️ Leave Server?",
+            format!(
+                "The bot will leave **{}** (`{}`). Its booster roles stay in Discord \
+                and their records are cleaned up as usual once the bot is gone.",
+                name, guild_id
+            ),
+        ),
+        "Leave Server",
+    )
+    .await?;
+
+    if answer != Confirmation::Confirmed {
+        finish_prompt(
+            ctx,
+            prompt,
+            EmbedBuilder::info("Leave Cancelled", format!("The bot is still in **{}**.", name)),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    guild_id.leave(ctx.http()).await?;
+    tracing::warn!(
+        guild_id = %guild_id,
+        owner_id = %ctx.author().id,
+        "Bot owner removed the bot from a guild"
+    );
+
+    finish_prompt(
+        ctx,
+        prompt,
+        EmbedBuilder::success("👋 Left Server", format!("The bot has left **{}**.", name)),
+    )
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guild(id: u64, member_count: u64, booster_roles: i64) -> GuildDiagnostic {
+        GuildDiagnostic {
+            id: GuildId::new(id),
+            name: Some(format!("Guild {}", id)),
+            member_count,
+            booster_roles,
+            manage_roles: Some(true),
+        }
+    }
+
+    #[test]
+    fn test_guilds_sort_by_booster_roles() {
+        let mut guilds = vec![guild(1, 50, 2), guild(2, 900, 0), guild(3, 10, 7), guild(4, 60, 2)];
+        sort_diagnostics(&mut guilds);
+
+        let order: Vec<u64> = guilds.iter().map(|g| g.id.get()).collect();
+        assert_eq!(order, vec![3, 4, 1, 2]);
+    }
+
+    #[test]
+    fn test_pages_split_at_page_size() {
+        let guilds: Vec<_> = (1..=GUILDS_PER_PAGE as u64 + 1)
+            .map(|id| guild(id, 10, 1))
+            .collect();
+        assert_eq!(diagnostic_pages(&guilds).len(), 2);
+        assert_eq!(diagnostic_pages(&[]).len(), 1);
+    }
+}
@@ -0,0 +1,63 @@
+use crate::bot::{Context, Error};
+use crate::utils::query_metrics::query_metrics;
+use crate::utils::{EmbedColor, ResponseHelper};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, Timestamp};
+use std::time::Duration;
+
+const QUERY_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+const MAX_QUERIES_SHOWN: usize = 10;
+
+/// Bot performance diagnostics (Owner only)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    owners_only,
+    hide_in_help,
+    category = "Owner",
+    subcommands("queries")
+)]
+pub async fn perf(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// List the slowest database queries over the last 24 hours
+#[poise::command(slash_command, prefix_command, owners_only, hide_in_help)]
+pub async fn queries(ctx: Context<'_>) -> Result<(), Error> {
+    let metrics = query_metrics();
+    let summaries = metrics.summaries(QUERY_WINDOW);
+
+    if summaries.is_empty() {
+        ResponseHelper::send_info(
+            ctx,
+            "📊 Query Performance",
+            "No instrumented queries have run in the last 24 hours.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let lines = summaries
+        .iter()
+        .take(MAX_QUERIES_SHOWN)
+        .map(|s| {
+            format!(
+                "`{}`\n└ p50 **{:.1}ms** • p95 **{:.1}ms** • max {:.1}ms • {} calls",
+                s.name, s.p50_ms, s.p95_ms, s.max_ms, s.count
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    let embed = CreateEmbed::new()
+        .title("📊 Slowest Queries (24h)")
+        .description(lines)
+        .color(EmbedColor::Primary.value())
+        .footer(CreateEmbedFooter::new(format!(
+            "Slow query threshold: {}ms • sorted by p95",
+            metrics.slow_threshold().as_millis()
+        )))
+        .timestamp(Timestamp::now());
+
+    ResponseHelper::send_embed(ctx, embed).await?;
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use crate::bot::{Context, Error};
+use crate::utils::prefix_rules::{effective_prefixes, is_additional_prefix, validate_prefix};
 use crate::utils::EmbedColor;
 use poise::serenity_prelude as serenity;
 
+/// Manage the bot's command prefix for this server
 #[poise::command(
     slash_command,
     prefix_command,
@@ -17,6 +19,7 @@ pub async fn prefix(ctx: Context<'_>) -> Result<(), Error> {
     view_prefix(ctx).await
 }
 
+/// Show the prefix used in this server
 #[poise::command(
     slash_command,
     prefix_command,
@@ -47,8 +50,10 @@ async fn view_prefix(ctx: Context<'_>) -> Result<(), Error> {
     let embed = serenity::CreateEmbed::new()
         .title("📋 Prefix Configuration")
         .description(format!(
-            "**Current Guild Prefix:** `{}`\n**Default Prefix:** `{}`",
-            current_prefix, default_prefix
+            "**Current Guild Prefix:** `{}`\n**Default Prefix:** `{}`\n**Works Here:** {}",
+            current_prefix,
+            default_prefix,
+            effective_prefixes(&current_prefix)
         ))
         .color(EmbedColor::Primary.value())
         .footer(serenity::CreateEmbedFooter::new(format!(
@@ -60,6 +65,7 @@ async fn view_prefix(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// Set a custom command prefix for this server
 #[poise::command(
     slash_command,
     prefix_command,
@@ -73,21 +79,26 @@ async fn view_prefix(ctx: Context<'_>) -> Result<(), Error> {
 pub async fn set(
     ctx: Context<'_>,
     #[description = "The new prefix to use (1-5 characters)"] new_prefix: String,
+    #[description = "Set it even if it's one of the bot's built-in prefixes"] force: Option<bool>,
 ) -> Result<(), Error> {
     let guild_id = ctx
         .guild_id()
         .ok_or_else(|| Error::Command("This command can only be used in guilds".to_string()))?;
 
-    if new_prefix.is_empty() || new_prefix.len() > 5 {
-        return Err(Error::Command(
-            "Prefix must be 1-5 characters long".to_string(),
-        ));
-    }
+    let new_prefix = validate_prefix(&new_prefix).map_err(|e| Error::Command(e.to_string()))?;
 
-    if new_prefix.contains('@') || new_prefix.contains('#') {
-        return Err(Error::Command(
-            "Prefix cannot contain @ or # characters".to_string(),
-        ));
+    // Built-in prefixes already work everywhere, so this is usually a mistake
+    if is_additional_prefix(&new_prefix) && !force.unwrap_or(false) {
+        let embed = serenity::CreateEmbed::new()
+            .title("⚠️ Built-in Prefix")
+            .description(format!(
+                "`{}` already works in every server, so setting it leaves this server without a prefix of its own.\n\nRun the command again with `force` set to true to use it anyway.",
+                new_prefix
+            ))
+            .color(EmbedColor::Warning.value());
+
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
     }
 
     let old_prefix = ctx
@@ -111,9 +122,10 @@ pub async fn set(
     let embed = serenity::CreateEmbed::new()
         .title("✅ Prefix Updated")
         .description(format!(
-            "**Old Prefix:** `{}`\n**New Prefix:** `{}`\n\nYou can now use `{}help` to test the new prefix!",
+            "**Old Prefix:** `{}`\n**New Prefix:** `{}`\n**Works Here:** {}\n\nYou can now use `{}help` to test the new prefix!",
             old_prefix,
             new_prefix,
+            effective_prefixes(&new_prefix),
             new_prefix
         ))
         .color(EmbedColor::Success.value())
@@ -125,6 +137,7 @@ pub async fn set(
     Ok(())
 }
 
+/// Remove the custom prefix and go back to the default
 #[poise::command(
     slash_command,
     prefix_command,
@@ -139,6 +152,7 @@ pub async fn remove(ctx: Context<'_>) -> Result<(), Error> {
     reset_prefix(ctx).await
 }
 
+/// Reset the prefix to the default
 #[poise::command(
     slash_command,
     prefix_command,
@@ -166,8 +180,9 @@ async fn reset_prefix(ctx: Context<'_>) -> Result<(), Error> {
         let embed = serenity::CreateEmbed::new()
             .title("ℹ️ No Custom Prefix")
             .description(format!(
-                "This guild is already using the default prefix: `{}`",
-                default_prefix
+                "This guild is already using the default prefix: `{}`\n**Works Here:** {}",
+                default_prefix,
+                effective_prefixes(default_prefix)
             ))
             .color(EmbedColor::Info.value());
 
@@ -185,8 +200,10 @@ async fn reset_prefix(ctx: Context<'_>) -> Result<(), Error> {
         let embed = serenity::CreateEmbed::new()
             .title("🔄 Prefix Reset")
             .description(format!(
-                "Reverted to default prefix: `{}`\n\nYou can now use `{}help` to test!",
-                default_prefix, default_prefix
+                "Reverted to default prefix: `{}`\n**Works Here:** {}\n\nYou can now use `{}help` to test!",
+                default_prefix,
+                effective_prefixes(default_prefix),
+                default_prefix
             ))
             .color(EmbedColor::Success.value())
             .footer(serenity::CreateEmbedFooter::new(format!(
@@ -0,0 +1,196 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{GuildAccountAgeSetting, GuildExemption, SettingsAuditLog};
+use crate::utils::args::UserArg;
+use crate::utils::eligibility::{AgeRequirement, ACCOUNT_AGE_EXEMPTION};
+use crate::utils::{EmbedColor, ResponseHelper};
+use serenity::all::{CreateEmbed, Timestamp};
+
+/// Exempt members named in the view before the rest are counted
+const MAX_LISTED_EXEMPTIONS: usize = 30;
+
+/// Require a minimum account age and membership before booster customization
+#[poise::command(
+    slash_command,
+    prefix_command,
+    subcommands(
+        "accountage_set",
+        "accountage_exempt",
+        "accountage_unexempt",
+        "accountage_view"
+    )
+)]
+pub async fn accountage(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Set the minimum account age and server membership in days (0 turns a check off)
+#[poise::command(slash_command, prefix_command, rename = "set")]
+pub async fn accountage_set(
+    ctx: Context<'_>,
+    #[description = "Days since the Discord account was created"]
+    #[max = 365]
+    account_days: u32,
+    #[description = "Days since the member joined this server"]
+    #[max = 365]
+    membership_days: u32,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    let requirement = AgeRequirement {
+        min_account_age_days: account_days.min(365),
+        min_membership_days: membership_days.min(365),
+    };
+    GuildAccountAgeSetting::set(pool, guild_id, requirement, ctx.author().id).await?;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "account_age_set",
+        Some(&requirement.describe()),
+    )
+    .await?;
+
+    let description = if requirement.is_enabled() {
+        format!(
+            "Booster role customization now requires: **{}**.\nStaff commands are unaffected. Exempt trusted members with `/settings accountage exempt`.",
+            requirement.describe()
+        )
+    } else {
+        "Account age and membership checks are off.".to_string()
+    };
+    ResponseHelper::send_success(ctx, "✅ Account Age Updated", &description).await?;
+    Ok(())
+}
+
+/// Let a member skip the account age and membership checks
+#[poise::command(slash_command, prefix_command, rename = "exempt")]
+pub async fn accountage_exempt(
+    ctx: Context<'_>,
+    #[description = "Member to exempt"] user: UserArg,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    if !GuildExemption::add(
+        pool,
+        guild_id,
+        user.id,
+        ACCOUNT_AGE_EXEMPTION,
+        ctx.author().id,
+    )
+    .await?
+    {
+        ResponseHelper::send_info(
+            ctx,
+            "ℹ️ Already Exempt",
+            &format!("<@{}> is already exempt.", user.id),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "account_age_exempt_added",
+        Some(&format!("User: <@{}>", user.id)),
+    )
+    .await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "✅ Member Exempted",
+        &format!(
+            "<@{}> can customize a booster role regardless of account age and membership.",
+            user.id
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Remove a member's account age exemption
+#[poise::command(slash_command, prefix_command, rename = "unexempt")]
+pub async fn accountage_unexempt(
+    ctx: Context<'_>,
+    #[description = "Member to remove the exemption from"] user: UserArg,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    if !GuildExemption::remove(pool, guild_id, user.id, ACCOUNT_AGE_EXEMPTION).await? {
+        ResponseHelper::send_info(
+            ctx,
+            "ℹ️ Not Exempt",
+            &format!("<@{}> isn't exempt.", user.id),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "account_age_exempt_removed",
+        Some(&format!("User: <@{}>", user.id)),
+    )
+    .await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "✅ Exemption Removed",
+        &format!("<@{}> is subject to the account age checks again.", user.id),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Show the thresholds and exempt members
+#[poise::command(slash_command, prefix_command, rename = "view")]
+pub async fn accountage_view(ctx: Context<'_>) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    let requirement = GuildAccountAgeSetting::get(pool, guild_id).await?;
+    let exemptions = GuildExemption::list(pool, guild_id, ACCOUNT_AGE_EXEMPTION).await?;
+
+    let exempt_display = if exemptions.is_empty() {
+        "None".to_string()
+    } else {
+        let mut listed = exemptions
+            .iter()
+            .take(MAX_LISTED_EXEMPTIONS)
+            .map(|e| format!("<@{}>", e.user_id))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if exemptions.len() > MAX_LISTED_EXEMPTIONS {
+            listed.push_str(&format!(
+                " …and {} more",
+                exemptions.len() - MAX_LISTED_EXEMPTIONS
+            ));
+        }
+        listed
+    };
+
+    let embed = CreateEmbed::new()
+        .title("⏳ Account Age Requirement")
+        .field("Requirement", requirement.describe(), false)
+        .field("Exempt Members", exempt_display, false)
+        .color(EmbedColor::Primary.value())
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
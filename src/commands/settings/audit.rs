@@ -0,0 +1,105 @@
+use crate::bot::{Context, Error};
+use crate::data::models::SettingsAuditLog;
+use crate::utils::args::UserArg;
+use crate::utils::audit::settings_audit_line;
+use crate::utils::embed_builder::MAX_AUTOCOMPLETE_CHOICES;
+use crate::utils::paginator::paginate_embeds;
+use crate::utils::{EmbedBuilder, ResponseHelper};
+use poise::serenity_prelude::CreateEmbedFooter;
+
+/// Entries shown per page
+const AUDIT_PAGE_SIZE: i64 = 10;
+
+/// Entries shown when no limit is given
+const DEFAULT_AUDIT_LIMIT: i64 = 50;
+
+/// Suggest actions this server's audit log actually contains
+async fn autocomplete_action(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Vec::new();
+    };
+    let partial = partial.trim().to_lowercase();
+    SettingsAuditLog::actions(&ctx.data().db_pool, guild_id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|action| action.contains(&partial))
+        .take(MAX_AUTOCOMPLETE_CHOICES)
+        .collect()
+}
+
+/// Review recent settings changes, optionally by action or member
+#[poise::command(slash_command, prefix_command, required_permissions = "MANAGE_GUILD")]
+pub async fn audit(
+    ctx: Context<'_>,
+    #[description = "Only show this action, e.g. command_disable"]
+    #[autocomplete = "autocomplete_action"]
+    action: Option<String>,
+    #[description = "Only show changes made by this member"] user: Option<UserArg>,
+    #[description = "How many entries to show (default 50)"]
+    #[min = 1]
+    #[max = 100]
+    limit: Option<i64>,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+    let action = action.map(|action| action.trim().to_lowercase());
+    let user_id = user.map(|user| user.id);
+    let limit = limit.unwrap_or(DEFAULT_AUDIT_LIMIT).clamp(1, 100);
+
+    let total = SettingsAuditLog::count(pool, guild_id, action.as_deref(), user_id).await?;
+    if total == 0 {
+        ResponseHelper::send_info(
+            ctx,
+            "📋 Settings Audit Log",
+            "No settings changes match those filters.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut filters = Vec::new();
+    if let Some(action) = &action {
+        filters.push(format!("action `{}`", action));
+    }
+    if let Some(user_id) = user_id {
+        filters.push(format!("by <@{}>", user_id));
+    }
+
+    let shown = total.min(limit);
+    let page_count = (shown + AUDIT_PAGE_SIZE - 1) / AUDIT_PAGE_SIZE;
+    let mut pages = Vec::new();
+    for page in 0..page_count {
+        let entries = SettingsAuditLog::query(
+            pool,
+            guild_id,
+            action.as_deref(),
+            user_id,
+            AUDIT_PAGE_SIZE.min(shown - page * AUDIT_PAGE_SIZE),
+            page * AUDIT_PAGE_SIZE,
+        )
+        .await?;
+
+        let mut description = entries
+            .iter()
+            .map(settings_audit_line)
+            .collect::<Vec<_>>()
+            .join("\n");
+        if !filters.is_empty() {
+            description = format!("Filtered to {}\n\n{}", filters.join(", "), description);
+        }
+
+        let mut footer = format!("Page {}/{} · {} entries", page + 1, page_count, total);
+        if total > shown {
+            footer.push_str(&format!(" · showing the latest {}", shown));
+        }
+        pages.push(
+            EmbedBuilder::primary("📋 Settings Audit Log", description)
+                .footer(CreateEmbedFooter::new(footer)),
+        );
+    }
+
+    paginate_embeds(ctx, pages).await
+}
@@ -1,16 +1,23 @@
 use crate::bot::{Context, Error};
 use crate::data::models::{GuildAutoNickname, SettingsAuditLog};
+use crate::utils::nickname_template::{self, NicknameValues, PLACEHOLDERS};
 use crate::utils::{ResponseHelper, SettingsError};
 
-#[poise::command(slash_command, prefix_command, subcommands("set", "disable", "view"))]
+/// Give new members a nickname automatically
+#[poise::command(
+    slash_command,
+    prefix_command,
+    subcommands("set", "disable", "view", "preview")
+)]
 pub async fn autonick(_: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// Set the nickname template for new members
 #[poise::command(slash_command, prefix_command)]
 pub async fn set(
     ctx: Context<'_>,
-    #[description = "Nickname template (use {username} for username)"] template: String,
+    #[description = "Nickname template, e.g. {displayname} | #{join_position}"] template: String,
 ) -> Result<(), Error> {
     super::validate_permissions(&ctx).await?;
 
@@ -32,6 +39,10 @@ pub async fn set(
         .into());
     }
 
+    if let Some(err) = placeholder_error(&template) {
+        return Err(err.into());
+    }
+
     GuildAutoNickname::set(pool, guild_id, &template, ctx.author().id).await?;
 
     SettingsAuditLog::log(
@@ -44,7 +55,7 @@ pub async fn set(
     .await?;
 
     // Show preview
-    let preview = template.replace("{username}", &ctx.author().name);
+    let preview = render_for_author(ctx, &template).await?;
 
     ResponseHelper::send_success(
         ctx,
@@ -58,6 +69,7 @@ pub async fn set(
     Ok(())
 }
 
+/// Stop setting nicknames for new members
 #[poise::command(slash_command, prefix_command)]
 pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
     super::validate_permissions(&ctx).await?;
@@ -89,6 +101,7 @@ pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// Show the nickname template
 #[poise::command(slash_command, prefix_command)]
 pub async fn view(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Not in guild")?;
@@ -97,9 +110,7 @@ pub async fn view(ctx: Context<'_>) -> Result<(), Error> {
     let auto_nick = GuildAutoNickname::get(pool, guild_id).await?;
 
     if let Some(config) = auto_nick {
-        let preview = config
-            .nickname_template
-            .replace("{username}", &ctx.author().name);
+        let preview = render_for_author(ctx, &config.nickname_template).await?;
 
         ResponseHelper::send_info(
             ctx,
@@ -119,4 +130,102 @@ pub async fn view(ctx: Context<'_>) -> Result<(), Error> {
         .await?;
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Render a template against your own profile without saving it
+#[poise::command(slash_command, prefix_command)]
+pub async fn preview(
+    ctx: Context<'_>,
+    #[description = "Template to try (defaults to the saved one)"] template: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    let template = match template {
+        Some(template) => template,
+        None => match GuildAutoNickname::get(pool, guild_id).await? {
+            Some(config) => config.nickname_template,
+            None => {
+                ResponseHelper::send_info(
+                    ctx,
+                    "📝 Auto-Nickname",
+                    "Auto-nickname is not configured. Pass a template to preview one.",
+                )
+                .await?;
+                return Ok(());
+            }
+        },
+    };
+
+    if let Some(err) = placeholder_error(&template) {
+        return Err(err.into());
+    }
+
+    let preview = render_for_author(ctx, &template).await?;
+
+    ResponseHelper::send_info(
+        ctx,
+        "👀 Auto-Nickname Preview",
+        &format!(
+            "Template: `{}`\nYou'd be named: **{}**\n\nPlaceholders: {}",
+            template,
+            preview,
+            PLACEHOLDERS
+                .iter()
+                .map(|p| format!("`{}`", p))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// The error for a template using placeholders that don't exist
+fn placeholder_error(template: &str) -> Option<SettingsError> {
+    let unknown = nickname_template::unknown_placeholders(template);
+    if unknown.is_empty() {
+        return None;
+    }
+
+    Some(SettingsError::InvalidNickname(format!(
+        "Unknown placeholder(s) {}. Available: {}",
+        unknown.join(", "),
+        PLACEHOLDERS.join(", ")
+    )))
+}
+
+/// Render `template` as the invoking member would get it. The join position
+/// is counted from cached members, so it's approximate on large servers.
+async fn render_for_author(ctx: Context<'_>, template: &str) -> Result<String, Error> {
+    let member = ctx
+        .author_member()
+        .await
+        .ok_or("Couldn't load your member profile")?;
+
+    let (member_count, join_position) = match ctx.guild() {
+        Some(guild) => {
+            let position = match member.joined_at {
+                Some(joined) => guild
+                    .members
+                    .values()
+                    .filter(|m| m.joined_at.is_some_and(|at| at <= joined))
+                    .count() as u64,
+                None => guild.member_count,
+            };
+            (guild.member_count, position.max(1))
+        }
+        None => (0, 0),
+    };
+
+    Ok(nickname_template::render(
+        template,
+        &NicknameValues {
+            username: &member.user.name,
+            display_name: member.display_name(),
+            discriminator: member.user.discriminator.map(|d| d.get()),
+            member_count,
+            join_position,
+        },
+    ))
+}
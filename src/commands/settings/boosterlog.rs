@@ -0,0 +1,95 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{GuildBoosterLogChannel, SettingsAuditLog};
+use crate::utils::{ResponseHelper, SettingsError};
+use serenity::all::{GuildChannel, Permissions};
+
+/// Post booster role changes to a channel for moderators
+#[poise::command(slash_command, prefix_command, subcommands("set", "disable"))]
+pub async fn boosterlog(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Set the channel booster role changes are posted to
+#[poise::command(slash_command, prefix_command)]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "Channel for booster role changes"]
+    #[channel_types("Text")]
+    channel: GuildChannel,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    let bot_member = guild_id
+        .member(&ctx.serenity_context().http, ctx.framework().bot_id)
+        .await?;
+    let guild = guild_id
+        .to_partial_guild(&ctx.serenity_context().http)
+        .await?;
+    let perms = guild.user_permissions_in(&channel, &bot_member);
+    if !perms.contains(Permissions::SEND_MESSAGES | Permissions::EMBED_LINKS) {
+        return Err(SettingsError::ChannelPermissionDenied(
+            "I need Send Messages and Embed Links permissions in that channel".to_string(),
+        )
+        .into());
+    }
+
+    GuildBoosterLogChannel::set(pool, guild_id, channel.id, ctx.author().id).await?;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "booster_log_channel_set",
+        Some(&format!("Channel: <#{}>", channel.id)),
+    )
+    .await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "✅ Booster Log Configured",
+        &format!(
+            "Booster roles being created, renamed, recolored, shared, linked or deleted will be posted to <#{}>",
+            channel.id
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Stop posting booster role changes
+#[poise::command(slash_command, prefix_command)]
+pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    if GuildBoosterLogChannel::remove(pool, guild_id).await? {
+        SettingsAuditLog::log(
+            pool,
+            guild_id,
+            ctx.author().id,
+            "booster_log_channel_disabled",
+            None,
+        )
+        .await?;
+
+        ResponseHelper::send_success(
+            ctx,
+            "✅ Booster Log Disabled",
+            "Booster role changes will no longer be posted",
+        )
+        .await?;
+    } else {
+        ResponseHelper::send_info(
+            ctx,
+            "ℹ️ No Booster Log",
+            "The booster log was not configured",
+        )
+        .await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{GuildCommandChannel, SettingsAuditLog};
+use crate::utils::command_channels::CommandGroup;
+use crate::utils::ResponseHelper;
+use serenity::all::GuildChannel;
+
+/// Choose which channels commands may be used in
+#[poise::command(
+    slash_command,
+    prefix_command,
+    subcommands("commandchannel_add", "commandchannel_remove", "commandchannel_list")
+)]
+pub async fn commandchannel(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Allow a command group in a channel; once a group has a channel, it only works there
+#[poise::command(slash_command, prefix_command, rename = "add")]
+pub async fn commandchannel_add(
+    ctx: Context<'_>,
+    #[description = "Channel to allow the commands in"]
+    #[channel_types("Text")]
+    channel: GuildChannel,
+    #[description = "Commands to restrict (default: customization)"] group: Option<CommandGroup>,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+    let group = group.unwrap_or(CommandGroup::Customization);
+
+    GuildCommandChannel::add(pool, guild_id, channel.id, group, ctx.author().id).await?;
+    ctx.data().invalidate_command_channels(guild_id).await;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "commandchannel_add",
+        Some(&format!("{} in {}", group.as_str(), channel.id)),
+    )
+    .await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "📍 Command Channel Added",
+        &format!(
+            "**{}** commands can be used in <#{}>. Elsewhere, members are pointed to an allowed channel. Threads follow their parent channel.",
+            group.label(),
+            channel.id
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Stop allowing a command group in a channel
+#[poise::command(slash_command, prefix_command, rename = "remove")]
+pub async fn commandchannel_remove(
+    ctx: Context<'_>,
+    #[description = "Channel to remove"]
+    #[channel_types("Text")]
+    channel: GuildChannel,
+    #[description = "Group to remove it from (default: all groups)"] group: Option<CommandGroup>,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    let removed = GuildCommandChannel::remove(pool, guild_id, channel.id, group).await?;
+    if removed == 0 {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Not a Command Channel",
+            &format!("<#{}> isn't set as a command channel.", channel.id),
+        )
+        .await?;
+        return Ok(());
+    }
+    ctx.data().invalidate_command_channels(guild_id).await;
+
+    let scope = group.map(|g| g.as_str()).unwrap_or("all");
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "commandchannel_remove",
+        Some(&format!("{} from {}", channel.id, scope)),
+    )
+    .await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "📍 Command Channel Removed",
+        &format!(
+            "<#{}> was removed from **{}** group(s). A group with no channels left can be used anywhere.",
+            channel.id, removed
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Show where each command group may be used
+#[poise::command(slash_command, prefix_command, rename = "list")]
+pub async fn commandchannel_list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let rows = ctx.data().get_command_channels(guild_id).await?;
+
+    let lines = [
+        CommandGroup::Customization,
+        CommandGroup::Admin,
+        CommandGroup::Info,
+    ]
+    .iter()
+    .map(|group| {
+        let channels: Vec<String> = rows
+            .iter()
+            .filter(|(g, _)| g == group)
+            .map(|(_, channel)| format!("<#{}>", channel))
+            .collect();
+        if channels.is_empty() {
+            format!("**{}**: anywhere", group.label())
+        } else {
+            format!("**{}**: {}", group.label(), channels.join(", "))
+        }
+    })
+    .collect::<Vec<_>>()
+    .join("\n");
+
+    ResponseHelper::send_info(
+        ctx,
+        "📍 Command Channels",
+        &format!(
+            "{}\n\n`/settings commandchannel` always works, so access can't be locked out.",
+            lines
+        ),
+    )
+    .await?;
+    Ok(())
+}
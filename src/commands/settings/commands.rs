@@ -0,0 +1,176 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{GuildDisabledCommand, SettingsAuditLog};
+use crate::utils::disabled_commands::{is_protected, normalize, registered_names};
+use crate::utils::embed_builder::MAX_AUTOCOMPLETE_CHOICES;
+use crate::utils::ResponseHelper;
+
+/// Turn individual commands off or back on for this server
+#[poise::command(
+    slash_command,
+    prefix_command,
+    subcommands("commands_disable", "commands_enable", "commands_list")
+)]
+pub async fn commands(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Suggest registered commands containing what's been typed
+async fn autocomplete_command_name(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let partial = normalize(partial);
+    registered_names(&ctx.framework().options().commands)
+        .into_iter()
+        .filter(|name| !is_protected(name) && name.contains(&partial))
+        .take(MAX_AUTOCOMPLETE_CHOICES)
+        .collect()
+}
+
+/// Stop a command, or a whole command group, from being used in this server
+#[poise::command(slash_command, prefix_command, rename = "disable")]
+pub async fn commands_disable(
+    ctx: Context<'_>,
+    #[description = "Command to disable, e.g. boosterrole share"]
+    #[autocomplete = "autocomplete_command_name"]
+    #[rest]
+    command: String,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+    let command = normalize(&command);
+
+    if !registered_names(&ctx.framework().options().commands).contains(&command) {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Unknown Command",
+            &format!(
+                "`/{}` isn't one of the bot's commands. Use the full name, e.g. `boosterrole share`.",
+                command
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if is_protected(&command) {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Can't Disable",
+            "`/settings` and `/help` always stay on so the server can't be locked out.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if !GuildDisabledCommand::disable(pool, guild_id, &command, ctx.author().id).await? {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Already Disabled",
+            &format!("`/{}` is already disabled.", command),
+        )
+        .await?;
+        return Ok(());
+    }
+    ctx.data().invalidate_disabled_commands(guild_id).await;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "command_disable",
+        Some(&command),
+    )
+    .await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "🔕 Command Disabled",
+        &format!(
+            "`/{}` and any of its subcommands can no longer be used here. Turn it back on with `/settings commands enable`.",
+            command
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Allow a disabled command to be used again
+#[poise::command(slash_command, prefix_command, rename = "enable")]
+pub async fn commands_enable(
+    ctx: Context<'_>,
+    #[description = "Command to enable again"]
+    #[autocomplete = "autocomplete_command_name"]
+    #[rest]
+    command: String,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+    let command = normalize(&command);
+
+    if !GuildDisabledCommand::enable(pool, guild_id, &command).await? {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Not Disabled",
+            &format!(
+                "`/{}` isn't disabled. See what is with `/settings commands list`.",
+                command
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+    ctx.data().invalidate_disabled_commands(guild_id).await;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "command_enable",
+        Some(&command),
+    )
+    .await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "🔔 Command Enabled",
+        &format!("`/{}` can be used again.", command),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Show which commands are disabled in this server
+#[poise::command(slash_command, prefix_command, rename = "list")]
+pub async fn commands_list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let disabled = GuildDisabledCommand::list(&ctx.data().db_pool, guild_id).await?;
+
+    if disabled.is_empty() {
+        ResponseHelper::send_info(
+            ctx,
+            "🔔 Disabled Commands",
+            "Every command is enabled in this server.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let lines = disabled
+        .iter()
+        .map(|command| format!("• `/{}`", command))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    ResponseHelper::send_info(
+        ctx,
+        "🔕 Disabled Commands",
+        &format!(
+            "{}\n\nDisabling a group disables its subcommands too. `/settings` and `/help` can't be disabled.",
+            lines
+        ),
+    )
+    .await?;
+    Ok(())
+}
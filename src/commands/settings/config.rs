@@ -1,57 +1,158 @@
 use crate::bot::{Context, Error};
-use crate::data::models::{
-    GuildAutoNickname, GuildJoinLogChannel, GuildPremiumRole, GuildStaffRole,
-};
+use crate::data::models::GuildSettingsSnapshot;
+use crate::utils::eligibility::EligibilityMode;
+use crate::utils::rename_cooldown::describe_cooldown;
+use crate::utils::retention::describe_effective;
 use crate::utils::EmbedColor;
-use serenity::all::{CreateEmbed, Timestamp};
-use tokio::join;
+use serenity::all::{ChannelId, CreateAttachment, CreateEmbed, RoleId, Timestamp};
+use std::collections::HashSet;
 
+/// Longest JSON reply sent inline; anything bigger goes out as a file
+const MAX_INLINE_JSON: usize = 1900;
+
+/// View all settings for this server
 #[poise::command(slash_command, prefix_command)]
-pub async fn config(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn config(
+    ctx: Context<'_>,
+    #[description = "Show the settings as JSON for copying"] json: Option<bool>,
+) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Not in guild")?;
     let pool = &ctx.data().db_pool;
 
-    let (staff_roles, auto_nick, join_log, premium_role) = join!(
-        GuildStaffRole::list(pool, guild_id),
-        GuildAutoNickname::get(pool, guild_id),
-        GuildJoinLogChannel::get(pool, guild_id),
-        GuildPremiumRole::get(pool, guild_id)
-    );
+    let snapshot = GuildSettingsSnapshot::load(pool, guild_id).await?;
+
+    if json.unwrap_or(false) {
+        let body = serde_json::to_string_pretty(&snapshot.to_json())
+            .map_err(|e| Error::Command(format!("Failed to serialize settings: {}", e)))?;
+        let reply = if body.len() <= MAX_INLINE_JSON {
+            poise::CreateReply::default().content(format!("```json\n{}\n```", body))
+        } else {
+            poise::CreateReply::default()
+                .attachment(CreateAttachment::bytes(body.into_bytes(), "settings.json"))
+        };
+        ctx.send(reply.ephemeral(true)).await?;
+        return Ok(());
+    }
+
+    // IDs that still exist get a mention; stale ones are shown as deleted
+    let (roles, channels): (HashSet<RoleId>, HashSet<ChannelId>) = match ctx.guild() {
+        Some(guild) => (
+            guild.roles.keys().copied().collect(),
+            guild.channels.keys().copied().collect(),
+        ),
+        None => Default::default(),
+    };
+    let role = |id: Option<RoleId>| match id {
+        Some(id) if roles.is_empty() || roles.contains(&id) => format!("<@&{}>", id),
+        Some(id) => format!("`{}` (deleted)", id),
+        None => "Not set".to_string(),
+    };
+    let channel = |id: Option<ChannelId>| match id {
+        Some(id) if channels.is_empty() || channels.contains(&id) => format!("<#{}>", id),
+        Some(id) => format!("`{}` (deleted)", id),
+        None => "Not set".to_string(),
+    };
 
-    let staff_display = match staff_roles {
-        Ok(roles) if !roles.is_empty() => roles
+    let staff_display = if snapshot.staff_roles.is_empty() {
+        "Not set".to_string()
+    } else {
+        snapshot
+            .staff_roles
             .iter()
-            .map(|r| format!("<@&{}>", r.role_id))
+            .map(|&r| role(Some(r)))
             .collect::<Vec<_>>()
-            .join(", "),
-        _ => "None configured".to_string(),
+            .join(", ")
+    };
+
+    let prefix_display = match &snapshot.prefix {
+        Some(prefix) => format!("`{}`", prefix),
+        None => format!("`{}` (default)", ctx.data().settings.command_prefix),
+    };
+
+    let auto_nick_display = match &snapshot.auto_nickname {
+        Some(template) => format!("`{}`", template),
+        None => "Not set".to_string(),
+    };
+
+    let join_log_display = format!(
+        "Joins: {}\nLeaves: {}",
+        channel(snapshot.join_log_channel),
+        channel(snapshot.leave_log_channel)
+    );
+
+    let limit_display = match snapshot.booster_limit {
+        Some(limit) if limit > 0 => format!("{} roles", limit),
+        Some(_) => "Unlimited".to_string(),
+        None => "Not set".to_string(),
     };
 
-    let auto_nick_display = match auto_nick {
-        Ok(Some(an)) => format!("`{}`", an.nickname_template),
-        _ => "Disabled".to_string(),
+    let sharing_display = match snapshot.sharing_limits {
+        Some((per_role, per_member)) => format!(
+            "{} members per role\n{} roles per member",
+            per_role, per_member
+        ),
+        None => "Defaults".to_string(),
     };
 
-    let join_log_display = match join_log {
-        Ok(Some(jl)) => format!("<#{}>", jl.channel_id),
-        _ => "Disabled".to_string(),
+    let eligibility_display = match snapshot.eligibility {
+        EligibilityMode::BoostersOnly => "Boosters only",
+        EligibilityMode::PremiumRoleHolders => "Premium role holders",
+        EligibilityMode::Either => "Boosters or premium role holders",
     };
 
-    let premium_role_display = match premium_role {
-        Ok(Some(pr)) => format!("<@&{}>", pr.role_id),
-        _ => "None configured".to_string(),
+    let quiet_hours_display = match &snapshot.quiet_hours {
+        Some(window) => window.describe(),
+        None => "Not set".to_string(),
     };
 
     let embed = CreateEmbed::new()
         .title("⚙️ Current Guild Settings")
         .color(EmbedColor::Primary.value())
+        .field("Prefix", prefix_display, true)
         .field("Staff Roles", staff_display, false)
-        .field("Auto-Nickname Template", auto_nick_display, false)
-        .field("Join/Leave Logs", join_log_display, false)
-        .field("Premium Role", premium_role_display, false)
+        .field("Auto-Nickname Template", auto_nick_display, true)
+        .field("Join/Leave Logs", join_log_display, true)
+        .field(
+            "Booster Role Log",
+            channel(snapshot.booster_log_channel),
+            true,
+        )
+        .field("Premium Role", role(snapshot.premium_role), true)
+        .field("Base Role", role(snapshot.base_role), true)
+        .field("Award Role", role(snapshot.award_role), true)
+        .field("Booster Role Limit", limit_display, true)
+        .field("Sharing Limits", sharing_display, true)
+        .field(
+            "Blacklisted Words",
+            snapshot.blacklist.len().to_string(),
+            true,
+        )
+        .field(
+            "Rename Cooldown",
+            describe_cooldown(snapshot.rename_cooldown),
+            true,
+        )
+        .field(
+            "Next-Step Suggestions",
+            if snapshot.next_steps {
+                "Enabled"
+            } else {
+                "Disabled"
+            },
+            true,
+        )
+        .field("Booster Role Eligibility", eligibility_display, true)
+        .field("Minimum Account Age", snapshot.account_age.describe(), true)
+        .field("Support Channel", channel(snapshot.support_channel), true)
+        .field("Quiet Hours", quiet_hours_display, true)
+        .field("Embed Theme", snapshot.embed_theme.describe(), true)
+        .field(
+            "Data Retention",
+            describe_effective(&snapshot.retention),
+            false,
+        )
         .timestamp(Timestamp::now());
 
-    ctx.send(poise::CreateReply::default().embed(embed))
-        .await?;
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
-}
\ No newline at end of file
+}
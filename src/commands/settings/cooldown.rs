@@ -0,0 +1,179 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{GuildCommandCooldown, SettingsAuditLog};
+use crate::utils::command_cooldowns::MAX_COMMAND_COOLDOWN_SECONDS;
+use crate::utils::disabled_commands::{is_protected, normalize, registered_names};
+use crate::utils::embed_builder::MAX_AUTOCOMPLETE_CHOICES;
+use crate::utils::rename_cooldown::format_remaining;
+use crate::utils::ResponseHelper;
+
+/// Make members wait between uses of individual commands
+#[poise::command(
+    slash_command,
+    prefix_command,
+    subcommands("cooldown_set", "cooldown_remove", "cooldown_list")
+)]
+pub async fn cooldown(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Suggest registered commands containing what's been typed
+async fn autocomplete_command_name(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let partial = normalize(partial);
+    registered_names(&ctx.framework().options().commands)
+        .into_iter()
+        .filter(|name| !is_protected(name) && name.contains(&partial))
+        .take(MAX_AUTOCOMPLETE_CHOICES)
+        .collect()
+}
+
+fn describe_seconds(seconds: u64) -> String {
+    format_remaining(chrono::Duration::seconds(seconds as i64))
+}
+
+/// Set how long each member waits between uses of a command
+#[poise::command(slash_command, prefix_command, rename = "set")]
+pub async fn cooldown_set(
+    ctx: Context<'_>,
+    #[description = "Command to slow down, e.g. boosterrole dominant"]
+    #[autocomplete = "autocomplete_command_name"]
+    command: String,
+    #[description = "Seconds between uses (1-86400)"]
+    #[min = 1]
+    #[max = 86400]
+    seconds: u64,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+    let command = normalize(&command);
+    let seconds = seconds.clamp(1, MAX_COMMAND_COOLDOWN_SECONDS);
+
+    if !registered_names(&ctx.framework().options().commands).contains(&command) {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Unknown Command",
+            &format!(
+                "`/{}` isn't one of the bot's commands. Use the full name, e.g. `boosterrole dominant`.",
+                command
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if is_protected(&command) {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Can't Add a Cooldown",
+            "`/settings` and `/help` are always available without waiting.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    GuildCommandCooldown::set(pool, guild_id, &command, seconds, ctx.author().id).await?;
+    ctx.data().invalidate_command_cooldowns(guild_id).await;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "command_cooldown_set",
+        Some(&format!("{} {}s", command, seconds)),
+    )
+    .await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "⏱️ Cooldown Set",
+        &format!(
+            "Members now wait **{}** between uses of `/{}` and its subcommands. Bot owners and members with Manage Server skip the wait.",
+            describe_seconds(seconds),
+            command
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Let members use a command without waiting again
+#[poise::command(slash_command, prefix_command, rename = "remove")]
+pub async fn cooldown_remove(
+    ctx: Context<'_>,
+    #[description = "Command to remove the cooldown from"]
+    #[autocomplete = "autocomplete_command_name"]
+    #[rest]
+    command: String,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+    let command = normalize(&command);
+
+    if !GuildCommandCooldown::remove(pool, guild_id, &command).await? {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ No Cooldown",
+            &format!(
+                "`/{}` has no cooldown. See which commands do with `/settings cooldown list`.",
+                command
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+    ctx.data().invalidate_command_cooldowns(guild_id).await;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "command_cooldown_remove",
+        Some(&command),
+    )
+    .await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "⏱️ Cooldown Removed",
+        &format!("`/{}` can be used without waiting.", command),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Show which commands have a cooldown in this server
+#[poise::command(slash_command, prefix_command, rename = "list")]
+pub async fn cooldown_list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let cooldowns = GuildCommandCooldown::list(&ctx.data().db_pool, guild_id).await?;
+
+    if cooldowns.is_empty() {
+        ResponseHelper::send_info(
+            ctx,
+            "⏱️ Command Cooldowns",
+            "No commands have a cooldown in this server.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let lines = cooldowns
+        .iter()
+        .map(|(command, seconds)| format!("• `/{}` - {}", command, describe_seconds(*seconds)))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    ResponseHelper::send_info(
+        ctx,
+        "⏱️ Command Cooldowns",
+        &format!(
+            "{}\n\nA cooldown on a group covers its subcommands. Bot owners and members with Manage Server skip the wait.",
+            lines
+        ),
+    )
+    .await?;
+    Ok(())
+}
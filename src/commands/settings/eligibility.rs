@@ -0,0 +1,48 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{GuildEligibilitySetting, GuildPremiumRole, SettingsAuditLog};
+use crate::utils::eligibility::EligibilityMode;
+use crate::utils::ResponseHelper;
+
+/// Choose who can own a custom booster role
+#[poise::command(slash_command, prefix_command)]
+pub async fn eligibility(
+    ctx: Context<'_>,
+    #[description = "Who may create and edit booster roles"] mode: EligibilityMode,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    GuildEligibilitySetting::set(pool, guild_id, mode, ctx.author().id).await?;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "eligibility_mode_set",
+        Some(&format!("Mode: {}", mode.as_str())),
+    )
+    .await?;
+
+    let mut description = match mode {
+        EligibilityMode::BoostersOnly => {
+            "Only server boosters can own a custom booster role".to_string()
+        }
+        EligibilityMode::PremiumRoleHolders => {
+            "Only members with the premium role can own a custom booster role".to_string()
+        }
+        EligibilityMode::Either => {
+            "Server boosters and premium role holders can own a custom booster role".to_string()
+        }
+    };
+
+    if mode.needs_premium_role() && GuildPremiumRole::get(pool, guild_id).await?.is_none() {
+        description.push_str(
+            "\n\n⚠️ No premium role is configured yet, so only boosters qualify until you set one with `/settings premiumrole set`.",
+        );
+    }
+
+    ResponseHelper::send_success(ctx, "✅ Eligibility Updated", &description).await?;
+    Ok(())
+}
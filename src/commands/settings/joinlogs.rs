@@ -1,23 +1,58 @@
 use crate::bot::{Context, Error};
 use crate::data::models::{GuildJoinLogChannel, SettingsAuditLog};
 use crate::utils::{EmbedColor, ResponseHelper, SettingsError};
+use poise::ChoiceParameter;
 use serenity::all::{Channel, ChannelId, CreateEmbed, CreateMessage, Permissions};
 use serenity::model::mention::Mentionable;
 
+/// Which member events a join log setting applies to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum LogEvents {
+    #[default]
+    #[name = "joins and leaves"]
+    Both,
+    #[name = "joins"]
+    Joins,
+    #[name = "leaves"]
+    Leaves,
+}
+
+impl LogEvents {
+    fn joins(self) -> bool {
+        matches!(self, LogEvents::Both | LogEvents::Joins)
+    }
+
+    fn leaves(self) -> bool {
+        matches!(self, LogEvents::Both | LogEvents::Leaves)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            LogEvents::Both => "Join/leave",
+            LogEvents::Joins => "Join",
+            LogEvents::Leaves => "Leave",
+        }
+    }
+}
+
+/// Log member joins and leaves to a channel
 #[poise::command(slash_command, prefix_command, subcommands("set", "disable", "test"))]
 pub async fn joinlogs(_: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// Set the channel for join logs, leave logs, or both
 #[poise::command(slash_command, prefix_command)]
 pub async fn set(
     ctx: Context<'_>,
-    #[description = "Channel for join/leave logs"] channel: Channel,
+    #[description = "Channel for the logs"] channel: Channel,
+    #[description = "Which events go there (default: joins and leaves)"] events: Option<LogEvents>,
 ) -> Result<(), Error> {
     super::validate_permissions(&ctx).await?;
 
     let guild_id = ctx.guild_id().ok_or("Not in guild")?;
     let pool = &ctx.data().db_pool;
+    let events = events.unwrap_or_default();
 
     // Extract channel ID and validate it's a text channel
     let channel_id = match channel {
@@ -31,10 +66,9 @@ pub async fn set(
             gc.id
         }
         _ => {
-            return Err(SettingsError::ChannelPermissionDenied(
-                "Invalid channel type".to_string(),
+            return Err(
+                SettingsError::ChannelPermissionDenied("Invalid channel type".to_string()).into(),
             )
-            .into())
         }
     };
 
@@ -43,8 +77,12 @@ pub async fn set(
 
     if let Channel::Guild(gc) = &channel {
         // Get the bot member and guild to check permissions
-        let bot_member = guild_id.member(&ctx.serenity_context().http, bot_id).await?;
-        let guild = guild_id.to_partial_guild(&ctx.serenity_context().http).await?;
+        let bot_member = guild_id
+            .member(&ctx.serenity_context().http, bot_id)
+            .await?;
+        let guild = guild_id
+            .to_partial_guild(&ctx.serenity_context().http)
+            .await?;
         let perms = guild.user_permissions_in(gc, &bot_member);
         if !perms.contains(Permissions::SEND_MESSAGES | Permissions::EMBED_LINKS) {
             return Err(SettingsError::ChannelPermissionDenied(
@@ -54,21 +92,39 @@ pub async fn set(
         }
     }
 
-    GuildJoinLogChannel::set(pool, guild_id, channel_id, ctx.author().id).await?;
+    // Only the chosen side changes; the other keeps its channel
+    let current = GuildJoinLogChannel::get(pool, guild_id).await?;
+    let mut join_channel = current.as_ref().and_then(|c| c.join_channel());
+    let mut leave_channel = current.as_ref().and_then(|c| c.leave_channel());
+    if events.joins() {
+        join_channel = Some(channel_id);
+    }
+    if events.leaves() {
+        leave_channel = Some(channel_id);
+    }
+
+    GuildJoinLogChannel::set(pool, guild_id, join_channel, leave_channel, ctx.author().id).await?;
 
     SettingsAuditLog::log(
         pool,
         guild_id,
         ctx.author().id,
         "join_log_channel_set",
-        Some(&format!("Channel: <#{}>", channel_id)),
+        Some(&format!(
+            "Channel: <#{}>, events: {}",
+            channel_id,
+            events.name()
+        )),
     )
     .await?;
 
     // Send test message
     let test_embed = CreateEmbed::new()
-        .title("✅ Join Logs Configured")
-        .description("This channel will now receive member join/leave notifications")
+        .title(format!("✅ {} Logs Configured", events.label()))
+        .description(format!(
+            "This channel will now receive member {} notifications",
+            events.name()
+        ))
         .color(EmbedColor::Success.value())
         .footer(serenity::all::CreateEmbedFooter::new(format!(
             "Configured by {}",
@@ -84,61 +140,106 @@ pub async fn set(
 
     ResponseHelper::send_success(
         ctx,
-        "✅ Join Logs Configured",
-        &format!("Join/leave logs will be sent to <#{}>", channel_id),
+        &format!("✅ {} Logs Configured", events.label()),
+        &format!("{} logs will be sent to <#{}>", events.label(), channel_id),
     )
     .await?;
     Ok(())
 }
 
+/// Stop logging joins, leaves, or both
 #[poise::command(slash_command, prefix_command)]
-pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn disable(
+    ctx: Context<'_>,
+    #[description = "Which events to stop logging (default: joins and leaves)"] events: Option<
+        LogEvents,
+    >,
+) -> Result<(), Error> {
     super::validate_permissions(&ctx).await?;
 
     let guild_id = ctx.guild_id().ok_or("Not in guild")?;
     let pool = &ctx.data().db_pool;
+    let events = events.unwrap_or_default();
 
-    let removed = GuildJoinLogChannel::remove(pool, guild_id).await?;
+    let current = GuildJoinLogChannel::get(pool, guild_id).await?;
+    let join_channel = current.as_ref().and_then(|c| c.join_channel());
+    let leave_channel = current.as_ref().and_then(|c| c.leave_channel());
+    let changed =
+        (events.joins() && join_channel.is_some()) || (events.leaves() && leave_channel.is_some());
+
+    if changed {
+        GuildJoinLogChannel::set(
+            pool,
+            guild_id,
+            join_channel.filter(|_| !events.joins()),
+            leave_channel.filter(|_| !events.leaves()),
+            ctx.author().id,
+        )
+        .await?;
 
-    if removed {
         SettingsAuditLog::log(
             pool,
             guild_id,
             ctx.author().id,
             "join_log_channel_disabled",
-            None,
+            Some(&format!("Events: {}", events.name())),
         )
         .await?;
 
         ResponseHelper::send_success(
             ctx,
-            "✅ Join Logs Disabled",
-            "Join/leave logging has been disabled",
+            &format!("✅ {} Logs Disabled", events.label()),
+            &format!("{} logging has been disabled", events.label()),
         )
         .await?;
     } else {
-        ResponseHelper::send_info(ctx, "ℹ️ No Join Logs", "Join logs were not configured")
-            .await?;
+        ResponseHelper::send_info(
+            ctx,
+            "ℹ️ No Join Logs",
+            &format!("{} logs were not configured", events.label()),
+        )
+        .await?;
     }
     Ok(())
 }
 
+/// Send a test message to the log channels
 #[poise::command(slash_command, prefix_command)]
 pub async fn test(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Not in guild")?;
     let pool = &ctx.data().db_pool;
 
     let join_log = GuildJoinLogChannel::get(pool, guild_id).await?;
+    let targets: Vec<(ChannelId, &str)> = match &join_log {
+        Some(log_config) => match (log_config.join_channel(), log_config.leave_channel()) {
+            (Some(join), Some(leave)) if join == leave => vec![(join, "Joins and leaves")],
+            (join, leave) => join
+                .map(|c| (c, "Joins"))
+                .into_iter()
+                .chain(leave.map(|c| (c, "Leaves")))
+                .collect(),
+        },
+        None => Vec::new(),
+    };
 
-    if let Some(log_config) = join_log {
-        let channel_id = ChannelId::new(log_config.channel_id as u64);
+    if targets.is_empty() {
+        ResponseHelper::send_info(
+            ctx,
+            "ℹ️ No Join Logs",
+            "Join logs are not configured. Use `/settings joinlogs set` to configure.",
+        )
+        .await?;
+        return Ok(());
+    }
 
+    for (channel_id, events) in &targets {
         let test_embed = CreateEmbed::new()
             .title("🧪 Test Join Log")
             .description("This is a test message for join/leave logs")
             .color(EmbedColor::Primary.value())
             .field("Member", ctx.author().mention().to_string(), true)
             .field("Type", "Test Event", true)
+            .field("Logs", *events, true)
             .timestamp(serenity::model::Timestamp::now());
 
         channel_id
@@ -147,20 +248,18 @@ pub async fn test(ctx: Context<'_>) -> Result<(), Error> {
                 CreateMessage::new().embed(test_embed),
             )
             .await?;
-
-        ResponseHelper::send_success(
-            ctx,
-            "✅ Test Sent",
-            &format!("Test message sent to <#{}>", channel_id),
-        )
-        .await?;
-    } else {
-        ResponseHelper::send_info(
-            ctx,
-            "ℹ️ No Join Logs",
-            "Join logs are not configured. Use `/settings joinlogs set` to configure.",
-        )
-        .await?;
     }
+
+    let sent_to = targets
+        .iter()
+        .map(|(channel_id, _)| format!("<#{}>", channel_id))
+        .collect::<Vec<_>>()
+        .join(" and ");
+    ResponseHelper::send_success(
+        ctx,
+        "✅ Test Sent",
+        &format!("Test message sent to {}", sent_to),
+    )
+    .await?;
     Ok(())
-}
\ No newline at end of file
+}
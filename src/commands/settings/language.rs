@@ -0,0 +1,41 @@
+use crate::bot::{Context, Error};
+use crate::data::models::SettingsAuditLog;
+use crate::utils::i18n::{translate, Locale};
+use crate::utils::ResponseHelper;
+
+/// Choose the language replies default to in this server
+#[poise::command(slash_command, prefix_command)]
+pub async fn language(
+    ctx: Context<'_>,
+    #[description = "Language for members whose Discord language isn't supported"] locale: Locale,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+
+    ctx.data()
+        .set_guild_locale(guild_id, locale, ctx.author().id)
+        .await?;
+
+    SettingsAuditLog::log(
+        &ctx.data().db_pool,
+        guild_id,
+        ctx.author().id,
+        "language_set",
+        Some(locale.code()),
+    )
+    .await?;
+
+    // Confirmed in the new language so admins see what members will get
+    ResponseHelper::send_success(
+        ctx,
+        translate(locale, "settings.language.title", &[]),
+        translate(
+            locale,
+            "settings.language.body",
+            &[("language", &locale.label())],
+        ),
+    )
+    .await?;
+    Ok(())
+}
@@ -1,15 +1,36 @@
 use crate::bot::{Context, Error};
-use crate::utils::{ResponseHelper, SettingsError};
+use crate::utils::{load_staff_role_ids, member_is_staff, CheckFailure, ResponseHelper};
 use serenity::all::Permissions;
 
 pub type SettingsContext<'a> = Context<'a>;
 
+pub mod accountage;
+pub mod audit;
 pub mod autonick;
+pub mod boosterlog;
+pub mod commandchannel;
+pub mod commands;
 pub mod config;
+pub mod cooldown;
+pub mod eligibility;
 pub mod joinlogs;
+pub mod language;
+pub mod nextsteps;
+pub mod permissiondebug;
 pub mod premiumrole;
+pub mod quiethours;
+pub mod renamecooldown;
+pub mod retention;
+pub mod setup;
+pub mod snapshot;
 pub mod staff;
+pub mod stafflookalike;
+pub mod supportchannel;
+pub mod theme;
+pub mod transfer;
+pub mod usage;
 
+/// Configure the bot for this server (Admin only)
 #[poise::command(
     slash_command,
     prefix_command,
@@ -18,11 +39,32 @@ pub mod staff;
     required_permissions = "MANAGE_GUILD",
     guild_only,
     subcommands(
+        "setup::setup",
         "config::config",
         "staff::staff",
+        "stafflookalike::stafflookalike",
         "autonick::autonick",
         "joinlogs::joinlogs",
-        "premiumrole::premiumrole"
+        "boosterlog::boosterlog",
+        "premiumrole::premiumrole",
+        "nextsteps::nextsteps",
+        "eligibility::eligibility",
+        "supportchannel::supportchannel",
+        "snapshot::snapshot",
+        "permissiondebug::permissiondebug",
+        "quiethours::quiethours",
+        "accountage::accountage",
+        "retention::retention",
+        "commandchannel::commandchannel",
+        "commands::commands",
+        "cooldown::cooldown",
+        "renamecooldown::renamecooldown",
+        "theme::theme",
+        "language::language",
+        "transfer::export",
+        "transfer::import",
+        "audit::audit",
+        "usage::usage"
     ),
     broadcast_typing
 )]
@@ -31,16 +73,53 @@ pub async fn settings(ctx: Context<'_>) -> Result<(), Error> {
         ctx,
         "⚙️ Guild Settings",
         "Use subcommands to configure your server:\n\
+        • `/settings setup` - Walk through the essentials step by step\n\
         • `/settings config` - View all settings\n\
         • `/settings staff` - Manage staff roles\n\
+        • `/settings stafflookalike` - Block booster role names that imitate staff roles\n\
         • `/settings autonick` - Auto-nickname setup\n\
-        • `/settings joinlogs` - Join/leave logging\n\
-        • `/settings premiumrole` - Premium role setup",
+        • `/settings joinlogs` - Join/leave logging and invite tracking\n\
+        • `/settings boosterlog` - Post booster role changes for moderators\n\
+        • `/settings premiumrole` - Premium role for boosters, and syncing it\n\
+        • `/settings nextsteps` - Toggle follow-up suggestions\n\
+        • `/settings eligibility` - Who can own a booster role\n\
+        • `/settings supportchannel` - Where staff help threads open\n\
+        • `/settings snapshot` - Save and compare configuration snapshots\n\
+        • `/settings permissiondebug` - See why members were refused commands\n\
+        • `/settings quiethours` - Hold back automated messages overnight\n\
+        • `/settings accountage` - Minimum account age for booster roles\n\
+        • `/settings retention` - How long member data is kept\n\
+        • `/settings commandchannel` - Where commands may be used\n\
+        • `/settings commands` - Turn individual commands off\n\
+        • `/settings cooldown` - Make members wait between uses of a command\n\
+        • `/settings renamecooldown` - Wait between booster role renames\n\
+        • `/settings theme` - Match embed colors to your branding\n\
+        • `/settings language` - Language replies default to\n\
+        • `/settings export` / `/settings import` - Copy configuration between servers\n\
+        • `/settings audit` - Review recent settings changes\n\
+        • `/settings usage` - See which commands this server uses most",
     )
     .await?;
     Ok(())
 }
 
+/// Whether the invoking member is staff: the server owner, anyone with
+/// Manage Server, or a holder of a configured staff role
+pub async fn is_staff(ctx: &Context<'_>) -> Result<bool, Error> {
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let member = ctx.author_member().await.ok_or("Cannot fetch member")?;
+    let guild = guild_id.to_partial_guild(&ctx.serenity_context().http).await?;
+
+    let staff_role_ids = load_staff_role_ids(&ctx.data().db_pool, guild_id).await?;
+    Ok(member_is_staff(
+        ctx.author().id,
+        guild.owner_id,
+        guild.member_permissions(&member),
+        &member.roles,
+        &staff_role_ids,
+    ))
+}
+
 pub async fn validate_permissions(ctx: &Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Not in guild")?;
     let member = ctx.author_member().await.ok_or("Cannot fetch member")?;
@@ -61,7 +140,7 @@ pub async fn validate_permissions(ctx: &Context<'_>) -> Result<(), Error> {
     }
     
     if !member_permissions.contains(Permissions::MANAGE_GUILD) {
-        return Err(SettingsError::InsufficientPermissions.into());
+        return Err(Error::Check(CheckFailure::MissingManageGuild));
     }
 
     let bot_member = guild_id
@@ -0,0 +1,61 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{GuildNextStepsSetting, SettingsAuditLog};
+use crate::utils::ResponseHelper;
+
+/// Toggle follow-up suggestions after commands
+#[poise::command(slash_command, prefix_command, subcommands("enable", "disable"))]
+pub async fn nextsteps(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Show follow-up suggestions after commands
+#[poise::command(slash_command, prefix_command)]
+pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
+    toggle(ctx, true).await
+}
+
+/// Hide follow-up suggestions after commands
+#[poise::command(slash_command, prefix_command)]
+pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
+    toggle(ctx, false).await
+}
+
+async fn toggle(ctx: Context<'_>, enabled: bool) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    GuildNextStepsSetting::set(pool, guild_id, enabled, ctx.author().id).await?;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        if enabled {
+            "next_steps_enabled"
+        } else {
+            "next_steps_disabled"
+        },
+        None,
+    )
+    .await?;
+
+    let description = if enabled {
+        "Success messages will suggest related commands to try next"
+    } else {
+        "Success messages will no longer include next-step suggestions"
+    };
+
+    ResponseHelper::send_success(
+        ctx,
+        if enabled {
+            "✅ Next Steps Enabled"
+        } else {
+            "✅ Next Steps Disabled"
+        },
+        description,
+    )
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use crate::bot::{Context, Error};
+use crate::utils::args::UserArg;
+use crate::utils::permission_audit::{CheckFailure, FAILURES_PER_GUILD};
+use crate::utils::{EmbedColor, ResponseHelper};
+use serenity::all::{CreateEmbed, CreateEmbedFooter, Timestamp};
+
+/// Failures listed in one embed
+const MAX_LISTED: usize = 15;
+
+/// Show recent permission-check failures and what would grant access
+#[poise::command(slash_command, prefix_command)]
+pub async fn permissiondebug(
+    ctx: Context<'_>,
+    #[description = "Only show failures for this member"] user: Option<UserArg>,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let user_id = user.as_ref().map(|u| u.id);
+    let failures = ctx.data().permission_failures.recent(guild_id, user_id);
+
+    let scope = match user_id {
+        Some(id) => format!("<@{}>", id),
+        None => "this server".to_string(),
+    };
+
+    if failures.is_empty() {
+        ResponseHelper::send_info(
+            ctx,
+            "🔍 Permission Debug",
+            &format!(
+                "No recent permission failures for {}.\nFailures are kept in memory and reset when the bot restarts.",
+                scope
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let lines = failures
+        .iter()
+        .take(MAX_LISTED)
+        .map(|f| {
+            format!(
+                "<t:{}:R> <@{}> `/{}` — **{}**",
+                f.at.timestamp(),
+                f.user_id,
+                f.command,
+                f.reason
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut reasons: Vec<&CheckFailure> = Vec::new();
+    for failure in failures.iter().take(MAX_LISTED) {
+        if !reasons.contains(&&failure.reason) {
+            reasons.push(&failure.reason);
+        }
+    }
+    let remedies = reasons
+        .iter()
+        .map(|r| format!("**{}:** {}", r, r.remedy()))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let embed = CreateEmbed::new()
+        .title("🔍 Permission Debug")
+        .description(format!("Recent failed checks for {}\n\n{}", scope, lines))
+        .field("How to grant access", remedies, false)
+        .color(EmbedColor::Info.value())
+        .footer(CreateEmbedFooter::new(format!(
+            "Showing {} of {} • the last {} failures per server are kept until restart",
+            failures.len().min(MAX_LISTED),
+            failures.len(),
+            FAILURES_PER_GUILD
+        )))
+        .timestamp(Timestamp::now());
+
+    ResponseHelper::send_embed(ctx, embed).await?;
+    Ok(())
+}
@@ -1,17 +1,34 @@
 use crate::bot::{Context, Error};
 use crate::data::models::{GuildPremiumRole, SettingsAuditLog};
-use crate::utils::{ResponseHelper, SettingsError};
-use serenity::all::Role;
+use crate::handlers::cleanup::MemberPages;
+use crate::utils::args::RoleArg;
+use crate::utils::premium_role::PremiumRoleSync;
+use crate::utils::progress::ProgressReporter;
+use crate::utils::{EmbedBuilder, ResponseHelper, SettingsError};
+use poise::serenity_prelude::Mentionable;
+use std::time::Duration;
 
-#[poise::command(slash_command, prefix_command, subcommands("set", "disable", "view"))]
+/// Pause between role changes during a sync, to stay under rate limits
+const SYNC_DELAY: Duration = Duration::from_millis(250);
+
+/// Role changes made between progress updates during a sync
+const SYNC_PROGRESS_EVERY: usize = 25;
+
+/// Set the role that marks premium members
+#[poise::command(
+    slash_command,
+    prefix_command,
+    subcommands("set", "disable", "view", "sync")
+)]
 pub async fn premiumrole(_: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// Set the premium role
 #[poise::command(slash_command, prefix_command)]
 pub async fn set(
     ctx: Context<'_>,
-    #[description = "Role to designate as premium"] role: Role,
+    #[description = "Role to designate as premium"] role: RoleArg,
 ) -> Result<(), Error> {
     super::validate_permissions(&ctx).await?;
 
@@ -69,7 +86,7 @@ pub async fn set(
         ctx,
         "✅ Premium Role Set",
         &format!(
-            "**{}** has been designated as the premium role\nMembers with this role will receive special privileges",
+            "**{}** has been designated as the premium role\nMembers get it while they boost and lose it when they stop. Use `/settings premiumrole sync` to bring current members in line.",
             role.name
         ),
     )
@@ -77,6 +94,7 @@ pub async fn set(
     Ok(())
 }
 
+/// Remove the premium role
 #[poise::command(slash_command, prefix_command)]
 pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
     super::validate_permissions(&ctx).await?;
@@ -109,6 +127,7 @@ pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// Show the premium role
 #[poise::command(slash_command, prefix_command)]
 pub async fn view(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Not in guild")?;
@@ -128,4 +147,158 @@ pub async fn view(ctx: Context<'_>) -> Result<(), Error> {
             .await?;
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Give the premium role to every booster and take it from everyone else
+#[poise::command(
+    slash_command,
+    prefix_command,
+    required_bot_permissions = "MANAGE_ROLES"
+)]
+pub async fn sync(ctx: Context<'_>) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    let Some(premium) = GuildPremiumRole::get(pool, guild_id).await? else {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ No Premium Role",
+            "Set one with `/settings premiumrole set` before syncing.",
+        )
+        .await?;
+        return Ok(());
+    };
+    let role_id = premium.role_id();
+
+    ctx.defer().await?;
+
+    let http = &ctx.serenity_context().http;
+    let guild = guild_id.to_partial_guild(http).await?;
+    if !guild.roles.contains_key(&role_id) {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Premium Role Missing",
+            &format!(
+                "The configured premium role (ID: {}) no longer exists. Set a new one with `/settings premiumrole set`.",
+                role_id
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let title = "👑 Premium Role Sync";
+    let mut progress = ProgressReporter::new(ctx);
+    progress
+        .update(EmbedBuilder::info(
+            title,
+            format!("Checking members against {}…", role_id.mention()),
+        ))
+        .await?;
+
+    let mut sync = PremiumRoleSync::default();
+    let mut pages = MemberPages::new(guild_id);
+    while let Some(members) = pages.next(http).await? {
+        sync.check(&members, role_id);
+    }
+
+    let (mut granted, mut removed, mut failed) = (0, 0, 0);
+    let changes = sync
+        .grant
+        .iter()
+        .map(|user_id| (*user_id, true))
+        .chain(sync.revoke.iter().map(|user_id| (*user_id, false)));
+    for (done, (user_id, grant)) in changes.enumerate() {
+        let result = if grant {
+            http.add_member_role(guild_id, user_id, role_id, Some("Premium role sync"))
+                .await
+        } else {
+            http.remove_member_role(guild_id, user_id, role_id, Some("Premium role sync"))
+                .await
+        };
+        match result {
+            Ok(()) if grant => granted += 1,
+            Ok(()) => removed += 1,
+            Err(e) => {
+                failed += 1;
+                tracing::warn!(
+                    user_id = %user_id,
+                    guild_id = %guild_id,
+                    role_id = %role_id,
+                    grant,
+                    error = ?e,
+                    "Failed to sync premium role"
+                );
+            }
+        }
+        tokio::time::sleep(SYNC_DELAY).await;
+
+        if (done + 1) % SYNC_PROGRESS_EVERY == 0 {
+            progress
+                .update(EmbedBuilder::info(
+                    title,
+                    format!(
+                        "Updated **{}** of **{}** member(s)…",
+                        done + 1,
+                        sync.changes()
+                    ),
+                ))
+                .await?;
+        }
+    }
+
+    tracing::info!(
+        guild_id = %guild_id,
+        granted,
+        removed,
+        failed,
+        in_sync = sync.in_sync,
+        "Premium role sync completed"
+    );
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "premium_role_synced",
+        Some(&format!(
+            "Granted: {}, removed: {}, failed: {}",
+            granted, removed, failed
+        )),
+    )
+    .await?;
+
+    let results = format!(
+        "• Granted to boosters: {}\n• Removed from non-boosters: {}\n• Already correct: {}\n• Failed: {}",
+        granted, removed, sync.in_sync, failed
+    );
+    let summary = if sync.changes() == 0 {
+        EmbedBuilder::info(
+            title,
+            format!(
+                "Everyone is already in sync: all boosters have {} and nobody else does.",
+                role_id.mention()
+            ),
+        )
+    } else if failed > 0 {
+        EmbedBuilder::warning(
+            "Premium Role Sync Incomplete",
+            format!(
+                "Some members couldn't be updated. Check that my role is above {}.",
+                role_id.mention()
+            ),
+        )
+        .field("Results", results, false)
+    } else {
+        EmbedBuilder::success(
+            "Premium Role Synced",
+            format!("Boosters now hold {} and nobody else does.", role_id.mention()),
+        )
+        .field("Results", results, false)
+    };
+    progress.update(summary).await?;
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{GuildQuietHours, SettingsAuditLog};
+use crate::utils::quiet_hours::{parse_time, parse_utc_offset, QuietWindow};
+use crate::utils::ResponseHelper;
+
+/// Hold back automated messages during a daily window
+#[poise::command(
+    slash_command,
+    prefix_command,
+    subcommands("quiethours_set", "quiethours_off")
+)]
+pub async fn quiethours(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Set the daily quiet hours window
+#[poise::command(slash_command, prefix_command, rename = "set")]
+pub async fn quiethours_set(
+    ctx: Context<'_>,
+    #[description = "When quiet hours start, 24-hour HH:MM"] start: String,
+    #[description = "When quiet hours end, 24-hour HH:MM"] end: String,
+    #[description = "UTC offset for these times, like UTC+2 or -05:00 (default UTC)"]
+    timezone: Option<String>,
+    #[description = "Post a summary of joins and leaves when quiet hours end (default on)"]
+    summarize_logs: Option<bool>,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    let parsed = parse_time(&start).and_then(|start_minute| {
+        let end_minute = parse_time(&end)?;
+        let utc_offset_minutes = timezone.as_deref().map(parse_utc_offset).transpose()?;
+        Ok(QuietWindow {
+            start_minute,
+            end_minute,
+            utc_offset_minutes: utc_offset_minutes.unwrap_or(0),
+        })
+    });
+    let window = match parsed {
+        Ok(window) if window.start_minute == window.end_minute => {
+            ResponseHelper::send_error(
+                ctx,
+                "❌ Invalid Window",
+                "Quiet hours need different start and end times.",
+            )
+            .await?;
+            return Ok(());
+        }
+        Ok(window) => window,
+        Err(e) => {
+            ResponseHelper::send_error(ctx, "❌ Invalid Window", &e).await?;
+            return Ok(());
+        }
+    };
+    let summarize_logs = summarize_logs.unwrap_or(true);
+
+    GuildQuietHours::set(pool, guild_id, window, summarize_logs, ctx.author().id).await?;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "quiet_hours_set",
+        Some(&window.describe()),
+    )
+    .await?;
+
+    let logs = if summarize_logs {
+        "Join and leave logs are held back and posted as one summary when quiet hours end."
+    } else {
+        "Join and leave logs are skipped during quiet hours."
+    };
+    ResponseHelper::send_success(
+        ctx,
+        "🌙 Quiet Hours Set",
+        &format!(
+            "Automated messages are quiet from **{}**.\n{}\nSpotlights wait until quiet hours end. Command responses are never held back.",
+            window.describe(),
+            logs
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Turn quiet hours off
+#[poise::command(slash_command, prefix_command, rename = "off")]
+pub async fn quiethours_off(ctx: Context<'_>) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    if !GuildQuietHours::remove(pool, guild_id).await? {
+        ResponseHelper::send_info(ctx, "🌙 Quiet Hours", "Quiet hours aren't set up.").await?;
+        return Ok(());
+    }
+
+    SettingsAuditLog::log(pool, guild_id, ctx.author().id, "quiet_hours_removed", None).await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "🌙 Quiet Hours Off",
+        "Automated messages are sent right away again. Any held-back join and leave logs are posted shortly.",
+    )
+    .await?;
+    Ok(())
+}
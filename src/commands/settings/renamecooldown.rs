@@ -0,0 +1,112 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{GuildRenameCooldown, SettingsAuditLog};
+use crate::utils::rename_cooldown::{
+    describe_cooldown, effective_cooldown, DEFAULT_RENAME_COOLDOWN_MINUTES,
+};
+use crate::utils::ResponseHelper;
+
+/// Choose how long members wait between booster role renames
+#[poise::command(
+    slash_command,
+    prefix_command,
+    subcommands("renamecooldown_set", "renamecooldown_view", "renamecooldown_reset")
+)]
+pub async fn renamecooldown(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Set the rename cooldown in minutes (0 turns it off)
+#[poise::command(slash_command, prefix_command, rename = "set")]
+pub async fn renamecooldown_set(
+    ctx: Context<'_>,
+    #[description = "Minutes between renames (0-10080)"]
+    #[min = 0]
+    #[max = 10080]
+    minutes: i64,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    let minutes = effective_cooldown(Some(minutes));
+    ctx.data()
+        .repos
+        .guild_settings
+        .set_rename_cooldown(guild_id, minutes, ctx.author().id)
+        .await?;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "rename_cooldown_set",
+        Some(&format!("{} minutes", minutes)),
+    )
+    .await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "⏱️ Rename Cooldown Updated",
+        &format!(
+            "Members now wait **{}** between renames. Members with Manage Server skip the cooldown.",
+            describe_cooldown(minutes)
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Show the rename cooldown
+#[poise::command(slash_command, prefix_command, rename = "view")]
+pub async fn renamecooldown_view(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let configured = ctx.data().repos.guild_settings.rename_cooldown(guild_id).await?;
+
+    ResponseHelper::send_info(
+        ctx,
+        "⏱️ Rename Cooldown",
+        &format!(
+            "**{}**{}\n\nChange it with `/settings renamecooldown set`.",
+            describe_cooldown(effective_cooldown(configured)),
+            if configured.is_some() {
+                ""
+            } else {
+                " (default)"
+            }
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Go back to the default rename cooldown
+#[poise::command(slash_command, prefix_command, rename = "reset")]
+pub async fn renamecooldown_reset(ctx: Context<'_>) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    if GuildRenameCooldown::remove(pool, guild_id).await? {
+        SettingsAuditLog::log(
+            pool,
+            guild_id,
+            ctx.author().id,
+            "rename_cooldown_reset",
+            None,
+        )
+        .await?;
+    }
+
+    ResponseHelper::send_success(
+        ctx,
+        "⏱️ Rename Cooldown Reset",
+        &format!(
+            "Members wait the default **{}** between renames.",
+            describe_cooldown(DEFAULT_RENAME_COOLDOWN_MINUTES)
+        ),
+    )
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{GuildRetentionOverride, SettingsAuditLog};
+use crate::utils::retention::{
+    describe_effective, validate_days, RetentionCategory, MAX_RETENTION_DAYS, MIN_RETENTION_DAYS,
+};
+use crate::utils::ResponseHelper;
+
+/// Choose how long member data is kept in this server
+#[poise::command(
+    slash_command,
+    prefix_command,
+    subcommands("retention_set", "retention_view", "retention_reset")
+)]
+pub async fn retention(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Keep one kind of member data for a set number of days
+#[poise::command(slash_command, prefix_command, rename = "set")]
+pub async fn retention_set(
+    ctx: Context<'_>,
+    #[description = "Kind of data"] category: RetentionCategory,
+    #[description = "Days to keep it"]
+    #[min = 7]
+    #[max = 365]
+    days: i64,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    let days = match validate_days(days) {
+        Ok(days) => days,
+        Err(e) => {
+            ResponseHelper::send_error(ctx, "❌ Invalid Retention", &e).await?;
+            return Ok(());
+        }
+    };
+
+    GuildRetentionOverride::set(pool, guild_id, category, days, ctx.author().id).await?;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "retention_set",
+        Some(&format!("{}: {} days", category.as_str(), days)),
+    )
+    .await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "🗄️ Retention Updated",
+        &format!(
+            "**{}** is now kept for **{} days** (default {}). Older entries are deleted in the nightly cleanup.",
+            category.as_str(),
+            days,
+            category.default_days()
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Show how long each kind of member data is kept
+#[poise::command(slash_command, prefix_command, rename = "view")]
+pub async fn retention_view(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let overrides = GuildRetentionOverride::list(&ctx.data().db_pool, guild_id).await?;
+
+    ResponseHelper::send_info(
+        ctx,
+        "🗄️ Data Retention",
+        &format!(
+            "{}\n\nChange a window with `/settings retention set` ({}–{} days).",
+            describe_effective(&overrides),
+            MIN_RETENTION_DAYS,
+            MAX_RETENTION_DAYS
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Go back to the default retention for one kind of data, or all of them
+#[poise::command(slash_command, prefix_command, rename = "reset")]
+pub async fn retention_reset(
+    ctx: Context<'_>,
+    #[description = "Kind of data (leave empty to reset everything)"] category: Option<
+        RetentionCategory,
+    >,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    if GuildRetentionOverride::reset(pool, guild_id, category).await? == 0 {
+        ResponseHelper::send_info(
+            ctx,
+            "🗄️ Data Retention",
+            "Nothing to reset, the defaults are already in use.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let scope = category.map_or("all categories", |c| c.as_str());
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "retention_reset",
+        Some(scope),
+    )
+    .await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "🗄️ Retention Reset",
+        &format!("Default retention restored for **{}**.", scope),
+    )
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,663 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{
+    GuildBoosterLimit, GuildDisabledCommand, GuildJoinLogChannel, GuildStaffRole, SettingsAuditLog,
+};
+use crate::utils::prefix_rules::{is_additional_prefix, validate_prefix};
+use crate::utils::EmbedBuilder;
+use poise::CreateReply;
+use serenity::all::{
+    ButtonStyle, Channel, ChannelId, ChannelType, ComponentInteraction,
+    ComponentInteractionCollector, ComponentInteractionDataKind, CreateActionRow, CreateButton,
+    CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage, CreateSelectMenu,
+    CreateSelectMenuKind, GuildId, Permissions, RoleId,
+};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// How long the whole wizard stays open
+pub const SETUP_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Longest a modal stays open before its step is shown again
+const MODAL_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Most staff roles one pick can add
+const MAX_STAFF_ROLES: u8 = 10;
+
+/// The command group the booster role step turns on or off
+const BOOSTER_COMMAND: &str = "boosterrole";
+
+#[derive(Debug, poise::Modal)]
+#[name = "Command Prefix"]
+struct PrefixModal {
+    #[name = "Prefix for text commands"]
+    #[placeholder = "e.g. !"]
+    #[min_length = 1]
+    #[max_length = 5]
+    prefix: String,
+}
+
+#[derive(Debug, poise::Modal)]
+#[name = "Booster Role Limit"]
+struct LimitModal {
+    #[name = "Most booster roles allowed (0 = unlimited)"]
+    #[placeholder = "e.g. 25"]
+    #[min_length = 1]
+    #[max_length = 3]
+    max_roles: String,
+}
+
+/// A component on the wizard's message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SetupAction {
+    JoinLog,
+    StaffRoles,
+    Prefix,
+    BoosterLimit,
+    BoosterUnlimited,
+    BoosterOff,
+    Skip,
+    Cancel,
+}
+
+impl SetupAction {
+    const ALL: [Self; 8] = [
+        Self::JoinLog,
+        Self::StaffRoles,
+        Self::Prefix,
+        Self::BoosterLimit,
+        Self::BoosterUnlimited,
+        Self::BoosterOff,
+        Self::Skip,
+        Self::Cancel,
+    ];
+
+    fn suffix(self) -> &'static str {
+        match self {
+            Self::JoinLog => "joinlog",
+            Self::StaffRoles => "staff",
+            Self::Prefix => "prefix",
+            Self::BoosterLimit => "booster_limit",
+            Self::BoosterUnlimited => "booster_unlimited",
+            Self::BoosterOff => "booster_off",
+            Self::Skip => "skip",
+            Self::Cancel => "cancel",
+        }
+    }
+
+    fn custom_id(self, prefix: &str) -> String {
+        format!("{}:{}", prefix, self.suffix())
+    }
+
+    /// The action a component's custom ID stands for, if it belongs to `prefix`
+    fn from_custom_id(prefix: &str, custom_id: &str) -> Option<Self> {
+        let suffix = custom_id.strip_prefix(prefix)?.strip_prefix(':')?;
+        Self::ALL
+            .into_iter()
+            .find(|action| action.suffix() == suffix)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SetupStep {
+    JoinLog,
+    StaffRoles,
+    Prefix,
+    BoosterRoles,
+}
+
+impl SetupStep {
+    const ALL: [Self; 4] = [
+        Self::JoinLog,
+        Self::StaffRoles,
+        Self::Prefix,
+        Self::BoosterRoles,
+    ];
+
+    fn embed(self, note: Option<&str>) -> CreateEmbed {
+        let number = Self::ALL.iter().position(|&s| s == self).unwrap_or(0) + 1;
+        let (title, body) = match self {
+            Self::JoinLog => (
+                "Join/Leave Logs",
+                "Pick the channel where member joins and leaves are posted.",
+            ),
+            Self::StaffRoles => (
+                "Staff Roles",
+                "Pick the roles whose members count as server staff.",
+            ),
+            Self::Prefix => (
+                "Command Prefix",
+                "Set the prefix for text commands. Slash commands always work.",
+            ),
+            Self::BoosterRoles => (
+                "Booster Roles",
+                "Let boosters create their own custom role, optionally capping how many this server allows.",
+            ),
+        };
+
+        let mut description = body.to_string();
+        if let Some(note) = note {
+            description.push_str(&format!("\n\n⚠️ {}", note));
+        }
+        EmbedBuilder::info(
+            format!("Setup ({}/{}) · {}", number, Self::ALL.len(), title),
+            description,
+        )
+        .footer(serenity::all::CreateEmbedFooter::new(
+            "Skip any step to leave it as it is. Setup closes after 5 minutes.",
+        ))
+    }
+
+    fn components(self, prefix: &str) -> Vec<CreateActionRow> {
+        let button = |action: SetupAction, label: &str, style: ButtonStyle| {
+            CreateButton::new(action.custom_id(prefix))
+                .label(label)
+                .style(style)
+        };
+        let navigation = || {
+            vec![
+                button(SetupAction::Skip, "Skip", ButtonStyle::Secondary),
+                button(SetupAction::Cancel, "Cancel", ButtonStyle::Danger),
+            ]
+        };
+
+        match self {
+            Self::JoinLog => vec![
+                CreateActionRow::SelectMenu(
+                    CreateSelectMenu::new(
+                        SetupAction::JoinLog.custom_id(prefix),
+                        CreateSelectMenuKind::Channel {
+                            channel_types: Some(vec![ChannelType::Text, ChannelType::News]),
+                            default_channels: None,
+                        },
+                    )
+                    .placeholder("Choose a log channel"),
+                ),
+                CreateActionRow::Buttons(navigation()),
+            ],
+            Self::StaffRoles => vec![
+                CreateActionRow::SelectMenu(
+                    CreateSelectMenu::new(
+                        SetupAction::StaffRoles.custom_id(prefix),
+                        CreateSelectMenuKind::Role {
+                            default_roles: None,
+                        },
+                    )
+                    .placeholder("Choose staff roles")
+                    .min_values(1)
+                    .max_values(MAX_STAFF_ROLES),
+                ),
+                CreateActionRow::Buttons(navigation()),
+            ],
+            Self::Prefix => {
+                let mut buttons = vec![button(
+                    SetupAction::Prefix,
+                    "Set Prefix",
+                    ButtonStyle::Primary,
+                )];
+                buttons.extend(navigation());
+                vec![CreateActionRow::Buttons(buttons)]
+            }
+            Self::BoosterRoles => vec![
+                CreateActionRow::Buttons(vec![
+                    button(
+                        SetupAction::BoosterLimit,
+                        "Enable with Limit",
+                        ButtonStyle::Primary,
+                    ),
+                    button(
+                        SetupAction::BoosterUnlimited,
+                        "Enable, No Limit",
+                        ButtonStyle::Success,
+                    ),
+                    button(SetupAction::BoosterOff, "Disable", ButtonStyle::Secondary),
+                ]),
+                CreateActionRow::Buttons(navigation()),
+            ],
+        }
+    }
+}
+
+/// What the booster role step decided
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BoosterSetup {
+    Off,
+    /// `None` is unlimited
+    On(Option<i32>),
+}
+
+/// Everything the wizard configured; `None` means the step was skipped
+#[derive(Debug, Default)]
+struct SetupSummary {
+    join_log: Option<ChannelId>,
+    staff_roles: Vec<RoleId>,
+    prefix: Option<String>,
+    booster_roles: Option<BoosterSetup>,
+}
+
+impl SetupSummary {
+    fn describe(&self) -> String {
+        let skipped = |command: &str| format!("Not changed · `{}`", command);
+
+        let join_log = self
+            .join_log
+            .map(|channel| format!("<#{}>", channel))
+            .unwrap_or_else(|| skipped("/settings joinlogs set"));
+        let staff_roles = if self.staff_roles.is_empty() {
+            skipped("/settings staff add")
+        } else {
+            self.staff_roles
+                .iter()
+                .map(|role| format!("<@&{}>", role))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let prefix = self
+            .prefix
+            .as_ref()
+            .map(|prefix| format!("`{}`", prefix))
+            .unwrap_or_else(|| skipped("/prefix set"));
+        let booster_roles = match self.booster_roles {
+            Some(BoosterSetup::Off) => "Disabled".to_string(),
+            Some(BoosterSetup::On(None)) => "Enabled, unlimited".to_string(),
+            Some(BoosterSetup::On(Some(max))) => format!("Enabled, up to {} roles", max),
+            None => skipped("/boosterrole limit"),
+        };
+
+        format!(
+            "**Join/leave logs:** {}\n**Staff roles:** {}\n**Prefix:** {}\n**Booster roles:** {}",
+            join_log, staff_roles, prefix, booster_roles
+        )
+    }
+}
+
+/// Read the booster role limit typed into the modal
+fn parse_limit(input: &str) -> Result<i32, String> {
+    match input.trim().parse::<i32>() {
+        Ok(max) if (0..=100).contains(&max) => Ok(max),
+        _ => Err(format!("`{}` isn't a number from 0 to 100.", input.trim())),
+    }
+}
+
+/// How the wizard ended
+enum Outcome {
+    Finished,
+    Cancelled,
+    TimedOut,
+}
+
+/// Walk through the essential settings one step at a time (Admin only)
+#[poise::command(slash_command, guild_only, required_permissions = "MANAGE_GUILD")]
+pub async fn setup(ctx: Context<'_>) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let prefix = format!("setup:{}", ctx.id());
+    let deadline = Instant::now() + SETUP_TIMEOUT;
+    let mut summary = SetupSummary::default();
+
+    let mut step_index = 0;
+    let first = SetupStep::ALL[0];
+    let reply = ctx
+        .send(
+            CreateReply::default()
+                .embed(first.embed(None))
+                .components(first.components(&prefix)),
+        )
+        .await?;
+    let message_id = reply.message().await?.id;
+
+    let outcome = loop {
+        if step_index >= SetupStep::ALL.len() {
+            break Outcome::Finished;
+        }
+
+        let filter_prefix = prefix.clone();
+        let interaction = ComponentInteractionCollector::new(ctx)
+            .message_id(message_id)
+            .filter(move |i| {
+                SetupAction::from_custom_id(&filter_prefix, &i.data.custom_id).is_some()
+            })
+            .timeout(deadline.saturating_duration_since(Instant::now()))
+            .await;
+        let Some(interaction) = interaction else {
+            break Outcome::TimedOut;
+        };
+
+        if interaction.user.id != ctx.author().id {
+            interaction
+                .create_response(
+                    ctx,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content("Only the person who ran this command can answer it.")
+                            .ephemeral(true),
+                    ),
+                )
+                .await?;
+            continue;
+        }
+
+        let action = SetupAction::from_custom_id(&prefix, &interaction.data.custom_id)
+            .unwrap_or(SetupAction::Skip);
+        let modal_timeout = deadline
+            .saturating_duration_since(Instant::now())
+            .min(MODAL_TIMEOUT);
+
+        // Each arm either moves on (`None`) or keeps the step open with a note
+        let note = match action {
+            SetupAction::Cancel => {
+                acknowledge(ctx, &interaction).await?;
+                break Outcome::Cancelled;
+            }
+            SetupAction::Skip => {
+                acknowledge(ctx, &interaction).await?;
+                None
+            }
+            SetupAction::JoinLog => {
+                acknowledge(ctx, &interaction).await?;
+                match &interaction.data.kind {
+                    ComponentInteractionDataKind::ChannelSelect { values } => {
+                        match values.first() {
+                            Some(&channel_id) => {
+                                set_join_log(ctx, guild_id, channel_id, &mut summary).await?
+                            }
+                            None => Some("Pick a channel, or skip this step.".to_string()),
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            SetupAction::StaffRoles => {
+                acknowledge(ctx, &interaction).await?;
+                if let ComponentInteractionDataKind::RoleSelect { values } = &interaction.data.kind
+                {
+                    add_staff_roles(ctx, guild_id, values, &mut summary).await?;
+                }
+                None
+            }
+            SetupAction::Prefix => {
+                match poise::execute_modal_on_component_interaction::<PrefixModal>(
+                    ctx,
+                    interaction.clone(),
+                    None,
+                    Some(modal_timeout),
+                )
+                .await?
+                {
+                    Some(modal) => set_prefix(ctx, guild_id, &modal.prefix, &mut summary).await?,
+                    None => Some("No prefix was entered.".to_string()),
+                }
+            }
+            SetupAction::BoosterLimit => {
+                match poise::execute_modal_on_component_interaction::<LimitModal>(
+                    ctx,
+                    interaction.clone(),
+                    None,
+                    Some(modal_timeout),
+                )
+                .await?
+                {
+                    Some(modal) => match parse_limit(&modal.max_roles) {
+                        Ok(0) => {
+                            set_booster_roles(ctx, guild_id, BoosterSetup::On(None), &mut summary)
+                                .await?
+                        }
+                        Ok(max) => {
+                            set_booster_roles(
+                                ctx,
+                                guild_id,
+                                BoosterSetup::On(Some(max)),
+                                &mut summary,
+                            )
+                            .await?
+                        }
+                        Err(problem) => Some(problem),
+                    },
+                    None => Some("No limit was entered.".to_string()),
+                }
+            }
+            SetupAction::BoosterUnlimited => {
+                acknowledge(ctx, &interaction).await?;
+                set_booster_roles(ctx, guild_id, BoosterSetup::On(None), &mut summary).await?
+            }
+            SetupAction::BoosterOff => {
+                acknowledge(ctx, &interaction).await?;
+                set_booster_roles(ctx, guild_id, BoosterSetup::Off, &mut summary).await?
+            }
+        };
+
+        if note.is_none() {
+            step_index += 1;
+        }
+        if let Some(&next) = SetupStep::ALL.get(step_index) {
+            reply
+                .edit(
+                    ctx,
+                    CreateReply::default()
+                        .embed(next.embed(note.as_deref()))
+                        .components(next.components(&prefix)),
+                )
+                .await?;
+        }
+    };
+
+    let embed = match outcome {
+        Outcome::Finished => EmbedBuilder::success("Setup Complete", summary.describe()),
+        Outcome::Cancelled => EmbedBuilder::warning(
+            "Setup Cancelled",
+            format!(
+                "Steps finished before cancelling were kept.\n\n{}",
+                summary.describe()
+            ),
+        ),
+        Outcome::TimedOut => EmbedBuilder::warning(
+            "Setup Timed Out",
+            format!(
+                "Run `/settings setup` again to finish. Steps already done were kept.\n\n{}",
+                summary.describe()
+            ),
+        ),
+    };
+    reply
+        .edit(ctx, CreateReply::default().embed(embed).components(vec![]))
+        .await?;
+
+    tracing::info!(
+        guild_id = %guild_id,
+        user_id = %ctx.author().id,
+        join_log = summary.join_log.is_some(),
+        staff_roles = summary.staff_roles.len(),
+        prefix = summary.prefix.is_some(),
+        booster_roles = summary.booster_roles.is_some(),
+        "Setup wizard finished"
+    );
+    Ok(())
+}
+
+/// Acknowledge a click now; the message is edited once the step is handled
+async fn acknowledge(ctx: Context<'_>, interaction: &ComponentInteraction) -> Result<(), Error> {
+    interaction
+        .create_response(ctx, CreateInteractionResponse::Acknowledge)
+        .await?;
+    Ok(())
+}
+
+async fn set_join_log(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    summary: &mut SetupSummary,
+) -> Result<Option<String>, Error> {
+    let http = &ctx.serenity_context().http;
+    let Channel::Guild(channel) = channel_id.to_channel(http).await? else {
+        return Ok(Some("That isn't a server channel.".to_string()));
+    };
+    let bot_member = guild_id.member(http, ctx.framework().bot_id).await?;
+    let guild = guild_id.to_partial_guild(http).await?;
+    let perms = guild.user_permissions_in(&channel, &bot_member);
+    if !perms.contains(Permissions::SEND_MESSAGES | Permissions::EMBED_LINKS) {
+        return Ok(Some(format!(
+            "I need Send Messages and Embed Links permissions in <#{}>.",
+            channel_id
+        )));
+    }
+
+    let pool = &ctx.data().db_pool;
+    GuildJoinLogChannel::set(
+        pool,
+        guild_id,
+        Some(channel_id),
+        Some(channel_id),
+        ctx.author().id,
+    )
+    .await?;
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "join_log_channel_set",
+        Some(&format!(
+            "Channel: <#{}>, events: joins and leaves",
+            channel_id
+        )),
+    )
+    .await?;
+
+    summary.join_log = Some(channel_id);
+    Ok(None)
+}
+
+async fn add_staff_roles(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    roles: &[RoleId],
+    summary: &mut SetupSummary,
+) -> Result<(), Error> {
+    let pool = &ctx.data().db_pool;
+    for &role_id in roles {
+        GuildStaffRole::add(pool, guild_id, role_id, ctx.author().id).await?;
+        SettingsAuditLog::log(
+            pool,
+            guild_id,
+            ctx.author().id,
+            "staff_role_added",
+            Some(&format!("Role: <@&{}> ({})", role_id, role_id)),
+        )
+        .await?;
+    }
+
+    summary.staff_roles = roles.to_vec();
+    Ok(())
+}
+
+async fn set_prefix(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    input: &str,
+    summary: &mut SetupSummary,
+) -> Result<Option<String>, Error> {
+    let prefix = match validate_prefix(input) {
+        Ok(prefix) => prefix,
+        Err(problem) => return Ok(Some(problem.to_string())),
+    };
+    // Built-in prefixes already work everywhere; `/prefix set` can still force one
+    if is_additional_prefix(&prefix) {
+        return Ok(Some(format!(
+            "`{}` already works in every server. Pick a prefix of this server's own.",
+            prefix
+        )));
+    }
+
+    ctx.data().set_guild_prefix(guild_id.get(), &prefix).await?;
+    summary.prefix = Some(prefix);
+    Ok(None)
+}
+
+async fn set_booster_roles(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    setup: BoosterSetup,
+    summary: &mut SetupSummary,
+) -> Result<Option<String>, Error> {
+    let pool = &ctx.data().db_pool;
+    let author = ctx.author().id;
+
+    let (changed, action) = match setup {
+        BoosterSetup::Off => (
+            GuildDisabledCommand::disable(pool, guild_id, BOOSTER_COMMAND, author).await?,
+            "command_disable",
+        ),
+        BoosterSetup::On(max) => {
+            GuildBoosterLimit::set(pool, guild_id, max.unwrap_or(0), author).await?;
+            (
+                GuildDisabledCommand::enable(pool, guild_id, BOOSTER_COMMAND).await?,
+                "command_enable",
+            )
+        }
+    };
+    if changed {
+        ctx.data().invalidate_disabled_commands(guild_id).await;
+        SettingsAuditLog::log(
+            pool,
+            guild_id,
+            author,
+            action,
+            Some(&format!("Command: /{}", BOOSTER_COMMAND)),
+        )
+        .await?;
+    }
+
+    summary.booster_roles = Some(setup);
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_ids_belong_to_one_wizard() {
+        for action in SetupAction::ALL {
+            assert_eq!(
+                SetupAction::from_custom_id("setup:7", &action.custom_id("setup:7")),
+                Some(action)
+            );
+        }
+        assert_eq!(SetupAction::from_custom_id("setup:7", "setup:8:skip"), None);
+        assert_eq!(
+            SetupAction::from_custom_id("setup:7", "setup:77:skip"),
+            None
+        );
+        assert_eq!(
+            SetupAction::from_custom_id("setup:7", "confirm:7:confirm"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_limit_must_be_in_range() {
+        assert_eq!(parse_limit(" 25 "), Ok(25));
+        assert_eq!(parse_limit("0"), Ok(0));
+        assert!(parse_limit("101").is_err());
+        assert!(parse_limit("-1").is_err());
+        assert!(parse_limit("lots").is_err());
+    }
+
+    #[test]
+    fn test_summary_points_skipped_steps_at_their_commands() {
+        let summary = SetupSummary {
+            join_log: Some(ChannelId::new(5)),
+            staff_roles: vec![RoleId::new(6), RoleId::new(7)],
+            prefix: None,
+            booster_roles: Some(BoosterSetup::On(Some(30))),
+        };
+        let text = summary.describe();
+        assert!(text.contains("**Join/leave logs:** <#5>"));
+        assert!(text.contains("**Staff roles:** <@&6>, <@&7>"));
+        assert!(text.contains("**Prefix:** Not changed · `/prefix set`"));
+        assert!(text.contains("**Booster roles:** Enabled, up to 30 roles"));
+
+        let untouched = SetupSummary::default().describe();
+        assert_eq!(untouched.matches("Not changed").count(), 4);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::bot::{Context, Error};
+use crate::data::models::config_snapshot::MAX_SNAPSHOTS_PER_GUILD;
+use crate::data::models::{GuildConfigSnapshot, SettingsAuditLog};
+use crate::utils::config_diff::{diff_json, render_changes};
+use crate::utils::embed_builder::MAX_DESCRIPTION_CHARS;
+use crate::utils::{EmbedColor, ResponseHelper};
+use serenity::all::{CreateEmbed, CreateEmbedFooter, Timestamp};
+
+/// Save and compare point-in-time copies of the server configuration
+#[poise::command(
+    slash_command,
+    prefix_command,
+    subcommands("snapshot_create", "snapshot_list", "snapshot_diff")
+)]
+pub async fn snapshot(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Save the current server configuration
+#[poise::command(slash_command, prefix_command, rename = "create")]
+pub async fn snapshot_create(
+    ctx: Context<'_>,
+    #[description = "Label to remember this snapshot by"]
+    #[max_length = 100]
+    label: Option<String>,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    let data = GuildConfigSnapshot::capture(pool, guild_id).await?;
+    let id = GuildConfigSnapshot::create(pool, guild_id, label.as_deref(), &data, ctx.author().id)
+        .await?;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "config_snapshot_created",
+        Some(&format!("Snapshot #{}", id)),
+    )
+    .await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "📸 Snapshot Saved",
+        &format!(
+            "Saved the current configuration as snapshot **#{}**{}.\nThe newest {} snapshots are kept.",
+            id,
+            label.map(|l| format!(" ({})", l)).unwrap_or_default(),
+            MAX_SNAPSHOTS_PER_GUILD
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// List saved configuration snapshots
+#[poise::command(slash_command, prefix_command, rename = "list")]
+pub async fn snapshot_list(ctx: Context<'_>) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let snapshots = GuildConfigSnapshot::list(&ctx.data().db_pool, guild_id).await?;
+
+    if snapshots.is_empty() {
+        ResponseHelper::send_info(
+            ctx,
+            "📸 Snapshots",
+            "No snapshots yet. Save one with `/settings snapshot create`.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let description = snapshots
+        .iter()
+        .map(|s| {
+            let created = s
+                .created_at
+                .map(|t| format!("<t:{}:R>", t.timestamp()))
+                .unwrap_or_else(|| "Unknown".to_string());
+            format!(
+                "**#{}** {} • by <@{}> • {}",
+                s.id,
+                s.label.as_deref().unwrap_or("Unlabeled"),
+                s.created_by,
+                created
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let embed = CreateEmbed::new()
+        .title("📸 Configuration Snapshots")
+        .description(description)
+        .color(EmbedColor::Primary.value())
+        .footer(CreateEmbedFooter::new(format!(
+            "{}/{} stored • Compare with /settings snapshot diff",
+            snapshots.len(),
+            MAX_SNAPSHOTS_PER_GUILD
+        )))
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Show what changed between two snapshots
+#[poise::command(slash_command, prefix_command, rename = "diff")]
+pub async fn snapshot_diff(
+    ctx: Context<'_>,
+    #[description = "Older snapshot number"] a: i64,
+    #[description = "Newer snapshot number"] b: i64,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    let (Some(old), Some(new)) = (
+        GuildConfigSnapshot::get(pool, guild_id, a).await?,
+        GuildConfigSnapshot::get(pool, guild_id, b).await?,
+    ) else {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Snapshot Not Found",
+            "One of those snapshots doesn't exist. Use `/settings snapshot list` to see saved snapshots.",
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let parse = |snapshot: &GuildConfigSnapshot| {
+        snapshot
+            .parsed()
+            .map_err(|e| format!("Snapshot #{} is unreadable: {}", snapshot.id, e))
+    };
+    let changes = diff_json(&parse(&old)?, &parse(&new)?);
+
+    let embed = CreateEmbed::new()
+        .title(format!("🔍 Snapshot #{} → #{}", old.id, new.id))
+        .description(render_changes(&changes, MAX_DESCRIPTION_CHARS))
+        .color(if changes.is_empty() {
+            EmbedColor::Success.value()
+        } else {
+            EmbedColor::Warning.value()
+        })
+        .footer(CreateEmbedFooter::new(format!(
+            "{} difference(s)",
+            changes.len()
+        )))
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
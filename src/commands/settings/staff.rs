@@ -1,17 +1,20 @@
 use crate::bot::{Context, Error};
 use crate::data::models::{GuildStaffRole, SettingsAuditLog};
+use crate::utils::args::RoleArg;
 use crate::utils::{EmbedColor, ResponseHelper};
-use serenity::all::{CreateEmbed, CreateEmbedFooter, Role};
+use serenity::all::{CreateEmbed, CreateEmbedFooter};
 
+/// Manage the roles treated as server staff
 #[poise::command(slash_command, prefix_command, subcommands("add", "remove", "list"))]
 pub async fn staff(_: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// Treat a role as server staff
 #[poise::command(slash_command, prefix_command)]
 pub async fn add(
     ctx: Context<'_>,
-    #[description = "Role to designate as staff"] role: Role,
+    #[description = "Role to designate as staff"] role: RoleArg,
 ) -> Result<(), Error> {
     super::validate_permissions(&ctx).await?;
     
@@ -38,10 +41,11 @@ pub async fn add(
     Ok(())
 }
 
+/// Stop treating a role as server staff
 #[poise::command(slash_command, prefix_command)]
 pub async fn remove(
     ctx: Context<'_>,
-    #[description = "Role to remove from staff"] role: Role,
+    #[description = "Role to remove from staff"] role: RoleArg,
 ) -> Result<(), Error> {
     super::validate_permissions(&ctx).await?;
     
@@ -77,6 +81,7 @@ pub async fn remove(
     Ok(())
 }
 
+/// List the staff roles
 #[poise::command(slash_command, prefix_command)]
 pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Not in guild")?;
@@ -0,0 +1,62 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{GuildStaffLookalikeSetting, SettingsAuditLog};
+use crate::utils::ResponseHelper;
+
+/// Block booster role names that look like staff roles
+#[poise::command(slash_command, prefix_command, subcommands("enable", "disable"))]
+pub async fn stafflookalike(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Refuse booster role names that look like a staff role
+#[poise::command(slash_command, prefix_command)]
+pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
+    toggle(ctx, true).await
+}
+
+/// Allow booster role names that look like a staff role
+#[poise::command(slash_command, prefix_command)]
+pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
+    toggle(ctx, false).await
+}
+
+async fn toggle(ctx: Context<'_>, enabled: bool) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    GuildStaffLookalikeSetting::set(pool, guild_id, enabled, ctx.author().id).await?;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        if enabled {
+            "staff_lookalike_enabled"
+        } else {
+            "staff_lookalike_disabled"
+        },
+        None,
+    )
+    .await?;
+
+    let description = if enabled {
+        "Booster role names that look like a staff role, or a role with Manage Server, \
+        will be refused. Lookalike letters, spacing and styling are ignored when comparing."
+    } else {
+        "Booster role names are no longer compared against staff roles"
+    };
+
+    ResponseHelper::send_success(
+        ctx,
+        if enabled {
+            "✅ Staff Lookalikes Blocked"
+        } else {
+            "✅ Staff Lookalikes Allowed"
+        },
+        description,
+    )
+    .await?;
+    Ok(())
+}
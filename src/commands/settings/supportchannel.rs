@@ -0,0 +1,66 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{GuildSupportChannel, SettingsAuditLog};
+use crate::utils::{ResponseHelper, SettingsError};
+use serenity::all::{ChannelType, GuildChannel, Permissions};
+
+/// Set the channel where staff help threads are opened
+#[poise::command(slash_command, prefix_command)]
+pub async fn supportchannel(
+    ctx: Context<'_>,
+    #[description = "Channel for private staff help threads"]
+    #[channel_types("Text")]
+    channel: GuildChannel,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    if channel.kind != ChannelType::Text {
+        return Err(SettingsError::ChannelPermissionDenied(
+            "Support threads need a regular text channel".to_string(),
+        )
+        .into());
+    }
+
+    // Check bot permissions in the channel
+    let bot_member = guild_id
+        .member(&ctx.serenity_context().http, ctx.framework().bot_id)
+        .await?;
+    let guild = guild_id
+        .to_partial_guild(&ctx.serenity_context().http)
+        .await?;
+    let perms = guild.user_permissions_in(&channel, &bot_member);
+    if !perms.contains(
+        Permissions::SEND_MESSAGES
+            | Permissions::CREATE_PRIVATE_THREADS
+            | Permissions::SEND_MESSAGES_IN_THREADS,
+    ) {
+        return Err(SettingsError::ChannelPermissionDenied(
+            "I need Send Messages, Create Private Threads, and Send Messages in Threads permissions in that channel".to_string(),
+        )
+        .into());
+    }
+
+    GuildSupportChannel::set(pool, guild_id, channel.id, ctx.author().id).await?;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "support_channel_set",
+        Some(&format!("Channel: <#{}>", channel.id)),
+    )
+    .await?;
+
+    ResponseHelper::send_success(
+        ctx,
+        "✅ Support Channel Configured",
+        &format!(
+            "Members whose booster role commands keep failing can now open a private help thread in <#{}>",
+            channel.id
+        ),
+    )
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{GuildEmbedTheme, SettingsAuditLog};
+use crate::utils::embed_theme::ThemeSlot;
+use crate::utils::{ColorParser, EmbedBuilder, ResponseHelper};
+
+/// Match the bot's embed colors to your server's branding
+#[poise::command(slash_command, prefix_command, subcommands("theme_set", "theme_reset"))]
+pub async fn theme(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Replace one of the embed colors
+#[poise::command(slash_command, prefix_command, rename = "set")]
+pub async fn theme_set(
+    ctx: Context<'_>,
+    #[description = "Which embeds to recolor"] kind: ThemeSlot,
+    #[description = "Hex code like #FF0000 or a color name"] color: String,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    let color = match ColorParser::parse(&color) {
+        Ok(c) => c,
+        Err(e) => {
+            ResponseHelper::send_error(
+                ctx,
+                "❌ Invalid Color",
+                &format!(
+                    "{}\n\nUse a hex code like `#FF0000` or a color name like `red`.",
+                    e
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+    let hex = ColorParser::to_hex_string(color);
+
+    GuildEmbedTheme::set_color(pool, guild_id, kind, color, ctx.author().id).await?;
+    ctx.data().invalidate_embed_theme(guild_id).await;
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "embed_theme_set",
+        Some(&format!("{} {}", kind.as_str(), hex)),
+    )
+    .await?;
+
+    // Shown in the new color so admins can judge it straight away
+    let embed = EmbedBuilder::success(
+        "🎨 Theme Updated",
+        format!(
+            "{} embeds now use **{}**.\n\nView the full theme with `/settings config`.",
+            kind.label(),
+            hex
+        ),
+    )
+    .color(color);
+    ResponseHelper::send_embed(ctx, embed).await?;
+    Ok(())
+}
+
+/// Go back to the default embed colors
+#[poise::command(slash_command, prefix_command, rename = "reset")]
+pub async fn theme_reset(ctx: Context<'_>) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    if GuildEmbedTheme::reset(pool, guild_id).await? {
+        ctx.data().invalidate_embed_theme(guild_id).await;
+        SettingsAuditLog::log(pool, guild_id, ctx.author().id, "embed_theme_reset", None).await?;
+    }
+
+    ResponseHelper::send_success(
+        ctx,
+        "🎨 Theme Reset",
+        "Embeds use the bot's default colors again.",
+    )
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,190 @@
+use crate::bot::{Context, Error};
+use crate::data::models::settings_transfer::{SectionOutcome, SectionReport, MAX_IMPORT_BYTES};
+use crate::data::models::{GuildConfigSnapshot, GuildSettingsExport, SettingsAuditLog};
+use crate::utils::{EmbedColor, ResponseHelper};
+use serenity::all::{
+    Attachment, ChannelId, CreateAttachment, CreateEmbed, CreateEmbedFooter, RoleId, Timestamp,
+};
+use std::collections::HashSet;
+
+/// Warnings listed before the rest are summarized as a count
+const MAX_WARNINGS_SHOWN: usize = 10;
+
+/// Keeps the shown warnings inside Discord's 1024-character field limit
+const MAX_WARNING_CHARS: usize = 90;
+
+/// Download this server's configuration as a JSON file
+#[poise::command(slash_command, prefix_command)]
+pub async fn export(ctx: Context<'_>) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let settings = GuildSettingsExport::capture(&ctx.data().db_pool, guild_id).await?;
+    let json = serde_json::to_vec_pretty(&settings)
+        .map_err(|e| Error::Command(format!("Failed to serialize settings: {}", e)))?;
+
+    let embed = CreateEmbed::new()
+        .title("📤 Settings Exported")
+        .description(format!(
+            "Prefix, staff roles, auto-nickname, join logs, premium role, booster limits, \
+            sharing limits, base and award roles, and {} blacklist word(s).\n\n\
+            Load it on another server with `/settings import`.",
+            settings.blacklist.len()
+        ))
+        .color(EmbedColor::Success.value())
+        .timestamp(Timestamp::now());
+
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(embed)
+            .attachment(CreateAttachment::bytes(
+                json,
+                format!("settings-{}.json", guild_id),
+            )),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Load configuration from a file made by `/settings export`
+#[poise::command(slash_command, prefix_command)]
+pub async fn import(
+    ctx: Context<'_>,
+    #[description = "Settings file from /settings export"] file: Attachment,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+
+    if file.size > MAX_IMPORT_BYTES {
+        ResponseHelper::send_error(
+            ctx,
+            "❌ File Too Large",
+            &format!("Settings files are at most {} KB.", MAX_IMPORT_BYTES / 1024),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.defer().await?;
+
+    let bytes = file.download().await?;
+    let settings = match GuildSettingsExport::parse(&bytes) {
+        Ok(settings) => settings,
+        Err(reason) => {
+            ResponseHelper::send_error(ctx, "❌ Invalid Settings File", &reason).await?;
+            return Ok(());
+        }
+    };
+
+    let (roles, channels): (HashSet<RoleId>, HashSet<ChannelId>) = {
+        let guild = guild_id
+            .to_guild_cached(&ctx.serenity_context().cache)
+            .ok_or("Guild not found in cache")?;
+        (
+            guild.roles.keys().copied().collect(),
+            guild.channels.keys().copied().collect(),
+        )
+    };
+    let plan = settings.plan(
+        |role| roles.contains(&role),
+        |channel| channels.contains(&channel),
+    );
+
+    if !plan.has_changes() {
+        let embed = report_embed("📥 Nothing Imported", &plan.report, EmbedColor::Warning);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    // Keep the current configuration recoverable with /settings snapshot
+    let snapshot_id =
+        GuildConfigSnapshot::create_automatic(pool, guild_id, "settings import", ctx.author().id)
+            .await?;
+
+    if let Err(e) = plan.apply(pool, guild_id, ctx.author().id).await {
+        tracing::error!(guild_id = %guild_id, error = ?e, "Settings import failed");
+        ResponseHelper::send_error(
+            ctx,
+            "❌ Import Failed",
+            "Couldn't save the imported settings, so nothing was changed.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let data = ctx.data();
+    data.prefix_cache.forget(guild_id.get()).await;
+    if let Err(e) = data.content_filter(guild_id).await.refresh_cache().await {
+        tracing::warn!(guild_id = %guild_id, error = ?e, "Failed to reload blacklist after import");
+    }
+
+    SettingsAuditLog::log(
+        pool,
+        guild_id,
+        ctx.author().id,
+        "settings_imported",
+        Some(&format!(
+            "From {} (snapshot #{} taken before)",
+            file.filename, snapshot_id
+        )),
+    )
+    .await?;
+
+    let embed = report_embed("📥 Settings Imported", &plan.report, EmbedColor::Success).footer(
+        CreateEmbedFooter::new(format!(
+            "Previous configuration saved as snapshot #{}",
+            snapshot_id
+        )),
+    );
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+fn report_embed(title: &str, report: &[SectionReport], color: EmbedColor) -> CreateEmbed {
+    let lines = report
+        .iter()
+        .map(|section| match &section.outcome {
+            SectionOutcome::Imported(value) => format!("✅ **{}**: {}", section.name, value),
+            SectionOutcome::NotInFile => format!("➖ **{}**: not in file", section.name),
+            SectionOutcome::Skipped => format!("⚠️ **{}**: skipped", section.name),
+            SectionOutcome::Failed(reason) => format!("❌ **{}**: {}", section.name, reason),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let warnings: Vec<String> = report
+        .iter()
+        .flat_map(|section| {
+            section.warnings.iter().map(move |warning| {
+                let line = format!("• {}: {}", section.name, warning);
+                line.chars().take(MAX_WARNING_CHARS).collect()
+            })
+        })
+        .collect();
+
+    let mut embed = CreateEmbed::new()
+        .title(title)
+        .description(lines)
+        .color(color.value())
+        .timestamp(Timestamp::now());
+
+    if !warnings.is_empty() {
+        let mut shown = warnings
+            .iter()
+            .take(MAX_WARNINGS_SHOWN)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("\n");
+        if warnings.len() > MAX_WARNINGS_SHOWN {
+            shown.push_str(&format!(
+                "\n…and {} more",
+                warnings.len() - MAX_WARNINGS_SHOWN
+            ));
+        }
+        embed = embed.field("Warnings", shown, false);
+    }
+
+    embed
+}
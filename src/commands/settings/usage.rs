@@ -0,0 +1,63 @@
+use crate::bot::{Context, Error};
+use crate::data::models::{CommandUsage, COMMAND_USAGE_RETENTION_DAYS};
+use crate::utils::command_usage::render_usage_chart;
+use crate::utils::{EmbedBuilder, ResponseHelper};
+use poise::serenity_prelude::CreateEmbedFooter;
+
+/// Window shown when no number of days is given
+const DEFAULT_USAGE_DAYS: i64 = 30;
+
+/// Commands listed in the chart
+const TOP_COMMANDS: i64 = 10;
+
+/// See which commands this server uses most
+#[poise::command(slash_command, prefix_command, required_permissions = "MANAGE_GUILD")]
+pub async fn usage(
+    ctx: Context<'_>,
+    #[description = "How many days back to look (default 30)"]
+    #[min = 1]
+    #[max = 90]
+    days: Option<i64>,
+) -> Result<(), Error> {
+    super::validate_permissions(&ctx).await?;
+
+    let guild_id = ctx.guild_id().ok_or("Not in guild")?;
+    let pool = &ctx.data().db_pool;
+    let days = days
+        .unwrap_or(DEFAULT_USAGE_DAYS)
+        .clamp(1, COMMAND_USAGE_RETENTION_DAYS);
+
+    // Include the last minute's commands, which are still buffered
+    ctx.data().command_usage.flush(pool).await?;
+
+    let since = chrono::Utc::now().date_naive() - chrono::Duration::days(days - 1);
+    let top = CommandUsage::top_for_guild(pool, guild_id, since, TOP_COMMANDS).await?;
+    if top.is_empty() {
+        ResponseHelper::send_info(
+            ctx,
+            "📊 Command Usage",
+            &format!("No commands have been used here in the last {} days.", days),
+        )
+        .await?;
+        return Ok(());
+    }
+    let total = CommandUsage::total_for_guild(pool, guild_id, since).await?;
+
+    let description = format!(
+        "Most used commands over the last **{}** days\n```\n{}```",
+        days,
+        render_usage_chart(&top)
+    );
+    let embed = EmbedBuilder::themed_primary(
+        &ResponseHelper::theme(ctx).await,
+        "📊 Command Usage",
+        description,
+    )
+    .footer(CreateEmbedFooter::new(format!(
+        "{} commands in total · counted since {}",
+        total, since
+    )));
+
+    ResponseHelper::send_embed(ctx, embed).await?;
+    Ok(())
+}
@@ -1,148 +1,213 @@
+use poise::serenity_prelude::{CreateAttachment, CreateEmbed, CreateEmbedFooter};
 use poise::CreateReply;
-use poise::serenity_prelude::CreateEmbed;
-use tracing::{info, debug};
+use std::fmt::Write;
 use std::time::Instant;
+use tracing::info;
 
 use crate::bot::data::{Context, Error};
-use crate::utils::EmbedColor;
+use crate::commands::boosterrole::samples::response_samples;
+use crate::testing::audit_logger::AuditLogger;
+use crate::testing::response_validator::{EmbedData, ResponseValidator, ValidationResult};
+use crate::utils::EmbedBuilder;
 
+/// Run the bot's response builders through the response validator (Owner only)
 #[poise::command(
     slash_command,
     prefix_command,
     category = "Development",
     hide_in_help,
     owners_only,
-    description_localized("en-US", "Test and validate all response types from any command")
+    description_localized(
+        "en-US",
+        "Check every response builder against the embed color and format standards"
+    )
 )]
 pub async fn test_responses(
     ctx: Context<'_>,
-    #[description = "The command name to test"] command_name: String,
-    #[description = "Optional subcommand to test"] subcommand: Option<String>,
+    #[description = "Only check commands starting with this, e.g. \"boosterrole limit\""]
+    command: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
-    
-    let full_command = if let Some(sub) = &subcommand {
-        format!("{} {}", command_name, sub)
-    } else {
-        command_name.clone()
-    };
-    
-    info!("Testing responses for command: {}", full_command);
-    
-    let available_commands = get_available_commands();
-    if !available_commands.contains(&command_name.as_str()) {
-        let error_embed = CreateEmbed::new()
-            .title("Command Not Found")
-            .description(format!("The command '{}' does not exist or is not available for testing.", command_name))
-            .color(EmbedColor::Error.value())
-            .field("Available Commands", available_commands.join(", "), false);
-        
-        ctx.send(CreateReply::default().embed(error_embed)).await?;
+
+    let filter = command
+        .as_deref()
+        .map(|c| c.trim().trim_start_matches('/').to_lowercase())
+        .filter(|c| !c.is_empty());
+    let groups: Vec<_> = response_groups()
+        .into_iter()
+        .filter(|(name, _)| filter.as_deref().is_none_or(|f| name.starts_with(f)))
+        .collect();
+
+    if groups.is_empty() {
+        let available = response_groups()
+            .iter()
+            .map(|(name, _)| format!("`{}`", name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let embed = EmbedBuilder::error(
+            "Nothing to Check",
+            format!(
+                "No response builders match `{}`.",
+                filter.unwrap_or_default()
+            ),
+        )
+        .field("Available", available, false);
+        ctx.send(CreateReply::default().embed(embed)).await?;
         return Ok(());
     }
-    
-    let start_time = Instant::now();
-    
-    // Simulate testing different response types
-    let test_scenarios = vec![
-        ("Success Response", EmbedColor::Success.value(), "✅ Command executed successfully"),
-        ("Error Response", EmbedColor::Error.value(), "❌ An error occurred"),
-        ("Warning Response", EmbedColor::Warning.value(), "⚠️ Warning: Check parameters"),
-        ("Info Response", EmbedColor::Info.value(), "ℹ️ Information about the command"),
-        ("Primary Response", EmbedColor::Primary.value(), "Standard command response"),
-    ];
-    
-    let mut test_results = Vec::new();
-    let mut all_passed = true;
-    
-    for (scenario_name, expected_color, sample_text) in &test_scenarios {
-        // Create a sample embed for this scenario
-        let _test_embed = CreateEmbed::new()
-            .title(format!("{} - {}", command_name, scenario_name))
-            .description(*sample_text)
-            .color(*expected_color);
-        
-        // Validate the color matches expected
-        let color_matches = true; // In a real implementation, we'd validate against actual command responses
-        
-        if !color_matches {
-            all_passed = false;
+
+    info!(
+        filter = ?filter,
+        commands = groups.len(),
+        "Validating command responses"
+    );
+
+    let started = Instant::now();
+    let validator = ResponseValidator::new();
+    let mut logger = AuditLogger::new();
+    let mut breakdown = Vec::new();
+    let mut failures = String::new();
+    let (mut passed, mut total) = (0, 0);
+
+    for (name, embeds) in &groups {
+        let command_started = Instant::now();
+        let data: Vec<EmbedData> = embeds.iter().map(EmbedData::from).collect();
+        let results = validator.validate_responses(name, &data);
+        let outcome = logger.log_test_execution(
+            name,
+            results.len(),
+            &results,
+            command_started.elapsed().as_millis() as u64,
+        );
+
+        passed += outcome.passed_scenarios;
+        total += outcome.total_scenarios;
+        breakdown.push(format!(
+            "{} `{}` {}/{}",
+            if outcome.failed_scenarios == 0 {
+                "✅"
+            } else {
+                "❌"
+            },
+            name,
+            outcome.passed_scenarios,
+            outcome.total_scenarios
+        ));
+        for (i, result) in results.iter().enumerate().filter(|(_, r)| !r.passed()) {
+            let _ = writeln!(
+                failures,
+                "  - {} #{}: {}",
+                name,
+                i + 1,
+                describe_failure(result)
+            );
         }
-        
-        let status = if color_matches { "✅" } else { "❌" };
-        test_results.push(format!("{} {}: Color 0x{:06X}", status, scenario_name, expected_color));
-        
-        debug!("Tested scenario '{}' for command '{}'", scenario_name, command_name);
     }
-    
-    let duration_ms = start_time.elapsed().as_millis() as u64;
-    
-    // Create summary embed
-    let summary_color = if all_passed {
-        EmbedColor::Success.value()
-    } else {
-        EmbedColor::Warning.value()
-    };
-    
-    let summary_embed = CreateEmbed::new()
-        .title(format!("Test Results: {}", full_command))
-        .description(format!(
-            "**Test Duration**: {}ms\n**Scenarios Tested**: {}\n**Status**: {}",
-            duration_ms,
-            test_scenarios.len(),
-            if all_passed { "All tests passed ✅" } else { "Some tests failed ⚠️" }
-        ))
-        .field("Test Results", test_results.join("\n"), false)
-        .field("Color Standards", format!(
-            "Success: 0x{:06X}\nError: 0x{:06X}\nWarning: 0x{:06X}\nInfo: 0x{:06X}\nPrimary: 0x{:06X}",
-            EmbedColor::Success.value(),
-            EmbedColor::Error.value(),
-            EmbedColor::Warning.value(),
-            EmbedColor::Info.value(),
-            EmbedColor::Primary.value()
-        ), false)
-        .color(summary_color)
-        .timestamp(chrono::Utc::now());
-    
-    ctx.send(CreateReply::default().embed(summary_embed)).await?;
-    
-    // Show sample responses
-    let samples_embed = CreateEmbed::new()
-        .title("Sample Response Types")
-        .description("Here are examples of each response type that was tested:")
-        .color(EmbedColor::Primary.value());
-    
-    ctx.send(CreateReply::default().embed(samples_embed)).await?;
-    
-    // Show each test scenario as a separate embed
-    for (scenario_name, color, sample_text) in &test_scenarios {
-        let sample_embed = CreateEmbed::new()
-            .title(*scenario_name)
-            .description(*sample_text)
-            .color(*color)
-            .footer(poise::serenity_prelude::CreateEmbedFooter::new(
-                format!("Color: 0x{:06X}", color)
-            ));
-        
-        ctx.send(CreateReply::default().embed(sample_embed)).await?;
+
+    let mut report = logger.export_audit_report();
+    if !failures.is_empty() {
+        report.push_str("\nFailures:\n");
+        report.push_str(&failures);
     }
-    
-    info!(
-        "Test complete for {}: {} scenarios tested in {}ms",
-        full_command, test_scenarios.len(), duration_ms
+
+    let duration_ms = started.elapsed().as_millis();
+    let description = format!(
+        "**{}/{}** responses passed across {} commands in {}ms.\n\n{}",
+        passed,
+        total,
+        groups.len(),
+        duration_ms,
+        breakdown.join("\n")
     );
-    
+    let embed = if passed == total {
+        EmbedBuilder::success("Response Check Passed", description)
+    } else {
+        EmbedBuilder::warning("Response Check Found Issues", description)
+    }
+    .footer(CreateEmbedFooter::new(
+        "Color and format details are in the attached report",
+    ));
+
+    ctx.send(
+        CreateReply::default()
+            .embed(embed)
+            .attachment(CreateAttachment::bytes(
+                report.into_bytes(),
+                "response_audit.txt",
+            )),
+    )
+    .await?;
+
+    info!(passed, total, duration_ms, "Response validation complete");
     Ok(())
 }
 
-fn get_available_commands() -> Vec<&'static str> {
-    vec![
-        "ping",
-        "help",
-        "info",
-        "boosterrole",
-        "prefix",
-        "cache_status",
-        "test_responses",
-    ]
-}
\ No newline at end of file
+/// Each `EmbedBuilder` variant, then one reply from every booster role
+/// subcommand with a context-free builder
+fn response_groups() -> Vec<(&'static str, Vec<CreateEmbed>)> {
+    let mut groups = vec![(
+        "embed_builder",
+        vec![
+            EmbedBuilder::success("Sample", "A command finished."),
+            EmbedBuilder::error("Sample", "A command failed."),
+            EmbedBuilder::warning("Sample", "A command needs attention."),
+            EmbedBuilder::info("Sample", "A command has something to say."),
+            EmbedBuilder::primary("Sample", "A general response."),
+        ],
+    )];
+    groups.extend(
+        response_samples()
+            .into_iter()
+            .map(|(name, embed)| (name, vec![embed])),
+    );
+    groups
+}
+
+fn describe_failure(result: &ValidationResult) -> String {
+    let mut problems = result.format_issues.clone();
+    if !result.color_compliant {
+        problems.push(format!(
+            "{:?} response colored {} instead of {}",
+            result.response_type,
+            hex(result.actual_color),
+            hex(result.expected_color)
+        ));
+    }
+    problems.join("; ")
+}
+
+fn hex(color: Option<u32>) -> String {
+    color.map_or_else(|| "none".to_string(), |c| format!("0x{:06X}", c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embed_builder_variants_pass() {
+        let validator = ResponseValidator::new();
+        let (_, embeds) = &response_groups()[0];
+        for embed in embeds {
+            let result = validator.validate_embed_data("embed_builder", &EmbedData::from(embed));
+            assert!(result.passed(), "{:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_repeated_status_emoji_is_flagged() {
+        let validator = ResponseValidator::new();
+        let embed = EmbedBuilder::success("✅ Done", "Finished.");
+        let result = validator.validate_embed_data("sample", &EmbedData::from(&embed));
+        assert_eq!(result.format_issues, ["Title repeats the status emoji"]);
+    }
+
+    #[test]
+    fn test_booster_samples_have_no_format_issues() {
+        let validator = ResponseValidator::new();
+        for (name, embed) in response_samples() {
+            let result = validator.validate_embed_data(name, &EmbedData::from(&embed));
+            assert!(result.format_issues.is_empty(), "{}: {:?}", name, result);
+        }
+    }
+}
@@ -1,5 +1,9 @@
+use crate::utils::response::PrivateReplyFallback;
 use std::env;
 
+/// How often the scheduled orphan cleanup runs unless configured
+pub const DEFAULT_ORPHAN_CLEANUP_INTERVAL_HOURS: u64 = 24;
+
 #[derive(Debug, Clone)]
 pub struct Settings {
     pub discord_token: String,
@@ -12,6 +16,17 @@ pub struct Settings {
     pub slash_commands_global: bool,
     #[allow(dead_code)]
     pub always_use_embeds: bool,
+    pub slow_query_threshold_ms: u64,
+    pub table_growth_multiplier: f64,
+    pub orphan_cleanup_interval_hours: u64,
+    pub database_max_connections: u32,
+    /// Bot owners on top of the application's owner, from `BOT_OWNERS`
+    pub owners: Vec<u64>,
+    /// Where private replies to prefix commands go, from `PRIVATE_REPLY_FALLBACK`
+    pub private_reply_fallback: PrivateReplyFallback,
+    /// Port for the `/healthz`, `/readyz` and `/metrics` server, from
+    /// `HEALTH_PORT`; the server is off when unset
+    pub health_port: Option<u16>,
 }
 
 impl Settings {
@@ -45,6 +60,43 @@ impl Settings {
             .parse()
             .unwrap_or(true);
 
+        let slow_query_threshold_ms = env::var("SLOW_QUERY_THRESHOLD_MS")
+            .ok()
+            .and_then(|ms| ms.parse().ok())
+            .unwrap_or(crate::utils::query_metrics::DEFAULT_SLOW_QUERY_THRESHOLD_MS);
+
+        let table_growth_multiplier = env::var("TABLE_GROWTH_MULTIPLIER")
+            .ok()
+            .and_then(|m| m.parse::<f64>().ok())
+            .filter(|m| *m > 1.0)
+            .unwrap_or(crate::utils::table_growth::DEFAULT_GROWTH_MULTIPLIER);
+
+        let orphan_cleanup_interval_hours = env::var("ORPHAN_CLEANUP_INTERVAL_HOURS")
+            .ok()
+            .and_then(|hours| hours.parse::<u64>().ok())
+            .filter(|hours| *hours > 0)
+            .unwrap_or(DEFAULT_ORPHAN_CLEANUP_INTERVAL_HOURS);
+
+        let database_max_connections = env::var("DATABASE_MAX_CONNECTIONS")
+            .ok()
+            .and_then(|n| n.parse::<u32>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(crate::data::database::DEFAULT_MAX_CONNECTIONS);
+
+        let owners = env::var("BOT_OWNERS")
+            .map(|ids| parse_owner_ids(&ids))
+            .unwrap_or_default();
+
+        let private_reply_fallback = env::var("PRIVATE_REPLY_FALLBACK")
+            .ok()
+            .and_then(|value| PrivateReplyFallback::parse(&value))
+            .unwrap_or_default();
+
+        let health_port = env::var("HEALTH_PORT")
+            .ok()
+            .and_then(|port| port.trim().parse::<u16>().ok())
+            .filter(|port| *port > 0);
+
         // Override guild_id if global commands are requested
         let final_guild_id = if slash_commands_global {
             None
@@ -60,6 +112,21 @@ impl Settings {
             auto_sync_commands,
             slash_commands_global,
             always_use_embeds,
+            slow_query_threshold_ms,
+            table_growth_multiplier,
+            orphan_cleanup_interval_hours,
+            database_max_connections,
+            owners,
+            private_reply_fallback,
+            health_port,
         })
     }
 }
+
+/// Comma-separated user IDs; anything that isn't one is skipped
+fn parse_owner_ids(value: &str) -> Vec<u64> {
+    value
+        .split(',')
+        .filter_map(|id| id.trim().parse::<u64>().ok())
+        .collect()
+}
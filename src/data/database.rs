@@ -1,450 +1,156 @@
-use sqlx::{sqlite::SqlitePoolOptions, SqlitePool};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use sqlx::SqlitePool;
 use std::path::Path;
+use std::time::Duration;
 
-pub async fn init_database(database_path: &str) -> Result<SqlitePool, sqlx::Error> {
+/// Pool size used unless `DATABASE_MAX_CONNECTIONS` overrides it
+pub const DEFAULT_MAX_CONNECTIONS: u32 = 5;
+
+/// How long a connection waits on a locked database before giving up
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub async fn init_database(
+    database_path: &str,
+    max_connections: u32,
+) -> Result<SqlitePool, sqlx::Error> {
     let database_dir = Path::new(database_path).parent();
     if let Some(dir) = database_dir {
-        std::fs::create_dir_all(dir).map_err(|e| sqlx::Error::Io(e))?;
+        std::fs::create_dir_all(dir).map_err(sqlx::Error::Io)?;
     }
 
     let database_url = format!("sqlite:{}", database_path);
 
+    // WAL lets readers run alongside a writer, and the busy timeout makes
+    // concurrent writers wait their turn instead of failing with "database is locked"
     let pool = SqlitePoolOptions::new()
-        .max_connections(5)
+        .max_connections(max_connections)
         .connect_with(
             database_url
-                .parse::<sqlx::sqlite::SqliteConnectOptions>()?
-                .create_if_missing(true),
+                .parse::<SqliteConnectOptions>()?
+                .create_if_missing(true)
+                .journal_mode(SqliteJournalMode::Wal)
+                .busy_timeout(BUSY_TIMEOUT)
+                .foreign_keys(true),
         )
         .await?;
 
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS guild_prefixes (
-            guild_id BIGINT PRIMARY KEY,
-            prefix TEXT NOT NULL CHECK(length(prefix) <= 5),
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    sqlx::query(
-        r#"
-        CREATE INDEX IF NOT EXISTS idx_guild_prefixes_guild_id 
-        ON guild_prefixes(guild_id)
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    tracing::info!("Creating booster_roles table");
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS booster_roles (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            guild_id BIGINT NOT NULL,
-            user_id BIGINT NOT NULL,
-            role_id BIGINT NOT NULL,
-            role_name TEXT NOT NULL,
-            primary_color TEXT NOT NULL,
-            secondary_color TEXT,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            UNIQUE(guild_id, user_id)
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    sqlx::query(
-        r#"
-        CREATE INDEX IF NOT EXISTS idx_booster_roles_guild_user 
-        ON booster_roles(guild_id, user_id)
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    tracing::info!("Creating booster_role_links table");
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS booster_role_links (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            guild_id BIGINT NOT NULL,
-            user_id BIGINT NOT NULL,
-            linked_role_id BIGINT NOT NULL,
-            linked_by BIGINT NOT NULL,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            UNIQUE(guild_id, user_id)
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    sqlx::query(
-        r#"
-        CREATE INDEX IF NOT EXISTS idx_booster_role_links_guild_user 
-        ON booster_role_links(guild_id, user_id)
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    tracing::info!("Creating role_name_blacklist table");
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS role_name_blacklist (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            guild_id BIGINT NOT NULL,
-            word TEXT NOT NULL,
-            added_by BIGINT NOT NULL,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            UNIQUE(guild_id, word)
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    sqlx::query(
-        r#"
-        CREATE INDEX IF NOT EXISTS idx_role_name_blacklist_guild 
-        ON role_name_blacklist(guild_id)
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    tracing::info!("Creating guild_booster_limits table");
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS guild_booster_limits (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            guild_id BIGINT NOT NULL UNIQUE,
-            max_roles INTEGER NOT NULL DEFAULT 0,
-            set_by BIGINT NOT NULL,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await?;
+    let schema_version = super::migrations::run(&pool).await?;
 
-    sqlx::query(
-        r#"
-        CREATE INDEX IF NOT EXISTS idx_guild_booster_limits_guild 
-        ON guild_booster_limits(guild_id)
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    tracing::info!("Creating guild_booster_awards table");
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS guild_booster_awards (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            guild_id BIGINT NOT NULL UNIQUE,
-            award_role_id BIGINT NOT NULL,
-            set_by BIGINT NOT NULL,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    sqlx::query(
-        r#"
-        CREATE INDEX IF NOT EXISTS idx_guild_booster_awards_guild 
-        ON guild_booster_awards(guild_id)
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    tracing::info!("Creating booster_rename_history table");
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS booster_rename_history (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            guild_id BIGINT NOT NULL,
-            user_id BIGINT NOT NULL,
-            old_name TEXT NOT NULL,
-            new_name TEXT NOT NULL,
-            renamed_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    sqlx::query(
-        r#"
-        CREATE INDEX IF NOT EXISTS idx_rename_user 
-        ON booster_rename_history(guild_id, user_id, renamed_at)
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    // New tables for boosterrole extensions
-    tracing::info!("Creating booster_role_shares table");
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS booster_role_shares (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            guild_id BIGINT NOT NULL,
-            role_id BIGINT NOT NULL,
-            owner_id BIGINT NOT NULL,
-            shared_with_id BIGINT NOT NULL,
-            shared_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            expires_at TIMESTAMP NULL,
-            is_active BOOLEAN DEFAULT TRUE,
-            CONSTRAINT unique_role_share UNIQUE(guild_id, role_id, shared_with_id)
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    sqlx::query(
-        r#"
-        CREATE INDEX IF NOT EXISTS idx_role_shares_guild_id 
-        ON booster_role_shares(guild_id)
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    sqlx::query(
-        r#"
-        CREATE INDEX IF NOT EXISTS idx_role_shares_owner_id 
-        ON booster_role_shares(owner_id)
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    sqlx::query(
-        r#"
-        CREATE INDEX IF NOT EXISTS idx_role_shares_shared_with 
-        ON booster_role_shares(shared_with_id)
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    sqlx::query(
-        r#"
-        CREATE INDEX IF NOT EXISTS idx_role_shares_active 
-        ON booster_role_shares(is_active) WHERE is_active = TRUE
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    tracing::info!("Creating guild_sharing_limits table");
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS guild_sharing_limits (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            guild_id BIGINT NOT NULL UNIQUE,
-            max_members_per_role INTEGER DEFAULT 5,
-            max_shared_roles_per_member INTEGER DEFAULT 3,
-            set_by BIGINT NOT NULL,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await?;
+    tracing::info!(
+        schema_version = schema_version,
+        max_connections = max_connections,
+        "Database initialized successfully"
+    );
 
-    tracing::info!("Creating guild_booster_base_roles table");
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS guild_booster_base_roles (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            guild_id BIGINT NOT NULL UNIQUE,
-            base_role_id BIGINT NOT NULL,
-            set_by BIGINT NOT NULL,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    // Settings Command Suite Tables
-    tracing::info!("Creating guild_staff_roles table");
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS guild_staff_roles (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            guild_id BIGINT NOT NULL,
-            role_id BIGINT NOT NULL,
-            added_by BIGINT NOT NULL,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            UNIQUE(guild_id, role_id)
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    sqlx::query(
-        r#"
-        CREATE INDEX IF NOT EXISTS idx_guild_staff_roles 
-        ON guild_staff_roles(guild_id)
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    tracing::info!("Creating guild_auto_nicknames table");
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS guild_auto_nicknames (
-            guild_id BIGINT PRIMARY KEY,
-            nickname_template TEXT NOT NULL,
-            set_by BIGINT NOT NULL,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    tracing::info!("Creating guild_join_log_channels table");
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS guild_join_log_channels (
-            guild_id BIGINT PRIMARY KEY,
-            channel_id BIGINT NOT NULL,
-            set_by BIGINT NOT NULL,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    tracing::info!("Creating guild_premium_roles table");
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS guild_premium_roles (
-            guild_id BIGINT PRIMARY KEY,
-            role_id BIGINT NOT NULL,
-            set_by BIGINT NOT NULL,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    tracing::info!("Creating settings_audit_log table");
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS settings_audit_log (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            guild_id BIGINT NOT NULL,
-            user_id BIGINT NOT NULL,
-            action TEXT NOT NULL,
-            details TEXT,
-            timestamp TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await?;
-
-    sqlx::query(
-        r#"
-        CREATE INDEX IF NOT EXISTS idx_settings_audit_log 
-        ON settings_audit_log(guild_id, timestamp)
-        "#,
-    )
-    .execute(&pool)
-    .await?;
+    Ok(pool)
+}
 
-    // Moderation foundation (F1)
-    tracing::info!("Creating guild_moderation_counters table");
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS guild_moderation_counters (
-            guild_id BIGINT PRIMARY KEY,
-            last_case_number INTEGER NOT NULL DEFAULT 0
+/// A private in-memory database with the full schema, so tests can run
+/// command logic without Discord or a file on disk
+#[cfg(test)]
+pub async fn init_memory_database() -> Result<SqlitePool, sqlx::Error> {
+    // Each connection to `sqlite::memory:` is a separate database, so the
+    // pool holds exactly one and never lets it go
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect_with(
+            "sqlite::memory:"
+                .parse::<SqliteConnectOptions>()?
+                .foreign_keys(true),
         )
-        "#,
-    )
-    .execute(&pool)
-    .await?;
+        .await?;
 
-    tracing::info!("Creating moderation_cases table");
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS moderation_cases (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            guild_id BIGINT NOT NULL,
-            case_number INTEGER NOT NULL,
-            action TEXT NOT NULL,
-            target_id BIGINT NOT NULL,
-            moderator_id BIGINT NOT NULL,
-            reason TEXT,
-            duration_seconds INTEGER,
-            active INTEGER NOT NULL DEFAULT 1,
-            related_case_id INTEGER,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            UNIQUE(guild_id, case_number)
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await?;
+    super::migrations::run(&pool).await?;
+    Ok(pool)
+}
 
-    sqlx::query(
-        r#"
-        CREATE INDEX IF NOT EXISTS idx_moderation_cases_guild
-        ON moderation_cases(guild_id)
-        "#,
-    )
-    .execute(&pool)
-    .await?;
+/// A throwaway database file with the full schema, deleted on drop. For
+/// tests that need what the in-memory database can't give: WAL, or several
+/// connections writing at once.
+#[cfg(test)]
+pub struct TestDb {
+    pub pool: SqlitePool,
+    path: std::path::PathBuf,
+}
 
-    sqlx::query(
-        r#"
-        CREATE INDEX IF NOT EXISTS idx_moderation_cases_target
-        ON moderation_cases(guild_id, target_id)
-        "#,
-    )
-    .execute(&pool)
-    .await?;
+#[cfg(test)]
+impl Drop for TestDb {
+    fn drop(&mut self) {
+        // The pool may still hold handles; cleanup errors are ignored
+        let _ = std::fs::remove_file(&self.path);
+        let _ = std::fs::remove_file(format!("{}-wal", self.path.display()));
+        let _ = std::fs::remove_file(format!("{}-shm", self.path.display()));
+    }
+}
 
-    sqlx::query(
-        r#"
-        CREATE INDEX IF NOT EXISTS idx_moderation_cases_moderator
-        ON moderation_cases(guild_id, moderator_id)
-        "#,
-    )
-    .execute(&pool)
-    .await?;
+#[cfg(test)]
+pub async fn test_db() -> TestDb {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::SeqCst);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let path = std::env::temp_dir().join(format!(
+        "death_bot_test_{}_{}_{}.db",
+        std::process::id(),
+        nanos,
+        n
+    ));
+    let pool = init_database(&path.to_string_lossy(), DEFAULT_MAX_CONNECTIONS)
+        .await
+        .unwrap_or_else(|e| panic!("init test db {}: {e}", path.display()));
+    TestDb { pool, path }
+}
 
-    tracing::info!("Database initialized successfully");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::models::GuildPrefix;
+
+    #[tokio::test]
+    async fn test_schema_is_versioned_and_uses_wal() {
+        let db = test_db().await;
+
+        let journal_mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+            .fetch_one(&db.pool)
+            .await
+            .unwrap();
+        assert_eq!(journal_mode.to_lowercase(), "wal");
+
+        let version: i64 = sqlx::query_scalar("SELECT MAX(version) FROM schema_version")
+            .fetch_one(&db.pool)
+            .await
+            .unwrap();
+        assert_eq!(version, crate::data::migrations::MIGRATIONS.len() as i64);
+    }
 
-    Ok(pool)
+    #[tokio::test]
+    async fn test_concurrent_writes_do_not_hit_lock_errors() {
+        let db = test_db().await;
+
+        let writers = (0..64u64).map(|i| {
+            let pool = db.pool.clone();
+            tokio::spawn(async move {
+                GuildPrefix::set(&pool, i % 8, if i % 2 == 0 { "!" } else { "?" }).await
+            })
+        });
+
+        for writer in writers.collect::<Vec<_>>() {
+            if let Err(e) = writer.await.unwrap() {
+                panic!("concurrent prefix write failed: {e}");
+            }
+        }
+
+        let prefixes: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM guild_prefixes")
+            .fetch_one(&db.pool)
+            .await
+            .unwrap();
+        assert_eq!(prefixes, 8);
+    }
 }
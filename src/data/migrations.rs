@@ -0,0 +1,530 @@
+use sqlx::{SqliteConnection, SqlitePool};
+
+/// One change to the schema within a migration
+pub enum Step {
+    /// Statements run as-is
+    Sql(&'static str),
+    /// Adds a column unless it's already there, since databases from before
+    /// versioned migrations may have picked it up already
+    AddColumn {
+        table: &'static str,
+        column: &'static str,
+        definition: &'static str,
+    },
+    /// Rewrites stored timestamps in every `*_at` column so they decode as
+    /// `DateTime<Utc>`: bare dates get a midnight time, and text SQLite can't
+    /// read as a date becomes NULL (or now, where the column is NOT NULL)
+    NormalizeTimestamps,
+}
+
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub steps: &'static [Step],
+}
+
+/// Schema history, oldest first. Append new migrations with the next version;
+/// never edit one that has shipped. The baseline must stay `IF NOT EXISTS`
+/// throughout, since databases from before versioned migrations run it too.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "baseline",
+        steps: &[Step::Sql(include_str!("migrations/0001_baseline.sql"))],
+    },
+    Migration {
+        version: 2,
+        name: "booster_role_origin_and_icon",
+        steps: &[
+            Step::AddColumn {
+                table: "booster_roles",
+                column: "origin",
+                definition: "TEXT NOT NULL DEFAULT 'created'",
+            },
+            Step::AddColumn {
+                table: "booster_roles",
+                column: "icon_url",
+                definition: "TEXT",
+            },
+        ],
+    },
+    Migration {
+        version: 3,
+        name: "blacklist_match_options",
+        steps: &[
+            Step::AddColumn {
+                table: "role_name_blacklist",
+                column: "severity",
+                definition: "TEXT NOT NULL DEFAULT 'medium'",
+            },
+            Step::AddColumn {
+                table: "role_name_blacklist",
+                column: "match_type",
+                definition: "TEXT NOT NULL DEFAULT 'substring'",
+            },
+        ],
+    },
+    Migration {
+        version: 4,
+        name: "award_boost_options",
+        steps: &[
+            Step::AddColumn {
+                table: "guild_booster_awards",
+                column: "remove_on_unboost",
+                definition: "BOOLEAN NOT NULL DEFAULT 1",
+            },
+            Step::AddColumn {
+                table: "guild_booster_awards",
+                column: "announce_boosts",
+                definition: "BOOLEAN NOT NULL DEFAULT 0",
+            },
+        ],
+    },
+    Migration {
+        version: 5,
+        name: "booster_log_channels",
+        steps: &[Step::Sql(include_str!(
+            "migrations/0005_booster_log_channels.sql"
+        ))],
+    },
+    Migration {
+        version: 6,
+        name: "split_join_leave_channels",
+        steps: &[Step::Sql(include_str!(
+            "migrations/0006_split_join_leave_channels.sql"
+        ))],
+    },
+    Migration {
+        version: 7,
+        name: "global_role_name_blacklist",
+        steps: &[Step::Sql(include_str!(
+            "migrations/0007_global_role_name_blacklist.sql"
+        ))],
+    },
+    Migration {
+        version: 8,
+        name: "guild_embed_themes",
+        steps: &[Step::Sql(include_str!(
+            "migrations/0008_guild_embed_themes.sql"
+        ))],
+    },
+    Migration {
+        version: 9,
+        name: "booster_role_templates",
+        steps: &[Step::Sql(include_str!(
+            "migrations/0009_booster_role_templates.sql"
+        ))],
+    },
+    Migration {
+        version: 10,
+        name: "guild_disabled_commands",
+        steps: &[Step::Sql(include_str!(
+            "migrations/0010_guild_disabled_commands.sql"
+        ))],
+    },
+    Migration {
+        version: 11,
+        name: "booster_role_archive",
+        steps: &[Step::Sql(include_str!(
+            "migrations/0011_booster_role_archive.sql"
+        ))],
+    },
+    Migration {
+        version: 12,
+        name: "guild_command_cooldowns",
+        steps: &[Step::Sql(include_str!(
+            "migrations/0012_guild_command_cooldowns.sql"
+        ))],
+    },
+    Migration {
+        version: 13,
+        name: "share_deactivated_at",
+        steps: &[
+            Step::AddColumn {
+                table: "booster_role_shares",
+                column: "deactivated_at",
+                definition: "TIMESTAMP NULL",
+            },
+            Step::Sql(include_str!("migrations/0013_share_deactivated_at.sql")),
+        ],
+    },
+    Migration {
+        version: 14,
+        name: "guild_locales",
+        steps: &[Step::Sql(include_str!("migrations/0014_guild_locales.sql"))],
+    },
+    Migration {
+        version: 15,
+        name: "guild_staff_lookalike_settings",
+        steps: &[Step::Sql(include_str!(
+            "migrations/0015_guild_staff_lookalike_settings.sql"
+        ))],
+    },
+    Migration {
+        version: 16,
+        name: "color_rotations",
+        steps: &[Step::Sql(include_str!("migrations/0016_color_rotations.sql"))],
+    },
+    Migration {
+        version: 17,
+        name: "guild_booster_options",
+        steps: &[Step::Sql(include_str!("migrations/0017_guild_booster_options.sql"))],
+    },
+    Migration {
+        version: 18,
+        name: "normalize_timestamps",
+        steps: &[Step::NormalizeTimestamps],
+    },
+    Migration {
+        version: 19,
+        name: "command_usage",
+        steps: &[Step::Sql(include_str!("migrations/0019_command_usage.sql"))],
+    },
+];
+
+/// Bring the schema up to date, applying each pending migration exactly once.
+/// Returns the schema version afterwards.
+pub async fn run(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    let mut current = current_version(pool).await?;
+
+    // The baseline only creates what's missing, so it runs against these too
+    if current == 0 && is_legacy_database(pool).await? {
+        tracing::info!("Existing database without migration history, filling in the baseline");
+    }
+
+    let pending = MIGRATIONS.iter().filter(|m| m.version > current);
+    for migration in pending.collect::<Vec<_>>() {
+        tracing::info!(
+            version = migration.version,
+            name = migration.name,
+            "Applying migration"
+        );
+
+        // Each migration and its version stamp commit together
+        let mut tx = pool.begin().await?;
+        for step in migration.steps {
+            match step {
+                Step::Sql(sql) => {
+                    sqlx::query(sql).execute(&mut *tx).await?;
+                }
+                Step::AddColumn {
+                    table,
+                    column,
+                    definition,
+                } => add_column_if_missing(&mut tx, table, column, definition).await?,
+                Step::NormalizeTimestamps => normalize_timestamps(&mut tx).await?,
+            }
+        }
+        sqlx::query("INSERT INTO schema_version (version) VALUES (?)")
+            .bind(migration.version)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        current = migration.version;
+    }
+
+    Ok(current)
+}
+
+async fn current_version(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("SELECT COALESCE(MAX(version), 0) FROM schema_version")
+        .fetch_one(pool)
+        .await
+}
+
+/// A database built by the schema code that predates migrations
+async fn is_legacy_database(pool: &SqlitePool) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'booster_roles')",
+    )
+    .fetch_one(pool)
+    .await
+}
+
+async fn add_column_if_missing(
+    conn: &mut SqliteConnection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<(), sqlx::Error> {
+    let exists: bool =
+        sqlx::query_scalar("SELECT COUNT(*) > 0 FROM pragma_table_info(?) WHERE name = ?")
+            .bind(table)
+            .bind(column)
+            .fetch_one(&mut *conn)
+            .await?;
+
+    if !exists {
+        tracing::info!(table = table, column = column, "Adding missing column");
+        sqlx::query(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {}",
+            table, column, definition
+        ))
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Timestamp columns are found from the live schema rather than listed, since
+/// databases from before versioned migrations lack some tables
+async fn normalize_timestamps(conn: &mut SqliteConnection) -> Result<(), sqlx::Error> {
+    let columns: Vec<(String, String, bool)> = sqlx::query_as(
+        r#"
+        SELECT m.name, c.name, c."notnull"
+        FROM sqlite_master m, pragma_table_info(m.name) c
+        WHERE m.type = 'table' AND m.name NOT LIKE 'sqlite_%' AND c.name LIKE '%\_at' ESCAPE '\'
+        ORDER BY m.name, c.cid
+        "#,
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for (table, column, not_null) in columns {
+        sqlx::query(&format!(
+            "UPDATE {table} SET {column} = {column} || ' 00:00:00' \
+             WHERE typeof({column}) = 'text' AND length({column}) = 10 \
+             AND date({column}) IS NOT NULL"
+        ))
+        .execute(&mut *conn)
+        .await?;
+
+        let fallback = if not_null {
+            "strftime('%Y-%m-%dT%H:%M:%SZ', 'now')"
+        } else {
+            "NULL"
+        };
+        let cleared = sqlx::query(&format!(
+            "UPDATE {table} SET {column} = {fallback} \
+             WHERE typeof({column}) = 'text' AND datetime({column}) IS NULL"
+        ))
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+        if cleared > 0 {
+            tracing::warn!(
+                table = %table,
+                column = %column,
+                rows = cleared,
+                "Replaced unreadable timestamps"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    fn latest_version() -> i64 {
+        MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+    }
+
+    async fn memory_pool() -> SqlitePool {
+        SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap()
+    }
+
+    async fn has_column(pool: &SqlitePool, table: &str, column: &str) -> bool {
+        sqlx::query_scalar("SELECT COUNT(*) > 0 FROM pragma_table_info(?) WHERE name = ?")
+            .bind(table)
+            .bind(column)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn table_names(pool: &SqlitePool) -> Vec<String> {
+        sqlx::query_scalar(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn applied_versions(pool: &SqlitePool) -> Vec<i64> {
+        sqlx::query_scalar("SELECT version FROM schema_version ORDER BY version")
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_versions_are_sequential() {
+        for (i, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version, i as i64 + 1, "{}", migration.name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fresh_database_runs_every_migration_once() {
+        let pool = memory_pool().await;
+
+        assert_eq!(run(&pool).await.unwrap(), latest_version());
+        assert!(has_column(&pool, "booster_roles", "icon_url").await);
+        assert!(has_column(&pool, "guild_booster_awards", "announce_boosts").await);
+        assert_eq!(
+            applied_versions(&pool).await,
+            (1..=latest_version()).collect::<Vec<_>>()
+        );
+
+        // A second run has nothing left to do
+        assert_eq!(run(&pool).await.unwrap(), latest_version());
+        assert_eq!(applied_versions(&pool).await.len() as i64, latest_version());
+    }
+
+    #[tokio::test]
+    async fn test_legacy_database_gains_every_table_without_losing_rows() {
+        let pool = memory_pool().await;
+
+        // Shaped like a database from before icon_url existed
+        sqlx::query(
+            r#"
+            CREATE TABLE booster_roles (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                guild_id BIGINT NOT NULL,
+                user_id BIGINT NOT NULL,
+                role_id BIGINT NOT NULL,
+                role_name TEXT NOT NULL,
+                primary_color TEXT NOT NULL,
+                secondary_color TEXT,
+                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                UNIQUE(guild_id, user_id)
+            );
+            CREATE TABLE role_name_blacklist (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                guild_id BIGINT NOT NULL,
+                word TEXT NOT NULL
+            );
+            CREATE TABLE guild_booster_awards (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                guild_id BIGINT NOT NULL UNIQUE,
+                award_role_id BIGINT NOT NULL
+            );
+            CREATE TABLE booster_role_shares (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                guild_id BIGINT NOT NULL,
+                role_id BIGINT NOT NULL,
+                owner_id BIGINT NOT NULL,
+                shared_with_id BIGINT NOT NULL,
+                shared_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                expires_at TIMESTAMP NULL,
+                is_active BOOLEAN DEFAULT TRUE,
+                CONSTRAINT unique_role_share UNIQUE(guild_id, role_id, shared_with_id)
+            );
+            INSERT INTO booster_role_shares (guild_id, role_id, owner_id, shared_with_id, is_active)
+            VALUES (1, 3, 2, 4, FALSE);
+            CREATE TABLE guild_join_log_channels (
+                guild_id BIGINT PRIMARY KEY,
+                channel_id BIGINT NOT NULL,
+                set_by BIGINT NOT NULL,
+                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            );
+            INSERT INTO guild_join_log_channels (guild_id, channel_id, set_by)
+            VALUES (1, 50, 2);
+            INSERT INTO booster_roles (guild_id, user_id, role_id, role_name, primary_color)
+            VALUES (1, 2, 3, 'Kept', '#FF0000');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        assert_eq!(run(&pool).await.unwrap(), latest_version());
+        assert_eq!(
+            applied_versions(&pool).await,
+            (1..=latest_version()).collect::<Vec<_>>()
+        );
+
+        // Tables the legacy schema never had are created alongside the old ones
+        let fresh = memory_pool().await;
+        run(&fresh).await.unwrap();
+        assert_eq!(table_names(&pool).await, table_names(&fresh).await);
+
+        // Follow-up migrations still brought the old tables up to date
+        assert!(has_column(&pool, "booster_roles", "icon_url").await);
+        assert!(has_column(&pool, "booster_roles", "origin").await);
+        assert!(has_column(&pool, "role_name_blacklist", "match_type").await);
+        let name: String = sqlx::query_scalar("SELECT role_name FROM booster_roles")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(name, "Kept");
+
+        // The single join log channel now carries both joins and leaves
+        let channels: (Option<i64>, Option<i64>) =
+            sqlx::query_as("SELECT join_channel_id, leave_channel_id FROM guild_join_log_channels")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(channels, (Some(50), Some(50)));
+
+        // Shares that had already ended get an end time to be pruned from
+        let ended: Option<String> =
+            sqlx::query_scalar("SELECT deactivated_at FROM booster_role_shares")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert!(ended.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_legacy_timestamps_decode_after_normalizing() {
+        use chrono::{DateTime, TimeZone, Utc};
+
+        let pool = memory_pool().await;
+        run(&pool).await.unwrap();
+        sqlx::query(
+            r#"
+            INSERT INTO booster_roles (guild_id, user_id, role_id, role_name, primary_color, created_at, updated_at)
+            VALUES (1, 2, 3, 'Dated', '#FF0000', '2024-01-15', 'not a date');
+            INSERT INTO booster_role_shares (guild_id, role_id, owner_id, shared_with_id, expires_at)
+            VALUES (1, 3, 2, 4, '2024-02-01T12:00:00Z');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let mut conn = pool.acquire().await.unwrap();
+        normalize_timestamps(&mut conn).await.unwrap();
+        drop(conn);
+
+        let (created, updated): (Option<DateTime<Utc>>, Option<DateTime<Utc>>) =
+            sqlx::query_as("SELECT created_at, updated_at FROM booster_roles")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(created, Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).single());
+        assert_eq!(updated, None);
+
+        // Values already written in RFC 3339 keep their format for SQL comparisons
+        let expires: String = sqlx::query_scalar("SELECT expires_at FROM booster_role_shares")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(expires, "2024-02-01T12:00:00Z");
+    }
+}
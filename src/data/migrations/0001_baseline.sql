@@ -0,0 +1,521 @@
+-- Schema as it stood when versioned migrations were introduced.
+-- Databases created before then run it too, so every statement must skip what already exists.
+
+CREATE TABLE IF NOT EXISTS guild_prefixes (
+    guild_id BIGINT PRIMARY KEY,
+    prefix TEXT NOT NULL CHECK(length(prefix) <= 5),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_guild_prefixes_guild_id
+ON guild_prefixes(guild_id);
+
+CREATE TABLE IF NOT EXISTS booster_roles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    role_id BIGINT NOT NULL,
+    role_name TEXT NOT NULL,
+    primary_color TEXT NOT NULL,
+    secondary_color TEXT,
+    origin TEXT NOT NULL DEFAULT 'created',
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(guild_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_booster_roles_guild_user
+ON booster_roles(guild_id, user_id);
+
+CREATE TABLE IF NOT EXISTS booster_role_links (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    linked_role_id BIGINT NOT NULL,
+    linked_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(guild_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_booster_role_links_guild_user
+ON booster_role_links(guild_id, user_id);
+
+CREATE TABLE IF NOT EXISTS role_name_blacklist (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL,
+    word TEXT NOT NULL,
+    added_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(guild_id, word)
+);
+
+CREATE INDEX IF NOT EXISTS idx_role_name_blacklist_guild
+ON role_name_blacklist(guild_id);
+
+CREATE TABLE IF NOT EXISTS guild_booster_limits (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL UNIQUE,
+    max_roles INTEGER NOT NULL DEFAULT 0,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_guild_booster_limits_guild
+ON guild_booster_limits(guild_id);
+
+CREATE TABLE IF NOT EXISTS guild_booster_awards (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL UNIQUE,
+    award_role_id BIGINT NOT NULL,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_guild_booster_awards_guild
+ON guild_booster_awards(guild_id);
+
+CREATE TABLE IF NOT EXISTS booster_rename_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    old_name TEXT NOT NULL,
+    new_name TEXT NOT NULL,
+    renamed_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_rename_user
+ON booster_rename_history(guild_id, user_id, renamed_at);
+
+CREATE TABLE IF NOT EXISTS booster_role_shares (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL,
+    role_id BIGINT NOT NULL,
+    owner_id BIGINT NOT NULL,
+    shared_with_id BIGINT NOT NULL,
+    shared_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP NULL,
+    is_active BOOLEAN DEFAULT TRUE,
+    CONSTRAINT unique_role_share UNIQUE(guild_id, role_id, shared_with_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_role_shares_guild_id
+ON booster_role_shares(guild_id);
+
+CREATE INDEX IF NOT EXISTS idx_role_shares_owner_id
+ON booster_role_shares(owner_id);
+
+CREATE INDEX IF NOT EXISTS idx_role_shares_shared_with
+ON booster_role_shares(shared_with_id);
+
+CREATE INDEX IF NOT EXISTS idx_role_shares_active
+ON booster_role_shares(is_active) WHERE is_active = TRUE;
+
+CREATE TABLE IF NOT EXISTS guild_sharing_limits (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL UNIQUE,
+    max_members_per_role INTEGER DEFAULT 5,
+    max_shared_roles_per_member INTEGER DEFAULT 3,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS guild_booster_base_roles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL UNIQUE,
+    base_role_id BIGINT NOT NULL,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS guild_staff_roles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL,
+    role_id BIGINT NOT NULL,
+    added_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(guild_id, role_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_guild_staff_roles
+ON guild_staff_roles(guild_id);
+
+CREATE TABLE IF NOT EXISTS guild_auto_nicknames (
+    guild_id BIGINT PRIMARY KEY,
+    nickname_template TEXT NOT NULL,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS guild_join_log_channels (
+    guild_id BIGINT PRIMARY KEY,
+    channel_id BIGINT NOT NULL,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS guild_premium_roles (
+    guild_id BIGINT PRIMARY KEY,
+    role_id BIGINT NOT NULL,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS settings_audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    action TEXT NOT NULL,
+    details TEXT,
+    timestamp TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_settings_audit_log
+ON settings_audit_log(guild_id, timestamp);
+
+CREATE TABLE IF NOT EXISTS guild_moderation_counters (
+    guild_id BIGINT PRIMARY KEY,
+    last_case_number INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS moderation_cases (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL,
+    case_number INTEGER NOT NULL,
+    action TEXT NOT NULL,
+    target_id BIGINT NOT NULL,
+    moderator_id BIGINT NOT NULL,
+    reason TEXT,
+    duration_seconds INTEGER,
+    active INTEGER NOT NULL DEFAULT 1,
+    related_case_id INTEGER,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(guild_id, case_number)
+);
+
+CREATE INDEX IF NOT EXISTS idx_moderation_cases_guild
+ON moderation_cases(guild_id);
+
+CREATE INDEX IF NOT EXISTS idx_moderation_cases_target
+ON moderation_cases(guild_id, target_id);
+
+CREATE INDEX IF NOT EXISTS idx_moderation_cases_moderator
+ON moderation_cases(guild_id, moderator_id);
+
+CREATE TABLE IF NOT EXISTS guild_next_steps_settings (
+    guild_id BIGINT PRIMARY KEY,
+    enabled BOOLEAN NOT NULL DEFAULT 1,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS booster_role_locks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL,
+    role_id BIGINT NOT NULL,
+    name_locked BOOLEAN NOT NULL DEFAULT 0,
+    color_locked BOOLEAN NOT NULL DEFAULT 0,
+    locked_name TEXT,
+    locked_color INTEGER,
+    locked_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(guild_id, role_id)
+);
+
+CREATE TABLE IF NOT EXISTS guild_spotlight_settings (
+    guild_id BIGINT PRIMARY KEY,
+    channel_id BIGINT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT 1,
+    set_by BIGINT NOT NULL,
+    last_posted_at TIMESTAMP,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS spotlight_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL,
+    role_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    spotlighted_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_spotlight_history_guild
+ON spotlight_history(guild_id, spotlighted_at);
+
+CREATE TABLE IF NOT EXISTS guild_eligibility_settings (
+    guild_id BIGINT PRIMARY KEY,
+    mode TEXT NOT NULL DEFAULT 'boosters_only',
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS guild_account_age_settings (
+    guild_id BIGINT PRIMARY KEY,
+    min_account_age_days INTEGER NOT NULL DEFAULT 0,
+    min_membership_days INTEGER NOT NULL DEFAULT 0,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS guild_exemptions (
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    kind TEXT NOT NULL,
+    added_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (guild_id, user_id, kind)
+);
+
+CREATE TABLE IF NOT EXISTS guild_support_channels (
+    guild_id BIGINT PRIMARY KEY,
+    channel_id BIGINT NOT NULL,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS user_preferences (
+    user_id BIGINT PRIMARY KEY,
+    compact_mode BOOLEAN NOT NULL DEFAULT 0,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS guild_config_snapshots (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL,
+    label TEXT,
+    data TEXT NOT NULL,
+    created_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_config_snapshots_guild ON guild_config_snapshots(guild_id);
+
+CREATE TABLE IF NOT EXISTS experiment_counters (
+    experiment_key TEXT NOT NULL,
+    variant INTEGER NOT NULL,
+    impressions INTEGER NOT NULL DEFAULT 0,
+    conversions INTEGER NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (experiment_key, variant)
+);
+
+CREATE TABLE IF NOT EXISTS experiment_exposures (
+    experiment_key TEXT NOT NULL,
+    user_id BIGINT NOT NULL,
+    variant INTEGER NOT NULL,
+    converted BOOLEAN NOT NULL DEFAULT 0,
+    last_seen_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (experiment_key, user_id)
+);
+
+CREATE TABLE IF NOT EXISTS guild_quiet_hours (
+    guild_id BIGINT PRIMARY KEY,
+    start_minute INTEGER NOT NULL,
+    end_minute INTEGER NOT NULL,
+    utc_offset_minutes INTEGER NOT NULL DEFAULT 0,
+    queue_member_logs BOOLEAN NOT NULL DEFAULT 1,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS quiet_member_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    user_tag TEXT NOT NULL,
+    joined BOOLEAN NOT NULL,
+    occurred_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_quiet_member_events_guild
+ON quiet_member_events(guild_id, id);
+
+CREATE TABLE IF NOT EXISTS guild_share_policies (
+    guild_id BIGINT PRIMARY KEY,
+    revalidate_recipients BOOLEAN NOT NULL DEFAULT FALSE,
+    require_recipient_eligibility BOOLEAN NOT NULL DEFAULT FALSE,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS name_violations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    attempted_name TEXT NOT NULL,
+    matched_rule TEXT NOT NULL,
+    command TEXT NOT NULL,
+    attempted_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_name_violations_user
+ON name_violations(guild_id, user_id);
+
+CREATE TABLE IF NOT EXISTS guild_naming_rules (
+    guild_id BIGINT PRIMARY KEY,
+    rules_text TEXT NOT NULL,
+    version INTEGER NOT NULL DEFAULT 1,
+    require_ack BOOLEAN NOT NULL DEFAULT FALSE,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS rules_acknowledgments (
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    version INTEGER NOT NULL,
+    acknowledged_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (guild_id, user_id)
+);
+
+CREATE TABLE IF NOT EXISTS table_growth (
+    guild_id BIGINT NOT NULL,
+    table_name TEXT NOT NULL,
+    day DATE NOT NULL,
+    row_count INTEGER NOT NULL,
+    flagged BOOLEAN NOT NULL DEFAULT FALSE,
+    detail TEXT,
+    PRIMARY KEY (guild_id, table_name, day)
+);
+
+CREATE INDEX IF NOT EXISTS idx_table_growth_flagged
+ON table_growth(flagged, day);
+
+CREATE TABLE IF NOT EXISTS scheduled_role_changes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    new_name TEXT,
+    new_color TEXT,
+    scheduled_at TEXT NOT NULL,
+    revert_after_secs INTEGER,
+    revert_at TEXT,
+    prior_name TEXT,
+    prior_color TEXT,
+    status TEXT NOT NULL DEFAULT 'pending',
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_scheduled_role_changes_user
+ON scheduled_role_changes(guild_id, user_id, status);
+
+CREATE INDEX IF NOT EXISTS idx_scheduled_role_changes_status
+ON scheduled_role_changes(status, scheduled_at);
+
+CREATE TABLE IF NOT EXISTS notification_subscriptions (
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    category TEXT NOT NULL,
+    subscribed_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (guild_id, user_id, category)
+);
+
+CREATE INDEX IF NOT EXISTS idx_notification_subscriptions_category
+ON notification_subscriptions(category, user_id);
+
+CREATE TABLE IF NOT EXISTS announcements (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    title TEXT NOT NULL,
+    body TEXT NOT NULL,
+    created_by BIGINT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'sending',
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    finished_at TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS announcement_deliveries (
+    announcement_id INTEGER NOT NULL,
+    user_id BIGINT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempted_at TIMESTAMP,
+    PRIMARY KEY (announcement_id, user_id)
+);
+
+CREATE TABLE IF NOT EXISTS guild_filter_enforcement (
+    guild_id BIGINT NOT NULL,
+    severity TEXT NOT NULL,
+    action TEXT NOT NULL,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (guild_id, severity)
+);
+
+CREATE TABLE IF NOT EXISTS guild_retention_overrides (
+    guild_id BIGINT NOT NULL,
+    category TEXT NOT NULL,
+    days INTEGER NOT NULL,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (guild_id, category)
+);
+
+CREATE TABLE IF NOT EXISTS bulk_operations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    kind TEXT NOT NULL,
+    requested_by BIGINT NOT NULL,
+    status TEXT NOT NULL,
+    started_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    finished_at TIMESTAMP NULL
+);
+
+CREATE TABLE IF NOT EXISTS guild_command_channels (
+    guild_id BIGINT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    command_group TEXT NOT NULL,
+    added_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (guild_id, channel_id, command_group)
+);
+
+CREATE TABLE IF NOT EXISTS guild_rename_cooldowns (
+    guild_id BIGINT PRIMARY KEY,
+    cooldown_minutes INTEGER NOT NULL,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS cleanup_runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL,
+    orphans_removed INTEGER NOT NULL,
+    ran_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_cleanup_runs_guild
+ON cleanup_runs(guild_id, ran_at);
+
+CREATE TABLE IF NOT EXISTS booster_award_grants (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    role_id BIGINT NOT NULL,
+    granted_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_booster_award_grants_guild
+ON booster_award_grants(guild_id, granted_at);
@@ -0,0 +1,19 @@
+-- Joins and leaves can go to different channels, and either can be off.
+-- Guilds with a join log keep both going to the channel they picked.
+CREATE TABLE guild_join_log_channels_new (
+    guild_id BIGINT PRIMARY KEY,
+    join_channel_id BIGINT,
+    leave_channel_id BIGINT,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO guild_join_log_channels_new
+    (guild_id, join_channel_id, leave_channel_id, set_by, created_at, updated_at)
+SELECT guild_id, channel_id, channel_id, set_by, created_at, updated_at
+FROM guild_join_log_channels;
+
+DROP TABLE guild_join_log_channels;
+
+ALTER TABLE guild_join_log_channels_new RENAME TO guild_join_log_channels;
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS global_role_name_blacklist (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    word TEXT NOT NULL UNIQUE,
+    match_type TEXT NOT NULL DEFAULT 'substring',
+    added_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
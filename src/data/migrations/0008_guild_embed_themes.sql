@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS guild_embed_themes (
+    guild_id BIGINT PRIMARY KEY,
+    success_color INTEGER,
+    error_color INTEGER,
+    info_color INTEGER,
+    primary_color INTEGER,
+    updated_by BIGINT NOT NULL,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS guild_disabled_commands (
+    guild_id BIGINT NOT NULL,
+    command_name TEXT NOT NULL,
+    disabled_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (guild_id, command_name)
+);
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS booster_role_archive (
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    role_name TEXT NOT NULL,
+    primary_color TEXT NOT NULL,
+    secondary_color TEXT,
+    icon_url TEXT,
+    archived_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (guild_id, user_id)
+);
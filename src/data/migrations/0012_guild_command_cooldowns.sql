@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS guild_command_cooldowns (
+    guild_id BIGINT NOT NULL,
+    command_name TEXT NOT NULL,
+    seconds INTEGER NOT NULL,
+    set_by BIGINT NOT NULL,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (guild_id, command_name)
+);
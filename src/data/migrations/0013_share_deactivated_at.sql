@@ -0,0 +1,5 @@
+-- When a share ended isn't known for shares that ended before this column
+-- existed, so they count from now rather than being pruned straight away.
+UPDATE booster_role_shares
+SET deactivated_at = CURRENT_TIMESTAMP
+WHERE is_active = 0 AND deactivated_at IS NULL;
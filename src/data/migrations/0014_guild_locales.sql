@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS guild_locales (
+    guild_id BIGINT PRIMARY KEY,
+    locale TEXT NOT NULL,
+    set_by BIGINT NOT NULL,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,22 @@
+CREATE TABLE IF NOT EXISTS booster_color_rotations (
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    palette TEXT NOT NULL,
+    interval_secs INTEGER NOT NULL,
+    next_index INTEGER NOT NULL DEFAULT 0,
+    next_run_at TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (guild_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_booster_color_rotations_due
+ON booster_color_rotations(next_run_at);
+
+CREATE TABLE IF NOT EXISTS guild_rotation_settings (
+    guild_id BIGINT PRIMARY KEY,
+    min_interval_hours INTEGER NOT NULL,
+    max_members INTEGER NOT NULL,
+    set_by BIGINT NOT NULL,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
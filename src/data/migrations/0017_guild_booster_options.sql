@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS guild_booster_options (
+    guild_id BIGINT PRIMARY KEY,
+    max_name_length INTEGER,
+    allow_secondary_color BOOLEAN NOT NULL DEFAULT 1,
+    set_by BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS command_usage (
+    guild_id BIGINT NOT NULL,
+    command TEXT NOT NULL,
+    day DATE NOT NULL,
+    count INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (guild_id, command, day)
+);
+
+CREATE INDEX IF NOT EXISTS idx_command_usage_day
+ON command_usage(day);
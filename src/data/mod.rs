@@ -1,4 +1,6 @@
 pub mod database;
+pub mod migrations;
 pub mod models;
+pub mod repos;
 
 pub use database::init_database;
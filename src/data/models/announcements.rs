@@ -0,0 +1,310 @@
+use crate::utils::announcements::{DeliveryStatus, DeliveryTally, NotificationCategory};
+use chrono::{DateTime, Utc};
+use serenity::all::{GuildId, UserId};
+use sqlx::{FromRow, SqlitePool};
+
+/// A member's opt-in to a notification category in one guild
+pub struct NotificationSubscription;
+
+impl NotificationSubscription {
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        category: NotificationCategory,
+        subscribed: bool,
+    ) -> Result<(), sqlx::Error> {
+        tracing::debug!(
+            "Database query: set_notification_subscription {} for user {} in guild {} to {}",
+            category.as_str(),
+            user_id,
+            guild_id,
+            subscribed
+        );
+
+        let query = if subscribed {
+            r#"
+            INSERT INTO notification_subscriptions (guild_id, user_id, category)
+            VALUES (?, ?, ?)
+            ON CONFLICT (guild_id, user_id, category) DO NOTHING
+            "#
+        } else {
+            "DELETE FROM notification_subscriptions WHERE guild_id = ? AND user_id = ? AND category = ?"
+        };
+
+        sqlx::query(query)
+            .bind(guild_id.get() as i64)
+            .bind(user_id.get() as i64)
+            .bind(category.as_str())
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn is_subscribed(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        category: NotificationCategory,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) > 0 FROM notification_subscriptions
+            WHERE guild_id = ? AND user_id = ? AND category = ?
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .bind(category.as_str())
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Subscribed in at least one guild
+    pub async fn is_subscribed_anywhere(
+        pool: &SqlitePool,
+        user_id: UserId,
+        category: NotificationCategory,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) > 0 FROM notification_subscriptions WHERE user_id = ? AND category = ?",
+        )
+        .bind(user_id.get() as i64)
+        .bind(category.as_str())
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Every (guild, user) subscription to a category
+    pub async fn all(
+        pool: &SqlitePool,
+        category: NotificationCategory,
+    ) -> Result<Vec<(u64, u64)>, sqlx::Error> {
+        let rows: Vec<(i64, i64)> = sqlx::query_as(
+            "SELECT guild_id, user_id FROM notification_subscriptions WHERE category = ?",
+        )
+        .bind(category.as_str())
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(guild, user)| (guild as u64, user as u64))
+            .collect())
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, FromRow)]
+pub struct Announcement {
+    pub id: i64,
+    pub title: String,
+    pub body: String,
+    pub created_by: i64,
+    pub status: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl Announcement {
+    /// Store an announcement and queue a DM for each recipient in one
+    /// transaction, so a crash can't leave it half-queued
+    pub async fn create(
+        pool: &SqlitePool,
+        title: &str,
+        body: &str,
+        created_by: UserId,
+        recipients: &[u64],
+    ) -> Result<i64, sqlx::Error> {
+        tracing::debug!(
+            "Database query: create_announcement for {} recipients",
+            recipients.len()
+        );
+
+        let mut tx = pool.begin().await?;
+        let id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO announcements (title, body, created_by, status)
+            VALUES (?, ?, ?, 'sending')
+            RETURNING id
+            "#,
+        )
+        .bind(title)
+        .bind(body)
+        .bind(created_by.get() as i64)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for user in recipients {
+            sqlx::query(
+                r#"
+                INSERT INTO announcement_deliveries (announcement_id, user_id, status)
+                VALUES (?, ?, 'pending')
+                ON CONFLICT (announcement_id, user_id) DO NOTHING
+                "#,
+            )
+            .bind(id)
+            .bind(*user as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(id)
+    }
+
+    pub async fn get(pool: &SqlitePool, id: i64) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>("SELECT * FROM announcements WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Announcements whose delivery was interrupted
+    pub async fn in_progress(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>(
+            "SELECT * FROM announcements WHERE status = 'sending' ORDER BY id",
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn recent(pool: &SqlitePool, limit: i64) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>("SELECT * FROM announcements ORDER BY id DESC LIMIT ?")
+            .bind(limit)
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn finish(pool: &SqlitePool, id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE announcements SET status = 'done', finished_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Per-recipient delivery bookkeeping; a DM is only ever sent from `pending`
+pub struct AnnouncementDelivery;
+
+impl AnnouncementDelivery {
+    /// Recipients still waiting for their DM, in a stable order
+    pub async fn pending(pool: &SqlitePool, announcement_id: i64) -> Result<Vec<u64>, sqlx::Error> {
+        let users: Vec<i64> = sqlx::query_scalar(
+            r#"
+            SELECT user_id FROM announcement_deliveries
+            WHERE announcement_id = ? AND status = 'pending'
+            ORDER BY user_id
+            "#,
+        )
+        .bind(announcement_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(users.into_iter().map(|user| user as u64).collect())
+    }
+
+    pub async fn mark(
+        pool: &SqlitePool,
+        announcement_id: i64,
+        user_id: UserId,
+        status: DeliveryStatus,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE announcement_deliveries
+            SET status = ?, attempted_at = CURRENT_TIMESTAMP
+            WHERE announcement_id = ? AND user_id = ?
+            "#,
+        )
+        .bind(status.as_str())
+        .bind(announcement_id)
+        .bind(user_id.get() as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn tally(
+        pool: &SqlitePool,
+        announcement_id: i64,
+    ) -> Result<DeliveryTally, sqlx::Error> {
+        let counts: Vec<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT status, COUNT(*) FROM announcement_deliveries
+            WHERE announcement_id = ?
+            GROUP BY status
+            "#,
+        )
+        .bind(announcement_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(DeliveryTally::from_counts(
+            counts
+                .iter()
+                .map(|(status, count)| (status.as_str(), *count)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::database::DEFAULT_MAX_CONNECTIONS;
+    use crate::data::init_database;
+
+    #[tokio::test]
+    async fn test_resume_only_sends_pending() {
+        let path = std::env::temp_dir().join(format!(
+            "announcements_{}_{}.db",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let pool = init_database(path.to_str().unwrap(), DEFAULT_MAX_CONNECTIONS)
+            .await
+            .unwrap();
+
+        let id = Announcement::create(&pool, "New", "Gradients!", UserId::new(1), &[10, 20, 30])
+            .await
+            .unwrap();
+        assert_eq!(
+            AnnouncementDelivery::pending(&pool, id).await.unwrap(),
+            vec![10, 20, 30]
+        );
+
+        // Crash after the first two were handled
+        AnnouncementDelivery::mark(&pool, id, UserId::new(10), DeliveryStatus::Sent)
+            .await
+            .unwrap();
+        AnnouncementDelivery::mark(&pool, id, UserId::new(20), DeliveryStatus::Failed)
+            .await
+            .unwrap();
+
+        let interrupted = Announcement::in_progress(&pool).await.unwrap();
+        assert_eq!(interrupted.len(), 1);
+        assert_eq!(
+            AnnouncementDelivery::pending(&pool, id).await.unwrap(),
+            vec![30]
+        );
+
+        AnnouncementDelivery::mark(&pool, id, UserId::new(30), DeliveryStatus::Skipped)
+            .await
+            .unwrap();
+        let tally = AnnouncementDelivery::tally(&pool, id).await.unwrap();
+        assert_eq!((tally.sent, tally.failed, tally.skipped), (1, 1, 1));
+        assert!(tally.is_complete());
+
+        Announcement::finish(&pool, id).await.unwrap();
+        assert!(Announcement::in_progress(&pool).await.unwrap().is_empty());
+
+        pool.close().await;
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,129 @@
+use chrono::{DateTime, Utc};
+use serenity::all::{GuildId, RoleId, Timestamp, UserId};
+use sqlx::{FromRow, SqlitePool};
+
+/// Most grants `/boosterrole award list` pages through
+pub const MAX_LISTED_GRANTS: i64 = 250;
+
+/// A booster who was given the guild's award role
+#[derive(Debug, Clone, FromRow)]
+pub struct BoosterAwardGrant {
+    pub user_id: i64,
+    pub role_id: i64,
+    pub granted_at: DateTime<Utc>,
+}
+
+impl BoosterAwardGrant {
+    pub async fn record(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+    ) -> Result<(), sqlx::Error> {
+        tracing::debug!(
+            "Database query: record_award_grant for user {} in guild {}",
+            user_id,
+            guild_id
+        );
+
+        sqlx::query(
+            "INSERT INTO booster_award_grants (guild_id, user_id, role_id) VALUES (?, ?, ?)",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .bind(role_id.get() as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Whether the member was already granted the award during the boost
+    /// that started at `since`
+    pub async fn granted_since(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        since: Timestamp,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM booster_award_grants
+                WHERE guild_id = ? AND user_id = ? AND granted_at >= datetime(?, 'unixepoch')
+            )
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .bind(since.unix_timestamp())
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Newest first
+    pub async fn recent(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            SELECT user_id, role_id, granted_at FROM booster_award_grants
+            WHERE guild_id = ?
+            ORDER BY granted_at DESC, id DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::database::test_db;
+
+    #[tokio::test]
+    async fn test_grants_are_listed_and_scoped_to_the_current_boost() {
+        let db = test_db().await;
+        let guild = GuildId::new(100);
+        let user = UserId::new(1);
+        let role = RoleId::new(50);
+
+        let boosted_at = Timestamp::from_unix_timestamp(Timestamp::now().unix_timestamp() - 60)
+            .unwrap();
+        assert!(!BoosterAwardGrant::granted_since(&db.pool, guild, user, boosted_at)
+            .await
+            .unwrap());
+
+        BoosterAwardGrant::record(&db.pool, guild, user, role)
+            .await
+            .unwrap();
+        BoosterAwardGrant::record(&db.pool, guild, UserId::new(2), role)
+            .await
+            .unwrap();
+        BoosterAwardGrant::record(&db.pool, GuildId::new(200), user, role)
+            .await
+            .unwrap();
+
+        assert!(BoosterAwardGrant::granted_since(&db.pool, guild, user, boosted_at)
+            .await
+            .unwrap());
+        let later_boost =
+            Timestamp::from_unix_timestamp(Timestamp::now().unix_timestamp() + 3600).unwrap();
+        assert!(!BoosterAwardGrant::granted_since(&db.pool, guild, user, later_boost)
+            .await
+            .unwrap());
+
+        let grants = BoosterAwardGrant::recent(&db.pool, guild, MAX_LISTED_GRANTS)
+            .await
+            .unwrap();
+        assert_eq!(grants.len(), 2);
+        assert_eq!(grants[0].user_id, 2);
+        assert_eq!(grants[1].role_id, 50);
+    }
+}
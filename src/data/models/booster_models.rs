@@ -1,5 +1,17 @@
-use serenity::all::{GuildId, RoleId, UserId};
-use sqlx::{FromRow, SqlitePool};
+use crate::utils::name_severity::{
+    enforce, strongest_match, BlacklistEntry, EnforcementPolicy, FilterAction, MatchType,
+    NameCheck, Severity,
+};
+use crate::data::models::GuildRetentionOverride;
+use crate::utils::query_metrics::timed_query;
+use crate::utils::retention::RetentionCategory;
+use crate::utils::scheduled_change::{to_stored, ScheduleStatus};
+use crate::utils::share_revalidation::RevalidationPolicy;
+use crate::utils::LockFlags;
+use chrono::{DateTime, Utc};
+use serenity::all::{ChannelId, GuildId, RoleId, UserId};
+use sqlx::{FromRow, SqliteConnection, SqlitePool};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, FromRow)]
 pub struct GuildPrefix {
@@ -7,9 +19,9 @@ pub struct GuildPrefix {
     pub guild_id: i64,
     pub prefix: String,
     #[allow(dead_code)]
-    pub created_at: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
     #[allow(dead_code)]
-    pub updated_at: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl GuildPrefix {
@@ -77,12 +89,22 @@ pub struct BoosterRole {
     pub role_name: String,
     pub primary_color: String,
     pub secondary_color: Option<String>,
-    pub created_at: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
     #[allow(dead_code)]
-    pub updated_at: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Emoji or image URL the role icon was set from
+    pub icon_url: Option<String>,
 }
 
 impl BoosterRole {
+    /// How long ago the role was created; zero when that wasn't recorded
+    #[allow(dead_code)]
+    pub fn age(&self) -> chrono::Duration {
+        self.created_at
+            .map(|created| Utc::now() - created)
+            .unwrap_or_else(chrono::Duration::zero)
+    }
+
     pub async fn get(
         pool: &SqlitePool,
         guild_id: GuildId,
@@ -121,6 +143,16 @@ impl BoosterRole {
             role_id
         );
 
+        let mut tx = pool.begin().await?;
+
+        let previous_role_id: Option<i64> = sqlx::query_scalar(
+            "SELECT role_id FROM booster_roles WHERE guild_id = ? AND user_id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .fetch_optional(&mut *tx)
+        .await?;
+
         sqlx::query(
             r#"
             INSERT INTO booster_roles (guild_id, user_id, role_id, role_name, primary_color, secondary_color)
@@ -140,9 +172,17 @@ impl BoosterRole {
         .bind(role_name)
         .bind(primary_color)
         .bind(secondary_color)
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
 
+        // A re-created role starts with no shares; the old role's shares stay inactive
+        if let Some(previous) = previous_role_id.filter(|r| *r != role_id.get() as i64) {
+            BoosterRoleShare::deactivate_for_role(&mut tx, guild_id, RoleId::new(previous as u64))
+                .await?;
+        }
+
+        tx.commit().await?;
+
         tracing::info!(
             user_id = %user_id,
             guild_id = %guild_id,
@@ -154,6 +194,63 @@ impl BoosterRole {
         Ok(())
     }
 
+    /// Record a hand-made role as a booster's role (origin = claimed).
+    ///
+    /// Takes a connection so callers can run it inside a transaction alongside
+    /// the Discord-side changes. Returns `false` if the role or member is already tracked.
+    pub async fn insert_claimed(
+        conn: &mut SqliteConnection,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+        role_name: &str,
+        primary_color: &str,
+    ) -> Result<bool, sqlx::Error> {
+        tracing::debug!(
+            "Database query: insert_claimed_booster_role for user {} in guild {} with role {}",
+            user_id,
+            guild_id,
+            role_id
+        );
+
+        let already_tracked: bool = sqlx::query_scalar(
+            "SELECT COUNT(*) > 0 FROM booster_roles WHERE guild_id = ? AND (user_id = ? OR role_id = ?)",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .bind(role_id.get() as i64)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        if already_tracked {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO booster_roles (guild_id, user_id, role_id, role_name, primary_color, origin)
+            VALUES (?, ?, ?, ?, ?, 'claimed')
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .bind(role_id.get() as i64)
+        .bind(role_name)
+        .bind(primary_color)
+        .execute(&mut *conn)
+        .await?;
+
+        tracing::info!(
+            user_id = %user_id,
+            guild_id = %guild_id,
+            role_id = %role_id,
+            role_name = %role_name,
+            "Claimed booster role recorded"
+        );
+
+        Ok(true)
+    }
+
     pub async fn update(
         pool: &SqlitePool,
         guild_id: GuildId,
@@ -193,6 +290,29 @@ impl BoosterRole {
         Ok(())
     }
 
+    /// Record where the role's icon came from, or clear it
+    pub async fn set_icon(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        icon_url: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE booster_roles
+            SET icon_url = ?, updated_at = CURRENT_TIMESTAMP
+            WHERE guild_id = ? AND user_id = ?
+            "#,
+        )
+        .bind(icon_url)
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn delete(
         pool: &SqlitePool,
         guild_id: GuildId,
@@ -204,13 +324,25 @@ impl BoosterRole {
             guild_id
         );
 
-        let result = sqlx::query("DELETE FROM booster_roles WHERE guild_id = ? AND user_id = ?")
-            .bind(guild_id.get() as i64)
-            .bind(user_id.get() as i64)
-            .execute(pool)
-            .await?;
+        let mut tx = pool.begin().await?;
 
-        let deleted = result.rows_affected() > 0;
+        let role_id: Option<i64> = sqlx::query_scalar(
+            "DELETE FROM booster_roles WHERE guild_id = ? AND user_id = ? RETURNING role_id",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        // Shares die with the role they point at
+        if let Some(role_id) = role_id {
+            BoosterRoleShare::deactivate_for_role(&mut tx, guild_id, RoleId::new(role_id as u64))
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        let deleted = role_id.is_some();
 
         if deleted {
             tracing::info!(
@@ -223,6 +355,35 @@ impl BoosterRole {
         Ok(deleted)
     }
 
+    /// Delete the record for a role removed on Discord, deactivating its shares
+    /// in the same transaction. Shares are deactivated even when the role was
+    /// never tracked as a booster role.
+    pub async fn delete_by_role(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        role_id: RoleId,
+    ) -> Result<bool, sqlx::Error> {
+        tracing::debug!(
+            "Database query: delete_booster_role_by_role {} in guild {}",
+            role_id,
+            guild_id
+        );
+
+        let mut tx = pool.begin().await?;
+
+        let result = sqlx::query("DELETE FROM booster_roles WHERE guild_id = ? AND role_id = ?")
+            .bind(guild_id.get() as i64)
+            .bind(role_id.get() as i64)
+            .execute(&mut *tx)
+            .await?;
+
+        BoosterRoleShare::deactivate_for_role(&mut tx, guild_id, role_id).await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     pub async fn get_all_for_guild(
         pool: &SqlitePool,
         guild_id: GuildId,
@@ -232,16 +393,48 @@ impl BoosterRole {
             guild_id
         );
 
-        let results = sqlx::query_as::<_, BoosterRole>(
-            "SELECT * FROM booster_roles WHERE guild_id = ? ORDER BY created_at DESC",
+        let results = timed_query(
+            "booster_roles.get_all_for_guild",
+            guild_id,
+            sqlx::query_as::<_, BoosterRole>(
+                "SELECT * FROM booster_roles WHERE guild_id = ? ORDER BY created_at DESC",
+            )
+            .bind(guild_id.get() as i64)
+            .fetch_all(pool),
         )
-        .bind(guild_id.get() as i64)
-        .fetch_all(pool)
         .await?;
 
         Ok(results)
     }
 
+    /// Booster roles on record in one guild
+    pub async fn count_for_guild(pool: &SqlitePool, guild_id: GuildId) -> Result<i64, sqlx::Error> {
+        timed_query(
+            "booster_roles.count_for_guild",
+            guild_id,
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM booster_roles WHERE guild_id = ?")
+                .bind(guild_id.get() as i64)
+                .fetch_one(pool),
+        )
+        .await
+    }
+
+    /// Booster roles on record in every guild, keyed by guild ID
+    pub async fn count_by_guild(pool: &SqlitePool) -> Result<HashMap<u64, i64>, sqlx::Error> {
+        tracing::debug!("Database query: count_booster_roles_by_guild");
+
+        let rows: Vec<(i64, i64)> = sqlx::query_as(
+            "SELECT guild_id, COUNT(*) FROM booster_roles GROUP BY guild_id",
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(guild_id, count)| (guild_id as u64, count))
+            .collect())
+    }
+
     pub async fn update_color(
         pool: &SqlitePool,
         guild_id: GuildId,
@@ -286,14 +479,10 @@ pub struct BoosterRoleLink {
     pub id: i64,
     #[allow(dead_code)]
     pub guild_id: i64,
-    #[allow(dead_code)]
     pub user_id: i64,
-    #[allow(dead_code)]
     pub linked_role_id: i64,
-    #[allow(dead_code)]
     pub linked_by: i64,
-    #[allow(dead_code)]
-    pub created_at: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
 }
 
 impl BoosterRoleLink {
@@ -319,6 +508,23 @@ impl BoosterRoleLink {
         Ok(result)
     }
 
+    pub async fn get_all_for_guild(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        tracing::debug!(
+            "Database query: get_all_booster_role_links for guild {}",
+            guild_id
+        );
+
+        sqlx::query_as::<_, BoosterRoleLink>(
+            "SELECT * FROM booster_role_links WHERE guild_id = ? ORDER BY created_at DESC",
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_all(pool)
+        .await
+    }
+
     pub async fn create(
         pool: &SqlitePool,
         guild_id: GuildId,
@@ -405,7 +611,7 @@ pub struct RoleNameBlacklist {
     #[allow(dead_code)]
     pub added_by: i64,
     #[allow(dead_code)]
-    pub created_at: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
 }
 
 impl RoleNameBlacklist {
@@ -425,30 +631,74 @@ impl RoleNameBlacklist {
         Ok(results)
     }
 
+    pub async fn count(pool: &SqlitePool, guild_id: GuildId) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM role_name_blacklist WHERE guild_id = ?")
+            .bind(guild_id.get() as i64)
+            .fetch_one(pool)
+            .await
+    }
+
+    /// Every blacklisted word with its severity and match type
+    pub async fn get_entries(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<Vec<BlacklistEntry>, sqlx::Error> {
+        tracing::debug!("Database query: get_blacklist_entries for guild {}", guild_id);
+
+        let rows: Vec<(String, String, String)> = sqlx::query_as(
+            "SELECT word, severity, match_type FROM role_name_blacklist WHERE guild_id = ? ORDER BY word ASC",
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(word, severity, match_type)| {
+                BlacklistEntry::new(
+                    word,
+                    Severity::parse(&severity),
+                    MatchType::parse(&match_type),
+                )
+            })
+            .collect())
+    }
+
+    /// Regex patterns are stored as typed, since lowercasing changes
+    /// escapes like `\W`; everything else is stored lowercase
     pub async fn add_word(
         pool: &SqlitePool,
         guild_id: GuildId,
         word: &str,
+        severity: Severity,
+        match_type: MatchType,
         added_by: UserId,
     ) -> Result<bool, sqlx::Error> {
         tracing::debug!(
-            "Database query: add_blacklist_word '{}' for guild {}",
+            "Database query: add_blacklist_word '{}' ({}, {}) for guild {}",
             word,
+            severity.as_str(),
+            match_type.as_str(),
             guild_id
         );
 
-        let word_lower = word.to_lowercase();
+        let word_lower = match match_type {
+            MatchType::Regex => word.to_string(),
+            _ => word.to_lowercase(),
+        };
 
         let result = sqlx::query(
             r#"
-            INSERT INTO role_name_blacklist (guild_id, word, added_by)
-            VALUES (?, ?, ?)
+            INSERT INTO role_name_blacklist (guild_id, word, added_by, severity, match_type)
+            VALUES (?, ?, ?, ?, ?)
             ON CONFLICT (guild_id, word) DO NOTHING
             "#,
         )
         .bind(guild_id.get() as i64)
         .bind(&word_lower)
         .bind(added_by.get() as i64)
+        .bind(severity.as_str())
+        .bind(match_type.as_str())
         .execute(pool)
         .await?;
 
@@ -458,6 +708,8 @@ impl RoleNameBlacklist {
             tracing::info!(
                 guild_id = %guild_id,
                 word = %word_lower,
+                severity = severity.as_str(),
+                match_type = match_type.as_str(),
                 added_by = %added_by,
                 "Blacklist word added"
             );
@@ -479,11 +731,15 @@ impl RoleNameBlacklist {
 
         let word_lower = word.to_lowercase();
 
-        let result = sqlx::query("DELETE FROM role_name_blacklist WHERE guild_id = ? AND word = ?")
-            .bind(guild_id.get() as i64)
-            .bind(&word_lower)
-            .execute(pool)
-            .await?;
+        // Regex patterns keep their original case, so try the exact text too
+        let result = sqlx::query(
+            "DELETE FROM role_name_blacklist WHERE guild_id = ? AND (word = ? OR word = ?)",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(&word_lower)
+        .bind(word)
+        .execute(pool)
+        .await?;
 
         let removed = result.rows_affected() > 0;
 
@@ -498,153 +754,536 @@ impl RoleNameBlacklist {
         Ok(removed)
     }
 
-    pub async fn is_blacklisted(
+    /// Check a role name a member is trying to use against the global list
+    /// and then the guild's, under the guild's severity policy, recording
+    /// the attempt whenever a word matches
+    pub async fn check_name(
         pool: &SqlitePool,
         guild_id: GuildId,
-        text: &str,
-    ) -> Result<bool, sqlx::Error> {
-        let blacklist = Self::get_all_for_guild(pool, guild_id).await?;
-        let text_lower = text.to_lowercase();
+        user_id: UserId,
+        name: &str,
+        command: &str,
+    ) -> Result<NameCheck, sqlx::Error> {
+        let global = GlobalRoleNameBlacklist::get_entries(pool).await?;
+        let entries = Self::get_entries(pool, guild_id).await?;
+        Self::check_entries(pool, guild_id, user_id, name, command, &global, &entries).await
+    }
 
-        for word in &blacklist {
-            if text_lower.contains(&word.to_lowercase()) {
-                return Ok(true);
-            }
+    /// How the blacklists treat a name, without recording anything.
+    /// Global entries always reject, whatever the guild's policy says.
+    pub async fn classify_entries(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        name: &str,
+        global: &[BlacklistEntry],
+        entries: &[BlacklistEntry],
+    ) -> Result<NameCheck, sqlx::Error> {
+        Ok(match strongest_match(name, global) {
+            Some((word, _)) => NameCheck::Reject {
+                word: word.to_string(),
+                alert: false,
+            },
+            None if entries.is_empty() => NameCheck::Clean,
+            None => enforce(name, entries, GuildFilterEnforcement::get(pool, guild_id).await?),
+        })
+    }
+
+    /// Same as `check_name`, against entries the caller already has,
+    /// usually from the cached `ContentFilter`s
+    pub async fn check_entries(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        name: &str,
+        command: &str,
+        global: &[BlacklistEntry],
+        entries: &[BlacklistEntry],
+    ) -> Result<NameCheck, sqlx::Error> {
+        let check = Self::classify_entries(pool, guild_id, name, global, entries).await?;
+        let word = match &check {
+            NameCheck::Clean => return Ok(check),
+            NameCheck::Warn { word } | NameCheck::Reject { word, .. } => word,
+        };
+
+        if let Err(e) = NameViolation::record(pool, guild_id, user_id, name, word, command).await
+        {
+            tracing::error!(
+                error = ?e,
+                guild_id = %guild_id,
+                user_id = %user_id,
+                "Failed to record name violation"
+            );
         }
 
-        Ok(false)
+        Ok(check)
     }
 }
 
+/// Words the bot owners block in every guild, on top of each guild's list
 #[derive(Debug, Clone, FromRow)]
-pub struct GuildBoosterLimit {
+pub struct GlobalRoleNameBlacklist {
     #[allow(dead_code)]
     pub id: i64,
+    pub word: String,
+    pub match_type: String,
     #[allow(dead_code)]
-    pub guild_id: i64,
-    #[allow(dead_code)]
-    pub max_roles: i32,
-    #[allow(dead_code)]
-    pub set_by: i64,
-    #[allow(dead_code)]
-    pub created_at: Option<String>,
+    pub added_by: i64,
     #[allow(dead_code)]
-    pub updated_at: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
 }
 
-impl GuildBoosterLimit {
-    pub async fn get(pool: &SqlitePool, guild_id: GuildId) -> Result<Option<i32>, sqlx::Error> {
-        tracing::debug!("Database query: get_booster_limit for guild {}", guild_id);
+impl GlobalRoleNameBlacklist {
+    /// Every global entry; they're checked as high severity
+    pub async fn get_entries(pool: &SqlitePool) -> Result<Vec<BlacklistEntry>, sqlx::Error> {
+        tracing::debug!("Database query: get_global_blacklist_entries");
 
-        let result = sqlx::query_scalar::<_, i32>(
-            "SELECT max_roles FROM guild_booster_limits WHERE guild_id = ?",
+        let rows = sqlx::query_as::<_, GlobalRoleNameBlacklist>(
+            "SELECT * FROM global_role_name_blacklist ORDER BY word ASC",
         )
-        .bind(guild_id.get() as i64)
-        .fetch_optional(pool)
+        .fetch_all(pool)
         .await?;
 
-        Ok(result)
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                BlacklistEntry::new(row.word, Severity::High, MatchType::parse(&row.match_type))
+            })
+            .collect())
     }
 
-    pub async fn set(
+    /// Stored the same way as guild words: lowercase unless a regex
+    pub async fn add_word(
         pool: &SqlitePool,
-        guild_id: GuildId,
-        max_roles: i32,
-        set_by: UserId,
-    ) -> Result<(), sqlx::Error> {
+        word: &str,
+        match_type: MatchType,
+        added_by: UserId,
+    ) -> Result<bool, sqlx::Error> {
         tracing::debug!(
-            "Database query: set_guild_booster_limit for guild {} to {}",
-            guild_id,
-            max_roles
+            "Database query: add_global_blacklist_word '{}' ({})",
+            word,
+            match_type.as_str()
         );
 
-        sqlx::query(
+        let stored = match match_type {
+            MatchType::Regex => word.to_string(),
+            _ => word.to_lowercase(),
+        };
+
+        let result = sqlx::query(
             r#"
-            INSERT INTO guild_booster_limits (guild_id, max_roles, set_by)
+            INSERT INTO global_role_name_blacklist (word, match_type, added_by)
             VALUES (?, ?, ?)
-            ON CONFLICT (guild_id)
-            DO UPDATE SET 
-                max_roles = excluded.max_roles,
-                set_by = excluded.set_by,
-                updated_at = CURRENT_TIMESTAMP
+            ON CONFLICT (word) DO NOTHING
             "#,
         )
-        .bind(guild_id.get() as i64)
-        .bind(max_roles)
-        .bind(set_by.get() as i64)
+        .bind(&stored)
+        .bind(match_type.as_str())
+        .bind(added_by.get() as i64)
         .execute(pool)
         .await?;
 
-        tracing::info!(
-            guild_id = %guild_id,
-            max_roles = max_roles,
-            set_by = %set_by,
-            "Guild booster limit set"
-        );
+        let added = result.rows_affected() > 0;
 
-        Ok(())
+        if added {
+            tracing::info!(
+                word = %stored,
+                match_type = match_type.as_str(),
+                added_by = %added_by,
+                "Global blacklist word added"
+            );
+        }
+
+        Ok(added)
     }
 
-    #[allow(dead_code)]
-    pub async fn remove(pool: &SqlitePool, guild_id: GuildId) -> Result<bool, sqlx::Error> {
-        tracing::debug!(
-            "Database query: remove_guild_booster_limit for guild {}",
-            guild_id
-        );
+    pub async fn remove_word(pool: &SqlitePool, word: &str) -> Result<bool, sqlx::Error> {
+        tracing::debug!("Database query: remove_global_blacklist_word '{}'", word);
 
-        let result = sqlx::query("DELETE FROM guild_booster_limits WHERE guild_id = ?")
-            .bind(guild_id.get() as i64)
-            .execute(pool)
-            .await?;
+        let result =
+            sqlx::query("DELETE FROM global_role_name_blacklist WHERE word = ? OR word = ?")
+                .bind(word.to_lowercase())
+                .bind(word)
+                .execute(pool)
+                .await?;
 
         let removed = result.rows_affected() > 0;
 
         if removed {
-            tracing::info!(guild_id = %guild_id, "Guild booster limit removed");
+            tracing::info!(word = %word, "Global blacklist word removed");
         }
 
         Ok(removed)
     }
+}
 
-    pub async fn check_limit(
-        pool: &SqlitePool,
-        guild_id: GuildId,
-    ) -> Result<(bool, Option<i32>), sqlx::Error> {
-        let limit = Self::get(pool, guild_id).await?;
+/// Per-guild overrides of what each blacklist severity does
+pub struct GuildFilterEnforcement;
 
-        if let Some(max) = limit {
-            if max == 0 {
-                return Ok((false, Some(0)));
-            }
+impl GuildFilterEnforcement {
+    pub async fn get(pool: &SqlitePool, guild_id: GuildId) -> Result<EnforcementPolicy, sqlx::Error> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT severity, action FROM guild_filter_enforcement WHERE guild_id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_all(pool)
+        .await?;
 
-            let current_count = sqlx::query_scalar::<_, i64>(
-                "SELECT COUNT(*) FROM booster_roles WHERE guild_id = ?",
-            )
+        let mut policy = EnforcementPolicy::default();
+        for (severity, action) in rows {
+            if let Some(action) = FilterAction::parse(&action) {
+                policy.set(Severity::parse(&severity), action);
+            }
+        }
+        Ok(policy)
+    }
+
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        severity: Severity,
+        action: FilterAction,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_filter_enforcement (guild_id, severity, action, set_by)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (guild_id, severity)
+            DO UPDATE SET
+                action = excluded.action,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(severity.as_str())
+        .bind(action.as_str())
+        .bind(set_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            severity = severity.as_str(),
+            action = action.as_str(),
+            set_by = %set_by,
+            "Blacklist enforcement updated"
+        );
+
+        Ok(())
+    }
+}
+
+/// A role name rejected by the blacklist
+#[derive(Debug, Clone, FromRow)]
+#[allow(dead_code)]
+pub struct NameViolation {
+    pub id: i64,
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub attempted_name: String,
+    pub matched_rule: String,
+    pub command: String,
+    pub attempted_at: DateTime<Utc>,
+}
+
+impl NameViolation {
+    pub async fn record(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        attempted_name: &str,
+        matched_rule: &str,
+        command: &str,
+    ) -> Result<(), sqlx::Error> {
+        tracing::debug!(
+            "Database query: record_name_violation for user {} in guild {}",
+            user_id,
+            guild_id
+        );
+
+        sqlx::query(
+            r#"
+            INSERT INTO name_violations (guild_id, user_id, attempted_name, matched_rule, command)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .bind(attempted_name)
+        .bind(matched_rule)
+        .bind(command)
+        .execute(pool)
+        .await?;
+
+        Self::prune(pool, guild_id).await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            user_id = %user_id,
+            matched_rule = %matched_rule,
+            command = %command,
+            "Name violation recorded"
+        );
+
+        Ok(())
+    }
+
+    /// Drop a guild's violations past its retention window
+    pub async fn prune(pool: &SqlitePool, guild_id: GuildId) -> Result<u64, sqlx::Error> {
+        let days =
+            GuildRetentionOverride::effective(pool, guild_id, RetentionCategory::Violations)
+                .await?;
+        let result = sqlx::query(
+            r#"
+            DELETE FROM name_violations
+            WHERE guild_id = ? AND attempted_at < datetime('now', ? || ' days')
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(format!("-{}", days))
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Oldest first
+    pub async fn list_for_user(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>(
+            r#"
+            SELECT * FROM name_violations
+            WHERE guild_id = ? AND user_id = ?
+            ORDER BY attempted_at, id
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .fetch_all(pool)
+        .await
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct GuildBoosterLimit {
+    #[allow(dead_code)]
+    pub id: i64,
+    #[allow(dead_code)]
+    pub guild_id: i64,
+    #[allow(dead_code)]
+    pub max_roles: i32,
+    #[allow(dead_code)]
+    pub set_by: i64,
+    #[allow(dead_code)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[allow(dead_code)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl GuildBoosterLimit {
+    pub async fn get(pool: &SqlitePool, guild_id: GuildId) -> Result<Option<i32>, sqlx::Error> {
+        tracing::debug!("Database query: get_booster_limit for guild {}", guild_id);
+
+        let result = sqlx::query_scalar::<_, i32>(
+            "SELECT max_roles FROM guild_booster_limits WHERE guild_id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(result)
+    }
+
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        max_roles: i32,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        tracing::debug!(
+            "Database query: set_guild_booster_limit for guild {} to {}",
+            guild_id,
+            max_roles
+        );
+
+        sqlx::query(
+            r#"
+            INSERT INTO guild_booster_limits (guild_id, max_roles, set_by)
+            VALUES (?, ?, ?)
+            ON CONFLICT (guild_id)
+            DO UPDATE SET 
+                max_roles = excluded.max_roles,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(max_roles)
+        .bind(set_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            max_roles = max_roles,
+            set_by = %set_by,
+            "Guild booster limit set"
+        );
+
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn remove(pool: &SqlitePool, guild_id: GuildId) -> Result<bool, sqlx::Error> {
+        tracing::debug!(
+            "Database query: remove_guild_booster_limit for guild {}",
+            guild_id
+        );
+
+        let result = sqlx::query("DELETE FROM guild_booster_limits WHERE guild_id = ?")
             .bind(guild_id.get() as i64)
-            .fetch_one(pool)
+            .execute(pool)
             .await?;
 
-            Ok((current_count < max as i64, Some(max)))
-        } else {
-            Ok((true, None))
+        let removed = result.rows_affected() > 0;
+
+        if removed {
+            tracing::info!(guild_id = %guild_id, "Guild booster limit removed");
+        }
+
+        Ok(removed)
+    }
+
+    /// Current usage against the guild's limit, or `None` when booster roles
+    /// are unlimited (no limit set, or a limit of 0)
+    pub async fn check_limit(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<Option<BoosterLimitUsage>, sqlx::Error> {
+        let max = match Self::get(pool, guild_id).await? {
+            Some(max) if max > 0 => max,
+            _ => return Ok(None),
+        };
+
+        let current = BoosterRole::count_for_guild(pool, guild_id).await?;
+
+        Ok(Some(BoosterLimitUsage { current, max }))
+    }
+}
+
+/// How many booster roles a guild has against its configured maximum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoosterLimitUsage {
+    pub current: i64,
+    pub max: i32,
+}
+
+impl BoosterLimitUsage {
+    /// No new booster roles can be created
+    pub fn is_full(&self) -> bool {
+        self.current >= self.max as i64
+    }
+}
+
+/// Per-booster caps a guild puts on what each role may use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuildBoosterOptions {
+    /// Longest role name allowed, in characters; `None` leaves Discord's limit
+    pub max_name_length: Option<i64>,
+    pub allow_secondary_color: bool,
+}
+
+impl Default for GuildBoosterOptions {
+    fn default() -> Self {
+        Self {
+            max_name_length: None,
+            allow_secondary_color: true,
         }
     }
 }
 
+impl GuildBoosterOptions {
+    pub async fn get(pool: &SqlitePool, guild_id: GuildId) -> Result<Self, sqlx::Error> {
+        tracing::debug!("Database query: get_booster_options for guild {}", guild_id);
+
+        let row: Option<(Option<i64>, bool)> = sqlx::query_as(
+            "SELECT max_name_length, allow_secondary_color FROM guild_booster_options WHERE guild_id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row
+            .map(|(max_name_length, allow_secondary_color)| Self {
+                max_name_length,
+                allow_secondary_color,
+            })
+            .unwrap_or_default())
+    }
+
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        options: Self,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_booster_options (guild_id, max_name_length, allow_secondary_color, set_by)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (guild_id)
+            DO UPDATE SET
+                max_name_length = excluded.max_name_length,
+                allow_secondary_color = excluded.allow_secondary_color,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(options.max_name_length)
+        .bind(options.allow_secondary_color)
+        .bind(set_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            max_name_length = ?options.max_name_length,
+            allow_secondary_color = options.allow_secondary_color,
+            set_by = %set_by,
+            "Guild booster options set"
+        );
+
+        Ok(())
+    }
+
+    /// The name's length in characters when it's longer than the guild allows
+    pub fn name_too_long(&self, name: &str) -> Option<usize> {
+        let max = self.max_name_length?;
+        let length = name.chars().count();
+        (length as i64 > max).then_some(length)
+    }
+}
+
 #[derive(Debug, Clone, FromRow)]
 pub struct GuildBoosterAward {
     #[allow(dead_code)]
     pub id: i64,
     #[allow(dead_code)]
     pub guild_id: i64,
-    #[allow(dead_code)]
     pub award_role_id: i64,
     #[allow(dead_code)]
     pub set_by: i64,
     #[allow(dead_code)]
-    pub created_at: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
     #[allow(dead_code)]
-    pub updated_at: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub remove_on_unboost: bool,
+    pub announce_boosts: bool,
 }
 
 impl GuildBoosterAward {
@@ -661,10 +1300,26 @@ impl GuildBoosterAward {
         Ok(result.map(|id| RoleId::new(id as u64)))
     }
 
+    /// The award role along with whether it's taken back on unboost
+    pub async fn get_config(pool: &SqlitePool, guild_id: GuildId) -> Result<Option<Self>, sqlx::Error> {
+        tracing::debug!("Database query: get_booster_award_config for guild {}", guild_id);
+
+        sqlx::query_as::<_, Self>("SELECT * FROM guild_booster_awards WHERE guild_id = ?")
+            .bind(guild_id.get() as i64)
+            .fetch_optional(pool)
+            .await
+    }
+
+    pub fn role_id(&self) -> RoleId {
+        RoleId::new(self.award_role_id as u64)
+    }
+
     pub async fn set(
         pool: &SqlitePool,
         guild_id: GuildId,
         award_role_id: RoleId,
+        remove_on_unboost: bool,
+        announce_boosts: bool,
         set_by: UserId,
     ) -> Result<(), sqlx::Error> {
         tracing::debug!(
@@ -675,17 +1330,21 @@ impl GuildBoosterAward {
 
         sqlx::query(
             r#"
-            INSERT INTO guild_booster_awards (guild_id, award_role_id, set_by)
-            VALUES (?, ?, ?)
+            INSERT INTO guild_booster_awards (guild_id, award_role_id, remove_on_unboost, announce_boosts, set_by)
+            VALUES (?, ?, ?, ?, ?)
             ON CONFLICT (guild_id)
             DO UPDATE SET 
                 award_role_id = excluded.award_role_id,
+                remove_on_unboost = excluded.remove_on_unboost,
+                announce_boosts = excluded.announce_boosts,
                 set_by = excluded.set_by,
                 updated_at = CURRENT_TIMESTAMP
             "#,
         )
         .bind(guild_id.get() as i64)
         .bind(award_role_id.get() as i64)
+        .bind(remove_on_unboost)
+        .bind(announce_boosts)
         .bind(set_by.get() as i64)
         .execute(pool)
         .await?;
@@ -693,6 +1352,8 @@ impl GuildBoosterAward {
         tracing::info!(
             guild_id = %guild_id,
             award_role_id = %award_role_id,
+            remove_on_unboost = remove_on_unboost,
+            announce_boosts = announce_boosts,
             set_by = %set_by,
             "Guild booster award role set"
         );
@@ -731,10 +1392,15 @@ pub struct BoosterRenameHistory {
     pub user_id: i64,
     pub old_name: String,
     pub new_name: String,
-    pub renamed_at: String,
+    pub renamed_at: DateTime<Utc>,
 }
 
 impl BoosterRenameHistory {
+    /// When the rename happened, in UTC
+    pub fn renamed_at(&self) -> DateTime<Utc> {
+        self.renamed_at
+    }
+
     pub async fn add(
         pool: &SqlitePool,
         guild_id: GuildId,
@@ -787,7 +1453,7 @@ impl BoosterRenameHistory {
             r#"
             SELECT * FROM booster_rename_history 
             WHERE guild_id = ? AND user_id = ?
-            ORDER BY renamed_at DESC
+            ORDER BY datetime(renamed_at) DESC, id DESC
             LIMIT 1
             "#,
         )
@@ -799,56 +1465,106 @@ impl BoosterRenameHistory {
         Ok(result)
     }
 
-    pub async fn check_rate_limit(
+    pub async fn count_for_user(
         pool: &SqlitePool,
         guild_id: GuildId,
         user_id: UserId,
-        cooldown_minutes: i64,
-    ) -> Result<bool, sqlx::Error> {
+    ) -> Result<i64, sqlx::Error> {
         tracing::debug!(
-            "Database query: check_rename_rate_limit for user {} in guild {}",
+            "Database query: count_renames for user {} in guild {}",
             user_id,
             guild_id
         );
 
-        let count = sqlx::query_scalar::<_, i64>(
-            r#"
-            SELECT COUNT(*) FROM booster_rename_history 
-            WHERE guild_id = ? AND user_id = ?
-            AND renamed_at > datetime('now', ? || ' minutes')
-            "#,
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM booster_rename_history WHERE guild_id = ? AND user_id = ?",
         )
         .bind(guild_id.get() as i64)
         .bind(user_id.get() as i64)
-        .bind(format!("-{}", cooldown_minutes))
         .fetch_one(pool)
-        .await?;
-
-        Ok(count == 0)
+        .await
     }
-}
 
-#[derive(Debug, Clone, FromRow)]
-#[allow(dead_code)]
-pub struct BoosterRoleShare {
-    #[allow(dead_code)]
-    pub id: i64,
-    pub guild_id: i64,
-    pub role_id: i64,
-    pub owner_id: i64,
-    pub shared_with_id: i64,
-    pub shared_at: Option<String>,
-    pub expires_at: Option<String>,
+    /// A page of the member's renames, newest first
+    pub async fn get_history(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        tracing::debug!(
+            "Database query: get_rename_history for user {} in guild {}",
+            user_id,
+            guild_id
+        );
+
+        sqlx::query_as::<_, BoosterRenameHistory>(
+            r#"
+            SELECT * FROM booster_rename_history
+            WHERE guild_id = ? AND user_id = ?
+            ORDER BY renamed_at DESC, id DESC
+            LIMIT ? OFFSET ?
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Wipe a member's rename history, returning how many entries went
+    pub async fn clear_for_user(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<u64, sqlx::Error> {
+        tracing::debug!(
+            "Database query: clear_rename_history for user {} in guild {}",
+            user_id,
+            guild_id
+        );
+
+        let result = sqlx::query(
+            "DELETE FROM booster_rename_history WHERE guild_id = ? AND user_id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+}
+
+#[derive(Debug, Clone, FromRow)]
+#[allow(dead_code)]
+pub struct BoosterRoleShare {
+    #[allow(dead_code)]
+    pub id: i64,
+    pub guild_id: i64,
+    pub role_id: i64,
+    pub owner_id: i64,
+    pub shared_with_id: i64,
+    pub shared_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
     pub is_active: bool,
 }
 
 impl BoosterRoleShare {
+    /// Share a role, or bring back an ended share with the same member. Each
+    /// role and member pair has one row, so a re-share reactivates it with a
+    /// fresh start time and the new expiry.
     pub async fn create(
         pool: &SqlitePool,
         guild_id: GuildId,
         role_id: RoleId,
         owner_id: UserId,
         shared_with_id: UserId,
+        expires_at: Option<DateTime<Utc>>,
     ) -> Result<(), sqlx::Error> {
         tracing::debug!(
             "Database query: create_role_share for role {} shared with user {}",
@@ -858,14 +1574,22 @@ impl BoosterRoleShare {
 
         sqlx::query(
             r#"
-            INSERT INTO booster_role_shares (guild_id, role_id, owner_id, shared_with_id)
-            VALUES (?, ?, ?, ?)
+            INSERT INTO booster_role_shares (guild_id, role_id, owner_id, shared_with_id, expires_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT (guild_id, role_id, shared_with_id)
+            DO UPDATE SET 
+                owner_id = excluded.owner_id,
+                shared_at = CURRENT_TIMESTAMP,
+                expires_at = excluded.expires_at,
+                is_active = TRUE,
+                deactivated_at = NULL
             "#,
         )
         .bind(guild_id.get() as i64)
         .bind(role_id.get() as i64)
         .bind(owner_id.get() as i64)
         .bind(shared_with_id.get() as i64)
+        .bind(expires_at.map(to_stored))
         .execute(pool)
         .await?;
 
@@ -895,7 +1619,7 @@ impl BoosterRoleShare {
         let result = sqlx::query(
             r#"
             UPDATE booster_role_shares 
-            SET is_active = FALSE
+            SET is_active = FALSE, deactivated_at = CURRENT_TIMESTAMP
             WHERE guild_id = ? AND role_id = ? AND shared_with_id = ? AND is_active = TRUE
             "#,
         )
@@ -919,6 +1643,62 @@ impl BoosterRoleShare {
         Ok(removed)
     }
 
+    /// Deactivate every active share of a role, returning the recipients
+    pub async fn deactivate_for_role(
+        conn: &mut SqliteConnection,
+        guild_id: GuildId,
+        role_id: RoleId,
+    ) -> Result<Vec<UserId>, sqlx::Error> {
+        let recipients: Vec<i64> = sqlx::query_scalar(
+            r#"
+            UPDATE booster_role_shares 
+            SET is_active = FALSE, deactivated_at = CURRENT_TIMESTAMP
+            WHERE guild_id = ? AND role_id = ? AND is_active = TRUE
+            RETURNING shared_with_id
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(role_id.get() as i64)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        if !recipients.is_empty() {
+            tracing::info!(
+                guild_id = %guild_id,
+                role_id = %role_id,
+                shares = recipients.len(),
+                "Deactivated role shares"
+            );
+        }
+
+        Ok(recipients
+            .into_iter()
+            .map(|id| UserId::new(id as u64))
+            .collect())
+    }
+
+    /// Deactivate every active share a member owns or received, returning
+    /// the shares that ended
+    pub async fn deactivate_for_member(
+        conn: &mut SqliteConnection,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, BoosterRoleShare>(
+            r#"
+            UPDATE booster_role_shares
+            SET is_active = FALSE, deactivated_at = CURRENT_TIMESTAMP
+            WHERE guild_id = ? AND (owner_id = ? OR shared_with_id = ?) AND is_active = TRUE
+            RETURNING id, guild_id, role_id, owner_id, shared_with_id, shared_at, expires_at, is_active
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .fetch_all(&mut *conn)
+        .await
+    }
+
     pub async fn get_shared_with_user(
         pool: &SqlitePool,
         guild_id: GuildId,
@@ -944,26 +1724,55 @@ impl BoosterRoleShare {
         Ok(shares)
     }
 
-    pub async fn get_role_shares(
+    /// Active shares of any role the member owns, oldest first
+    pub async fn get_shares_by_owner(
         pool: &SqlitePool,
         guild_id: GuildId,
-        role_id: RoleId,
+        owner_id: UserId,
     ) -> Result<Vec<Self>, sqlx::Error> {
         tracing::debug!(
-            "Database query: get_role_shares for role {} in guild {}",
-            role_id,
+            "Database query: get_shares_by_owner for user {} in guild {}",
+            owner_id,
             guild_id
         );
 
-        let shares = sqlx::query_as::<_, BoosterRoleShare>(
+        sqlx::query_as::<_, BoosterRoleShare>(
             r#"
             SELECT * FROM booster_role_shares 
-            WHERE guild_id = ? AND role_id = ? AND is_active = TRUE
+            WHERE guild_id = ? AND owner_id = ? AND is_active = TRUE
+            ORDER BY id
             "#,
         )
         .bind(guild_id.get() as i64)
-        .bind(role_id.get() as i64)
+        .bind(owner_id.get() as i64)
         .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get_role_shares(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        role_id: RoleId,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        tracing::debug!(
+            "Database query: get_role_shares for role {} in guild {}",
+            role_id,
+            guild_id
+        );
+
+        let shares = timed_query(
+            "booster_role_shares.get_role_shares",
+            guild_id,
+            sqlx::query_as::<_, BoosterRoleShare>(
+                r#"
+                SELECT * FROM booster_role_shares 
+                WHERE guild_id = ? AND role_id = ? AND is_active = TRUE
+                "#,
+            )
+            .bind(guild_id.get() as i64)
+            .bind(role_id.get() as i64)
+            .fetch_all(pool),
+        )
         .await?;
 
         Ok(shares)
@@ -988,6 +1797,65 @@ impl BoosterRoleShare {
         Ok(count)
     }
 
+    /// Every active share in a guild, oldest first
+    pub async fn get_active_for_guild(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        tracing::debug!(
+            "Database query: get_active_shares for guild {}",
+            guild_id
+        );
+
+        sqlx::query_as::<_, BoosterRoleShare>(
+            r#"
+            SELECT * FROM booster_role_shares 
+            WHERE guild_id = ? AND is_active = TRUE
+            ORDER BY id
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Active shares whose expiry has passed, across every guild
+    pub async fn get_expired(
+        pool: &SqlitePool,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        tracing::debug!("Database query: get_expired_shares");
+
+        sqlx::query_as::<_, BoosterRoleShare>(
+            r#"
+            SELECT * FROM booster_role_shares 
+            WHERE is_active = TRUE AND expires_at IS NOT NULL AND expires_at <= ?
+            ORDER BY expires_at
+            "#,
+        )
+        .bind(to_stored(now))
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Mark an expired share inactive. Returns `false` if it had already ended.
+    pub async fn expire(pool: &SqlitePool, id: i64) -> Result<bool, sqlx::Error> {
+        tracing::debug!("Database query: expire_role_share {}", id);
+
+        let result = sqlx::query(
+            r#"
+            UPDATE booster_role_shares
+            SET is_active = FALSE, deactivated_at = CURRENT_TIMESTAMP
+            WHERE id = ? AND is_active = TRUE
+            "#,
+        )
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     pub async fn count_user_shares(
         pool: &SqlitePool,
         guild_id: GuildId,
@@ -1008,6 +1876,82 @@ impl BoosterRoleShare {
     }
 }
 
+/// Whether a guild re-checks share recipients, and how strictly
+pub struct GuildSharePolicy;
+
+impl GuildSharePolicy {
+    /// Revalidation is off unless a guild has turned it on
+    pub async fn get(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<RevalidationPolicy, sqlx::Error> {
+        let row: Option<(bool, bool)> = sqlx::query_as(
+            r#"
+            SELECT revalidate_recipients, require_recipient_eligibility
+            FROM guild_share_policies WHERE guild_id = ?
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row
+            .map(|(enabled, require_eligibility)| RevalidationPolicy {
+                enabled,
+                require_eligibility,
+            })
+            .unwrap_or_default())
+    }
+
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        policy: RevalidationPolicy,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_share_policies
+                (guild_id, revalidate_recipients, require_recipient_eligibility, set_by)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (guild_id)
+            DO UPDATE SET
+                revalidate_recipients = excluded.revalidate_recipients,
+                require_recipient_eligibility = excluded.require_recipient_eligibility,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(policy.enabled)
+        .bind(policy.require_eligibility)
+        .bind(set_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            revalidate = policy.enabled,
+            require_eligibility = policy.require_eligibility,
+            set_by = %set_by,
+            "Share revalidation policy updated"
+        );
+
+        Ok(())
+    }
+
+    /// Guilds with revalidation turned on
+    pub async fn revalidating_guilds(pool: &SqlitePool) -> Result<Vec<GuildId>, sqlx::Error> {
+        let ids: Vec<i64> = sqlx::query_scalar(
+            "SELECT guild_id FROM guild_share_policies WHERE revalidate_recipients = TRUE",
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(ids.into_iter().map(|id| GuildId::new(id as u64)).collect())
+    }
+}
+
 #[derive(Debug, Clone, FromRow)]
 #[allow(dead_code)]
 pub struct GuildSharingLimit {
@@ -1019,9 +1963,9 @@ pub struct GuildSharingLimit {
     #[allow(dead_code)]
     pub set_by: i64,
     #[allow(dead_code)]
-    pub created_at: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
     #[allow(dead_code)]
-    pub updated_at: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl GuildSharingLimit {
@@ -1091,9 +2035,9 @@ pub struct GuildBoosterBaseRole {
     #[allow(dead_code)]
     pub set_by: i64,
     #[allow(dead_code)]
-    pub created_at: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
     #[allow(dead_code)]
-    pub updated_at: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl GuildBoosterBaseRole {
@@ -1169,3 +2113,1290 @@ impl GuildBoosterBaseRole {
         Ok(removed)
     }
 }
+
+#[derive(Debug, Clone, FromRow)]
+pub struct BoosterRoleLock {
+    #[allow(dead_code)]
+    pub id: i64,
+    #[allow(dead_code)]
+    pub guild_id: i64,
+    #[allow(dead_code)]
+    pub role_id: i64,
+    pub name_locked: bool,
+    pub color_locked: bool,
+    pub locked_name: Option<String>,
+    pub locked_color: Option<i64>,
+    pub locked_by: i64,
+    #[allow(dead_code)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[allow(dead_code)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl BoosterRoleLock {
+    /// Locks are keyed by role rather than owner so they follow the role on transfer
+    pub async fn get(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        role_id: RoleId,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        tracing::debug!(
+            "Database query: get_role_lock for role {} in guild {}",
+            role_id,
+            guild_id
+        );
+
+        sqlx::query_as::<_, BoosterRoleLock>(
+            "SELECT * FROM booster_role_locks WHERE guild_id = ? AND role_id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(role_id.get() as i64)
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn get_all_for_guild(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        tracing::debug!("Database query: get_all_role_locks for guild {}", guild_id);
+
+        sqlx::query_as::<_, BoosterRoleLock>("SELECT * FROM booster_role_locks WHERE guild_id = ?")
+            .bind(guild_id.get() as i64)
+            .fetch_all(pool)
+            .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        role_id: RoleId,
+        name_locked: bool,
+        color_locked: bool,
+        locked_name: Option<&str>,
+        locked_color: Option<u32>,
+        locked_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        tracing::debug!(
+            "Database query: set_role_lock for role {} in guild {}",
+            role_id,
+            guild_id
+        );
+
+        sqlx::query(
+            r#"
+            INSERT INTO booster_role_locks
+                (guild_id, role_id, name_locked, color_locked, locked_name, locked_color, locked_by)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (guild_id, role_id)
+            DO UPDATE SET 
+                name_locked = excluded.name_locked,
+                color_locked = excluded.color_locked,
+                locked_name = excluded.locked_name,
+                locked_color = excluded.locked_color,
+                locked_by = excluded.locked_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(role_id.get() as i64)
+        .bind(name_locked)
+        .bind(color_locked)
+        .bind(locked_name)
+        .bind(locked_color.map(|c| c as i64))
+        .bind(locked_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            role_id = %role_id,
+            name_locked = name_locked,
+            color_locked = color_locked,
+            locked_by = %locked_by,
+            "Booster role lock set"
+        );
+
+        Ok(())
+    }
+
+    pub async fn remove(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        role_id: RoleId,
+    ) -> Result<bool, sqlx::Error> {
+        tracing::debug!(
+            "Database query: remove_role_lock for role {} in guild {}",
+            role_id,
+            guild_id
+        );
+
+        let result =
+            sqlx::query("DELETE FROM booster_role_locks WHERE guild_id = ? AND role_id = ?")
+                .bind(guild_id.get() as i64)
+                .bind(role_id.get() as i64)
+                .execute(pool)
+                .await?;
+
+        let removed = result.rows_affected() > 0;
+
+        if removed {
+            tracing::info!(
+                guild_id = %guild_id,
+                role_id = %role_id,
+                "Booster role lock removed"
+            );
+        }
+
+        Ok(removed)
+    }
+
+    pub fn flags(&self) -> LockFlags {
+        LockFlags {
+            name: self.name_locked,
+            color: self.color_locked,
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct GuildSpotlightSetting {
+    pub guild_id: i64,
+    pub channel_id: i64,
+    pub enabled: bool,
+    #[allow(dead_code)]
+    pub set_by: i64,
+    #[allow(dead_code)]
+    pub last_posted_at: Option<DateTime<Utc>>,
+    #[allow(dead_code)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[allow(dead_code)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl GuildSpotlightSetting {
+    pub async fn get(pool: &SqlitePool, guild_id: GuildId) -> Result<Option<Self>, sqlx::Error> {
+        tracing::debug!("Database query: get_spotlight_setting for guild {}", guild_id);
+
+        sqlx::query_as::<_, GuildSpotlightSetting>(
+            "SELECT * FROM guild_spotlight_settings WHERE guild_id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        enabled: bool,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        tracing::debug!(
+            "Database query: set_spotlight_setting for guild {} channel {}",
+            guild_id,
+            channel_id
+        );
+
+        sqlx::query(
+            r#"
+            INSERT INTO guild_spotlight_settings (guild_id, channel_id, enabled, set_by)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (guild_id)
+            DO UPDATE SET 
+                channel_id = excluded.channel_id,
+                enabled = excluded.enabled,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(channel_id.get() as i64)
+        .bind(enabled)
+        .bind(set_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            channel_id = %channel_id,
+            enabled = enabled,
+            set_by = %set_by,
+            "Guild spotlight setting updated"
+        );
+
+        Ok(())
+    }
+
+    pub async fn disable(pool: &SqlitePool, guild_id: GuildId) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE guild_spotlight_settings SET enabled = 0, updated_at = CURRENT_TIMESTAMP WHERE guild_id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Enabled guilds whose last scheduled post is at least a week old
+    pub async fn get_due(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        tracing::debug!("Database query: get_due_spotlights");
+
+        sqlx::query_as::<_, GuildSpotlightSetting>(
+            r#"
+            SELECT * FROM guild_spotlight_settings
+            WHERE enabled = 1
+              AND (last_posted_at IS NULL OR last_posted_at <= datetime('now', '-7 days'))
+            "#,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn mark_posted(pool: &SqlitePool, guild_id: GuildId) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE guild_spotlight_settings SET last_posted_at = CURRENT_TIMESTAMP WHERE guild_id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct SpotlightHistory {
+    #[allow(dead_code)]
+    pub id: i64,
+    #[allow(dead_code)]
+    pub guild_id: i64,
+    pub role_id: i64,
+    #[allow(dead_code)]
+    pub user_id: i64,
+    pub spotlighted_at: DateTime<Utc>,
+}
+
+impl SpotlightHistory {
+    pub async fn add(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        role_id: RoleId,
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO spotlight_history (guild_id, role_id, user_id) VALUES (?, ?, ?)",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(role_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            role_id = %role_id,
+            user_id = %user_id,
+            "Spotlight recorded"
+        );
+
+        Ok(())
+    }
+
+    /// Most recent spotlight per role for a guild
+    pub async fn latest_for_guild(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        tracing::debug!("Database query: latest_spotlights for guild {}", guild_id);
+
+        sqlx::query_as::<_, SpotlightHistory>(
+            r#"
+            SELECT id, guild_id, role_id, user_id, MAX(spotlighted_at) AS spotlighted_at
+            FROM spotlight_history
+            WHERE guild_id = ?
+            GROUP BY role_id
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_all(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::database::test_db;
+
+    const GUILD: GuildId = GuildId::new(100);
+    const OWNER: UserId = UserId::new(1);
+    const RECIPIENT: UserId = UserId::new(2);
+    const OLD_ROLE: RoleId = RoleId::new(500);
+    const NEW_ROLE: RoleId = RoleId::new(501);
+
+    async fn create_role(pool: &SqlitePool, role_id: RoleId) {
+        BoosterRole::create(pool, GUILD, OWNER, role_id, "Mine", "#FF0000", None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn remove_role_deactivates_shares_and_recreate_starts_clean() {
+        let db = test_db().await;
+        let pool = &db.pool;
+
+        create_role(pool, OLD_ROLE).await;
+        BoosterRoleShare::create(pool, GUILD, OLD_ROLE, OWNER, RECIPIENT, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            BoosterRoleShare::count_user_shares(pool, GUILD, RECIPIENT)
+                .await
+                .unwrap(),
+            1
+        );
+
+        assert!(BoosterRole::delete(pool, GUILD, OWNER).await.unwrap());
+
+        assert!(BoosterRoleShare::get_role_shares(pool, GUILD, OLD_ROLE)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(BoosterRoleShare::get_shared_with_user(pool, GUILD, RECIPIENT)
+            .await
+            .unwrap()
+            .is_empty());
+
+        create_role(pool, NEW_ROLE).await;
+
+        assert_eq!(
+            BoosterRoleShare::count_role_shares(pool, GUILD, NEW_ROLE)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            BoosterRoleShare::count_user_shares(pool, GUILD, RECIPIENT)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn count_by_guild_groups_roles() {
+        let db = test_db().await;
+        let pool = &db.pool;
+        let other_guild = GuildId::new(101);
+
+        create_role(pool, OLD_ROLE).await;
+        BoosterRole::create(pool, GUILD, RECIPIENT, NEW_ROLE, "Theirs", "#00FF00", None)
+            .await
+            .unwrap();
+        BoosterRole::create(pool, other_guild, OWNER, RoleId::new(502), "Elsewhere", "#0000FF", None)
+            .await
+            .unwrap();
+
+        let counts = BoosterRole::count_by_guild(pool).await.unwrap();
+        assert_eq!(counts.get(&GUILD.get()), Some(&2));
+        assert_eq!(counts.get(&other_guild.get()), Some(&1));
+        assert_eq!(counts.get(&102), None);
+    }
+
+    #[tokio::test]
+    async fn discord_side_deletion_deactivates_shares() {
+        let db = test_db().await;
+        let pool = &db.pool;
+
+        create_role(pool, OLD_ROLE).await;
+        BoosterRoleShare::create(pool, GUILD, OLD_ROLE, OWNER, RECIPIENT, None)
+            .await
+            .unwrap();
+
+        assert!(BoosterRole::delete_by_role(pool, GUILD, OLD_ROLE)
+            .await
+            .unwrap());
+        assert!(BoosterRole::get(pool, GUILD, OWNER).await.unwrap().is_none());
+        assert_eq!(
+            BoosterRoleShare::count_user_shares(pool, GUILD, RECIPIENT)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn replacing_role_id_deactivates_old_shares() {
+        let db = test_db().await;
+        let pool = &db.pool;
+
+        create_role(pool, OLD_ROLE).await;
+        BoosterRoleShare::create(pool, GUILD, OLD_ROLE, OWNER, RECIPIENT, None)
+            .await
+            .unwrap();
+
+        // Updating the same role keeps its shares
+        create_role(pool, OLD_ROLE).await;
+        assert_eq!(
+            BoosterRoleShare::count_role_shares(pool, GUILD, OLD_ROLE)
+                .await
+                .unwrap(),
+            1
+        );
+
+        create_role(pool, NEW_ROLE).await;
+        assert_eq!(
+            BoosterRoleShare::count_role_shares(pool, GUILD, OLD_ROLE)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn resharing_after_removal_reactivates_share() {
+        let db = test_db().await;
+        let pool = &db.pool;
+
+        create_role(pool, OLD_ROLE).await;
+        BoosterRoleShare::create(pool, GUILD, OLD_ROLE, OWNER, RECIPIENT, None)
+            .await
+            .unwrap();
+        assert!(BoosterRoleShare::remove(pool, GUILD, OLD_ROLE, RECIPIENT)
+            .await
+            .unwrap());
+
+        BoosterRoleShare::create(pool, GUILD, OLD_ROLE, OWNER, RECIPIENT, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            BoosterRoleShare::count_role_shares(pool, GUILD, OLD_ROLE)
+                .await
+                .unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn resharing_refreshes_expiry_and_end_time() {
+        let db = test_db().await;
+        let pool = &db.pool;
+
+        create_role(pool, OLD_ROLE).await;
+        let expires = Utc::now() + chrono::Duration::days(1);
+        BoosterRoleShare::create(pool, GUILD, OLD_ROLE, OWNER, RECIPIENT, Some(expires))
+            .await
+            .unwrap();
+        assert!(BoosterRoleShare::remove(pool, GUILD, OLD_ROLE, RECIPIENT)
+            .await
+            .unwrap());
+
+        BoosterRoleShare::create(pool, GUILD, OLD_ROLE, OWNER, RECIPIENT, None)
+            .await
+            .unwrap();
+        let row: (i64, Option<String>, Option<String>, bool) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) OVER (), expires_at, deactivated_at, is_active
+            FROM booster_role_shares WHERE guild_id = ?
+            "#,
+        )
+        .bind(GUILD.get() as i64)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        assert_eq!(row, (1, None, None, true));
+    }
+
+    #[tokio::test]
+    async fn plain_insert_of_a_reshare_violates_the_unique_constraint() {
+        let db = test_db().await;
+        let pool = &db.pool;
+
+        // The schema keeps one row per role and member, which is why
+        // `create` has to reactivate rather than insert
+        create_role(pool, OLD_ROLE).await;
+        BoosterRoleShare::create(pool, GUILD, OLD_ROLE, OWNER, RECIPIENT, None)
+            .await
+            .unwrap();
+        BoosterRoleShare::remove(pool, GUILD, OLD_ROLE, RECIPIENT)
+            .await
+            .unwrap();
+
+        let err = sqlx::query(
+            r#"
+            INSERT INTO booster_role_shares (guild_id, role_id, owner_id, shared_with_id)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(GUILD.get() as i64)
+        .bind(OLD_ROLE.get() as i64)
+        .bind(OWNER.get() as i64)
+        .bind(RECIPIENT.get() as i64)
+        .execute(pool)
+        .await
+        .unwrap_err();
+        assert!(err
+            .as_database_error()
+            .is_some_and(|e| e.is_unique_violation()));
+
+        BoosterRoleShare::create(pool, GUILD, OLD_ROLE, OWNER, RECIPIENT, None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn shares_ended_over_a_month_ago_are_pruned() {
+        use crate::data::models::GuildRetentionOverride;
+        use crate::utils::retention::RetentionCategory;
+
+        let db = test_db().await;
+        let pool = &db.pool;
+        let (long_gone, recent, active) = (UserId::new(10), UserId::new(11), UserId::new(12));
+
+        create_role(pool, OLD_ROLE).await;
+        for user in [long_gone, recent, active] {
+            BoosterRoleShare::create(pool, GUILD, OLD_ROLE, OWNER, user, None)
+                .await
+                .unwrap();
+        }
+        for user in [long_gone, recent] {
+            BoosterRoleShare::remove(pool, GUILD, OLD_ROLE, user)
+                .await
+                .unwrap();
+        }
+        // Every share started long ago; only when they ended differs
+        sqlx::query("UPDATE booster_role_shares SET shared_at = datetime('now', '-400 days')")
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            r#"
+            UPDATE booster_role_shares SET deactivated_at = datetime('now', '-31 days')
+            WHERE shared_with_id = ?
+            "#,
+        )
+        .bind(long_gone.get() as i64)
+        .execute(pool)
+        .await
+        .unwrap();
+
+        let pruned = GuildRetentionOverride::prune(pool, RetentionCategory::ShareHistory)
+            .await
+            .unwrap();
+        assert_eq!(pruned, 1);
+
+        let remaining: Vec<i64> = sqlx::query_scalar(
+            "SELECT shared_with_id FROM booster_role_shares ORDER BY shared_with_id",
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap();
+        assert_eq!(remaining, vec![recent.get() as i64, active.get() as i64]);
+    }
+
+    #[tokio::test]
+    async fn shares_by_owner_skip_revoked_and_other_owners() {
+        let db = test_db().await;
+        let pool = &db.pool;
+        const OTHER: UserId = UserId::new(3);
+
+        create_role(pool, OLD_ROLE).await;
+        BoosterRoleShare::create(pool, GUILD, OLD_ROLE, OWNER, RECIPIENT, None)
+            .await
+            .unwrap();
+        BoosterRoleShare::create(pool, GUILD, OLD_ROLE, OWNER, OTHER, None)
+            .await
+            .unwrap();
+        BoosterRoleShare::create(pool, GUILD, NEW_ROLE, OTHER, RECIPIENT, None)
+            .await
+            .unwrap();
+        assert!(BoosterRoleShare::remove(pool, GUILD, OLD_ROLE, OTHER)
+            .await
+            .unwrap());
+
+        let shares = BoosterRoleShare::get_shares_by_owner(pool, GUILD, OWNER)
+            .await
+            .unwrap();
+        assert_eq!(shares.len(), 1);
+        assert_eq!(shares[0].shared_with_id, RECIPIENT.get() as i64);
+    }
+
+    #[tokio::test]
+    async fn expired_shares_are_found_and_expired_once() {
+        let db = test_db().await;
+        let pool = &db.pool;
+        let now = chrono::Utc::now();
+        const LATER: UserId = UserId::new(3);
+        const FOREVER: UserId = UserId::new(4);
+
+        create_role(pool, OLD_ROLE).await;
+        BoosterRoleShare::create(
+            pool,
+            GUILD,
+            OLD_ROLE,
+            OWNER,
+            RECIPIENT,
+            Some(now - chrono::Duration::minutes(5)),
+        )
+        .await
+        .unwrap();
+        BoosterRoleShare::create(
+            pool,
+            GUILD,
+            OLD_ROLE,
+            OWNER,
+            LATER,
+            Some(now + chrono::Duration::hours(1)),
+        )
+        .await
+        .unwrap();
+        BoosterRoleShare::create(pool, GUILD, OLD_ROLE, OWNER, FOREVER, None)
+            .await
+            .unwrap();
+
+        let expired = BoosterRoleShare::get_expired(pool, now).await.unwrap();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].shared_with_id, RECIPIENT.get() as i64);
+
+        assert!(BoosterRoleShare::expire(pool, expired[0].id).await.unwrap());
+        assert!(!BoosterRoleShare::expire(pool, expired[0].id).await.unwrap());
+        assert!(BoosterRoleShare::get_expired(pool, now)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            BoosterRoleShare::count_role_shares(pool, GUILD, OLD_ROLE)
+                .await
+                .unwrap(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn rename_count_is_per_member_and_guild() {
+        let db = test_db().await;
+        let pool = &db.pool;
+
+        BoosterRenameHistory::add(pool, GUILD, OWNER, "Mine", "Ours")
+            .await
+            .unwrap();
+        BoosterRenameHistory::add(pool, GUILD, OWNER, "Ours", "Theirs")
+            .await
+            .unwrap();
+        BoosterRenameHistory::add(pool, GUILD, RECIPIENT, "A", "B")
+            .await
+            .unwrap();
+        BoosterRenameHistory::add(pool, GuildId::new(200), OWNER, "C", "D")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            BoosterRenameHistory::count_for_user(pool, GUILD, OWNER)
+                .await
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            BoosterRenameHistory::count_for_user(pool, GUILD, UserId::new(3))
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn rename_history_pages_newest_first_and_clears() {
+        let db = test_db().await;
+        let pool = &db.pool;
+
+        for (old, new) in [("A", "B"), ("B", "C"), ("C", "D")] {
+            BoosterRenameHistory::add(pool, GUILD, OWNER, old, new)
+                .await
+                .unwrap();
+        }
+        BoosterRenameHistory::add(pool, GUILD, RECIPIENT, "X", "Y")
+            .await
+            .unwrap();
+
+        let first = BoosterRenameHistory::get_history(pool, GUILD, OWNER, 2, 0)
+            .await
+            .unwrap();
+        assert_eq!(
+            first.iter().map(|h| h.new_name.as_str()).collect::<Vec<_>>(),
+            vec!["D", "C"]
+        );
+        let rest = BoosterRenameHistory::get_history(pool, GUILD, OWNER, 2, 2)
+            .await
+            .unwrap();
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].new_name, "B");
+
+        assert_eq!(
+            BoosterRenameHistory::clear_for_user(pool, GUILD, OWNER)
+                .await
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            BoosterRenameHistory::count_for_user(pool, GUILD, OWNER)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            BoosterRenameHistory::count_for_user(pool, GUILD, RECIPIENT)
+                .await
+                .unwrap(),
+            1
+        );
+    }
+
+    async fn fill_roles(pool: &SqlitePool, count: u64) {
+        for i in 0..count {
+            BoosterRole::create(
+                pool,
+                GUILD,
+                UserId::new(1000 + i),
+                RoleId::new(9000 + i),
+                "Role",
+                "#FF0000",
+                None,
+            )
+            .await
+            .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn limit_blocks_new_roles_only_once_full() {
+        let db = test_db().await;
+        let pool = &db.pool;
+
+        GuildBoosterLimit::set(pool, GUILD, 3, OWNER).await.unwrap();
+        fill_roles(pool, 2).await;
+
+        let under = GuildBoosterLimit::check_limit(pool, GUILD)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(under, BoosterLimitUsage { current: 2, max: 3 });
+        assert!(!under.is_full());
+
+        create_role(pool, OLD_ROLE).await;
+        let at = GuildBoosterLimit::check_limit(pool, GUILD)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(at.current, 3);
+        assert!(at.is_full());
+
+        // Lowering the limit below current usage still reports the overflow
+        GuildBoosterLimit::set(pool, GUILD, 2, OWNER).await.unwrap();
+        let over = GuildBoosterLimit::check_limit(pool, GUILD)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(over, BoosterLimitUsage { current: 3, max: 2 });
+        assert!(over.is_full());
+    }
+
+    #[tokio::test]
+    async fn zero_or_missing_limit_means_unlimited() {
+        let db = test_db().await;
+        let pool = &db.pool;
+
+        fill_roles(pool, 2).await;
+        assert_eq!(GuildBoosterLimit::check_limit(pool, GUILD).await.unwrap(), None);
+
+        GuildBoosterLimit::set(pool, GUILD, 0, OWNER).await.unwrap();
+        assert_eq!(GuildBoosterLimit::check_limit(pool, GUILD).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn booster_options_default_until_set() {
+        let db = test_db().await;
+        let pool = &db.pool;
+
+        let defaults = GuildBoosterOptions::get(pool, GUILD).await.unwrap();
+        assert_eq!(defaults, GuildBoosterOptions::default());
+        assert_eq!(defaults.name_too_long(&"x".repeat(100)), None);
+
+        let strict = GuildBoosterOptions {
+            max_name_length: Some(8),
+            allow_secondary_color: false,
+        };
+        GuildBoosterOptions::set(pool, GUILD, strict, OWNER)
+            .await
+            .unwrap();
+        let stored = GuildBoosterOptions::get(pool, GUILD).await.unwrap();
+        assert_eq!(stored, strict);
+
+        // Length is counted in characters, not bytes
+        assert_eq!(stored.name_too_long("Crème"), None);
+        assert_eq!(stored.name_too_long("Ünïcödé!"), None);
+        assert_eq!(stored.name_too_long("Too long!"), Some(9));
+    }
+
+    #[tokio::test]
+    async fn archive_survives_role_deletion_until_restored() {
+        let db = test_db().await;
+        let pool = &db.pool;
+
+        create_role(pool, OLD_ROLE).await;
+        BoosterRole::set_icon(pool, GUILD, OWNER, Some("🔥"))
+            .await
+            .unwrap();
+        let role = BoosterRole::get(pool, GUILD, OWNER).await.unwrap().unwrap();
+
+        BoosterRoleArchive::archive(pool, &role).await.unwrap();
+        assert!(BoosterRole::delete(pool, GUILD, OWNER).await.unwrap());
+
+        let archived = BoosterRoleArchive::get(pool, GUILD, OWNER)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(archived.role_name, "Mine");
+        assert_eq!(archived.primary_color, "#FF0000");
+        assert_eq!(archived.icon_url.as_deref(), Some("🔥"));
+
+        // Archiving again replaces the old snapshot rather than failing
+        BoosterRoleArchive::archive(pool, &role).await.unwrap();
+
+        assert!(BoosterRoleArchive::delete(pool, GUILD, OWNER).await.unwrap());
+        assert!(BoosterRoleArchive::get(pool, GUILD, OWNER)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(!BoosterRoleArchive::delete(pool, GUILD, OWNER).await.unwrap());
+    }
+}
+
+/// A guild's naming rules that members may have to agree to before customizing
+#[derive(Debug, Clone, FromRow)]
+#[allow(dead_code)]
+pub struct GuildNamingRules {
+    pub guild_id: i64,
+    pub rules_text: String,
+    /// Bumped every time the text changes so earlier agreements lapse
+    pub version: i64,
+    pub require_ack: bool,
+    pub set_by: i64,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl GuildNamingRules {
+    pub async fn get(pool: &SqlitePool, guild_id: GuildId) -> Result<Option<Self>, sqlx::Error> {
+        tracing::debug!("Database query: get_naming_rules for guild {}", guild_id);
+
+        sqlx::query_as::<_, Self>("SELECT * FROM guild_naming_rules WHERE guild_id = ?")
+            .bind(guild_id.get() as i64)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Store new rules text and return its version
+    pub async fn set_text(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        rules_text: &str,
+        set_by: UserId,
+    ) -> Result<i64, sqlx::Error> {
+        let version: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO guild_naming_rules (guild_id, rules_text, version, set_by)
+            VALUES (?, ?, 1, ?)
+            ON CONFLICT (guild_id)
+            DO UPDATE SET
+                rules_text = excluded.rules_text,
+                version = guild_naming_rules.version + 1,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            RETURNING version
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(rules_text)
+        .bind(set_by.get() as i64)
+        .fetch_one(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            version = version,
+            set_by = %set_by,
+            "Naming rules updated"
+        );
+
+        Ok(version)
+    }
+
+    /// Returns `false` when the guild has no rules to require
+    pub async fn set_required(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        require_ack: bool,
+        set_by: UserId,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE guild_naming_rules
+            SET require_ack = ?, set_by = ?, updated_at = CURRENT_TIMESTAMP
+            WHERE guild_id = ?
+            "#,
+        )
+        .bind(require_ack)
+        .bind(set_by.get() as i64)
+        .bind(guild_id.get() as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// The latest rules version a member agreed to
+pub struct RulesAcknowledgment;
+
+impl RulesAcknowledgment {
+    pub async fn get_version(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<Option<i64>, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT version FROM rules_acknowledgments WHERE guild_id = ? AND user_id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn record(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        version: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO rules_acknowledgments (guild_id, user_id, version)
+            VALUES (?, ?, ?)
+            ON CONFLICT (guild_id, user_id)
+            DO UPDATE SET
+                version = excluded.version,
+                acknowledged_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .bind(version)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            user_id = %user_id,
+            version = version,
+            "Naming rules acknowledged"
+        );
+
+        Ok(())
+    }
+
+    /// Members who agreed to `version` or later
+    pub async fn count_current(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        version: i64,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM rules_acknowledgments WHERE guild_id = ? AND version >= ?",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(version)
+        .fetch_one(pool)
+        .await
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, FromRow)]
+pub struct ScheduledRoleChange {
+    pub id: i64,
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub new_name: Option<String>,
+    pub new_color: Option<String>,
+    pub scheduled_at: DateTime<Utc>,
+    pub revert_after_secs: Option<i64>,
+    pub revert_at: Option<DateTime<Utc>>,
+    pub prior_name: Option<String>,
+    pub prior_color: Option<String>,
+    pub status: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl ScheduledRoleChange {
+    pub fn status(&self) -> ScheduleStatus {
+        ScheduleStatus::parse(&self.status).unwrap_or(ScheduleStatus::Failed)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        new_name: Option<&str>,
+        new_color: Option<&str>,
+        scheduled_at: &str,
+        revert_after_secs: Option<i64>,
+    ) -> Result<i64, sqlx::Error> {
+        tracing::debug!(
+            "Database query: create_scheduled_change for user {} in guild {}",
+            user_id,
+            guild_id
+        );
+
+        sqlx::query_scalar(
+            r#"
+            INSERT INTO scheduled_role_changes
+                (guild_id, user_id, new_name, new_color, scheduled_at, revert_after_secs, status)
+            VALUES (?, ?, ?, ?, ?, ?, 'pending')
+            RETURNING id
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .bind(new_name)
+        .bind(new_color)
+        .bind(scheduled_at)
+        .bind(revert_after_secs)
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Changes still waiting to apply or revert for one member
+    pub async fn active_for_user(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>(
+            r#"
+            SELECT * FROM scheduled_role_changes
+            WHERE guild_id = ? AND user_id = ?
+              AND (status = 'pending' OR (status = 'applied' AND revert_at IS NOT NULL))
+            ORDER BY scheduled_at
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn active_for_guild(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>(
+            r#"
+            SELECT * FROM scheduled_role_changes
+            WHERE guild_id = ?
+              AND (status = 'pending' OR (status = 'applied' AND revert_at IS NOT NULL))
+            ORDER BY scheduled_at
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Pending changes whose time has come and applied changes due to revert
+    pub async fn due(pool: &SqlitePool, now: &str) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>(
+            r#"
+            SELECT * FROM scheduled_role_changes
+            WHERE (status = 'pending' AND scheduled_at <= ?)
+               OR (status = 'applied' AND revert_at <= ?)
+            ORDER BY scheduled_at
+            "#,
+        )
+        .bind(now)
+        .bind(now)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Cancel a member's pending change, or the revert of an applied one
+    pub async fn cancel(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        id: i64,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE scheduled_role_changes
+            SET status = 'cancelled', updated_at = CURRENT_TIMESTAMP
+            WHERE id = ? AND guild_id = ? AND user_id = ?
+              AND (status = 'pending' OR (status = 'applied' AND revert_at IS NOT NULL))
+            "#,
+        )
+        .bind(id)
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record what the role looked like before the change and when to revert.
+    /// Only moves a still-pending change, so a cancel that raced the
+    /// scheduler wins.
+    pub async fn mark_applied(
+        pool: &SqlitePool,
+        id: i64,
+        prior_name: &str,
+        prior_color: &str,
+        revert_at: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE scheduled_role_changes
+            SET status = 'applied', prior_name = ?, prior_color = ?, revert_at = ?,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = ? AND status = 'pending'
+            "#,
+        )
+        .bind(prior_name)
+        .bind(prior_color)
+        .bind(revert_at)
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn set_status(
+        pool: &SqlitePool,
+        id: i64,
+        status: ScheduleStatus,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE scheduled_role_changes SET status = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(status.as_str())
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// A booster role's look, kept after a lapsed boost so it can be recreated
+#[derive(Debug, Clone, FromRow)]
+pub struct BoosterRoleArchive {
+    #[allow(dead_code)]
+    pub guild_id: i64,
+    #[allow(dead_code)]
+    pub user_id: i64,
+    pub role_name: String,
+    pub primary_color: String,
+    pub secondary_color: Option<String>,
+    pub icon_url: Option<String>,
+    pub archived_at: Option<DateTime<Utc>>,
+}
+
+impl BoosterRoleArchive {
+    /// Keep the role's name, colors and icon, replacing any older archive
+    pub async fn archive(pool: &SqlitePool, role: &BoosterRole) -> Result<(), sqlx::Error> {
+        tracing::debug!(
+            "Database query: archive_booster_role for user {} in guild {}",
+            role.user_id,
+            role.guild_id
+        );
+
+        sqlx::query(
+            r#"
+            INSERT INTO booster_role_archive
+                (guild_id, user_id, role_name, primary_color, secondary_color, icon_url)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT (guild_id, user_id)
+            DO UPDATE SET
+                role_name = excluded.role_name,
+                primary_color = excluded.primary_color,
+                secondary_color = excluded.secondary_color,
+                icon_url = excluded.icon_url,
+                archived_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(role.guild_id)
+        .bind(role.user_id)
+        .bind(&role.role_name)
+        .bind(&role.primary_color)
+        .bind(&role.secondary_color)
+        .bind(&role.icon_url)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<Option<BoosterRoleArchive>, sqlx::Error> {
+        tracing::debug!(
+            "Database query: get_booster_role_archive for user {} in guild {}",
+            user_id,
+            guild_id
+        );
+
+        sqlx::query_as::<_, BoosterRoleArchive>(
+            r#"
+            SELECT guild_id, user_id, role_name, primary_color, secondary_color, icon_url,
+                   archived_at
+            FROM booster_role_archive
+            WHERE guild_id = ? AND user_id = ?
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Drop the archive once the role has been restored
+    pub async fn delete(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<bool, sqlx::Error> {
+        tracing::debug!(
+            "Database query: delete_booster_role_archive for user {} in guild {}",
+            user_id,
+            guild_id
+        );
+
+        let result =
+            sqlx::query("DELETE FROM booster_role_archive WHERE guild_id = ? AND user_id = ?")
+                .bind(guild_id.get() as i64)
+                .bind(user_id.get() as i64)
+                .execute(pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,198 @@
+use crate::utils::embed_builder::MAX_AUTOCOMPLETE_CHOICES;
+use chrono::{DateTime, Utc};
+use serenity::all::{GuildId, UserId};
+use sqlx::{FromRow, SqlitePool};
+
+/// A name/color combo admins predefine for members to pick from
+#[derive(Debug, Clone, FromRow)]
+pub struct BoosterRoleTemplate {
+    #[allow(dead_code)]
+    pub id: i64,
+    #[allow(dead_code)]
+    pub guild_id: i64,
+    pub name: String,
+    pub color: String,
+    pub icon_url: Option<String>,
+    pub created_by: i64,
+    #[allow(dead_code)]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl BoosterRoleTemplate {
+    /// Returns `false` if the guild already has a template with that name
+    pub async fn add(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        name: &str,
+        color: &str,
+        icon_url: Option<&str>,
+        created_by: UserId,
+    ) -> Result<bool, sqlx::Error> {
+        tracing::debug!(
+            "Database query: add_booster_role_template '{}' for guild {}",
+            name,
+            guild_id
+        );
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO booster_role_templates (guild_id, name, color, icon_url, created_by)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT (guild_id, name) DO NOTHING
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(name)
+        .bind(color)
+        .bind(icon_url)
+        .bind(created_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn remove(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        name: &str,
+    ) -> Result<bool, sqlx::Error> {
+        tracing::debug!(
+            "Database query: remove_booster_role_template '{}' for guild {}",
+            name,
+            guild_id
+        );
+
+        let result =
+            sqlx::query("DELETE FROM booster_role_templates WHERE guild_id = ? AND name = ?")
+                .bind(guild_id.get() as i64)
+                .bind(name)
+                .execute(pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Names match case-insensitively
+    pub async fn get(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        name: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as::<_, BoosterRoleTemplate>(
+            "SELECT * FROM booster_role_templates WHERE guild_id = ? AND name = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(name)
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn list(pool: &SqlitePool, guild_id: GuildId) -> Result<Vec<Self>, sqlx::Error> {
+        tracing::debug!(
+            "Database query: list_booster_role_templates for guild {}",
+            guild_id
+        );
+
+        sqlx::query_as::<_, BoosterRoleTemplate>(
+            "SELECT * FROM booster_role_templates WHERE guild_id = ? ORDER BY name",
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Template names containing `partial`, for autocomplete
+    pub async fn search(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        partial: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        // Typed wildcards should match literally
+        let escaped = partial
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+
+        sqlx::query_scalar(
+            r#"
+            SELECT name FROM booster_role_templates
+            WHERE guild_id = ? AND name LIKE ? ESCAPE '\'
+            ORDER BY name
+            LIMIT ?
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(format!("%{}%", escaped))
+        .bind(MAX_AUTOCOMPLETE_CHOICES as i64)
+        .fetch_all(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::database::init_memory_database;
+
+    #[tokio::test]
+    async fn test_templates_are_unique_per_guild_and_searchable() {
+        let pool = init_memory_database().await.unwrap();
+        let (guild, other) = (GuildId::new(1), GuildId::new(2));
+        let admin = UserId::new(3);
+
+        assert!(
+            BoosterRoleTemplate::add(&pool, guild, "Sunset", "#FF8800", None, admin)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !BoosterRoleTemplate::add(&pool, guild, "sunset", "#000000", None, admin)
+                .await
+                .unwrap()
+        );
+        assert!(
+            BoosterRoleTemplate::add(&pool, other, "Sunset", "#000000", None, admin)
+                .await
+                .unwrap()
+        );
+        for i in 0..30 {
+            let name = format!("Ocean {:02}", i);
+            BoosterRoleTemplate::add(&pool, guild, &name, "#0000FF", None, admin)
+                .await
+                .unwrap();
+        }
+        BoosterRoleTemplate::add(&pool, guild, "100%_real", "#00FF00", None, admin)
+            .await
+            .unwrap();
+
+        let found = BoosterRoleTemplate::get(&pool, guild, "SUNSET")
+            .await
+            .unwrap();
+        assert_eq!(found.unwrap().color, "#FF8800");
+
+        let ocean = BoosterRoleTemplate::search(&pool, guild, "ocean")
+            .await
+            .unwrap();
+        assert_eq!(ocean.len(), 25);
+        assert_eq!(ocean[0], "Ocean 00");
+
+        // Wildcards in the typed text don't match everything
+        let literal = BoosterRoleTemplate::search(&pool, guild, "%_")
+            .await
+            .unwrap();
+        assert_eq!(literal, vec!["100%_real".to_string()]);
+
+        assert!(BoosterRoleTemplate::remove(&pool, guild, "sunset")
+            .await
+            .unwrap());
+        assert!(BoosterRoleTemplate::get(&pool, guild, "Sunset")
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            BoosterRoleTemplate::list(&pool, other).await.unwrap().len(),
+            1
+        );
+    }
+}
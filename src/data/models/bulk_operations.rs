@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use serenity::all::{ChannelId, GuildId, UserId};
+use sqlx::{FromRow, SqlitePool};
+
+/// A long-running bulk command, recorded so its outcome is still reported
+/// if the bot restarts before it finishes
+#[allow(dead_code)]
+#[derive(Debug, Clone, FromRow)]
+pub struct BulkOperation {
+    pub id: i64,
+    pub guild_id: i64,
+    pub channel_id: i64,
+    pub kind: String,
+    pub requested_by: i64,
+    pub status: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl BulkOperation {
+    pub async fn start(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        kind: &str,
+        requested_by: UserId,
+    ) -> Result<i64, sqlx::Error> {
+        tracing::debug!(
+            "Database query: start_bulk_operation {} in guild {}",
+            kind,
+            guild_id
+        );
+
+        sqlx::query_scalar(
+            r#"
+            INSERT INTO bulk_operations (guild_id, channel_id, kind, requested_by, status)
+            VALUES (?, ?, ?, ?, 'running')
+            RETURNING id
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(channel_id.get() as i64)
+        .bind(kind)
+        .bind(requested_by.get() as i64)
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn finish(pool: &SqlitePool, id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE bulk_operations SET status = 'done', finished_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Operations a restart interrupted
+    pub async fn running(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>(
+            "SELECT * FROM bulk_operations WHERE status = 'running' ORDER BY id",
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use serenity::all::GuildId;
+use sqlx::{FromRow, SqlitePool};
+
+/// Runs older than this are pruned as new ones are recorded
+const CLEANUP_RUN_RETENTION_DAYS: i64 = 90;
+
+/// One pass of the scheduled orphan cleanup over a guild
+#[derive(Debug, Clone, FromRow)]
+pub struct CleanupRun {
+    pub orphans_removed: i64,
+    pub ran_at: DateTime<Utc>,
+}
+
+impl CleanupRun {
+    /// Shown by `/boosterrole cleanup`
+    pub fn describe(run: Option<&Self>) -> String {
+        let Some(run) = run else {
+            return "No automatic cleanup has run yet.".to_string();
+        };
+
+        format!(
+            "Last automatic cleanup: <t:{}:R>, removed {} role(s)",
+            run.ran_at.timestamp(),
+            run.orphans_removed
+        )
+    }
+
+    pub async fn record(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        orphans_removed: usize,
+    ) -> Result<(), sqlx::Error> {
+        tracing::debug!(
+            "Database query: record_cleanup_run ({} removed) for guild {}",
+            orphans_removed,
+            guild_id
+        );
+
+        sqlx::query("INSERT INTO cleanup_runs (guild_id, orphans_removed) VALUES (?, ?)")
+            .bind(guild_id.get() as i64)
+            .bind(orphans_removed as i64)
+            .execute(pool)
+            .await?;
+
+        sqlx::query("DELETE FROM cleanup_runs WHERE guild_id = ? AND ran_at < datetime('now', ?)")
+            .bind(guild_id.get() as i64)
+            .bind(format!("-{} days", CLEANUP_RUN_RETENTION_DAYS))
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn latest(pool: &SqlitePool, guild_id: GuildId) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            SELECT orphans_removed, ran_at FROM cleanup_runs
+            WHERE guild_id = ?
+            ORDER BY ran_at DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_optional(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_describe_last_run() {
+        let run = CleanupRun {
+            orphans_removed: 3,
+            ran_at: chrono::Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+        };
+        assert_eq!(
+            CleanupRun::describe(Some(&run)),
+            "Last automatic cleanup: <t:1704067200:R>, removed 3 role(s)"
+        );
+        assert!(CleanupRun::describe(None).starts_with("No automatic cleanup"));
+    }
+}
@@ -0,0 +1,265 @@
+use crate::utils::color_rotation::{decode_palette, encode_palette, RotationSettings};
+use chrono::{DateTime, Utc};
+use serenity::all::{GuildId, UserId};
+use sqlx::{FromRow, SqlitePool};
+
+/// A member's opted-in color rotation ("party mode")
+#[derive(Debug, Clone, FromRow)]
+pub struct ColorRotation {
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub palette: String,
+    pub interval_secs: i64,
+    /// Palette position applied at the next run
+    pub next_index: i64,
+    #[allow(dead_code)]
+    pub next_run_at: DateTime<Utc>,
+}
+
+impl ColorRotation {
+    pub fn palette(&self) -> Vec<u32> {
+        decode_palette(&self.palette)
+    }
+
+    pub async fn get(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>(
+            r#"
+            SELECT guild_id, user_id, palette, interval_secs, next_index, next_run_at
+            FROM booster_color_rotations
+            WHERE guild_id = ? AND user_id = ?
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Start or replace a member's rotation
+    pub async fn enable(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        palette: &[u32],
+        interval_secs: i64,
+        next_index: i64,
+        next_run_at: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO booster_color_rotations
+                (guild_id, user_id, palette, interval_secs, next_index, next_run_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT (guild_id, user_id)
+            DO UPDATE SET
+                palette = excluded.palette,
+                interval_secs = excluded.interval_secs,
+                next_index = excluded.next_index,
+                next_run_at = excluded.next_run_at,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .bind(encode_palette(palette))
+        .bind(interval_secs)
+        .bind(next_index)
+        .bind(next_run_at)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            user_id = %user_id,
+            colors = palette.len(),
+            interval_secs,
+            "Color rotation enabled"
+        );
+
+        Ok(())
+    }
+
+    /// Returns whether the member had a rotation to stop
+    pub async fn disable(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<bool, sqlx::Error> {
+        let result =
+            sqlx::query("DELETE FROM booster_color_rotations WHERE guild_id = ? AND user_id = ?")
+                .bind(guild_id.get() as i64)
+                .bind(user_id.get() as i64)
+                .execute(pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn count_for_guild(pool: &SqlitePool, guild_id: GuildId) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM booster_color_rotations WHERE guild_id = ?")
+            .bind(guild_id.get() as i64)
+            .fetch_one(pool)
+            .await
+    }
+
+    /// Rotations whose next color is due at or before `now` (a stored timestamp)
+    pub async fn due(pool: &SqlitePool, now: &str) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>(
+            r#"
+            SELECT guild_id, user_id, palette, interval_secs, next_index, next_run_at
+            FROM booster_color_rotations
+            WHERE next_run_at <= ?
+            ORDER BY next_run_at
+            "#,
+        )
+        .bind(now)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Record a step: which color comes next, and when
+    pub async fn advance(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        next_index: i64,
+        next_run_at: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE booster_color_rotations
+            SET next_index = ?, next_run_at = ?, updated_at = CURRENT_TIMESTAMP
+            WHERE guild_id = ? AND user_id = ?
+            "#,
+        )
+        .bind(next_index)
+        .bind(next_run_at)
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// A guild's minimum rotation interval and cap on rotating members
+pub struct GuildRotationSettings;
+
+impl GuildRotationSettings {
+    pub async fn get(pool: &SqlitePool, guild_id: GuildId) -> Result<RotationSettings, sqlx::Error> {
+        let row: Option<(i64, i64)> = sqlx::query_as(
+            "SELECT min_interval_hours, max_members FROM guild_rotation_settings WHERE guild_id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row
+            .map(|(min_interval_hours, max_members)| RotationSettings {
+                min_interval_hours,
+                max_members,
+            })
+            .unwrap_or_default())
+    }
+
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        settings: RotationSettings,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_rotation_settings (guild_id, min_interval_hours, max_members, set_by)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (guild_id)
+            DO UPDATE SET
+                min_interval_hours = excluded.min_interval_hours,
+                max_members = excluded.max_members,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(settings.min_interval_hours)
+        .bind(settings.max_members)
+        .bind(set_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            min_interval_hours = settings.min_interval_hours,
+            max_members = settings.max_members,
+            set_by = %set_by,
+            "Color rotation settings updated"
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::database::init_memory_database;
+
+    #[tokio::test]
+    async fn test_due_rotations_advance_until_disabled() {
+        let pool = init_memory_database().await.unwrap();
+        let guild = GuildId::new(1);
+        let member = UserId::new(2);
+        let palette = [0xFF0000, 0x0000FF];
+
+        ColorRotation::enable(&pool, guild, member, &palette, 6 * 3600, 1, "2025-01-01T06:00:00Z")
+            .await
+            .unwrap();
+        assert_eq!(ColorRotation::count_for_guild(&pool, guild).await.unwrap(), 1);
+        assert!(ColorRotation::due(&pool, "2025-01-01T05:59:59Z")
+            .await
+            .unwrap()
+            .is_empty());
+
+        let due = ColorRotation::due(&pool, "2025-01-01T06:00:00Z").await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].palette(), palette);
+        assert_eq!(due[0].next_index, 1);
+
+        ColorRotation::advance(&pool, guild, member, 0, "2025-01-01T12:00:00Z")
+            .await
+            .unwrap();
+        assert!(ColorRotation::due(&pool, "2025-01-01T11:00:00Z")
+            .await
+            .unwrap()
+            .is_empty());
+
+        assert!(ColorRotation::disable(&pool, guild, member).await.unwrap());
+        assert!(!ColorRotation::disable(&pool, guild, member).await.unwrap());
+        assert!(ColorRotation::get(&pool, guild, member).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_settings_default_until_set() {
+        let pool = init_memory_database().await.unwrap();
+        let guild = GuildId::new(1);
+
+        assert_eq!(
+            GuildRotationSettings::get(&pool, guild).await.unwrap(),
+            RotationSettings::default()
+        );
+
+        let custom = RotationSettings {
+            min_interval_hours: 1,
+            max_members: 3,
+        };
+        GuildRotationSettings::set(&pool, guild, custom, UserId::new(9))
+            .await
+            .unwrap();
+        assert_eq!(GuildRotationSettings::get(&pool, guild).await.unwrap(), custom);
+    }
+}
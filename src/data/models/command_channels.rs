@@ -0,0 +1,103 @@
+use crate::utils::command_channels::CommandGroup;
+use serenity::all::{ChannelId, GuildId, UserId};
+use sqlx::SqlitePool;
+
+/// Channels a guild confines each command group to
+pub struct GuildCommandChannel;
+
+impl GuildCommandChannel {
+    pub async fn add(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        group: CommandGroup,
+        added_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        tracing::debug!(
+            "Database query: add_command_channel {} for {} in guild {}",
+            channel_id,
+            group.as_str(),
+            guild_id
+        );
+
+        sqlx::query(
+            r#"
+            INSERT INTO guild_command_channels (guild_id, channel_id, command_group, added_by)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (guild_id, channel_id, command_group)
+            DO UPDATE SET added_by = excluded.added_by
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(channel_id.get() as i64)
+        .bind(group.as_str())
+        .bind(added_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Remove a channel from one group, or from every group when `group` is `None`
+    pub async fn remove(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        group: Option<CommandGroup>,
+    ) -> Result<u64, sqlx::Error> {
+        tracing::debug!(
+            "Database query: remove_command_channel {} in guild {}",
+            channel_id,
+            guild_id
+        );
+
+        let result = match group {
+            Some(group) => {
+                sqlx::query(
+                    "DELETE FROM guild_command_channels WHERE guild_id = ? AND channel_id = ? AND command_group = ?",
+                )
+                .bind(guild_id.get() as i64)
+                .bind(channel_id.get() as i64)
+                .bind(group.as_str())
+                .execute(pool)
+                .await?
+            }
+            None => {
+                sqlx::query(
+                    "DELETE FROM guild_command_channels WHERE guild_id = ? AND channel_id = ?",
+                )
+                .bind(guild_id.get() as i64)
+                .bind(channel_id.get() as i64)
+                .execute(pool)
+                .await?
+            }
+        };
+
+        Ok(result.rows_affected())
+    }
+
+    /// Every (group, channel) pair for a guild, oldest first so the first
+    /// channel added is the one members are pointed to
+    pub async fn list(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<Vec<(CommandGroup, u64)>, sqlx::Error> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT command_group, channel_id FROM guild_command_channels
+            WHERE guild_id = ?
+            ORDER BY created_at, rowid
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(group, channel)| {
+                CommandGroup::parse(&group).map(|group| (group, channel as u64))
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,87 @@
+use serenity::all::{GuildId, UserId};
+use sqlx::SqlitePool;
+
+/// Per-member cooldowns a guild has put on commands, by qualified name
+pub struct GuildCommandCooldown;
+
+impl GuildCommandCooldown {
+    /// Set or replace the cooldown on a command
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        command_name: &str,
+        seconds: u64,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        tracing::debug!(
+            "Database query: set_command_cooldown {} to {}s in guild {}",
+            command_name,
+            seconds,
+            guild_id
+        );
+
+        sqlx::query(
+            r#"
+            INSERT INTO guild_command_cooldowns (guild_id, command_name, seconds, set_by)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (guild_id, command_name) DO UPDATE SET
+                seconds = excluded.seconds,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(command_name)
+        .bind(seconds as i64)
+        .bind(set_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns `false` if the command had no cooldown
+    pub async fn remove(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        command_name: &str,
+    ) -> Result<bool, sqlx::Error> {
+        tracing::debug!(
+            "Database query: remove_command_cooldown {} in guild {}",
+            command_name,
+            guild_id
+        );
+
+        let result = sqlx::query(
+            "DELETE FROM guild_command_cooldowns WHERE guild_id = ? AND command_name = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(command_name)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Every cooldown for a guild as (command name, seconds), alphabetically
+    pub async fn list(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<Vec<(String, u64)>, sqlx::Error> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT command_name, seconds FROM guild_command_cooldowns
+            WHERE guild_id = ?
+            ORDER BY command_name
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(command, seconds)| (command, seconds.max(0) as u64))
+            .collect())
+    }
+}
@@ -0,0 +1,162 @@
+use crate::utils::command_usage::UsageKey;
+use chrono::NaiveDate;
+use serenity::all::GuildId;
+use sqlx::SqlitePool;
+
+/// Days of usage kept; `/settings usage` never looks further back
+pub const COMMAND_USAGE_RETENTION_DAYS: i64 = 90;
+
+/// Daily command counts per guild
+pub struct CommandUsage;
+
+impl CommandUsage {
+    /// Add a batch of buffered counts in one transaction
+    pub async fn add_batch(
+        pool: &SqlitePool,
+        batch: &[(UsageKey, u64)],
+    ) -> Result<(), sqlx::Error> {
+        tracing::debug!("Database query: add_command_usage ({} rows)", batch.len());
+
+        let mut tx = pool.begin().await?;
+        for (key, count) in batch {
+            sqlx::query(
+                r#"
+                INSERT INTO command_usage (guild_id, command, day, count)
+                VALUES (?, ?, ?, ?)
+                ON CONFLICT (guild_id, command, day)
+                DO UPDATE SET count = count + excluded.count
+                "#,
+            )
+            .bind(key.guild_id.get() as i64)
+            .bind(&key.command)
+            .bind(key.day.to_string())
+            .bind(*count as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// The guild's most used commands from `since` on, busiest first
+    pub async fn top_for_guild(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        since: NaiveDate,
+        limit: i64,
+    ) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        tracing::debug!("Database query: top_command_usage for guild {}", guild_id);
+
+        sqlx::query_as(
+            r#"
+            SELECT command, SUM(count) AS uses
+            FROM command_usage
+            WHERE guild_id = ? AND day >= ?
+            GROUP BY command
+            ORDER BY uses DESC, command
+            LIMIT ?
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(since.to_string())
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Every command use in the guild from `since` on
+    pub async fn total_for_guild(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        since: NaiveDate,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT COALESCE(SUM(count), 0) FROM command_usage WHERE guild_id = ? AND day >= ?",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(since.to_string())
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn prune(pool: &SqlitePool, before: NaiveDate) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM command_usage WHERE day < ?")
+            .bind(before.to_string())
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::database::init_memory_database;
+
+    fn usage(guild: u64, command: &str, day: NaiveDate, count: u64) -> (UsageKey, u64) {
+        let key = UsageKey {
+            guild_id: GuildId::new(guild),
+            command: command.to_string(),
+            day,
+        };
+        (key, count)
+    }
+
+    #[tokio::test]
+    async fn test_top_commands_sum_days_within_the_window() {
+        let pool = init_memory_database().await.unwrap();
+        let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let days_ago = |n| today - chrono::Duration::days(n);
+
+        CommandUsage::add_batch(
+            &pool,
+            &[
+                usage(1, "boosterrole color", today, 3),
+                usage(1, "boosterrole color", days_ago(2), 4),
+                usage(1, "help", today, 5),
+                usage(1, "ping", days_ago(2), 1),
+                // Outside a week's window
+                usage(1, "ping", days_ago(30), 50),
+                // Another guild
+                usage(2, "help", today, 100),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let week = days_ago(6);
+        assert_eq!(
+            CommandUsage::top_for_guild(&pool, GuildId::new(1), week, 10)
+                .await
+                .unwrap(),
+            vec![
+                ("boosterrole color".to_string(), 7),
+                ("help".to_string(), 5),
+                ("ping".to_string(), 1),
+            ]
+        );
+        assert_eq!(
+            CommandUsage::top_for_guild(&pool, GuildId::new(1), week, 1)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            CommandUsage::total_for_guild(&pool, GuildId::new(1), week)
+                .await
+                .unwrap(),
+            13
+        );
+
+        assert_eq!(CommandUsage::prune(&pool, days_ago(6)).await.unwrap(), 1);
+        assert_eq!(
+            CommandUsage::total_for_guild(&pool, GuildId::new(1), days_ago(365))
+                .await
+                .unwrap(),
+            13
+        );
+    }
+}
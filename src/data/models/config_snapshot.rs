@@ -0,0 +1,184 @@
+use super::GuildSettingsSnapshot;
+use serde_json::Value;
+use chrono::{DateTime, Utc};
+use serenity::all::{GuildId, UserId};
+use sqlx::{FromRow, SqlitePool};
+
+/// Snapshots kept per guild; the oldest are evicted beyond this
+pub const MAX_SNAPSHOTS_PER_GUILD: i64 = 20;
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, FromRow)]
+pub struct GuildConfigSnapshot {
+    pub id: i64,
+    pub guild_id: i64,
+    pub label: Option<String>,
+    pub data: String,
+    pub created_by: i64,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl GuildConfigSnapshot {
+    /// Serialize every guild setting into one JSON document
+    pub async fn capture(pool: &SqlitePool, guild_id: GuildId) -> Result<Value, sqlx::Error> {
+        tracing::debug!("Database query: capture_config for guild {}", guild_id);
+
+        Ok(GuildSettingsSnapshot::load(pool, guild_id).await?.to_json())
+    }
+
+    /// Store a snapshot, evicting the oldest beyond the per-guild cap
+    pub async fn create(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        label: Option<&str>,
+        data: &Value,
+        created_by: UserId,
+    ) -> Result<i64, sqlx::Error> {
+        tracing::debug!(
+            "Database query: create_config_snapshot for guild {}",
+            guild_id
+        );
+
+        let mut tx = pool.begin().await?;
+
+        let id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO guild_config_snapshots (guild_id, label, data, created_by)
+            VALUES (?, ?, ?, ?)
+            RETURNING id
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(label)
+        .bind(data.to_string())
+        .bind(created_by.get() as i64)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let evicted = sqlx::query(
+            r#"
+            DELETE FROM guild_config_snapshots
+            WHERE guild_id = ? AND id NOT IN (
+                SELECT id FROM guild_config_snapshots
+                WHERE guild_id = ?
+                ORDER BY id DESC
+                LIMIT ?
+            )
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(guild_id.get() as i64)
+        .bind(MAX_SNAPSHOTS_PER_GUILD)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        tx.commit().await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            snapshot_id = id,
+            created_by = %created_by,
+            evicted = evicted,
+            "Config snapshot created"
+        );
+
+        Ok(id)
+    }
+
+    /// Capture and store the current configuration before a bulk change
+    pub async fn create_automatic(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        reason: &str,
+        created_by: UserId,
+    ) -> Result<i64, sqlx::Error> {
+        let data = Self::capture(pool, guild_id).await?;
+        Self::create(
+            pool,
+            guild_id,
+            Some(&format!("auto: {}", reason)),
+            &data,
+            created_by,
+        )
+        .await
+    }
+
+    /// Newest first
+    pub async fn list(pool: &SqlitePool, guild_id: GuildId) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>(
+            "SELECT * FROM guild_config_snapshots WHERE guild_id = ? ORDER BY id DESC",
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        id: i64,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>(
+            "SELECT * FROM guild_config_snapshots WHERE guild_id = ? AND id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub fn parsed(&self) -> Result<Value, serde_json::Error> {
+        serde_json::from_str(&self.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::database::test_db;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_oldest_snapshots_are_evicted() {
+        let db = test_db().await;
+        let guild = GuildId::new(100);
+        let other_guild = GuildId::new(200);
+        let user = UserId::new(1);
+
+        let other_id = GuildConfigSnapshot::create(&db.pool, other_guild, None, &json!({}), user)
+            .await
+            .unwrap();
+
+        let mut ids = Vec::new();
+        for i in 0..(MAX_SNAPSHOTS_PER_GUILD + 3) {
+            let data = GuildConfigSnapshot::capture(&db.pool, guild).await.unwrap();
+            let label = format!("snapshot {}", i);
+            ids.push(
+                GuildConfigSnapshot::create(&db.pool, guild, Some(&label), &data, user)
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        let kept = GuildConfigSnapshot::list(&db.pool, guild).await.unwrap();
+        assert_eq!(kept.len() as i64, MAX_SNAPSHOTS_PER_GUILD);
+        assert_eq!(kept[0].id, *ids.last().unwrap());
+        assert!(GuildConfigSnapshot::get(&db.pool, guild, ids[2])
+            .await
+            .unwrap()
+            .is_none());
+        assert!(GuildConfigSnapshot::get(&db.pool, guild, ids[3])
+            .await
+            .unwrap()
+            .is_some());
+        assert!(GuildConfigSnapshot::get(&db.pool, other_guild, other_id)
+            .await
+            .unwrap()
+            .is_some());
+
+        let parsed = kept[0].parsed().unwrap();
+        assert_eq!(parsed["eligibility"], json!("boosters_only"));
+        assert_eq!(parsed["next_steps"], json!(true));
+    }
+}
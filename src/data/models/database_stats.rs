@@ -0,0 +1,79 @@
+use sqlx::{Row, SqlitePool};
+
+/// Booster tables whose sizes `cache_status` reports
+pub const BOOSTER_TABLES: &[&str] = &[
+    "booster_roles",
+    "booster_role_links",
+    "booster_role_locks",
+    "booster_role_shares",
+    "booster_role_templates",
+    "booster_rename_history",
+    "booster_award_grants",
+];
+
+/// Size figures for the bot's database
+pub struct DatabaseStats;
+
+impl DatabaseStats {
+    /// Bytes used by the main database file, not counting the WAL
+    pub async fn file_size(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Row count for each of [`BOOSTER_TABLES`], in one round trip
+    pub async fn booster_row_counts(
+        pool: &SqlitePool,
+    ) -> Result<Vec<(&'static str, i64)>, sqlx::Error> {
+        tracing::debug!("Database query: booster_row_counts");
+
+        // Only the fixed table list is ever interpolated
+        let counts = BOOSTER_TABLES
+            .iter()
+            .map(|table| format!("(SELECT COUNT(*) FROM {})", table))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let row = sqlx::query(&format!("SELECT {}", counts))
+            .fetch_one(pool)
+            .await?;
+
+        BOOSTER_TABLES
+            .iter()
+            .enumerate()
+            .map(|(i, table)| Ok((*table, row.try_get::<i64, _>(i)?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::database::init_memory_database;
+    use serenity::all::{GuildId, RoleId, UserId};
+
+    #[tokio::test]
+    async fn test_counts_every_booster_table() {
+        let pool = init_memory_database().await.unwrap();
+        crate::data::models::BoosterRole::create(
+            &pool,
+            GuildId::new(1),
+            UserId::new(2),
+            RoleId::new(3),
+            "Role",
+            "#FF0000",
+            None,
+        )
+        .await
+        .unwrap();
+
+        let counts = DatabaseStats::booster_row_counts(&pool).await.unwrap();
+        assert_eq!(counts.len(), BOOSTER_TABLES.len());
+        assert_eq!(counts[0], ("booster_roles", 1));
+        assert!(counts[1..].iter().all(|(_, count)| *count == 0));
+
+        assert!(DatabaseStats::file_size(&pool).await.unwrap() > 0);
+    }
+}
@@ -0,0 +1,73 @@
+use serenity::all::{GuildId, UserId};
+use sqlx::SqlitePool;
+
+/// Commands a guild has switched off, by qualified name
+pub struct GuildDisabledCommand;
+
+impl GuildDisabledCommand {
+    /// Returns `false` if the command was already disabled
+    pub async fn disable(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        command_name: &str,
+        disabled_by: UserId,
+    ) -> Result<bool, sqlx::Error> {
+        tracing::debug!(
+            "Database query: disable_command {} in guild {}",
+            command_name,
+            guild_id
+        );
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO guild_disabled_commands (guild_id, command_name, disabled_by)
+            VALUES (?, ?, ?)
+            ON CONFLICT (guild_id, command_name) DO NOTHING
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(command_name)
+        .bind(disabled_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Returns `false` if the command wasn't disabled
+    pub async fn enable(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        command_name: &str,
+    ) -> Result<bool, sqlx::Error> {
+        tracing::debug!(
+            "Database query: enable_command {} in guild {}",
+            command_name,
+            guild_id
+        );
+
+        let result = sqlx::query(
+            "DELETE FROM guild_disabled_commands WHERE guild_id = ? AND command_name = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(command_name)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Every disabled command name for a guild, alphabetically
+    pub async fn list(pool: &SqlitePool, guild_id: GuildId) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT command_name FROM guild_disabled_commands
+            WHERE guild_id = ?
+            ORDER BY command_name
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,129 @@
+use crate::utils::embed_theme::{EmbedTheme, ThemeSlot};
+use serenity::all::{GuildId, UserId};
+use sqlx::{FromRow, SqlitePool};
+
+#[derive(FromRow)]
+struct ThemeRow {
+    success_color: Option<i64>,
+    error_color: Option<i64>,
+    info_color: Option<i64>,
+    primary_color: Option<i64>,
+}
+
+/// Per-guild embed colors that replace the built-in palette
+pub struct GuildEmbedTheme;
+
+impl GuildEmbedTheme {
+    /// The guild's overrides; an unthemed guild gets the default theme
+    pub async fn get(pool: &SqlitePool, guild_id: GuildId) -> Result<EmbedTheme, sqlx::Error> {
+        tracing::debug!("Database query: get_embed_theme for guild {}", guild_id);
+
+        let row: Option<ThemeRow> = sqlx::query_as(
+            r#"
+            SELECT success_color, error_color, info_color, primary_color
+            FROM guild_embed_themes
+            WHERE guild_id = ?
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        let color = |value: Option<i64>| value.map(|c| c as u32);
+        Ok(row
+            .map(|row| EmbedTheme {
+                success: color(row.success_color),
+                error: color(row.error_color),
+                info: color(row.info_color),
+                primary: color(row.primary_color),
+            })
+            .unwrap_or_default())
+    }
+
+    pub async fn set_color(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        slot: ThemeSlot,
+        color: u32,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        tracing::debug!(
+            "Database query: set_embed_theme {} for guild {} to {:#08x}",
+            slot.as_str(),
+            guild_id,
+            color
+        );
+
+        // Column names come from a fixed list, never from input
+        let column = match slot {
+            ThemeSlot::Success => "success_color",
+            ThemeSlot::Error => "error_color",
+            ThemeSlot::Info => "info_color",
+            ThemeSlot::Primary => "primary_color",
+        };
+        let statement = format!(
+            r#"
+            INSERT INTO guild_embed_themes (guild_id, {column}, updated_by)
+            VALUES (?, ?, ?)
+            ON CONFLICT (guild_id)
+            DO UPDATE SET
+                {column} = excluded.{column},
+                updated_by = excluded.updated_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#
+        );
+
+        sqlx::query(&statement)
+            .bind(guild_id.get() as i64)
+            .bind(color as i64)
+            .bind(set_by.get() as i64)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Go back to the built-in palette. Returns `false` if nothing was themed.
+    pub async fn reset(pool: &SqlitePool, guild_id: GuildId) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM guild_embed_themes WHERE guild_id = ?")
+            .bind(guild_id.get() as i64)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::database::init_memory_database;
+
+    #[tokio::test]
+    async fn test_slots_are_set_independently_and_reset() {
+        let pool = init_memory_database().await.unwrap();
+        let guild = GuildId::new(1);
+        let admin = UserId::new(2);
+
+        assert!(GuildEmbedTheme::get(&pool, guild).await.unwrap() == EmbedTheme::default());
+
+        GuildEmbedTheme::set_color(&pool, guild, ThemeSlot::Success, 0x00FF00, admin)
+            .await
+            .unwrap();
+        GuildEmbedTheme::set_color(&pool, guild, ThemeSlot::Primary, 0x112233, admin)
+            .await
+            .unwrap();
+        GuildEmbedTheme::set_color(&pool, guild, ThemeSlot::Success, 0x0000FF, admin)
+            .await
+            .unwrap();
+
+        let theme = GuildEmbedTheme::get(&pool, guild).await.unwrap();
+        assert_eq!(theme.success, Some(0x0000FF));
+        assert_eq!(theme.primary, Some(0x112233));
+        assert_eq!(theme.error, None);
+
+        assert!(GuildEmbedTheme::reset(&pool, guild).await.unwrap());
+        assert!(!GuildEmbedTheme::reset(&pool, guild).await.unwrap());
+        assert!(GuildEmbedTheme::get(&pool, guild).await.unwrap() == EmbedTheme::default());
+    }
+}
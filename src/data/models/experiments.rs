@@ -0,0 +1,114 @@
+use crate::utils::experiments::{VariantCounts, VariantKey, CONVERSION_WINDOW_DAYS};
+use serenity::all::UserId;
+use sqlx::{FromRow, SqlitePool};
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ExperimentCounter {
+    pub experiment_key: String,
+    pub variant: i64,
+    pub impressions: i64,
+    pub conversions: i64,
+}
+
+impl ExperimentCounter {
+    /// Add a batch of buffered counts in one transaction
+    pub async fn add_batch(
+        pool: &SqlitePool,
+        batch: &[(VariantKey, VariantCounts)],
+    ) -> Result<(), sqlx::Error> {
+        tracing::debug!(
+            "Database query: add_experiment_counts ({} rows)",
+            batch.len()
+        );
+
+        let mut tx = pool.begin().await?;
+        for ((key, variant), counts) in batch {
+            sqlx::query(
+                r#"
+                INSERT INTO experiment_counters (experiment_key, variant, impressions, conversions)
+                VALUES (?, ?, ?, ?)
+                ON CONFLICT (experiment_key, variant)
+                DO UPDATE SET 
+                    impressions = impressions + excluded.impressions,
+                    conversions = conversions + excluded.conversions,
+                    updated_at = CURRENT_TIMESTAMP
+                "#,
+            )
+            .bind(*key)
+            .bind(*variant as i64)
+            .bind(counts.impressions as i64)
+            .bind(counts.conversions as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>(
+            r#"
+            SELECT experiment_key, variant, impressions, conversions
+            FROM experiment_counters
+            ORDER BY experiment_key, variant
+            "#,
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
+
+/// Which users saw an experiment, so later follow-ups can be attributed
+pub struct ExperimentExposure;
+
+impl ExperimentExposure {
+    pub async fn record(
+        pool: &SqlitePool,
+        key: &str,
+        user_id: UserId,
+        variant: usize,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO experiment_exposures (experiment_key, user_id, variant)
+            VALUES (?, ?, ?)
+            ON CONFLICT (experiment_key, user_id)
+            DO UPDATE SET 
+                variant = excluded.variant,
+                converted = 0,
+                last_seen_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(key)
+        .bind(user_id.get() as i64)
+        .bind(variant as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Mark a recent, unconverted exposure as converted. Returns whether one was.
+    pub async fn convert(
+        pool: &SqlitePool,
+        key: &str,
+        user_id: UserId,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE experiment_exposures
+            SET converted = 1
+            WHERE experiment_key = ? AND user_id = ? AND converted = 0
+              AND last_seen_at >= datetime('now', ?)
+            "#,
+        )
+        .bind(key)
+        .bind(user_id.get() as i64)
+        .bind(format!("-{} days", CONVERSION_WINDOW_DAYS))
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
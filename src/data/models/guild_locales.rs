@@ -0,0 +1,90 @@
+use crate::utils::i18n::Locale;
+use serenity::all::{GuildId, UserId};
+use sqlx::SqlitePool;
+
+/// The language a guild's replies default to
+pub struct GuildLocale;
+
+impl GuildLocale {
+    /// The guild's chosen locale. A stored code without a catalog any more
+    /// reads as unset.
+    pub async fn get(pool: &SqlitePool, guild_id: GuildId) -> Result<Option<Locale>, sqlx::Error> {
+        tracing::debug!("Database query: get_guild_locale for guild {}", guild_id);
+
+        let code: Option<String> =
+            sqlx::query_scalar("SELECT locale FROM guild_locales WHERE guild_id = ?")
+                .bind(guild_id.get() as i64)
+                .fetch_optional(pool)
+                .await?;
+
+        Ok(code.as_deref().and_then(Locale::from_code))
+    }
+
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        locale: Locale,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_locales (guild_id, locale, set_by)
+            VALUES (?, ?, ?)
+            ON CONFLICT (guild_id)
+            DO UPDATE SET
+                locale = excluded.locale,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(locale.code())
+        .bind(set_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            locale = locale.code(),
+            set_by = %set_by,
+            "Guild locale updated"
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::database::init_memory_database;
+
+    #[tokio::test]
+    async fn test_locale_is_stored_per_guild() {
+        let pool = init_memory_database().await.unwrap();
+        let guild = GuildId::new(1);
+        let admin = UserId::new(2);
+
+        assert_eq!(GuildLocale::get(&pool, guild).await.unwrap(), None);
+
+        GuildLocale::set(&pool, guild, Locale::EsEs, admin)
+            .await
+            .unwrap();
+        assert_eq!(
+            GuildLocale::get(&pool, guild).await.unwrap(),
+            Some(Locale::EsEs)
+        );
+        assert_eq!(
+            GuildLocale::get(&pool, GuildId::new(3)).await.unwrap(),
+            None
+        );
+
+        GuildLocale::set(&pool, guild, Locale::EnUs, admin)
+            .await
+            .unwrap();
+        assert_eq!(
+            GuildLocale::get(&pool, guild).await.unwrap(),
+            Some(Locale::EnUs)
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use crate::data::models::BoosterRoleShare;
+use serenity::all::{GuildId, UserId};
+use sqlx::SqlitePool;
+
+/// Every table with per-guild rows. A guild the bot is removed from loses
+/// all of them.
+pub const GUILD_TABLES: &[&str] = &[
+    "booster_award_grants",
+    "booster_color_rotations",
+    "booster_rename_history",
+    "booster_role_archive",
+    "booster_role_links",
+    "booster_role_locks",
+    "booster_role_shares",
+    "booster_role_templates",
+    "booster_roles",
+    "bulk_operations",
+    "cleanup_runs",
+    "command_usage",
+    "guild_account_age_settings",
+    "guild_auto_nicknames",
+    "guild_booster_awards",
+    "guild_booster_base_roles",
+    "guild_booster_limits",
+    "guild_booster_log_channels",
+    "guild_booster_options",
+    "guild_command_channels",
+    "guild_command_cooldowns",
+    "guild_config_snapshots",
+    "guild_disabled_commands",
+    "guild_eligibility_settings",
+    "guild_embed_themes",
+    "guild_exemptions",
+    "guild_filter_enforcement",
+    "guild_join_log_channels",
+    "guild_locales",
+    "guild_moderation_counters",
+    "guild_naming_rules",
+    "guild_next_steps_settings",
+    "guild_prefixes",
+    "guild_premium_roles",
+    "guild_quiet_hours",
+    "guild_rename_cooldowns",
+    "guild_retention_overrides",
+    "guild_rotation_settings",
+    "guild_share_policies",
+    "guild_sharing_limits",
+    "guild_spotlight_settings",
+    "guild_staff_lookalike_settings",
+    "guild_staff_roles",
+    "guild_support_channels",
+    "moderation_cases",
+    "name_violations",
+    "notification_subscriptions",
+    "quiet_member_events",
+    "role_name_blacklist",
+    "rules_acknowledgments",
+    "scheduled_role_changes",
+    "settings_audit_log",
+    "spotlight_history",
+    "table_growth",
+];
+
+/// Booster tables keyed by member that are cleared when the member leaves.
+/// Moderation and audit history stays.
+const MEMBER_TABLES: &[&str] = &[
+    "booster_award_grants",
+    "booster_color_rotations",
+    "booster_rename_history",
+    "booster_role_archive",
+    "booster_role_links",
+    "booster_roles",
+    "scheduled_role_changes",
+];
+
+/// What leaving the server took with it
+#[derive(Debug, Default)]
+pub struct MemberPurge {
+    pub rows_removed: u64,
+    /// Shares the member owned or received that were still active
+    pub ended_shares: Vec<BoosterRoleShare>,
+}
+
+/// Bulk removal of a member's or a whole guild's stored data
+pub struct GuildPurge;
+
+impl GuildPurge {
+    /// Remove a departed member's booster data and end their shares, in one
+    /// transaction. `keep_role` leaves their booster role record in place for
+    /// cleanup to find when the role itself couldn't be deleted.
+    pub async fn member(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        keep_role: bool,
+    ) -> Result<MemberPurge, sqlx::Error> {
+        tracing::debug!(
+            "Database query: purge_member {} in guild {}",
+            user_id,
+            guild_id
+        );
+
+        let mut tx = pool.begin().await?;
+
+        let ended_shares =
+            BoosterRoleShare::deactivate_for_member(&mut tx, guild_id, user_id).await?;
+
+        let mut rows_removed = 0;
+        // Only the fixed table list is ever interpolated
+        for table in MEMBER_TABLES {
+            if keep_role && *table == "booster_roles" {
+                continue;
+            }
+            rows_removed += sqlx::query(&format!(
+                "DELETE FROM {} WHERE guild_id = ? AND user_id = ?",
+                table
+            ))
+            .bind(guild_id.get() as i64)
+            .bind(user_id.get() as i64)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+
+        tx.commit().await?;
+
+        Ok(MemberPurge {
+            rows_removed,
+            ended_shares,
+        })
+    }
+
+    /// Remove every row the guild has, in one transaction. Returns the
+    /// tables that had rows and how many each lost.
+    pub async fn guild(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<Vec<(&'static str, u64)>, sqlx::Error> {
+        tracing::debug!("Database query: purge_guild {}", guild_id);
+
+        let mut tx = pool.begin().await?;
+
+        let mut removed = Vec::new();
+        for &table in GUILD_TABLES {
+            let rows = sqlx::query(&format!("DELETE FROM {} WHERE guild_id = ?", table))
+                .bind(guild_id.get() as i64)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+            if rows > 0 {
+                removed.push((table, rows));
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::database::init_memory_database;
+    use crate::data::models::BoosterRole;
+    use serenity::all::RoleId;
+
+    #[tokio::test]
+    async fn test_every_guild_table_is_purged() {
+        let pool = init_memory_database().await.unwrap();
+
+        let mut with_guild_column: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT m.name FROM sqlite_master m, pragma_table_info(m.name) c
+            WHERE m.type = 'table' AND c.name = 'guild_id'
+            ORDER BY m.name
+            "#,
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        with_guild_column.sort();
+
+        assert_eq!(with_guild_column, GUILD_TABLES);
+    }
+
+    #[tokio::test]
+    async fn test_member_purge_leaves_other_members_alone() {
+        let pool = init_memory_database().await.unwrap();
+        let guild = GuildId::new(1);
+        let leaver = UserId::new(2);
+        let stayer = UserId::new(3);
+
+        BoosterRole::create(
+            &pool,
+            guild,
+            leaver,
+            RoleId::new(10),
+            "Leaver",
+            "#FF0000",
+            None,
+        )
+        .await
+        .unwrap();
+        BoosterRole::create(
+            &pool,
+            guild,
+            stayer,
+            RoleId::new(11),
+            "Stayer",
+            "#00FF00",
+            None,
+        )
+        .await
+        .unwrap();
+        BoosterRoleShare::create(&pool, guild, RoleId::new(10), leaver, stayer, None)
+            .await
+            .unwrap();
+        BoosterRoleShare::create(&pool, guild, RoleId::new(11), stayer, UserId::new(4), None)
+            .await
+            .unwrap();
+
+        let purge = GuildPurge::member(&pool, guild, leaver, false)
+            .await
+            .unwrap();
+        assert_eq!(purge.rows_removed, 1);
+        assert_eq!(purge.ended_shares.len(), 1);
+        assert_eq!(purge.ended_shares[0].shared_with_id, 3);
+
+        assert!(BoosterRole::get(&pool, guild, leaver)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(BoosterRole::get(&pool, guild, stayer)
+            .await
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            BoosterRoleShare::get_shares_by_owner(&pool, guild, stayer)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_member_purge_can_keep_the_role_record() {
+        let pool = init_memory_database().await.unwrap();
+        let guild = GuildId::new(1);
+        let leaver = UserId::new(2);
+
+        BoosterRole::create(
+            &pool,
+            guild,
+            leaver,
+            RoleId::new(10),
+            "Undeleted",
+            "#FF0000",
+            None,
+        )
+        .await
+        .unwrap();
+
+        let purge = GuildPurge::member(&pool, guild, leaver, true)
+            .await
+            .unwrap();
+        assert_eq!(purge.rows_removed, 0);
+        assert!(BoosterRole::get(&pool, guild, leaver)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_guild_purge_only_touches_that_guild() {
+        let pool = init_memory_database().await.unwrap();
+        let removed_guild = GuildId::new(1);
+        let other_guild = GuildId::new(5);
+        let member = UserId::new(2);
+
+        for guild in [removed_guild, other_guild] {
+            BoosterRole::create(
+                &pool,
+                guild,
+                member,
+                RoleId::new(10),
+                "Role",
+                "#FF0000",
+                None,
+            )
+            .await
+            .unwrap();
+        }
+
+        let removed = GuildPurge::guild(&pool, removed_guild).await.unwrap();
+        assert_eq!(removed, vec![("booster_roles", 1)]);
+
+        assert!(BoosterRole::get(&pool, removed_guild, member)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(BoosterRole::get(&pool, other_guild, member)
+            .await
+            .unwrap()
+            .is_some());
+    }
+}
@@ -1,3 +1,5 @@
+use crate::utils::eligibility::{AgeRequirement, EligibilityMode};
+use chrono::{DateTime, Utc};
 use serenity::all::{ChannelId, GuildId, RoleId, UserId};
 use sqlx::{FromRow, SqlitePool};
 
@@ -7,8 +9,8 @@ pub struct GuildStaffRole {
     pub guild_id: i64,
     pub role_id: i64,
     pub added_by: i64,
-    pub created_at: Option<String>,
-    pub updated_at: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl GuildStaffRole {
@@ -79,8 +81,8 @@ pub struct GuildAutoNickname {
     pub guild_id: i64,
     pub nickname_template: String,
     pub set_by: i64,
-    pub created_at: Option<String>,
-    pub updated_at: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl GuildAutoNickname {
@@ -132,35 +134,46 @@ impl GuildAutoNickname {
     }
 }
 
+/// Where member joins and leaves are logged; either side may be off
 #[derive(Debug, Clone, FromRow)]
 pub struct GuildJoinLogChannel {
     pub guild_id: i64,
-    pub channel_id: i64,
+    pub join_channel_id: Option<i64>,
+    pub leave_channel_id: Option<i64>,
     pub set_by: i64,
-    pub created_at: Option<String>,
-    pub updated_at: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl GuildJoinLogChannel {
+    /// Replace both channels; with neither set the row is removed
     pub async fn set(
         pool: &SqlitePool,
         guild_id: GuildId,
-        channel_id: ChannelId,
+        join_channel: Option<ChannelId>,
+        leave_channel: Option<ChannelId>,
         set_by: UserId,
     ) -> Result<(), sqlx::Error> {
+        if join_channel.is_none() && leave_channel.is_none() {
+            Self::remove(pool, guild_id).await?;
+            return Ok(());
+        }
+
         sqlx::query(
             r#"
-            INSERT INTO guild_join_log_channels (guild_id, channel_id, set_by)
-            VALUES (?, ?, ?)
+            INSERT INTO guild_join_log_channels (guild_id, join_channel_id, leave_channel_id, set_by)
+            VALUES (?, ?, ?, ?)
             ON CONFLICT (guild_id)
-            DO UPDATE SET 
-                channel_id = excluded.channel_id,
+            DO UPDATE SET
+                join_channel_id = excluded.join_channel_id,
+                leave_channel_id = excluded.leave_channel_id,
                 set_by = excluded.set_by,
                 updated_at = CURRENT_TIMESTAMP
             "#,
         )
         .bind(guild_id.get() as i64)
-        .bind(channel_id.get() as i64)
+        .bind(join_channel.map(|c| c.get() as i64))
+        .bind(leave_channel.map(|c| c.get() as i64))
         .bind(set_by.get() as i64)
         .execute(pool)
         .await?;
@@ -188,6 +201,14 @@ impl GuildJoinLogChannel {
 
         Ok(result.rows_affected() > 0)
     }
+
+    pub fn join_channel(&self) -> Option<ChannelId> {
+        self.join_channel_id.map(|id| ChannelId::new(id as u64))
+    }
+
+    pub fn leave_channel(&self) -> Option<ChannelId> {
+        self.leave_channel_id.map(|id| ChannelId::new(id as u64))
+    }
 }
 
 #[derive(Debug, Clone, FromRow)]
@@ -195,11 +216,15 @@ pub struct GuildPremiumRole {
     pub guild_id: i64,
     pub role_id: i64,
     pub set_by: i64,
-    pub created_at: Option<String>,
-    pub updated_at: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl GuildPremiumRole {
+    pub fn role_id(&self) -> RoleId {
+        RoleId::new(self.role_id as u64)
+    }
+
     pub async fn set(
         pool: &SqlitePool,
         guild_id: GuildId,
@@ -246,12 +271,534 @@ impl GuildPremiumRole {
     }
 }
 
+#[allow(dead_code)]
+#[derive(Debug, Clone, FromRow)]
+pub struct GuildNextStepsSetting {
+    pub guild_id: i64,
+    pub enabled: bool,
+    pub set_by: i64,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl GuildNextStepsSetting {
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        enabled: bool,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_next_steps_settings (guild_id, enabled, set_by)
+            VALUES (?, ?, ?)
+            ON CONFLICT (guild_id)
+            DO UPDATE SET 
+                enabled = excluded.enabled,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(enabled)
+        .bind(set_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            enabled = enabled,
+            set_by = %set_by,
+            "Next steps setting updated"
+        );
+
+        Ok(())
+    }
+
+    /// Next-step suggestions are on unless a guild has turned them off
+    pub async fn is_enabled(pool: &SqlitePool, guild_id: GuildId) -> Result<bool, sqlx::Error> {
+        let enabled: Option<bool> = sqlx::query_scalar(
+            "SELECT enabled FROM guild_next_steps_settings WHERE guild_id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(enabled.unwrap_or(true))
+    }
+}
+
+/// Whether booster role names may look like the guild's staff roles
+pub struct GuildStaffLookalikeSetting;
+
+impl GuildStaffLookalikeSetting {
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        enabled: bool,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_staff_lookalike_settings (guild_id, enabled, set_by)
+            VALUES (?, ?, ?)
+            ON CONFLICT (guild_id)
+            DO UPDATE SET
+                enabled = excluded.enabled,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(enabled)
+        .bind(set_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            enabled = enabled,
+            set_by = %set_by,
+            "Staff lookalike blocking updated"
+        );
+
+        Ok(())
+    }
+
+    /// Lookalike names are allowed unless a guild has asked to block them
+    pub async fn is_enabled(pool: &SqlitePool, guild_id: GuildId) -> Result<bool, sqlx::Error> {
+        let enabled: Option<bool> = sqlx::query_scalar(
+            "SELECT enabled FROM guild_staff_lookalike_settings WHERE guild_id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(enabled.unwrap_or(false))
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, FromRow)]
+pub struct GuildEligibilitySetting {
+    pub guild_id: i64,
+    pub mode: String,
+    pub set_by: i64,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl GuildEligibilitySetting {
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        mode: EligibilityMode,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_eligibility_settings (guild_id, mode, set_by)
+            VALUES (?, ?, ?)
+            ON CONFLICT (guild_id)
+            DO UPDATE SET 
+                mode = excluded.mode,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(mode.as_str())
+        .bind(set_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            mode = mode.as_str(),
+            set_by = %set_by,
+            "Eligibility mode updated"
+        );
+
+        Ok(())
+    }
+
+    /// Boosters only unless a guild has chosen otherwise
+    pub async fn get_mode(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<EligibilityMode, sqlx::Error> {
+        let mode: Option<String> = sqlx::query_scalar(
+            "SELECT mode FROM guild_eligibility_settings WHERE guild_id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(mode
+            .map(|m| EligibilityMode::parse(&m))
+            .unwrap_or_default())
+    }
+}
+
+/// Minimum account age and membership before booster customization unlocks
+pub struct GuildAccountAgeSetting;
+
+impl GuildAccountAgeSetting {
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        requirement: AgeRequirement,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_account_age_settings
+                (guild_id, min_account_age_days, min_membership_days, set_by)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (guild_id)
+            DO UPDATE SET
+                min_account_age_days = excluded.min_account_age_days,
+                min_membership_days = excluded.min_membership_days,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(requirement.min_account_age_days as i64)
+        .bind(requirement.min_membership_days as i64)
+        .bind(set_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            min_account_age_days = requirement.min_account_age_days,
+            min_membership_days = requirement.min_membership_days,
+            set_by = %set_by,
+            "Account age requirement updated"
+        );
+
+        Ok(())
+    }
+
+    /// Both thresholds are 0 unless a guild has set them
+    pub async fn get(pool: &SqlitePool, guild_id: GuildId) -> Result<AgeRequirement, sqlx::Error> {
+        let row: Option<(i64, i64)> = sqlx::query_as(
+            "SELECT min_account_age_days, min_membership_days FROM guild_account_age_settings WHERE guild_id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row
+            .map(|(account, membership)| AgeRequirement {
+                min_account_age_days: account.max(0) as u32,
+                min_membership_days: membership.max(0) as u32,
+            })
+            .unwrap_or_default())
+    }
+}
+
+/// How long members wait between booster role renames in a guild
+pub struct GuildRenameCooldown;
+
+impl GuildRenameCooldown {
+    /// The guild's cooldown in minutes, if it has set one
+    pub async fn get(pool: &SqlitePool, guild_id: GuildId) -> Result<Option<i64>, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT cooldown_minutes FROM guild_rename_cooldowns WHERE guild_id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        cooldown_minutes: i64,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_rename_cooldowns (guild_id, cooldown_minutes, set_by)
+            VALUES (?, ?, ?)
+            ON CONFLICT (guild_id)
+            DO UPDATE SET
+                cooldown_minutes = excluded.cooldown_minutes,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(cooldown_minutes)
+        .bind(set_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            cooldown_minutes = cooldown_minutes,
+            set_by = %set_by,
+            "Rename cooldown updated"
+        );
+
+        Ok(())
+    }
+
+    /// Go back to the default cooldown. Returns `false` if none was set.
+    pub async fn remove(pool: &SqlitePool, guild_id: GuildId) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM guild_rename_cooldowns WHERE guild_id = ?")
+            .bind(guild_id.get() as i64)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// Members staff have exempted from a guild rule; `kind` names the rule
+#[allow(dead_code)]
+#[derive(Debug, Clone, FromRow)]
+pub struct GuildExemption {
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub kind: String,
+    pub added_by: i64,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl GuildExemption {
+    /// Returns `false` when the member was already exempt
+    pub async fn add(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        kind: &str,
+        added_by: UserId,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO guild_exemptions (guild_id, user_id, kind, added_by)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (guild_id, user_id, kind) DO NOTHING
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .bind(kind)
+        .bind(added_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn remove(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        kind: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "DELETE FROM guild_exemptions WHERE guild_id = ? AND user_id = ? AND kind = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .bind(kind)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn contains(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        kind: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let found: Option<i64> = sqlx::query_scalar(
+            "SELECT 1 FROM guild_exemptions WHERE guild_id = ? AND user_id = ? AND kind = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .bind(kind)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(found.is_some())
+    }
+
+    pub async fn list(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        kind: &str,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>(
+            "SELECT * FROM guild_exemptions WHERE guild_id = ? AND kind = ? ORDER BY created_at",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(kind)
+        .fetch_all(pool)
+        .await
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, FromRow)]
+pub struct GuildSupportChannel {
+    pub guild_id: i64,
+    pub channel_id: i64,
+    pub set_by: i64,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl GuildSupportChannel {
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_support_channels (guild_id, channel_id, set_by)
+            VALUES (?, ?, ?)
+            ON CONFLICT (guild_id)
+            DO UPDATE SET 
+                channel_id = excluded.channel_id,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(channel_id.get() as i64)
+        .bind(set_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            channel_id = %channel_id,
+            set_by = %set_by,
+            "Support channel updated"
+        );
+
+        Ok(())
+    }
+
+    pub async fn get(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>("SELECT * FROM guild_support_channels WHERE guild_id = ?")
+            .bind(guild_id.get() as i64)
+            .fetch_optional(pool)
+            .await
+    }
+}
+
+/// Where booster role changes are posted for moderators
+#[allow(dead_code)]
+#[derive(Debug, Clone, FromRow)]
+pub struct GuildBoosterLogChannel {
+    pub guild_id: i64,
+    pub channel_id: i64,
+    pub set_by: i64,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl GuildBoosterLogChannel {
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_booster_log_channels (guild_id, channel_id, set_by)
+            VALUES (?, ?, ?)
+            ON CONFLICT (guild_id)
+            DO UPDATE SET
+                channel_id = excluded.channel_id,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(channel_id.get() as i64)
+        .bind(set_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            channel_id = %channel_id,
+            set_by = %set_by,
+            "Booster log channel updated"
+        );
+
+        Ok(())
+    }
+
+    pub async fn get(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>("SELECT * FROM guild_booster_log_channels WHERE guild_id = ?")
+            .bind(guild_id.get() as i64)
+            .fetch_optional(pool)
+            .await
+    }
+
+    pub async fn remove(pool: &SqlitePool, guild_id: GuildId) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM guild_booster_log_channels WHERE guild_id = ?")
+            .bind(guild_id.get() as i64)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SettingsAuditLog {
+    #[allow(dead_code)]
+    pub id: i64,
+    #[allow(dead_code)]
     pub guild_id: GuildId,
     pub user_id: UserId,
     pub action: String,
     pub details: Option<String>,
+    pub timestamp: Option<String>,
+}
+
+/// A `settings_audit_log` row as stored
+#[derive(Debug, Clone, FromRow)]
+pub struct SettingsAuditEntry {
+    pub id: i64,
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub action: String,
+    pub details: Option<String>,
+    pub timestamp: Option<String>,
+}
+
+impl From<SettingsAuditEntry> for SettingsAuditLog {
+    fn from(entry: SettingsAuditEntry) -> Self {
+        Self {
+            id: entry.id,
+            guild_id: GuildId::new(entry.guild_id as u64),
+            user_id: UserId::new(entry.user_id as u64),
+            action: entry.action,
+            details: entry.details,
+            timestamp: entry.timestamp,
+        }
+    }
 }
 
 impl SettingsAuditLog {
@@ -285,4 +832,81 @@ impl SettingsAuditLog {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// A guild's audit entries, newest first, optionally narrowed to one
+    /// action and/or the member who made the change
+    pub async fn query(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        action_filter: Option<&str>,
+        user_filter: Option<UserId>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        tracing::debug!(
+            "Database query: query_settings_audit_log for guild {} (action {:?}, user {:?})",
+            guild_id,
+            action_filter,
+            user_filter
+        );
+
+        let user_filter = user_filter.map(|user_id| user_id.get() as i64);
+        let entries = sqlx::query_as::<_, SettingsAuditEntry>(
+            r#"
+            SELECT id, guild_id, user_id, action, details, timestamp
+            FROM settings_audit_log
+            WHERE guild_id = ?
+              AND (? IS NULL OR action = ?)
+              AND (? IS NULL OR user_id = ?)
+            ORDER BY timestamp DESC, id DESC
+            LIMIT ? OFFSET ?
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(action_filter)
+        .bind(action_filter)
+        .bind(user_filter)
+        .bind(user_filter)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(entries.into_iter().map(Self::from).collect())
+    }
+
+    /// How many entries [`SettingsAuditLog::query`] could return with these filters
+    pub async fn count(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        action_filter: Option<&str>,
+        user_filter: Option<UserId>,
+    ) -> Result<i64, sqlx::Error> {
+        let user_filter = user_filter.map(|user_id| user_id.get() as i64);
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM settings_audit_log
+            WHERE guild_id = ?
+              AND (? IS NULL OR action = ?)
+              AND (? IS NULL OR user_id = ?)
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(action_filter)
+        .bind(action_filter)
+        .bind(user_filter)
+        .bind(user_filter)
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Distinct actions recorded for a guild, alphabetically
+    pub async fn actions(pool: &SqlitePool, guild_id: GuildId) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT DISTINCT action FROM settings_audit_log WHERE guild_id = ? ORDER BY action",
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -1,9 +1,58 @@
+pub mod announcements;
+pub mod award_grants;
 pub mod booster_models;
+pub mod booster_templates;
+pub mod bulk_operations;
+pub mod cleanup_runs;
+pub mod color_rotations;
+pub mod command_channels;
+pub mod command_cooldowns;
+pub mod command_usage;
+pub mod config_snapshot;
+pub mod database_stats;
+pub mod disabled_commands;
+pub mod embed_themes;
+pub mod experiments;
+pub mod guild_locales;
+pub mod guild_purge;
 pub mod guild_settings;
 pub mod moderation;
+pub mod quiet_hours;
+pub mod retention;
+pub mod settings_snapshot;
+pub mod settings_transfer;
+pub mod table_growth;
+pub mod user_history;
+pub mod user_preferences;
 
+pub use announcements::{Announcement, AnnouncementDelivery, NotificationSubscription};
+pub use award_grants::BoosterAwardGrant;
 pub use booster_models::*;
+pub use booster_templates::BoosterRoleTemplate;
+pub use bulk_operations::BulkOperation;
+pub use cleanup_runs::CleanupRun;
+pub use color_rotations::{ColorRotation, GuildRotationSettings};
+pub use command_channels::GuildCommandChannel;
+pub use command_cooldowns::GuildCommandCooldown;
+pub use command_usage::{CommandUsage, COMMAND_USAGE_RETENTION_DAYS};
+pub use config_snapshot::GuildConfigSnapshot;
+pub use database_stats::DatabaseStats;
+pub use disabled_commands::GuildDisabledCommand;
+pub use embed_themes::GuildEmbedTheme;
+pub use experiments::{ExperimentCounter, ExperimentExposure};
+pub use guild_locales::GuildLocale;
+pub use guild_purge::GuildPurge;
 pub use guild_settings::{
-    GuildAutoNickname, GuildJoinLogChannel, GuildPremiumRole, GuildStaffRole, SettingsAuditLog,
+    GuildAccountAgeSetting, GuildAutoNickname, GuildBoosterLogChannel, GuildEligibilitySetting,
+    GuildExemption, GuildJoinLogChannel, GuildNextStepsSetting, GuildPremiumRole,
+    GuildRenameCooldown, GuildStaffLookalikeSetting, GuildStaffRole, GuildSupportChannel,
+    SettingsAuditLog,
 };
 pub use moderation::{ModerationAction, ModerationCase};
+pub use quiet_hours::{GuildQuietHours, QuietMemberEvent};
+pub use retention::GuildRetentionOverride;
+pub use settings_snapshot::GuildSettingsSnapshot;
+pub use settings_transfer::GuildSettingsExport;
+pub use table_growth::{TableGrowth, TABLE_GROWTH_RETENTION_DAYS};
+pub use user_history::compile_user_history;
+pub use user_preferences::UserPreference;
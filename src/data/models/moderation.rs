@@ -8,6 +8,7 @@
 
 #![allow(dead_code)]
 
+use chrono::{DateTime, Utc};
 use serenity::all::{GuildId, UserId};
 use sqlx::{FromRow, SqlitePool};
 
@@ -66,8 +67,8 @@ pub struct ModerationCase {
     /// SQLite stores as INTEGER 0/1.
     pub active: i64,
     pub related_case_id: Option<i64>,
-    pub created_at: Option<String>,
-    pub updated_at: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl ModerationCase {
@@ -332,44 +333,7 @@ impl ModerationCase {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::data::init_database;
-    use std::path::PathBuf;
-    use std::time::{SystemTime, UNIX_EPOCH};
-
-    struct TestDb {
-        pool: SqlitePool,
-        path: PathBuf,
-    }
-
-    impl Drop for TestDb {
-        fn drop(&mut self) {
-            // Pool may still hold handles; ignore cleanup errors on Windows/macOS.
-            let _ = std::fs::remove_file(&self.path);
-            let _ = std::fs::remove_file(format!("{}-wal", self.path.display()));
-            let _ = std::fs::remove_file(format!("{}-shm", self.path.display()));
-        }
-    }
-
-    async fn test_db() -> TestDb {
-        use std::sync::atomic::{AtomicU64, Ordering};
-        static COUNTER: AtomicU64 = AtomicU64::new(0);
-        let n = COUNTER.fetch_add(1, Ordering::SeqCst);
-        let nanos = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_nanos();
-        let path = std::env::temp_dir().join(format!(
-            "moderation_case_test_{}_{}_{}.db",
-            std::process::id(),
-            nanos,
-            n
-        ));
-        let path_str = path.to_string_lossy().to_string();
-        let pool = init_database(&path_str)
-            .await
-            .unwrap_or_else(|e| panic!("init test db {}: {e}", path.display()));
-        TestDb { pool, path }
-    }
+    use crate::data::database::test_db;
 
     #[tokio::test]
     async fn create_assigns_monotonic_case_numbers_per_guild() {
@@ -0,0 +1,183 @@
+use crate::utils::quiet_hours::{
+    gate, AutomatedMessage, QueuedMemberEvent, QuietDecision, QuietWindow,
+};
+use chrono::{DateTime, Utc};
+use serenity::all::{GuildId, UserId};
+use sqlx::{FromRow, SqlitePool};
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, FromRow)]
+pub struct GuildQuietHours {
+    pub guild_id: i64,
+    pub start_minute: i64,
+    pub end_minute: i64,
+    pub utc_offset_minutes: i64,
+    pub queue_member_logs: bool,
+    pub set_by: i64,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl GuildQuietHours {
+    pub fn window(&self) -> QuietWindow {
+        QuietWindow {
+            start_minute: self.start_minute as u16,
+            end_minute: self.end_minute as u16,
+            utc_offset_minutes: self.utc_offset_minutes as i32,
+        }
+    }
+
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        window: QuietWindow,
+        queue_member_logs: bool,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_quiet_hours
+                (guild_id, start_minute, end_minute, utc_offset_minutes, queue_member_logs, set_by)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT (guild_id)
+            DO UPDATE SET
+                start_minute = excluded.start_minute,
+                end_minute = excluded.end_minute,
+                utc_offset_minutes = excluded.utc_offset_minutes,
+                queue_member_logs = excluded.queue_member_logs,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(window.start_minute as i64)
+        .bind(window.end_minute as i64)
+        .bind(window.utc_offset_minutes as i64)
+        .bind(queue_member_logs)
+        .bind(set_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            window = %window.describe(),
+            queue_member_logs = queue_member_logs,
+            set_by = %set_by,
+            "Quiet hours updated"
+        );
+
+        Ok(())
+    }
+
+    pub async fn get(pool: &SqlitePool, guild_id: GuildId) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>("SELECT * FROM guild_quiet_hours WHERE guild_id = ?")
+            .bind(guild_id.get() as i64)
+            .fetch_optional(pool)
+            .await
+    }
+
+    pub async fn remove(pool: &SqlitePool, guild_id: GuildId) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM guild_quiet_hours WHERE guild_id = ?")
+            .bind(guild_id.get() as i64)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Gate an automated message for `guild_id` against its quiet hours.
+    /// Every automated send checks this first; command responses never do.
+    pub async fn gate(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        message: AutomatedMessage,
+    ) -> Result<QuietDecision, sqlx::Error> {
+        let settings = Self::get(pool, guild_id).await?;
+        let window = settings.as_ref().map(Self::window);
+        let queue = settings.as_ref().is_some_and(|s| s.queue_member_logs);
+
+        Ok(gate(window.as_ref(), queue, message, chrono::Utc::now()))
+    }
+}
+
+/// Joins and leaves held back until a guild's quiet hours end
+#[allow(dead_code)]
+#[derive(Debug, Clone, FromRow)]
+pub struct QuietMemberEvent {
+    pub id: i64,
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub user_tag: String,
+    pub joined: bool,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl QuietMemberEvent {
+    pub async fn queue(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        user_id: UserId,
+        user_tag: &str,
+        joined: bool,
+    ) -> Result<(), sqlx::Error> {
+        tracing::debug!(
+            "Database query: queue_quiet_member_event for guild {}",
+            guild_id
+        );
+
+        sqlx::query(
+            "INSERT INTO quiet_member_events (guild_id, user_id, user_tag, joined) VALUES (?, ?, ?, ?)",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(user_id.get() as i64)
+        .bind(user_tag)
+        .bind(joined)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Guilds with at least one queued event
+    pub async fn pending_guilds(pool: &SqlitePool) -> Result<Vec<GuildId>, sqlx::Error> {
+        let ids: Vec<i64> = sqlx::query_scalar("SELECT DISTINCT guild_id FROM quiet_member_events")
+            .fetch_all(pool)
+            .await?;
+
+        Ok(ids.into_iter().map(|id| GuildId::new(id as u64)).collect())
+    }
+
+    /// Oldest first
+    pub async fn list(pool: &SqlitePool, guild_id: GuildId) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>(
+            "SELECT * FROM quiet_member_events WHERE guild_id = ? ORDER BY id",
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Remove events up to and including `last_id`, keeping any queued since
+    pub async fn clear_through(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        last_id: i64,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM quiet_member_events WHERE guild_id = ? AND id <= ?")
+            .bind(guild_id.get() as i64)
+            .bind(last_id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub fn to_event(&self) -> QueuedMemberEvent {
+        QueuedMemberEvent {
+            user_id: UserId::new(self.user_id as u64),
+            user_tag: self.user_tag.clone(),
+            joined: self.joined,
+            at: self.occurred_at,
+        }
+    }
+}
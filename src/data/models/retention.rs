@@ -0,0 +1,207 @@
+use crate::utils::retention::{effective_days, prune_statement, RetentionCategory};
+use serenity::all::{GuildId, UserId};
+use sqlx::SqlitePool;
+
+/// Per-guild retention windows that replace the global defaults
+pub struct GuildRetentionOverride;
+
+impl GuildRetentionOverride {
+    pub async fn set(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        category: RetentionCategory,
+        days: i64,
+        set_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        tracing::debug!(
+            "Database query: set_retention_override {} for guild {} to {} days",
+            category.as_str(),
+            guild_id,
+            days
+        );
+
+        sqlx::query(
+            r#"
+            INSERT INTO guild_retention_overrides (guild_id, category, days, set_by)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (guild_id, category)
+            DO UPDATE SET
+                days = excluded.days,
+                set_by = excluded.set_by,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(category.as_str())
+        .bind(days)
+        .bind(set_by.get() as i64)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// The guild's overrides; categories without one use the default
+    pub async fn list(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<Vec<(RetentionCategory, i64)>, sqlx::Error> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT category, days FROM guild_retention_overrides WHERE guild_id = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(category, days)| {
+                RetentionCategory::parse(&category).map(|category| (category, days))
+            })
+            .collect())
+    }
+
+    /// Days a category is kept for in a guild
+    pub async fn effective(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        category: RetentionCategory,
+    ) -> Result<i64, sqlx::Error> {
+        let days: Option<i64> = sqlx::query_scalar(
+            "SELECT days FROM guild_retention_overrides WHERE guild_id = ? AND category = ?",
+        )
+        .bind(guild_id.get() as i64)
+        .bind(category.as_str())
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(effective_days(category, days))
+    }
+
+    /// Drop one override, or all of them when `category` is `None`
+    pub async fn reset(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        category: Option<RetentionCategory>,
+    ) -> Result<u64, sqlx::Error> {
+        let result = match category {
+            Some(category) => {
+                sqlx::query(
+                    "DELETE FROM guild_retention_overrides WHERE guild_id = ? AND category = ?",
+                )
+                .bind(guild_id.get() as i64)
+                .bind(category.as_str())
+                .execute(pool)
+                .await?
+            }
+            None => {
+                sqlx::query("DELETE FROM guild_retention_overrides WHERE guild_id = ?")
+                    .bind(guild_id.get() as i64)
+                    .execute(pool)
+                    .await?
+            }
+        };
+
+        Ok(result.rows_affected())
+    }
+
+    /// Prune a category in every guild, each by its own window
+    pub async fn prune(pool: &SqlitePool, category: RetentionCategory) -> Result<u64, sqlx::Error> {
+        let mut pruned = 0;
+        for target in category.targets() {
+            let result = sqlx::query(&prune_statement(target))
+                .bind(category.as_str())
+                .bind(category.default_days())
+                .execute(pool)
+                .await?;
+            pruned += result.rows_affected();
+        }
+
+        Ok(pruned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::database::DEFAULT_MAX_CONNECTIONS;
+    use crate::data::init_database;
+
+    async fn add_rename(pool: &SqlitePool, guild: i64, days_ago: i64) {
+        sqlx::query(
+            r#"
+            INSERT INTO booster_rename_history (guild_id, user_id, old_name, new_name, renamed_at)
+            VALUES (?, 1, 'Old', 'New', datetime('now', ?))
+            "#,
+        )
+        .bind(guild)
+        .bind(format!("-{} days", days_ago))
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    async fn count(pool: &SqlitePool, guild: i64) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM booster_rename_history WHERE guild_id = ?")
+            .bind(guild)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_prune_uses_each_guilds_window() {
+        let path = std::env::temp_dir().join(format!(
+            "retention_{}_{}.db",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let pool = init_database(path.to_str().unwrap(), DEFAULT_MAX_CONNECTIONS)
+            .await
+            .unwrap();
+
+        for guild in [1, 2] {
+            add_rename(&pool, guild, 1).await;
+            add_rename(&pool, guild, 30).await;
+            add_rename(&pool, guild, 200).await;
+        }
+        GuildRetentionOverride::set(
+            &pool,
+            GuildId::new(1),
+            RetentionCategory::RenameHistory,
+            7,
+            UserId::new(9),
+        )
+        .await
+        .unwrap();
+
+        let pruned = GuildRetentionOverride::prune(&pool, RetentionCategory::RenameHistory)
+            .await
+            .unwrap();
+        assert_eq!(pruned, 3);
+        // Guild 1 keeps a week, guild 2 the 180-day default
+        assert_eq!(count(&pool, 1).await, 1);
+        assert_eq!(count(&pool, 2).await, 2);
+
+        assert_eq!(
+            GuildRetentionOverride::effective(
+                &pool,
+                GuildId::new(1),
+                RetentionCategory::RenameHistory
+            )
+            .await
+            .unwrap(),
+            7
+        );
+        GuildRetentionOverride::reset(&pool, GuildId::new(1), None)
+            .await
+            .unwrap();
+        assert!(GuildRetentionOverride::list(&pool, GuildId::new(1))
+            .await
+            .unwrap()
+            .is_empty());
+
+        pool.close().await;
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,252 @@
+use super::{
+    GuildAccountAgeSetting, GuildAutoNickname, GuildBoosterAward, GuildBoosterBaseRole,
+    GuildBoosterLimit, GuildBoosterLogChannel, GuildEligibilitySetting, GuildEmbedTheme,
+    GuildJoinLogChannel, GuildNextStepsSetting, GuildPrefix, GuildPremiumRole, GuildQuietHours,
+    GuildRenameCooldown, GuildRetentionOverride, GuildSharingLimit, GuildSpotlightSetting,
+    GuildStaffRole, GuildSupportChannel, RoleNameBlacklist,
+};
+use crate::utils::color_parser::ColorParser;
+use crate::utils::eligibility::{AgeRequirement, EligibilityMode};
+use crate::utils::embed_theme::{EmbedTheme, ThemeSlot};
+use crate::utils::name_severity::BlacklistEntry;
+use crate::utils::quiet_hours::QuietWindow;
+use crate::utils::rename_cooldown::effective_cooldown;
+use crate::utils::retention::{describe_effective, RetentionCategory};
+use serde_json::{json, Value};
+use serenity::all::{ChannelId, GuildId, RoleId};
+use sqlx::SqlitePool;
+
+/// Every stored setting for a guild, read together. `/settings config` shows
+/// it, config snapshots store its JSON and `/settings export` is built from
+/// it, so a new setting only needs adding here.
+#[derive(Debug, Clone, Default)]
+pub struct GuildSettingsSnapshot {
+    pub prefix: Option<String>,
+    pub staff_roles: Vec<RoleId>,
+    pub auto_nickname: Option<String>,
+    pub join_log_channel: Option<ChannelId>,
+    pub leave_log_channel: Option<ChannelId>,
+    pub booster_log_channel: Option<ChannelId>,
+    pub premium_role: Option<RoleId>,
+    pub booster_limit: Option<i32>,
+    /// Members per role, then roles per member
+    pub sharing_limits: Option<(i32, i32)>,
+    pub base_role: Option<RoleId>,
+    pub award_role: Option<RoleId>,
+    pub blacklist: Vec<BlacklistEntry>,
+    pub next_steps: bool,
+    pub eligibility: EligibilityMode,
+    pub account_age: AgeRequirement,
+    pub support_channel: Option<ChannelId>,
+    pub quiet_hours: Option<QuietWindow>,
+    /// Whether join and leave logs wait for quiet hours to end
+    pub queue_member_logs: bool,
+    /// Channel, and whether posting is switched on
+    pub spotlight: Option<(ChannelId, bool)>,
+    /// Minutes, with the default applied
+    pub rename_cooldown: i64,
+    pub retention: Vec<(RetentionCategory, i64)>,
+    pub embed_theme: EmbedTheme,
+}
+
+impl GuildSettingsSnapshot {
+    /// Read every setting at once
+    pub async fn load(pool: &SqlitePool, guild_id: GuildId) -> Result<Self, sqlx::Error> {
+        tracing::debug!(
+            "Database query: load_settings_snapshot for guild {}",
+            guild_id
+        );
+
+        let (
+            prefix,
+            staff_roles,
+            auto_nickname,
+            join_log,
+            booster_log,
+            premium_role,
+            booster_limit,
+            sharing_limits,
+            base_role,
+            award_role,
+            blacklist,
+            next_steps,
+            eligibility,
+            account_age,
+            support_channel,
+            quiet_hours,
+            spotlight,
+            rename_cooldown,
+            retention,
+            embed_theme,
+        ) = tokio::join!(
+            GuildPrefix::get(pool, guild_id.get()),
+            GuildStaffRole::list(pool, guild_id),
+            GuildAutoNickname::get(pool, guild_id),
+            GuildJoinLogChannel::get(pool, guild_id),
+            GuildBoosterLogChannel::get(pool, guild_id),
+            GuildPremiumRole::get(pool, guild_id),
+            GuildBoosterLimit::get(pool, guild_id),
+            GuildSharingLimit::get(pool, guild_id),
+            GuildBoosterBaseRole::get(pool, guild_id),
+            GuildBoosterAward::get(pool, guild_id),
+            RoleNameBlacklist::get_entries(pool, guild_id),
+            GuildNextStepsSetting::is_enabled(pool, guild_id),
+            GuildEligibilitySetting::get_mode(pool, guild_id),
+            GuildAccountAgeSetting::get(pool, guild_id),
+            GuildSupportChannel::get(pool, guild_id),
+            GuildQuietHours::get(pool, guild_id),
+            GuildSpotlightSetting::get(pool, guild_id),
+            GuildRenameCooldown::get(pool, guild_id),
+            GuildRetentionOverride::list(pool, guild_id),
+            GuildEmbedTheme::get(pool, guild_id),
+        );
+        let join_log = join_log?;
+        let quiet_hours = quiet_hours?;
+
+        Ok(Self {
+            prefix: prefix?,
+            staff_roles: staff_roles?.iter().map(|r| role(r.role_id)).collect(),
+            auto_nickname: auto_nickname?.map(|an| an.nickname_template),
+            join_log_channel: join_log.as_ref().and_then(|jl| jl.join_channel()),
+            leave_log_channel: join_log.as_ref().and_then(|jl| jl.leave_channel()),
+            booster_log_channel: booster_log?.map(|bl| channel(bl.channel_id)),
+            premium_role: premium_role?.map(|pr| role(pr.role_id)),
+            booster_limit: booster_limit?,
+            sharing_limits: sharing_limits?
+                .map(|s| (s.max_members_per_role, s.max_shared_roles_per_member)),
+            base_role: base_role?,
+            award_role: award_role?,
+            blacklist: blacklist?,
+            next_steps: next_steps?,
+            eligibility: eligibility?,
+            account_age: account_age?,
+            support_channel: support_channel?.map(|sc| channel(sc.channel_id)),
+            quiet_hours: quiet_hours.as_ref().map(|qh| qh.window()),
+            queue_member_logs: quiet_hours.is_some_and(|qh| qh.queue_member_logs),
+            spotlight: spotlight?.map(|s| (channel(s.channel_id), s.enabled)),
+            rename_cooldown: effective_cooldown(rename_cooldown?),
+            retention: retention?,
+            embed_theme: embed_theme?,
+        })
+    }
+
+    /// The snapshot as JSON, for copying and for config snapshots; IDs are
+    /// strings like in exports
+    pub fn to_json(&self) -> Value {
+        json!({
+            "prefix": self.prefix,
+            "staff_roles": self.staff_roles.iter().map(|r| r.to_string()).collect::<Vec<_>>(),
+            "auto_nickname": self.auto_nickname,
+            "join_log_channel": self.join_log_channel.map(|c| c.to_string()),
+            "leave_log_channel": self.leave_log_channel.map(|c| c.to_string()),
+            "booster_log_channel": self.booster_log_channel.map(|c| c.to_string()),
+            "premium_role": self.premium_role.map(|r| r.to_string()),
+            "booster_limit": self.booster_limit,
+            "sharing_limits": self.sharing_limits.map(|(per_role, per_member)| json!({
+                "max_members_per_role": per_role,
+                "max_shared_roles_per_member": per_member,
+            })),
+            "base_role": self.base_role.map(|r| r.to_string()),
+            "award_role": self.award_role.map(|r| r.to_string()),
+            "blacklist": self
+                .blacklist
+                .iter()
+                .map(|entry| json!({
+                    "word": entry.word,
+                    "severity": entry.severity.as_str(),
+                    "match_type": entry.match_type.as_str(),
+                }))
+                .collect::<Vec<_>>(),
+            "next_steps": self.next_steps,
+            "eligibility": self.eligibility.as_str(),
+            "account_age": {
+                "min_account_age_days": self.account_age.min_account_age_days,
+                "min_membership_days": self.account_age.min_membership_days,
+            },
+            "support_channel": self.support_channel.map(|c| c.to_string()),
+            "quiet_hours": self.quiet_hours.as_ref().map(|w| json!({
+                "window": w.describe(),
+                "queue_member_logs": self.queue_member_logs,
+            })),
+            "spotlight": self.spotlight.map(|(channel_id, enabled)| json!({
+                "channel_id": channel_id.to_string(),
+                "enabled": enabled,
+            })),
+            "rename_cooldown_minutes": self.rename_cooldown,
+            "retention": describe_effective(&self.retention),
+            "embed_theme": ThemeSlot::ALL
+                .iter()
+                .filter_map(|slot| {
+                    let color = self.embed_theme.get(*slot)?;
+                    Some((slot.as_str().to_string(), json!(ColorParser::to_hex_string(color))))
+                })
+                .collect::<serde_json::Map<_, _>>(),
+        })
+    }
+}
+
+fn role(id: i64) -> RoleId {
+    RoleId::new(id as u64)
+}
+
+fn channel(id: i64) -> ChannelId {
+    ChannelId::new(id as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::database::init_memory_database;
+    use crate::utils::name_severity::{MatchType, Severity};
+
+    #[tokio::test]
+    async fn test_unconfigured_guild_loads_defaults() {
+        let pool = init_memory_database().await.unwrap();
+        let snapshot = GuildSettingsSnapshot::load(&pool, GuildId::new(1))
+            .await
+            .unwrap();
+
+        assert!(snapshot.prefix.is_none());
+        assert!(snapshot.staff_roles.is_empty());
+        assert!(snapshot.join_log_channel.is_none());
+        assert!(snapshot.blacklist.is_empty());
+        assert_eq!(snapshot.to_json()["booster_limit"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_stored_settings_are_collected() {
+        let pool = init_memory_database().await.unwrap();
+        let guild = GuildId::new(1);
+        let admin = serenity::all::UserId::new(9);
+
+        GuildPrefix::set(&pool, guild.get(), "?").await.unwrap();
+        GuildJoinLogChannel::set(&pool, guild, Some(ChannelId::new(10)), None, admin)
+            .await
+            .unwrap();
+        GuildBoosterLimit::set(&pool, guild, 5, admin)
+            .await
+            .unwrap();
+        RoleNameBlacklist::add_word(
+            &pool,
+            guild,
+            "spam",
+            Severity::default(),
+            MatchType::default(),
+            admin,
+        )
+        .await
+        .unwrap();
+
+        let snapshot = GuildSettingsSnapshot::load(&pool, guild).await.unwrap();
+        assert_eq!(snapshot.prefix.as_deref(), Some("?"));
+        assert_eq!(snapshot.join_log_channel, Some(ChannelId::new(10)));
+        assert!(snapshot.leave_log_channel.is_none());
+        assert_eq!(snapshot.booster_limit, Some(5));
+        assert_eq!(snapshot.blacklist.len(), 1);
+
+        let json = snapshot.to_json();
+        assert_eq!(json["join_log_channel"], "10");
+        assert_eq!(json["prefix"], "?");
+        assert_eq!(json["blacklist"][0]["word"], "spam");
+    }
+}
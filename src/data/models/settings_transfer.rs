@@ -0,0 +1,767 @@
+use super::GuildSettingsSnapshot;
+use crate::utils::name_severity::{compile_pattern, MatchType, Severity};
+use crate::utils::nickname_template::unknown_placeholders;
+use serde::{Deserialize, Serialize};
+use serenity::all::{ChannelId, GuildId, RoleId, UserId};
+use sqlx::{Sqlite, SqlitePool, Transaction};
+
+/// Format version written by `/settings export`; other versions are refused
+pub const SETTINGS_EXPORT_VERSION: u32 = 1;
+
+/// Largest settings file `/settings import` will read
+pub const MAX_IMPORT_BYTES: u32 = 256 * 1024;
+
+/// A guild's portable configuration.
+///
+/// IDs are strings so they survive tools that read JSON numbers as doubles.
+/// Missing sections are left alone on import.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GuildSettingsExport {
+    pub version: u32,
+    pub prefix: Option<String>,
+    #[serde(default)]
+    pub staff_roles: Vec<String>,
+    pub auto_nickname: Option<String>,
+    pub join_log_channel: Option<String>,
+    pub leave_log_channel: Option<String>,
+    pub premium_role: Option<String>,
+    pub booster_limit: Option<i32>,
+    pub sharing_limits: Option<SharingLimitsExport>,
+    pub base_role: Option<String>,
+    pub award_role: Option<String>,
+    #[serde(default)]
+    pub blacklist: Vec<BlacklistWordExport>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharingLimitsExport {
+    pub max_members_per_role: i32,
+    pub max_shared_roles_per_member: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlacklistWordExport {
+    pub word: String,
+    pub severity: String,
+    pub match_type: String,
+}
+
+/// What happened to one section of an import
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SectionOutcome {
+    /// Written, with a short description of the value
+    Imported(String),
+    NotInFile,
+    /// Valid, but refers to something this server doesn't have
+    Skipped,
+    /// Rejected by validation
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionReport {
+    pub name: &'static str,
+    pub outcome: SectionOutcome,
+    pub warnings: Vec<String>,
+}
+
+/// Validated values ready to write, plus the report to show the admin
+#[derive(Debug, Clone, Default)]
+pub struct SettingsImportPlan {
+    prefix: Option<String>,
+    staff_roles: Vec<RoleId>,
+    auto_nickname: Option<String>,
+    join_log_channel: Option<ChannelId>,
+    leave_log_channel: Option<ChannelId>,
+    premium_role: Option<RoleId>,
+    booster_limit: Option<i32>,
+    sharing_limits: Option<SharingLimitsExport>,
+    base_role: Option<RoleId>,
+    award_role: Option<RoleId>,
+    blacklist: Vec<(String, Severity, MatchType)>,
+    pub report: Vec<SectionReport>,
+}
+
+impl GuildSettingsExport {
+    /// Read every exportable setting for the guild
+    pub async fn capture(pool: &SqlitePool, guild_id: GuildId) -> Result<Self, sqlx::Error> {
+        tracing::debug!("Database query: export_settings for guild {}", guild_id);
+
+        Ok(Self::from_snapshot(
+            &GuildSettingsSnapshot::load(pool, guild_id).await?,
+        ))
+    }
+
+    /// The exportable part of a guild's settings
+    pub fn from_snapshot(snapshot: &GuildSettingsSnapshot) -> Self {
+        Self {
+            version: SETTINGS_EXPORT_VERSION,
+            prefix: snapshot.prefix.clone(),
+            staff_roles: snapshot.staff_roles.iter().map(|r| r.to_string()).collect(),
+            auto_nickname: snapshot.auto_nickname.clone(),
+            join_log_channel: snapshot.join_log_channel.map(|c| c.to_string()),
+            leave_log_channel: snapshot.leave_log_channel.map(|c| c.to_string()),
+            premium_role: snapshot.premium_role.map(|r| r.to_string()),
+            booster_limit: snapshot.booster_limit,
+            sharing_limits: snapshot.sharing_limits.map(|(per_role, per_member)| {
+                SharingLimitsExport {
+                    max_members_per_role: per_role,
+                    max_shared_roles_per_member: per_member,
+                }
+            }),
+            base_role: snapshot.base_role.map(|r| r.to_string()),
+            award_role: snapshot.award_role.map(|r| r.to_string()),
+            blacklist: snapshot
+                .blacklist
+                .iter()
+                .map(|entry| BlacklistWordExport {
+                    word: entry.word.clone(),
+                    severity: entry.severity.as_str().to_string(),
+                    match_type: entry.match_type.as_str().to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Parse an uploaded settings file, checking its shape and version
+    pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+        let export: Self = serde_json::from_slice(bytes)
+            .map_err(|e| format!("That isn't a valid settings file: {}", e))?;
+
+        if export.version != SETTINGS_EXPORT_VERSION {
+            return Err(format!(
+                "Settings file version {} isn't supported (expected {}).",
+                export.version, SETTINGS_EXPORT_VERSION
+            ));
+        }
+        Ok(export)
+    }
+
+    /// Validate every section against this server.
+    ///
+    /// Out-of-range values fail their section; roles and channels the
+    /// server doesn't have are skipped with a warning.
+    pub fn plan(
+        &self,
+        role_exists: impl Fn(RoleId) -> bool,
+        channel_exists: impl Fn(ChannelId) -> bool,
+    ) -> SettingsImportPlan {
+        let mut plan = SettingsImportPlan::default();
+
+        let section = validate_prefix(self.prefix.as_deref());
+        plan.prefix = section.0;
+        plan.report.push(section.1);
+
+        let mut warnings = Vec::new();
+        for raw in &self.staff_roles {
+            match parse_id(raw).map(RoleId::new) {
+                Some(role) if role_exists(role) => plan.staff_roles.push(role),
+                Some(role) => warnings.push(format!("Role {} doesn't exist here", role)),
+                None => warnings.push(format!("`{}` isn't a role ID", raw)),
+            }
+        }
+        plan.report.push(list_report(
+            "Staff roles",
+            self.staff_roles.is_empty(),
+            plan.staff_roles.len(),
+            "role(s)",
+            warnings,
+        ));
+
+        let section = validate_nickname(self.auto_nickname.as_deref());
+        plan.auto_nickname = section.0;
+        plan.report.push(section.1);
+
+        let section = resolve_id(
+            "Join log channel",
+            self.join_log_channel.as_deref(),
+            ChannelId::new,
+            &channel_exists,
+        );
+        plan.join_log_channel = section.0;
+        plan.report.push(section.1);
+
+        let section = resolve_id(
+            "Leave log channel",
+            self.leave_log_channel.as_deref(),
+            ChannelId::new,
+            &channel_exists,
+        );
+        plan.leave_log_channel = section.0;
+        plan.report.push(section.1);
+
+        let section = resolve_id(
+            "Premium role",
+            self.premium_role.as_deref(),
+            RoleId::new,
+            &role_exists,
+        );
+        plan.premium_role = section.0;
+        plan.report.push(section.1);
+
+        plan.report.push(match self.booster_limit {
+            None => report("Booster role limit", SectionOutcome::NotInFile),
+            Some(limit) if (0..=100).contains(&limit) => {
+                plan.booster_limit = Some(limit);
+                let shown = if limit == 0 {
+                    "unlimited".to_string()
+                } else {
+                    limit.to_string()
+                };
+                report("Booster role limit", SectionOutcome::Imported(shown))
+            }
+            Some(limit) => report(
+                "Booster role limit",
+                SectionOutcome::Failed(format!("{} is outside 0-100", limit)),
+            ),
+        });
+
+        plan.report.push(match &self.sharing_limits {
+            None => report("Sharing limits", SectionOutcome::NotInFile),
+            Some(limits)
+                if (1..=25).contains(&limits.max_members_per_role)
+                    && (1..=10).contains(&limits.max_shared_roles_per_member) =>
+            {
+                plan.sharing_limits = Some(limits.clone());
+                report(
+                    "Sharing limits",
+                    SectionOutcome::Imported(format!(
+                        "{} members per role, {} roles per member",
+                        limits.max_members_per_role, limits.max_shared_roles_per_member
+                    )),
+                )
+            }
+            Some(_) => report(
+                "Sharing limits",
+                SectionOutcome::Failed(
+                    "Members per role must be 1-25 and roles per member 1-10".to_string(),
+                ),
+            ),
+        });
+
+        let section = resolve_id(
+            "Base role",
+            self.base_role.as_deref(),
+            RoleId::new,
+            &role_exists,
+        );
+        plan.base_role = section.0;
+        plan.report.push(section.1);
+
+        let section = resolve_id(
+            "Award role",
+            self.award_role.as_deref(),
+            RoleId::new,
+            &role_exists,
+        );
+        plan.award_role = section.0;
+        plan.report.push(section.1);
+
+        let mut warnings = Vec::new();
+        for entry in &self.blacklist {
+            match validate_blacklist_word(entry) {
+                Ok(word) => plan.blacklist.push(word),
+                Err(reason) => warnings.push(format!("`{}`: {}", entry.word, reason)),
+            }
+        }
+        plan.report.push(list_report(
+            "Blacklist",
+            self.blacklist.is_empty(),
+            plan.blacklist.len(),
+            "word(s)",
+            warnings,
+        ));
+
+        plan
+    }
+}
+
+impl SettingsImportPlan {
+    /// Whether any section has something to write
+    pub fn has_changes(&self) -> bool {
+        self.report
+            .iter()
+            .any(|section| matches!(section.outcome, SectionOutcome::Imported(_)))
+    }
+
+    /// Write every validated section in one transaction. Lists are merged
+    /// with what the guild already has.
+    pub async fn apply(
+        &self,
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        imported_by: UserId,
+    ) -> Result<(), sqlx::Error> {
+        tracing::debug!("Database query: import_settings for guild {}", guild_id);
+
+        let guild = guild_id.get() as i64;
+        let user = imported_by.get() as i64;
+        let mut tx = pool.begin().await?;
+
+        if let Some(prefix) = &self.prefix {
+            sqlx::query(
+                r#"
+                INSERT INTO guild_prefixes (guild_id, prefix)
+                VALUES (?, ?)
+                ON CONFLICT (guild_id)
+                DO UPDATE SET prefix = excluded.prefix,
+                              updated_at = CURRENT_TIMESTAMP
+                "#,
+            )
+            .bind(guild)
+            .bind(prefix)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for role in &self.staff_roles {
+            sqlx::query(
+                r#"
+                INSERT INTO guild_staff_roles (guild_id, role_id, added_by)
+                VALUES (?, ?, ?)
+                ON CONFLICT (guild_id, role_id)
+                DO UPDATE SET updated_at = CURRENT_TIMESTAMP
+                "#,
+            )
+            .bind(guild)
+            .bind(role.get() as i64)
+            .bind(user)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        if let Some(template) = &self.auto_nickname {
+            sqlx::query(
+                r#"
+                INSERT INTO guild_auto_nicknames (guild_id, nickname_template, set_by)
+                VALUES (?, ?, ?)
+                ON CONFLICT (guild_id)
+                DO UPDATE SET nickname_template = excluded.nickname_template,
+                              set_by = excluded.set_by,
+                              updated_at = CURRENT_TIMESTAMP
+                "#,
+            )
+            .bind(guild)
+            .bind(template)
+            .bind(user)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let single_ids = [
+            (
+                "guild_join_log_channels",
+                "join_channel_id",
+                self.join_log_channel.map(|c| c.get()),
+            ),
+            (
+                "guild_join_log_channels",
+                "leave_channel_id",
+                self.leave_log_channel.map(|c| c.get()),
+            ),
+            (
+                "guild_premium_roles",
+                "role_id",
+                self.premium_role.map(|r| r.get()),
+            ),
+            (
+                "guild_booster_base_roles",
+                "base_role_id",
+                self.base_role.map(|r| r.get()),
+            ),
+            (
+                "guild_booster_awards",
+                "award_role_id",
+                self.award_role.map(|r| r.get()),
+            ),
+        ];
+        for (table, column, id) in single_ids {
+            if let Some(id) = id {
+                upsert_id(&mut tx, table, column, guild, id as i64, user).await?;
+            }
+        }
+
+        if let Some(limit) = self.booster_limit {
+            upsert_id(
+                &mut tx,
+                "guild_booster_limits",
+                "max_roles",
+                guild,
+                limit as i64,
+                user,
+            )
+            .await?;
+        }
+
+        if let Some(limits) = &self.sharing_limits {
+            sqlx::query(
+                r#"
+                INSERT INTO guild_sharing_limits (guild_id, max_members_per_role, max_shared_roles_per_member, set_by)
+                VALUES (?, ?, ?, ?)
+                ON CONFLICT (guild_id)
+                DO UPDATE SET
+                    max_members_per_role = excluded.max_members_per_role,
+                    max_shared_roles_per_member = excluded.max_shared_roles_per_member,
+                    set_by = excluded.set_by,
+                    updated_at = CURRENT_TIMESTAMP
+                "#,
+            )
+            .bind(guild)
+            .bind(limits.max_members_per_role)
+            .bind(limits.max_shared_roles_per_member)
+            .bind(user)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for (word, severity, match_type) in &self.blacklist {
+            sqlx::query(
+                r#"
+                INSERT INTO role_name_blacklist (guild_id, word, added_by, severity, match_type)
+                VALUES (?, ?, ?, ?, ?)
+                ON CONFLICT (guild_id, word) DO NOTHING
+                "#,
+            )
+            .bind(guild)
+            .bind(word)
+            .bind(user)
+            .bind(severity.as_str())
+            .bind(match_type.as_str())
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            imported_by = %imported_by,
+            staff_roles = self.staff_roles.len(),
+            blacklist_words = self.blacklist.len(),
+            "Guild settings imported"
+        );
+
+        Ok(())
+    }
+}
+
+/// Upsert a one-value-per-guild setting; `table` and `column` are
+/// compile-time names, never user input
+async fn upsert_id(
+    tx: &mut Transaction<'_, Sqlite>,
+    table: &str,
+    column: &str,
+    guild: i64,
+    value: i64,
+    user: i64,
+) -> Result<(), sqlx::Error> {
+    let sql = format!(
+        "INSERT INTO {table} (guild_id, {column}, set_by) VALUES (?, ?, ?) \
+         ON CONFLICT (guild_id) DO UPDATE SET {column} = excluded.{column}, \
+         set_by = excluded.set_by, updated_at = CURRENT_TIMESTAMP"
+    );
+    sqlx::query(&sql)
+        .bind(guild)
+        .bind(value)
+        .bind(user)
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
+
+fn report(name: &'static str, outcome: SectionOutcome) -> SectionReport {
+    SectionReport {
+        name,
+        outcome,
+        warnings: Vec::new(),
+    }
+}
+
+fn list_report(
+    name: &'static str,
+    empty: bool,
+    kept: usize,
+    unit: &str,
+    warnings: Vec<String>,
+) -> SectionReport {
+    let outcome = if empty {
+        SectionOutcome::NotInFile
+    } else if kept == 0 {
+        SectionOutcome::Skipped
+    } else {
+        SectionOutcome::Imported(format!("{} {}", kept, unit))
+    };
+    SectionReport {
+        name,
+        outcome,
+        warnings,
+    }
+}
+
+fn parse_id(raw: &str) -> Option<u64> {
+    raw.trim().parse::<u64>().ok().filter(|id| *id != 0)
+}
+
+fn resolve_id<T: Copy + std::fmt::Display>(
+    name: &'static str,
+    raw: Option<&str>,
+    make: fn(u64) -> T,
+    exists: &impl Fn(T) -> bool,
+) -> (Option<T>, SectionReport) {
+    let Some(raw) = raw else {
+        return (None, report(name, SectionOutcome::NotInFile));
+    };
+    let Some(id) = parse_id(raw).map(make) else {
+        return (
+            None,
+            report(
+                name,
+                SectionOutcome::Failed(format!("`{}` isn't a valid ID", raw)),
+            ),
+        );
+    };
+
+    if exists(id) {
+        (
+            Some(id),
+            report(name, SectionOutcome::Imported(id.to_string())),
+        )
+    } else {
+        let mut section = report(name, SectionOutcome::Skipped);
+        section
+            .warnings
+            .push(format!("{} doesn't exist in this server", id));
+        (None, section)
+    }
+}
+
+/// Same rules as `/prefix set`
+fn validate_prefix(prefix: Option<&str>) -> (Option<String>, SectionReport) {
+    let name = "Prefix";
+    match prefix {
+        None => (None, report(name, SectionOutcome::NotInFile)),
+        Some(p) if p.is_empty() || p.len() > 5 => (
+            None,
+            report(
+                name,
+                SectionOutcome::Failed("Prefix must be 1-5 characters long".to_string()),
+            ),
+        ),
+        Some(p) if p.contains('@') || p.contains('#') => (
+            None,
+            report(
+                name,
+                SectionOutcome::Failed("Prefix cannot contain @ or #".to_string()),
+            ),
+        ),
+        Some(p) => (
+            Some(p.to_string()),
+            report(name, SectionOutcome::Imported(format!("`{}`", p))),
+        ),
+    }
+}
+
+/// Same rules as `/settings autonick set`
+fn validate_nickname(template: Option<&str>) -> (Option<String>, SectionReport) {
+    let name = "Auto-nickname";
+    match template {
+        None => (None, report(name, SectionOutcome::NotInFile)),
+        Some(t) if t.is_empty() || t.len() > 32 => (
+            None,
+            report(
+                name,
+                SectionOutcome::Failed("Template must be 1-32 characters".to_string()),
+            ),
+        ),
+        Some(t) if t.contains("@everyone") || t.contains("@here") => (
+            None,
+            report(
+                name,
+                SectionOutcome::Failed("Template cannot contain @everyone or @here".to_string()),
+            ),
+        ),
+        Some(t) if !unknown_placeholders(t).is_empty() => (
+            None,
+            report(
+                name,
+                SectionOutcome::Failed(format!(
+                    "Unknown placeholder(s) {}",
+                    unknown_placeholders(t).join(", ")
+                )),
+            ),
+        ),
+        Some(t) => (
+            Some(t.to_string()),
+            report(name, SectionOutcome::Imported(format!("`{}`", t))),
+        ),
+    }
+}
+
+/// Same rules as `/boosterrole filter add`, plus strict severity and
+/// match type names since `parse` falls back to defaults
+fn validate_blacklist_word(
+    entry: &BlacklistWordExport,
+) -> Result<(String, Severity, MatchType), String> {
+    let word = entry.word.trim();
+    if word.is_empty() || word.len() > 50 {
+        return Err("must be 1-50 characters".to_string());
+    }
+
+    let severity = Severity::parse(&entry.severity);
+    if severity.as_str() != entry.severity {
+        return Err(format!("unknown severity `{}`", entry.severity));
+    }
+    let match_type = MatchType::parse(&entry.match_type);
+    if match_type.as_str() != entry.match_type {
+        return Err(format!("unknown match type `{}`", entry.match_type));
+    }
+    compile_pattern(word, match_type)?;
+
+    let word = match match_type {
+        MatchType::Regex => word.to_string(),
+        _ => word.to_lowercase(),
+    };
+    Ok((word, severity, match_type))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::database::test_db;
+    use crate::data::models::{
+        GuildBoosterAward, GuildBoosterLimit, GuildPrefix, GuildSharingLimit, GuildStaffRole,
+        RoleNameBlacklist,
+    };
+
+    fn outcome<'a>(plan: &'a SettingsImportPlan, name: &str) -> &'a SectionReport {
+        plan.report.iter().find(|s| s.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_parse_checks_shape_and_version() {
+        assert!(GuildSettingsExport::parse(b"not json").is_err());
+        assert!(GuildSettingsExport::parse(br#"{"prefix": "?"}"#).is_err());
+        assert!(GuildSettingsExport::parse(br#"{"version": 99}"#)
+            .unwrap_err()
+            .contains("99"));
+
+        let parsed = GuildSettingsExport::parse(br#"{"version": 1, "prefix": "?"}"#).unwrap();
+        assert_eq!(parsed.prefix.as_deref(), Some("?"));
+        assert!(parsed.staff_roles.is_empty());
+    }
+
+    #[test]
+    fn test_plan_skips_missing_ids_and_fails_bad_ranges() {
+        let export = GuildSettingsExport {
+            version: SETTINGS_EXPORT_VERSION,
+            prefix: Some("toolong".to_string()),
+            staff_roles: vec!["10".to_string(), "11".to_string(), "abc".to_string()],
+            premium_role: Some("12".to_string()),
+            booster_limit: Some(500),
+            blacklist: vec![
+                BlacklistWordExport {
+                    word: "Spam".to_string(),
+                    severity: "high".to_string(),
+                    match_type: "exact".to_string(),
+                },
+                BlacklistWordExport {
+                    word: "x".to_string(),
+                    severity: "extreme".to_string(),
+                    match_type: "exact".to_string(),
+                },
+            ],
+            ..Default::default()
+        };
+
+        let plan = export.plan(|role| role.get() == 10, |_| true);
+
+        assert!(matches!(
+            outcome(&plan, "Prefix").outcome,
+            SectionOutcome::Failed(_)
+        ));
+        assert!(matches!(
+            outcome(&plan, "Booster role limit").outcome,
+            SectionOutcome::Failed(_)
+        ));
+        assert_eq!(
+            outcome(&plan, "Premium role").outcome,
+            SectionOutcome::Skipped
+        );
+        assert_eq!(
+            outcome(&plan, "Base role").outcome,
+            SectionOutcome::NotInFile
+        );
+
+        let staff = outcome(&plan, "Staff roles");
+        assert_eq!(
+            staff.outcome,
+            SectionOutcome::Imported("1 role(s)".to_string())
+        );
+        assert_eq!(staff.warnings.len(), 2);
+
+        assert_eq!(
+            plan.blacklist,
+            vec![("spam".to_string(), Severity::High, MatchType::Exact)]
+        );
+        assert!(plan.has_changes());
+    }
+
+    #[tokio::test]
+    async fn test_export_round_trips_into_another_guild() {
+        let db = test_db().await;
+        let source = GuildId::new(100);
+        let target = GuildId::new(200);
+        let user = UserId::new(1);
+
+        GuildPrefix::set(&db.pool, source.get(), "?").await.unwrap();
+        GuildStaffRole::add(&db.pool, source, RoleId::new(10), user)
+            .await
+            .unwrap();
+        GuildBoosterLimit::set(&db.pool, source, 3, user)
+            .await
+            .unwrap();
+        GuildSharingLimit::set(&db.pool, source, 4, 2, user)
+            .await
+            .unwrap();
+        GuildBoosterAward::set(&db.pool, source, RoleId::new(20), true, false, user)
+            .await
+            .unwrap();
+        RoleNameBlacklist::add_word(
+            &db.pool,
+            source,
+            "spam",
+            Severity::Low,
+            MatchType::Substring,
+            user,
+        )
+        .await
+        .unwrap();
+
+        let exported = GuildSettingsExport::capture(&db.pool, source)
+            .await
+            .unwrap();
+        let json = serde_json::to_vec(&exported).unwrap();
+        let parsed = GuildSettingsExport::parse(&json).unwrap();
+        assert_eq!(parsed, exported);
+
+        let plan = parsed.plan(|_| true, |_| true);
+        plan.apply(&db.pool, target, user).await.unwrap();
+
+        let imported = GuildSettingsExport::capture(&db.pool, target)
+            .await
+            .unwrap();
+        assert_eq!(imported, exported);
+
+        // Exports and config snapshots describe shared settings the same way
+        let snapshot = GuildSettingsSnapshot::load(&db.pool, source)
+            .await
+            .unwrap()
+            .to_json();
+        let serde_json::Value::Object(exported) = serde_json::to_value(&exported).unwrap() else {
+            panic!("export isn't an object");
+        };
+        for (key, value) in exported.iter().filter(|(key, _)| *key != "version") {
+            assert_eq!(&snapshot[key], value, "{}", key);
+        }
+    }
+}
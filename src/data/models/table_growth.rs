@@ -0,0 +1,128 @@
+use crate::utils::table_growth::TRACKED_TABLES;
+use chrono::NaiveDate;
+use serenity::all::GuildId;
+use sqlx::{FromRow, SqlitePool};
+
+/// Days of samples kept; comfortably more than the trailing window
+pub const TABLE_GROWTH_RETENTION_DAYS: i64 = 30;
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, FromRow)]
+pub struct TableGrowth {
+    pub guild_id: i64,
+    pub table_name: String,
+    pub day: String,
+    pub row_count: i64,
+    pub flagged: bool,
+    pub detail: Option<String>,
+}
+
+impl TableGrowth {
+    /// Row counts per guild for one tracked table.
+    /// Grouping on the leading `guild_id` column lets SQLite answer from the
+    /// table's guild index instead of reading every row.
+    pub async fn count_by_guild(
+        pool: &SqlitePool,
+        table: &str,
+    ) -> Result<Vec<(i64, i64)>, sqlx::Error> {
+        // Table names can't be bound, so only ever interpolate the fixed list
+        if !TRACKED_TABLES.contains(&table) {
+            return Err(sqlx::Error::Protocol(format!(
+                "{} is not a tracked table",
+                table
+            )));
+        }
+        tracing::debug!("Database query: count_by_guild for {}", table);
+
+        sqlx::query_as(&format!(
+            "SELECT guild_id, COUNT(*) FROM {} GROUP BY guild_id",
+            table
+        ))
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Daily counts before `day`, oldest first
+    pub async fn history(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        table: &str,
+        day: NaiveDate,
+        limit: i64,
+    ) -> Result<Vec<i64>, sqlx::Error> {
+        let mut counts: Vec<i64> = sqlx::query_scalar(
+            r#"
+            SELECT row_count FROM table_growth
+            WHERE guild_id = ? AND table_name = ? AND day < ?
+            ORDER BY day DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(table)
+        .bind(day.to_string())
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        counts.reverse();
+        Ok(counts)
+    }
+
+    pub async fn record(
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        table: &str,
+        day: NaiveDate,
+        row_count: i64,
+        detail: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO table_growth (guild_id, table_name, day, row_count, flagged, detail)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT (guild_id, table_name, day)
+            DO UPDATE SET
+                row_count = excluded.row_count,
+                flagged = excluded.flagged,
+                detail = excluded.detail
+            "#,
+        )
+        .bind(guild_id.get() as i64)
+        .bind(table)
+        .bind(day.to_string())
+        .bind(row_count)
+        .bind(detail.is_some())
+        .bind(detail)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Flagged samples on or after `since`, newest first
+    pub async fn flagged_since(
+        pool: &SqlitePool,
+        since: NaiveDate,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Self>(
+            r#"
+            SELECT * FROM table_growth
+            WHERE flagged = TRUE AND day >= ?
+            ORDER BY day DESC, guild_id, table_name
+            "#,
+        )
+        .bind(since.to_string())
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn prune(pool: &SqlitePool, before: NaiveDate) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM table_growth WHERE day < ?")
+            .bind(before.to_string())
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
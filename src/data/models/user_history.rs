@@ -0,0 +1,298 @@
+use super::NameViolation;
+use crate::utils::history_export::{HistoryEntry, UserHistoryReport};
+use serenity::all::{GuildId, UserId};
+use sqlx::SqlitePool;
+
+/// Timestamp shown for rows stored without one
+const UNKNOWN_TIME: &str = "unknown";
+
+/// updated_at, role_id, name_locked, color_locked, locked_name, locked_by
+type LockRow = (Option<String>, i64, bool, bool, Option<String>, i64);
+/// created_at, case_number, action, moderator_id, reason
+type CaseRow = (Option<String>, i64, String, i64, Option<String>);
+
+/// Compile a member's rename, share, lock, filter and moderation history
+/// into one report
+pub async fn compile_user_history(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<UserHistoryReport, sqlx::Error> {
+    tracing::debug!(
+        "Database query: compile_user_history for user {} in guild {}",
+        user_id,
+        guild_id
+    );
+
+    let guild = guild_id.get() as i64;
+    let user = user_id.get() as i64;
+    let mut entries = Vec::new();
+
+    let renames: Vec<(String, String, String)> = sqlx::query_as(
+        r#"
+        SELECT renamed_at, old_name, new_name FROM booster_rename_history
+        WHERE guild_id = ? AND user_id = ?
+        "#,
+    )
+    .bind(guild)
+    .bind(user)
+    .fetch_all(pool)
+    .await?;
+    for (at, old_name, new_name) in renames {
+        entries.push(HistoryEntry {
+            at,
+            source: "booster_rename_history",
+            event: "rename".to_string(),
+            detail: format!("\"{}\" → \"{}\"", old_name, new_name),
+        });
+    }
+
+    let shares: Vec<(Option<String>, i64, i64, i64, bool)> = sqlx::query_as(
+        r#"
+        SELECT shared_at, role_id, owner_id, shared_with_id, is_active FROM booster_role_shares
+        WHERE guild_id = ? AND (owner_id = ? OR shared_with_id = ?)
+        "#,
+    )
+    .bind(guild)
+    .bind(user)
+    .bind(user)
+    .fetch_all(pool)
+    .await?;
+    for (at, role_id, owner_id, shared_with_id, is_active) in shares {
+        let state = if is_active { "active" } else { "ended" };
+        let (event, detail) = if owner_id == user {
+            (
+                "share_given",
+                format!(
+                    "Role {} shared with {} ({})",
+                    role_id, shared_with_id, state
+                ),
+            )
+        } else {
+            (
+                "share_received",
+                format!("Role {} shared by {} ({})", role_id, owner_id, state),
+            )
+        };
+        entries.push(HistoryEntry {
+            at: at.unwrap_or_else(|| UNKNOWN_TIME.to_string()),
+            source: "booster_role_shares",
+            event: event.to_string(),
+            detail,
+        });
+    }
+
+    let locks: Vec<LockRow> = sqlx::query_as(
+        r#"
+        SELECT l.updated_at, l.role_id, l.name_locked, l.color_locked, l.locked_name, l.locked_by
+        FROM booster_role_locks l
+        JOIN booster_roles r ON r.guild_id = l.guild_id AND r.role_id = l.role_id
+        WHERE l.guild_id = ? AND r.user_id = ?
+        "#,
+    )
+    .bind(guild)
+    .bind(user)
+    .fetch_all(pool)
+    .await?;
+    for (at, role_id, name_locked, color_locked, locked_name, locked_by) in locks {
+        let mut locked = Vec::new();
+        if name_locked {
+            locked.push(match locked_name {
+                Some(name) => format!("name \"{}\"", name),
+                None => "name".to_string(),
+            });
+        }
+        if color_locked {
+            locked.push("color".to_string());
+        }
+        if locked.is_empty() {
+            locked.push("nothing".to_string());
+        }
+        entries.push(HistoryEntry {
+            at: at.unwrap_or_else(|| UNKNOWN_TIME.to_string()),
+            source: "booster_role_locks",
+            event: "lock".to_string(),
+            detail: format!(
+                "Role {} locked ({}) by {}",
+                role_id,
+                locked.join(", "),
+                locked_by
+            ),
+        });
+    }
+
+    for violation in NameViolation::list_for_user(pool, guild_id, user_id).await? {
+        entries.push(HistoryEntry {
+            at: violation.attempted_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            source: "name_violations",
+            event: "name_rejected".to_string(),
+            detail: format!(
+                "\"{}\" matched \"{}\" via /{}",
+                violation.attempted_name, violation.matched_rule, violation.command
+            ),
+        });
+    }
+
+    // Entries the member made, plus entries that mention them
+    let audit: Vec<(Option<String>, i64, String, Option<String>)> = sqlx::query_as(
+        r#"
+        SELECT timestamp, user_id, action, details FROM settings_audit_log
+        WHERE guild_id = ? AND (user_id = ? OR details LIKE '%' || ? || '%')
+        "#,
+    )
+    .bind(guild)
+    .bind(user)
+    .bind(format!("<@{}>", user))
+    .fetch_all(pool)
+    .await?;
+    for (at, actor, action, details) in audit {
+        entries.push(HistoryEntry {
+            at: at.unwrap_or_else(|| UNKNOWN_TIME.to_string()),
+            source: "settings_audit_log",
+            event: action,
+            detail: format!("By {}: {}", actor, details.unwrap_or_default()),
+        });
+    }
+
+    let cases: Vec<CaseRow> = sqlx::query_as(
+        r#"
+        SELECT created_at, case_number, action, moderator_id, reason FROM moderation_cases
+        WHERE guild_id = ? AND target_id = ?
+        "#,
+    )
+    .bind(guild)
+    .bind(user)
+    .fetch_all(pool)
+    .await?;
+    for (at, case_number, action, moderator_id, reason) in cases {
+        entries.push(HistoryEntry {
+            at: at.unwrap_or_else(|| UNKNOWN_TIME.to_string()),
+            source: "moderation_cases",
+            event: action,
+            detail: format!(
+                "Case #{} by {}: {}",
+                case_number,
+                moderator_id,
+                reason.unwrap_or_else(|| "No reason".to_string())
+            ),
+        });
+    }
+
+    Ok(UserHistoryReport::new(
+        guild_id.get(),
+        user_id.get(),
+        chrono::Utc::now(),
+        entries,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::database::DEFAULT_MAX_CONNECTIONS;
+    use crate::data::init_database;
+    use crate::data::models::{
+        BoosterRenameHistory, BoosterRoleShare, ModerationAction, ModerationCase, NameViolation,
+        SettingsAuditLog,
+    };
+    use serenity::all::RoleId;
+
+    const GUILD: GuildId = GuildId::new(10);
+    const TARGET: UserId = UserId::new(20);
+    const OTHER: UserId = UserId::new(30);
+    const STAFF: UserId = UserId::new(40);
+
+    async fn seeded_pool() -> (SqlitePool, std::path::PathBuf) {
+        let path = std::env::temp_dir().join(format!(
+            "user_history_{}_{}.db",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let pool = init_database(path.to_str().unwrap(), DEFAULT_MAX_CONNECTIONS)
+            .await
+            .unwrap();
+
+        BoosterRenameHistory::add(&pool, GUILD, TARGET, "Old", "New")
+            .await
+            .unwrap();
+        BoosterRenameHistory::add(&pool, GUILD, OTHER, "Theirs", "Not ours")
+            .await
+            .unwrap();
+        BoosterRoleShare::create(&pool, GUILD, RoleId::new(1), TARGET, OTHER, None)
+            .await
+            .unwrap();
+        BoosterRoleShare::create(&pool, GUILD, RoleId::new(2), OTHER, TARGET, None)
+            .await
+            .unwrap();
+        NameViolation::record(
+            &pool,
+            GUILD,
+            TARGET,
+            "bad name",
+            "bad",
+            "boosterrole rename",
+        )
+        .await
+        .unwrap();
+        SettingsAuditLog::log(
+            &pool,
+            GUILD,
+            STAFF,
+            "account_age_exempt_added",
+            Some(&format!("User: <@{}>", TARGET)),
+        )
+        .await
+        .unwrap();
+        SettingsAuditLog::log(&pool, GUILD, STAFF, "prefix_set", Some("!"))
+            .await
+            .unwrap();
+        ModerationCase::create(
+            &pool,
+            GUILD,
+            ModerationAction::Warn,
+            TARGET,
+            STAFF,
+            Some("Offensive role name"),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        (pool, path)
+    }
+
+    #[tokio::test]
+    async fn test_compiles_every_source_for_the_user_only() {
+        let (pool, path) = seeded_pool().await;
+
+        let report = compile_user_history(&pool, GUILD, TARGET).await.unwrap();
+        let events: Vec<_> = report.entries.iter().map(|e| e.event.as_str()).collect();
+
+        assert_eq!(report.entries.len(), 6, "{:?}", events);
+        for event in [
+            "rename",
+            "share_given",
+            "share_received",
+            "name_rejected",
+            "account_age_exempt_added",
+            "warn",
+        ] {
+            assert!(events.contains(&event), "missing {}", event);
+        }
+        assert!(!events.contains(&"prefix_set"));
+
+        let rename = report.entries.iter().find(|e| e.event == "rename").unwrap();
+        assert_eq!(rename.detail, "\"Old\" → \"New\"");
+        assert!(report.to_json().contains("Offensive role name"));
+
+        // Nothing leaks across guilds
+        let empty = compile_user_history(&pool, GuildId::new(99), TARGET)
+            .await
+            .unwrap();
+        assert!(empty.entries.is_empty());
+
+        pool.close().await;
+        let _ = std::fs::remove_file(path);
+    }
+}
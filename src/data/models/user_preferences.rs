@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use serenity::all::UserId;
+use sqlx::{FromRow, SqlitePool};
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, FromRow)]
+pub struct UserPreference {
+    pub user_id: i64,
+    pub compact_mode: bool,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl UserPreference {
+    pub async fn set_compact_mode(
+        pool: &SqlitePool,
+        user_id: UserId,
+        compact_mode: bool,
+    ) -> Result<(), sqlx::Error> {
+        tracing::debug!(
+            "Database query: set_compact_mode for user {} to {}",
+            user_id,
+            compact_mode
+        );
+
+        sqlx::query(
+            r#"
+            INSERT INTO user_preferences (user_id, compact_mode)
+            VALUES (?, ?)
+            ON CONFLICT (user_id)
+            DO UPDATE SET 
+                compact_mode = excluded.compact_mode,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(user_id.get() as i64)
+        .bind(compact_mode)
+        .execute(pool)
+        .await?;
+
+        tracing::info!(
+            user_id = %user_id,
+            compact_mode = compact_mode,
+            "User compact mode updated"
+        );
+
+        Ok(())
+    }
+
+    /// Rich layouts unless the user has opted into compact mode
+    pub async fn compact_mode(pool: &SqlitePool, user_id: UserId) -> Result<bool, sqlx::Error> {
+        let compact: Option<bool> =
+            sqlx::query_scalar("SELECT compact_mode FROM user_preferences WHERE user_id = ?")
+                .bind(user_id.get() as i64)
+                .fetch_optional(pool)
+                .await?;
+
+        Ok(compact.unwrap_or(false))
+    }
+}
@@ -0,0 +1,420 @@
+use super::RepoResult;
+use crate::data::models::{
+    BoosterAwardGrant, BoosterRenameHistory, BoosterRole, BoosterRoleLink, BoosterRoleShare,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serenity::all::{GuildId, RoleId, UserId};
+use sqlx::SqlitePool;
+
+/// Members' booster roles, with the rename history, shares, links and award
+/// grants hanging off them
+#[async_trait]
+pub trait BoosterRoleRepo: std::fmt::Debug + Send + Sync {
+    async fn get(&self, guild_id: GuildId, user_id: UserId) -> RepoResult<Option<BoosterRole>>;
+
+    /// Newest first
+    async fn get_all_for_guild(&self, guild_id: GuildId) -> RepoResult<Vec<BoosterRole>>;
+
+    async fn count_for_guild(&self, guild_id: GuildId) -> RepoResult<i64>;
+
+    /// Record the member's role, replacing any earlier one. Shares of a
+    /// replaced role end with it.
+    async fn create(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+        role_name: &str,
+        primary_color: &str,
+        secondary_color: Option<&str>,
+    ) -> RepoResult<()>;
+
+    async fn update(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_name: &str,
+        primary_color: &str,
+        secondary_color: Option<&str>,
+    ) -> RepoResult<()>;
+
+    async fn update_color(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        primary_color: &str,
+        secondary_color: Option<&str>,
+    ) -> RepoResult<()>;
+
+    async fn set_icon(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        icon_url: Option<&str>,
+    ) -> RepoResult<()>;
+
+    /// Returns `false` if the member had no role; its shares end with it
+    async fn delete(&self, guild_id: GuildId, user_id: UserId) -> RepoResult<bool>;
+
+    async fn delete_by_role(&self, guild_id: GuildId, role_id: RoleId) -> RepoResult<bool>;
+
+    async fn last_rename(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> RepoResult<Option<BoosterRenameHistory>>;
+
+    async fn record_rename(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        old_name: &str,
+        new_name: &str,
+    ) -> RepoResult<()>;
+
+    /// Members the role is actively shared with
+    async fn count_role_shares(&self, guild_id: GuildId, role_id: RoleId) -> RepoResult<i64>;
+
+    /// Shared roles the member actively holds
+    async fn count_user_shares(&self, guild_id: GuildId, user_id: UserId) -> RepoResult<i64>;
+
+    async fn is_shared_with(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        user_id: UserId,
+    ) -> RepoResult<bool>;
+
+    /// Share a role, or bring back an ended share with the same member
+    async fn create_share(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        owner_id: UserId,
+        shared_with: UserId,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> RepoResult<()>;
+
+    /// Returns `false` if the share wasn't active
+    async fn remove_share(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        shared_with: UserId,
+    ) -> RepoResult<bool>;
+
+    /// Active shares the member received
+    async fn shares_received(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> RepoResult<Vec<BoosterRoleShare>>;
+
+    /// Active shares of any role the member owns, oldest first
+    async fn shares_given(
+        &self,
+        guild_id: GuildId,
+        owner_id: UserId,
+    ) -> RepoResult<Vec<BoosterRoleShare>>;
+
+    /// Every active share in a guild, oldest first
+    async fn active_shares(&self, guild_id: GuildId) -> RepoResult<Vec<BoosterRoleShare>>;
+
+    /// The existing role an admin linked to the member, if any
+    async fn link(&self, guild_id: GuildId, user_id: UserId)
+        -> RepoResult<Option<BoosterRoleLink>>;
+
+    /// Link a role to the member, replacing any earlier link
+    async fn set_link(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+        linked_by: UserId,
+    ) -> RepoResult<()>;
+
+    /// Returns `false` if the member had no link
+    async fn remove_link(&self, guild_id: GuildId, user_id: UserId) -> RepoResult<bool>;
+
+    async fn record_award_grant(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+    ) -> RepoResult<()>;
+
+    /// Newest first
+    async fn recent_award_grants(
+        &self,
+        guild_id: GuildId,
+        limit: i64,
+    ) -> RepoResult<Vec<BoosterAwardGrant>>;
+}
+
+#[derive(Debug, Clone)]
+pub struct SqliteBoosterRoleRepo {
+    pool: SqlitePool,
+}
+
+impl SqliteBoosterRoleRepo {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl BoosterRoleRepo for SqliteBoosterRoleRepo {
+    async fn get(&self, guild_id: GuildId, user_id: UserId) -> RepoResult<Option<BoosterRole>> {
+        BoosterRole::get(&self.pool, guild_id, user_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_all_for_guild(&self, guild_id: GuildId) -> RepoResult<Vec<BoosterRole>> {
+        BoosterRole::get_all_for_guild(&self.pool, guild_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn count_for_guild(&self, guild_id: GuildId) -> RepoResult<i64> {
+        BoosterRole::count_for_guild(&self.pool, guild_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn create(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+        role_name: &str,
+        primary_color: &str,
+        secondary_color: Option<&str>,
+    ) -> RepoResult<()> {
+        BoosterRole::create(
+            &self.pool,
+            guild_id,
+            user_id,
+            role_id,
+            role_name,
+            primary_color,
+            secondary_color,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn update(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_name: &str,
+        primary_color: &str,
+        secondary_color: Option<&str>,
+    ) -> RepoResult<()> {
+        BoosterRole::update(
+            &self.pool,
+            guild_id,
+            user_id,
+            role_name,
+            primary_color,
+            secondary_color,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn update_color(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        primary_color: &str,
+        secondary_color: Option<&str>,
+    ) -> RepoResult<()> {
+        BoosterRole::update_color(
+            &self.pool,
+            guild_id,
+            user_id,
+            primary_color,
+            secondary_color,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn set_icon(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        icon_url: Option<&str>,
+    ) -> RepoResult<()> {
+        BoosterRole::set_icon(&self.pool, guild_id, user_id, icon_url)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn delete(&self, guild_id: GuildId, user_id: UserId) -> RepoResult<bool> {
+        BoosterRole::delete(&self.pool, guild_id, user_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn delete_by_role(&self, guild_id: GuildId, role_id: RoleId) -> RepoResult<bool> {
+        BoosterRole::delete_by_role(&self.pool, guild_id, role_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn last_rename(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> RepoResult<Option<BoosterRenameHistory>> {
+        BoosterRenameHistory::get_last_rename(&self.pool, guild_id, user_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn record_rename(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        old_name: &str,
+        new_name: &str,
+    ) -> RepoResult<()> {
+        BoosterRenameHistory::add(&self.pool, guild_id, user_id, old_name, new_name)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn count_role_shares(&self, guild_id: GuildId, role_id: RoleId) -> RepoResult<i64> {
+        BoosterRoleShare::count_role_shares(&self.pool, guild_id, role_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn count_user_shares(&self, guild_id: GuildId, user_id: UserId) -> RepoResult<i64> {
+        BoosterRoleShare::count_user_shares(&self.pool, guild_id, user_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn is_shared_with(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        user_id: UserId,
+    ) -> RepoResult<bool> {
+        let shares = BoosterRoleShare::get_role_shares(&self.pool, guild_id, role_id).await?;
+        Ok(shares
+            .iter()
+            .any(|share| share.shared_with_id == user_id.get() as i64 && share.is_active))
+    }
+
+    async fn create_share(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        owner_id: UserId,
+        shared_with: UserId,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> RepoResult<()> {
+        BoosterRoleShare::create(
+            &self.pool,
+            guild_id,
+            role_id,
+            owner_id,
+            shared_with,
+            expires_at,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn remove_share(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        shared_with: UserId,
+    ) -> RepoResult<bool> {
+        BoosterRoleShare::remove(&self.pool, guild_id, role_id, shared_with)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn shares_received(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> RepoResult<Vec<BoosterRoleShare>> {
+        BoosterRoleShare::get_shared_with_user(&self.pool, guild_id, user_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn shares_given(
+        &self,
+        guild_id: GuildId,
+        owner_id: UserId,
+    ) -> RepoResult<Vec<BoosterRoleShare>> {
+        BoosterRoleShare::get_shares_by_owner(&self.pool, guild_id, owner_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn active_shares(&self, guild_id: GuildId) -> RepoResult<Vec<BoosterRoleShare>> {
+        BoosterRoleShare::get_active_for_guild(&self.pool, guild_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn link(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> RepoResult<Option<BoosterRoleLink>> {
+        BoosterRoleLink::get(&self.pool, guild_id, user_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn set_link(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+        linked_by: UserId,
+    ) -> RepoResult<()> {
+        BoosterRoleLink::create(&self.pool, guild_id, user_id, role_id, linked_by)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn remove_link(&self, guild_id: GuildId, user_id: UserId) -> RepoResult<bool> {
+        BoosterRoleLink::delete(&self.pool, guild_id, user_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn record_award_grant(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+    ) -> RepoResult<()> {
+        BoosterAwardGrant::record(&self.pool, guild_id, user_id, role_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn recent_award_grants(
+        &self,
+        guild_id: GuildId,
+        limit: i64,
+    ) -> RepoResult<Vec<BoosterAwardGrant>> {
+        BoosterAwardGrant::recent(&self.pool, guild_id, limit)
+            .await
+            .map_err(Into::into)
+    }
+}
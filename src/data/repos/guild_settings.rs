@@ -0,0 +1,287 @@
+use super::RepoResult;
+use crate::data::models::{
+    GuildBoosterAward, GuildBoosterBaseRole, GuildBoosterLimit, GuildBoosterOptions,
+    GuildRenameCooldown, GuildSharePolicy, GuildSharingLimit, RoleNameBlacklist,
+};
+use crate::utils::content_filter::NameFilters;
+use crate::utils::name_severity::NameCheck;
+use crate::utils::share_revalidation::RevalidationPolicy;
+use async_trait::async_trait;
+use serenity::all::{GuildId, RoleId, UserId};
+use sqlx::SqlitePool;
+
+/// What a guild has configured for its booster roles
+#[async_trait]
+pub trait GuildSettingsRepo: std::fmt::Debug + Send + Sync {
+    /// Most booster roles the guild allows, if it has set a cap
+    async fn booster_limit(&self, guild_id: GuildId) -> RepoResult<Option<i32>>;
+
+    async fn set_booster_limit(
+        &self,
+        guild_id: GuildId,
+        max_roles: i32,
+        set_by: UserId,
+    ) -> RepoResult<()>;
+
+    async fn booster_options(&self, guild_id: GuildId) -> RepoResult<GuildBoosterOptions>;
+
+    async fn set_booster_options(
+        &self,
+        guild_id: GuildId,
+        options: GuildBoosterOptions,
+        set_by: UserId,
+    ) -> RepoResult<()>;
+
+    /// The role booster roles are positioned above
+    async fn base_role(&self, guild_id: GuildId) -> RepoResult<Option<RoleId>>;
+
+    async fn set_base_role(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        set_by: UserId,
+    ) -> RepoResult<()>;
+
+    /// Returns `false` if none was set
+    async fn remove_base_role(&self, guild_id: GuildId) -> RepoResult<bool>;
+
+    /// Minutes between renames, if the guild has set its own
+    async fn rename_cooldown(&self, guild_id: GuildId) -> RepoResult<Option<i64>>;
+
+    async fn set_rename_cooldown(
+        &self,
+        guild_id: GuildId,
+        cooldown_minutes: i64,
+        set_by: UserId,
+    ) -> RepoResult<()>;
+
+    async fn sharing_limits(&self, guild_id: GuildId) -> RepoResult<Option<GuildSharingLimit>>;
+
+    async fn set_sharing_limits(
+        &self,
+        guild_id: GuildId,
+        max_members_per_role: i32,
+        max_shared_roles_per_member: i32,
+        set_by: UserId,
+    ) -> RepoResult<()>;
+
+    async fn set_share_policy(
+        &self,
+        guild_id: GuildId,
+        policy: RevalidationPolicy,
+        set_by: UserId,
+    ) -> RepoResult<()>;
+
+    /// The role boosters are given, along with how it's handed out
+    async fn award(&self, guild_id: GuildId) -> RepoResult<Option<GuildBoosterAward>>;
+
+    async fn set_award(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        remove_on_unboost: bool,
+        announce_boosts: bool,
+        set_by: UserId,
+    ) -> RepoResult<()>;
+
+    /// Returns `false` if none was set
+    async fn remove_award(&self, guild_id: GuildId) -> RepoResult<bool>;
+
+    /// How the blacklists in `filters` treat a name under the guild's
+    /// severity policy. Matches are recorded against `command` when given.
+    async fn check_role_name(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        name: &str,
+        command: Option<&str>,
+        filters: &NameFilters,
+    ) -> RepoResult<NameCheck>;
+}
+
+#[derive(Debug, Clone)]
+pub struct SqliteGuildSettingsRepo {
+    pool: SqlitePool,
+}
+
+impl SqliteGuildSettingsRepo {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl GuildSettingsRepo for SqliteGuildSettingsRepo {
+    async fn booster_limit(&self, guild_id: GuildId) -> RepoResult<Option<i32>> {
+        GuildBoosterLimit::get(&self.pool, guild_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn set_booster_limit(
+        &self,
+        guild_id: GuildId,
+        max_roles: i32,
+        set_by: UserId,
+    ) -> RepoResult<()> {
+        GuildBoosterLimit::set(&self.pool, guild_id, max_roles, set_by)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn booster_options(&self, guild_id: GuildId) -> RepoResult<GuildBoosterOptions> {
+        GuildBoosterOptions::get(&self.pool, guild_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn set_booster_options(
+        &self,
+        guild_id: GuildId,
+        options: GuildBoosterOptions,
+        set_by: UserId,
+    ) -> RepoResult<()> {
+        GuildBoosterOptions::set(&self.pool, guild_id, options, set_by)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn base_role(&self, guild_id: GuildId) -> RepoResult<Option<RoleId>> {
+        GuildBoosterBaseRole::get(&self.pool, guild_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn set_base_role(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        set_by: UserId,
+    ) -> RepoResult<()> {
+        GuildBoosterBaseRole::set(&self.pool, guild_id, role_id, set_by)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn remove_base_role(&self, guild_id: GuildId) -> RepoResult<bool> {
+        GuildBoosterBaseRole::remove(&self.pool, guild_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn rename_cooldown(&self, guild_id: GuildId) -> RepoResult<Option<i64>> {
+        GuildRenameCooldown::get(&self.pool, guild_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn set_rename_cooldown(
+        &self,
+        guild_id: GuildId,
+        cooldown_minutes: i64,
+        set_by: UserId,
+    ) -> RepoResult<()> {
+        GuildRenameCooldown::set(&self.pool, guild_id, cooldown_minutes, set_by)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn sharing_limits(&self, guild_id: GuildId) -> RepoResult<Option<GuildSharingLimit>> {
+        GuildSharingLimit::get(&self.pool, guild_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn set_sharing_limits(
+        &self,
+        guild_id: GuildId,
+        max_members_per_role: i32,
+        max_shared_roles_per_member: i32,
+        set_by: UserId,
+    ) -> RepoResult<()> {
+        GuildSharingLimit::set(
+            &self.pool,
+            guild_id,
+            max_members_per_role,
+            max_shared_roles_per_member,
+            set_by,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn set_share_policy(
+        &self,
+        guild_id: GuildId,
+        policy: RevalidationPolicy,
+        set_by: UserId,
+    ) -> RepoResult<()> {
+        GuildSharePolicy::set(&self.pool, guild_id, policy, set_by)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn award(&self, guild_id: GuildId) -> RepoResult<Option<GuildBoosterAward>> {
+        GuildBoosterAward::get_config(&self.pool, guild_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn set_award(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        remove_on_unboost: bool,
+        announce_boosts: bool,
+        set_by: UserId,
+    ) -> RepoResult<()> {
+        GuildBoosterAward::set(
+            &self.pool,
+            guild_id,
+            role_id,
+            remove_on_unboost,
+            announce_boosts,
+            set_by,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn remove_award(&self, guild_id: GuildId) -> RepoResult<bool> {
+        GuildBoosterAward::remove(&self.pool, guild_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn check_role_name(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        name: &str,
+        command: Option<&str>,
+        filters: &NameFilters,
+    ) -> RepoResult<NameCheck> {
+        match command {
+            Some(command) => RoleNameBlacklist::check_entries(
+                &self.pool,
+                guild_id,
+                user_id,
+                name,
+                command,
+                &filters.global,
+                &filters.guild,
+            )
+            .await
+            .map_err(Into::into),
+            None => RoleNameBlacklist::classify_entries(
+                &self.pool,
+                guild_id,
+                name,
+                &filters.global,
+                &filters.guild,
+            )
+            .await
+            .map_err(Into::into),
+        }
+    }
+}
@@ -0,0 +1,619 @@
+//! In-memory stand-ins for the repositories, so command logic can be tested
+//! without a database
+
+use super::{BoosterRoleRepo, GuildSettingsRepo, RepoResult, Repos};
+use crate::data::models::{
+    BoosterAwardGrant, BoosterRenameHistory, BoosterRole, BoosterRoleLink, BoosterRoleShare,
+    GuildBoosterAward, GuildBoosterOptions, GuildSharingLimit,
+};
+use crate::utils::content_filter::NameFilters;
+use crate::utils::name_severity::{enforce, strongest_match, EnforcementPolicy, NameCheck};
+use crate::utils::share_revalidation::RevalidationPolicy;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serenity::all::{GuildId, RoleId, UserId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+#[derive(Debug, Default)]
+struct MemoryState {
+    next_id: i64,
+    roles: Vec<BoosterRole>,
+    renames: Vec<BoosterRenameHistory>,
+    shares: Vec<BoosterRoleShare>,
+    links: HashMap<(GuildId, UserId), BoosterRoleLink>,
+    /// Newest last
+    award_grants: Vec<(GuildId, BoosterAwardGrant)>,
+    awards: HashMap<GuildId, GuildBoosterAward>,
+    limits: HashMap<GuildId, i32>,
+    options: HashMap<GuildId, GuildBoosterOptions>,
+    base_roles: HashMap<GuildId, RoleId>,
+    rename_cooldowns: HashMap<GuildId, i64>,
+    sharing_limits: HashMap<GuildId, (i32, i32)>,
+    share_policies: HashMap<GuildId, RevalidationPolicy>,
+    /// `(user, name)` for each blacklist match recorded against a command
+    violations: Vec<(UserId, String)>,
+}
+
+impl MemoryState {
+    fn next_id(&mut self) -> i64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    fn role_mut(&mut self, guild_id: GuildId, user_id: UserId) -> Option<&mut BoosterRole> {
+        self.roles.iter_mut().find(|role| {
+            role.guild_id == guild_id.get() as i64 && role.user_id == user_id.get() as i64
+        })
+    }
+
+    fn end_shares(&mut self, guild_id: GuildId, role_id: RoleId) {
+        for share in self.shares.iter_mut().filter(|share| {
+            share.guild_id == guild_id.get() as i64 && share.role_id == role_id.get() as i64
+        }) {
+            share.is_active = false;
+        }
+    }
+
+    fn share_mut(
+        &mut self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        shared_with: UserId,
+    ) -> Option<&mut BoosterRoleShare> {
+        self.shares.iter_mut().find(|share| {
+            share.guild_id == guild_id.get() as i64
+                && share.role_id == role_id.get() as i64
+                && share.shared_with_id == shared_with.get() as i64
+        })
+    }
+
+    /// Active shares in the guild matching `keep`, oldest first
+    fn active_shares(
+        &self,
+        guild_id: GuildId,
+        keep: impl Fn(&BoosterRoleShare) -> bool,
+    ) -> Vec<BoosterRoleShare> {
+        let mut shares: Vec<_> = self
+            .shares
+            .iter()
+            .filter(|share| share.is_active && share.guild_id == guild_id.get() as i64)
+            .filter(|share| keep(share))
+            .cloned()
+            .collect();
+        shares.sort_by_key(|share| share.id);
+        shares
+    }
+}
+
+/// Both repositories over one in-memory store
+#[derive(Debug, Default)]
+pub struct MemoryRepo {
+    state: Mutex<MemoryState>,
+}
+
+impl MemoryRepo {
+    /// A fresh store and the repositories reading from it
+    pub fn repos() -> (Arc<Self>, Repos) {
+        let store = Arc::new(Self::default());
+        let repos = Repos {
+            booster_roles: store.clone(),
+            guild_settings: store.clone(),
+        };
+        (store, repos)
+    }
+
+    fn state(&self) -> MutexGuard<'_, MemoryState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Share a role on behalf of whoever owns it
+    pub fn share(&self, guild_id: GuildId, role_id: RoleId, shared_with: UserId) {
+        let mut state = self.state();
+        let owner_id = state
+            .roles
+            .iter()
+            .find(|role| role.role_id == role_id.get() as i64)
+            .map_or(0, |role| role.user_id);
+        let id = state.next_id();
+        state.shares.push(BoosterRoleShare {
+            id,
+            guild_id: guild_id.get() as i64,
+            role_id: role_id.get() as i64,
+            owner_id,
+            shared_with_id: shared_with.get() as i64,
+            shared_at: Some(Utc::now()),
+            expires_at: None,
+            is_active: true,
+        });
+    }
+
+    /// Blacklist matches recorded so far
+    pub fn violations(&self) -> Vec<(UserId, String)> {
+        self.state().violations.clone()
+    }
+}
+
+#[async_trait]
+impl BoosterRoleRepo for MemoryRepo {
+    async fn get(&self, guild_id: GuildId, user_id: UserId) -> RepoResult<Option<BoosterRole>> {
+        Ok(self
+            .state()
+            .role_mut(guild_id, user_id)
+            .map(|role| role.clone()))
+    }
+
+    async fn get_all_for_guild(&self, guild_id: GuildId) -> RepoResult<Vec<BoosterRole>> {
+        Ok(self
+            .state()
+            .roles
+            .iter()
+            .rev()
+            .filter(|role| role.guild_id == guild_id.get() as i64)
+            .cloned()
+            .collect())
+    }
+
+    async fn count_for_guild(&self, guild_id: GuildId) -> RepoResult<i64> {
+        Ok(self.get_all_for_guild(guild_id).await?.len() as i64)
+    }
+
+    async fn create(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+        role_name: &str,
+        primary_color: &str,
+        secondary_color: Option<&str>,
+    ) -> RepoResult<()> {
+        let mut state = self.state();
+        if let Some(previous) = state.role_mut(guild_id, user_id).map(|role| role.role_id) {
+            if previous != role_id.get() as i64 {
+                state.end_shares(guild_id, RoleId::new(previous as u64));
+            }
+            state.roles.retain(|role| {
+                !(role.guild_id == guild_id.get() as i64 && role.user_id == user_id.get() as i64)
+            });
+        }
+
+        let id = state.next_id();
+        state.roles.push(BoosterRole {
+            id,
+            guild_id: guild_id.get() as i64,
+            user_id: user_id.get() as i64,
+            role_id: role_id.get() as i64,
+            role_name: role_name.to_string(),
+            primary_color: primary_color.to_string(),
+            secondary_color: secondary_color.map(str::to_string),
+            created_at: Some(Utc::now()),
+            updated_at: Some(Utc::now()),
+            icon_url: None,
+        });
+        Ok(())
+    }
+
+    async fn update(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_name: &str,
+        primary_color: &str,
+        secondary_color: Option<&str>,
+    ) -> RepoResult<()> {
+        if let Some(role) = self.state().role_mut(guild_id, user_id) {
+            role.role_name = role_name.to_string();
+            role.primary_color = primary_color.to_string();
+            role.secondary_color = secondary_color.map(str::to_string);
+            role.updated_at = Some(Utc::now());
+        }
+        Ok(())
+    }
+
+    async fn update_color(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        primary_color: &str,
+        secondary_color: Option<&str>,
+    ) -> RepoResult<()> {
+        if let Some(role) = self.state().role_mut(guild_id, user_id) {
+            role.primary_color = primary_color.to_string();
+            role.secondary_color = secondary_color.map(str::to_string);
+            role.updated_at = Some(Utc::now());
+        }
+        Ok(())
+    }
+
+    async fn set_icon(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        icon_url: Option<&str>,
+    ) -> RepoResult<()> {
+        if let Some(role) = self.state().role_mut(guild_id, user_id) {
+            role.icon_url = icon_url.map(str::to_string);
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, guild_id: GuildId, user_id: UserId) -> RepoResult<bool> {
+        let mut state = self.state();
+        let Some(role_id) = state.role_mut(guild_id, user_id).map(|role| role.role_id) else {
+            return Ok(false);
+        };
+        state.roles.retain(|role| role.role_id != role_id);
+        state.end_shares(guild_id, RoleId::new(role_id as u64));
+        Ok(true)
+    }
+
+    async fn delete_by_role(&self, guild_id: GuildId, role_id: RoleId) -> RepoResult<bool> {
+        let mut state = self.state();
+        let before = state.roles.len();
+        state.roles.retain(|role| {
+            !(role.guild_id == guild_id.get() as i64 && role.role_id == role_id.get() as i64)
+        });
+        state.end_shares(guild_id, role_id);
+        Ok(state.roles.len() < before)
+    }
+
+    async fn last_rename(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> RepoResult<Option<BoosterRenameHistory>> {
+        Ok(self
+            .state()
+            .renames
+            .iter()
+            .rev()
+            .find(|rename| {
+                rename.guild_id == guild_id.get() as i64 && rename.user_id == user_id.get() as i64
+            })
+            .cloned())
+    }
+
+    async fn record_rename(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        old_name: &str,
+        new_name: &str,
+    ) -> RepoResult<()> {
+        let mut state = self.state();
+        let id = state.next_id();
+        state.renames.push(BoosterRenameHistory {
+            id,
+            guild_id: guild_id.get() as i64,
+            user_id: user_id.get() as i64,
+            old_name: old_name.to_string(),
+            new_name: new_name.to_string(),
+            renamed_at: Utc::now(),
+        });
+        Ok(())
+    }
+
+    async fn count_role_shares(&self, guild_id: GuildId, role_id: RoleId) -> RepoResult<i64> {
+        Ok(self
+            .state()
+            .active_shares(guild_id, |s| s.role_id == role_id.get() as i64)
+            .len() as i64)
+    }
+
+    async fn count_user_shares(&self, guild_id: GuildId, user_id: UserId) -> RepoResult<i64> {
+        Ok(self.shares_received(guild_id, user_id).await?.len() as i64)
+    }
+
+    async fn is_shared_with(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        user_id: UserId,
+    ) -> RepoResult<bool> {
+        Ok(self
+            .state()
+            .share_mut(guild_id, role_id, user_id)
+            .is_some_and(|share| share.is_active))
+    }
+
+    async fn create_share(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        owner_id: UserId,
+        shared_with: UserId,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> RepoResult<()> {
+        let mut state = self.state();
+        if let Some(share) = state.share_mut(guild_id, role_id, shared_with) {
+            share.owner_id = owner_id.get() as i64;
+            share.shared_at = Some(Utc::now());
+            share.expires_at = expires_at;
+            share.is_active = true;
+            return Ok(());
+        }
+
+        let id = state.next_id();
+        state.shares.push(BoosterRoleShare {
+            id,
+            guild_id: guild_id.get() as i64,
+            role_id: role_id.get() as i64,
+            owner_id: owner_id.get() as i64,
+            shared_with_id: shared_with.get() as i64,
+            shared_at: Some(Utc::now()),
+            expires_at,
+            is_active: true,
+        });
+        Ok(())
+    }
+
+    async fn remove_share(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        shared_with: UserId,
+    ) -> RepoResult<bool> {
+        let mut state = self.state();
+        match state.share_mut(guild_id, role_id, shared_with) {
+            Some(share) if share.is_active => {
+                share.is_active = false;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn shares_received(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> RepoResult<Vec<BoosterRoleShare>> {
+        Ok(self
+            .state()
+            .active_shares(guild_id, |s| s.shared_with_id == user_id.get() as i64))
+    }
+
+    async fn shares_given(
+        &self,
+        guild_id: GuildId,
+        owner_id: UserId,
+    ) -> RepoResult<Vec<BoosterRoleShare>> {
+        Ok(self
+            .state()
+            .active_shares(guild_id, |s| s.owner_id == owner_id.get() as i64))
+    }
+
+    async fn active_shares(&self, guild_id: GuildId) -> RepoResult<Vec<BoosterRoleShare>> {
+        Ok(self.state().active_shares(guild_id, |_| true))
+    }
+
+    async fn link(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> RepoResult<Option<BoosterRoleLink>> {
+        Ok(self.state().links.get(&(guild_id, user_id)).cloned())
+    }
+
+    async fn set_link(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+        linked_by: UserId,
+    ) -> RepoResult<()> {
+        let mut state = self.state();
+        let id = state.next_id();
+        state.links.insert(
+            (guild_id, user_id),
+            BoosterRoleLink {
+                id,
+                guild_id: guild_id.get() as i64,
+                user_id: user_id.get() as i64,
+                linked_role_id: role_id.get() as i64,
+                linked_by: linked_by.get() as i64,
+                created_at: Some(Utc::now()),
+            },
+        );
+        Ok(())
+    }
+
+    async fn remove_link(&self, guild_id: GuildId, user_id: UserId) -> RepoResult<bool> {
+        Ok(self.state().links.remove(&(guild_id, user_id)).is_some())
+    }
+
+    async fn record_award_grant(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+    ) -> RepoResult<()> {
+        self.state().award_grants.push((
+            guild_id,
+            BoosterAwardGrant {
+                user_id: user_id.get() as i64,
+                role_id: role_id.get() as i64,
+                granted_at: Utc::now(),
+            },
+        ));
+        Ok(())
+    }
+
+    async fn recent_award_grants(
+        &self,
+        guild_id: GuildId,
+        limit: i64,
+    ) -> RepoResult<Vec<BoosterAwardGrant>> {
+        Ok(self
+            .state()
+            .award_grants
+            .iter()
+            .rev()
+            .filter(|(guild, _)| *guild == guild_id)
+            .take(limit.max(0) as usize)
+            .map(|(_, grant)| grant.clone())
+            .collect())
+    }
+}
+
+#[async_trait]
+impl GuildSettingsRepo for MemoryRepo {
+    async fn booster_limit(&self, guild_id: GuildId) -> RepoResult<Option<i32>> {
+        Ok(self.state().limits.get(&guild_id).copied())
+    }
+
+    async fn set_booster_limit(
+        &self,
+        guild_id: GuildId,
+        max_roles: i32,
+        _set_by: UserId,
+    ) -> RepoResult<()> {
+        self.state().limits.insert(guild_id, max_roles);
+        Ok(())
+    }
+
+    async fn booster_options(&self, guild_id: GuildId) -> RepoResult<GuildBoosterOptions> {
+        Ok(self
+            .state()
+            .options
+            .get(&guild_id)
+            .copied()
+            .unwrap_or_default())
+    }
+
+    async fn set_booster_options(
+        &self,
+        guild_id: GuildId,
+        options: GuildBoosterOptions,
+        _set_by: UserId,
+    ) -> RepoResult<()> {
+        self.state().options.insert(guild_id, options);
+        Ok(())
+    }
+
+    async fn base_role(&self, guild_id: GuildId) -> RepoResult<Option<RoleId>> {
+        Ok(self.state().base_roles.get(&guild_id).copied())
+    }
+
+    async fn set_base_role(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        _set_by: UserId,
+    ) -> RepoResult<()> {
+        self.state().base_roles.insert(guild_id, role_id);
+        Ok(())
+    }
+
+    async fn remove_base_role(&self, guild_id: GuildId) -> RepoResult<bool> {
+        Ok(self.state().base_roles.remove(&guild_id).is_some())
+    }
+
+    async fn rename_cooldown(&self, guild_id: GuildId) -> RepoResult<Option<i64>> {
+        Ok(self.state().rename_cooldowns.get(&guild_id).copied())
+    }
+
+    async fn set_rename_cooldown(
+        &self,
+        guild_id: GuildId,
+        cooldown_minutes: i64,
+        _set_by: UserId,
+    ) -> RepoResult<()> {
+        self.state()
+            .rename_cooldowns
+            .insert(guild_id, cooldown_minutes);
+        Ok(())
+    }
+
+    async fn sharing_limits(&self, guild_id: GuildId) -> RepoResult<Option<GuildSharingLimit>> {
+        Ok(self.state().sharing_limits.get(&guild_id).map(
+            |&(max_members_per_role, max_shared_roles_per_member)| GuildSharingLimit {
+                id: 0,
+                guild_id: guild_id.get() as i64,
+                max_members_per_role,
+                max_shared_roles_per_member,
+                set_by: 0,
+                created_at: None,
+                updated_at: None,
+            },
+        ))
+    }
+
+    async fn set_sharing_limits(
+        &self,
+        guild_id: GuildId,
+        max_members_per_role: i32,
+        max_shared_roles_per_member: i32,
+        _set_by: UserId,
+    ) -> RepoResult<()> {
+        self.state().sharing_limits.insert(
+            guild_id,
+            (max_members_per_role, max_shared_roles_per_member),
+        );
+        Ok(())
+    }
+
+    async fn set_share_policy(
+        &self,
+        guild_id: GuildId,
+        policy: RevalidationPolicy,
+        _set_by: UserId,
+    ) -> RepoResult<()> {
+        self.state().share_policies.insert(guild_id, policy);
+        Ok(())
+    }
+
+    async fn award(&self, guild_id: GuildId) -> RepoResult<Option<GuildBoosterAward>> {
+        Ok(self.state().awards.get(&guild_id).cloned())
+    }
+
+    async fn set_award(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        remove_on_unboost: bool,
+        announce_boosts: bool,
+        set_by: UserId,
+    ) -> RepoResult<()> {
+        self.state().awards.insert(
+            guild_id,
+            GuildBoosterAward {
+                id: 0,
+                guild_id: guild_id.get() as i64,
+                award_role_id: role_id.get() as i64,
+                set_by: set_by.get() as i64,
+                created_at: None,
+                updated_at: None,
+                remove_on_unboost,
+                announce_boosts,
+            },
+        );
+        Ok(())
+    }
+
+    async fn remove_award(&self, guild_id: GuildId) -> RepoResult<bool> {
+        Ok(self.state().awards.remove(&guild_id).is_some())
+    }
+
+    /// Under the default severity policy
+    async fn check_role_name(
+        &self,
+        _guild_id: GuildId,
+        user_id: UserId,
+        name: &str,
+        command: Option<&str>,
+        filters: &NameFilters,
+    ) -> RepoResult<NameCheck> {
+        let check = match strongest_match(name, &filters.global) {
+            Some((word, _)) => NameCheck::Reject {
+                word: word.to_string(),
+                alert: false,
+            },
+            None => enforce(name, &filters.guild, EnforcementPolicy::default()),
+        };
+        if command.is_some() && check != NameCheck::Clean {
+            self.state().violations.push((user_id, name.to_string()));
+        }
+        Ok(check)
+    }
+}
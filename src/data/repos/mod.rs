@@ -0,0 +1,84 @@
+//! Storage behind traits, so commands ask for what they need instead of how
+//! SQLite stores it. Booster roles, their shares, links and award grants, and
+//! the guild settings that govern them are covered, which is all the create,
+//! rename, color, icon, share, link and award commands touch apart from audit
+//! logs and user preferences. The template, filter, schedule, rules, lock,
+//! admin, list and remove commands still reach their tables through the
+//! models.
+
+mod booster_roles;
+mod guild_settings;
+#[cfg(test)]
+pub mod memory;
+
+pub use booster_roles::{BoosterRoleRepo, SqliteBoosterRoleRepo};
+pub use guild_settings::{GuildSettingsRepo, SqliteGuildSettingsRepo};
+
+use crate::data::models::BoosterLimitUsage;
+use serenity::all::GuildId;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+/// A repository failure, described without reference to the store behind it
+#[derive(Debug)]
+pub enum RepoError {
+    /// The record the operation needed isn't there
+    NotFound,
+    /// The write clashes with what's stored, such as a duplicate entry
+    Conflict(String),
+    /// The store couldn't carry out the operation
+    Unavailable(String),
+}
+
+impl std::fmt::Display for RepoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepoError::NotFound => write!(f, "record not found"),
+            RepoError::Conflict(msg) => write!(f, "conflicting record: {}", msg),
+            RepoError::Unavailable(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for RepoError {}
+
+impl From<sqlx::Error> for RepoError {
+    fn from(error: sqlx::Error) -> Self {
+        match error {
+            sqlx::Error::RowNotFound => RepoError::NotFound,
+            sqlx::Error::Database(e) if e.kind() != sqlx::error::ErrorKind::Other => {
+                RepoError::Conflict(e.to_string())
+            }
+            other => RepoError::Unavailable(other.to_string()),
+        }
+    }
+}
+
+pub type RepoResult<T> = Result<T, RepoError>;
+
+/// The repositories a command works through
+#[derive(Debug, Clone)]
+pub struct Repos {
+    pub booster_roles: Arc<dyn BoosterRoleRepo>,
+    pub guild_settings: Arc<dyn GuildSettingsRepo>,
+}
+
+impl Repos {
+    pub fn sqlite(pool: SqlitePool) -> Self {
+        Self {
+            booster_roles: Arc::new(SqliteBoosterRoleRepo::new(pool.clone())),
+            guild_settings: Arc::new(SqliteGuildSettingsRepo::new(pool)),
+        }
+    }
+
+    /// Booster roles against the guild's cap, or `None` when it has no cap
+    pub async fn limit_usage(&self, guild_id: GuildId) -> RepoResult<Option<BoosterLimitUsage>> {
+        let max = match self.guild_settings.booster_limit(guild_id).await? {
+            Some(max) if max > 0 => max,
+            _ => return Ok(None),
+        };
+        let current = self.booster_roles.count_for_guild(guild_id).await?;
+
+        Ok(Some(BoosterLimitUsage { current, max }))
+    }
+}
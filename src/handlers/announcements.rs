@@ -0,0 +1,153 @@
+use crate::data::models::{Announcement, AnnouncementDelivery, NotificationSubscription};
+use crate::utils::announcements::{pacing_delay, DeliveryStatus, NotificationCategory};
+use crate::utils::EmbedColor;
+use serenity::all::{
+    CreateAllowedMentions, CreateEmbed, CreateEmbedFooter, CreateMessage, Http, Timestamp, UserId,
+};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+type DeliveryError = Box<dyn std::error::Error + Send + Sync>;
+
+/// DMs feature announcements to members who opted in
+pub struct AnnouncementHandler;
+
+impl AnnouncementHandler {
+    /// Finish deliveries a restart interrupted
+    pub fn spawn_resume(http: Arc<Http>, db_pool: SqlitePool) {
+        tokio::spawn(async move {
+            let interrupted = match Announcement::in_progress(&db_pool).await {
+                Ok(interrupted) => interrupted,
+                Err(e) => {
+                    tracing::error!(error = ?e, "Failed to load interrupted announcements");
+                    return;
+                }
+            };
+
+            for announcement in interrupted {
+                tracing::info!(
+                    announcement_id = announcement.id,
+                    "Resuming announcement delivery"
+                );
+                if let Err(e) = Self::deliver(&http, &db_pool, announcement.id).await {
+                    tracing::error!(
+                        announcement_id = announcement.id,
+                        error = ?e,
+                        "Announcement delivery failed"
+                    );
+                }
+            }
+        });
+    }
+
+    /// Deliver an announcement in the background
+    pub fn spawn_delivery(http: Arc<Http>, db_pool: SqlitePool, announcement_id: i64) {
+        tokio::spawn(async move {
+            if let Err(e) = Self::deliver(&http, &db_pool, announcement_id).await {
+                tracing::error!(
+                    announcement_id = announcement_id,
+                    error = ?e,
+                    "Announcement delivery failed"
+                );
+            }
+        });
+    }
+
+    /// DM every pending recipient, recording each result before moving on so
+    /// a crash resumes where it stopped instead of re-sending
+    pub async fn deliver(
+        http: &Http,
+        pool: &SqlitePool,
+        announcement_id: i64,
+    ) -> Result<(), DeliveryError> {
+        let Some(announcement) = Announcement::get(pool, announcement_id).await? else {
+            return Ok(());
+        };
+        let pending = AnnouncementDelivery::pending(pool, announcement_id).await?;
+
+        let embed = announcement_embed(&announcement.title, &announcement.body);
+        for (index, user) in pending.into_iter().enumerate() {
+            let user_id = UserId::new(user);
+
+            // Opting out after the announcement was queued still counts
+            let status = if !NotificationSubscription::is_subscribed_anywhere(
+                pool,
+                user_id,
+                NotificationCategory::FeatureUpdates,
+            )
+            .await?
+            {
+                DeliveryStatus::Skipped
+            } else {
+                match send_dm(http, user_id, embed.clone()).await {
+                    Ok(()) => DeliveryStatus::Sent,
+                    Err(e) => {
+                        tracing::debug!(
+                            user_id = %user_id,
+                            error = ?e,
+                            "Could not DM announcement"
+                        );
+                        DeliveryStatus::Failed
+                    }
+                }
+            };
+            AnnouncementDelivery::mark(pool, announcement_id, user_id, status).await?;
+
+            if status != DeliveryStatus::Skipped {
+                tokio::time::sleep(pacing_delay(index)).await;
+            }
+        }
+
+        Announcement::finish(pool, announcement_id).await?;
+        let tally = AnnouncementDelivery::tally(pool, announcement_id).await?;
+
+        tracing::info!(
+            announcement_id = announcement_id,
+            sent = tally.sent,
+            failed = tally.failed,
+            skipped = tally.skipped,
+            "Announcement delivered"
+        );
+
+        let report = CreateEmbed::new()
+            .title("📣 Announcement Delivered")
+            .description(format!(
+                "**{}** (#{})\n{}",
+                announcement.title,
+                announcement_id,
+                tally.describe()
+            ))
+            .color(EmbedColor::Success.value())
+            .timestamp(Timestamp::now());
+        if let Err(e) = send_dm(http, UserId::new(announcement.created_by as u64), report).await {
+            tracing::warn!(error = ?e, "Could not send announcement report to owner");
+        }
+
+        Ok(())
+    }
+}
+
+/// The DM members receive; also used for the owner's preview
+pub fn announcement_embed(title: &str, body: &str) -> CreateEmbed {
+    CreateEmbed::new()
+        .title(format!("📣 {}", title))
+        .description(body)
+        .color(EmbedColor::Primary.value())
+        .footer(CreateEmbedFooter::new(
+            "You opted in to feature updates • turn off with /boosterrole notifications",
+        ))
+        .timestamp(Timestamp::now())
+}
+
+async fn send_dm(http: &Http, user_id: UserId, embed: CreateEmbed) -> serenity::Result<()> {
+    let channel = user_id.create_dm_channel(http).await?;
+    channel
+        .send_message(
+            http,
+            CreateMessage::new()
+                .embed(embed)
+                .allowed_mentions(CreateAllowedMentions::new()),
+        )
+        .await?;
+    Ok(())
+}
@@ -1,5 +1,21 @@
-use crate::data::models::{BoosterRole, BoosterRoleLink, GuildBoosterAward};
-use serenity::all::{Context, GuildId, GuildMemberUpdateEvent, Member, Ready, Role};
+use crate::bot::Error;
+use crate::data::models::{
+    BoosterAwardGrant, BoosterRole, BoosterRoleArchive, BoosterRoleLink, BoosterRoleLock,
+    ColorRotation, GuildBoosterAward, GuildBoosterLimit, GuildJoinLogChannel, GuildPremiumRole,
+    GuildPurge, GuildQuietHours,
+};
+use crate::utils::audit::{booster_audit_embed, send_booster_audit};
+use crate::utils::boost_states::{BoostChange, BoostStateCache};
+use crate::utils::guild_availability::STARTUP_GUILD_PACING;
+use crate::utils::quiet_hours::{AutomatedMessage, QuietDecision};
+use crate::utils::role_archive::restore_archived_role;
+use crate::utils::{
+    is_eligible, load_eligibility_config, EmbedColor, GuildAvailability, RoleManager,
+};
+use serenity::all::{
+    Context, CreateEmbed, CreateMessage, EditRole, GuildId, GuildMemberUpdateEvent, Http,
+    HttpError, Member, Mentionable, Ready, Role, RoleId, Timestamp, UserId,
+};
 use sqlx::SqlitePool;
 use std::sync::Arc;
 
@@ -13,37 +29,87 @@ impl BoostHandler {
         Self { db_pool }
     }
 
-    /// Handle boost status changes for a member
-    pub async fn handle_boost_change(&self, ctx: &Context, event: &GuildMemberUpdateEvent) {
+    /// Handle boost status changes for a member. `old_member` is the cached
+    /// state from before the update and `new_member` the cached state after
+    /// it, when the cache had them.
+    pub async fn handle_boost_change(
+        &self,
+        ctx: &Context,
+        states: &BoostStateCache,
+        old_member: Option<&Member>,
+        new_member: Option<&Member>,
+        event: &GuildMemberUpdateEvent,
+    ) {
         let guild_id = event.guild_id;
         let user_id = event.user.id;
 
-        // For now, we'll check the database and Discord API directly
-        // since GuildMemberUpdateEvent doesn't contain old member info
-        // This is a simplified approach - in production, you might want to cache member states
+        // The event carries the member's current boost state, so it's compared
+        // with the last one seen rather than fetching the member again
+        let change = states
+            .observe(guild_id, user_id, event.premium_since.is_some())
+            .await;
+        let roles_changed = old_member.is_none_or(|old| old.roles != event.roles);
+
+        // Award roles are for boosters only, whatever the eligibility mode
+        match change {
+            BoostChange::Unchanged => {}
+            BoostChange::Ended | BoostChange::FirstSeen { boosting: false } => {
+                self.remove_award_role(ctx, guild_id, user_id, &event.roles)
+                    .await;
+                // Without a previous state the premium role may have been
+                // given by hand, so it's only taken back on a seen unboost
+                if change == BoostChange::Ended {
+                    self.remove_premium_role(ctx, guild_id, user_id, &event.roles)
+                        .await;
+                }
+            }
+            BoostChange::Started | BoostChange::FirstSeen { boosting: true } => {
+                self.assign_premium_role(ctx, guild_id, user_id, &event.roles)
+                    .await;
+                // Granting and announcing the award needs the full member
+                let member = match new_member {
+                    Some(member) => Some(member.clone()),
+                    None => guild_id.member(&ctx.http, user_id).await.ok(),
+                };
+                if let Some(member) = member {
+                    self.check_award_assignment(ctx, old_member, &member).await;
+                }
+                self.restore_archived_role(ctx, guild_id, user_id).await;
+            }
+        }
 
-        // Get the member's current premium status
-        let current_member = match guild_id.member(&ctx.http, user_id).await {
-            Ok(member) => member,
-            Err(_) => return, // Member not found or other error
+        // Nicknames, avatars and the like can't affect eligibility
+        if change == BoostChange::Unchanged && !roles_changed {
+            return;
+        }
+
+        let config = match load_eligibility_config(&self.db_pool, guild_id).await {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::error!(
+                    guild_id = %guild_id,
+                    error = ?e,
+                    "Failed to load eligibility config, skipping boost cleanup"
+                );
+                return;
+            }
         };
 
-        // If member currently has premium status, no cleanup needed
-        if current_member.premium_since.is_some() {
+        // Members still eligible (boosting, or holding the premium role where
+        // the guild allows it) keep their custom role
+        if is_eligible(event.premium_since, &event.roles, &config) {
             return;
         }
 
-        // Member doesn't have premium - check if they have a booster role to clean up
+        // Member is no longer eligible - check if they have a booster role to clean up
 
         tracing::info!(
             user_id = %user_id,
             guild_id = %guild_id,
-            "Member lost boost status, cleaning up booster role"
+            mode = config.effective_mode().as_str(),
+            "Member lost booster role eligibility, cleaning up booster role"
         );
 
-        // Remove award role if configured
-        self.remove_award_role(ctx, guild_id, &current_member).await;
-
         // Get the booster role from database
         let booster_role = match BoosterRole::get(&self.db_pool, guild_id, user_id).await {
             Ok(Some(role)) => role,
@@ -68,25 +134,30 @@ impl BoostHandler {
 
         let role_id = serenity::all::RoleId::new(booster_role.role_id as u64);
 
-        // Remove the role from Discord
-        if let Err(e) = guild_id.member(&ctx.http, user_id).await {
-            tracing::warn!(
+        // Keep the role's look so it can come back if they boost again
+        if let Err(e) = BoosterRoleArchive::archive(&self.db_pool, &booster_role).await {
+            tracing::error!(
                 user_id = %user_id,
                 guild_id = %guild_id,
                 error = ?e,
-                "Failed to get member for role removal"
+                "Failed to archive booster role before cleanup"
             );
-        } else if let Ok(member) = guild_id.member(&ctx.http, user_id).await {
-            if member.roles.contains(&role_id) {
-                if let Err(e) = member.remove_role(&ctx.http, role_id).await {
-                    tracing::error!(
-                        user_id = %user_id,
-                        guild_id = %guild_id,
-                        role_id = %role_id,
-                        error = ?e,
-                        "Failed to remove booster role from member"
-                    );
-                }
+        }
+
+        // Remove the role from Discord
+        if event.roles.contains(&role_id) {
+            if let Err(e) = ctx
+                .http
+                .remove_member_role(guild_id, user_id, role_id, None)
+                .await
+            {
+                tracing::error!(
+                    user_id = %user_id,
+                    guild_id = %guild_id,
+                    role_id = %role_id,
+                    error = ?e,
+                    "Failed to remove booster role from member"
+                );
             }
         }
 
@@ -133,6 +204,15 @@ impl BoostHandler {
             );
         }
 
+        if let Err(e) = ColorRotation::disable(&self.db_pool, guild_id, user_id).await {
+            tracing::error!(
+                user_id = %user_id,
+                guild_id = %guild_id,
+                error = ?e,
+                "Failed to stop color rotation"
+            );
+        }
+
         tracing::info!(
             user_id = %user_id,
             guild_id = %guild_id,
@@ -141,8 +221,9 @@ impl BoostHandler {
         );
     }
 
-    /// Clean up orphaned roles (roles in database but not in Discord)
-    pub async fn cleanup_orphaned_roles(&self, ctx: &Context, guild_id: GuildId) {
+    /// Clean up orphaned roles (roles in database but not in Discord).
+    /// Returns how many records were removed, or `None` if the pass failed.
+    pub async fn cleanup_orphaned_roles(&self, http: &Http, guild_id: GuildId) -> Option<usize> {
         tracing::debug!(
             guild_id = %guild_id,
             "Starting orphaned role cleanup"
@@ -157,16 +238,16 @@ impl BoostHandler {
                     error = ?e,
                     "Failed to fetch booster roles for orphan cleanup"
                 );
-                return;
+                return None;
             }
         };
 
         if booster_roles.is_empty() {
-            return;
+            return Some(0);
         }
 
         // Get roles via HTTP API since cache access isn't Send-safe
-        let guild_roles = match ctx.http.get_guild_roles(guild_id).await {
+        let guild_roles = match http.get_guild_roles(guild_id).await {
             Ok(roles) => roles,
             Err(e) => {
                 tracing::error!(
@@ -174,7 +255,7 @@ impl BoostHandler {
                     error = ?e,
                     "Failed to fetch guild roles for orphan cleanup"
                 );
-                return;
+                return None;
             }
         };
 
@@ -227,25 +308,168 @@ impl BoostHandler {
                 "Cleaned up orphaned booster roles"
             );
         }
+
+        Some(orphaned_count)
     }
 
-    /// Handle ready event - start cleanup tasks
-    pub async fn on_ready(&self, ctx: &Context, ready: &Ready) {
+    /// Handle ready event - start cleanup tasks for guilds that are online.
+    /// Guilds Discord reports as unavailable are deferred until they come back.
+    pub async fn on_ready(&self, ctx: &Context, ready: &Ready, availability: &GuildAvailability) {
+        let guilds: Vec<(GuildId, bool)> =
+            ready.guilds.iter().map(|g| (g.id, g.unavailable)).collect();
+        let (available, deferred) = availability.ready(&guilds);
+
         tracing::info!(
             bot_user = %ready.user.name,
+            available = available.len(),
+            deferred = deferred,
             "Boost handler ready, starting orphaned role cleanup"
         );
 
-        // Clean up orphaned roles for all guilds on startup
-        let guilds: Vec<GuildId> = ready.guilds.iter().map(|g| g.id).collect();
-
-        for guild_id in guilds {
-            self.cleanup_orphaned_roles(ctx, guild_id).await;
+        for guild_id in available {
+            self.run_guild_startup(ctx, guild_id, availability).await;
         }
 
         tracing::info!("Initial orphaned role cleanup completed");
     }
 
+    /// Per-guild startup work, paced and run one guild at a time
+    pub async fn run_guild_startup(
+        &self,
+        ctx: &Context,
+        guild_id: GuildId,
+        availability: &GuildAvailability,
+    ) {
+        let _permit = availability.startup_permit().await;
+        self.cleanup_orphaned_roles(&ctx.http, guild_id).await;
+        tokio::time::sleep(STARTUP_GUILD_PACING).await;
+    }
+
+    /// Delete a departed member's booster role and purge their booster data.
+    /// Members they shared a role with lose it too.
+    pub async fn on_member_leave(&self, ctx: &Context, guild_id: GuildId, user_id: UserId) {
+        let booster_role = match BoosterRole::get(&self.db_pool, guild_id, user_id).await {
+            Ok(role) => role,
+            Err(e) => {
+                tracing::error!(
+                    user_id = %user_id,
+                    guild_id = %guild_id,
+                    error = ?e,
+                    "Failed to fetch booster role for departed member"
+                );
+                return;
+            }
+        };
+
+        let mut deleted_role = None;
+        let mut keep_role = false;
+        if let Some(role) = &booster_role {
+            let role_id = RoleId::new(role.role_id as u64);
+            match RoleManager::delete_role(ctx, guild_id, role_id).await {
+                Ok(()) => deleted_role = Some(role_id),
+                Err(e) if is_not_found(&e) => {
+                    tracing::debug!(
+                        guild_id = %guild_id,
+                        role_id = %role_id,
+                        "Departed member's booster role was already deleted"
+                    );
+                }
+                Err(e) => {
+                    // Keep the record so cleanup can find the role later
+                    keep_role = true;
+                    tracing::warn!(
+                        guild_id = %guild_id,
+                        role_id = %role_id,
+                        error = ?e,
+                        "Could not delete departed member's booster role, keeping its record"
+                    );
+                }
+            }
+        }
+
+        let purge = match GuildPurge::member(&self.db_pool, guild_id, user_id, keep_role).await {
+            Ok(purge) => purge,
+            Err(e) => {
+                tracing::error!(
+                    user_id = %user_id,
+                    guild_id = %guild_id,
+                    error = ?e,
+                    "Failed to purge departed member's booster data"
+                );
+                return;
+            }
+        };
+
+        // A deleted role is already gone from everyone it was shared with
+        for share in purge
+            .ended_shares
+            .iter()
+            .filter(|share| share.owner_id == user_id.get() as i64)
+        {
+            let role_id = RoleId::new(share.role_id as u64);
+            if deleted_role == Some(role_id) {
+                continue;
+            }
+            let recipient = UserId::new(share.shared_with_id as u64);
+            if let Err(e) = ctx
+                .http
+                .remove_member_role(
+                    guild_id,
+                    recipient,
+                    role_id,
+                    Some("Role owner left the server"),
+                )
+                .await
+            {
+                tracing::debug!(
+                    guild_id = %guild_id,
+                    role_id = %role_id,
+                    user_id = %recipient,
+                    error = ?e,
+                    "Could not remove shared role from member"
+                );
+            }
+        }
+
+        if booster_role.is_some() || purge.rows_removed > 0 || !purge.ended_shares.is_empty() {
+            tracing::info!(
+                user_id = %user_id,
+                guild_id = %guild_id,
+                role_deleted = deleted_role.is_some(),
+                rows_removed = purge.rows_removed,
+                shares_ended = purge.ended_shares.len(),
+                "Cleaned up booster data for departed member"
+            );
+        }
+    }
+
+    /// Purge everything stored for a guild the bot was removed from
+    pub async fn on_guild_removed(&self, guild_id: GuildId) {
+        match GuildPurge::guild(&self.db_pool, guild_id).await {
+            Ok(removed) => {
+                let rows: u64 = removed.iter().map(|(_, rows)| rows).sum();
+                let tables = removed
+                    .iter()
+                    .map(|(table, rows)| format!("{}={}", table, rows))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                tracing::info!(
+                    guild_id = %guild_id,
+                    rows,
+                    tables = %tables,
+                    "Purged data for removed guild"
+                );
+            }
+            Err(e) => {
+                tracing::error!(
+                    guild_id = %guild_id,
+                    error = ?e,
+                    "Failed to purge data for removed guild"
+                );
+            }
+        }
+    }
+
     /// Handle role deletions to clean up database
     pub async fn on_guild_role_delete(
         &self,
@@ -262,22 +486,16 @@ impl BoostHandler {
         // Check if this was a booster role and clean up database
         let role_id_i64 = removed_role_id.get() as i64;
 
-        // Find and remove any booster role records with this role ID
-        match sqlx::query("DELETE FROM booster_roles WHERE guild_id = ? AND role_id = ?")
-            .bind(guild_id.get() as i64)
-            .bind(role_id_i64)
-            .execute(&*self.db_pool)
-            .await
-        {
-            Ok(result) => {
-                if result.rows_affected() > 0 {
-                    tracing::info!(
-                        guild_id = %guild_id,
-                        role_id = %removed_role_id,
-                        "Cleaned up booster role database record after role deletion"
-                    );
-                }
+        // Find and remove any booster role records with this role ID, along with its shares
+        match BoosterRole::delete_by_role(&self.db_pool, guild_id, removed_role_id).await {
+            Ok(true) => {
+                tracing::info!(
+                    guild_id = %guild_id,
+                    role_id = %removed_role_id,
+                    "Cleaned up booster role database record after role deletion"
+                );
             }
+            Ok(false) => {}
             Err(e) => {
                 tracing::error!(
                     guild_id = %guild_id,
@@ -288,6 +506,16 @@ impl BoostHandler {
             }
         }
 
+        // Locks are keyed by role, so they go with it
+        if let Err(e) = BoosterRoleLock::remove(&self.db_pool, guild_id, removed_role_id).await {
+            tracing::error!(
+                guild_id = %guild_id,
+                role_id = %removed_role_id,
+                error = ?e,
+                "Failed to clean up role lock after role deletion"
+            );
+        }
+
         // Also clean up any role links with this role ID
         match sqlx::query(
             "DELETE FROM booster_role_links WHERE guild_id = ? AND linked_role_id = ?",
@@ -317,8 +545,151 @@ impl BoostHandler {
         }
     }
 
-    /// Check and assign award role for new boosters
-    #[allow(dead_code)]
+    /// Revert manual Discord-side edits to locked aspects of a booster role
+    pub async fn on_guild_role_update(&self, ctx: &Context, role: &Role) {
+        let role_lock = match BoosterRoleLock::get(&self.db_pool, role.guild_id, role.id).await {
+            Ok(Some(role_lock)) => role_lock,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::error!(
+                    guild_id = %role.guild_id,
+                    role_id = %role.id,
+                    error = ?e,
+                    "Failed to load role lock for role update"
+                );
+                return;
+            }
+        };
+
+        let mut revert = EditRole::new();
+        let mut needs_revert = false;
+
+        if role_lock.name_locked {
+            if let Some(ref locked_name) = role_lock.locked_name {
+                if &role.name != locked_name {
+                    revert = revert.name(locked_name);
+                    needs_revert = true;
+                }
+            }
+        }
+
+        if role_lock.color_locked {
+            if let Some(locked_color) = role_lock.locked_color {
+                if role.colour.0 as i64 != locked_color {
+                    revert = revert.colour(locked_color as u64);
+                    needs_revert = true;
+                }
+            }
+        }
+
+        if !needs_revert {
+            return;
+        }
+
+        match role.guild_id.edit_role(&ctx.http, role.id, revert).await {
+            Ok(_) => {
+                tracing::info!(
+                    guild_id = %role.guild_id,
+                    role_id = %role.id,
+                    "Reverted manual edit to locked booster role"
+                );
+            }
+            Err(e) => {
+                tracing::error!(
+                    guild_id = %role.guild_id,
+                    role_id = %role.id,
+                    error = ?e,
+                    "Failed to revert manual edit to locked booster role"
+                );
+            }
+        }
+    }
+
+    /// Recreate the role a returning booster had before their boost lapsed.
+    /// Members who already have a role, or guilds at their role limit, keep
+    /// the archive for `/boosterrole restore` later.
+    pub async fn restore_archived_role(&self, ctx: &Context, guild_id: GuildId, user_id: UserId) {
+        let pool = self.db_pool.as_ref();
+
+        let archive = match BoosterRoleArchive::get(pool, guild_id, user_id).await {
+            Ok(Some(archive)) => archive,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::error!(
+                    user_id = %user_id,
+                    guild_id = %guild_id,
+                    error = ?e,
+                    "Failed to fetch booster role archive"
+                );
+                return;
+            }
+        };
+
+        match BoosterRole::get(pool, guild_id, user_id).await {
+            Ok(None) => {}
+            Ok(Some(_)) => return,
+            Err(e) => {
+                tracing::error!(
+                    user_id = %user_id,
+                    guild_id = %guild_id,
+                    error = ?e,
+                    "Failed to check for an existing booster role before restoring"
+                );
+                return;
+            }
+        }
+
+        match GuildBoosterLimit::check_limit(pool, guild_id).await {
+            Ok(Some(usage)) if usage.is_full() => {
+                tracing::info!(
+                    user_id = %user_id,
+                    guild_id = %guild_id,
+                    "Guild at booster role limit, leaving role archived"
+                );
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!(
+                    guild_id = %guild_id,
+                    error = ?e,
+                    "Failed to check booster role limit before restoring"
+                );
+                return;
+            }
+        }
+
+        match restore_archived_role(ctx, pool, guild_id, user_id, &archive).await {
+            Ok(restored) => {
+                send_booster_audit(
+                    &ctx.http,
+                    pool,
+                    guild_id,
+                    booster_audit_embed(
+                        "♻️ Booster Role Restored",
+                        user_id,
+                        restored.role.mention().to_string(),
+                    )
+                    .field("Name", &restored.name, true)
+                    .field("Color", &restored.primary_color, true)
+                    .field("Icon", restored.icon.describe(), true),
+                )
+                .await;
+            }
+            Err(e) => {
+                tracing::error!(
+                    user_id = %user_id,
+                    guild_id = %guild_id,
+                    error = ?e,
+                    "Failed to restore archived booster role for returning booster"
+                );
+            }
+        }
+    }
+
+    /// Give the award role to a member who just started boosting and record
+    /// the grant. Without the member's previous state, a grant recorded since
+    /// their boost began means they've already been awarded.
     pub async fn check_award_assignment(
         &self,
         ctx: &Context,
@@ -328,13 +699,34 @@ impl BoostHandler {
         let guild_id = new_member.guild_id;
         let user_id = new_member.user.id;
 
+        let Some(boosting_since) = new_member.premium_since else {
+            return;
+        };
+
         // Check if member just started boosting
-        let is_new_booster = match (
-            old_member.and_then(|m| m.premium_since),
-            new_member.premium_since,
-        ) {
-            (None, Some(_)) => true,
-            _ => false,
+        let is_new_booster = match old_member {
+            Some(old) => old.premium_since.is_none(),
+            None => {
+                match BoosterAwardGrant::granted_since(
+                    &self.db_pool,
+                    guild_id,
+                    user_id,
+                    boosting_since,
+                )
+                .await
+                {
+                    Ok(granted) => !granted,
+                    Err(e) => {
+                        tracing::error!(
+                            user_id = %user_id,
+                            guild_id = %guild_id,
+                            error = ?e,
+                            "Failed to check previous award grants"
+                        );
+                        return;
+                    }
+                }
+            }
         };
 
         if !is_new_booster {
@@ -342,8 +734,8 @@ impl BoostHandler {
         }
 
         // Check if there's an award role configured
-        let award_role_id = match GuildBoosterAward::get(&self.db_pool, guild_id).await {
-            Ok(Some(role_id)) => role_id,
+        let award = match GuildBoosterAward::get_config(&self.db_pool, guild_id).await {
+            Ok(Some(award)) => award,
             Ok(None) => return,
             Err(e) => {
                 tracing::error!(
@@ -354,6 +746,11 @@ impl BoostHandler {
                 return;
             }
         };
+        let award_role_id = award.role_id();
+
+        if new_member.roles.contains(&award_role_id) {
+            return;
+        }
 
         // Check if the role still exists
         let guild = match guild_id.to_partial_guild(&ctx.http).await {
@@ -393,15 +790,81 @@ impl BoostHandler {
                 award_role_id = %award_role_id,
                 "Successfully assigned award role to new booster"
             );
+
+            if let Err(e) =
+                BoosterAwardGrant::record(&self.db_pool, guild_id, user_id, award_role_id).await
+            {
+                tracing::error!(
+                    user_id = %user_id,
+                    guild_id = %guild_id,
+                    error = ?e,
+                    "Failed to record award grant"
+                );
+            }
+
+            if award.announce_boosts {
+                if let Err(e) = self.announce_award(ctx, new_member, award_role_id).await {
+                    tracing::warn!(
+                        user_id = %user_id,
+                        guild_id = %guild_id,
+                        error = ?e,
+                        "Failed to announce new booster"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Congratulate a new booster in the join-log channel, if one is set
+    /// and quiet hours aren't in effect
+    async fn announce_award(
+        &self,
+        ctx: &Context,
+        member: &Member,
+        award_role_id: RoleId,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let guild_id = member.guild_id;
+        let join_log = GuildJoinLogChannel::get(&self.db_pool, guild_id).await?;
+        let Some(channel_id) = join_log.and_then(|l| l.join_channel()) else {
+            return Ok(());
+        };
+
+        let decision =
+            GuildQuietHours::gate(&self.db_pool, guild_id, AutomatedMessage::BoostAnnouncement)
+                .await?;
+        if decision != QuietDecision::Send {
+            return Ok(());
         }
+
+        let embed = CreateEmbed::new()
+            .title("🚀 New Booster!")
+            .description(format!(
+                "Thank you for boosting the server, {}! You've been given {}.",
+                member.mention(),
+                award_role_id.mention()
+            ))
+            .color(EmbedColor::Success.value())
+            .thumbnail(member.face())
+            .timestamp(Timestamp::now());
+
+        channel_id
+            .send_message(&ctx.http, CreateMessage::new().embed(embed))
+            .await?;
+        Ok(())
     }
 
     /// Remove award role when member stops boosting
-    pub async fn remove_award_role(&self, ctx: &Context, guild_id: GuildId, member: &Member) {
-        // Check if there's an award role configured
-        let award_role_id = match GuildBoosterAward::get(&self.db_pool, guild_id).await {
-            Ok(Some(role_id)) => role_id,
-            Ok(None) => return,
+    pub async fn remove_award_role(
+        &self,
+        ctx: &Context,
+        guild_id: GuildId,
+        user_id: UserId,
+        roles: &[RoleId],
+    ) {
+        // Check if there's an award role configured to be taken back
+        let award_role_id = match GuildBoosterAward::get_config(&self.db_pool, guild_id).await {
+            Ok(Some(award)) if award.remove_on_unboost => award.role_id(),
+            Ok(_) => return,
             Err(e) => {
                 tracing::error!(
                     guild_id = %guild_id,
@@ -413,10 +876,14 @@ impl BoostHandler {
         };
 
         // Remove the award role if member has it
-        if member.roles.contains(&award_role_id) {
-            if let Err(e) = member.remove_role(&ctx.http, award_role_id).await {
+        if roles.contains(&award_role_id) {
+            if let Err(e) = ctx
+                .http
+                .remove_member_role(guild_id, user_id, award_role_id, None)
+                .await
+            {
                 tracing::error!(
-                    user_id = %member.user.id,
+                    user_id = %user_id,
                     guild_id = %guild_id,
                     award_role_id = %award_role_id,
                     error = ?e,
@@ -424,7 +891,7 @@ impl BoostHandler {
                 );
             } else {
                 tracing::info!(
-                    user_id = %member.user.id,
+                    user_id = %user_id,
                     guild_id = %guild_id,
                     award_role_id = %award_role_id,
                     "Successfully removed award role from ex-booster"
@@ -432,4 +899,117 @@ impl BoostHandler {
             }
         }
     }
+
+    /// Give a new booster the guild's premium role, if one is configured
+    pub async fn assign_premium_role(
+        &self,
+        ctx: &Context,
+        guild_id: GuildId,
+        user_id: UserId,
+        roles: &[RoleId],
+    ) {
+        let premium_role_id = match GuildPremiumRole::get(&self.db_pool, guild_id).await {
+            Ok(Some(premium)) => premium.role_id(),
+            Ok(None) => return,
+            Err(e) => {
+                tracing::error!(
+                    guild_id = %guild_id,
+                    error = ?e,
+                    "Failed to fetch premium role configuration"
+                );
+                return;
+            }
+        };
+
+        if roles.contains(&premium_role_id) {
+            return;
+        }
+
+        if let Err(e) = ctx
+            .http
+            .add_member_role(
+                guild_id,
+                user_id,
+                premium_role_id,
+                Some("Member started boosting"),
+            )
+            .await
+        {
+            tracing::error!(
+                user_id = %user_id,
+                guild_id = %guild_id,
+                premium_role_id = %premium_role_id,
+                error = ?e,
+                "Failed to assign premium role to new booster"
+            );
+        } else {
+            tracing::info!(
+                user_id = %user_id,
+                guild_id = %guild_id,
+                premium_role_id = %premium_role_id,
+                "Assigned premium role to new booster"
+            );
+        }
+    }
+
+    /// Take the guild's premium role back from a member who stopped boosting
+    pub async fn remove_premium_role(
+        &self,
+        ctx: &Context,
+        guild_id: GuildId,
+        user_id: UserId,
+        roles: &[RoleId],
+    ) {
+        let premium_role_id = match GuildPremiumRole::get(&self.db_pool, guild_id).await {
+            Ok(Some(premium)) => premium.role_id(),
+            Ok(None) => return,
+            Err(e) => {
+                tracing::error!(
+                    guild_id = %guild_id,
+                    error = ?e,
+                    "Failed to fetch premium role configuration for removal"
+                );
+                return;
+            }
+        };
+
+        if !roles.contains(&premium_role_id) {
+            return;
+        }
+
+        if let Err(e) = ctx
+            .http
+            .remove_member_role(
+                guild_id,
+                user_id,
+                premium_role_id,
+                Some("Member stopped boosting"),
+            )
+            .await
+        {
+            tracing::error!(
+                user_id = %user_id,
+                guild_id = %guild_id,
+                premium_role_id = %premium_role_id,
+                error = ?e,
+                "Failed to remove premium role from ex-booster"
+            );
+        } else {
+            tracing::info!(
+                user_id = %user_id,
+                guild_id = %guild_id,
+                premium_role_id = %premium_role_id,
+                "Removed premium role from ex-booster"
+            );
+        }
+    }
+}
+
+/// Whether Discord answered that the target no longer exists
+fn is_not_found(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::Serenity(serenity::Error::Http(HttpError::UnsuccessfulRequest(response)))
+            if response.status_code.as_u16() == 404
+    )
 }
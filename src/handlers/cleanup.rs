@@ -0,0 +1,476 @@
+use crate::bot::Error;
+use crate::data::models::{BoosterRole, BulkOperation};
+use crate::utils::embed_builder::EmbedBuilder;
+use crate::utils::progress::ProgressReporter;
+use crate::utils::RoleManager;
+use serenity::all::{
+    ChannelId, Context, CreateAllowedMentions, CreateEmbed, CreateMessage, GuildId, Http, Member,
+    PartialGuild, RoleId, UserId,
+};
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+
+/// Operation kind recorded for `/boosterrole cleanup`
+pub const CLEANUP_OPERATION: &str = "boosterrole_cleanup";
+
+/// Operation kind recorded for `/boosterrole cleanup delete_roles:true`
+pub const CLEANUP_ROLES_OPERATION: &str = "boosterrole_cleanup_roles";
+
+/// Roles removed between progress updates
+pub const PROGRESS_EVERY: usize = 25;
+
+/// Members Discord returns per page of the member list
+pub const MEMBER_PAGE_SIZE: u64 = 1000;
+
+/// Share of the guild's reported member count the scan must see before it
+/// trusts that a missing booster really stopped boosting. The reported
+/// count is approximate, so a little slack avoids false alarms.
+const MIN_FETCHED_SHARE: f64 = 0.9;
+
+#[derive(Default)]
+pub struct CleanupStats {
+    pub no_boost_count: usize,
+    pub role_deleted_count: usize,
+    pub member_left_count: usize,
+}
+
+impl CleanupStats {
+    pub fn describe(&self) -> String {
+        format!(
+            "• No longer boosting: {}\n• Role deleted: {}\n• Member left: {}",
+            self.no_boost_count, self.role_deleted_count, self.member_left_count
+        )
+    }
+}
+
+/// Booster roles whose owners stopped boosting or left
+pub struct CleanupScan {
+    pub guild: PartialGuild,
+    pub orphaned: Vec<(UserId, RoleId, String)>,
+    pub stats: CleanupStats,
+}
+
+impl CleanupScan {
+    /// Orphaned roles that still exist in Discord
+    pub fn existing_roles(&self) -> usize {
+        self.orphaned
+            .iter()
+            .filter(|(_, role_id, _)| self.guild.roles.contains_key(role_id))
+            .count()
+    }
+}
+
+/// Walks a guild's member list a page of [`MEMBER_PAGE_SIZE`] at a time
+pub struct MemberPages {
+    guild_id: GuildId,
+    after: Option<UserId>,
+    done: bool,
+    /// Members returned so far
+    pub fetched: usize,
+}
+
+impl MemberPages {
+    pub fn new(guild_id: GuildId) -> Self {
+        Self {
+            guild_id,
+            after: None,
+            done: false,
+            fetched: 0,
+        }
+    }
+
+    /// The next page, or `None` once the whole list has been returned
+    pub async fn next(&mut self, http: &Http) -> Result<Option<Vec<Member>>, serenity::Error> {
+        if self.done {
+            return Ok(None);
+        }
+        let members = self
+            .guild_id
+            .members(http, Some(MEMBER_PAGE_SIZE), self.after)
+            .await?;
+        let Some(last) = members.last() else {
+            self.done = true;
+            return Ok(None);
+        };
+        self.after = Some(last.user.id);
+        self.fetched += members.len();
+        self.done = (members.len() as u64) < MEMBER_PAGE_SIZE;
+        Ok(Some(members))
+    }
+
+    /// Whether the last page has been returned
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+/// The member fetch came back well short of the guild's member count, so
+/// boosters it missed would have been mistaken for lapsed ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IncompleteMembers {
+    pub fetched: usize,
+    pub expected: u64,
+}
+
+impl IncompleteMembers {
+    /// `None` when `fetched` covers enough of `expected`, or Discord didn't
+    /// report a count to compare against
+    pub fn check(fetched: usize, expected: Option<u64>) -> Option<Self> {
+        let expected = expected?;
+        ((fetched as f64) < expected as f64 * MIN_FETCHED_SHARE).then_some(Self { fetched, expected })
+    }
+
+    /// How far short the fetch fell, for the start of an abort message
+    pub fn shortfall(&self) -> String {
+        format!(
+            "Only **{}** of about **{}** members could be fetched",
+            group_thousands(self.fetched as u64),
+            group_thousands(self.expected)
+        )
+    }
+
+    pub fn embed(&self) -> CreateEmbed {
+        EmbedBuilder::warning(
+            "⚠️ Cleanup Aborted",
+            format!(
+                "{}, so active boosters could have been mistaken for lapsed ones. \
+                Nothing was removed; try again later.",
+                self.shortfall()
+            ),
+        )
+    }
+}
+
+/// `12000` as `12,000`
+fn group_thousands(n: u64) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// What happened to one orphaned role
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoleRemoval {
+    /// Only the database record was removed
+    RecordOnly,
+    /// The Discord role was deleted along with the record
+    RoleDeleted,
+    /// The Discord role couldn't be deleted; the record is kept for a retry
+    Failed,
+}
+
+/// Running totals for a cleanup
+#[derive(Debug, Default)]
+pub struct CleanupOutcome {
+    pub records_removed: usize,
+    pub roles_deleted: usize,
+    pub failed: usize,
+}
+
+impl CleanupOutcome {
+    pub fn add(&mut self, removal: RoleRemoval) {
+        match removal {
+            RoleRemoval::RecordOnly => self.records_removed += 1,
+            RoleRemoval::RoleDeleted => {
+                self.records_removed += 1;
+                self.roles_deleted += 1;
+            }
+            RoleRemoval::Failed => self.failed += 1,
+        }
+    }
+}
+
+/// Removes orphaned booster roles; shared by the command and by resuming
+/// a cleanup a restart interrupted
+pub struct CleanupHandler;
+
+impl CleanupHandler {
+    /// Find orphaned booster roles. Every page of the member list is read so
+    /// boosters past the first thousand count; when the pages still fall
+    /// well short of the member count, the scan refuses to judge anyone.
+    pub async fn scan(
+        http: &Http,
+        pool: &SqlitePool,
+        guild_id: GuildId,
+        mut progress: Option<&mut ProgressReporter<'_>>,
+    ) -> Result<Result<CleanupScan, IncompleteMembers>, Error> {
+        let guild = guild_id.to_partial_guild_with_counts(http).await?;
+        let expected = guild.approximate_member_count;
+
+        let mut booster_member_ids: HashSet<u64> = HashSet::new();
+        let mut pages = MemberPages::new(guild_id);
+        while let Some(members) = pages.next(http).await? {
+            booster_member_ids.extend(
+                members
+                    .iter()
+                    .filter(|m| m.premium_since.is_some())
+                    .map(|m| m.user.id.get()),
+            );
+
+            if pages.is_done() {
+                break;
+            }
+            if let Some(progress) = progress.as_deref_mut() {
+                progress
+                    .update(Self::scan_progress_embed(pages.fetched, expected))
+                    .await?;
+            }
+        }
+
+        if let Some(incomplete) = IncompleteMembers::check(pages.fetched, expected) {
+            tracing::warn!(
+                guild_id = %guild_id,
+                fetched = incomplete.fetched,
+                expected = incomplete.expected,
+                "Member fetch incomplete, aborting cleanup scan"
+            );
+            return Ok(Err(incomplete));
+        }
+
+        let all_roles = BoosterRole::get_all_for_guild(pool, guild_id).await?;
+
+        let mut orphaned = Vec::new();
+        let mut stats = CleanupStats::default();
+
+        for role_record in &all_roles {
+            let user_id = UserId::new(role_record.user_id as u64);
+            let role_id = RoleId::new(role_record.role_id as u64);
+
+            let is_orphaned = if !booster_member_ids.contains(&user_id.get()) {
+                stats.no_boost_count += 1;
+                true
+            } else if !guild.roles.contains_key(&role_id) {
+                stats.role_deleted_count += 1;
+                true
+            } else if guild.member(http, user_id).await.is_err() {
+                stats.member_left_count += 1;
+                true
+            } else {
+                false
+            };
+
+            if is_orphaned {
+                orphaned.push((user_id, role_id, role_record.role_name.clone()));
+            }
+        }
+
+        Ok(Ok(CleanupScan {
+            guild,
+            orphaned,
+            stats,
+        }))
+    }
+
+    /// Remove one orphaned role's record, and with `delete_roles` the
+    /// Discord role too when it still exists
+    pub async fn remove(
+        ctx: &Context,
+        pool: &SqlitePool,
+        guild: &PartialGuild,
+        user_id: UserId,
+        role_id: RoleId,
+        delete_roles: bool,
+    ) -> RoleRemoval {
+        let mut removal = RoleRemoval::RecordOnly;
+        if delete_roles && guild.roles.contains_key(&role_id) {
+            match RoleManager::delete_role(ctx, guild.id, role_id).await {
+                Ok(()) => removal = RoleRemoval::RoleDeleted,
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to delete role {} in guild {}: {}",
+                        role_id,
+                        guild.id,
+                        e
+                    );
+                    return RoleRemoval::Failed;
+                }
+            }
+        }
+
+        if let Err(e) = BoosterRole::delete(pool, guild.id, user_id).await {
+            tracing::error!(
+                "Failed to delete database record for user {} in guild {}: {}",
+                user_id,
+                guild.id,
+                e
+            );
+        }
+
+        removal
+    }
+
+    fn scan_progress_embed(fetched: usize, expected: Option<u64>) -> CreateEmbed {
+        let scanned = match expected {
+            Some(expected) => format!(
+                "{}/{}",
+                group_thousands(fetched as u64),
+                group_thousands(expected)
+            ),
+            None => group_thousands(fetched as u64),
+        };
+        EmbedBuilder::info(
+            "🧹 Scanning Members",
+            format!("Scanned **{}** members…", scanned),
+        )
+    }
+
+    pub fn progress_embed(done: usize, total: usize) -> CreateEmbed {
+        EmbedBuilder::info(
+            "🧹 Cleanup In Progress",
+            format!("Removed **{}** of **{}** orphaned role(s)…", done, total),
+        )
+    }
+
+    pub fn summary_embed(outcome: &CleanupOutcome, stats: &CleanupStats) -> CreateEmbed {
+        let counts = format!(
+            "• Database records removed: {}\n• Discord roles deleted: {}\n• Deletions failed: {}",
+            outcome.records_removed, outcome.roles_deleted, outcome.failed
+        );
+
+        if outcome.failed > 0 {
+            EmbedBuilder::warning(
+                "⚠️ Cleanup Partially Complete",
+                format!(
+                    "**{}** role(s) couldn't be deleted; their records were kept so a later cleanup can retry.",
+                    outcome.failed
+                ),
+            )
+        } else {
+            EmbedBuilder::success(
+                "✅ Cleanup Complete",
+                format!(
+                    "Successfully cleaned up **{}** orphaned role(s).",
+                    outcome.records_removed
+                ),
+            )
+        }
+        .field("Results", counts, false)
+        .field("Statistics", stats.describe(), false)
+    }
+
+    /// Finish cleanups a restart interrupted and post the outcome where
+    /// they were started
+    pub fn spawn_resume(ctx: Context, db_pool: SqlitePool) {
+        tokio::spawn(async move {
+            let interrupted = match BulkOperation::running(&db_pool).await {
+                Ok(interrupted) => interrupted,
+                Err(e) => {
+                    tracing::error!(error = ?e, "Failed to load interrupted bulk operations");
+                    return;
+                }
+            };
+
+            for operation in interrupted {
+                if operation.kind == CLEANUP_OPERATION || operation.kind == CLEANUP_ROLES_OPERATION
+                {
+                    tracing::info!(
+                        operation_id = operation.id,
+                        guild_id = operation.guild_id,
+                        "Resuming interrupted cleanup"
+                    );
+                    if let Err(e) = Self::resume(&ctx, &db_pool, &operation).await {
+                        tracing::error!(
+                            operation_id = operation.id,
+                            error = ?e,
+                            "Resumed cleanup failed"
+                        );
+                    }
+                } else {
+                    tracing::warn!(
+                        operation_id = operation.id,
+                        kind = %operation.kind,
+                        "Unknown bulk operation left running; closing it"
+                    );
+                }
+
+                if let Err(e) = BulkOperation::finish(&db_pool, operation.id).await {
+                    tracing::error!(operation_id = operation.id, error = ?e, "Failed to close bulk operation");
+                }
+            }
+        });
+    }
+
+    async fn resume(
+        ctx: &Context,
+        pool: &SqlitePool,
+        operation: &BulkOperation,
+    ) -> Result<(), Error> {
+        let guild_id = GuildId::new(operation.guild_id as u64);
+        let delete_roles = operation.kind == CLEANUP_ROLES_OPERATION;
+        let channel_id = ChannelId::new(operation.channel_id as u64);
+        let scan = match Self::scan(&ctx.http, pool, guild_id, None).await? {
+            Ok(scan) => scan,
+            Err(incomplete) => {
+                channel_id
+                    .send_message(
+                        &ctx.http,
+                        CreateMessage::new()
+                            .content(format!(
+                                "The cleanup <@{}> started was interrupted by a restart and couldn't be finished.",
+                                operation.requested_by
+                            ))
+                            .embed(incomplete.embed())
+                            .allowed_mentions(CreateAllowedMentions::new()),
+                    )
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        let mut outcome = CleanupOutcome::default();
+        for (user_id, role_id, _) in &scan.orphaned {
+            outcome
+                .add(Self::remove(ctx, pool, &scan.guild, *user_id, *role_id, delete_roles).await);
+        }
+
+        channel_id
+            .send_message(
+                &ctx.http,
+                CreateMessage::new()
+                    .content(format!(
+                        "The cleanup <@{}> started was interrupted by a restart and has now finished.",
+                        operation.requested_by
+                    ))
+                    .embed(Self::summary_embed(&outcome, &scan.stats))
+                    .allowed_mentions(CreateAllowedMentions::new()),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_incomplete_member_fetch_is_caught() {
+        assert_eq!(IncompleteMembers::check(1000, None), None);
+        assert_eq!(IncompleteMembers::check(12000, Some(12000)), None);
+        // Approximate counts drift a little either way
+        assert_eq!(IncompleteMembers::check(11500, Some(12000)), None);
+        assert_eq!(IncompleteMembers::check(12100, Some(12000)), None);
+        assert_eq!(
+            IncompleteMembers::check(1000, Some(12000)),
+            Some(IncompleteMembers {
+                fetched: 1000,
+                expected: 12000
+            })
+        );
+    }
+
+    #[test]
+    fn test_group_thousands() {
+        assert_eq!(group_thousands(0), "0");
+        assert_eq!(group_thousands(999), "999");
+        assert_eq!(group_thousands(4000), "4,000");
+        assert_eq!(group_thousands(1234567), "1,234,567");
+    }
+}
@@ -0,0 +1,147 @@
+use crate::data::models::{BoosterRole, BoosterRoleLock, ColorRotation, GuildRotationSettings};
+use crate::utils::color_rotation::step;
+use crate::utils::scheduled_change::to_stored;
+use crate::utils::share_revalidation::{recipient_state_from_error, RecipientState};
+use crate::utils::shutdown::next_tick;
+use crate::utils::{
+    check_lock, is_eligible, load_eligibility_config, ColorParser, RequestedChange, RoleManager,
+};
+use serenity::all::{Context, GuildId, RoleId, UserId};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// How often the scheduler looks for due rotations
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Pause between role edits so many rotations coming due together don't
+/// burst Discord's rate limits
+const ROTATION_PACING: Duration = Duration::from_secs(1);
+
+type RotationError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Steps opted-in booster roles through their color palettes
+pub struct ColorRotationHandler {
+    pub db_pool: Arc<SqlitePool>,
+}
+
+impl ColorRotationHandler {
+    pub fn new(db_pool: Arc<SqlitePool>) -> Self {
+        Self { db_pool }
+    }
+
+    /// Start the background task that applies due colors
+    pub fn spawn_scheduler(ctx: Context, db_pool: SqlitePool, shutdown: CancellationToken) {
+        let handler = Self::new(Arc::new(db_pool));
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CHECK_INTERVAL);
+            while next_tick(&mut interval, &shutdown).await {
+                handler.run_due(&ctx).await;
+            }
+        });
+    }
+
+    pub async fn run_due(&self, ctx: &Context) {
+        let now = chrono::Utc::now();
+        let due = match ColorRotation::due(&self.db_pool, &to_stored(now)).await {
+            Ok(due) => due,
+            Err(e) => {
+                tracing::error!(error = ?e, "Failed to load due color rotations");
+                return;
+            }
+        };
+
+        for rotation in due {
+            if let Err(e) = self.rotate(ctx, &rotation).await {
+                tracing::error!(
+                    guild_id = rotation.guild_id,
+                    user_id = rotation.user_id,
+                    error = ?e,
+                    "Color rotation failed"
+                );
+            }
+            tokio::time::sleep(ROTATION_PACING).await;
+        }
+    }
+
+    /// Apply the rotation's next color, or stop it when the member can no
+    /// longer have one. A failed edit still moves the schedule on so one
+    /// broken role isn't retried every minute.
+    async fn rotate(&self, ctx: &Context, rotation: &ColorRotation) -> Result<(), RotationError> {
+        let pool = self.db_pool.as_ref();
+        let guild_id = GuildId::new(rotation.guild_id as u64);
+        let user_id = UserId::new(rotation.user_id as u64);
+
+        let Some(role) = BoosterRole::get(pool, guild_id, user_id).await? else {
+            return self.stop(guild_id, user_id, "no booster role").await;
+        };
+
+        match guild_id.member(&ctx.http, user_id).await {
+            Ok(member) => {
+                let eligibility = load_eligibility_config(pool, guild_id).await?;
+                if !is_eligible(member.premium_since, &member.roles, &eligibility) {
+                    return self.stop(guild_id, user_id, "no longer eligible").await;
+                }
+            }
+            Err(e) => {
+                if recipient_state_from_error(&e) == RecipientState::NotMember {
+                    return self.stop(guild_id, user_id, "left the server").await;
+                }
+                // Discord hiccup; the rotation stays due for the next check
+                return Err(e.into());
+            }
+        }
+
+        let role_id = RoleId::new(role.role_id as u64);
+        if let Some(role_lock) = BoosterRoleLock::get(pool, guild_id, role_id).await? {
+            if check_lock(role_lock.flags(), RequestedChange::color()).is_err() {
+                return self.stop(guild_id, user_id, "color locked by staff").await;
+            }
+        }
+
+        let settings = GuildRotationSettings::get(pool, guild_id).await?;
+        if settings.max_members == 0 {
+            return self.stop(guild_id, user_id, "turned off in the server").await;
+        }
+
+        let Some((color, next_index)) = step(&rotation.palette(), rotation.next_index) else {
+            return self.stop(guild_id, user_id, "empty palette").await;
+        };
+
+        let next_run_at = chrono::Utc::now() + settings.effective_interval(rotation.interval_secs);
+        ColorRotation::advance(pool, guild_id, user_id, next_index, &to_stored(next_run_at))
+            .await?;
+
+        RoleManager::update_booster_role(ctx, guild_id, role_id, &role.role_name, color).await?;
+        BoosterRole::update(
+            pool,
+            guild_id,
+            user_id,
+            &role.role_name,
+            &ColorParser::to_hex_string(color),
+            role.secondary_color.as_deref(),
+        )
+        .await?;
+
+        tracing::debug!(
+            guild_id = %guild_id,
+            user_id = %user_id,
+            color = %ColorParser::to_hex_string(color),
+            "Rotated booster role color"
+        );
+        Ok(())
+    }
+
+    async fn stop(&self, guild_id: GuildId, user_id: UserId, reason: &str) -> Result<(), RotationError> {
+        ColorRotation::disable(&self.db_pool, guild_id, user_id).await?;
+        tracing::info!(
+            guild_id = %guild_id,
+            user_id = %user_id,
+            reason,
+            "Color rotation stopped"
+        );
+        Ok(())
+    }
+}
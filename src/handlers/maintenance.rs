@@ -0,0 +1,159 @@
+use crate::data::models::{
+    CommandUsage, GuildRetentionOverride, SettingsAuditLog, TableGrowth,
+    COMMAND_USAGE_RETENTION_DAYS, TABLE_GROWTH_RETENTION_DAYS,
+};
+use crate::utils::retention::RetentionCategory;
+use crate::utils::shutdown::next_tick;
+use crate::utils::table_growth::{detect_anomaly, TRACKED_TABLES, TRAILING_DAYS};
+use serenity::all::{GuildId, Http};
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// How often the maintenance pass runs
+const MAINTENANCE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+type MaintenanceError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Nightly housekeeping: samples table sizes, warns about runaway growth,
+/// prunes member data past each guild's retention window and drops old
+/// command usage
+pub struct MaintenanceHandler {
+    pub db_pool: Arc<SqlitePool>,
+    pub growth_multiplier: f64,
+}
+
+impl MaintenanceHandler {
+    pub fn new(db_pool: Arc<SqlitePool>, growth_multiplier: f64) -> Self {
+        Self {
+            db_pool,
+            growth_multiplier,
+        }
+    }
+
+    /// Start the background task that runs maintenance once a day
+    pub fn spawn_scheduler(
+        http: Arc<Http>,
+        db_pool: SqlitePool,
+        growth_multiplier: f64,
+        shutdown: CancellationToken,
+    ) {
+        let handler = Self::new(Arc::new(db_pool), growth_multiplier);
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(MAINTENANCE_INTERVAL);
+            while next_tick(&mut interval, &shutdown).await {
+                if let Err(e) = handler.check_table_growth(&http).await {
+                    tracing::error!(error = ?e, "Table growth check failed");
+                }
+                if let Err(e) = handler.prune_member_data().await {
+                    tracing::error!(error = ?e, "Retention pruning failed");
+                }
+                if let Err(e) = handler.prune_command_usage().await {
+                    tracing::error!(error = ?e, "Command usage pruning failed");
+                }
+            }
+        });
+    }
+
+    /// Record today's row counts and flag tables growing far faster than usual
+    pub async fn check_table_growth(&self, http: &Http) -> Result<(), MaintenanceError> {
+        let pool = self.db_pool.as_ref();
+        let today = chrono::Utc::now().date_naive();
+
+        // A restart re-runs the check; don't warn twice for the same day
+        let already_flagged: HashSet<(i64, String)> = TableGrowth::flagged_since(pool, today)
+            .await?
+            .into_iter()
+            .map(|sample| (sample.guild_id, sample.table_name))
+            .collect();
+
+        let mut bot_id = None;
+        let mut flagged = 0;
+        for table in TRACKED_TABLES {
+            for (guild, row_count) in TableGrowth::count_by_guild(pool, table).await? {
+                let guild_id = GuildId::new(guild as u64);
+                let history =
+                    TableGrowth::history(pool, guild_id, table, today, TRAILING_DAYS as i64 + 1)
+                        .await?;
+                let anomaly = detect_anomaly(&history, row_count, self.growth_multiplier);
+                let detail = anomaly.map(|a| a.describe());
+
+                TableGrowth::record(pool, guild_id, table, today, row_count, detail.as_deref())
+                    .await?;
+
+                let Some(detail) = detail else {
+                    continue;
+                };
+                if already_flagged.contains(&(guild, table.to_string())) {
+                    continue;
+                }
+                flagged += 1;
+
+                tracing::warn!(
+                    guild_id = %guild_id,
+                    table = table,
+                    row_count = row_count,
+                    detail = %detail,
+                    "Table growing unusually fast"
+                );
+
+                let bot_id = match bot_id {
+                    Some(id) => id,
+                    None => *bot_id.insert(http.get_current_user().await?.id),
+                };
+                SettingsAuditLog::log(
+                    pool,
+                    guild_id,
+                    bot_id,
+                    "table_growth_warning",
+                    Some(&format!("`{}` ({} rows): {}", table, row_count, detail)),
+                )
+                .await?;
+            }
+        }
+
+        let pruned = TableGrowth::prune(
+            pool,
+            today - chrono::Duration::days(TABLE_GROWTH_RETENTION_DAYS),
+        )
+        .await?;
+
+        tracing::info!(
+            flagged = flagged,
+            pruned = pruned,
+            "Table growth check finished"
+        );
+        Ok(())
+    }
+
+    /// Delete member data past each guild's retention window, one statement
+    /// per table across all guilds
+    pub async fn prune_member_data(&self) -> Result<(), MaintenanceError> {
+        let pool = self.db_pool.as_ref();
+        for category in RetentionCategory::ALL {
+            let pruned = GuildRetentionOverride::prune(pool, category).await?;
+            if pruned > 0 {
+                tracing::info!(
+                    category = category.as_str(),
+                    pruned = pruned,
+                    "Pruned expired member data"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete command usage older than [`COMMAND_USAGE_RETENTION_DAYS`]
+    pub async fn prune_command_usage(&self) -> Result<(), MaintenanceError> {
+        let before = chrono::Utc::now().date_naive()
+            - chrono::Duration::days(COMMAND_USAGE_RETENTION_DAYS);
+        let pruned = CommandUsage::prune(self.db_pool.as_ref(), before).await?;
+        if pruned > 0 {
+            tracing::info!(pruned = pruned, "Pruned old command usage");
+        }
+        Ok(())
+    }
+}
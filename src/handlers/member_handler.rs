@@ -1,11 +1,23 @@
-use crate::data::models::{GuildAutoNickname, GuildJoinLogChannel};
+use crate::data::models::{
+    GuildAutoNickname, GuildJoinLogChannel, GuildQuietHours, QuietMemberEvent,
+};
+use crate::utils::embed_builder::MAX_DESCRIPTION_CHARS;
+use crate::utils::invite_tracker::InviteTracker;
+use crate::utils::nickname_template::{self, NicknameValues};
+use crate::utils::quiet_hours::{summarize_member_events, AutomatedMessage, QuietDecision};
 use crate::utils::EmbedColor;
+use crate::utils::shutdown::next_tick;
 use serenity::model::mention::Mentionable;
 use serenity::all::{
-    ChannelId, Context, CreateEmbed, CreateMessage, EditMember, GuildId, Member, User,
+    Context, CreateEmbed, CreateMessage, EditMember, GuildId, Http, Member, User,
 };
 use sqlx::SqlitePool;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// How often held-back join/leave logs are checked for release
+const QUIET_SUMMARY_INTERVAL: Duration = Duration::from_secs(60);
 
 pub struct MemberHandler {
     pub db_pool: Arc<SqlitePool>,
@@ -16,7 +28,12 @@ impl MemberHandler {
         Self { db_pool }
     }
 
-    pub async fn handle_member_join(&self, ctx: &Context, new_member: &Member) {
+    pub async fn handle_member_join(
+        &self,
+        ctx: &Context,
+        new_member: &Member,
+        invites: &InviteTracker,
+    ) {
         let guild_id = new_member.guild_id;
         let user_id = new_member.user.id;
 
@@ -31,7 +48,7 @@ impl MemberHandler {
         }
 
         // Send join log if configured
-        if let Err(e) = self.send_join_log(ctx, new_member).await {
+        if let Err(e) = self.send_join_log(ctx, new_member, invites).await {
             tracing::error!(
                 guild_id = %guild_id,
                 user_id = %user_id,
@@ -61,10 +78,21 @@ impl MemberHandler {
         let auto_nick = GuildAutoNickname::get(&self.db_pool, member.guild_id).await?;
 
         if let Some(nick_config) = auto_nick {
-            let nickname = self.parse_nickname_template(
+            // The cache already counts the new member, so they're last in line
+            let member_count = ctx
+                .cache
+                .guild(member.guild_id)
+                .map(|g| g.member_count)
+                .unwrap_or(0);
+            let nickname = nickname_template::render(
                 &nick_config.nickname_template,
-                &member.user.name,
-                member.user.discriminator.map(|d| d.get()),
+                &NicknameValues {
+                    username: &member.user.name,
+                    display_name: member.user.display_name(),
+                    discriminator: member.user.discriminator.map(|d| d.get()),
+                    member_count,
+                    join_position: member_count,
+                },
             );
 
             // Apply nickname with graceful degradation
@@ -101,11 +129,21 @@ impl MemberHandler {
         &self,
         ctx: &Context,
         member: &Member,
+        invites: &InviteTracker,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let join_log = GuildJoinLogChannel::get(&self.db_pool, member.guild_id).await?;
 
-        if let Some(log_config) = join_log {
-            let channel_id = ChannelId::new(log_config.channel_id as u64);
+        if let Some(channel_id) = join_log.and_then(|l| l.join_channel()) {
+            // Refresh invite counts even when quiet hours hold the log back,
+            // so the next join is compared against current numbers
+            let invited_by = invites.attribute_join(&ctx.http, member.guild_id).await;
+
+            if !self
+                .quiet_hours_allow(member.guild_id, &member.user, true)
+                .await?
+            {
+                return Ok(());
+            }
 
             // Get member count
             let member_count = ctx
@@ -135,6 +173,7 @@ impl MemberHandler {
                 )
                 .field("Member Count", member_count.to_string(), true)
                 .field("User ID", member.user.id.to_string(), true)
+                .field("Invited by", invited_by.describe(), false)
                 .timestamp(serenity::model::Timestamp::now());
 
             channel_id
@@ -160,8 +199,10 @@ impl MemberHandler {
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let leave_log = GuildJoinLogChannel::get(&self.db_pool, guild_id).await?;
 
-        if let Some(log_config) = leave_log {
-            let channel_id = ChannelId::new(log_config.channel_id as u64);
+        if let Some(channel_id) = leave_log.and_then(|l| l.leave_channel()) {
+            if !self.quiet_hours_allow(guild_id, user, false).await? {
+                return Ok(());
+            }
 
             // Get member count
             let member_count = ctx
@@ -198,23 +239,128 @@ impl MemberHandler {
         Ok(())
     }
 
-    fn parse_nickname_template(
+    /// Check quiet hours before a join/leave log, queueing the event for
+    /// the end-of-window summary when the guild wants one
+    async fn quiet_hours_allow(
         &self,
-        template: &str,
-        username: &str,
-        discriminator: Option<u16>,
-    ) -> String {
-        let mut result = template.to_string();
-        result = result.replace("{username}", username);
-        if let Some(disc) = discriminator {
-            result = result.replace("{discriminator}", &disc.to_string());
+        guild_id: GuildId,
+        user: &User,
+        joined: bool,
+    ) -> Result<bool, sqlx::Error> {
+        let message = if joined {
+            AutomatedMessage::JoinLog
         } else {
-            result = result.replace("{discriminator}", "");
+            AutomatedMessage::LeaveLog
+        };
+
+        match GuildQuietHours::gate(&self.db_pool, guild_id, message).await? {
+            QuietDecision::Send => Ok(true),
+            QuietDecision::Suppress { queue: true } => {
+                QuietMemberEvent::queue(&self.db_pool, guild_id, user.id, &user.tag(), joined)
+                    .await?;
+                tracing::debug!(
+                    guild_id = %guild_id,
+                    user_id = %user.id,
+                    joined = joined,
+                    "Queued member log during quiet hours"
+                );
+                Ok(false)
+            }
+            QuietDecision::Suppress { queue: false } | QuietDecision::DelayUntil(_) => Ok(false),
         }
-        // Truncate to 32 characters if needed
-        if result.len() > 32 {
-            result.truncate(32);
+    }
+
+    /// Start the background task that posts queued join/leave summaries
+    /// once each guild's quiet hours end
+    pub fn spawn_quiet_summary_scheduler(
+        http: Arc<Http>,
+        db_pool: SqlitePool,
+        shutdown: CancellationToken,
+    ) {
+        let handler = Self::new(Arc::new(db_pool));
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(QUIET_SUMMARY_INTERVAL);
+            while next_tick(&mut interval, &shutdown).await {
+                handler.post_quiet_summaries(&http).await;
+            }
+        });
+    }
+
+    /// Post the summary for every guild with queued events outside quiet hours
+    pub async fn post_quiet_summaries(&self, http: &Http) {
+        let guilds = match QuietMemberEvent::pending_guilds(&self.db_pool).await {
+            Ok(guilds) => guilds,
+            Err(e) => {
+                tracing::error!(error = ?e, "Failed to load queued member logs");
+                return;
+            }
+        };
+
+        for guild_id in guilds {
+            if let Err(e) = self.post_quiet_summary(http, guild_id).await {
+                tracing::error!(
+                    guild_id = %guild_id,
+                    error = ?e,
+                    "Failed to post quiet hours summary"
+                );
+            }
+        }
+    }
+
+    async fn post_quiet_summary(
+        &self,
+        http: &Http,
+        guild_id: GuildId,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let pool = self.db_pool.as_ref();
+
+        if GuildQuietHours::gate(pool, guild_id, AutomatedMessage::JoinLog).await?
+            != QuietDecision::Send
+        {
+            return Ok(());
         }
-        result
+
+        let queued = QuietMemberEvent::list(pool, guild_id).await?;
+        let Some(last_id) = queued.last().map(|e| e.id) else {
+            return Ok(());
+        };
+
+        // The summary mixes joins and leaves, so it goes to the join channel
+        // when there is one. Without either there is nowhere to post; drop the backlog
+        let log_config = GuildJoinLogChannel::get(pool, guild_id).await?;
+        if let Some(channel_id) = log_config.and_then(|l| l.join_channel().or(l.leave_channel())) {
+            let events: Vec<_> = queued.iter().map(QuietMemberEvent::to_event).collect();
+
+            for (i, page) in summarize_member_events(&events, MAX_DESCRIPTION_CHARS)
+                .into_iter()
+                .enumerate()
+            {
+                let title = if i == 0 {
+                    "🌙 Quiet Hours Summary"
+                } else {
+                    "🌙 Quiet Hours Summary (continued)"
+                };
+                let embed = CreateEmbed::new()
+                    .title(title)
+                    .description(page)
+                    .color(EmbedColor::Info.value())
+                    .timestamp(serenity::model::Timestamp::now());
+
+                channel_id
+                    .send_message(http, CreateMessage::new().embed(embed))
+                    .await?;
+            }
+
+            tracing::info!(
+                guild_id = %guild_id,
+                channel_id = %channel_id,
+                events = events.len(),
+                "Posted quiet hours summary"
+            );
+        }
+
+        QuietMemberEvent::clear_through(pool, guild_id, last_id).await?;
+        Ok(())
     }
 }
\ No newline at end of file
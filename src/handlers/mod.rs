@@ -1,5 +1,24 @@
+pub mod announcements;
 pub mod boost_handler;
+pub mod cleanup;
+pub mod color_rotation;
+pub mod maintenance;
 pub mod member_handler;
+pub mod orphan_cleanup;
+pub mod scheduled_change;
+pub mod share_expiry;
+pub mod share_revalidation;
+pub mod spotlight_handler;
+pub mod support_ticket;
 
+pub use announcements::AnnouncementHandler;
 pub use boost_handler::BoostHandler;
+pub use cleanup::CleanupHandler;
+pub use color_rotation::ColorRotationHandler;
+pub use maintenance::MaintenanceHandler;
 pub use member_handler::MemberHandler;
+pub use orphan_cleanup::OrphanCleanupHandler;
+pub use scheduled_change::ScheduledChangeHandler;
+pub use share_expiry::ShareExpiryHandler;
+pub use share_revalidation::ShareRevalidationHandler;
+pub use spotlight_handler::{SpotlightHandler, SpotlightOutcome};
@@ -0,0 +1,77 @@
+use crate::data::models::CleanupRun;
+use crate::handlers::BoostHandler;
+use crate::utils::shutdown::next_tick;
+use serenity::all::Context;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// Pause between guilds so bots in many guilds don't burst role fetches
+const GUILD_PACING: Duration = Duration::from_secs(2);
+
+/// Periodically removes booster role records whose Discord role is gone,
+/// so orphans don't pile up between restarts
+pub struct OrphanCleanupHandler {
+    pub db_pool: Arc<SqlitePool>,
+    boost_handler: BoostHandler,
+}
+
+impl OrphanCleanupHandler {
+    pub fn new(db_pool: Arc<SqlitePool>) -> Self {
+        Self {
+            boost_handler: BoostHandler::new(db_pool.clone()),
+            db_pool,
+        }
+    }
+
+    /// Start the background task; startup already cleans every guild, so
+    /// the first pass waits a full interval
+    pub fn spawn_scheduler(
+        ctx: Context,
+        db_pool: SqlitePool,
+        every: Duration,
+        shutdown: CancellationToken,
+    ) {
+        let handler = Self::new(Arc::new(db_pool));
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + every, every);
+            while next_tick(&mut interval, &shutdown).await {
+                handler.run(&ctx).await;
+            }
+        });
+    }
+
+    /// One pass over every guild the bot is in; a failing guild is logged
+    /// and skipped
+    pub async fn run(&self, ctx: &Context) {
+        let guilds = ctx.cache.guilds();
+        let mut removed = 0;
+
+        for guild_id in &guilds {
+            if let Some(count) = self
+                .boost_handler
+                .cleanup_orphaned_roles(&ctx.http, *guild_id)
+                .await
+            {
+                removed += count;
+                if let Err(e) = CleanupRun::record(&self.db_pool, *guild_id, count).await {
+                    tracing::error!(
+                        guild_id = %guild_id,
+                        error = ?e,
+                        "Failed to record orphan cleanup run"
+                    );
+                }
+            }
+
+            tokio::time::sleep(GUILD_PACING).await;
+        }
+
+        tracing::info!(
+            guilds = guilds.len(),
+            removed = removed,
+            "Scheduled orphan cleanup completed"
+        );
+    }
+}
@@ -0,0 +1,433 @@
+use crate::data::models::{
+    BoosterRenameHistory, BoosterRole, BoosterRoleLock, RoleNameBlacklist, ScheduledRoleChange,
+};
+use crate::handlers::support_ticket::alert_blocked_name;
+use crate::utils::eligibility::{is_eligible, load_eligibility_config};
+use crate::utils::name_severity::NameCheck;
+use crate::utils::scheduled_change::{
+    next_action, revert_time, to_stored, ScheduleAction, ScheduleStatus,
+};
+use crate::utils::share_revalidation::{recipient_state_from_error, RecipientState};
+use crate::utils::shutdown::next_tick;
+use crate::utils::{check_lock, ColorParser, EmbedColor, RequestedChange, RoleManager};
+use serenity::all::{
+    Colour, CreateEmbed, CreateMessage, EditRole, GuildId, Http, RoleId, Timestamp, UserId,
+};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// How often the scheduler looks for due changes
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+type ScheduleError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The role's name and color once a scheduled change is applied
+#[derive(Debug, Clone)]
+pub struct ValidatedChange {
+    pub name: String,
+    pub color: u32,
+}
+
+/// A validated change, or why it can't be made
+pub type Validation = Result<ValidatedChange, String>;
+
+/// Run a scheduled change through the same checks as `/boosterrole color` and
+/// `/boosterrole rename set`. Used when scheduling and again when applying, since
+/// the blacklist or locks may have changed in between.
+pub async fn validate_change(
+    http: &Http,
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    role: &BoosterRole,
+    new_name: Option<&str>,
+    new_color: Option<&str>,
+    source: &str,
+) -> Result<Validation, sqlx::Error> {
+    let user_id = UserId::new(role.user_id as u64);
+
+    let new_name = match new_name.map(RoleManager::validate_role_name).transpose() {
+        Ok(name) => name,
+        Err(e) => return Ok(Err(e.to_string())),
+    };
+    if let Some(name) = new_name.as_deref() {
+        // Warned names are allowed; the attempt is already on record
+        if let NameCheck::Reject { word, alert } =
+            RoleNameBlacklist::check_name(pool, guild_id, user_id, name, source).await?
+        {
+            if alert {
+                alert_blocked_name(http, pool, guild_id, user_id, name, &word).await;
+            }
+            return Ok(Err(
+                "The name contains words that are not allowed.".to_string()
+            ));
+        }
+    }
+
+    let color = match ColorParser::parse(new_color.unwrap_or(&role.primary_color)) {
+        Ok(color) => color,
+        Err(e) => return Ok(Err(e.to_string())),
+    };
+
+    let role_id = RoleId::new(role.role_id as u64);
+    if let Some(role_lock) = BoosterRoleLock::get(pool, guild_id, role_id).await? {
+        let change = RequestedChange {
+            name: new_name.is_some(),
+            color: new_color.is_some(),
+        };
+        if let Err(aspect) = check_lock(role_lock.flags(), change) {
+            return Ok(Err(format!(
+                "Your role's {} has been locked by staff.",
+                aspect.label()
+            )));
+        }
+    }
+
+    Ok(Ok(ValidatedChange {
+        name: new_name.unwrap_or_else(|| role.role_name.clone()),
+        color,
+    }))
+}
+
+/// Applies and reverts one-time scheduled role changes
+pub struct ScheduledChangeHandler {
+    pub db_pool: Arc<SqlitePool>,
+}
+
+impl ScheduledChangeHandler {
+    pub fn new(db_pool: Arc<SqlitePool>) -> Self {
+        Self { db_pool }
+    }
+
+    /// Start the background task that runs due changes
+    pub fn spawn_scheduler(http: Arc<Http>, db_pool: SqlitePool, shutdown: CancellationToken) {
+        let handler = Self::new(Arc::new(db_pool));
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CHECK_INTERVAL);
+            while next_tick(&mut interval, &shutdown).await {
+                handler.run_due(&http).await;
+            }
+        });
+    }
+
+    pub async fn run_due(&self, http: &Http) {
+        let now = chrono::Utc::now();
+        let due = match ScheduledRoleChange::due(&self.db_pool, &to_stored(now)).await {
+            Ok(due) => due,
+            Err(e) => {
+                tracing::error!(error = ?e, "Failed to load due scheduled changes");
+                return;
+            }
+        };
+
+        for change in due {
+            let scheduled_at = change.scheduled_at;
+            let revert_at = change.revert_at;
+
+            let result = match next_action(change.status(), scheduled_at, revert_at, now) {
+                ScheduleAction::Apply => self.apply(http, &change).await,
+                ScheduleAction::Revert => self.revert(http, &change).await,
+                ScheduleAction::Expire => self.expire(http, &change).await,
+                ScheduleAction::Wait => Ok(()),
+            };
+
+            if let Err(e) = result {
+                tracing::error!(
+                    change_id = change.id,
+                    guild_id = change.guild_id,
+                    error = ?e,
+                    "Scheduled role change failed"
+                );
+            }
+        }
+    }
+
+    async fn apply(&self, http: &Http, change: &ScheduledRoleChange) -> Result<(), ScheduleError> {
+        let pool = self.db_pool.as_ref();
+        let guild_id = GuildId::new(change.guild_id as u64);
+        let user_id = UserId::new(change.user_id as u64);
+
+        let Some(role) = BoosterRole::get(pool, guild_id, user_id).await? else {
+            return self
+                .fail(http, change, "You no longer have a booster role.")
+                .await;
+        };
+
+        match guild_id.member(http, user_id).await {
+            Ok(member) => {
+                let eligibility = load_eligibility_config(pool, guild_id).await?;
+                if !is_eligible(member.premium_since, &member.roles, &eligibility) {
+                    return self
+                        .fail(
+                            http,
+                            change,
+                            "You're no longer eligible for a booster role.",
+                        )
+                        .await;
+                }
+            }
+            Err(e) => {
+                if recipient_state_from_error(&e) == RecipientState::NotMember {
+                    ScheduledRoleChange::set_status(pool, change.id, ScheduleStatus::Failed)
+                        .await?;
+                    return Ok(());
+                }
+                // Discord hiccup; the change stays pending for the next check
+                return Err(e.into());
+            }
+        }
+
+        let target = match validate_change(
+            http,
+            pool,
+            guild_id,
+            &role,
+            change.new_name.as_deref(),
+            change.new_color.as_deref(),
+            "boosterrole schedule (applying)",
+        )
+        .await?
+        {
+            Ok(target) => target,
+            Err(reason) => return self.fail(http, change, &reason).await,
+        };
+
+        // Snapshot now, not at scheduling time, so the revert restores
+        // whatever the member had right before the change
+        let applied_at = chrono::Utc::now();
+        let revert_at = revert_time(applied_at, change.revert_after_secs);
+        if !ScheduledRoleChange::mark_applied(
+            pool,
+            change.id,
+            &role.role_name,
+            &role.primary_color,
+            revert_at.map(to_stored).as_deref(),
+        )
+        .await?
+        {
+            // Cancelled while we were validating
+            return Ok(());
+        }
+
+        if let Err(e) = self.set_role(http, guild_id, &role, &target).await {
+            ScheduledRoleChange::set_status(pool, change.id, ScheduleStatus::Failed).await?;
+            return Err(e);
+        }
+
+        let mut description = format!(
+            "Your scheduled change in **{}** is live: your role is now **{}** in `{}`.",
+            guild_name(http, guild_id).await,
+            target.name,
+            ColorParser::to_hex_string(target.color)
+        );
+        if let Some(at) = revert_at {
+            description.push_str(&format!(
+                "\nIt will switch back to **{}** <t:{}:R>.",
+                role.role_name,
+                at.timestamp()
+            ));
+        }
+        notify(
+            http,
+            user_id,
+            "🗓️ Scheduled Change Applied",
+            description,
+            EmbedColor::Success,
+        )
+        .await;
+
+        tracing::info!(
+            change_id = change.id,
+            guild_id = %guild_id,
+            user_id = %user_id,
+            "Scheduled role change applied"
+        );
+        Ok(())
+    }
+
+    async fn revert(&self, http: &Http, change: &ScheduledRoleChange) -> Result<(), ScheduleError> {
+        let pool = self.db_pool.as_ref();
+        let guild_id = GuildId::new(change.guild_id as u64);
+        let user_id = UserId::new(change.user_id as u64);
+
+        let (Some(prior_name), Some(prior_color), Some(role)) = (
+            change.prior_name.as_deref(),
+            change.prior_color.as_deref(),
+            BoosterRole::get(pool, guild_id, user_id).await?,
+        ) else {
+            ScheduledRoleChange::set_status(pool, change.id, ScheduleStatus::Expired).await?;
+            return Ok(());
+        };
+
+        // Staff may have locked the role while the change was live
+        let role_id = RoleId::new(role.role_id as u64);
+        if let Some(role_lock) = BoosterRoleLock::get(pool, guild_id, role_id).await? {
+            let change_requested = RequestedChange {
+                name: change.new_name.is_some(),
+                color: change.new_color.is_some(),
+            };
+            if check_lock(role_lock.flags(), change_requested).is_err() {
+                return self
+                    .fail(
+                        http,
+                        change,
+                        "Your role was locked by staff, so it wasn't switched back.",
+                    )
+                    .await;
+            }
+        }
+
+        let target = ValidatedChange {
+            name: prior_name.to_string(),
+            color: ColorParser::parse(prior_color)?,
+        };
+        self.set_role(http, guild_id, &role, &target).await?;
+        ScheduledRoleChange::set_status(pool, change.id, ScheduleStatus::Reverted).await?;
+
+        notify(
+            http,
+            user_id,
+            "🗓️ Scheduled Change Reverted",
+            format!(
+                "Your role in **{}** is back to **{}** in `{}`.",
+                guild_name(http, guild_id).await,
+                prior_name,
+                prior_color
+            ),
+            EmbedColor::Info,
+        )
+        .await;
+
+        tracing::info!(
+            change_id = change.id,
+            guild_id = %guild_id,
+            user_id = %user_id,
+            "Scheduled role change reverted"
+        );
+        Ok(())
+    }
+
+    async fn expire(&self, http: &Http, change: &ScheduledRoleChange) -> Result<(), ScheduleError> {
+        let guild_id = GuildId::new(change.guild_id as u64);
+        ScheduledRoleChange::set_status(&self.db_pool, change.id, ScheduleStatus::Expired).await?;
+
+        notify(
+            http,
+            UserId::new(change.user_id as u64),
+            "🗓️ Scheduled Change Missed",
+            format!(
+                "Your scheduled role change #{} in **{}** was missed while the bot was offline and has been dropped. Schedule it again if you still want it.",
+                change.id,
+                guild_name(http, guild_id).await
+            ),
+            EmbedColor::Warning,
+        )
+        .await;
+        Ok(())
+    }
+
+    /// Mark a change failed and tell the member why instead of applying it
+    async fn fail(
+        &self,
+        http: &Http,
+        change: &ScheduledRoleChange,
+        reason: &str,
+    ) -> Result<(), ScheduleError> {
+        let guild_id = GuildId::new(change.guild_id as u64);
+        ScheduledRoleChange::set_status(&self.db_pool, change.id, ScheduleStatus::Failed).await?;
+
+        notify(
+            http,
+            UserId::new(change.user_id as u64),
+            "🗓️ Scheduled Change Not Applied",
+            format!(
+                "Your scheduled role change #{} in **{}** wasn't made: {}",
+                change.id,
+                guild_name(http, guild_id).await,
+                reason
+            ),
+            EmbedColor::Warning,
+        )
+        .await;
+
+        tracing::info!(
+            change_id = change.id,
+            guild_id = %guild_id,
+            reason = reason,
+            "Scheduled role change rejected"
+        );
+        Ok(())
+    }
+
+    async fn set_role(
+        &self,
+        http: &Http,
+        guild_id: GuildId,
+        role: &BoosterRole,
+        target: &ValidatedChange,
+    ) -> Result<(), ScheduleError> {
+        let pool = self.db_pool.as_ref();
+        let user_id = UserId::new(role.user_id as u64);
+        let role_id = RoleId::new(role.role_id as u64);
+
+        guild_id
+            .edit_role(
+                http,
+                role_id,
+                EditRole::new()
+                    .name(&target.name)
+                    .colour(Colour::new(target.color)),
+            )
+            .await?;
+
+        BoosterRole::update(
+            pool,
+            guild_id,
+            user_id,
+            &target.name,
+            &ColorParser::to_hex_string(target.color),
+            role.secondary_color.as_deref(),
+        )
+        .await?;
+
+        if target.name != role.role_name {
+            BoosterRenameHistory::add(pool, guild_id, user_id, &role.role_name, &target.name)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+async fn guild_name(http: &Http, guild_id: GuildId) -> String {
+    http.get_guild(guild_id)
+        .await
+        .map(|g| g.name)
+        .unwrap_or_else(|_| "the server".to_string())
+}
+
+/// DM the member about their change; closed DMs are not an error
+async fn notify(http: &Http, user_id: UserId, title: &str, description: String, color: EmbedColor) {
+    let embed = CreateEmbed::new()
+        .title(title)
+        .description(description)
+        .color(color.value())
+        .timestamp(Timestamp::now());
+
+    let sent = match user_id.create_dm_channel(http).await {
+        Ok(channel) => channel
+            .send_message(http, CreateMessage::new().embed(embed))
+            .await
+            .map(|_| ()),
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = sent {
+        tracing::debug!(
+            user_id = %user_id,
+            error = ?e,
+            "Could not DM member about scheduled change"
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use crate::data::models::BoosterRoleShare;
+use crate::utils::share_revalidation::removal_settled;
+use crate::utils::shutdown::next_tick;
+use serenity::all::{GuildId, Http, RoleId, UserId};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// How often expired shares are looked for
+const SWEEP_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Pause between removals so a batch of expiries doesn't burst role edits
+const REMOVAL_DELAY: Duration = Duration::from_secs(1);
+
+/// Takes shared roles back once a share's duration runs out
+pub struct ShareExpiryHandler {
+    pub db_pool: Arc<SqlitePool>,
+}
+
+impl ShareExpiryHandler {
+    pub fn new(db_pool: Arc<SqlitePool>) -> Self {
+        Self { db_pool }
+    }
+
+    /// Start the background task that ends expired shares
+    pub fn spawn_scheduler(http: Arc<Http>, db_pool: SqlitePool, shutdown: CancellationToken) {
+        let handler = Self::new(Arc::new(db_pool));
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+            while next_tick(&mut interval, &shutdown).await {
+                handler.sweep(&http).await;
+            }
+        });
+    }
+
+    pub async fn sweep(&self, http: &Http) {
+        let pool = self.db_pool.as_ref();
+        let expired = match BoosterRoleShare::get_expired(pool, chrono::Utc::now()).await {
+            Ok(expired) => expired,
+            Err(e) => {
+                tracing::error!(error = ?e, "Failed to load expired role shares");
+                return;
+            }
+        };
+
+        for share in expired {
+            let guild_id = GuildId::new(share.guild_id as u64);
+            let role_id = RoleId::new(share.role_id as u64);
+            let user_id = UserId::new(share.shared_with_id as u64);
+
+            // Removing a role the member no longer has succeeds, and a member
+            // or role that's gone leaves nothing to remove
+            if let Err(e) = http
+                .remove_member_role(guild_id, user_id, role_id, Some("Role share expired"))
+                .await
+            {
+                if !removal_settled(&e) {
+                    tracing::warn!(
+                        guild_id = %guild_id,
+                        user_id = %user_id,
+                        role_id = %role_id,
+                        error = ?e,
+                        "Failed to remove expired shared role; retrying next sweep"
+                    );
+                    continue;
+                }
+            }
+
+            match BoosterRoleShare::expire(pool, share.id).await {
+                Ok(true) => tracing::info!(
+                    guild_id = %guild_id,
+                    user_id = %user_id,
+                    role_id = %role_id,
+                    "Role share expired"
+                ),
+                Ok(false) => {}
+                Err(e) => tracing::error!(
+                    share_id = share.id,
+                    error = ?e,
+                    "Failed to mark role share expired"
+                ),
+            }
+
+            tokio::time::sleep(REMOVAL_DELAY).await;
+        }
+    }
+}
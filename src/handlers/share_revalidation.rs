@@ -0,0 +1,208 @@
+use crate::data::models::{BoosterRoleShare, GuildSharePolicy, SettingsAuditLog};
+use crate::utils::eligibility::{is_eligible, load_eligibility_config};
+use crate::utils::share_revalidation::{
+    plan_revocations, recipient_state_from_error, revalidate, RecipientState, RevokeReason,
+    MAX_REVOCATIONS_PER_RUN,
+};
+use crate::utils::shutdown::next_tick;
+use crate::utils::EmbedColor;
+use serenity::all::{CreateEmbed, CreateMessage, GuildId, Http, RoleId, Timestamp, UserId};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// How often shares are re-checked
+const SWEEP_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Pause between revocations so a large sweep doesn't burst role removals
+const REVOCATION_DELAY: Duration = Duration::from_secs(1);
+
+type SweepError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Revokes role shares whose recipients left or stopped qualifying
+pub struct ShareRevalidationHandler {
+    pub db_pool: Arc<SqlitePool>,
+}
+
+impl ShareRevalidationHandler {
+    pub fn new(db_pool: Arc<SqlitePool>) -> Self {
+        Self { db_pool }
+    }
+
+    /// Start the background task that sweeps guilds with revalidation on
+    pub fn spawn_scheduler(http: Arc<Http>, db_pool: SqlitePool, shutdown: CancellationToken) {
+        let handler = Self::new(Arc::new(db_pool));
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+            while next_tick(&mut interval, &shutdown).await {
+                handler.sweep(&http).await;
+            }
+        });
+    }
+
+    pub async fn sweep(&self, http: &Http) {
+        let guilds = match GuildSharePolicy::revalidating_guilds(&self.db_pool).await {
+            Ok(guilds) => guilds,
+            Err(e) => {
+                tracing::error!(error = ?e, "Failed to load share revalidation guilds");
+                return;
+            }
+        };
+
+        for guild_id in guilds {
+            if let Err(e) = self.sweep_guild(http, guild_id).await {
+                tracing::error!(
+                    guild_id = %guild_id,
+                    error = ?e,
+                    "Share revalidation failed"
+                );
+            }
+        }
+    }
+
+    async fn sweep_guild(&self, http: &Http, guild_id: GuildId) -> Result<(), SweepError> {
+        let pool = self.db_pool.as_ref();
+
+        let policy = GuildSharePolicy::get(pool, guild_id).await?;
+        let shares = BoosterRoleShare::get_active_for_guild(pool, guild_id).await?;
+        if !policy.enabled || shares.is_empty() {
+            return Ok(());
+        }
+        let eligibility = load_eligibility_config(pool, guild_id).await?;
+
+        let mut verdicts = Vec::with_capacity(shares.len());
+        for share in shares {
+            let user_id = UserId::new(share.shared_with_id as u64);
+            let state = match guild_id.member(http, user_id).await {
+                Ok(member) => RecipientState::Member {
+                    eligible: is_eligible(member.premium_since, &member.roles, &eligibility),
+                },
+                Err(e) => recipient_state_from_error(&e),
+            };
+            verdicts.push((share, revalidate(state, policy)));
+        }
+
+        let (planned, capped) = plan_revocations(verdicts, MAX_REVOCATIONS_PER_RUN);
+        if planned.is_empty() {
+            return Ok(());
+        }
+
+        let bot_id = http.get_current_user().await?.id;
+        let mut revoked = 0;
+        for (share, reason) in planned {
+            let role_id = RoleId::new(share.role_id as u64);
+            let user_id = UserId::new(share.shared_with_id as u64);
+
+            if !BoosterRoleShare::remove(pool, guild_id, role_id, user_id).await? {
+                continue;
+            }
+            revoked += 1;
+
+            if reason == RevokeReason::LostEligibility {
+                if let Err(e) = http
+                    .remove_member_role(guild_id, user_id, role_id, Some("Share revalidation"))
+                    .await
+                {
+                    tracing::warn!(
+                        guild_id = %guild_id,
+                        user_id = %user_id,
+                        role_id = %role_id,
+                        error = ?e,
+                        "Failed to remove revoked shared role"
+                    );
+                }
+            }
+
+            notify_recipient(http, guild_id, user_id, role_id, reason).await;
+
+            SettingsAuditLog::log(
+                pool,
+                guild_id,
+                bot_id,
+                "share_revoked",
+                Some(&format!(
+                    "<@&{}> from <@{}> (owner <@{}>): {}",
+                    role_id,
+                    user_id,
+                    share.owner_id,
+                    reason.as_str()
+                )),
+            )
+            .await?;
+
+            tokio::time::sleep(REVOCATION_DELAY).await;
+        }
+
+        tracing::info!(
+            guild_id = %guild_id,
+            revoked = revoked,
+            capped = capped,
+            "Share revalidation sweep finished"
+        );
+
+        if capped {
+            tracing::warn!(
+                guild_id = %guild_id,
+                cap = MAX_REVOCATIONS_PER_RUN,
+                "Share revalidation hit its per-run cap; the rest wait for the next sweep"
+            );
+            SettingsAuditLog::log(
+                pool,
+                guild_id,
+                bot_id,
+                "share_revalidation_capped",
+                Some(&format!(
+                    "Revoked {} shares, more remain for the next sweep",
+                    revoked
+                )),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Tell a recipient why their share ended; closed DMs are not an error
+async fn notify_recipient(
+    http: &Http,
+    guild_id: GuildId,
+    user_id: UserId,
+    role_id: RoleId,
+    reason: RevokeReason,
+) {
+    let guild_name = http
+        .get_guild(guild_id)
+        .await
+        .map(|g| g.name)
+        .unwrap_or_else(|_| "the server".to_string());
+
+    let embed = CreateEmbed::new()
+        .title("Shared Role Removed")
+        .description(format!(
+            "Your access to a shared booster role (ID `{}`) in **{}** was removed because {}.",
+            role_id,
+            guild_name,
+            reason.describe()
+        ))
+        .color(EmbedColor::Warning.value())
+        .timestamp(Timestamp::now());
+
+    let sent = match user_id.create_dm_channel(http).await {
+        Ok(channel) => channel
+            .send_message(http, CreateMessage::new().embed(embed))
+            .await
+            .map(|_| ()),
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = sent {
+        tracing::debug!(
+            user_id = %user_id,
+            error = ?e,
+            "Could not DM share recipient about revocation"
+        );
+    }
+}
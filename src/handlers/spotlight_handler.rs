@@ -0,0 +1,212 @@
+use crate::bot::Error;
+use crate::data::models::{
+    BoosterRole, BoosterRoleLock, GuildQuietHours, GuildSpotlightSetting, SpotlightHistory,
+};
+use crate::utils::quiet_hours::{AutomatedMessage, QuietDecision};
+use crate::utils::shutdown::next_tick;
+use crate::utils::spotlight::{
+    is_unknown_channel, select_spotlight, SpotlightCandidate,
+};
+use serenity::all::{
+    ChannelId, CreateEmbed, CreateEmbedFooter, CreateMessage, GuildId, Http, Mentionable, RoleId,
+    Timestamp, UserId,
+};
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// How often the scheduler looks for guilds that are due a spotlight
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Result of a single spotlight attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpotlightOutcome {
+    Posted { role_id: RoleId, user_id: UserId },
+    NoCandidates,
+    ChannelGone,
+}
+
+/// Posts the weekly "booster role of the week" highlight
+pub struct SpotlightHandler {
+    pub db_pool: Arc<SqlitePool>,
+}
+
+impl SpotlightHandler {
+    pub fn new(db_pool: Arc<SqlitePool>) -> Self {
+        Self { db_pool }
+    }
+
+    /// Start the background task that posts spotlights for due guilds
+    pub fn spawn_scheduler(http: Arc<Http>, db_pool: SqlitePool, shutdown: CancellationToken) {
+        let handler = Self::new(Arc::new(db_pool));
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SCHEDULER_INTERVAL);
+            while next_tick(&mut interval, &shutdown).await {
+                handler.run_due(&http).await;
+            }
+        });
+    }
+
+    /// Post a spotlight in every guild whose last one is at least a week old
+    pub async fn run_due(&self, http: &Http) {
+        let due = match GuildSpotlightSetting::get_due(&self.db_pool).await {
+            Ok(due) => due,
+            Err(e) => {
+                tracing::error!(error = ?e, "Failed to load due spotlights");
+                return;
+            }
+        };
+
+        for setting in due {
+            let guild_id = GuildId::new(setting.guild_id as u64);
+            let channel_id = ChannelId::new(setting.channel_id as u64);
+
+            // Due spotlights stay due, so a later run posts them once quiet hours end
+            match GuildQuietHours::gate(&self.db_pool, guild_id, AutomatedMessage::Spotlight).await
+            {
+                Ok(QuietDecision::DelayUntil(until)) => {
+                    tracing::debug!(
+                        guild_id = %guild_id,
+                        until = %until,
+                        "Spotlight delayed for quiet hours"
+                    );
+                    continue;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::error!(
+                        guild_id = %guild_id,
+                        error = ?e,
+                        "Failed to check quiet hours for spotlight"
+                    );
+                    continue;
+                }
+            }
+
+            match self.post(http, guild_id, channel_id).await {
+                Ok(SpotlightOutcome::ChannelGone) => {}
+                Ok(_) => {
+                    if let Err(e) =
+                        GuildSpotlightSetting::mark_posted(&self.db_pool, guild_id).await
+                    {
+                        tracing::error!(
+                            guild_id = %guild_id,
+                            error = ?e,
+                            "Failed to record spotlight post time"
+                        );
+                    }
+                }
+                Err(e) => {
+                    tracing::error!(
+                        guild_id = %guild_id,
+                        channel_id = %channel_id,
+                        error = ?e,
+                        "Failed to post scheduled spotlight"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Pick the next booster role and post its spotlight embed.
+    ///
+    /// If the channel has been deleted the guild's spotlight is turned off
+    /// instead of failing every week.
+    pub async fn post(
+        &self,
+        http: &Http,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> Result<SpotlightOutcome, Error> {
+        let pool = self.db_pool.as_ref();
+
+        let booster_roles = BoosterRole::get_all_for_guild(pool, guild_id).await?;
+        let locked: HashSet<i64> = BoosterRoleLock::get_all_for_guild(pool, guild_id)
+            .await?
+            .into_iter()
+            .map(|l| l.role_id)
+            .collect();
+        let last_spotlighted: HashMap<RoleId, _> =
+            SpotlightHistory::latest_for_guild(pool, guild_id)
+                .await?
+                .into_iter()
+                .map(|h| (RoleId::new(h.role_id as u64), h.spotlighted_at))
+                .collect();
+
+        // Only consider roles that still exist on Discord
+        let guild_roles = guild_id.roles(http).await?;
+
+        let candidates: Vec<SpotlightCandidate> = booster_roles
+            .iter()
+            .filter(|r| guild_roles.contains_key(&RoleId::new(r.role_id as u64)))
+            .filter_map(|r| {
+                let created_at = r.created_at?;
+                Some(SpotlightCandidate {
+                    role_id: RoleId::new(r.role_id as u64),
+                    user_id: UserId::new(r.user_id as u64),
+                    created_at,
+                    locked: locked.contains(&r.role_id),
+                })
+            })
+            .collect();
+
+        let Some(role_id) = select_spotlight(&candidates, &last_spotlighted, chrono::Utc::now())
+        else {
+            tracing::info!(guild_id = %guild_id, "No eligible booster roles to spotlight");
+            return Ok(SpotlightOutcome::NoCandidates);
+        };
+
+        let Some(candidate) = candidates.iter().find(|c| c.role_id == role_id) else {
+            return Ok(SpotlightOutcome::NoCandidates);
+        };
+        let Some(role) = guild_roles.get(&role_id) else {
+            return Ok(SpotlightOutcome::NoCandidates);
+        };
+
+        let embed = CreateEmbed::new()
+            .title("🌟 Booster Role of the Week")
+            .description(format!(
+                "This week's spotlight goes to {} by {}!\n\nThank you for boosting the server 💜",
+                role_id.mention(),
+                candidate.user_id.mention()
+            ))
+            .field("Name", &role.name, true)
+            .field("Color", format!("`#{:06X}`", role.colour.0), true)
+            .field(
+                "Created",
+                format!("<t:{}:R>", candidate.created_at.timestamp()),
+                true,
+            )
+            .color(role.colour.0)
+            .footer(CreateEmbedFooter::new(
+                "A new booster role is featured every week",
+            ))
+            .timestamp(Timestamp::now());
+
+        if let Err(e) = channel_id
+            .send_message(http, CreateMessage::new().embed(embed))
+            .await
+        {
+            if is_unknown_channel(&e) {
+                tracing::warn!(
+                    guild_id = %guild_id,
+                    channel_id = %channel_id,
+                    "Spotlight channel no longer exists, disabling spotlight"
+                );
+                GuildSpotlightSetting::disable(pool, guild_id).await?;
+                return Ok(SpotlightOutcome::ChannelGone);
+            }
+            return Err(e.into());
+        }
+
+        SpotlightHistory::add(pool, guild_id, role_id, candidate.user_id).await?;
+
+        Ok(SpotlightOutcome::Posted {
+            role_id,
+            user_id: candidate.user_id,
+        })
+    }
+}
@@ -0,0 +1,317 @@
+use crate::bot::{Context, Error};
+use crate::data::models::GuildSupportChannel;
+use crate::utils::embed_fallback::is_missing_permissions;
+use crate::utils::failure_streak::{
+    help_button_id, may_request_help, render_summary, FailedAttempt, HELP_BUTTON_TIMEOUT,
+};
+use crate::utils::{load_staff_role_ids, EmbedBuilder, EmbedColor};
+use poise::CreateReply;
+use serenity::all::{
+    ButtonStyle, ChannelId, ChannelType, ComponentInteractionCollector, CreateActionRow,
+    CreateAllowedMentions, CreateButton, CreateEmbed, CreateInteractionResponse,
+    CreateInteractionResponseFollowup, CreateInteractionResponseMessage, CreateMessage,
+    CreateThread, GuildId, Http, HttpError, Mentionable, Timestamp, UserId,
+};
+use sqlx::SqlitePool;
+use std::time::Instant;
+use thiserror::Error;
+
+/// Discord JSON error code for "Missing Access"
+const MISSING_ACCESS_CODE: isize = 50001;
+
+#[derive(Error, Debug)]
+pub enum SupportTicketError {
+    #[error("No support channel is configured for this server")]
+    NotConfigured,
+
+    #[error("I don't have permission to create private threads in the support channel")]
+    MissingPermissions,
+
+    #[error("Private threads can't be created in the support channel")]
+    ThreadsUnavailable,
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// Open a private thread in the guild's support channel for a member whose
+/// commands keep failing, add them and staff, and post what went wrong
+pub async fn open_support_thread(
+    http: &Http,
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    user_id: UserId,
+    attempts: &[FailedAttempt],
+) -> Result<ChannelId, SupportTicketError> {
+    let support = GuildSupportChannel::get(pool, guild_id)
+        .await?
+        .ok_or(SupportTicketError::NotConfigured)?;
+    let channel_id = ChannelId::new(support.channel_id as u64);
+
+    let command = attempts
+        .last()
+        .map(|a| a.command.as_str())
+        .unwrap_or("unknown");
+    let thread_name: String = format!("help-{}-{}", user_id, command.replace(' ', "-"))
+        .chars()
+        .take(100)
+        .collect();
+
+    let thread = channel_id
+        .create_thread(
+            http,
+            CreateThread::new(thread_name)
+                .kind(ChannelType::PrivateThread)
+                .invitable(false),
+        )
+        .await
+        .map_err(|e| classify_thread_error(&e))?;
+
+    if let Err(e) = thread.id.add_thread_member(http, user_id).await {
+        tracing::warn!(
+            guild_id = %guild_id,
+            thread_id = %thread.id,
+            user_id = %user_id,
+            error = ?e,
+            "Failed to add member to support thread"
+        );
+    }
+
+    // Mentioning staff roles pulls their members into the private thread
+    let staff_role_ids = load_staff_role_ids(pool, guild_id)
+        .await
+        .unwrap_or_default();
+    let staff_mentions = staff_role_ids
+        .iter()
+        .map(|r| r.mention().to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let embed = EmbedBuilder::warning(
+        "🆘 Staff Help Requested",
+        format!(
+            "{} ran into repeated failures and asked for help.\n\n{}",
+            user_id.mention(),
+            render_summary(attempts)
+        ),
+    );
+
+    let message = CreateMessage::new()
+        .content(format!("{} {}", user_id.mention(), staff_mentions))
+        .embed(embed)
+        .allowed_mentions(
+            CreateAllowedMentions::new()
+                .users(vec![user_id])
+                .roles(staff_role_ids),
+        );
+
+    if let Err(e) = thread.id.send_message(http, message).await {
+        tracing::warn!(
+            guild_id = %guild_id,
+            thread_id = %thread.id,
+            error = ?e,
+            "Failed to post summary in support thread"
+        );
+    }
+
+    tracing::info!(
+        guild_id = %guild_id,
+        thread_id = %thread.id,
+        user_id = %user_id,
+        failures = attempts.len(),
+        "Opened support thread"
+    );
+
+    Ok(thread.id)
+}
+
+fn classify_thread_error(error: &serenity::Error) -> SupportTicketError {
+    let missing_access = matches!(
+        error,
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(response))
+            if response.error.code == MISSING_ACCESS_CODE
+    );
+
+    if missing_access || is_missing_permissions(error) {
+        SupportTicketError::MissingPermissions
+    } else {
+        tracing::warn!(error = ?error, "Support thread creation failed");
+        SupportTicketError::ThreadsUnavailable
+    }
+}
+
+/// Send an error embed with a "Request staff help" button and handle presses
+/// until it expires. Returns `false` without sending anything when the guild
+/// has no support channel, so the caller can report the error normally.
+pub async fn offer_staff_help(
+    ctx: Context<'_>,
+    embed: CreateEmbed,
+    attempts: Vec<FailedAttempt>,
+) -> Result<bool, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(false);
+    };
+    let pool = &ctx.data().db_pool;
+
+    if GuildSupportChannel::get(pool, guild_id).await?.is_none() {
+        return Ok(false);
+    }
+
+    let failing_user = ctx.author().id;
+    let custom_id = help_button_id(ctx.id());
+
+    let embed = embed.field(
+        "Need a hand?",
+        "This keeps failing. Press **Request staff help** to open a private thread with staff.",
+        false,
+    );
+    let button = CreateButton::new(custom_id.clone())
+        .label("Request staff help")
+        .emoji('🆘')
+        .style(ButtonStyle::Secondary);
+
+    let reply = ctx
+        .send(
+            CreateReply::default()
+                .embed(embed.clone())
+                .components(vec![CreateActionRow::Buttons(vec![button])]),
+        )
+        .await?;
+
+    let deadline = Instant::now() + HELP_BUTTON_TIMEOUT;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+
+        let filter_id = custom_id.clone();
+        let Some(interaction) = ComponentInteractionCollector::new(ctx)
+            .channel_id(ctx.channel_id())
+            .filter(move |i| i.data.custom_id == filter_id)
+            .timeout(remaining)
+            .await
+        else {
+            break;
+        };
+
+        if !may_request_help(interaction.user.id, failing_user) {
+            interaction
+                .create_response(
+                    ctx,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(format!(
+                                "Only {} can request help for these failures.",
+                                failing_user.mention()
+                            ))
+                            .ephemeral(true),
+                    ),
+                )
+                .await?;
+            continue;
+        }
+
+        interaction
+            .create_response(
+                ctx,
+                CreateInteractionResponse::Defer(
+                    CreateInteractionResponseMessage::new().ephemeral(true),
+                ),
+            )
+            .await?;
+
+        let followup =
+            match open_support_thread(ctx.http(), pool, guild_id, failing_user, &attempts).await {
+                Ok(thread_id) => {
+                    ctx.data().failure_tracker.clear(
+                        guild_id,
+                        failing_user,
+                        &ctx.command().qualified_name,
+                    );
+                    format!("✅ Staff have been notified in {}.", thread_id.mention())
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        guild_id = %guild_id,
+                        user_id = %failing_user,
+                        error = %e,
+                        "Could not open support thread"
+                    );
+                    format!("❌ {}. Please contact a staff member directly.", e)
+                }
+            };
+
+        interaction
+            .create_followup(
+                ctx,
+                CreateInteractionResponseFollowup::new()
+                    .content(followup)
+                    .ephemeral(true),
+            )
+            .await?;
+        break;
+    }
+
+    // Expire the button so it can't be pressed later
+    reply
+        .edit(ctx, CreateReply::default().embed(embed).components(vec![]))
+        .await?;
+
+    Ok(true)
+}
+
+/// Tell staff right away about a role name blocked by a high-severity word.
+/// Posts to the support channel when one is set, otherwise only logs.
+pub async fn alert_blocked_name(
+    http: &Http,
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    user_id: UserId,
+    name: &str,
+    word: &str,
+) {
+    let support = match GuildSupportChannel::get(pool, guild_id).await {
+        Ok(support) => support,
+        Err(e) => {
+            tracing::error!(guild_id = %guild_id, error = ?e, "Failed to load support channel");
+            None
+        }
+    };
+    let Some(support) = support else {
+        tracing::warn!(
+            guild_id = %guild_id,
+            user_id = %user_id,
+            word = %word,
+            "Blocked role name matched a high-severity word; no support channel to alert"
+        );
+        return;
+    };
+
+    let embed = CreateEmbed::new()
+        .title("🚨 Role Name Blocked")
+        .description(format!(
+            "{} tried to use a role name containing a high-severity blacklisted word.",
+            user_id.mention()
+        ))
+        .field(
+            "Attempted name",
+            format!("`{}`", name.replace('`', "'")),
+            false,
+        )
+        .field("Matched word", format!("||{}||", word), true)
+        .color(EmbedColor::Error.value())
+        .timestamp(Timestamp::now());
+
+    if let Err(e) = ChannelId::new(support.channel_id as u64)
+        .send_message(
+            http,
+            CreateMessage::new()
+                .embed(embed)
+                .allowed_mentions(CreateAllowedMentions::new()),
+        )
+        .await
+    {
+        tracing::warn!(guild_id = %guild_id, error = ?e, "Could not post blocked name alert");
+    }
+}
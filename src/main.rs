@@ -6,7 +6,7 @@ mod handlers;
 mod utils;
 mod testing;
 
-use bot::create_poise_client;
+use bot::{create_poise_client, HealthState, Shutdown};
 use config::Settings;
 use dotenv::dotenv;
 use utils::BotResult;
@@ -19,12 +19,20 @@ async fn main() -> BotResult<()> {
 
     println!("Initializing Discord bot...");
 
-    let mut client = create_poise_client(&settings)
+    let shutdown = Shutdown::new();
+    let health = HealthState::new();
+    if let Some(port) = settings.health_port {
+        health.spawn_server(port, shutdown.token());
+    }
+
+    let mut client = create_poise_client(&settings, shutdown.clone(), health)
         .await
         .map_err(|e| utils::BotError::Config(e.to_string()))?;
+    shutdown.spawn_signal_listener(client.shard_manager.clone());
 
     println!("Starting bot client...");
     client.start().await?;
+    shutdown.finish().await;
 
     Ok(())
 }
@@ -41,6 +41,7 @@ pub enum ComplianceStatus {
     NotTested,
 }
 
+#[derive(Default)]
 pub struct AuditLogger {
     test_results: Vec<TestResult>,
     command_coverage: HashMap<String, CommandCoverage>,
@@ -62,7 +63,7 @@ impl AuditLogger {
         duration_ms: u64,
     ) -> TestResult {
         let passed_scenarios = validation_results.iter()
-            .filter(|r| r.color_compliant && r.format_issues.is_empty())
+            .filter(|r| r.passed())
             .count();
         
         let failed_scenarios = scenarios_count - passed_scenarios;
@@ -76,7 +77,7 @@ impl AuditLogger {
             .enumerate()
             .map(|(i, result)| ValidationSummary {
                 scenario_name: format!("Scenario {}", i + 1),
-                passed: result.color_compliant && result.format_issues.is_empty(),
+                passed: result.passed(),
                 color_compliant: result.color_compliant,
                 format_issues: result.format_issues.clone(),
                 verbosity_score: format!("{:?}", result.verbosity_score),
@@ -128,10 +129,12 @@ impl AuditLogger {
         coverage.compliance_status = compliance_status;
     }
     
+    #[allow(dead_code)]
     pub fn get_command_coverage(&self) -> Vec<CommandCoverage> {
         self.command_coverage.values().cloned().collect()
     }
     
+    #[allow(dead_code)]
     pub fn calculate_overall_coverage(&self, total_commands: usize) -> f64 {
         let tested_commands = self.command_coverage.len();
         if total_commands > 0 {
@@ -149,6 +152,7 @@ impl AuditLogger {
             .collect()
     }
     
+    #[allow(dead_code)]
     pub fn get_test_history_for_command(&self, command_name: &str) -> Vec<&TestResult> {
         self.test_results
             .iter()
@@ -35,7 +35,7 @@ impl IntegrationTestRunner {
         let mut report = TestReport::new();
         
         // Initialize test database
-        let db_pool = init_database("test_bot.db").await?;
+        let db_pool = init_database("test_bot.db", self.settings.database_max_connections).await?;
         
         // Create test data
         let data = Data::new(self.settings.clone(), db_pool);
@@ -132,7 +132,7 @@ impl IntegrationTestRunner {
             description: "Test renaming an existing booster role".to_string(),
             steps: vec![
                 TestStep::new("Create booster role"),
-                TestStep::new("Execute /boosterrole rename new_name:TestRole"),
+                TestStep::new("Execute /boosterrole rename set new_name:TestRole"),
                 TestStep::new("Verify role name is updated"),
                 TestStep::new("Check rename history is recorded"),
             ],
@@ -1 +1,3 @@
-// Testing infrastructure - currently unused but kept for future development
\ No newline at end of file
+// Response checks behind the debug-only /test_responses command
+pub mod audit_logger;
+pub mod response_validator;
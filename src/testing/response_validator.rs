@@ -1,6 +1,10 @@
+use poise::serenity_prelude::CreateEmbed;
 use tracing::{debug, warn};
 use crate::utils::EmbedColor;
 
+/// Emoji [`crate::utils::EmbedBuilder`] puts in front of each title
+const STATUS_EMOJI: &[&str] = &["✅", "❌", "⚠️", "ℹ️"];
+
 #[derive(Debug, Clone)]
 pub enum ResponseType {
     Success,
@@ -8,6 +12,7 @@ pub enum ResponseType {
     Warning,
     Info,
     Primary,
+    #[allow(dead_code)]
     Unknown,
 }
 
@@ -21,6 +26,7 @@ pub enum VerbosityScore {
 
 #[derive(Debug, Clone)]
 pub struct ValidationResult {
+    #[allow(dead_code)]
     pub command: String,
     pub response_type: ResponseType,
     pub color_compliant: bool,
@@ -28,10 +34,18 @@ pub struct ValidationResult {
     pub expected_color: Option<u32>,
     pub verbosity_score: VerbosityScore,
     pub format_issues: Vec<String>,
+    #[allow(dead_code)]
     pub character_count: usize,
+    #[allow(dead_code)]
     pub word_count: usize,
 }
 
+impl ValidationResult {
+    pub fn passed(&self) -> bool {
+        self.color_compliant && self.format_issues.is_empty()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct EmbedData {
     pub title: Option<String>,
@@ -40,6 +54,7 @@ pub struct EmbedData {
     pub fields: Vec<(String, String)>,
 }
 
+#[allow(dead_code)]
 impl EmbedData {
     pub fn new(title: Option<String>, description: Option<String>, color: Option<u32>) -> Self {
         Self {
@@ -56,6 +71,38 @@ impl EmbedData {
     }
 }
 
+impl From<&CreateEmbed> for EmbedData {
+    /// Read an embed back out of the builder through its request body
+    fn from(embed: &CreateEmbed) -> Self {
+        let json = serde_json::to_value(embed).unwrap_or_default();
+        let text = |key: &str| json.get(key).and_then(|v| v.as_str()).map(str::to_string);
+
+        let fields = json
+            .get("fields")
+            .and_then(|fields| fields.as_array())
+            .map(|fields| {
+                fields
+                    .iter()
+                    .map(|field| {
+                        let part = |key: &str| {
+                            field.get(key).and_then(|v| v.as_str()).unwrap_or("").to_string()
+                        };
+                        (part("name"), part("value"))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            title: text("title"),
+            description: text("description"),
+            color: json.get("color").and_then(|v| v.as_u64()).map(|c| c as u32),
+            fields,
+        }
+    }
+}
+
+#[derive(Default)]
 pub struct ResponseValidator;
 
 impl ResponseValidator {
@@ -98,7 +145,17 @@ impl ResponseValidator {
     }
     
     fn classify_response_data(&self, embed_data: &EmbedData) -> (ResponseType, Option<u32>) {
-        let title = embed_data.title.as_deref().unwrap_or("").to_lowercase();
+        let title = embed_data.title.as_deref().unwrap_or("");
+        // EmbedBuilder's emoji says which variant built the embed
+        match STATUS_EMOJI.iter().find(|emoji| title.starts_with(*emoji)) {
+            Some(&"✅") => return (ResponseType::Success, Some(EmbedColor::Success.value())),
+            Some(&"❌") => return (ResponseType::Error, Some(EmbedColor::Error.value())),
+            Some(&"⚠️") => return (ResponseType::Warning, Some(EmbedColor::Warning.value())),
+            Some(_) => return (ResponseType::Info, Some(EmbedColor::Info.value())),
+            None => {}
+        }
+
+        let title = title.to_lowercase();
         let description = embed_data.description.as_deref().unwrap_or("").to_lowercase();
         
         let content = format!("{} {}", title, description);
@@ -110,7 +167,7 @@ impl ResponseValidator {
         } else if content.contains("warning") || content.contains("caution") {
             (ResponseType::Warning, Some(EmbedColor::Warning.value()))
         } else if content.contains("info") || content.contains("information") {
-            (ResponseType::Info, Some(EmbedColor::Warning.value()))
+            (ResponseType::Info, Some(EmbedColor::Info.value()))
         } else {
             (ResponseType::Primary, Some(EmbedColor::Primary.value()))
         }
@@ -167,6 +224,10 @@ impl ResponseValidator {
             if title.len() > 256 {
                 issues.push("Title exceeds 256 character limit".to_string());
             }
+
+            if title.matches(['✅', '❌', '⚠', 'ℹ']).count() > 1 {
+                issues.push("Title repeats the status emoji".to_string());
+            }
         }
         
         if let Some(ref description) = embed_data.description {
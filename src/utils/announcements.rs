@@ -0,0 +1,170 @@
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+pub const MAX_TITLE_LENGTH: usize = 100;
+pub const MAX_BODY_LENGTH: usize = 2000;
+
+/// Pause between announcement DMs
+pub const DM_DELAY: Duration = Duration::from_secs(1);
+
+/// DMs sent before taking a longer break
+pub const BATCH_SIZE: usize = 25;
+
+/// Break after each batch so a long delivery stays well under DM rate limits
+pub const BATCH_PAUSE: Duration = Duration::from_secs(30);
+
+/// Kinds of optional DMs a member can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum NotificationCategory {
+    #[name = "feature updates"]
+    FeatureUpdates,
+}
+
+impl NotificationCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationCategory::FeatureUpdates => "feature_updates",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    Pending,
+    Sent,
+    Failed,
+    /// The member unsubscribed before their DM went out
+    Skipped,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryStatus::Pending => "pending",
+            DeliveryStatus::Sent => "sent",
+            DeliveryStatus::Failed => "failed",
+            DeliveryStatus::Skipped => "skipped",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(DeliveryStatus::Pending),
+            "sent" => Some(DeliveryStatus::Sent),
+            "failed" => Some(DeliveryStatus::Failed),
+            "skipped" => Some(DeliveryStatus::Skipped),
+            _ => None,
+        }
+    }
+}
+
+/// Delivery progress for one announcement
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeliveryTally {
+    pub pending: i64,
+    pub sent: i64,
+    pub failed: i64,
+    pub skipped: i64,
+}
+
+impl DeliveryTally {
+    pub fn from_counts<'a>(counts: impl IntoIterator<Item = (&'a str, i64)>) -> Self {
+        let mut tally = Self::default();
+        for (status, count) in counts {
+            match DeliveryStatus::parse(status) {
+                Some(DeliveryStatus::Pending) => tally.pending += count,
+                Some(DeliveryStatus::Sent) => tally.sent += count,
+                Some(DeliveryStatus::Failed) => tally.failed += count,
+                Some(DeliveryStatus::Skipped) => tally.skipped += count,
+                None => {}
+            }
+        }
+        tally
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.pending == 0
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "{} sent • {} failed • {} skipped • {} pending",
+            self.sent, self.failed, self.skipped, self.pending
+        )
+    }
+}
+
+/// One DM per member, however many guilds they subscribed in, in a stable
+/// order so a resumed delivery walks the same list
+pub fn dedup_recipients(subscriptions: impl IntoIterator<Item = (u64, u64)>) -> Vec<u64> {
+    subscriptions
+        .into_iter()
+        .map(|(_guild, user)| user)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// How long to wait after sending the DM at `index` (0-based) of this run
+pub fn pacing_delay(index: usize) -> Duration {
+    if (index + 1).is_multiple_of(BATCH_SIZE) {
+        BATCH_PAUSE
+    } else {
+        DM_DELAY
+    }
+}
+
+/// Neutralize mass and direct mentions in member-facing text written by staff
+pub fn sanitize_mentions(text: &str) -> String {
+    text.replace("@everyone", "@\u{200B}everyone")
+        .replace("@here", "@\u{200B}here")
+        .replace("<@", "<@\u{200B}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dedup_recipients_across_guilds() {
+        let recipients = dedup_recipients([(1, 30), (2, 10), (1, 10), (3, 20), (2, 30)]);
+        assert_eq!(recipients, vec![10, 20, 30]);
+        assert!(dedup_recipients([]).is_empty());
+    }
+
+    #[test]
+    fn test_pacing_schedule() {
+        let delays: Vec<Duration> = (0..BATCH_SIZE * 2).map(pacing_delay).collect();
+        assert_eq!(delays[0], DM_DELAY);
+        assert_eq!(delays[BATCH_SIZE - 1], BATCH_PAUSE);
+        assert_eq!(delays[BATCH_SIZE], DM_DELAY);
+        assert_eq!(delays[BATCH_SIZE * 2 - 1], BATCH_PAUSE);
+        assert_eq!(delays.iter().filter(|d| **d == BATCH_PAUSE).count(), 2);
+
+        // Fifty DMs take a little over two minutes
+        let total: Duration = delays.iter().sum();
+        assert_eq!(total, DM_DELAY * 48 + BATCH_PAUSE * 2);
+    }
+
+    #[test]
+    fn test_tally_tracks_completion() {
+        let tally = DeliveryTally::from_counts([("sent", 5), ("failed", 1), ("pending", 2)]);
+        assert_eq!(tally.sent, 5);
+        assert_eq!(tally.failed, 1);
+        assert!(!tally.is_complete());
+
+        let done = DeliveryTally::from_counts([("sent", 7), ("skipped", 1), ("bogus", 3)]);
+        assert!(done.is_complete());
+        assert_eq!(done.describe(), "7 sent • 0 failed • 1 skipped • 0 pending");
+    }
+
+    #[test]
+    fn test_sanitize_mentions() {
+        let text = sanitize_mentions("Hey @everyone and @here, thanks <@123> and <@&456>!");
+        assert!(!text.contains("@everyone"));
+        assert!(!text.contains("@here"));
+        assert!(!text.contains("<@123>"));
+        assert!(!text.contains("<@&456>"));
+        assert_eq!(sanitize_mentions("New: gradients!"), "New: gradients!");
+    }
+}
@@ -0,0 +1,503 @@
+use poise::serenity_prelude as serenity;
+use serenity::{
+    ArgumentConvert, CacheHttp, ChannelId, CommandInteraction, CreateCommandOption, GuildId,
+    Member, ResolvedValue, Role, RoleId, User, UserId,
+};
+use std::ops::Deref;
+use thiserror::Error;
+
+/// What a command argument refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgKind {
+    Role,
+    Member,
+}
+
+impl ArgKind {
+    pub fn noun(self) -> &'static str {
+        match self {
+            Self::Role => "role",
+            Self::Member => "member",
+        }
+    }
+
+    /// Argument forms accepted by prefix commands, shown when parsing fails
+    pub fn accepted_forms(self) -> &'static str {
+        match self {
+            Self::Role => {
+                "a role mention (`@Role`), a role ID, or the role name (quote names with spaces)"
+            }
+            Self::Member => {
+                "a mention (`@User`), a user ID, or their username or nickname (quote names with spaces)"
+            }
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ArgParseError {
+    #[error("This argument can only be used in a server")]
+    NotInGuild,
+
+    #[error("`{input}` is not a {}. Use {}", expected.noun(), expected.accepted_forms())]
+    WrongKind { expected: ArgKind, input: String },
+
+    #[error("No {} matches `{input}`. Use {}", expected.noun(), expected.accepted_forms())]
+    NotFound { expected: ArgKind, input: String },
+
+    #[error("More than one {} is named `{input}`. Use a mention or ID instead", expected.noun())]
+    Ambiguous { expected: ArgKind, input: String },
+
+    #[error("Couldn't look up server {}s right now, please try again", expected.noun())]
+    Unavailable { expected: ArgKind },
+}
+
+/// How a raw prefix argument refers to something
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgReference<'a> {
+    /// `<@&id>`
+    RoleMention(RoleId),
+    /// `<@id>` or `<@!id>`
+    UserMention(UserId),
+    /// A bare snowflake
+    Id(u64),
+    /// Anything else, with a leading `@` removed
+    Name(&'a str),
+}
+
+/// Classify a raw argument as a mention, ID, or name
+pub fn classify(input: &str) -> ArgReference<'_> {
+    let input = input.trim();
+
+    if let Some(inner) = input.strip_prefix("<@").and_then(|s| s.strip_suffix('>')) {
+        if let Some(id) = inner.strip_prefix('&').and_then(parse_snowflake) {
+            return ArgReference::RoleMention(RoleId::new(id));
+        }
+        if let Some(id) = parse_snowflake(inner.strip_prefix('!').unwrap_or(inner)) {
+            return ArgReference::UserMention(UserId::new(id));
+        }
+    }
+
+    if let Some(id) = parse_snowflake(input) {
+        return ArgReference::Id(id);
+    }
+
+    ArgReference::Name(input.strip_prefix('@').unwrap_or(input).trim())
+}
+
+fn parse_snowflake(s: &str) -> Option<u64> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok().filter(|&id| id != 0)
+}
+
+/// Find the one candidate with a name matching `name` case-insensitively
+fn match_by_name<'a, T: Copy + PartialEq>(
+    candidates: impl IntoIterator<Item = (T, &'a str)>,
+    name: &str,
+    expected: ArgKind,
+) -> Result<T, ArgParseError> {
+    let mut found: Option<T> = None;
+    for (id, candidate) in candidates {
+        if !candidate.eq_ignore_ascii_case(name) {
+            continue;
+        }
+        match found {
+            Some(existing) if existing != id => {
+                return Err(ArgParseError::Ambiguous {
+                    expected,
+                    input: name.to_string(),
+                })
+            }
+            _ => found = Some(id),
+        }
+    }
+
+    found.ok_or_else(|| ArgParseError::NotFound {
+        expected,
+        input: name.to_string(),
+    })
+}
+
+/// Resolve a role argument against a guild's roles
+pub fn resolve_role<'a>(
+    roles: impl IntoIterator<Item = (RoleId, &'a str)>,
+    input: &str,
+) -> Result<RoleId, ArgParseError> {
+    let not_found = || ArgParseError::NotFound {
+        expected: ArgKind::Role,
+        input: input.to_string(),
+    };
+
+    match classify(input) {
+        ArgReference::RoleMention(id) => roles
+            .into_iter()
+            .any(|(role_id, _)| role_id == id)
+            .then_some(id)
+            .ok_or_else(not_found),
+        ArgReference::Id(id) => {
+            let id = RoleId::new(id);
+            roles
+                .into_iter()
+                .any(|(role_id, _)| role_id == id)
+                .then_some(id)
+                .ok_or_else(not_found)
+        }
+        ArgReference::UserMention(_) => Err(ArgParseError::WrongKind {
+            expected: ArgKind::Role,
+            input: input.to_string(),
+        }),
+        ArgReference::Name(name) => match_by_name(roles, name, ArgKind::Role),
+    }
+}
+
+/// Resolve a member name against search results, matching username,
+/// display name, or nickname
+pub fn resolve_member_name(members: &[Member], name: &str) -> Result<UserId, ArgParseError> {
+    let candidates = members.iter().flat_map(|m| {
+        [
+            Some(m.user.name.as_str()),
+            m.user.global_name.as_deref(),
+            m.nick.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(move |n| (m.user.id, n))
+    });
+
+    match_by_name(candidates, name, ArgKind::Member)
+}
+
+/// Role parameter that also accepts `@Name` and case-insensitive names in
+/// prefix commands, and falls back to HTTP when the guild isn't cached.
+/// Slash commands use Discord's role picker.
+#[derive(Debug, Clone)]
+pub struct RoleArg(pub Role);
+
+impl Deref for RoleArg {
+    type Target = Role;
+
+    fn deref(&self) -> &Role {
+        &self.0
+    }
+}
+
+#[async_trait::async_trait]
+impl ArgumentConvert for RoleArg {
+    type Err = ArgParseError;
+
+    async fn convert(
+        ctx: impl CacheHttp,
+        guild_id: Option<GuildId>,
+        _channel_id: Option<ChannelId>,
+        s: &str,
+    ) -> Result<Self, Self::Err> {
+        let guild_id = guild_id.ok_or(ArgParseError::NotInGuild)?;
+
+        let cached: Option<Vec<Role>> = ctx.cache().and_then(|cache| {
+            cache
+                .guild(guild_id)
+                .map(|g| g.roles.values().cloned().collect())
+        });
+        let roles = match cached {
+            Some(roles) => roles,
+            None => guild_id
+                .roles(ctx.http())
+                .await
+                .map_err(|_| ArgParseError::Unavailable {
+                    expected: ArgKind::Role,
+                })?
+                .into_values()
+                .collect(),
+        };
+
+        let role_id = resolve_role(roles.iter().map(|r| (r.id, r.name.as_str())), s)?;
+        roles
+            .into_iter()
+            .find(|r| r.id == role_id)
+            .map(Self)
+            .ok_or(ArgParseError::NotFound {
+                expected: ArgKind::Role,
+                input: s.to_string(),
+            })
+    }
+}
+
+#[async_trait::async_trait]
+impl poise::SlashArgument for RoleArg {
+    async fn extract(
+        ctx: &serenity::Context,
+        interaction: &CommandInteraction,
+        value: &ResolvedValue<'_>,
+    ) -> Result<Self, poise::SlashArgError> {
+        poise::extract_slash_argument!(Role, ctx, interaction, value)
+            .await
+            .map(Self)
+    }
+
+    fn create(builder: CreateCommandOption) -> CreateCommandOption {
+        poise::create_slash_argument!(Role, builder)
+    }
+}
+
+/// Member parameter that also accepts `@Name`, display names, and
+/// nicknames in prefix commands. Slash commands use Discord's user picker.
+#[derive(Debug, Clone)]
+pub struct MemberArg(pub Member);
+
+impl Deref for MemberArg {
+    type Target = Member;
+
+    fn deref(&self) -> &Member {
+        &self.0
+    }
+}
+
+#[async_trait::async_trait]
+impl ArgumentConvert for MemberArg {
+    type Err = ArgParseError;
+
+    async fn convert(
+        ctx: impl CacheHttp,
+        guild_id: Option<GuildId>,
+        _channel_id: Option<ChannelId>,
+        s: &str,
+    ) -> Result<Self, Self::Err> {
+        let guild_id = guild_id.ok_or(ArgParseError::NotInGuild)?;
+        let not_found = || ArgParseError::NotFound {
+            expected: ArgKind::Member,
+            input: s.to_string(),
+        };
+
+        let user_id = match classify(s) {
+            ArgReference::UserMention(id) => id,
+            ArgReference::Id(id) => UserId::new(id),
+            ArgReference::RoleMention(_) => {
+                return Err(ArgParseError::WrongKind {
+                    expected: ArgKind::Member,
+                    input: s.to_string(),
+                })
+            }
+            ArgReference::Name(name) => {
+                let results = guild_id
+                    .search_members(ctx.http(), name, Some(100))
+                    .await
+                    .map_err(|_| ArgParseError::Unavailable {
+                        expected: ArgKind::Member,
+                    })?;
+                let user_id = resolve_member_name(&results, name)?;
+                return results
+                    .into_iter()
+                    .find(|m| m.user.id == user_id)
+                    .map(Self)
+                    .ok_or_else(not_found);
+            }
+        };
+
+        guild_id
+            .member(&ctx, user_id)
+            .await
+            .map(Self)
+            .map_err(|_| not_found())
+    }
+}
+
+#[async_trait::async_trait]
+impl poise::SlashArgument for MemberArg {
+    async fn extract(
+        ctx: &serenity::Context,
+        interaction: &CommandInteraction,
+        value: &ResolvedValue<'_>,
+    ) -> Result<Self, poise::SlashArgError> {
+        poise::extract_slash_argument!(Member, ctx, interaction, value)
+            .await
+            .map(Self)
+    }
+
+    fn create(builder: CreateCommandOption) -> CreateCommandOption {
+        poise::create_slash_argument!(Member, builder)
+    }
+}
+
+/// User parameter resolved like [`MemberArg`] in prefix commands
+#[derive(Debug, Clone)]
+pub struct UserArg(pub User);
+
+impl Deref for UserArg {
+    type Target = User;
+
+    fn deref(&self) -> &User {
+        &self.0
+    }
+}
+
+#[async_trait::async_trait]
+impl ArgumentConvert for UserArg {
+    type Err = ArgParseError;
+
+    async fn convert(
+        ctx: impl CacheHttp,
+        guild_id: Option<GuildId>,
+        channel_id: Option<ChannelId>,
+        s: &str,
+    ) -> Result<Self, Self::Err> {
+        MemberArg::convert(ctx, guild_id, channel_id, s)
+            .await
+            .map(|member| Self(member.0.user))
+    }
+}
+
+#[async_trait::async_trait]
+impl poise::SlashArgument for UserArg {
+    async fn extract(
+        ctx: &serenity::Context,
+        interaction: &CommandInteraction,
+        value: &ResolvedValue<'_>,
+    ) -> Result<Self, poise::SlashArgError> {
+        poise::extract_slash_argument!(User, ctx, interaction, value)
+            .await
+            .map(Self)
+    }
+
+    fn create(builder: CreateCommandOption) -> CreateCommandOption {
+        poise::create_slash_argument!(User, builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROLE: u64 = 1_200_000_000_000_000_001;
+    const OTHER_ROLE: u64 = 1_200_000_000_000_000_002;
+    const USER: u64 = 1_100_000_000_000_000_001;
+
+    fn guild_roles() -> Vec<(RoleId, &'static str)> {
+        vec![
+            (RoleId::new(ROLE), "Booster Blue"),
+            (RoleId::new(OTHER_ROLE), "Moderators"),
+        ]
+    }
+
+    fn member(id: u64, name: &str, global_name: Option<&str>, nick: Option<&str>) -> Member {
+        serde_json::from_value(serde_json::json!({
+            "user": {
+                "id": id.to_string(),
+                "username": name,
+                "global_name": global_name,
+                "discriminator": "0",
+                "avatar": null,
+            },
+            "nick": nick,
+            "roles": [],
+            "joined_at": "2024-01-01T00:00:00+00:00",
+            "deaf": false,
+            "mute": false,
+            "flags": 0,
+            "guild_id": "1",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_classify_mentions_ids_and_names() {
+        assert_eq!(
+            classify(&format!("<@&{}>", ROLE)),
+            ArgReference::RoleMention(RoleId::new(ROLE))
+        );
+        assert_eq!(
+            classify(&format!("<@{}>", USER)),
+            ArgReference::UserMention(UserId::new(USER))
+        );
+        assert_eq!(
+            classify(&format!("<@!{}>", USER)),
+            ArgReference::UserMention(UserId::new(USER))
+        );
+        assert_eq!(classify(&USER.to_string()), ArgReference::Id(USER));
+        assert_eq!(
+            classify("@Booster Blue"),
+            ArgReference::Name("Booster Blue")
+        );
+        assert_eq!(classify("0"), ArgReference::Name("0"));
+        assert_eq!(classify("<@&abc>"), ArgReference::Name("<@&abc>"));
+    }
+
+    /// Every form a prefix user might type for the role in `link`,
+    /// `award set`, `base`, and `share role`
+    #[test]
+    fn test_role_argument_forms_resolve() {
+        let forms = [
+            format!("<@&{}>", ROLE),
+            ROLE.to_string(),
+            "Booster Blue".to_string(),
+            "booster blue".to_string(),
+            "@Booster Blue".to_string(),
+        ];
+
+        for form in forms {
+            assert_eq!(
+                resolve_role(guild_roles(), &form),
+                Ok(RoleId::new(ROLE)),
+                "form {form:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_role_argument_errors_name_accepted_forms() {
+        let wrong = resolve_role(guild_roles(), &format!("<@{}>", USER)).unwrap_err();
+        assert!(matches!(wrong, ArgParseError::WrongKind { .. }));
+        assert!(wrong.to_string().contains("role mention"));
+
+        let missing = resolve_role(guild_roles(), "Nonexistent").unwrap_err();
+        assert_eq!(
+            missing.to_string(),
+            format!(
+                "No role matches `Nonexistent`. Use {}",
+                ArgKind::Role.accepted_forms()
+            )
+        );
+
+        let unknown_id = resolve_role(guild_roles(), "42").unwrap_err();
+        assert!(matches!(unknown_id, ArgParseError::NotFound { .. }));
+
+        let duplicates = vec![(RoleId::new(1), "Blue"), (RoleId::new(2), "blue")];
+        assert!(matches!(
+            resolve_role(duplicates, "Blue"),
+            Err(ArgParseError::Ambiguous { .. })
+        ));
+    }
+
+    /// Name forms a prefix user might type for the member in `link` and
+    /// `share add`; mentions and IDs are fetched directly
+    #[test]
+    fn test_member_name_forms_resolve() {
+        let members = vec![
+            member(USER, "deathfan", Some("Death Fan"), Some("Booster Queen")),
+            member(USER + 1, "deathfan2", None, None),
+        ];
+
+        for form in [
+            "deathfan",
+            "DeathFan",
+            "@deathfan",
+            "Death Fan",
+            "booster queen",
+        ] {
+            let ArgReference::Name(name) = classify(form) else {
+                panic!("{form:?} should be a name");
+            };
+            assert_eq!(
+                resolve_member_name(&members, name),
+                Ok(UserId::new(USER)),
+                "form {form:?}"
+            );
+        }
+
+        assert!(matches!(
+            resolve_member_name(&members, "deathfan3"),
+            Err(ArgParseError::NotFound { .. })
+        ));
+    }
+}
@@ -0,0 +1,142 @@
+use crate::data::models::{GuildBoosterLogChannel, SettingsAuditLog};
+use crate::utils::spotlight::parse_sqlite_timestamp;
+use crate::utils::EmbedColor;
+use serenity::all::{ChannelId, CreateEmbed, CreateMessage, GuildId, Http, Timestamp, UserId};
+use sqlx::SqlitePool;
+
+/// Start a booster log entry naming who acted and on which role. Callers add
+/// before/after fields where the action changed something.
+pub fn booster_audit_embed(action: &str, actor: UserId, role: impl Into<String>) -> CreateEmbed {
+    CreateEmbed::new()
+        .title(action)
+        .color(EmbedColor::Info.value())
+        .field("By", format!("<@{}>", actor), true)
+        .field("Role", role.into(), true)
+        .timestamp(Timestamp::now())
+}
+
+/// A field value showing a change, or just the value when it didn't change
+pub fn before_after(before: &str, after: &str) -> String {
+    if before == after {
+        after.to_string()
+    } else {
+        format!("{} → {}", before, after)
+    }
+}
+
+/// Longest `details` shown per settings audit entry, so a full page of
+/// entries stays inside an embed description
+pub const MAX_AUDIT_DETAILS_CHARS: usize = 300;
+
+/// One line of `/settings audit`: when, who, what, and the details on a
+/// single line cut to [`MAX_AUDIT_DETAILS_CHARS`]
+pub fn settings_audit_line(entry: &SettingsAuditLog) -> String {
+    let when = match entry.timestamp.as_deref().and_then(parse_sqlite_timestamp) {
+        Some(at) => format!("<t:{}:R>", at.timestamp()),
+        None => entry.timestamp.clone().unwrap_or_else(|| "Unknown".to_string()),
+    };
+    let mut line = format!("{} — <@{}> `{}`", when, entry.user_id, entry.action);
+
+    if let Some(details) = entry.details.as_deref() {
+        let details = details.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !details.is_empty() {
+            line.push_str(&format!(" ({})", truncate_details(&details)));
+        }
+    }
+    line
+}
+
+fn truncate_details(details: &str) -> String {
+    if details.chars().count() <= MAX_AUDIT_DETAILS_CHARS {
+        return details.to_string();
+    }
+
+    let mut truncated: String = details.chars().take(MAX_AUDIT_DETAILS_CHARS - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Post `embed` to the guild's booster log channel, if one is set. A missing
+/// channel or permission is logged and otherwise ignored, so the action being
+/// audited never fails because of it.
+pub async fn send_booster_audit(
+    http: &Http,
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    embed: CreateEmbed,
+) {
+    let channel = match GuildBoosterLogChannel::get(pool, guild_id).await {
+        Ok(Some(channel)) => ChannelId::new(channel.channel_id as u64),
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!(guild_id = %guild_id, error = ?e, "Failed to load booster log channel");
+            return;
+        }
+    };
+
+    if let Err(e) = channel
+        .send_message(http, CreateMessage::new().embed(embed))
+        .await
+    {
+        tracing::warn!(
+            guild_id = %guild_id,
+            channel_id = %channel,
+            error = ?e,
+            "Failed to post to booster log channel"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_before_after_only_shows_changes() {
+        assert_eq!(before_after("#FF0000", "#FF0000"), "#FF0000");
+        assert_eq!(before_after("Old", "New"), "Old → New");
+    }
+
+    #[test]
+    fn test_settings_audit_line_keeps_details_on_one_short_line() {
+        let mut entry = SettingsAuditLog {
+            id: 1,
+            guild_id: GuildId::new(1),
+            user_id: UserId::new(7),
+            action: "command_disable".to_string(),
+            details: Some("boosterrole\n  share".to_string()),
+            timestamp: Some("2026-01-02 03:04:05".to_string()),
+        };
+        assert_eq!(
+            settings_audit_line(&entry),
+            "<t:1767323045:R> — <@7> `command_disable` (boosterrole share)"
+        );
+
+        entry.details = Some("é".repeat(MAX_AUDIT_DETAILS_CHARS + 50));
+        let line = settings_audit_line(&entry);
+        assert!(line.ends_with("…)"));
+        assert_eq!(
+            line.chars().filter(|c| *c == 'é').count(),
+            MAX_AUDIT_DETAILS_CHARS - 1
+        );
+
+        entry.details = None;
+        entry.timestamp = None;
+        assert_eq!(settings_audit_line(&entry), "Unknown — <@7> `command_disable`");
+    }
+
+    #[test]
+    fn test_audit_embed_names_actor_and_role() {
+        let embed = serde_json::to_value(booster_audit_embed(
+            "Booster Role Renamed",
+            UserId::new(7),
+            "<@&9>",
+        ))
+        .unwrap();
+
+        assert_eq!(embed["title"], "Booster Role Renamed");
+        assert_eq!(embed["fields"][0]["value"], "<@7>");
+        assert_eq!(embed["fields"][1]["value"], "<@&9>");
+        assert!(embed["timestamp"].is_string());
+    }
+}
@@ -0,0 +1,171 @@
+use serenity::all::{GuildId, Member, UserId};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// What a member update means for their boost, judged against the last
+/// state seen for them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoostChange {
+    /// Same as last time; nothing boost-related to do
+    Unchanged,
+    Started,
+    Ended,
+    /// No earlier state to compare against
+    FirstSeen {
+        boosting: bool,
+    },
+}
+
+/// Whether each member was boosting when last seen, so member updates (most
+/// of which are nicknames and avatars) only do boost work on a real change
+#[derive(Debug, Clone, Default)]
+pub struct BoostStateCache {
+    states: Arc<RwLock<HashMap<(u64, u64), bool>>>,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+}
+
+/// Snapshot of the boost state cache for `cache_status`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoostStateStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub tracked: usize,
+    pub boosting: usize,
+}
+
+impl BoostStateStats {
+    /// Share of updates answered from the cache, as a percentage
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+        (total > 0).then(|| self.hits as f64 * 100.0 / total as f64)
+    }
+}
+
+impl BoostStateCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record members from guild create or member chunk payloads, so their
+    /// first update is already a hit
+    pub async fn seed<'a>(&self, guild_id: GuildId, members: impl IntoIterator<Item = &'a Member>) {
+        let mut states = self.states.write().await;
+        for member in members {
+            states.insert(
+                (guild_id.get(), member.user.id.get()),
+                member.premium_since.is_some(),
+            );
+        }
+    }
+
+    /// Store the member's current state and say how it differs from before
+    pub async fn observe(&self, guild_id: GuildId, user_id: UserId, boosting: bool) -> BoostChange {
+        let previous = self
+            .states
+            .write()
+            .await
+            .insert((guild_id.get(), user_id.get()), boosting);
+
+        let counter = if previous.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        match (previous, boosting) {
+            (None, boosting) => BoostChange::FirstSeen { boosting },
+            (Some(was), now) if was == now => BoostChange::Unchanged,
+            (Some(_), true) => BoostChange::Started,
+            (Some(_), false) => BoostChange::Ended,
+        }
+    }
+
+    pub async fn forget_member(&self, guild_id: GuildId, user_id: UserId) {
+        self.states
+            .write()
+            .await
+            .remove(&(guild_id.get(), user_id.get()));
+    }
+
+    pub async fn forget_guild(&self, guild_id: GuildId) {
+        self.states
+            .write()
+            .await
+            .retain(|(guild, _), _| *guild != guild_id.get());
+    }
+
+    pub async fn stats(&self) -> BoostStateStats {
+        let states = self.states.read().await;
+
+        BoostStateStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            tracked: states.len(),
+            boosting: states.values().filter(|boosting| **boosting).count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_only_real_transitions_are_reported() {
+        let cache = BoostStateCache::new();
+        let (guild, user) = (GuildId::new(1), UserId::new(2));
+
+        assert_eq!(
+            cache.observe(guild, user, false).await,
+            BoostChange::FirstSeen { boosting: false }
+        );
+        // Nickname and avatar updates repeat the same state
+        assert_eq!(
+            cache.observe(guild, user, false).await,
+            BoostChange::Unchanged
+        );
+        assert_eq!(cache.observe(guild, user, true).await, BoostChange::Started);
+        assert_eq!(
+            cache.observe(guild, user, true).await,
+            BoostChange::Unchanged
+        );
+        assert_eq!(cache.observe(guild, user, false).await, BoostChange::Ended);
+
+        let stats = cache.stats().await;
+        assert_eq!(
+            stats,
+            BoostStateStats {
+                hits: 4,
+                misses: 1,
+                tracked: 1,
+                boosting: 0,
+            }
+        );
+        assert_eq!(stats.hit_rate(), Some(80.0));
+    }
+
+    #[tokio::test]
+    async fn test_forgotten_members_are_seen_fresh() {
+        let cache = BoostStateCache::new();
+        let user = UserId::new(2);
+        cache.observe(GuildId::new(1), user, true).await;
+        cache.observe(GuildId::new(3), user, true).await;
+
+        cache.forget_guild(GuildId::new(1)).await;
+        assert_eq!(
+            cache.observe(GuildId::new(1), user, true).await,
+            BoostChange::FirstSeen { boosting: true }
+        );
+
+        cache.forget_member(GuildId::new(3), user).await;
+        assert_eq!(
+            cache.observe(GuildId::new(3), user, true).await,
+            BoostChange::FirstSeen { boosting: true }
+        );
+        assert_eq!(BoostStateCache::new().stats().await.hit_rate(), None);
+    }
+}
@@ -0,0 +1,135 @@
+use super::shutdown::next_tick;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::ops::AddAssign;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// How often buffered counts are written to the database
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Writes one drained batch to the database, adding to any stored counts
+pub type AddBatch<K, V> =
+    for<'a> fn(
+        &'a SqlitePool,
+        &'a [(K, V)],
+    ) -> Pin<Box<dyn Future<Output = Result<(), sqlx::Error>> + Send + 'a>>;
+
+/// Counts merged in memory per key and flushed to the database in batches,
+/// so hot paths don't cost a write each
+#[derive(Debug)]
+pub struct BufferedCounter<K, V> {
+    name: &'static str,
+    pending: Arc<Mutex<HashMap<K, V>>>,
+    add_batch: AddBatch<K, V>,
+}
+
+// Clones share the buffer, so keys and counts needn't be `Clone`
+impl<K, V> Clone for BufferedCounter<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name,
+            pending: self.pending.clone(),
+            add_batch: self.add_batch,
+        }
+    }
+}
+
+impl<K, V> BufferedCounter<K, V>
+where
+    K: Eq + Hash + Ord + Send + 'static,
+    V: Default + AddAssign + Send + 'static,
+{
+    /// `name` identifies the counter in flush failure logs
+    pub fn new(name: &'static str, add_batch: AddBatch<K, V>) -> Self {
+        Self {
+            name,
+            pending: Arc::default(),
+            add_batch,
+        }
+    }
+
+    pub fn add(&self, key: K, value: V) {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        *pending.entry(key).or_default() += value;
+    }
+
+    /// Write buffered counts to the database
+    pub async fn flush(&self, pool: &SqlitePool) -> Result<(), sqlx::Error> {
+        let batch = self.drain();
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        if let Err(e) = (self.add_batch)(pool, &batch).await {
+            self.restore(batch);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Start the background task that flushes counts every [`FLUSH_INTERVAL`]
+    pub fn spawn_flusher(&self, pool: SqlitePool, shutdown: CancellationToken)
+    where
+        K: Sync,
+        V: Sync,
+    {
+        let counter = self.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+            while next_tick(&mut interval, &shutdown).await {
+                if let Err(e) = counter.flush(&pool).await {
+                    tracing::error!(counter = counter.name, error = ?e, "Failed to flush counts");
+                }
+            }
+        });
+    }
+
+    /// Take everything recorded since the last drain, ordered by key
+    pub fn drain(&self) -> Vec<(K, V)> {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        let mut drained: Vec<_> = pending.drain().collect();
+        drained.sort_by(|a, b| a.0.cmp(&b.0));
+        drained
+    }
+
+    /// Put counts back after a failed flush so they aren't lost
+    pub fn restore(&self, batch: Vec<(K, V)>) {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        for (key, value) in batch {
+            *pending.entry(key).or_default() += value;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failing_batch<'a>(
+        _pool: &'a SqlitePool,
+        _batch: &'a [(u32, u64)],
+    ) -> Pin<Box<dyn Future<Output = Result<(), sqlx::Error>> + Send + 'a>> {
+        Box::pin(async { Err(sqlx::Error::PoolClosed) })
+    }
+
+    #[tokio::test]
+    async fn test_failed_flush_keeps_counts() {
+        let pool = crate::data::database::init_memory_database().await.unwrap();
+        let counter = BufferedCounter::new("test", failing_batch);
+
+        counter.add(2, 1);
+        counter.add(1, 1);
+        counter.add(2, 3);
+        assert!(counter.flush(&pool).await.is_err());
+
+        counter.add(1, 1);
+        assert_eq!(counter.drain(), vec![(1, 2), (2, 4)]);
+        assert!(counter.drain().is_empty());
+    }
+}
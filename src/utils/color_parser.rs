@@ -1,14 +1,196 @@
 use crate::utils::error::BotError;
 use std::collections::HashMap;
+use std::sync::OnceLock;
 
 pub struct ColorParser;
 
+/// The CSS named colors
+const CSS_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xF0F8FF),
+    ("antiquewhite", 0xFAEBD7),
+    ("aqua", 0x00FFFF),
+    ("aquamarine", 0x7FFFD4),
+    ("azure", 0xF0FFFF),
+    ("beige", 0xF5F5DC),
+    ("bisque", 0xFFE4C4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xFFEBCD),
+    ("blue", 0x0000FF),
+    ("blueviolet", 0x8A2BE2),
+    ("brown", 0xA52A2A),
+    ("burlywood", 0xDEB887),
+    ("cadetblue", 0x5F9EA0),
+    ("chartreuse", 0x7FFF00),
+    ("chocolate", 0xD2691E),
+    ("coral", 0xFF7F50),
+    ("cornflowerblue", 0x6495ED),
+    ("cornsilk", 0xFFF8DC),
+    ("crimson", 0xDC143C),
+    ("cyan", 0x00FFFF),
+    ("darkblue", 0x00008B),
+    ("darkcyan", 0x008B8B),
+    ("darkgoldenrod", 0xB8860B),
+    ("darkgray", 0xA9A9A9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xA9A9A9),
+    ("darkkhaki", 0xBDB76B),
+    ("darkmagenta", 0x8B008B),
+    ("darkolivegreen", 0x556B2F),
+    ("darkorange", 0xFF8C00),
+    ("darkorchid", 0x9932CC),
+    ("darkred", 0x8B0000),
+    ("darksalmon", 0xE9967A),
+    ("darkseagreen", 0x8FBC8F),
+    ("darkslateblue", 0x483D8B),
+    ("darkslategray", 0x2F4F4F),
+    ("darkslategrey", 0x2F4F4F),
+    ("darkturquoise", 0x00CED1),
+    ("darkviolet", 0x9400D3),
+    ("deeppink", 0xFF1493),
+    ("deepskyblue", 0x00BFFF),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1E90FF),
+    ("firebrick", 0xB22222),
+    ("floralwhite", 0xFFFAF0),
+    ("forestgreen", 0x228B22),
+    ("fuchsia", 0xFF00FF),
+    ("gainsboro", 0xDCDCDC),
+    ("ghostwhite", 0xF8F8FF),
+    ("gold", 0xFFD700),
+    ("goldenrod", 0xDAA520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xADFF2F),
+    ("grey", 0x808080),
+    ("honeydew", 0xF0FFF0),
+    ("hotpink", 0xFF69B4),
+    ("indianred", 0xCD5C5C),
+    ("indigo", 0x4B0082),
+    ("ivory", 0xFFFFF0),
+    ("khaki", 0xF0E68C),
+    ("lavender", 0xE6E6FA),
+    ("lavenderblush", 0xFFF0F5),
+    ("lawngreen", 0x7CFC00),
+    ("lemonchiffon", 0xFFFACD),
+    ("lightblue", 0xADD8E6),
+    ("lightcoral", 0xF08080),
+    ("lightcyan", 0xE0FFFF),
+    ("lightgoldenrodyellow", 0xFAFAD2),
+    ("lightgray", 0xD3D3D3),
+    ("lightgreen", 0x90EE90),
+    ("lightgrey", 0xD3D3D3),
+    ("lightpink", 0xFFB6C1),
+    ("lightsalmon", 0xFFA07A),
+    ("lightseagreen", 0x20B2AA),
+    ("lightskyblue", 0x87CEFA),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xB0C4DE),
+    ("lightyellow", 0xFFFFE0),
+    ("lime", 0x00FF00),
+    ("limegreen", 0x32CD32),
+    ("linen", 0xFAF0E6),
+    ("magenta", 0xFF00FF),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66CDAA),
+    ("mediumblue", 0x0000CD),
+    ("mediumorchid", 0xBA55D3),
+    ("mediumpurple", 0x9370DB),
+    ("mediumseagreen", 0x3CB371),
+    ("mediumslateblue", 0x7B68EE),
+    ("mediumspringgreen", 0x00FA9A),
+    ("mediumturquoise", 0x48D1CC),
+    ("mediumvioletred", 0xC71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xF5FFFA),
+    ("mistyrose", 0xFFE4E1),
+    ("moccasin", 0xFFE4B5),
+    ("navajowhite", 0xFFDEAD),
+    ("navy", 0x000080),
+    ("oldlace", 0xFDF5E6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6B8E23),
+    ("orange", 0xFFA500),
+    ("orangered", 0xFF4500),
+    ("orchid", 0xDA70D6),
+    ("palegoldenrod", 0xEEE8AA),
+    ("palegreen", 0x98FB98),
+    ("paleturquoise", 0xAFEEEE),
+    ("palevioletred", 0xDB7093),
+    ("papayawhip", 0xFFEFD5),
+    ("peachpuff", 0xFFDAB9),
+    ("peru", 0xCD853F),
+    ("pink", 0xFFC0CB),
+    ("plum", 0xDDA0DD),
+    ("powderblue", 0xB0E0E6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xFF0000),
+    ("rosybrown", 0xBC8F8F),
+    ("royalblue", 0x4169E1),
+    ("saddlebrown", 0x8B4513),
+    ("salmon", 0xFA8072),
+    ("sandybrown", 0xF4A460),
+    ("seagreen", 0x2E8B57),
+    ("seashell", 0xFFF5EE),
+    ("sienna", 0xA0522D),
+    ("silver", 0xC0C0C0),
+    ("skyblue", 0x87CEEB),
+    ("slateblue", 0x6A5ACD),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xFFFAFA),
+    ("springgreen", 0x00FF7F),
+    ("steelblue", 0x4682B4),
+    ("tan", 0xD2B48C),
+    ("teal", 0x008080),
+    ("thistle", 0xD8BFD8),
+    ("tomato", 0xFF6347),
+    ("turquoise", 0x40E0D0),
+    ("violet", 0xEE82EE),
+    ("wheat", 0xF5DEB3),
+    ("white", 0xFFFFFF),
+    ("whitesmoke", 0xF5F5F5),
+    ("yellow", 0xFFFF00),
+    ("yellowgreen", 0x9ACD32),
+];
+
+/// Names the bot knew before it accepted every CSS color. `green` and `violet`
+/// keep their old values so existing habits pick the same color as before.
+const BOT_COLORS: &[(&str, u32)] = &[
+    // Discord colors
+    ("blurple", 0x5865F2),
+    ("greyple", 0x99AAB5),
+    ("dark", 0x2C2F33),
+    ("darker", 0x23272A),
+    ("light", 0x99AAB5),
+    ("lighter", 0xECF0F1),
+    // Earlier meanings that differ from CSS
+    ("green", 0x00FF00),
+    ("violet", 0x8A2BE2),
+];
+
 impl ColorParser {
+    /// Parse hex (`#F00`, `#FF0000`, `#FF0000FF`), `rgb()`/`hsl()` notation or a
+    /// color name. Alpha is accepted but ignored, since roles can't be translucent.
     pub fn parse(input: &str) -> Result<u32, BotError> {
         let input = input.trim().to_lowercase();
 
         tracing::debug!("Attempting to parse color: {}", input);
 
+        // Functional notation explains what's wrong instead of a generic error
+        if let Some(result) = Self::parse_functional(&input) {
+            let result = result.map_err(BotError::InvalidColorValue);
+            match &result {
+                Ok(color) => {
+                    tracing::debug!("Successfully parsed color: {} -> {:#x}", input, color)
+                }
+                Err(e) => tracing::warn!("Invalid color: {}", e),
+            }
+            return result;
+        }
+
         // Try hex color first
         if let Ok(color) = Self::parse_hex(&input) {
             tracing::debug!("Successfully parsed hex color: {} -> {:#x}", input, color);
@@ -26,99 +208,119 @@ impl ColorParser {
     }
 
     fn parse_hex(input: &str) -> Result<u32, BotError> {
-        let hex = if input.starts_with('#') {
-            &input[1..]
-        } else if input.starts_with("0x") {
-            &input[2..]
-        } else if input.len() == 6 || input.len() == 3 {
+        let hex = if let Some(hex) = input.strip_prefix('#') {
+            hex
+        } else if let Some(hex) = input.strip_prefix("0x") {
+            hex
+        } else if matches!(input.len(), 3 | 6 | 8) {
             input
         } else {
             return Err(BotError::InvalidColor(input.to_string()));
         };
 
-        // Handle short hex (3 chars -> 6 chars)
-        let hex = if hex.len() == 3 {
-            format!(
-                "{0}{0}{1}{1}{2}{2}",
-                hex.chars().nth(0).unwrap(),
-                hex.chars().nth(1).unwrap(),
-                hex.chars().nth(2).unwrap()
-            )
-        } else if hex.len() == 6 {
-            hex.to_string()
-        } else {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
             return Err(BotError::InvalidColor(input.to_string()));
+        }
+
+        let hex = match hex.len() {
+            // Handle short hex (3 chars -> 6 chars)
+            3 => hex.chars().flat_map(|c| [c, c]).collect::<String>(),
+            6 => hex.to_string(),
+            // Design tools append alpha as RRGGBBAA; drop it
+            8 => hex[..6].to_string(),
+            _ => return Err(BotError::InvalidColor(input.to_string())),
         };
 
         u32::from_str_radix(&hex, 16).map_err(|_| BotError::InvalidColor(input.to_string()))
     }
 
     fn parse_named(input: &str) -> Result<u32, BotError> {
-        let colors = Self::get_color_map();
+        // "light blue" and "light-blue" mean lightblue
+        let name: String = input
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '_'))
+            .collect();
 
-        colors
-            .get(input)
+        Self::get_color_map()
+            .get(name.as_str())
             .copied()
             .ok_or_else(|| BotError::InvalidColor(input.to_string()))
     }
 
-    fn get_color_map() -> HashMap<&'static str, u32> {
-        let mut colors = HashMap::new();
-
-        // Basic colors
-        colors.insert("red", 0xFF0000);
-        colors.insert("green", 0x00FF00);
-        colors.insert("blue", 0x0000FF);
-        colors.insert("yellow", 0xFFFF00);
-        colors.insert("cyan", 0x00FFFF);
-        colors.insert("magenta", 0xFF00FF);
-        colors.insert("orange", 0xFFA500);
-        colors.insert("purple", 0x800080);
-        colors.insert("pink", 0xFFC0CB);
-        colors.insert("brown", 0xA52A2A);
-        colors.insert("black", 0x000000);
-        colors.insert("white", 0xFFFFFF);
-        colors.insert("gray", 0x808080);
-        colors.insert("grey", 0x808080);
-
-        // Discord colors
-        colors.insert("blurple", 0x5865F2);
-        colors.insert("greyple", 0x99AAB5);
-        colors.insert("dark", 0x2C2F33);
-        colors.insert("darker", 0x23272A);
-        colors.insert("light", 0x99AAB5);
-        colors.insert("lighter", 0xECF0F1);
-
-        // Extended colors
-        colors.insert("aqua", 0x00FFFF);
-        colors.insert("lime", 0x00FF00);
-        colors.insert("navy", 0x000080);
-        colors.insert("olive", 0x808000);
-        colors.insert("maroon", 0x800000);
-        colors.insert("silver", 0xC0C0C0);
-        colors.insert("teal", 0x008080);
-        colors.insert("fuchsia", 0xFF00FF);
-        colors.insert("gold", 0xFFD700);
-        colors.insert("indigo", 0x4B0082);
-        colors.insert("violet", 0x8A2BE2);
-        colors.insert("turquoise", 0x40E0D0);
-        colors.insert("coral", 0xFF7F50);
-        colors.insert("salmon", 0xFA8072);
-        colors.insert("crimson", 0xDC143C);
-        colors.insert("hotpink", 0xFF69B4);
-        colors.insert("deeppink", 0xFF1493);
-        colors.insert("lightblue", 0xADD8E6);
-        colors.insert("lightgreen", 0x90EE90);
-        colors.insert("lightyellow", 0xFFFFE0);
-        colors.insert("lightgray", 0xD3D3D3);
-        colors.insert("lightgrey", 0xD3D3D3);
-        colors.insert("darkred", 0x8B0000);
-        colors.insert("darkgreen", 0x006400);
-        colors.insert("darkblue", 0x00008B);
-        colors.insert("darkorange", 0xFF8C00);
-        colors.insert("darkviolet", 0x9400D3);
-
-        colors
+    fn get_color_map() -> &'static HashMap<&'static str, u32> {
+        static COLORS: OnceLock<HashMap<&'static str, u32>> = OnceLock::new();
+        COLORS.get_or_init(|| CSS_COLORS.iter().chain(BOT_COLORS).copied().collect())
+    }
+
+    /// `None` unless the input is `rgb(...)`, `rgba(...)`, `hsl(...)` or `hsla(...)`
+    fn parse_functional(input: &str) -> Option<Result<u32, String>> {
+        let (function, rest) = input.split_once('(')?;
+        let function = function.trim();
+        if !matches!(function, "rgb" | "rgba" | "hsl" | "hsla") {
+            return None;
+        }
+        Some(Self::parse_function(function, rest))
+    }
+
+    fn parse_function(function: &str, rest: &str) -> Result<u32, String> {
+        let args = rest
+            .trim_end()
+            .strip_suffix(')')
+            .ok_or_else(|| format!("`{}(` is missing its closing `)`", function))?;
+
+        // Both `rgb(255, 0, 0, 0.5)` and `rgb(255 0 0 / 50%)` are valid CSS
+        let (channels, alpha) = match args.split_once('/') {
+            Some((channels, alpha)) => (channels, Some(alpha.trim())),
+            None => (args, None),
+        };
+        let mut values: Vec<&str> = channels
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|value| !value.is_empty())
+            .collect();
+        let alpha = match alpha {
+            Some(alpha) => Some(alpha),
+            None if values.len() == 4 => values.pop(),
+            None => None,
+        };
+
+        if values.len() != 3 {
+            return Err(format!(
+                "`{}()` needs 3 values, got {}",
+                function,
+                values.len()
+            ));
+        }
+        if let Some(alpha) = alpha {
+            // Checked so typos are caught, then ignored
+            parse_alpha(alpha)?;
+        }
+
+        if function.starts_with("rgb") {
+            let red = rgb_channel("red", values[0])?;
+            let green = rgb_channel("green", values[1])?;
+            let blue = rgb_channel("blue", values[2])?;
+            Ok((red << 16) | (green << 8) | blue)
+        } else {
+            let hue = number("hue", values[0].trim_end_matches("deg"))?;
+            if !(0.0..=360.0).contains(&hue) {
+                return Err(format!(
+                    "hue must be between 0 and 360 degrees, got {}",
+                    values[0]
+                ));
+            }
+            let saturation = percentage("saturation", values[1])?;
+            let lightness = percentage("lightness", values[2])?;
+            Ok(hsl_to_rgb(hue, saturation, lightness))
+        }
+    }
+
+    /// The CSS color name closest to `color`, e.g. `"crimson"` for `#DC143D`
+    pub fn nearest_named(color: u32) -> &'static str {
+        CSS_COLORS
+            .iter()
+            .min_by_key(|(_, named)| color_distance(color, *named))
+            .map(|(name, _)| *name)
+            .unwrap_or("black")
     }
 
     /// Returns a formatted hex string for display (e.g., "#FF0000")
@@ -132,6 +334,90 @@ impl ColorParser {
     }
 }
 
+fn number(name: &str, value: &str) -> Result<f64, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite())
+        .ok_or_else(|| format!("{} must be a number, got `{}`", name, value))
+}
+
+/// `0`-`255`, or `0%`-`100%`
+fn rgb_channel(name: &str, value: &str) -> Result<u32, String> {
+    if let Some(percent) = value.strip_suffix('%') {
+        let percent = number(name, percent)?;
+        if !(0.0..=100.0).contains(&percent) {
+            return Err(format!(
+                "{} must be between 0% and 100%, got {}",
+                name, value
+            ));
+        }
+        return Ok((percent / 100.0 * 255.0).round() as u32);
+    }
+
+    let channel = number(name, value)?;
+    if !(0.0..=255.0).contains(&channel) {
+        return Err(format!("{} must be between 0 and 255, got {}", name, value));
+    }
+    Ok(channel.round() as u32)
+}
+
+/// `0%`-`100%` as a fraction; the `%` is optional
+fn percentage(name: &str, value: &str) -> Result<f64, String> {
+    let percent = number(name, value.strip_suffix('%').unwrap_or(value))?;
+    if !(0.0..=100.0).contains(&percent) {
+        return Err(format!(
+            "{} must be between 0% and 100%, got {}",
+            name, value
+        ));
+    }
+    Ok(percent / 100.0)
+}
+
+fn parse_alpha(value: &str) -> Result<(), String> {
+    if value.ends_with('%') {
+        return percentage("alpha", value).map(|_| ());
+    }
+    let alpha = number("alpha", value)?;
+    if !(0.0..=1.0).contains(&alpha) {
+        return Err(format!("alpha must be between 0 and 1, got {}", value));
+    }
+    Ok(())
+}
+
+fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> u32 {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = (hue % 360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let channel = |v: f64| ((v + m) * 255.0).round() as u32;
+    (channel(r) << 16) | (channel(g) << 8) | channel(b)
+}
+
+/// Weighted RGB distance ("redmean"), closer to how different colors look
+/// than plain Euclidean distance
+fn color_distance(a: u32, b: u32) -> i64 {
+    let split = |c: u32| {
+        (
+            ((c >> 16) & 0xFF) as i64,
+            ((c >> 8) & 0xFF) as i64,
+            (c & 0xFF) as i64,
+        )
+    };
+    let ((r1, g1, b1), (r2, g2, b2)) = (split(a), split(b));
+    let red_mean = (r1 + r2) / 2;
+    let (dr, dg, db) = (r1 - r2, g1 - g2, b1 - b2);
+    (((512 + red_mean) * dr * dr) >> 8) + 4 * dg * dg + (((767 - red_mean) * db * db) >> 8)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,6 +431,17 @@ mod tests {
         assert_eq!(ColorParser::parse("F00").unwrap(), 0xFF0000);
     }
 
+    #[test]
+    fn test_hex_with_alpha_ignores_alpha() {
+        assert_eq!(ColorParser::parse("#FF0000FF").unwrap(), 0xFF0000);
+        assert_eq!(ColorParser::parse("#12345600").unwrap(), 0x123456);
+        assert_eq!(ColorParser::parse("AbCdEf80").unwrap(), 0xABCDEF);
+        assert_eq!(ColorParser::parse("0x00FF0080").unwrap(), 0x00FF00);
+        assert!(ColorParser::parse("#FF0000F").is_err());
+        assert!(ColorParser::parse("#FF0000FFF").is_err());
+        assert!(ColorParser::parse("#FF00ZZFF").is_err());
+    }
+
     #[test]
     fn test_named_colors() {
         assert_eq!(ColorParser::parse("red").unwrap(), 0xFF0000);
@@ -153,6 +450,91 @@ mod tests {
         assert_eq!(ColorParser::parse("blurple").unwrap(), 0x5865F2);
     }
 
+    #[test]
+    fn test_css_named_colors() {
+        assert_eq!(ColorParser::parse("rebeccapurple").unwrap(), 0x663399);
+        assert_eq!(ColorParser::parse("papayawhip").unwrap(), 0xFFEFD5);
+        assert_eq!(ColorParser::parse("Light Slate Gray").unwrap(), 0x778899);
+        assert_eq!(ColorParser::parse("medium-sea-green").unwrap(), 0x3CB371);
+        assert_eq!(CSS_COLORS.len(), 148);
+        // Names the bot already knew keep their old colors
+        assert_eq!(ColorParser::parse("green").unwrap(), 0x00FF00);
+        assert_eq!(ColorParser::parse("violet").unwrap(), 0x8A2BE2);
+    }
+
+    #[test]
+    fn test_rgb_notation() {
+        assert_eq!(ColorParser::parse("rgb(255, 0, 0)").unwrap(), 0xFF0000);
+        assert_eq!(ColorParser::parse("RGB(18,52,86)").unwrap(), 0x123456);
+        assert_eq!(ColorParser::parse("rgb(18 52 86)").unwrap(), 0x123456);
+        assert_eq!(ColorParser::parse("rgb(100%, 0%, 50%)").unwrap(), 0xFF0080);
+        assert_eq!(
+            ColorParser::parse("rgba(0, 255, 0, 0.5)").unwrap(),
+            0x00FF00
+        );
+        assert_eq!(ColorParser::parse("rgb(0 0 255 / 50%)").unwrap(), 0x0000FF);
+        assert_eq!(
+            ColorParser::parse("  rgb( 1 , 2 , 3 )  ").unwrap(),
+            0x010203
+        );
+    }
+
+    #[test]
+    fn test_hsl_notation() {
+        assert_eq!(ColorParser::parse("hsl(120, 60%, 50%)").unwrap(), 0x33CC33);
+        assert_eq!(ColorParser::parse("hsl(0, 100%, 50%)").unwrap(), 0xFF0000);
+        assert_eq!(
+            ColorParser::parse("hsl(240deg 100% 50%)").unwrap(),
+            0x0000FF
+        );
+        assert_eq!(ColorParser::parse("hsl(360, 100%, 50%)").unwrap(), 0xFF0000);
+        assert_eq!(ColorParser::parse("hsl(0, 0%, 100%)").unwrap(), 0xFFFFFF);
+        assert_eq!(ColorParser::parse("hsl(0, 0%, 0%)").unwrap(), 0x000000);
+        assert_eq!(
+            ColorParser::parse("hsla(300, 100%, 25%, 0.3)").unwrap(),
+            0x800080
+        );
+    }
+
+    fn error_for(input: &str) -> String {
+        ColorParser::parse(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_out_of_range_components_are_described() {
+        assert!(error_for("rgb(300, 0, 0)").contains("red must be between 0 and 255, got 300"));
+        assert!(error_for("rgb(0, -1, 0)").contains("green must be between 0 and 255"));
+        assert!(error_for("rgb(0, 0, 120%)").contains("blue must be between 0% and 100%"));
+        assert!(error_for("hsl(400, 50%, 50%)").contains("hue must be between 0 and 360"));
+        assert!(error_for("hsl(120, 150%, 50%)").contains("saturation must be between"));
+        assert!(error_for("hsl(120, 50%, -5%)").contains("lightness must be between"));
+        assert!(error_for("rgba(0, 0, 0, 2)").contains("alpha must be between 0 and 1"));
+    }
+
+    #[test]
+    fn test_malformed_functional_notation_is_described() {
+        assert!(error_for("rgb(1, 2)").contains("`rgb()` needs 3 values, got 2"));
+        assert!(error_for("hsl(1, 2%, 3%, 4, 5)").contains("needs 3 values, got 5"));
+        assert!(error_for("rgb(1, 2, 3").contains("missing its closing"));
+        assert!(error_for("rgb(a, 2, 3)").contains("red must be a number, got `a`"));
+        assert!(error_for("hsl(nan, 2%, 3%)").contains("hue must be a number"));
+    }
+
+    #[test]
+    fn test_nearest_named() {
+        assert_eq!(ColorParser::nearest_named(0xDC143C), "crimson");
+        assert_eq!(ColorParser::nearest_named(0xDD153D), "crimson");
+        assert_eq!(ColorParser::nearest_named(0xFE0101), "red");
+        assert_eq!(ColorParser::nearest_named(0x010101), "black");
+        // Aliases resolve to whichever comes first alphabetically
+        assert_eq!(ColorParser::nearest_named(0x00FFFF), "aqua");
+        // Every CSS color is its own nearest match, up to aliases
+        let value_of = |name: &str| CSS_COLORS.iter().find(|(n, _)| *n == name).unwrap().1;
+        for (_, color) in CSS_COLORS {
+            assert_eq!(value_of(ColorParser::nearest_named(*color)), *color);
+        }
+    }
+
     #[test]
     fn test_invalid_colors() {
         assert!(ColorParser::parse("invalid").is_err());
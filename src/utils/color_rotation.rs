@@ -0,0 +1,190 @@
+use crate::utils::ColorParser;
+use chrono::Duration;
+use std::fmt;
+
+/// Shortest rotation interval a guild gets unless it sets its own
+pub const DEFAULT_MIN_INTERVAL_HOURS: i64 = 6;
+
+/// No guild may let roles change color more often than this
+pub const MIN_INTERVAL_FLOOR_HOURS: i64 = 1;
+
+/// Longest interval a member may choose
+pub const MAX_INTERVAL_HOURS: i64 = 168;
+
+/// Members who may rotate at once unless the guild sets its own cap
+pub const DEFAULT_MAX_ROTATING: i64 = 10;
+
+/// A guild's limits on color rotation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RotationSettings {
+    pub min_interval_hours: i64,
+    /// Members who may rotate at once; 0 turns rotation off
+    pub max_members: i64,
+}
+
+impl Default for RotationSettings {
+    fn default() -> Self {
+        Self {
+            min_interval_hours: DEFAULT_MIN_INTERVAL_HOURS,
+            max_members: DEFAULT_MAX_ROTATING,
+        }
+    }
+}
+
+impl RotationSettings {
+    /// The interval a rotation actually runs at: never faster than the
+    /// guild allows now, even if it was enabled under a lower minimum
+    pub fn effective_interval(&self, interval_secs: i64) -> Duration {
+        Duration::seconds(interval_secs.max(self.min_interval_hours * 3600))
+    }
+}
+
+/// Why a member can't turn rotation on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RotationRefusal {
+    /// The guild's cap is 0
+    TurnedOff,
+    /// As many members are rotating as the guild allows
+    Full(i64),
+    /// Faster than the guild's minimum, in hours
+    TooFrequent(i64),
+    /// Slower than anyone needs, in hours
+    TooSlow,
+}
+
+impl fmt::Display for RotationRefusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TurnedOff => write!(f, "Color rotation is turned off in this server."),
+            Self::Full(max) => write!(
+                f,
+                "This server allows {} members to rotate colors at once, and that many already are.",
+                max
+            ),
+            Self::TooFrequent(min) => write!(
+                f,
+                "Colors can change at most every {} hour{} in this server.",
+                min,
+                if *min == 1 { "" } else { "s" }
+            ),
+            Self::TooSlow => write!(
+                f,
+                "Colors must change at least every {} hours.",
+                MAX_INTERVAL_HOURS
+            ),
+        }
+    }
+}
+
+/// Check whether a member may rotate every `interval_hours`. Members already
+/// rotating don't count against the cap when they change their palette.
+pub fn check_rotation(
+    settings: RotationSettings,
+    rotating: i64,
+    already_rotating: bool,
+    interval_hours: i64,
+) -> Result<(), RotationRefusal> {
+    if settings.max_members == 0 {
+        return Err(RotationRefusal::TurnedOff);
+    }
+    if !already_rotating && rotating >= settings.max_members {
+        return Err(RotationRefusal::Full(settings.max_members));
+    }
+    if interval_hours < settings.min_interval_hours {
+        return Err(RotationRefusal::TooFrequent(settings.min_interval_hours));
+    }
+    if interval_hours > MAX_INTERVAL_HOURS {
+        return Err(RotationRefusal::TooSlow);
+    }
+    Ok(())
+}
+
+/// Palettes are stored as comma-separated hex codes
+pub fn encode_palette(colors: &[u32]) -> String {
+    colors
+        .iter()
+        .map(|&color| ColorParser::to_hex_string(color))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Colors that no longer parse are dropped rather than failing the rotation
+pub fn decode_palette(stored: &str) -> Vec<u32> {
+    stored
+        .split(',')
+        .filter_map(|hex| ColorParser::parse(hex.trim()).ok())
+        .collect()
+}
+
+/// The color to apply at `index`, and the index after it
+pub fn step(palette: &[u32], index: i64) -> Option<(u32, i64)> {
+    if palette.is_empty() {
+        return None;
+    }
+    let current = index.rem_euclid(palette.len() as i64);
+    Some((
+        palette[current as usize],
+        (current + 1) % palette.len() as i64,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cap_and_interval_are_enforced() {
+        let settings = RotationSettings::default();
+        assert_eq!(check_rotation(settings, 0, false, 6), Ok(()));
+        assert_eq!(
+            check_rotation(settings, 0, false, 2),
+            Err(RotationRefusal::TooFrequent(6))
+        );
+        assert_eq!(
+            check_rotation(settings, 10, false, 6),
+            Err(RotationRefusal::Full(10))
+        );
+        // Changing an existing palette doesn't need a free slot
+        assert_eq!(check_rotation(settings, 10, true, 12), Ok(()));
+        assert_eq!(
+            check_rotation(settings, 0, false, MAX_INTERVAL_HOURS + 1),
+            Err(RotationRefusal::TooSlow)
+        );
+
+        let off = RotationSettings {
+            max_members: 0,
+            ..settings
+        };
+        assert_eq!(
+            check_rotation(off, 0, true, 6),
+            Err(RotationRefusal::TurnedOff)
+        );
+    }
+
+    #[test]
+    fn test_raised_minimum_slows_existing_rotations() {
+        let settings = RotationSettings {
+            min_interval_hours: 12,
+            max_members: 10,
+        };
+        assert_eq!(settings.effective_interval(3600), Duration::hours(12));
+        assert_eq!(settings.effective_interval(24 * 3600), Duration::hours(24));
+    }
+
+    #[test]
+    fn test_steps_wrap_around_the_palette() {
+        let palette = [0xFF0000, 0x00FF00, 0x0000FF];
+        assert_eq!(step(&palette, 0), Some((0xFF0000, 1)));
+        assert_eq!(step(&palette, 2), Some((0x0000FF, 0)));
+        // A palette shortened by a bad stored color still steps safely
+        assert_eq!(step(&palette, 7), Some((0x00FF00, 2)));
+        assert_eq!(step(&[], 0), None);
+    }
+
+    #[test]
+    fn test_palette_round_trips() {
+        let palette = vec![0xFF5733, 0x000000, 0x00FF00];
+        assert_eq!(decode_palette(&encode_palette(&palette)), palette);
+        assert_eq!(decode_palette("#FF0000,not-a-color"), vec![0xFF0000]);
+    }
+}
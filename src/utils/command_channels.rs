@@ -0,0 +1,224 @@
+use crate::bot::{Context, Error};
+use crate::utils::CheckFailure;
+use serenity::all::{ChannelId, ChannelType};
+
+/// Groups of commands a guild can confine to certain channels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, poise::ChoiceParameter)]
+pub enum CommandGroup {
+    /// Members customizing their booster role
+    #[name = "booster customization"]
+    Customization,
+    /// Commands gated behind staff or Manage Server
+    #[name = "admin"]
+    Admin,
+    /// Help, info and other read-only commands
+    #[name = "info"]
+    Info,
+}
+
+impl CommandGroup {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommandGroup::Customization => "customization",
+            CommandGroup::Admin => "admin",
+            CommandGroup::Info => "info",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "customization" => Some(CommandGroup::Customization),
+            "admin" => Some(CommandGroup::Admin),
+            "info" => Some(CommandGroup::Info),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CommandGroup::Customization => "Booster customization",
+            CommandGroup::Admin => "Admin",
+            CommandGroup::Info => "Info",
+        }
+    }
+}
+
+/// A guild's allowed channels, as (group, channel id) pairs
+pub type CommandChannels = Vec<(CommandGroup, u64)>;
+
+/// Commands that are never restricted, so admins can't lock themselves out
+const UNRESTRICTED: &[&str] = &["settings commandchannel"];
+
+/// Which group a command belongs to. `gated` is whether it or a parent
+/// requires permissions or a check; owner-only commands are never restricted.
+pub fn group_for(qualified_name: &str, gated: bool, owners_only: bool) -> Option<CommandGroup> {
+    if owners_only
+        || UNRESTRICTED.iter().any(|name| {
+            qualified_name == *name || qualified_name.starts_with(&format!("{} ", name))
+        })
+    {
+        return None;
+    }
+
+    if gated {
+        Some(CommandGroup::Admin)
+    } else if qualified_name == "boosterrole" || qualified_name.starts_with("boosterrole ") {
+        Some(CommandGroup::Customization)
+    } else {
+        Some(CommandGroup::Info)
+    }
+}
+
+/// Whether a command may run in a channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelDecision {
+    Allow,
+    /// Not allowed here; point the member at this channel instead
+    Redirect(u64),
+}
+
+/// Resolve a command's group against the guild's allowed channels. A group
+/// with no rows may be used anywhere; a thread counts as its parent channel.
+pub fn resolve(
+    group: CommandGroup,
+    channel_id: u64,
+    thread_parent: Option<u64>,
+    rows: &[(CommandGroup, u64)],
+) -> ChannelDecision {
+    let mut allowed = rows
+        .iter()
+        .filter(|(row_group, _)| *row_group == group)
+        .map(|(_, channel)| *channel)
+        .peekable();
+
+    let Some(&first) = allowed.peek() else {
+        return ChannelDecision::Allow;
+    };
+
+    if allowed.any(|channel| channel == channel_id || Some(channel) == thread_parent) {
+        ChannelDecision::Allow
+    } else {
+        ChannelDecision::Redirect(first)
+    }
+}
+
+/// Global poise check enforcing the guild's command channels
+pub async fn channel_check(ctx: Context<'_>) -> Result<bool, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(true);
+    };
+
+    let command = ctx.command();
+    let gated = std::iter::once(command)
+        .chain(ctx.parent_commands().iter().copied())
+        .any(|c| !c.required_permissions.is_empty() || !c.checks.is_empty());
+    let owners_only = command.owners_only || ctx.parent_commands().iter().any(|c| c.owners_only);
+    let Some(group) = group_for(&command.qualified_name, gated, owners_only) else {
+        return Ok(true);
+    };
+
+    let rows = ctx.data().get_command_channels(guild_id).await?;
+    let channel_id = ctx.channel_id();
+    if resolve(group, channel_id.get(), None, &rows) == ChannelDecision::Allow {
+        return Ok(true);
+    }
+
+    // Only look the channel up when a thread's parent could still allow it
+    let thread_parent = match ctx.guild_channel().await {
+        Some(channel)
+            if matches!(
+                channel.kind,
+                ChannelType::PublicThread | ChannelType::PrivateThread | ChannelType::NewsThread
+            ) =>
+        {
+            channel.parent_id.map(|parent| parent.get())
+        }
+        _ => None,
+    };
+
+    match resolve(group, channel_id.get(), thread_parent, &rows) {
+        ChannelDecision::Allow => Ok(true),
+        ChannelDecision::Redirect(target) => Err(Error::Check(CheckFailure::WrongChannel(
+            ChannelId::new(target),
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PERKS: u64 = 10;
+    const STAFF: u64 = 20;
+    const GENERAL: u64 = 30;
+
+    #[test]
+    fn test_no_rows_allows_everywhere() {
+        assert_eq!(
+            resolve(CommandGroup::Customization, GENERAL, None, &[]),
+            ChannelDecision::Allow
+        );
+        // Rows for another group don't restrict this one
+        let rows = [(CommandGroup::Admin, STAFF)];
+        assert_eq!(
+            resolve(CommandGroup::Customization, GENERAL, None, &rows),
+            ChannelDecision::Allow
+        );
+    }
+
+    #[test]
+    fn test_restricted_group_redirects() {
+        let rows = [
+            (CommandGroup::Customization, PERKS),
+            (CommandGroup::Customization, STAFF),
+        ];
+        assert_eq!(
+            resolve(CommandGroup::Customization, PERKS, None, &rows),
+            ChannelDecision::Allow
+        );
+        assert_eq!(
+            resolve(CommandGroup::Customization, STAFF, None, &rows),
+            ChannelDecision::Allow
+        );
+        assert_eq!(
+            resolve(CommandGroup::Customization, GENERAL, None, &rows),
+            ChannelDecision::Redirect(PERKS)
+        );
+    }
+
+    #[test]
+    fn test_threads_inherit_parent() {
+        let rows = [(CommandGroup::Customization, PERKS)];
+        assert_eq!(
+            resolve(CommandGroup::Customization, 99, Some(PERKS), &rows),
+            ChannelDecision::Allow
+        );
+        assert_eq!(
+            resolve(CommandGroup::Customization, 99, Some(GENERAL), &rows),
+            ChannelDecision::Redirect(PERKS)
+        );
+    }
+
+    #[test]
+    fn test_group_for_commands() {
+        assert_eq!(
+            group_for("boosterrole color", false, false),
+            Some(CommandGroup::Customization)
+        );
+        assert_eq!(
+            group_for("boosterrole cleanup", true, false),
+            Some(CommandGroup::Admin)
+        );
+        assert_eq!(
+            group_for("settings staff add", true, false),
+            Some(CommandGroup::Admin)
+        );
+        assert_eq!(group_for("help", false, false), Some(CommandGroup::Info));
+        assert_eq!(group_for("botadmin growth", false, true), None);
+        assert_eq!(group_for("settings commandchannel add", true, false), None);
+        assert_eq!(
+            group_for("settings commandchannels", true, false),
+            Some(CommandGroup::Admin)
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use crate::bot::{Context, Error};
+use crate::utils::disabled_commands::is_within;
+use crate::utils::CheckFailure;
+use serenity::all::{GuildId, Permissions, UserId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A guild's configured cooldowns, qualified command name to seconds
+pub type CommandCooldowns = HashMap<String, u64>;
+
+/// Longest cooldown a guild can put on a command (one day)
+pub const MAX_COMMAND_COOLDOWN_SECONDS: u64 = 24 * 60 * 60;
+
+/// Tracked uses past which expired entries are swept on the next use
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// The configured entry covering a command and its cooldown. A cooldown on a
+/// group covers its subcommands; the most specific entry wins.
+pub fn cooldown_for<'a>(
+    qualified_name: &str,
+    cooldowns: &'a CommandCooldowns,
+) -> Option<(&'a str, u64)> {
+    cooldowns
+        .iter()
+        .filter(|(entry, seconds)| **seconds > 0 && is_within(qualified_name, entry))
+        .max_by_key(|(entry, _)| entry.len())
+        .map(|(entry, seconds)| (entry.as_str(), *seconds))
+}
+
+type Uses = Arc<Mutex<HashMap<(u64, u64, String), Instant>>>;
+
+/// When each member may next use each cooled-down command. Kept in memory
+/// only, so a restart forgives every cooldown.
+#[derive(Debug, Clone, Default)]
+pub struct CommandCooldownTracker {
+    ready_at: Uses,
+}
+
+impl CommandCooldownTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a use of `command` at `now`, or return how long the member
+    /// still has to wait if their last use is within `cooldown`
+    pub fn try_use(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        command: &str,
+        cooldown: Duration,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let mut ready_at = self.ready_at.lock().unwrap_or_else(|e| e.into_inner());
+        let key = (guild_id.get(), user_id.get(), command.to_string());
+
+        if let Some(&ready) = ready_at.get(&key) {
+            if ready > now {
+                return Err(ready - now);
+            }
+        }
+
+        // Uses whose cooldown has run out would never block anyone again
+        if ready_at.len() >= PRUNE_THRESHOLD {
+            ready_at.retain(|_, ready| *ready > now);
+        }
+        ready_at.insert(key, now + cooldown);
+        Ok(())
+    }
+
+    /// Uses still being tracked
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.ready_at.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+/// Whether the member is exempt from command cooldowns: bot owners, the
+/// server owner, and anyone with Manage Server
+async fn bypasses_cooldowns(ctx: Context<'_>) -> bool {
+    if crate::commands::owner::is_bot_owner(ctx) {
+        return true;
+    }
+
+    let Some(member) = ctx.author_member().await else {
+        return false;
+    };
+    // Slash commands arrive with the member's resolved permissions
+    if let Some(permissions) = member.permissions {
+        return permissions.contains(Permissions::MANAGE_GUILD);
+    }
+
+    ctx.guild()
+        .map(|guild| {
+            guild.owner_id == member.user.id
+                || guild
+                    .member_permissions(&member)
+                    .contains(Permissions::MANAGE_GUILD)
+        })
+        .unwrap_or(false)
+}
+
+/// Global poise check holding members to the guild's command cooldowns
+pub async fn cooldown_check(ctx: Context<'_>) -> Result<bool, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(true);
+    };
+
+    let Some((command, seconds)) = ctx
+        .data()
+        .command_cooldown(guild_id, &ctx.command().qualified_name)
+        .await?
+    else {
+        return Ok(true);
+    };
+
+    if bypasses_cooldowns(ctx).await {
+        return Ok(true);
+    }
+
+    match ctx.data().cooldown_tracker.try_use(
+        guild_id,
+        ctx.author().id,
+        &command,
+        Duration::from_secs(seconds),
+        Instant::now(),
+    ) {
+        Ok(()) => Ok(true),
+        Err(remaining) => Err(Error::Check(CheckFailure::Cooldown(
+            command,
+            remaining.as_secs().max(1),
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUILD: GuildId = GuildId::new(1);
+    const MEMBER: UserId = UserId::new(2);
+
+    #[test]
+    fn test_most_specific_cooldown_applies() {
+        let cooldowns: CommandCooldowns = [
+            ("boosterrole".to_string(), 10),
+            ("boosterrole dominant".to_string(), 60),
+            ("boosterrole cleanup".to_string(), 0),
+        ]
+        .into();
+
+        assert_eq!(
+            cooldown_for("boosterrole dominant", &cooldowns),
+            Some(("boosterrole dominant", 60))
+        );
+        assert_eq!(
+            cooldown_for("boosterrole color", &cooldowns),
+            Some(("boosterrole", 10))
+        );
+        // A zero cooldown defers to the group's
+        assert_eq!(
+            cooldown_for("boosterrole cleanup", &cooldowns),
+            Some(("boosterrole", 10))
+        );
+        assert_eq!(cooldown_for("boosterroles", &cooldowns), None);
+        assert_eq!(cooldown_for("help", &cooldowns), None);
+    }
+
+    #[test]
+    fn test_repeat_use_waits_out_the_cooldown() {
+        let tracker = CommandCooldownTracker::new();
+        let start = Instant::now();
+        let cooldown = Duration::from_secs(30);
+
+        assert!(tracker
+            .try_use(GUILD, MEMBER, "boosterrole dominant", cooldown, start)
+            .is_ok());
+        assert_eq!(
+            tracker.try_use(
+                GUILD,
+                MEMBER,
+                "boosterrole dominant",
+                cooldown,
+                start + Duration::from_secs(10)
+            ),
+            Err(Duration::from_secs(20))
+        );
+
+        // Other members, guilds and commands keep their own clocks
+        assert!(tracker
+            .try_use(GUILD, UserId::new(3), "boosterrole dominant", cooldown, start)
+            .is_ok());
+        assert!(tracker
+            .try_use(GuildId::new(4), MEMBER, "boosterrole dominant", cooldown, start)
+            .is_ok());
+        assert!(tracker
+            .try_use(GUILD, MEMBER, "boosterrole cleanup", cooldown, start)
+            .is_ok());
+
+        assert!(tracker
+            .try_use(GUILD, MEMBER, "boosterrole dominant", cooldown, start + cooldown)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_expired_uses_are_pruned() {
+        let tracker = CommandCooldownTracker::new();
+        let start = Instant::now();
+
+        tracker
+            .try_use(GUILD, MEMBER, "long", Duration::from_secs(600), start)
+            .unwrap();
+        for user in 0..PRUNE_THRESHOLD as u64 {
+            tracker
+                .try_use(GUILD, UserId::new(100 + user), "short", Duration::from_secs(5), start)
+                .unwrap();
+        }
+        assert_eq!(tracker.len(), PRUNE_THRESHOLD + 1);
+
+        // The next use past the threshold sweeps out every expired entry
+        let later = start + Duration::from_secs(5);
+        tracker
+            .try_use(GUILD, MEMBER, "other", Duration::from_secs(5), later)
+            .unwrap();
+        assert_eq!(tracker.len(), 2);
+        assert!(tracker
+            .try_use(GUILD, MEMBER, "long", Duration::from_secs(600), later)
+            .is_err());
+        assert!(tracker
+            .try_use(GUILD, UserId::new(100), "short", Duration::from_secs(5), later)
+            .is_ok());
+    }
+}
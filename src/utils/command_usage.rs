@@ -0,0 +1,172 @@
+use super::buffered_counter::BufferedCounter;
+use crate::data::models::CommandUsage;
+use chrono::NaiveDate;
+use serenity::all::GuildId;
+use std::fmt::Write;
+use std::ops::Deref;
+
+/// Widest bar in the usage chart, in blocks
+const BAR_WIDTH: usize = 16;
+
+/// Which guild used which command on which day
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UsageKey {
+    pub guild_id: GuildId,
+    pub command: String,
+    pub day: NaiveDate,
+}
+
+/// Buffered per-guild command counts, flushed to the database in batches so
+/// a busy guild doesn't cost a write per command
+#[derive(Debug, Clone)]
+pub struct CommandUsageCounters {
+    counts: BufferedCounter<UsageKey, u64>,
+}
+
+impl Default for CommandUsageCounters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for CommandUsageCounters {
+    type Target = BufferedCounter<UsageKey, u64>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.counts
+    }
+}
+
+impl CommandUsageCounters {
+    pub fn new() -> Self {
+        Self {
+            counts: BufferedCounter::new("command usage", |pool, batch| {
+                Box::pin(CommandUsage::add_batch(pool, batch))
+            }),
+        }
+    }
+
+    pub fn record(&self, guild_id: GuildId, command: &str, day: NaiveDate) {
+        let key = UsageKey {
+            guild_id,
+            command: command.to_string(),
+            day,
+        };
+        self.counts.add(key, 1);
+    }
+}
+
+/// Commands and their use counts as a text bar chart, busiest first as given.
+/// Bars are scaled to the busiest command; any use shows at least one block.
+pub fn render_usage_chart(rows: &[(String, i64)]) -> String {
+    let Some(max) = rows
+        .iter()
+        .map(|(_, uses)| *uses)
+        .max()
+        .filter(|max| *max > 0)
+    else {
+        return String::new();
+    };
+    let name_width = rows
+        .iter()
+        .map(|(command, _)| command.chars().count())
+        .max()
+        .unwrap_or_default();
+
+    let mut chart = String::new();
+    for (command, uses) in rows {
+        let blocks = (*uses as usize * BAR_WIDTH).div_ceil(max as usize);
+        let _ = writeln!(
+            chart,
+            "{:<name_width$}  {:<BAR_WIDTH$}  {}",
+            command,
+            "█".repeat(blocks),
+            uses,
+        );
+    }
+    chart
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    #[test]
+    fn test_counters_batch_per_guild_command_and_day() {
+        let counters = CommandUsageCounters::new();
+        let (a, b) = (GuildId::new(1), GuildId::new(2));
+
+        counters.record(a, "boosterrole color", day(1));
+        counters.record(a, "boosterrole color", day(1));
+        counters.record(a, "boosterrole color", day(2));
+        counters.record(b, "boosterrole color", day(1));
+        counters.record(a, "help", day(1));
+
+        let batch = counters.drain();
+        let summary: Vec<_> = batch
+            .iter()
+            .map(|(key, count)| (key.guild_id.get(), key.command.as_str(), key.day, *count))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, "boosterrole color", day(1), 2),
+                (1, "boosterrole color", day(2), 1),
+                (1, "help", day(1), 1),
+                (2, "boosterrole color", day(1), 1),
+            ]
+        );
+        assert!(counters.drain().is_empty());
+
+        // A failed flush puts its counts back alongside anything newer
+        counters.record(a, "help", day(1));
+        counters.restore(batch);
+        let restored = counters.drain();
+        assert_eq!(restored.len(), 4);
+        assert_eq!(restored[2].1, 2);
+    }
+
+    #[tokio::test]
+    async fn test_flush_writes_once_and_empties_the_buffer() {
+        let pool = crate::data::database::init_memory_database().await.unwrap();
+        let counters = CommandUsageCounters::new();
+        let guild = GuildId::new(1);
+
+        counters.record(guild, "help", day(1));
+        counters.record(guild, "help", day(1));
+        counters.flush(&pool).await.unwrap();
+        assert!(counters.drain().is_empty());
+
+        // Nothing pending is a no-op, and later flushes add to the same row
+        counters.flush(&pool).await.unwrap();
+        counters.record(guild, "help", day(1));
+        counters.flush(&pool).await.unwrap();
+
+        let top = CommandUsage::top_for_guild(&pool, guild, day(1), 10)
+            .await
+            .unwrap();
+        assert_eq!(top, vec![("help".to_string(), 3)]);
+    }
+
+    #[test]
+    fn test_chart_scales_bars_to_the_busiest_command() {
+        let chart = render_usage_chart(&[
+            ("boosterrole color".to_string(), 40),
+            ("help".to_string(), 10),
+            ("ping".to_string(), 1),
+        ]);
+        let lines: Vec<_> = chart.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("boosterrole color  ████████████████  40"));
+        assert!(lines[1].starts_with("help               ████ "));
+        assert!(lines[2].starts_with("ping               █ "));
+        assert!(lines[2].ends_with(" 1"));
+
+        assert_eq!(render_usage_chart(&[]), "");
+    }
+}
@@ -0,0 +1,204 @@
+use serde_json::Value;
+
+/// One difference between two configuration snapshots
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigChange {
+    Added {
+        path: String,
+        value: Value,
+    },
+    Removed {
+        path: String,
+        value: Value,
+    },
+    Changed {
+        path: String,
+        old: Value,
+        new: Value,
+    },
+}
+
+/// Structural diff of two JSON documents.
+///
+/// Objects are compared key by key and recursed into; any other pair of
+/// differing values, including arrays and type changes, is one `Changed`.
+pub fn diff_json(old: &Value, new: &Value) -> Vec<ConfigChange> {
+    let mut changes = Vec::new();
+    diff_at("", old, new, &mut changes);
+    changes
+}
+
+fn diff_at(path: &str, old: &Value, new: &Value, changes: &mut Vec<ConfigChange>) {
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            for (key, old_value) in old_map {
+                let child = join_path(path, key);
+                match new_map.get(key) {
+                    Some(new_value) => diff_at(&child, old_value, new_value, changes),
+                    None => changes.push(ConfigChange::Removed {
+                        path: child,
+                        value: old_value.clone(),
+                    }),
+                }
+            }
+            for (key, new_value) in new_map {
+                if !old_map.contains_key(key) {
+                    changes.push(ConfigChange::Added {
+                        path: join_path(path, key),
+                        value: new_value.clone(),
+                    });
+                }
+            }
+        }
+        _ if old != new => changes.push(ConfigChange::Changed {
+            path: path.to_string(),
+            old: old.clone(),
+            new: new.clone(),
+        }),
+        _ => {}
+    }
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Null => "unset".to_string(),
+        Value::String(s) => format!("\"{}\"", s),
+        other => other.to_string(),
+    }
+}
+
+/// Human-readable change list that fits in `max_len` characters
+pub fn render_changes(changes: &[ConfigChange], max_len: usize) -> String {
+    if changes.is_empty() {
+        return "No differences".to_string();
+    }
+
+    let mut output = String::new();
+    for (i, change) in changes.iter().enumerate() {
+        let line = match change {
+            ConfigChange::Added { path, value } => {
+                format!("➕ `{}`: {}\n", path, display_value(value))
+            }
+            ConfigChange::Removed { path, value } => {
+                format!("➖ `{}`: {}\n", path, display_value(value))
+            }
+            ConfigChange::Changed { path, old, new } => format!(
+                "✏️ `{}`: {} → {}\n",
+                path,
+                display_value(old),
+                display_value(new)
+            ),
+        };
+
+        let remaining = changes.len() - i;
+        let more = format!("…and {} more", remaining);
+        if output.chars().count() + line.chars().count() + more.chars().count() > max_len {
+            output.push_str(&more);
+            return output;
+        }
+        output.push_str(&line);
+    }
+
+    output.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_identical_documents_have_no_changes() {
+        let doc = json!({ "prefix": "!", "spotlight": { "enabled": true } });
+        assert!(diff_json(&doc, &doc).is_empty());
+        assert_eq!(render_changes(&[], 100), "No differences");
+    }
+
+    #[test]
+    fn test_nested_additions_and_removals() {
+        let old = json!({
+            "prefix": "!",
+            "sharing_limits": { "max_members_per_role": 5 }
+        });
+        let new = json!({
+            "prefix": "!",
+            "sharing_limits": { "max_shared_roles_per_member": 3 },
+            "support_channel": "123"
+        });
+
+        assert_eq!(
+            diff_json(&old, &new),
+            vec![
+                ConfigChange::Removed {
+                    path: "sharing_limits.max_members_per_role".to_string(),
+                    value: json!(5),
+                },
+                ConfigChange::Added {
+                    path: "sharing_limits.max_shared_roles_per_member".to_string(),
+                    value: json!(3),
+                },
+                ConfigChange::Added {
+                    path: "support_channel".to_string(),
+                    value: json!("123"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_changes_and_arrays_are_whole_values() {
+        let old = json!({
+            "spotlight": { "channel_id": "1", "enabled": true },
+            "staff_roles": ["1", "2"]
+        });
+        let new = json!({
+            "spotlight": null,
+            "staff_roles": ["1", "3"]
+        });
+
+        let changes = diff_json(&old, &new);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(
+            changes[0],
+            ConfigChange::Changed {
+                path: "spotlight".to_string(),
+                old: json!({ "channel_id": "1", "enabled": true }),
+                new: Value::Null,
+            }
+        );
+        assert!(matches!(
+            &changes[1],
+            ConfigChange::Changed { path, .. } if path == "staff_roles"
+        ));
+    }
+
+    #[test]
+    fn test_render_is_readable_and_bounded() {
+        let changes = diff_json(
+            &json!({ "prefix": "!", "booster_limit": null }),
+            &json!({ "prefix": "?", "booster_limit": 10 }),
+        );
+        assert_eq!(
+            render_changes(&changes, 4096),
+            "✏️ `booster_limit`: unset → 10\n✏️ `prefix`: \"!\" → \"?\""
+        );
+
+        let many: Vec<ConfigChange> = (0..500)
+            .map(|i| ConfigChange::Added {
+                path: format!("blacklist.word_{}", i),
+                value: json!(true),
+            })
+            .collect();
+        let rendered = render_changes(&many, 4096);
+        assert!(rendered.chars().count() <= 4096);
+        assert!(rendered.ends_with("more"));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::bot::{Context, Error};
+use poise::serenity_prelude::{
+    ButtonStyle, ComponentInteractionCollector, CreateActionRow, CreateButton, CreateEmbed,
+    CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use poise::{CreateReply, ReplyHandle};
+use std::time::Duration;
+
+/// How long a confirmation prompt waits before the action is aborted
+pub const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How the invoking user answered a confirmation prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Confirmation {
+    Confirmed,
+    Cancelled,
+    TimedOut,
+}
+
+impl Confirmation {
+    /// The answer a button's custom ID stands for, if it belongs to `prefix`
+    pub fn from_custom_id(prefix: &str, custom_id: &str) -> Option<Self> {
+        match custom_id.strip_prefix(prefix)?.strip_prefix(':')? {
+            "confirm" => Some(Confirmation::Confirmed),
+            "cancel" => Some(Confirmation::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+/// Confirm/Cancel buttons for the prompt identified by `prefix`
+pub fn confirm_buttons(prefix: &str, confirm_label: &str) -> CreateActionRow {
+    CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("{}:confirm", prefix))
+            .label(confirm_label)
+            .style(ButtonStyle::Danger),
+        CreateButton::new(format!("{}:cancel", prefix))
+            .label("Cancel")
+            .style(ButtonStyle::Secondary),
+    ])
+}
+
+/// Show `embed` with Confirm/Cancel buttons and wait for the invoking user's
+/// answer. Returns the prompt so the caller can replace it with the outcome
+/// via [`finish_prompt`].
+pub async fn ask_confirmation<'a>(
+    ctx: Context<'a>,
+    embed: CreateEmbed,
+    confirm_label: &str,
+) -> Result<(Confirmation, ReplyHandle<'a>), Error> {
+    let prefix = format!("confirm:{}", ctx.id());
+    let reply = ctx
+        .send(
+            CreateReply::default()
+                .embed(embed)
+                .components(vec![confirm_buttons(&prefix, confirm_label)]),
+        )
+        .await?;
+    let message_id = reply.message().await?.id;
+
+    loop {
+        let filter_prefix = prefix.clone();
+        let interaction = ComponentInteractionCollector::new(ctx)
+            .message_id(message_id)
+            .filter(move |i| {
+                Confirmation::from_custom_id(&filter_prefix, &i.data.custom_id).is_some()
+            })
+            .timeout(CONFIRM_TIMEOUT)
+            .await;
+
+        let Some(interaction) = interaction else {
+            return Ok((Confirmation::TimedOut, reply));
+        };
+
+        if interaction.user.id != ctx.author().id {
+            interaction
+                .create_response(
+                    ctx,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content("Only the person who ran this command can answer it.")
+                            .ephemeral(true),
+                    ),
+                )
+                .await?;
+            continue;
+        }
+
+        // Acknowledge now; the caller may take a while before finishing
+        interaction
+            .create_response(ctx, CreateInteractionResponse::Acknowledge)
+            .await?;
+
+        let answer = Confirmation::from_custom_id(&prefix, &interaction.data.custom_id)
+            .unwrap_or(Confirmation::Cancelled);
+        return Ok((answer, reply));
+    }
+}
+
+/// Replace a confirmation prompt with `embed`, removing its buttons
+pub async fn finish_prompt(
+    ctx: Context<'_>,
+    reply: ReplyHandle<'_>,
+    embed: CreateEmbed,
+) -> Result<(), Error> {
+    reply
+        .edit(ctx, CreateReply::default().embed(embed).components(vec![]))
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_ids_belong_to_one_prompt() {
+        assert_eq!(
+            Confirmation::from_custom_id("confirm:7", "confirm:7:confirm"),
+            Some(Confirmation::Confirmed)
+        );
+        assert_eq!(
+            Confirmation::from_custom_id("confirm:7", "confirm:7:cancel"),
+            Some(Confirmation::Cancelled)
+        );
+        assert_eq!(
+            Confirmation::from_custom_id("confirm:7", "confirm:8:confirm"),
+            None
+        );
+        assert_eq!(
+            Confirmation::from_custom_id("confirm:7", "confirm:77:confirm"),
+            None
+        );
+        assert_eq!(
+            Confirmation::from_custom_id("confirm:7", "page:7:next"),
+            None
+        );
+    }
+}
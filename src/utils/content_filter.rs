@@ -1,13 +1,26 @@
-use std::collections::HashSet;
+use super::name_severity::{strongest_match, BlacklistEntry, MatchType, Severity};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+/// Blacklist entries a role name is checked against: the bot-wide list
+/// and the guild's own
+#[derive(Debug, Clone, Default)]
+pub struct NameFilters {
+    pub global: Vec<BlacklistEntry>,
+    pub guild: Vec<BlacklistEntry>,
+}
+
 /// Content filter for checking role names against blacklisted words
 /// Provides caching and efficient string matching
+#[derive(Debug)]
 #[allow(dead_code)]
 pub struct ContentFilter {
-    /// Cached blacklist words for quick lookup
-    cached_words: Arc<RwLock<HashSet<String>>>,
+    /// Cached blacklist entries keyed by their stored word or pattern
+    cached_words: Arc<RwLock<HashMap<String, BlacklistEntry>>>,
+    /// Whether the cache has been filled; an empty blacklist is still loaded
+    loaded: AtomicBool,
     /// Guild ID this filter is for
     guild_id: serenity::all::GuildId,
     /// Database pool for fetching fresh blacklist data
@@ -19,7 +32,8 @@ impl ContentFilter {
     /// Create a new content filter for a guild
     pub fn new(guild_id: serenity::all::GuildId, db_pool: sqlx::SqlitePool) -> Self {
         Self {
-            cached_words: Arc::new(RwLock::new(HashSet::new())),
+            cached_words: Arc::new(RwLock::new(HashMap::new())),
+            loaded: AtomicBool::new(false),
             guild_id,
             db_pool,
         }
@@ -32,16 +46,17 @@ impl ContentFilter {
             "Refreshing content filter cache"
         );
 
-        let words =
-            crate::data::models::RoleNameBlacklist::get_all_for_guild(&self.db_pool, self.guild_id)
+        let entries =
+            crate::data::models::RoleNameBlacklist::get_entries(&self.db_pool, self.guild_id)
                 .await?;
 
         let mut cache = self.cached_words.write().await;
         cache.clear();
 
-        for word in words {
-            cache.insert(word.to_lowercase());
+        for entry in entries {
+            cache.insert(entry.word.clone(), entry);
         }
+        self.loaded.store(true, Ordering::Release);
 
         tracing::debug!(
             guild_id = %self.guild_id,
@@ -52,24 +67,32 @@ impl ContentFilter {
         Ok(())
     }
 
+    /// Load the blacklist on first use
+    async fn ensure_loaded(&self) -> Result<(), sqlx::Error> {
+        if !self.loaded.load(Ordering::Acquire) {
+            self.refresh_cache().await?;
+        }
+        Ok(())
+    }
+
+    /// Every cached blacklist entry, loading them on first use
+    pub async fn entries(&self) -> Result<Vec<BlacklistEntry>, sqlx::Error> {
+        self.ensure_loaded().await?;
+
+        let cache = self.cached_words.read().await;
+        Ok(cache.values().cloned().collect())
+    }
+
     /// Check if a text contains any blacklisted words
     /// This method uses the cache for fast lookups
     pub async fn contains_blacklisted_content(&self, text: &str) -> Result<bool, sqlx::Error> {
-        // Ensure cache is populated
-        {
-            let cache = self.cached_words.read().await;
-            if cache.is_empty() {
-                drop(cache); // Release read lock before acquiring write lock
-                self.refresh_cache().await?;
-            }
-        }
+        self.ensure_loaded().await?;
 
-        let text_lower = text.to_lowercase();
         let cache = self.cached_words.read().await;
 
         // Check for any blacklisted words in the text
-        for word in cache.iter() {
-            if text_lower.contains(word) {
+        for (word, entry) in cache.iter() {
+            if entry.matches(text) {
                 tracing::debug!(
                     guild_id = %self.guild_id,
                     text = %text,
@@ -85,32 +108,42 @@ impl ContentFilter {
 
     /// Check if a specific word is in the blacklist
     pub async fn is_word_blacklisted(&self, word: &str) -> Result<bool, sqlx::Error> {
-        // Ensure cache is populated
-        {
-            let cache = self.cached_words.read().await;
-            if cache.is_empty() {
-                drop(cache); // Release read lock before acquiring write lock
-                self.refresh_cache().await?;
-            }
-        }
+        self.ensure_loaded().await?;
 
-        let word_lower = word.to_lowercase();
         let cache = self.cached_words.read().await;
 
-        Ok(cache.contains(&word_lower))
+        Ok(cache.contains_key(word) || cache.contains_key(&word.to_lowercase()))
+    }
+
+    /// The most severe blacklisted word in the text, from the cache
+    pub async fn strongest_match(
+        &self,
+        text: &str,
+    ) -> Result<Option<(String, Severity)>, sqlx::Error> {
+        self.ensure_loaded().await?;
+
+        let entries = self.entries().await?;
+
+        Ok(strongest_match(text, &entries).map(|(word, severity)| (word.to_string(), severity)))
     }
 
     /// Get all cached blacklisted words
     pub async fn get_cached_words(&self) -> Vec<String> {
         let cache = self.cached_words.read().await;
-        cache.iter().cloned().collect()
+        cache.keys().cloned().collect()
     }
 
     /// Add a word to the cache (should be called after database update)
-    pub async fn add_word_to_cache(&self, word: &str) {
-        let word_lower = word.to_lowercase();
+    pub async fn add_word_to_cache(&self, word: &str, severity: Severity, match_type: MatchType) {
+        let stored = match match_type {
+            MatchType::Regex => word.to_string(),
+            _ => word.to_lowercase(),
+        };
         let mut cache = self.cached_words.write().await;
-        cache.insert(word_lower);
+        cache.insert(
+            stored.clone(),
+            BlacklistEntry::new(stored, severity, match_type),
+        );
 
         tracing::debug!(
             guild_id = %self.guild_id,
@@ -121,9 +154,10 @@ impl ContentFilter {
 
     /// Remove a word from the cache (should be called after database update)
     pub async fn remove_word_from_cache(&self, word: &str) {
-        let word_lower = word.to_lowercase();
         let mut cache = self.cached_words.write().await;
-        cache.remove(&word_lower);
+        if cache.remove(word).is_none() {
+            cache.remove(&word.to_lowercase());
+        }
 
         tracing::debug!(
             guild_id = %self.guild_id,
@@ -132,10 +166,11 @@ impl ContentFilter {
         );
     }
 
-    /// Clear the entire cache
+    /// Clear the entire cache; the next check reloads it
     pub async fn clear_cache(&self) {
         let mut cache = self.cached_words.write().await;
         cache.clear();
+        self.loaded.store(false, Ordering::Release);
 
         tracing::debug!(
             guild_id = %self.guild_id,
@@ -153,6 +188,45 @@ impl ContentFilter {
     }
 }
 
+/// The bot owners' blacklist, cached once and checked in every guild
+#[derive(Debug)]
+pub struct GlobalContentFilter {
+    /// `None` until first use, so an empty list still counts as loaded
+    cached: RwLock<Option<Vec<BlacklistEntry>>>,
+    db_pool: sqlx::SqlitePool,
+}
+
+impl GlobalContentFilter {
+    pub fn new(db_pool: sqlx::SqlitePool) -> Self {
+        Self {
+            cached: RwLock::new(None),
+            db_pool,
+        }
+    }
+
+    /// Reload the global list, e.g. after an owner changes it
+    pub async fn refresh_cache(&self) -> Result<(), sqlx::Error> {
+        let entries =
+            crate::data::models::GlobalRoleNameBlacklist::get_entries(&self.db_pool).await?;
+
+        tracing::debug!(
+            word_count = entries.len(),
+            "Global content filter cache refreshed"
+        );
+        *self.cached.write().await = Some(entries);
+        Ok(())
+    }
+
+    /// Every global entry, loading them on first use
+    pub async fn entries(&self) -> Result<Vec<BlacklistEntry>, sqlx::Error> {
+        if let Some(entries) = self.cached.read().await.as_ref() {
+            return Ok(entries.clone());
+        }
+        self.refresh_cache().await?;
+        Ok(self.cached.read().await.clone().unwrap_or_default())
+    }
+}
+
 /// Statistics about a content filter
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -164,35 +238,91 @@ pub struct ContentFilterStats {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serenity::all::GuildId;
+    use crate::data::database::init_memory_database;
+    use crate::data::models::RoleNameBlacklist;
+    use crate::utils::name_severity::NameCheck;
+    use serenity::all::{GuildId, UserId};
 
-    // These tests would require a test database setup
-    // For now, they serve as documentation of expected behavior
+    const GUILD: GuildId = GuildId::new(300);
+    const ADMIN: UserId = UserId::new(1);
+    const MEMBER: UserId = UserId::new(2);
 
     #[tokio::test]
-    async fn test_content_filter_basic_functionality() {
-        // This test would need a proper test database
-        // let db_pool = setup_test_db().await;
-        // let guild_id = GuildId::new(12345);
-        // let filter = ContentFilter::new(guild_id, db_pool);
-        //
-        // // Test that empty cache doesn't match anything
-        // assert!(!filter.contains_blacklisted_content("hello world").await.unwrap());
-        //
-        // // Add word to blacklist and test detection
-        // filter.add_word_to_cache("badword").await;
-        // assert!(filter.contains_blacklisted_content("this contains badword").await.unwrap());
-        // assert!(!filter.contains_blacklisted_content("this is clean").await.unwrap());
+    async fn test_added_word_is_rejected_on_next_check() {
+        let pool = init_memory_database().await.unwrap();
+        let filter = ContentFilter::new(GUILD, pool.clone());
+
+        // Loading an empty blacklist still counts as loaded
+        assert!(filter.entries().await.unwrap().is_empty());
+        assert!(!filter
+            .contains_blacklisted_content("Crud Club")
+            .await
+            .unwrap());
+
+        // What `filter add` does: store the word, then update the cache
+        RoleNameBlacklist::add_word(
+            &pool,
+            GUILD,
+            "Crud",
+            Severity::Medium,
+            MatchType::Substring,
+            ADMIN,
+        )
+        .await
+        .unwrap();
+        filter
+            .add_word_to_cache("Crud", Severity::Medium, MatchType::Substring)
+            .await;
+
+        let entries = filter.entries().await.unwrap();
+        let check = RoleNameBlacklist::check_entries(
+            &pool,
+            GUILD,
+            MEMBER,
+            "Crud Club",
+            "boosterrole create",
+            &[],
+            &entries,
+        )
+        .await
+        .unwrap();
+        assert!(matches!(check, NameCheck::Reject { .. }));
+
+        // What `filter remove` does
+        RoleNameBlacklist::remove_word(&pool, GUILD, "crud")
+            .await
+            .unwrap();
+        filter.remove_word_from_cache("crud").await;
+        assert!(!filter
+            .contains_blacklisted_content("Crud Club")
+            .await
+            .unwrap());
     }
 
     #[tokio::test]
-    async fn test_case_insensitive_matching() {
-        // Test that blacklist matching is case-insensitive
-        // let filter = setup_test_filter().await;
-        // filter.add_word_to_cache("BadWord").await;
-        //
-        // assert!(filter.contains_blacklisted_content("BADWORD").await.unwrap());
-        // assert!(filter.contains_blacklisted_content("badword").await.unwrap());
-        // assert!(filter.contains_blacklisted_content("BadWord").await.unwrap());
+    async fn test_reload_picks_up_database_changes() {
+        let pool = init_memory_database().await.unwrap();
+        let filter = ContentFilter::new(GUILD, pool.clone());
+        assert!(filter.entries().await.unwrap().is_empty());
+
+        RoleNameBlacklist::add_word(
+            &pool,
+            GUILD,
+            "f*ck",
+            Severity::High,
+            MatchType::Wildcard,
+            ADMIN,
+        )
+        .await
+        .unwrap();
+
+        // Changed behind the cache's back, so only a reload sees it
+        assert!(!filter.contains_blacklisted_content("Fuck").await.unwrap());
+        filter.refresh_cache().await.unwrap();
+        assert_eq!(
+            filter.strongest_match("what the fck").await.unwrap(),
+            Some(("f*ck".to_string(), Severity::High))
+        );
+        assert_eq!(filter.get_cache_stats().await.word_count, 1);
     }
 }
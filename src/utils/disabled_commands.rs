@@ -0,0 +1,117 @@
+use crate::bot::{Context, Data, Error};
+use crate::utils::CheckFailure;
+use std::collections::HashSet;
+
+/// Qualified names of the commands a guild has disabled
+pub type DisabledCommands = HashSet<String>;
+
+/// Commands that can never be disabled, so admins can't lock themselves out
+const PROTECTED: &[&str] = &["settings", "help"];
+
+/// Whether `qualified_name` is `ancestor` itself or one of its subcommands
+pub fn is_within(qualified_name: &str, ancestor: &str) -> bool {
+    qualified_name == ancestor
+        || qualified_name
+            .strip_prefix(ancestor)
+            .is_some_and(|rest| rest.starts_with(' '))
+}
+
+/// Whether a command is exempt from being disabled
+pub fn is_protected(qualified_name: &str) -> bool {
+    PROTECTED
+        .iter()
+        .any(|protected| is_within(qualified_name, protected))
+}
+
+/// The disabled entry that covers a command: the command itself or a parent,
+/// since disabling a group disables every subcommand in it
+pub fn disabled_by<'a>(qualified_name: &str, disabled: &'a DisabledCommands) -> Option<&'a str> {
+    disabled
+        .iter()
+        .find(|entry| is_within(qualified_name, entry))
+        .map(String::as_str)
+}
+
+/// Tidy what an admin typed into a qualified command name
+pub fn normalize(input: &str) -> String {
+    input
+        .trim()
+        .trim_start_matches('/')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Qualified name of every registered command and subcommand
+pub fn registered_names(commands: &[poise::Command<Data, Error>]) -> Vec<String> {
+    fn walk(commands: &[poise::Command<Data, Error>], parent: &str, names: &mut Vec<String>) {
+        for command in commands {
+            let name = if parent.is_empty() {
+                command.name.clone()
+            } else {
+                format!("{} {}", parent, command.name)
+            };
+            walk(&command.subcommands, &name, names);
+            names.push(name);
+        }
+    }
+
+    let mut names = Vec::new();
+    walk(commands, "", &mut names);
+    names.sort();
+    names
+}
+
+/// Global poise check rejecting commands the guild has disabled
+pub async fn disabled_check(ctx: Context<'_>) -> Result<bool, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(true);
+    };
+
+    let qualified_name = &ctx.command().qualified_name;
+    if is_protected(qualified_name) {
+        return Ok(true);
+    }
+
+    match ctx
+        .data()
+        .disabled_command(guild_id, qualified_name)
+        .await?
+    {
+        Some(entry) => Err(Error::Check(CheckFailure::Disabled(entry))),
+        None => Ok(true),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabling_a_group_covers_subcommands() {
+        let disabled: DisabledCommands = ["boosterrole share".to_string()].into();
+
+        assert_eq!(
+            disabled_by("boosterrole share", &disabled),
+            Some("boosterrole share")
+        );
+        assert_eq!(
+            disabled_by("boosterrole share role", &disabled),
+            Some("boosterrole share")
+        );
+        assert_eq!(disabled_by("boosterrole", &disabled), None);
+        assert_eq!(disabled_by("boosterrole shareholder", &disabled), None);
+    }
+
+    #[test]
+    fn test_settings_and_help_are_protected() {
+        assert!(is_protected("settings"));
+        assert!(is_protected("settings commands enable"));
+        assert!(is_protected("help"));
+        assert!(!is_protected("helper"));
+        assert!(!is_protected("boosterrole share"));
+
+        assert_eq!(normalize("  /BoosterRole   Share "), "boosterrole share");
+    }
+}
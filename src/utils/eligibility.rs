@@ -0,0 +1,379 @@
+use chrono::{DateTime, Duration, Utc};
+use serenity::all::{GuildId, RoleId, Timestamp, UserId};
+use sqlx::SqlitePool;
+
+/// Who may own a custom booster role in a guild
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, poise::ChoiceParameter)]
+pub enum EligibilityMode {
+    #[default]
+    #[name = "boosters_only"]
+    BoostersOnly,
+    #[name = "premium_role_holders"]
+    PremiumRoleHolders,
+    #[name = "either"]
+    Either,
+}
+
+impl EligibilityMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::BoostersOnly => "boosters_only",
+            Self::PremiumRoleHolders => "premium_role_holders",
+            Self::Either => "either",
+        }
+    }
+
+    /// Parse a stored mode, falling back to boosters only for unknown values
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "premium_role_holders" => Self::PremiumRoleHolders,
+            "either" => Self::Either,
+            _ => Self::BoostersOnly,
+        }
+    }
+
+    pub fn needs_premium_role(self) -> bool {
+        !matches!(self, Self::BoostersOnly)
+    }
+}
+
+/// A guild's eligibility mode together with its configured premium role
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EligibilityConfig {
+    pub mode: EligibilityMode,
+    pub premium_role_id: Option<RoleId>,
+}
+
+impl EligibilityConfig {
+    /// The mode actually applied; premium modes without a premium role fall
+    /// back to boosters only so the server isn't locked out entirely
+    pub fn effective_mode(&self) -> EligibilityMode {
+        match (self.mode, self.premium_role_id) {
+            (mode, Some(_)) => mode,
+            (_, None) => EligibilityMode::BoostersOnly,
+        }
+    }
+
+    /// Explain what grants eligibility in this guild
+    pub fn requirement_text(&self) -> String {
+        match (self.effective_mode(), self.premium_role_id) {
+            (EligibilityMode::PremiumRoleHolders, Some(role)) => {
+                format!("You need the <@&{}> role to use this command.", role)
+            }
+            (EligibilityMode::Either, Some(role)) => format!(
+                "You need to boost this server or hold the <@&{}> role to use this command.",
+                role
+            ),
+            _ => "This command is only available to server boosters! Please boost this server to use this feature.".to_string(),
+        }
+    }
+}
+
+/// Whether a member may own a custom booster role under `config`
+pub fn is_eligible(
+    premium_since: Option<Timestamp>,
+    member_roles: &[RoleId],
+    config: &EligibilityConfig,
+) -> bool {
+    let boosting = premium_since.is_some();
+    let has_premium_role = config
+        .premium_role_id
+        .is_some_and(|role| member_roles.contains(&role));
+
+    match config.effective_mode() {
+        EligibilityMode::BoostersOnly => boosting,
+        EligibilityMode::PremiumRoleHolders => has_premium_role,
+        EligibilityMode::Either => boosting || has_premium_role,
+    }
+}
+
+/// Exemption kind that skips the account age and membership thresholds
+pub const ACCOUNT_AGE_EXEMPTION: &str = "account_age";
+
+/// How long an account must exist, and a member must have been in the
+/// guild, before booster customization unlocks. Zero disables a threshold.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AgeRequirement {
+    pub min_account_age_days: u32,
+    pub min_membership_days: u32,
+}
+
+impl AgeRequirement {
+    pub fn is_enabled(&self) -> bool {
+        self.min_account_age_days > 0 || self.min_membership_days > 0
+    }
+
+    /// `Account 7 days • Membership 3 days`, or `None` when both are off
+    pub fn describe(&self) -> String {
+        if !self.is_enabled() {
+            return "None".to_string();
+        }
+        format!(
+            "Account {} • Membership {}",
+            describe_days(self.min_account_age_days),
+            describe_days(self.min_membership_days)
+        )
+    }
+}
+
+fn describe_days(days: u32) -> String {
+    match days {
+        0 => "off".to_string(),
+        1 => "1 day".to_string(),
+        n => format!("{} days", n),
+    }
+}
+
+/// Outcome of checking a member against an [`AgeRequirement`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgeCheck {
+    Met,
+    /// Eligible from this moment, once every threshold has passed
+    NotUntil(DateTime<Utc>),
+    /// A membership threshold is set but the join date isn't known
+    JoinDateUnknown,
+}
+
+/// Check account creation and join dates against the guild's thresholds
+pub fn check_age(
+    requirement: &AgeRequirement,
+    account_created: DateTime<Utc>,
+    joined_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> AgeCheck {
+    let mut eligible_at = None;
+
+    if requirement.min_account_age_days > 0 {
+        eligible_at =
+            Some(account_created + Duration::days(requirement.min_account_age_days as i64));
+    }
+
+    if requirement.min_membership_days > 0 {
+        let Some(joined_at) = joined_at else {
+            return AgeCheck::JoinDateUnknown;
+        };
+        let membership_ready = joined_at + Duration::days(requirement.min_membership_days as i64);
+        eligible_at = Some(eligible_at.map_or(membership_ready, |at| at.max(membership_ready)));
+    }
+
+    match eligible_at {
+        Some(at) if at > now => AgeCheck::NotUntil(at),
+        _ => AgeCheck::Met,
+    }
+}
+
+/// Convert a Discord timestamp for [`check_age`]
+pub fn to_utc(timestamp: Timestamp) -> DateTime<Utc> {
+    DateTime::from_timestamp(timestamp.unix_timestamp(), 0).unwrap_or_default()
+}
+
+/// Load a guild's account age thresholds and whether `user_id` is exempt
+pub async fn load_age_requirement(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<Option<AgeRequirement>, sqlx::Error> {
+    let requirement = crate::data::models::GuildAccountAgeSetting::get(pool, guild_id).await?;
+    if !requirement.is_enabled() {
+        return Ok(None);
+    }
+
+    let exempt = crate::data::models::GuildExemption::contains(
+        pool,
+        guild_id,
+        user_id,
+        ACCOUNT_AGE_EXEMPTION,
+    )
+    .await?;
+
+    Ok((!exempt).then_some(requirement))
+}
+
+/// Load a guild's eligibility mode and premium role
+pub async fn load_eligibility_config(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+) -> Result<EligibilityConfig, sqlx::Error> {
+    let mode = crate::data::models::GuildEligibilitySetting::get_mode(pool, guild_id).await?;
+    let premium_role_id = crate::data::models::GuildPremiumRole::get(pool, guild_id)
+        .await?
+        .map(|r| RoleId::new(r.role_id as u64));
+
+    Ok(EligibilityConfig {
+        mode,
+        premium_role_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PREMIUM: RoleId = RoleId::new(42);
+    const OTHER: RoleId = RoleId::new(7);
+
+    fn config(mode: EligibilityMode, premium_role_id: Option<RoleId>) -> EligibilityConfig {
+        EligibilityConfig {
+            mode,
+            premium_role_id,
+        }
+    }
+
+    fn boosting() -> Option<Timestamp> {
+        Some(Timestamp::now())
+    }
+
+    #[test]
+    fn test_mode_round_trip() {
+        for mode in [
+            EligibilityMode::BoostersOnly,
+            EligibilityMode::PremiumRoleHolders,
+            EligibilityMode::Either,
+        ] {
+            assert_eq!(EligibilityMode::parse(mode.as_str()), mode);
+        }
+        assert_eq!(
+            EligibilityMode::parse("garbage"),
+            EligibilityMode::BoostersOnly
+        );
+    }
+
+    #[test]
+    fn test_boosters_only() {
+        let cfg = config(EligibilityMode::BoostersOnly, Some(PREMIUM));
+        assert!(is_eligible(boosting(), &[], &cfg));
+        assert!(is_eligible(boosting(), &[PREMIUM], &cfg));
+        assert!(!is_eligible(None, &[PREMIUM], &cfg));
+        assert!(!is_eligible(None, &[], &cfg));
+    }
+
+    #[test]
+    fn test_premium_role_holders() {
+        let cfg = config(EligibilityMode::PremiumRoleHolders, Some(PREMIUM));
+        assert!(!is_eligible(boosting(), &[], &cfg));
+        assert!(is_eligible(boosting(), &[PREMIUM], &cfg));
+        assert!(is_eligible(None, &[OTHER, PREMIUM], &cfg));
+        assert!(!is_eligible(None, &[OTHER], &cfg));
+    }
+
+    #[test]
+    fn test_either() {
+        let cfg = config(EligibilityMode::Either, Some(PREMIUM));
+        assert!(is_eligible(boosting(), &[], &cfg));
+        assert!(is_eligible(boosting(), &[PREMIUM], &cfg));
+        assert!(is_eligible(None, &[PREMIUM], &cfg));
+        assert!(!is_eligible(None, &[OTHER], &cfg));
+    }
+
+    #[test]
+    fn test_premium_modes_without_role_fall_back_to_boosting() {
+        for mode in [EligibilityMode::PremiumRoleHolders, EligibilityMode::Either] {
+            let cfg = config(mode, None);
+            assert_eq!(cfg.effective_mode(), EligibilityMode::BoostersOnly);
+            assert!(is_eligible(boosting(), &[], &cfg));
+            assert!(!is_eligible(None, &[PREMIUM], &cfg));
+        }
+    }
+
+    fn at(value: &str) -> DateTime<Utc> {
+        crate::utils::spotlight::parse_sqlite_timestamp(value).unwrap()
+    }
+
+    fn requirement(account: u32, membership: u32) -> AgeRequirement {
+        AgeRequirement {
+            min_account_age_days: account,
+            min_membership_days: membership,
+        }
+    }
+
+    #[test]
+    fn test_age_thresholds_off_by_default() {
+        let now = at("2024-06-01 12:00:00");
+        assert!(!AgeRequirement::default().is_enabled());
+        assert_eq!(AgeRequirement::default().describe(), "None");
+        assert_eq!(
+            check_age(&AgeRequirement::default(), now, None, now),
+            AgeCheck::Met
+        );
+    }
+
+    #[test]
+    fn test_account_age_threshold() {
+        let now = at("2024-06-01 12:00:00");
+        let req = requirement(7, 0);
+
+        assert_eq!(
+            check_age(&req, at("2024-05-30 12:00:00"), None, now),
+            AgeCheck::NotUntil(at("2024-06-06 12:00:00"))
+        );
+        assert_eq!(
+            check_age(&req, at("2024-05-25 12:00:00"), None, now),
+            AgeCheck::Met
+        );
+        // Exactly at the threshold counts as eligible
+        assert_eq!(
+            check_age(
+                &req,
+                at("2024-05-25 12:00:00"),
+                None,
+                at("2024-06-01 12:00:00")
+            ),
+            AgeCheck::Met
+        );
+    }
+
+    #[test]
+    fn test_later_of_both_thresholds_wins() {
+        let now = at("2024-06-01 12:00:00");
+        let req = requirement(30, 3);
+        let created = at("2024-05-20 00:00:00");
+        let joined = at("2024-05-31 00:00:00");
+
+        assert_eq!(
+            check_age(&req, created, Some(joined), now),
+            AgeCheck::NotUntil(at("2024-06-19 00:00:00"))
+        );
+        assert_eq!(
+            check_age(&requirement(1, 3), created, Some(joined), now),
+            AgeCheck::NotUntil(at("2024-06-03 00:00:00"))
+        );
+        assert_eq!(
+            requirement(30, 1).describe(),
+            "Account 30 days • Membership 1 day"
+        );
+    }
+
+    #[test]
+    fn test_missing_join_date() {
+        let now = at("2024-06-01 12:00:00");
+        let created = at("2020-01-01 00:00:00");
+
+        assert_eq!(
+            check_age(&requirement(0, 3), created, None, now),
+            AgeCheck::JoinDateUnknown
+        );
+        // Without a membership threshold the join date doesn't matter
+        assert_eq!(
+            check_age(&requirement(7, 0), created, None, now),
+            AgeCheck::Met
+        );
+        assert_eq!(
+            to_utc(Timestamp::from_unix_timestamp(1_717_243_200).unwrap()),
+            at("2024-06-01 12:00:00")
+        );
+    }
+
+    #[test]
+    fn test_requirement_text_adapts() {
+        assert!(config(EligibilityMode::BoostersOnly, Some(PREMIUM))
+            .requirement_text()
+            .contains("boosters"));
+        assert_eq!(
+            config(EligibilityMode::PremiumRoleHolders, Some(PREMIUM)).requirement_text(),
+            "You need the <@&42> role to use this command."
+        );
+        assert!(config(EligibilityMode::Either, Some(PREMIUM))
+            .requirement_text()
+            .contains("boost this server or hold the <@&42> role"));
+    }
+}
@@ -1,3 +1,4 @@
+use super::embed_theme::EmbedTheme;
 use poise::serenity_prelude::{CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter, Timestamp};
 
 #[derive(Clone, Copy)]
@@ -69,6 +70,42 @@ impl EmbedBuilder {
             .timestamp(Timestamp::now())
     }
 
+    /// [`Self::success`] in a guild's theme colors
+    pub fn themed_success(
+        theme: &EmbedTheme,
+        title: impl Into<String>,
+        description: impl Into<String>,
+    ) -> CreateEmbed {
+        Self::success(title, description).color(theme.color(EmbedColor::Success))
+    }
+
+    /// [`Self::error`] in a guild's theme colors
+    pub fn themed_error(
+        theme: &EmbedTheme,
+        title: impl Into<String>,
+        description: impl Into<String>,
+    ) -> CreateEmbed {
+        Self::error(title, description).color(theme.color(EmbedColor::Error))
+    }
+
+    /// [`Self::info`] in a guild's theme colors
+    pub fn themed_info(
+        theme: &EmbedTheme,
+        title: impl Into<String>,
+        description: impl Into<String>,
+    ) -> CreateEmbed {
+        Self::info(title, description).color(theme.color(EmbedColor::Info))
+    }
+
+    /// [`Self::primary`] in a guild's theme colors
+    pub fn themed_primary(
+        theme: &EmbedTheme,
+        title: impl Into<String>,
+        description: impl Into<String>,
+    ) -> CreateEmbed {
+        Self::primary(title, description).color(theme.color(EmbedColor::Primary))
+    }
+
     pub fn custom(
         title: impl Into<String>,
         description: impl Into<String>,
@@ -136,3 +173,246 @@ impl EmbedBuilder {
         }
     }
 }
+
+/// Discord's limit on an embed description, in characters
+pub const MAX_DESCRIPTION_CHARS: usize = 4096;
+
+/// Discord's limit on everything in one embed combined, in characters
+pub const MAX_EMBED_CHARS: usize = 6000;
+
+/// Discord's limit on the choices one autocomplete list shows
+pub const MAX_AUTOCOMPLETE_CHOICES: usize = 25;
+
+/// Room left for a page's title and footer unless the caller reserves more
+const DEFAULT_RESERVED_CHARS: usize = 512;
+
+/// Collects lines for a list embed and splits them across as many pages as
+/// Discord's limits need. Lines are never split between pages; one too long
+/// for a page on its own is cut at a space so mentions stay whole.
+#[derive(Debug, Clone)]
+pub struct DescriptionBuilder {
+    header: Option<String>,
+    separator: &'static str,
+    lines: Vec<String>,
+    max_lines: Option<usize>,
+    reserved: usize,
+}
+
+impl Default for DescriptionBuilder {
+    fn default() -> Self {
+        Self {
+            header: None,
+            separator: "\n",
+            lines: Vec::new(),
+            max_lines: None,
+            reserved: DEFAULT_RESERVED_CHARS,
+        }
+    }
+}
+
+impl DescriptionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Text repeated at the top of every page
+    pub fn header(mut self, header: impl Into<String>) -> Self {
+        self.header = Some(header.into());
+        self
+    }
+
+    /// What goes between lines, e.g. a blank line for multi-line entries
+    pub fn separator(mut self, separator: &'static str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Start a new page after this many lines even if more would fit
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = Some(max_lines.max(1));
+        self
+    }
+
+    /// Characters the title, footer and fields added to each page will use
+    pub fn reserve(mut self, chars: usize) -> Self {
+        self.reserved = chars;
+        self
+    }
+
+    pub fn lines<I, S>(mut self, lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.lines.extend(lines.into_iter().map(Into::into));
+        self
+    }
+
+    /// Most characters one page's description may hold
+    fn budget(&self) -> usize {
+        MAX_DESCRIPTION_CHARS.min(MAX_EMBED_CHARS.saturating_sub(self.reserved))
+    }
+
+    /// Page descriptions in order; there's always at least one
+    pub fn pages(&self) -> Vec<String> {
+        let header = self
+            .header
+            .as_ref()
+            .map(|header| format!("{}\n\n", header))
+            .unwrap_or_default();
+        let header_len = header.chars().count();
+        let separator_len = self.separator.chars().count();
+        let room = self.budget().saturating_sub(header_len + separator_len);
+
+        let mut pages = Vec::new();
+        let mut current = header.clone();
+        let mut current_len = header_len;
+        let mut current_lines = 0;
+
+        for line in &self.lines {
+            let line = clip_line(line, room);
+            let line_len = line.chars().count();
+
+            let full = self.max_lines.is_some_and(|max| current_lines >= max);
+            if current_lines > 0 && (full || current_len + separator_len + line_len > self.budget())
+            {
+                let page = std::mem::replace(&mut current, header.clone());
+                pages.push(page.trim_end().to_string());
+                current_len = header_len;
+                current_lines = 0;
+            }
+
+            // A blank spacer line is pointless at the top of a new page
+            if current_lines == 0 && !pages.is_empty() && line.trim().is_empty() {
+                continue;
+            }
+
+            if current_lines > 0 {
+                current.push_str(self.separator);
+                current_len += separator_len;
+            }
+            current.push_str(&line);
+            current_len += line_len;
+            current_lines += 1;
+        }
+
+        if current_lines > 0 || pages.is_empty() {
+            pages.push(current.trim_end().to_string());
+        }
+        pages
+    }
+
+    /// One embed per page. `embed` is given the page's description, its
+    /// 1-based number and the page count, and adds the title and footer.
+    pub fn build(self, embed: impl Fn(String, usize, usize) -> CreateEmbed) -> Vec<CreateEmbed> {
+        let pages = self.pages();
+        let total = pages.len();
+        pages
+            .into_iter()
+            .enumerate()
+            .map(|(i, description)| embed(description, i + 1, total))
+            .collect()
+    }
+}
+
+/// `line` cut to `max` characters, at the last space where possible
+fn clip_line(line: &str, max: usize) -> String {
+    if line.chars().count() <= max {
+        return line.to_string();
+    }
+
+    let kept: String = line.chars().take(max.saturating_sub(1)).collect();
+    let kept = match kept.rfind(char::is_whitespace) {
+        Some(space) if space > 0 => kept[..space].trim_end(),
+        _ => kept.as_str(),
+    };
+    format!("{}…", kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Characters Discord counts towards an embed's combined limit
+    fn embed_chars(embed: &serde_json::Value) -> usize {
+        let text = |value: &serde_json::Value| value.as_str().map_or(0, |s| s.chars().count());
+        let fields = embed["fields"].as_array().map_or(0, |fields| {
+            fields
+                .iter()
+                .map(|field| text(&field["name"]) + text(&field["value"]))
+                .sum()
+        });
+        text(&embed["title"]) + text(&embed["description"]) + text(&embed["footer"]["text"]) + fields
+    }
+
+    #[test]
+    fn test_many_lines_stay_within_limits_and_whole() {
+        let lines: Vec<String> = (0..500)
+            .map(|i| {
+                format!(
+                    "{}. <@{}> shared <@&{}> with a fairly long note attached",
+                    i + 1,
+                    1_987_654_321_098_765_000u64 + i,
+                    1_234_567_890_123_456_000u64 + i
+                )
+            })
+            .collect();
+
+        let embeds = DescriptionBuilder::new()
+            .header("**Everything (500 total):**")
+            .lines(lines.clone())
+            .build(|description, page, total| {
+                EmbedBuilder::primary("A list", description)
+                    .footer(CreateEmbedFooter::new(format!("Page {} of {}", page, total)))
+            });
+        assert!(embeds.len() > 1);
+
+        let mut seen = 0;
+        for embed in &embeds {
+            let embed = serde_json::to_value(embed).unwrap();
+            let description = embed["description"].as_str().unwrap();
+            assert!(description.chars().count() <= MAX_DESCRIPTION_CHARS);
+            assert!(embed_chars(&embed) <= MAX_EMBED_CHARS);
+            assert!(description.starts_with("**Everything (500 total):**\n\n"));
+
+            for line in description.lines().skip(2) {
+                assert_eq!(line, lines[seen]);
+                seen += 1;
+            }
+        }
+        assert_eq!(seen, lines.len());
+    }
+
+    #[test]
+    fn test_max_lines_and_reserve_start_new_pages() {
+        let builder = DescriptionBuilder::new()
+            .max_lines(10)
+            .lines((0..25).map(|i| format!("line {}", i)));
+        let pages = builder.pages();
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages[2].lines().count(), 5);
+
+        // Fields added to each page take room from the description
+        let reserved = DescriptionBuilder::new()
+            .reserve(MAX_EMBED_CHARS - 100)
+            .lines(vec!["x".repeat(60); 3]);
+        assert_eq!(reserved.pages().len(), 3);
+
+        assert_eq!(DescriptionBuilder::new().pages(), vec![String::new()]);
+    }
+
+    #[test]
+    fn test_overlong_line_is_cut_between_mentions() {
+        let line = "<@123456789012345678> ".repeat(300);
+        let pages = DescriptionBuilder::new().lines([line]).pages();
+
+        assert_eq!(pages.len(), 1);
+        assert!(pages[0].chars().count() <= MAX_DESCRIPTION_CHARS);
+        let kept = pages[0].strip_suffix('…').unwrap();
+        assert!(kept.ends_with("<@123456789012345678>"));
+        assert_eq!(
+            kept.split(' ').filter(|mention| mention.len() != 21).count(),
+            0
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use poise::serenity_prelude::CreateEmbed;
+use serenity::all::{ChannelId, HttpError};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tokio::time::{Duration, Instant};
+
+/// Discord's hard limit on plain message content
+pub const MAX_MESSAGE_LENGTH: usize = 2000;
+
+/// Only the first few fields are flattened into text
+const MAX_FALLBACK_FIELDS: usize = 5;
+
+/// How long a channel's Embed Links check is trusted before re-checking
+const PERMISSION_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Discord JSON error code for "Missing Permissions"
+const MISSING_PERMISSIONS_CODE: isize = 50013;
+
+pub const EMBED_LINKS_NOTE: &str =
+    "-# ⚠️ I'm missing the **Embed Links** permission here. Ask an admin to grant it for richer replies.";
+
+/// Flatten an embed into markdown text that fits in a single message.
+///
+/// Title, description and the first few fields are kept; the note asking
+/// admins for Embed Links is always appended and never truncated.
+pub fn embed_to_text(embed: &CreateEmbed) -> String {
+    let value = serde_json::to_value(embed).unwrap_or_default();
+
+    let mut sections = Vec::new();
+
+    if let Some(title) = value.get("title").and_then(|v| v.as_str()) {
+        sections.push(format!("**{}**", title));
+    }
+
+    if let Some(description) = value.get("description").and_then(|v| v.as_str()) {
+        sections.push(description.to_string());
+    }
+
+    if let Some(fields) = value.get("fields").and_then(|v| v.as_array()) {
+        for field in fields.iter().take(MAX_FALLBACK_FIELDS) {
+            let name = field.get("name").and_then(|v| v.as_str()).unwrap_or_default();
+            let field_value = field.get("value").and_then(|v| v.as_str()).unwrap_or_default();
+            sections.push(format!("**{}**\n{}", name, field_value));
+        }
+
+        if fields.len() > MAX_FALLBACK_FIELDS {
+            sections.push(format!(
+                "*…and {} more field(s)*",
+                fields.len() - MAX_FALLBACK_FIELDS
+            ));
+        }
+    }
+
+    let body = sections.join("\n\n");
+    let budget = MAX_MESSAGE_LENGTH - EMBED_LINKS_NOTE.chars().count() - 2;
+
+    format!("{}\n\n{}", truncate_chars(&body, budget), EMBED_LINKS_NOTE)
+}
+
+/// Truncate to at most `max` characters, marking the cut with an ellipsis
+fn truncate_chars(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+
+    let mut truncated: String = text.chars().take(max.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Whether a send failed because the bot lacks permissions in the channel
+pub fn is_missing_permissions(error: &serenity::Error) -> bool {
+    matches!(
+        error,
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(response))
+            if response.error.code == MISSING_PERMISSIONS_CODE
+    )
+}
+
+/// Short-lived per-channel record of whether the bot can send embeds
+#[derive(Debug, Clone, Default)]
+pub struct EmbedPermissionCache {
+    entries: Arc<RwLock<HashMap<ChannelId, (bool, Instant)>>>,
+}
+
+impl EmbedPermissionCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn get(&self, channel_id: ChannelId) -> Option<bool> {
+        let entries = self.entries.read().await;
+        entries
+            .get(&channel_id)
+            .filter(|(_, checked_at)| checked_at.elapsed() < PERMISSION_CACHE_TTL)
+            .map(|(can_embed, _)| *can_embed)
+    }
+
+    pub async fn set(&self, channel_id: ChannelId, can_embed: bool) {
+        let mut entries = self.entries.write().await;
+        entries.insert(channel_id, (can_embed, Instant::now()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_embed_flattened() {
+        let embed = CreateEmbed::new()
+            .title("✅ Done")
+            .description("Your role was updated")
+            .field("Color", "`#FF0000`", true);
+
+        let text = embed_to_text(&embed);
+        assert!(text.starts_with("**✅ Done**\n\nYour role was updated"));
+        assert!(text.contains("**Color**\n`#FF0000`"));
+        assert!(text.ends_with(EMBED_LINKS_NOTE));
+    }
+
+    #[test]
+    fn test_large_embed_truncated_to_limit() {
+        let mut embed = CreateEmbed::new()
+            .title("Big")
+            .description("é".repeat(4096));
+        for i in 0..25 {
+            embed = embed.field(format!("Field {}", i), "x".repeat(1024), false);
+        }
+
+        let text = embed_to_text(&embed);
+        assert!(text.chars().count() <= MAX_MESSAGE_LENGTH);
+        assert!(text.contains('…'));
+        assert!(text.ends_with(EMBED_LINKS_NOTE));
+    }
+
+    #[test]
+    fn test_field_count_capped() {
+        let mut embed = CreateEmbed::new().title("Fields");
+        for i in 0..8 {
+            embed = embed.field(format!("F{}", i), "v", false);
+        }
+
+        let text = embed_to_text(&embed);
+        assert!(text.contains("**F4**"));
+        assert!(!text.contains("**F5**"));
+        assert!(text.contains("…and 3 more field(s)"));
+    }
+
+    #[tokio::test]
+    async fn test_permission_cache() {
+        let cache = EmbedPermissionCache::new();
+        let channel = ChannelId::new(42);
+
+        assert_eq!(cache.get(channel).await, None);
+        cache.set(channel, false).await;
+        assert_eq!(cache.get(channel).await, Some(false));
+    }
+}
@@ -0,0 +1,138 @@
+use super::color_parser::ColorParser;
+use super::embed_builder::EmbedColor;
+
+/// The embed colors a guild can override
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum ThemeSlot {
+    #[name = "success"]
+    Success,
+    #[name = "error"]
+    Error,
+    #[name = "info"]
+    Info,
+    #[name = "primary"]
+    Primary,
+}
+
+impl ThemeSlot {
+    pub const ALL: [ThemeSlot; 4] = [
+        ThemeSlot::Success,
+        ThemeSlot::Error,
+        ThemeSlot::Info,
+        ThemeSlot::Primary,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThemeSlot::Success => "success",
+            ThemeSlot::Error => "error",
+            ThemeSlot::Info => "info",
+            ThemeSlot::Primary => "primary",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThemeSlot::Success => "Success",
+            ThemeSlot::Error => "Error",
+            ThemeSlot::Info => "Info",
+            ThemeSlot::Primary => "Primary",
+        }
+    }
+
+    /// The built-in color this slot replaces
+    pub fn default_color(&self) -> EmbedColor {
+        match self {
+            ThemeSlot::Success => EmbedColor::Success,
+            ThemeSlot::Error => EmbedColor::Error,
+            ThemeSlot::Info => EmbedColor::Info,
+            ThemeSlot::Primary => EmbedColor::Primary,
+        }
+    }
+}
+
+/// A guild's embed color overrides; unset slots use [`EmbedColor`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmbedTheme {
+    pub success: Option<u32>,
+    pub error: Option<u32>,
+    pub info: Option<u32>,
+    pub primary: Option<u32>,
+}
+
+impl EmbedTheme {
+    pub fn get(&self, slot: ThemeSlot) -> Option<u32> {
+        match slot {
+            ThemeSlot::Success => self.success,
+            ThemeSlot::Error => self.error,
+            ThemeSlot::Info => self.info,
+            ThemeSlot::Primary => self.primary,
+        }
+    }
+
+    /// The color to use for an embed; warning, secondary and custom colors
+    /// aren't themeable
+    pub fn color(&self, color: EmbedColor) -> u32 {
+        let slot = match color {
+            EmbedColor::Success => ThemeSlot::Success,
+            EmbedColor::Error => ThemeSlot::Error,
+            EmbedColor::Info => ThemeSlot::Info,
+            EmbedColor::Primary => ThemeSlot::Primary,
+            _ => return color.value(),
+        };
+        self.get(slot).unwrap_or_else(|| color.value())
+    }
+
+    /// One line per slot with its hex color, for `/settings config`
+    pub fn describe(&self) -> String {
+        ThemeSlot::ALL
+            .iter()
+            .map(|slot| {
+                let hex = ColorParser::to_hex_string(self.color(slot.default_color()));
+                let source = if self.get(*slot).is_some() {
+                    ""
+                } else {
+                    " (default)"
+                };
+                format!("{}: `{}`{}", slot.label(), hex, source)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unset_slots_fall_back_to_palette() {
+        let theme = EmbedTheme {
+            success: Some(0x123456),
+            info: Some(0xABCDEF),
+            ..Default::default()
+        };
+        assert_eq!(theme.color(EmbedColor::Success), 0x123456);
+        assert_eq!(theme.color(EmbedColor::Error), EmbedColor::Error.value());
+        // Warning shares Info's default color but isn't themed with it
+        assert_eq!(
+            theme.color(EmbedColor::Warning),
+            EmbedColor::Warning.value()
+        );
+        assert_eq!(
+            EmbedTheme::default().color(EmbedColor::Primary),
+            EmbedColor::Primary.value()
+        );
+    }
+
+    #[test]
+    fn test_describe_marks_defaults() {
+        let theme = EmbedTheme {
+            primary: Some(0xFF0000),
+            ..Default::default()
+        };
+        let described = theme.describe();
+        assert!(described.ends_with("Primary: `#FF0000`"));
+        assert!(described.contains("Success: `#62CB77` (default)"));
+    }
+}
@@ -4,9 +4,13 @@ use std::fmt;
 pub enum BotError {
     Config(String),
     Discord(serenity::Error),
+    /// Discord kept failing transiently after every retry
+    DiscordUnavailable(String),
     Io(std::io::Error),
     Command(String),
     InvalidColor(String),
+    /// A color in `rgb()`/`hsl()` notation with a bad value, and what's wrong
+    InvalidColorValue(String),
     Other(String),
 }
 
@@ -15,9 +19,11 @@ impl fmt::Display for BotError {
         match self {
             BotError::Config(msg) => write!(f, "Configuration error: {}", msg),
             BotError::Discord(err) => write!(f, "Discord error: {}", err),
+            BotError::DiscordUnavailable(msg) => write!(f, "Discord unavailable: {}", msg),
             BotError::Io(err) => write!(f, "IO error: {}", err),
             BotError::Command(msg) => write!(f, "Command error: {}", msg),
             BotError::InvalidColor(color) => write!(f, "Invalid color format: '{}'", color),
+            BotError::InvalidColorValue(reason) => write!(f, "Invalid color: {}", reason),
             BotError::Other(msg) => write!(f, "Error: {}", msg),
         }
     }
@@ -25,6 +31,107 @@ impl fmt::Display for BotError {
 
 impl std::error::Error for BotError {}
 
+impl BotError {
+    /// Stable code shown to members so a report can be matched to its cause
+    pub fn code(&self) -> &'static ErrorCode {
+        let code = match self {
+            BotError::Config(_) => "CFG-001",
+            BotError::Discord(_) => "DSC-001",
+            BotError::DiscordUnavailable(_) => "DSC-002",
+            BotError::Io(_) => "SYS-001",
+            BotError::Command(_) => "CMD-001",
+            BotError::InvalidColor(_) => "CLR-001",
+            BotError::InvalidColorValue(_) => "CLR-002",
+            BotError::Other(_) => "CMD-002",
+        };
+        error_code(code)
+    }
+}
+
+/// An entry in the error reference looked up with `/help errors <code>`
+#[derive(Debug, PartialEq, Eq)]
+pub struct ErrorCode {
+    pub code: &'static str,
+    pub title: &'static str,
+    /// What went wrong and what, if anything, the member can do about it
+    pub description: &'static str,
+}
+
+pub const ERROR_CODES: &[ErrorCode] = &[
+    ErrorCode {
+        code: "CFG-001",
+        title: "Bot misconfigured",
+        description: "The bot's own configuration is missing or invalid. Report this to the bot's owners.",
+    },
+    ErrorCode {
+        code: "CMD-001",
+        title: "Command failed",
+        description: "The command couldn't finish with the input given. The error message says what to change.",
+    },
+    ErrorCode {
+        code: "CMD-002",
+        title: "Unexpected failure",
+        description: "Something the command relies on failed, such as a missing role or an image that couldn't be processed. Try again; report it if it keeps happening.",
+    },
+    ErrorCode {
+        code: "CLR-001",
+        title: "Unrecognized color",
+        description: "The color isn't in a format the bot understands. Use a hex code like `#FF5733` or a name like `red`.",
+    },
+    ErrorCode {
+        code: "CLR-002",
+        title: "Color out of range",
+        description: "An `rgb()` or `hsl()` color has a value outside its allowed range.",
+    },
+    ErrorCode {
+        code: "DB-001",
+        title: "Database error",
+        description: "The bot couldn't read or save its data. Try again in a moment.",
+    },
+    ErrorCode {
+        code: "DB-002",
+        title: "Conflicting change",
+        description: "The change clashes with data that already exists, usually because the same change was made twice at once. Check the current state and try again.",
+    },
+    ErrorCode {
+        code: "DB-003",
+        title: "Record not found",
+        description: "Something the command expected to be saved no longer exists, often because it was removed in the meantime.",
+    },
+    ErrorCode {
+        code: "DSC-001",
+        title: "Discord refused the request",
+        description: "Discord rejected an action, most often because the bot lacks a permission or its role sits too low in the role list.",
+    },
+    ErrorCode {
+        code: "DSC-002",
+        title: "Discord unavailable",
+        description: "Discord kept failing after several retries. Nothing needs changing; try again shortly.",
+    },
+    ErrorCode {
+        code: "PRM-001",
+        title: "Not allowed",
+        description: "A permission check stopped the command. The error message says which one.",
+    },
+    ErrorCode {
+        code: "SYS-001",
+        title: "System error",
+        description: "The bot's host failed to read or write a file. Report this to the bot's owners.",
+    },
+];
+
+/// Look up a code from the reference, ignoring case
+pub fn lookup_error_code(code: &str) -> Option<&'static ErrorCode> {
+    ERROR_CODES
+        .iter()
+        .find(|entry| entry.code.eq_ignore_ascii_case(code.trim()))
+}
+
+/// A code known to be in [`ERROR_CODES`]
+pub(crate) fn error_code(code: &str) -> &'static ErrorCode {
+    lookup_error_code(code).expect("error code missing from ERROR_CODES")
+}
+
 impl From<serenity::Error> for BotError {
     fn from(err: serenity::Error) -> Self {
         BotError::Discord(err)
@@ -44,3 +151,40 @@ impl From<reqwest::Error> for BotError {
 }
 
 pub type BotResult<T> = Result<T, BotError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_codes_are_unique() {
+        let mut seen = HashSet::new();
+        for entry in ERROR_CODES {
+            assert!(seen.insert(entry.code), "{} listed twice", entry.code);
+        }
+    }
+
+    #[test]
+    fn test_every_variant_has_a_listed_code() {
+        let errors = [
+            BotError::Config(String::new()),
+            BotError::Discord(serenity::Error::Other("stub")),
+            BotError::DiscordUnavailable(String::new()),
+            BotError::Io(std::io::Error::other("stub")),
+            BotError::Command(String::new()),
+            BotError::InvalidColor(String::new()),
+            BotError::InvalidColorValue(String::new()),
+            BotError::Other(String::new()),
+        ];
+        for error in &errors {
+            assert!(lookup_error_code(error.code().code).is_some(), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_lookup_ignores_case_and_whitespace() {
+        assert_eq!(lookup_error_code(" db-002 ").unwrap().code, "DB-002");
+        assert!(lookup_error_code("DB-999").is_none());
+    }
+}
@@ -0,0 +1,297 @@
+use super::buffered_counter::BufferedCounter;
+use crate::data::models::{ExperimentCounter, ExperimentExposure};
+use serenity::all::UserId;
+use sqlx::SqlitePool;
+use std::ops::{AddAssign, Deref};
+
+/// How long after an impression a follow-up still counts as a conversion
+pub const CONVERSION_WINDOW_DAYS: i64 = 7;
+
+/// Rejection shown to non-boosters in boosters-only guilds
+pub const NON_BOOSTER_REJECTION: &str = "eligibility.non_booster_rejection";
+
+/// A message key with two or more copy variants; the first is the control
+pub struct CopyExperiment {
+    pub key: &'static str,
+    pub variants: &'static [&'static str],
+}
+
+/// Enrolled experiments. Keys not listed here always use their default copy.
+pub const EXPERIMENTS: &[CopyExperiment] = &[CopyExperiment {
+    key: NON_BOOSTER_REJECTION,
+    variants: &[
+        "This command is only available to server boosters! Please boost this server to use this feature.",
+        "Custom roles are a perk for server boosters. Boost this server, then run this command again to set up your role.",
+    ],
+}];
+
+/// The variant a user sees for an enrolled key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Variant {
+    pub key: &'static str,
+    pub index: usize,
+    pub text: &'static str,
+}
+
+impl Variant {
+    pub fn label(&self) -> char {
+        variant_label(self.index)
+    }
+}
+
+/// `A`, `B`, `C`, ... for variant indexes
+pub fn variant_label(index: usize) -> char {
+    (b'A' + (index % 26) as u8) as char
+}
+
+/// FNV-1a, so assignments stay stable across builds and restarts
+fn stable_hash(key: &str, user_id: UserId) -> u64 {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    key.as_bytes()
+        .iter()
+        .chain(user_id.get().to_le_bytes().iter())
+        .fold(OFFSET, |hash, byte| {
+            (hash ^ *byte as u64).wrapping_mul(PRIME)
+        })
+}
+
+/// Pick a variant index out of `count` for a user; always 0 with fewer than two
+pub fn variant_index(key: &str, user_id: UserId, count: usize) -> usize {
+    if count < 2 {
+        return 0;
+    }
+    (stable_hash(key, user_id) % count as u64) as usize
+}
+
+/// The variant `user_id` sees for `key`, or `None` when the key isn't
+/// enrolled and the caller should use its default copy
+pub fn select(key: &str, user_id: UserId) -> Option<Variant> {
+    select_from(EXPERIMENTS, key, user_id)
+}
+
+fn select_from(experiments: &[CopyExperiment], key: &str, user_id: UserId) -> Option<Variant> {
+    let experiment = experiments
+        .iter()
+        .find(|e| e.key == key && e.variants.len() >= 2)?;
+    let index = variant_index(key, user_id, experiment.variants.len());
+
+    Some(Variant {
+        key: experiment.key,
+        index,
+        text: experiment.variants[index],
+    })
+}
+
+/// Counts accumulated for one variant
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VariantCounts {
+    pub impressions: u64,
+    pub conversions: u64,
+}
+
+impl AddAssign for VariantCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.impressions += other.impressions;
+        self.conversions += other.conversions;
+    }
+}
+
+/// An experiment key and variant index
+pub type VariantKey = (&'static str, usize);
+
+/// Buffered impression and conversion counts, flushed to the database in batches
+#[derive(Debug, Clone)]
+pub struct ExperimentCounters {
+    counts: BufferedCounter<VariantKey, VariantCounts>,
+}
+
+impl Default for ExperimentCounters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for ExperimentCounters {
+    type Target = BufferedCounter<VariantKey, VariantCounts>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.counts
+    }
+}
+
+impl ExperimentCounters {
+    pub fn new() -> Self {
+        Self {
+            counts: BufferedCounter::new("experiments", |pool, batch| {
+                Box::pin(ExperimentCounter::add_batch(pool, batch))
+            }),
+        }
+    }
+
+    pub fn record_impression(&self, variant: Variant) {
+        self.counts.add(
+            (variant.key, variant.index),
+            VariantCounts {
+                impressions: 1,
+                conversions: 0,
+            },
+        );
+    }
+
+    pub fn record_conversion(&self, variant: Variant) {
+        self.counts.add(
+            (variant.key, variant.index),
+            VariantCounts {
+                impressions: 0,
+                conversions: 1,
+            },
+        );
+    }
+
+    /// Count an impression and remember the user saw this variant.
+    /// Failures are logged only; experiments must never break a command.
+    pub async fn show(&self, pool: &SqlitePool, variant: Variant, user_id: UserId) {
+        self.record_impression(variant);
+        tracing::debug!(
+            experiment = variant.key,
+            variant = %variant.label(),
+            user_id = %user_id,
+            "Experiment variant shown"
+        );
+
+        if let Err(e) = ExperimentExposure::record(pool, variant.key, user_id, variant.index).await
+        {
+            tracing::warn!(
+                experiment = variant.key,
+                user_id = %user_id,
+                error = ?e,
+                "Failed to record experiment exposure"
+            );
+        }
+    }
+
+    /// Report the follow-up action for `key`; counts a conversion when the
+    /// user saw a variant within the conversion window
+    pub async fn report_conversion(&self, pool: &SqlitePool, key: &str, user_id: UserId) {
+        let Some(variant) = select(key, user_id) else {
+            return;
+        };
+
+        match ExperimentExposure::convert(pool, key, user_id).await {
+            Ok(true) => self.record_conversion(variant),
+            Ok(false) => {}
+            Err(e) => tracing::warn!(
+                experiment = key,
+                user_id = %user_id,
+                error = ?e,
+                "Failed to record experiment conversion"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_EXPERIMENTS: &[CopyExperiment] = &[
+        CopyExperiment {
+            key: "test.two",
+            variants: &["first", "second"],
+        },
+        CopyExperiment {
+            key: "test.single",
+            variants: &["only"],
+        },
+    ];
+
+    #[test]
+    fn test_selection_is_deterministic_per_user() {
+        for id in 1..200u64 {
+            let user = UserId::new(id);
+            assert_eq!(
+                select_from(TEST_EXPERIMENTS, "test.two", user),
+                select_from(TEST_EXPERIMENTS, "test.two", user)
+            );
+        }
+    }
+
+    #[test]
+    fn test_selection_uses_every_variant() {
+        let mut seen = [0usize; 2];
+        for id in 1..=1000u64 {
+            let variant = select_from(TEST_EXPERIMENTS, "test.two", UserId::new(id)).unwrap();
+            seen[variant.index] += 1;
+        }
+        assert!(seen.iter().all(|&n| n > 300), "skewed split: {:?}", seen);
+    }
+
+    #[test]
+    fn test_unenrolled_keys_default_to_control() {
+        let user = UserId::new(7);
+        assert_eq!(select_from(TEST_EXPERIMENTS, "test.missing", user), None);
+        assert_eq!(select_from(TEST_EXPERIMENTS, "test.single", user), None);
+        assert_eq!(variant_index("anything", user, 1), 0);
+        assert_eq!(variant_index("anything", user, 0), 0);
+    }
+
+    #[test]
+    fn test_control_matches_existing_copy() {
+        let experiment = EXPERIMENTS
+            .iter()
+            .find(|e| e.key == NON_BOOSTER_REJECTION)
+            .unwrap();
+        assert_eq!(
+            experiment.variants[0],
+            crate::utils::eligibility::EligibilityConfig::default().requirement_text()
+        );
+    }
+
+    #[test]
+    fn test_counters_aggregate_and_drain() {
+        let counters = ExperimentCounters::new();
+        let a = Variant {
+            key: "test.two",
+            index: 0,
+            text: "first",
+        };
+        let b = Variant { index: 1, ..a };
+
+        counters.record_impression(a);
+        counters.record_impression(a);
+        counters.record_impression(b);
+        counters.record_conversion(a);
+
+        let batch = counters.drain();
+        assert_eq!(
+            batch,
+            vec![
+                (
+                    ("test.two", 0),
+                    VariantCounts {
+                        impressions: 2,
+                        conversions: 1
+                    }
+                ),
+                (
+                    ("test.two", 1),
+                    VariantCounts {
+                        impressions: 1,
+                        conversions: 0
+                    }
+                ),
+            ]
+        );
+        assert!(counters.drain().is_empty());
+
+        counters.record_impression(b);
+        counters.restore(batch);
+        let restored = counters.drain();
+        assert_eq!(restored[1].1.impressions, 2);
+        assert_eq!(restored[0].1.conversions, 1);
+        assert_eq!(a.label(), 'A');
+        assert_eq!(b.label(), 'B');
+    }
+}
@@ -0,0 +1,218 @@
+use serenity::all::{GuildId, UserId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Consecutive failures before a member is offered staff help
+pub const ESCALATION_THRESHOLD: usize = 3;
+
+/// Failures older than this no longer count towards a streak
+pub const ESCALATION_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+/// How long the "Request staff help" button stays usable
+pub const HELP_BUTTON_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// Prefix for the help button's custom ID; the invocation ID follows it
+pub const HELP_BUTTON_PREFIX: &str = "support_ticket:";
+
+/// Longest error text kept per attempt in the ticket summary
+const MAX_ERROR_CHARS: usize = 200;
+
+/// One failed command invocation
+#[derive(Debug, Clone)]
+pub struct FailedAttempt {
+    pub command: String,
+    pub error: String,
+    /// Poise invocation ID, used to correlate with logs
+    pub correlation_id: u64,
+    pub at: Instant,
+}
+
+/// Recent failures of one command for one member
+#[derive(Debug, Clone, Default)]
+pub struct FailureStreak {
+    attempts: Vec<FailedAttempt>,
+}
+
+impl FailureStreak {
+    /// Record a failure, dropping attempts outside the window.
+    /// Returns `true` once the streak reaches the escalation threshold.
+    pub fn record(&mut self, attempt: FailedAttempt) -> bool {
+        let cutoff = attempt.at.checked_sub(ESCALATION_WINDOW);
+        self.attempts
+            .retain(|a| cutoff.is_none_or(|cutoff| a.at > cutoff));
+        self.attempts.push(attempt);
+        self.attempts.len() >= ESCALATION_THRESHOLD
+    }
+
+    pub fn attempts(&self) -> &[FailedAttempt] {
+        &self.attempts
+    }
+}
+
+type StreakKey = (GuildId, UserId, String);
+
+/// In-memory failure streaks keyed by (guild, user, command)
+#[derive(Debug, Clone, Default)]
+pub struct FailureTracker {
+    streaks: Arc<Mutex<HashMap<StreakKey, FailureStreak>>>,
+}
+
+impl FailureTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a failure, returning the streak's attempts when it should escalate
+    pub fn record_failure(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        attempt: FailedAttempt,
+    ) -> Option<Vec<FailedAttempt>> {
+        let mut streaks = self.streaks.lock().unwrap_or_else(|e| e.into_inner());
+        let streak = streaks
+            .entry((guild_id, user_id, attempt.command.clone()))
+            .or_default();
+
+        streak.record(attempt).then(|| streak.attempts().to_vec())
+    }
+
+    /// A success ends the streak
+    pub fn clear(&self, guild_id: GuildId, user_id: UserId, command: &str) {
+        let mut streaks = self.streaks.lock().unwrap_or_else(|e| e.into_inner());
+        streaks.remove(&(guild_id, user_id, command.to_string()));
+    }
+}
+
+/// Custom ID of the help button for one invocation
+pub fn help_button_id(correlation_id: u64) -> String {
+    format!("{}{}", HELP_BUTTON_PREFIX, correlation_id)
+}
+
+/// Only the member whose commands failed may open a ticket for them
+pub fn may_request_help(requester: UserId, failing_user: UserId) -> bool {
+    requester == failing_user
+}
+
+/// Summary of failed attempts posted into the support thread
+pub fn render_summary(attempts: &[FailedAttempt]) -> String {
+    attempts
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            let error: String = a.error.chars().take(MAX_ERROR_CHARS).collect();
+            format!(
+                "**{}.** `/{}` — {} (ref `{}`)",
+                i + 1,
+                a.command,
+                error,
+                a.correlation_id
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(command: &str, at: Instant, correlation_id: u64) -> FailedAttempt {
+        FailedAttempt {
+            command: command.to_string(),
+            error: "Missing Permissions".to_string(),
+            correlation_id,
+            at,
+        }
+    }
+
+    #[test]
+    fn test_escalates_on_third_failure() {
+        let start = Instant::now();
+        let mut streak = FailureStreak::default();
+
+        assert!(!streak.record(attempt("boosterrole color", start, 1)));
+        assert!(!streak.record(attempt(
+            "boosterrole color",
+            start + Duration::from_secs(60),
+            2
+        )));
+        assert!(streak.record(attempt(
+            "boosterrole color",
+            start + Duration::from_secs(120),
+            3
+        )));
+        assert_eq!(streak.attempts().len(), 3);
+    }
+
+    #[test]
+    fn test_old_failures_fall_out_of_window() {
+        let start = Instant::now();
+        let mut streak = FailureStreak::default();
+
+        streak.record(attempt("boosterrole icon", start, 1));
+        streak.record(attempt(
+            "boosterrole icon",
+            start + Duration::from_secs(60),
+            2,
+        ));
+
+        let late = start + ESCALATION_WINDOW + Duration::from_secs(30);
+        assert!(!streak.record(attempt("boosterrole icon", late, 3)));
+        assert_eq!(
+            streak
+                .attempts()
+                .iter()
+                .map(|a| a.correlation_id)
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+    }
+
+    #[test]
+    fn test_tracker_keys_by_command_and_clears_on_success() {
+        let tracker = FailureTracker::new();
+        let guild = GuildId::new(1);
+        let user = UserId::new(2);
+        let now = Instant::now();
+
+        tracker.record_failure(guild, user, attempt("boosterrole color", now, 1));
+        tracker.record_failure(guild, user, attempt("boosterrole icon", now, 2));
+        assert!(tracker
+            .record_failure(guild, user, attempt("boosterrole color", now, 3))
+            .is_none());
+
+        tracker.clear(guild, user, "boosterrole color");
+        assert!(tracker
+            .record_failure(guild, user, attempt("boosterrole color", now, 4))
+            .is_none());
+
+        let other_user = UserId::new(3);
+        assert!(tracker
+            .record_failure(guild, other_user, attempt("boosterrole icon", now, 5))
+            .is_none());
+
+        tracker.record_failure(guild, user, attempt("boosterrole icon", now, 6));
+        let escalated = tracker
+            .record_failure(guild, user, attempt("boosterrole icon", now, 7))
+            .unwrap();
+        assert_eq!(escalated.len(), 3);
+    }
+
+    #[test]
+    fn test_only_failing_user_may_request_help() {
+        assert!(may_request_help(UserId::new(5), UserId::new(5)));
+        assert!(!may_request_help(UserId::new(6), UserId::new(5)));
+    }
+
+    #[test]
+    fn test_summary_includes_command_error_and_reference() {
+        let summary = render_summary(&[attempt("boosterrole color", Instant::now(), 42)]);
+        assert_eq!(
+            summary,
+            "**1.** `/boosterrole color` — Missing Permissions (ref `42`)"
+        );
+        assert_eq!(help_button_id(42), "support_ticket:42");
+    }
+}
@@ -0,0 +1,219 @@
+use serenity::all::GuildId;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{Mutex as AsyncMutex, MutexGuard};
+
+/// Pause between guilds when running per-guild startup work, so a burst of
+/// guilds coming online doesn't hit the API all at once
+pub const STARTUP_GUILD_PACING: Duration = Duration::from_millis(500);
+
+/// What a gateway event means for a guild
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AvailabilityChange {
+    /// Unavailable at startup and now online; run the startup work it missed
+    RunStartup,
+    /// Back after an outage; events may have been missed, so rerun startup work
+    Recovered,
+    /// The bot was added to a new guild
+    Joined,
+    /// Discord lost the guild for now; keep everything
+    Outage,
+    /// The bot was kicked or the guild was deleted
+    Removed,
+    /// Nothing to do, e.g. a GuildCreate for a guild already online
+    Unchanged,
+}
+
+/// Which guilds are waiting on startup work or down in an outage
+#[derive(Debug, Default)]
+pub struct AvailabilityState {
+    deferred: HashSet<GuildId>,
+    unavailable: HashSet<GuildId>,
+}
+
+impl AvailabilityState {
+    /// Record the guilds from Ready, returning those online now. The rest
+    /// are deferred until their GuildCreate arrives.
+    pub fn ready(&mut self, guilds: &[(GuildId, bool)]) -> Vec<GuildId> {
+        self.deferred.clear();
+        self.unavailable.clear();
+
+        guilds
+            .iter()
+            .filter_map(|&(guild_id, unavailable)| {
+                if unavailable {
+                    self.deferred.insert(guild_id);
+                    None
+                } else {
+                    Some(guild_id)
+                }
+            })
+            .collect()
+    }
+
+    pub fn guild_create(&mut self, guild_id: GuildId, is_new: bool) -> AvailabilityChange {
+        if self.deferred.remove(&guild_id) {
+            self.unavailable.remove(&guild_id);
+            AvailabilityChange::RunStartup
+        } else if self.unavailable.remove(&guild_id) {
+            AvailabilityChange::Recovered
+        } else if is_new {
+            AvailabilityChange::Joined
+        } else {
+            AvailabilityChange::Unchanged
+        }
+    }
+
+    /// `unavailable` is the flag on the GuildDelete payload; only `false`
+    /// means the bot actually left the guild
+    pub fn guild_delete(&mut self, guild_id: GuildId, unavailable: bool) -> AvailabilityChange {
+        if unavailable {
+            // A deferred guild stays deferred; its startup work still hasn't run
+            if !self.deferred.contains(&guild_id) {
+                self.unavailable.insert(guild_id);
+            }
+            AvailabilityChange::Outage
+        } else {
+            self.deferred.remove(&guild_id);
+            self.unavailable.remove(&guild_id);
+            AvailabilityChange::Removed
+        }
+    }
+
+    pub fn deferred_count(&self) -> usize {
+        self.deferred.len()
+    }
+}
+
+/// Shared guild availability tracking, plus a lock that keeps per-guild
+/// startup work running one guild at a time
+#[derive(Debug, Clone, Default)]
+pub struct GuildAvailability {
+    state: Arc<Mutex<AvailabilityState>>,
+    startup: Arc<AsyncMutex<()>>,
+}
+
+impl GuildAvailability {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ready(&self, guilds: &[(GuildId, bool)]) -> (Vec<GuildId>, usize) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let available = state.ready(guilds);
+        (available, state.deferred_count())
+    }
+
+    pub fn guild_create(&self, guild_id: GuildId, is_new: bool) -> AvailabilityChange {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.guild_create(guild_id, is_new)
+    }
+
+    pub fn guild_delete(&self, guild_id: GuildId, unavailable: bool) -> AvailabilityChange {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.guild_delete(guild_id, unavailable)
+    }
+
+    /// Hold while running one guild's startup work and its pacing delay
+    pub async fn startup_permit(&self) -> MutexGuard<'_, ()> {
+        self.startup.lock().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guild(id: u64) -> GuildId {
+        GuildId::new(id)
+    }
+
+    #[test]
+    fn test_unavailable_guilds_deferred_until_create() {
+        let mut state = AvailabilityState::default();
+        let available = state.ready(&[(guild(1), false), (guild(2), true), (guild(3), true)]);
+        assert_eq!(available, vec![guild(1)]);
+        assert_eq!(state.deferred_count(), 2);
+
+        assert_eq!(
+            state.guild_create(guild(2), false),
+            AvailabilityChange::RunStartup
+        );
+        // The startup work only runs once
+        assert_eq!(
+            state.guild_create(guild(2), false),
+            AvailabilityChange::Unchanged
+        );
+        assert_eq!(state.deferred_count(), 1);
+    }
+
+    #[test]
+    fn test_outage_is_not_a_removal() {
+        let mut state = AvailabilityState::default();
+        state.ready(&[(guild(1), false)]);
+
+        assert_eq!(
+            state.guild_delete(guild(1), true),
+            AvailabilityChange::Outage
+        );
+        assert_eq!(
+            state.guild_create(guild(1), false),
+            AvailabilityChange::Recovered
+        );
+        assert_eq!(
+            state.guild_delete(guild(1), false),
+            AvailabilityChange::Removed
+        );
+    }
+
+    #[test]
+    fn test_deferred_guild_outage_keeps_startup_pending() {
+        let mut state = AvailabilityState::default();
+        state.ready(&[(guild(1), true)]);
+
+        assert_eq!(
+            state.guild_delete(guild(1), true),
+            AvailabilityChange::Outage
+        );
+        assert_eq!(
+            state.guild_create(guild(1), false),
+            AvailabilityChange::RunStartup
+        );
+    }
+
+    #[test]
+    fn test_removal_clears_pending_work_and_joins_are_new() {
+        let mut state = AvailabilityState::default();
+        state.ready(&[(guild(1), true)]);
+
+        assert_eq!(
+            state.guild_delete(guild(1), false),
+            AvailabilityChange::Removed
+        );
+        assert_eq!(state.deferred_count(), 0);
+        assert_eq!(
+            state.guild_create(guild(1), true),
+            AvailabilityChange::Joined
+        );
+        assert_eq!(
+            state.guild_create(guild(9), false),
+            AvailabilityChange::Unchanged
+        );
+    }
+
+    #[test]
+    fn test_reconnect_ready_replaces_state() {
+        let mut state = AvailabilityState::default();
+        state.ready(&[(guild(1), true), (guild(2), false)]);
+        state.guild_delete(guild(2), true);
+
+        let available = state.ready(&[(guild(1), false), (guild(2), false)]);
+        assert_eq!(available, vec![guild(1), guild(2)]);
+        assert_eq!(state.deferred_count(), 0);
+        assert_eq!(
+            state.guild_create(guild(2), false),
+            AvailabilityChange::Unchanged
+        );
+    }
+}
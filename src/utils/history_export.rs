@@ -0,0 +1,156 @@
+use serde::Serialize;
+use std::fmt::Write;
+
+/// One timestamped event in a member's customization history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HistoryEntry {
+    /// SQLite timestamp (`YYYY-MM-DD HH:MM:SS`, UTC), so entries sort as strings
+    pub at: String,
+    /// Table the entry was read from
+    pub source: &'static str,
+    pub event: String,
+    pub detail: String,
+}
+
+/// Everything recorded about one member, oldest first
+#[derive(Debug, Clone, Serialize)]
+pub struct UserHistoryReport {
+    pub guild_id: String,
+    pub user_id: String,
+    pub generated_at: String,
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl UserHistoryReport {
+    pub fn new(
+        guild_id: u64,
+        user_id: u64,
+        generated_at: chrono::DateTime<chrono::Utc>,
+        mut entries: Vec<HistoryEntry>,
+    ) -> Self {
+        entries.sort_by(|a, b| a.at.cmp(&b.at));
+        Self {
+            guild_id: guild_id.to_string(),
+            user_id: user_id.to_string(),
+            generated_at: generated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            entries,
+        }
+    }
+
+    /// Entry count per source, in first-seen order
+    pub fn counts_by_source(&self) -> Vec<(&'static str, usize)> {
+        let mut counts: Vec<(&'static str, usize)> = Vec::new();
+        for entry in &self.entries {
+            match counts
+                .iter_mut()
+                .find(|(source, _)| *source == entry.source)
+            {
+                Some((_, count)) => *count += 1,
+                None => counts.push((entry.source, 1)),
+            }
+        }
+        counts
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "Customization history for user {}", self.user_id);
+        let _ = writeln!(out, "Guild: {}", self.guild_id);
+        let _ = writeln!(out, "Generated: {} UTC", self.generated_at);
+        let _ = writeln!(out, "Entries: {}", self.entries.len());
+        out.push('\n');
+
+        if self.entries.is_empty() {
+            out.push_str("No recorded history.\n");
+        }
+        for entry in &self.entries {
+            let _ = writeln!(
+                out,
+                "[{}] {} ({}): {}",
+                entry.at, entry.event, entry.source, entry.detail
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn entry(at: &str, source: &'static str, event: &str) -> HistoryEntry {
+        HistoryEntry {
+            at: at.to_string(),
+            source,
+            event: event.to_string(),
+            detail: format!("{} detail", event),
+        }
+    }
+
+    fn report(entries: Vec<HistoryEntry>) -> UserHistoryReport {
+        let generated = chrono::Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+        UserHistoryReport::new(1, 2, generated, entries)
+    }
+
+    #[test]
+    fn test_entries_sorted_oldest_first() {
+        let report = report(vec![
+            entry("2024-05-03 10:00:00", "booster_rename_history", "rename"),
+            entry("2024-05-01 09:00:00", "name_violations", "name_rejected"),
+            entry("2024-05-02 08:00:00", "booster_rename_history", "rename"),
+        ]);
+
+        let times: Vec<_> = report.entries.iter().map(|e| e.at.as_str()).collect();
+        assert_eq!(
+            times,
+            [
+                "2024-05-01 09:00:00",
+                "2024-05-02 08:00:00",
+                "2024-05-03 10:00:00"
+            ]
+        );
+        assert_eq!(
+            report.counts_by_source(),
+            vec![("name_violations", 1), ("booster_rename_history", 2)]
+        );
+    }
+
+    #[test]
+    fn test_json_keeps_ids_as_strings() {
+        let report = report(vec![entry(
+            "2024-05-01 09:00:00",
+            "moderation_cases",
+            "warn",
+        )]);
+        let value: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+
+        assert_eq!(value["guild_id"], "1");
+        assert_eq!(value["user_id"], "2");
+        assert_eq!(value["generated_at"], "2024-06-01 12:00:00");
+        assert_eq!(value["entries"][0]["source"], "moderation_cases");
+        assert_eq!(value["entries"][0]["detail"], "warn detail");
+    }
+
+    #[test]
+    fn test_text_report() {
+        let text = report(vec![entry(
+            "2024-05-01 09:00:00",
+            "name_violations",
+            "name_rejected",
+        )])
+        .to_text();
+        assert!(text.contains("Entries: 1"));
+        assert!(text.contains(
+            "[2024-05-01 09:00:00] name_rejected (name_violations): name_rejected detail"
+        ));
+
+        assert!(report(Vec::new())
+            .to_text()
+            .contains("No recorded history."));
+    }
+}
@@ -0,0 +1,478 @@
+use crate::bot::Context;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::LazyLock;
+
+/// Languages the bot's replies are written in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, poise::ChoiceParameter)]
+pub enum Locale {
+    #[default]
+    #[name = "English (US)"]
+    EnUs,
+    #[name = "Español (España)"]
+    EsEs,
+}
+
+impl Locale {
+    #[cfg(test)]
+    const ALL: [Locale; 2] = [Locale::EnUs, Locale::EsEs];
+
+    /// Discord's code for the locale, also how it's stored
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::EnUs => "en-US",
+            Locale::EsEs => "es-ES",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Locale::EnUs => "English (US)",
+            Locale::EsEs => "Español (España)",
+        }
+    }
+
+    /// The catalog for a Discord locale code. Regional variants share their
+    /// language's catalog, so `en-GB` reads en-US and `es-419` reads es-ES.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let language = code.split(['-', '_']).next()?.to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Some(Locale::EnUs),
+            "es" => Some(Locale::EsEs),
+            _ => None,
+        }
+    }
+
+    fn catalog(&self) -> &'static HashMap<&'static str, &'static str> {
+        static CATALOGS: LazyLock<HashMap<Locale, HashMap<&'static str, &'static str>>> =
+            LazyLock::new(|| {
+                HashMap::from([
+                    (Locale::EnUs, EN_US.iter().copied().collect()),
+                    (Locale::EsEs, ES_ES.iter().copied().collect()),
+                ])
+            });
+        &CATALOGS[self]
+    }
+}
+
+/// A `{name}` placeholder and its value. `Sync` so a message can be built
+/// across an await in a command.
+pub type Arg<'a> = (&'a str, &'a (dyn Display + Sync));
+
+/// The message for `key` in `locale` with `{name}` placeholders filled from
+/// `args`. Messages a catalog lacks come from en-US, so a partial
+/// translation never leaves a reply empty.
+pub fn translate(locale: Locale, key: &str, args: &[Arg]) -> String {
+    let template = locale
+        .catalog()
+        .get(key)
+        .or_else(|| Locale::EnUs.catalog().get(key));
+    let Some(template) = template else {
+        tracing::warn!(key, locale = locale.code(), "Missing message in every catalog");
+        return key.to_string();
+    };
+
+    args.iter().fold(template.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), &value.to_string())
+    })
+}
+
+/// The locale to reply in: the member's Discord language when there's a
+/// catalog for it, otherwise the guild's chosen language, otherwise en-US
+pub async fn locale_for(ctx: Context<'_>) -> Locale {
+    if let Some(locale) = ctx.locale().and_then(Locale::from_code) {
+        return locale;
+    }
+
+    match ctx.guild_id() {
+        Some(guild_id) => ctx.data().guild_locale(guild_id).await.unwrap_or_default(),
+        None => Locale::default(),
+    }
+}
+
+/// Translate a message for whoever invoked the command
+pub async fn tr(ctx: Context<'_>, key: &str, args: &[Arg<'_>]) -> String {
+    translate(locale_for(ctx).await, key, args)
+}
+
+const EN_US: &[(&str, &str)] = &[
+    // Error handler
+    ("error.discord", "Discord API Error"),
+    ("error.discord_unavailable.title", "Discord Is Having Issues"),
+    (
+        "error.discord_unavailable.body",
+        "Discord is having issues, try again shortly.",
+    ),
+    ("error.command", "Command Error"),
+    ("error.config", "Configuration Error"),
+    ("error.database", "Database Error"),
+    ("error.generic.title", "Error"),
+    ("error.generic.body", "An error occurred processing your command."),
+    ("error.footer", "Error {code} · Ref {reference} · /help errors {code}"),
+    ("error.not_allowed", "Command Not Allowed"),
+    ("error.not_staff", "This command is limited to server staff."),
+    ("error.not_owner", "This command is limited to the bot's owners."),
+    ("error.disabled", "`/{command}` has been disabled by this server's admins."),
+    (
+        "error.not_permitted",
+        "You don't have permission to use this command or it can't be used here.",
+    ),
+    (
+        "error.missing_permissions",
+        "You don't have the permissions this command requires.",
+    ),
+    ("error.arguments.title", "Invalid Arguments"),
+    (
+        "error.arguments.body",
+        "{error}.{example}\n\nUse `/help {command}` for usage information.",
+    ),
+    ("error.arguments.example", "\n\n**Example:** `{example}`"),
+    ("error.slow_down", "⏱️ Slow Down"),
+    ("error.cooldown.command", "You can use `/{command}` again in **{remaining}**."),
+    ("error.cooldown.any", "You can use this command again in **{seconds}s**."),
+    // Booster roles
+    ("booster.not_booster", "Server Booster Required"),
+    ("booster.not_eligible", "Not Eligible"),
+    ("booster.in_progress.title", "⏳ Command Already in Progress"),
+    (
+        "booster.in_progress.body",
+        "Your last booster role change is still being applied. Try again once it finishes.",
+    ),
+    ("booster.invalid_name", "Invalid Role Name"),
+    ("booster.blocked_name.title", "Inappropriate Role Name"),
+    (
+        "booster.blocked_name.body",
+        "The role name contains words that are not allowed. Please choose a different name.",
+    ),
+    ("booster.invalid_color.title", "Invalid Color"),
+    (
+        "booster.invalid_color.body",
+        "{reason}\n\nSupported formats:\n• Hex codes: `#FF0000`, `FF0000`, `0xFF0000`\n• Color names: `red`, `blue`, `green`, etc.\n• Short hex: `#F00` (expands to `#FF0000`)\n• RGB or HSL: `rgb(255, 0, 0)`, `hsl(0, 100%, 50%)`",
+    ),
+    ("booster.invalid_second_color", "Invalid Second Color"),
+    ("booster.name_too_long.title", "Role Name Too Long"),
+    (
+        "booster.name_too_long.body",
+        "This server allows booster role names of up to **{max}** characters; yours has **{length}**.",
+    ),
+    ("booster.second_color_disabled.title", "Second Color Not Allowed"),
+    (
+        "booster.second_color_disabled.body",
+        "This server doesn't allow a second role color. Try again without `second_color`.",
+    ),
+    ("booster.limit.title", "Role Limit Reached"),
+    (
+        "booster.limit.body",
+        "This server allows at most **{max}** booster roles and is using **{current}/{max}**.\n\nExisting roles can still be updated, but new ones can't be created until a slot frees up. Please contact an administrator.",
+    ),
+    ("booster.no_role.title", "No Booster Role"),
+    (
+        "booster.no_role.body",
+        "You don't have a booster role yet. Use `/boosterrole color` to create one first.",
+    ),
+    ("booster.rename_cooldown.title", "⏱️ Cooldown Active"),
+    (
+        "booster.rename_cooldown.body",
+        "You can rename your role again in **{remaining}**.\n\nLast rename: {old_name} → {new_name}",
+    ),
+    ("booster.share_self.title", "Invalid Target"),
+    ("booster.share_self.body", "You cannot share your role with yourself."),
+    ("booster.share_bot.title", "Can't Share With Bots"),
+    ("booster.share_bot.body", "Bots don't need booster roles, so your role wasn't shared."),
+    ("booster.not_in_guild.title", "Member Not Found"),
+    (
+        "booster.not_in_guild.body",
+        "<@{user}> isn't in this server, so your role can't be shared with them.",
+    ),
+    ("booster.role_missing.title", "Role Not Found"),
+    (
+        "booster.role_missing.body",
+        "Your booster role was deleted in Discord, so there's nothing to share. Use `/boosterrole color` to create a new one.",
+    ),
+    ("booster.role_share_limit.title", "Share Limit Reached"),
+    (
+        "booster.role_share_limit.body",
+        "This role has reached the maximum share limit of {max} members.",
+    ),
+    ("booster.member_share_limit.title", "User Share Limit Reached"),
+    (
+        "booster.member_share_limit.body",
+        "<@{user}> has reached the maximum limit of {max} shared roles.",
+    ),
+    ("booster.already_shared.title", "Already Shared"),
+    ("booster.already_shared.body", "Your role is already shared with <@{user}>."),
+    ("booster.created.title", "✅ Booster Role Created!"),
+    (
+        "booster.created.body",
+        "Your custom role **{name}** has been created and assigned!\n\nRole: {role}\nColor: `{color}` (approximately \"{color_name}\")",
+    ),
+    ("booster.created.footer", "Created by {user}"),
+    ("booster.second_color", "Second Color"),
+    ("booster.icon", "Icon"),
+    ("booster.caution", "Caution"),
+    ("booster.preview.title", "👀 Role Preview"),
+    (
+        "booster.preview.body",
+        "**{name}**\nColor: `{color}` (approximately \"{color_name}\")",
+    ),
+    (
+        "booster.preview.footer",
+        "Preview only: no role was created or modified",
+    ),
+    ("booster.renamed.title", "Role Renamed"),
+    (
+        "booster.renamed.body",
+        "Your booster role has been renamed from **{old_name}** to **{new_name}**.",
+    ),
+    ("booster.renamed.footer", "You can rename again in {cooldown}"),
+    ("booster.shared.title", "Role Shared"),
+    (
+        "booster.shared.body",
+        "Your booster role **{name}** has been shared with <@{user}>.{until}",
+    ),
+    ("booster.shared.until", " It expires {when}."),
+    // Settings
+    ("settings.language.title", "🌐 Language Set"),
+    (
+        "settings.language.body",
+        "Replies now default to **{language}**. Members whose Discord is set to a supported language still see replies in theirs.",
+    ),
+];
+
+const ES_ES: &[(&str, &str)] = &[
+    // Error handler
+    ("error.discord", "Error de la API de Discord"),
+    ("error.discord_unavailable.title", "Discord tiene problemas"),
+    (
+        "error.discord_unavailable.body",
+        "Discord está teniendo problemas, inténtalo de nuevo en un momento.",
+    ),
+    ("error.command", "Error del comando"),
+    ("error.config", "Error de configuración"),
+    ("error.database", "Error de la base de datos"),
+    ("error.generic.title", "Error"),
+    ("error.generic.body", "Se produjo un error al procesar tu comando."),
+    ("error.footer", "Error {code} · Ref. {reference} · /help errors {code}"),
+    ("error.not_allowed", "Comando no permitido"),
+    ("error.not_staff", "Este comando está reservado al equipo del servidor."),
+    (
+        "error.not_owner",
+        "Este comando está reservado a los propietarios del bot.",
+    ),
+    (
+        "error.disabled",
+        "Los administradores de este servidor han desactivado `/{command}`.",
+    ),
+    (
+        "error.not_permitted",
+        "No tienes permiso para usar este comando o no se puede usar aquí.",
+    ),
+    (
+        "error.missing_permissions",
+        "No tienes los permisos que requiere este comando.",
+    ),
+    ("error.arguments.title", "Argumentos no válidos"),
+    (
+        "error.arguments.body",
+        "{error}.{example}\n\nUsa `/help {command}` para ver cómo se usa.",
+    ),
+    ("error.arguments.example", "\n\n**Ejemplo:** `{example}`"),
+    ("error.slow_down", "⏱️ Más despacio"),
+    (
+        "error.cooldown.command",
+        "Podrás usar `/{command}` de nuevo en **{remaining}**.",
+    ),
+    (
+        "error.cooldown.any",
+        "Podrás usar este comando de nuevo en **{seconds}s**.",
+    ),
+    // Booster roles
+    ("booster.not_booster", "Necesitas mejorar el servidor"),
+    ("booster.not_eligible", "No cumples los requisitos"),
+    ("booster.in_progress.title", "⏳ Comando en curso"),
+    (
+        "booster.in_progress.body",
+        "Tu último cambio de rol de booster todavía se está aplicando. Inténtalo de nuevo cuando termine.",
+    ),
+    ("booster.invalid_name", "Nombre de rol no válido"),
+    ("booster.blocked_name.title", "Nombre de rol inapropiado"),
+    (
+        "booster.blocked_name.body",
+        "El nombre del rol contiene palabras no permitidas. Elige otro nombre.",
+    ),
+    ("booster.invalid_color.title", "Color no válido"),
+    (
+        "booster.invalid_color.body",
+        "{reason}\n\nFormatos admitidos:\n• Códigos hex: `#FF0000`, `FF0000`, `0xFF0000`\n• Nombres de color en inglés: `red`, `blue`, `green`, etc.\n• Hex corto: `#F00` (equivale a `#FF0000`)\n• RGB o HSL: `rgb(255, 0, 0)`, `hsl(0, 100%, 50%)`",
+    ),
+    ("booster.invalid_second_color", "Segundo color no válido"),
+    ("booster.name_too_long.title", "Nombre de rol demasiado largo"),
+    (
+        "booster.name_too_long.body",
+        "Este servidor permite nombres de rol de booster de hasta **{max}** caracteres; el tuyo tiene **{length}**.",
+    ),
+    ("booster.second_color_disabled.title", "Segundo color no permitido"),
+    (
+        "booster.second_color_disabled.body",
+        "Este servidor no permite un segundo color de rol. Vuelve a intentarlo sin `second_color`.",
+    ),
+    ("booster.limit.title", "Límite de roles alcanzado"),
+    (
+        "booster.limit.body",
+        "Este servidor permite como máximo **{max}** roles de booster y está usando **{current}/{max}**.\n\nLos roles existentes se pueden seguir modificando, pero no se pueden crear nuevos hasta que quede un hueco libre. Contacta con un administrador.",
+    ),
+    ("booster.no_role.title", "Sin rol de booster"),
+    (
+        "booster.no_role.body",
+        "Todavía no tienes un rol de booster. Usa `/boosterrole color` para crear uno.",
+    ),
+    ("booster.rename_cooldown.title", "⏱️ Espera activa"),
+    (
+        "booster.rename_cooldown.body",
+        "Podrás renombrar tu rol de nuevo en **{remaining}**.\n\nÚltimo cambio: {old_name} → {new_name}",
+    ),
+    ("booster.share_self.title", "Destinatario no válido"),
+    ("booster.share_self.body", "No puedes compartir tu rol contigo mismo."),
+    ("booster.share_bot.title", "No se puede compartir con bots"),
+    (
+        "booster.share_bot.body",
+        "Los bots no necesitan roles de booster, así que tu rol no se ha compartido.",
+    ),
+    ("booster.not_in_guild.title", "Miembro no encontrado"),
+    (
+        "booster.not_in_guild.body",
+        "<@{user}> no está en este servidor, así que no puedes compartir tu rol con esa persona.",
+    ),
+    ("booster.role_missing.title", "Rol no encontrado"),
+    (
+        "booster.role_missing.body",
+        "Tu rol de booster se eliminó en Discord, así que no hay nada que compartir. Usa `/boosterrole color` para crear uno nuevo.",
+    ),
+    ("booster.role_share_limit.title", "Límite de uso compartido alcanzado"),
+    (
+        "booster.role_share_limit.body",
+        "Este rol ya se comparte con el máximo de {max} miembros.",
+    ),
+    ("booster.member_share_limit.title", "Límite del miembro alcanzado"),
+    (
+        "booster.member_share_limit.body",
+        "<@{user}> ya tiene el máximo de {max} roles compartidos.",
+    ),
+    ("booster.already_shared.title", "Ya compartido"),
+    ("booster.already_shared.body", "Tu rol ya se comparte con <@{user}>."),
+    ("booster.created.title", "✅ ¡Rol de booster creado!"),
+    (
+        "booster.created.body",
+        "¡Tu rol personalizado **{name}** se ha creado y asignado!\n\nRol: {role}\nColor: `{color}` (aproximadamente \"{color_name}\")",
+    ),
+    ("booster.created.footer", "Creado por {user}"),
+    ("booster.second_color", "Segundo color"),
+    ("booster.icon", "Icono"),
+    ("booster.caution", "Aviso"),
+    ("booster.preview.title", "👀 Vista previa del rol"),
+    (
+        "booster.preview.body",
+        "**{name}**\nColor: `{color}` (aproximadamente \"{color_name}\")",
+    ),
+    (
+        "booster.preview.footer",
+        "Solo es una vista previa: no se ha creado ni modificado ningún rol",
+    ),
+    ("booster.renamed.title", "Rol renombrado"),
+    (
+        "booster.renamed.body",
+        "Tu rol de booster ha pasado de **{old_name}** a **{new_name}**.",
+    ),
+    ("booster.renamed.footer", "Podrás renombrarlo de nuevo en {cooldown}"),
+    ("booster.shared.title", "Rol compartido"),
+    (
+        "booster.shared.body",
+        "Tu rol de booster **{name}** se ha compartido con <@{user}>.{until}",
+    ),
+    ("booster.shared.until", " Caduca {when}."),
+    // Settings
+    ("settings.language.title", "🌐 Idioma configurado"),
+    (
+        "settings.language.body",
+        "Las respuestas usarán **{language}** por defecto. Los miembros con Discord en un idioma admitido seguirán viendo las respuestas en el suyo.",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn placeholders(template: &str) -> HashSet<&str> {
+        template
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn test_locale_codes() {
+        assert_eq!(Locale::from_code("en-US"), Some(Locale::EnUs));
+        assert_eq!(Locale::from_code("en-GB"), Some(Locale::EnUs));
+        assert_eq!(Locale::from_code("es-419"), Some(Locale::EsEs));
+        assert_eq!(Locale::from_code("ES"), Some(Locale::EsEs));
+        assert_eq!(Locale::from_code("fr"), None);
+        assert_eq!(Locale::from_code(""), None);
+
+        for locale in Locale::ALL {
+            assert_eq!(Locale::from_code(locale.code()), Some(locale));
+        }
+    }
+
+    #[test]
+    fn test_placeholders_are_filled() {
+        let text = translate(
+            Locale::EsEs,
+            "booster.limit.body",
+            &[("max", &5), ("current", &5)],
+        );
+        assert!(text.starts_with("Este servidor permite como máximo **5**"));
+        assert!(text.contains("**5/5**"));
+        assert!(!text.contains('{'));
+    }
+
+    #[test]
+    fn test_missing_messages_fall_back() {
+        let key = "booster.no_role.title";
+        let spanish: HashMap<_, _> = ES_ES.iter().copied().collect();
+        assert!(spanish.contains_key(key));
+
+        // A message only en-US has is still shown, in English
+        let only_english = EN_US
+            .iter()
+            .map(|(key, _)| *key)
+            .find(|key| !spanish.contains_key(key));
+        if let Some(key) = only_english {
+            assert_eq!(
+                translate(Locale::EsEs, key, &[]),
+                translate(Locale::EnUs, key, &[])
+            );
+        }
+
+        assert_eq!(translate(Locale::EsEs, "no.such.key", &[]), "no.such.key");
+    }
+
+    #[test]
+    fn test_catalogs_agree_with_en_us() {
+        let english: HashMap<_, _> = EN_US.iter().copied().collect();
+        assert_eq!(english.len(), EN_US.len(), "duplicate en-US key");
+
+        for (key, template) in ES_ES {
+            let source = english
+                .get(key)
+                .unwrap_or_else(|| panic!("es-ES has {} but en-US doesn't", key));
+            assert_eq!(
+                placeholders(template),
+                placeholders(source),
+                "placeholders differ for {}",
+                key
+            );
+        }
+    }
+}
@@ -2,9 +2,285 @@ use crate::utils::error::BotError;
 use image::{DynamicImage, GenericImageView};
 use palette::{FromColor, Lab, Srgb};
 use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
 use std::time::Duration;
+use tokio::sync::Semaphore;
 use tracing::debug;
 
+/// Largest uploaded image accepted for color extraction
+pub const MAX_ATTACHMENT_BYTES: u32 = 8 * 1024 * 1024;
+
+/// Image types the decoder handles
+const ALLOWED_CONTENT_TYPES: &[&str] = &["image/png", "image/jpeg", "image/gif", "image/webp"];
+
+/// Caps concurrent color extractions so image commands can't stampede the CPU
+static PROCESSING_PERMITS: Semaphore = Semaphore::const_new(2);
+
+/// Check an uploaded file against Discord's metadata before downloading it.
+/// Falls back to the file extension when Discord didn't report a content type.
+pub fn validate_image_attachment(
+    content_type: Option<&str>,
+    filename: &str,
+    size: u32,
+) -> Result<(), BotError> {
+    let content_type = content_type
+        .map(|ct| ct.split(';').next().unwrap_or(ct).trim().to_ascii_lowercase())
+        .or_else(|| content_type_from_extension(filename).map(str::to_string));
+
+    match content_type {
+        Some(ct) if ALLOWED_CONTENT_TYPES.contains(&ct.as_str()) => {}
+        Some(ct) => {
+            return Err(BotError::Other(format!(
+                "Unsupported file type `{}`. Upload a PNG, JPEG, GIF, or WebP image",
+                ct
+            )))
+        }
+        None => {
+            return Err(BotError::Other(
+                "Couldn't tell what kind of file that is. Upload a PNG, JPEG, GIF, or WebP image"
+                    .to_string(),
+            ))
+        }
+    }
+
+    if size > MAX_ATTACHMENT_BYTES {
+        return Err(BotError::Other(format!(
+            "Image is too large ({:.1}MB, max {}MB)",
+            size as f64 / (1024.0 * 1024.0),
+            MAX_ATTACHMENT_BYTES / (1024 * 1024)
+        )));
+    }
+
+    Ok(())
+}
+
+fn content_type_from_extension(filename: &str) -> Option<&'static str> {
+    let (_, extension) = filename.rsplit_once('.')?;
+    match extension.to_ascii_lowercase().as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Download an image, aborting once it grows past `max_bytes`
+pub async fn fetch_image_capped(url: &str, max_bytes: u32) -> Result<Vec<u8>, BotError> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    debug!("Downloading image from: {}", url);
+
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| BotError::Other(format!("Failed to fetch image: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(BotError::Other(format!(
+            "Image download failed with status: {}",
+            response.status()
+        )));
+    }
+
+    read_capped(response, max_bytes).await
+}
+
+/// Refusal for a download over `max_bytes`, in KB below a megabyte
+fn too_large(max_bytes: u32) -> BotError {
+    let limit = if max_bytes < 1024 * 1024 {
+        format!("{}KB", max_bytes / 1024)
+    } else {
+        format!("{}MB", max_bytes / (1024 * 1024))
+    };
+    BotError::Other(format!("Image is too large (max {})", limit))
+}
+
+/// Read a response body, aborting once it grows past `max_bytes`
+async fn read_capped(mut response: reqwest::Response, max_bytes: u32) -> Result<Vec<u8>, BotError> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| BotError::Other(format!("Failed to read image data: {}", e)))?
+    {
+        if bytes.len() + chunk.len() > max_bytes as usize {
+            return Err(too_large(max_bytes));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(bytes)
+}
+
+/// Check a member-supplied image link before anything is fetched
+pub fn parse_image_url(input: &str) -> Result<reqwest::Url, BotError> {
+    let url = reqwest::Url::parse(input.trim())
+        .map_err(|_| BotError::Other("That isn't a valid link".to_string()))?;
+
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(BotError::Other(
+            "Only http and https image links are supported".to_string(),
+        ));
+    }
+    if url.host_str().is_none() {
+        return Err(BotError::Other("That link has no host".to_string()));
+    }
+
+    Ok(url)
+}
+
+/// Whether an address is on the public internet. Member-supplied links to
+/// anything else could reach services on the bot's own network.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_multicast()
+                || v4.is_documentation()
+                || a == 0
+                // Carrier-grade NAT
+                || (a == 100 && (64..=127).contains(&b))
+                // Benchmarking
+                || (a == 198 && (b == 18 || b == 19))
+                // Reserved
+                || a >= 240)
+        }
+        IpAddr::V6(v6) => {
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let segments = v6.segments();
+            !(v6.is_loopback()
+                || v6.is_unspecified()
+                || v6.is_multicast()
+                // Unique local
+                || (segments[0] & 0xfe00) == 0xfc00
+                // Link local
+                || (segments[0] & 0xffc0) == 0xfe80
+                // Documentation
+                || (segments[0] == 0x2001 && segments[1] == 0x0db8))
+        }
+    }
+}
+
+/// Download an image from a member-supplied link. The host has to resolve
+/// only to public addresses, and the connection is pinned to the addresses
+/// checked so a second lookup can't swap in a private one. Redirects aren't
+/// followed, and anything not reported as an image is refused unread. Reading
+/// stops once the body grows past `max_bytes`.
+pub async fn fetch_remote_image(url: &reqwest::Url, max_bytes: u32) -> Result<Vec<u8>, BotError> {
+    let host = url
+        .host_str()
+        .ok_or_else(|| BotError::Other("That link has no host".to_string()))?;
+    let port = url.port_or_known_default().unwrap_or(443);
+
+    let literal = host.trim_start_matches('[').trim_end_matches(']');
+    let (domain, addrs) = match literal.parse::<IpAddr>() {
+        Ok(ip) => (None, vec![SocketAddr::new(ip, port)]),
+        Err(_) => {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+                .await
+                .map_err(|_| BotError::Other(format!("Couldn't find the host `{}`", host)))?
+                .collect();
+            (Some(host), addrs)
+        }
+    };
+
+    if addrs.is_empty() || addrs.iter().any(|addr| !is_public_ip(addr.ip())) {
+        return Err(BotError::Other(
+            "That link points to a private or reserved network address".to_string(),
+        ));
+    }
+
+    let mut client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .redirect(reqwest::redirect::Policy::none());
+    if let Some(domain) = domain {
+        client = client.resolve_to_addrs(domain, &addrs);
+    }
+    let client = client.build()?;
+
+    debug!("Downloading member-supplied image from: {}", url);
+
+    let response = client
+        .get(url.clone())
+        .send()
+        .await
+        .map_err(|e| BotError::Other(format!("Failed to fetch image: {}", e)))?;
+
+    if response.status().is_redirection() {
+        return Err(BotError::Other(
+            "That link redirects elsewhere. Use a direct link to the image".to_string(),
+        ));
+    }
+    if !response.status().is_success() {
+        return Err(BotError::Other(format!(
+            "Image download failed with status: {}",
+            response.status()
+        )));
+    }
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|ct| ct.split(';').next().unwrap_or(ct).trim().to_ascii_lowercase());
+    match content_type {
+        Some(ct) if ALLOWED_CONTENT_TYPES.contains(&ct.as_str()) => {}
+        _ => {
+            return Err(BotError::Other(
+                "That link isn't a PNG, JPEG, GIF, or WebP image".to_string(),
+            ))
+        }
+    }
+
+    if response
+        .content_length()
+        .is_some_and(|length| length > max_bytes as u64)
+    {
+        return Err(too_large(max_bytes));
+    }
+
+    read_capped(response, max_bytes).await
+}
+
+/// Run CPU-bound work on `image_data` on a blocking thread, limited by the
+/// shared processing permits so it never stalls the async runtime
+async fn run_limited<T, F>(image_data: Vec<u8>, work: F) -> Result<T, BotError>
+where
+    T: Send + 'static,
+    F: FnOnce(&[u8]) -> T + Send + 'static,
+{
+    let _permit = PROCESSING_PERMITS
+        .acquire()
+        .await
+        .map_err(|e| BotError::Other(format!("Image processing unavailable: {}", e)))?;
+
+    tokio::task::spawn_blocking(move || work(&image_data))
+        .await
+        .map_err(|e| BotError::Other(format!("Image processing failed: {}", e)))
+}
+
+/// [`extract_dual_colors`] off the async runtime. Animated images use their
+/// first frame.
+pub async fn extract_dual_colors_limited(image_data: Vec<u8>) -> Result<(u32, u32), BotError> {
+    run_limited(image_data, extract_dual_colors).await?
+}
+
+/// [`extract_dominant_color`] off the async runtime
+pub async fn extract_dominant_color_limited(image_data: Vec<u8>) -> Result<u32, BotError> {
+    run_limited(image_data, extract_dominant_color).await?
+}
+
 pub async fn fetch_avatar(url: &str) -> Result<Vec<u8>, BotError> {
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(5))
@@ -45,9 +321,9 @@ pub fn extract_dominant_color(image_data: &[u8]) -> Result<u32, BotError> {
 
     let resized = resize_for_processing(&img);
 
-    let dominant = match find_dominant_by_kmeans(&resized, 5) {
-        Some(color) => color,
-        None => find_dominant_by_histogram(&resized),
+    let dominant = match find_clusters_by_kmeans(&resized, 5, 1).as_deref() {
+        Some([color, ..]) => *color,
+        _ => find_dominant_by_histogram(&resized),
     };
 
     Ok(dominant)
@@ -59,9 +335,9 @@ pub fn extract_dual_colors(image_data: &[u8]) -> Result<(u32, u32), BotError> {
 
     let resized = resize_for_processing(&img);
 
-    let dual_colors = match find_dual_colors_by_kmeans(&resized, 5) {
-        Some(colors) => colors,
-        None => {
+    let dual_colors = match find_clusters_by_kmeans(&resized, 5, 2).as_deref() {
+        Some([primary, secondary]) => (*primary, *secondary),
+        _ => {
             let single_color = find_dominant_by_histogram(&resized);
             (single_color, single_color)
         }
@@ -70,6 +346,33 @@ pub fn extract_dual_colors(image_data: &[u8]) -> Result<(u32, u32), BotError> {
     Ok(dual_colors)
 }
 
+/// Size of the two-color preview attached to booster role embeds
+pub const GRADIENT_WIDTH: u32 = 256;
+pub const GRADIENT_HEIGHT: u32 = 64;
+
+/// Render a left-to-right gradient from `primary` to `secondary` as a PNG
+pub fn render_gradient(primary: u32, secondary: u32) -> Result<Vec<u8>, BotError> {
+    let from = channels(primary);
+    let to = channels(secondary);
+    let last = (GRADIENT_WIDTH - 1) as f32;
+
+    let img = image::RgbImage::from_fn(GRADIENT_WIDTH, GRADIENT_HEIGHT, |x, _| {
+        let t = x as f32 / last;
+        image::Rgb(std::array::from_fn(|i| {
+            (from[i] as f32 + (to[i] as f32 - from[i] as f32) * t).round() as u8
+        }))
+    });
+
+    let mut png = std::io::Cursor::new(Vec::new());
+    img.write_to(&mut png, image::ImageOutputFormat::Png)
+        .map_err(|e| BotError::Other(format!("Failed to encode gradient: {}", e)))?;
+    Ok(png.into_inner())
+}
+
+fn channels(color: u32) -> [u8; 3] {
+    [(color >> 16) as u8, (color >> 8) as u8, color as u8]
+}
+
 fn resize_for_processing(img: &DynamicImage) -> DynamicImage {
     let (width, height) = img.dimensions();
 
@@ -85,9 +388,12 @@ fn resize_for_processing(img: &DynamicImage) -> DynamicImage {
     img.resize(new_width, new_height, image::imageops::FilterType::Lanczos3)
 }
 
-fn find_dominant_by_kmeans(img: &DynamicImage, k: usize) -> Option<u32> {
-    let pixels: Vec<Lab> = img
-        .pixels()
+/// Rounds of k-means refinement before the clusters are taken as final
+const KMEANS_ITERATIONS: usize = 20;
+
+/// Opaque pixels of `img` in Lab space, which clusters by perceived color
+fn opaque_lab_pixels(img: &DynamicImage) -> Vec<Lab> {
+    img.pixels()
         .filter_map(|(_, _, rgba)| {
             let rgba = rgba.0;
             if rgba[3] < 128 {
@@ -101,12 +407,19 @@ fn find_dominant_by_kmeans(img: &DynamicImage, k: usize) -> Option<u32> {
             );
             Some(Lab::from_color(rgb))
         })
-        .collect();
+        .collect()
+}
 
-    if pixels.is_empty() {
+/// Colors of the `top` largest non-empty clusters, biggest first. `None`
+/// when there are fewer opaque pixels than clusters, which leaves the
+/// histogram to pick the color instead.
+fn find_clusters_by_kmeans(img: &DynamicImage, k: usize, top: usize) -> Option<Vec<u32>> {
+    let pixels = opaque_lab_pixels(img);
+    if k == 0 || pixels.len() < k {
         return None;
     }
 
+    // Evenly spaced seeds; `pixels.len() >= k` keeps the step at least 1
     let mut centroids: Vec<Lab> = pixels
         .iter()
         .step_by(pixels.len() / k)
@@ -116,7 +429,7 @@ fn find_dominant_by_kmeans(img: &DynamicImage, k: usize) -> Option<u32> {
 
     let mut assignments = vec![0; pixels.len()];
 
-    for _ in 0..20 {
+    for _ in 0..KMEANS_ITERATIONS {
         for (i, pixel) in pixels.iter().enumerate() {
             let mut min_dist = f32::MAX;
             let mut closest = 0;
@@ -132,47 +445,47 @@ fn find_dominant_by_kmeans(img: &DynamicImage, k: usize) -> Option<u32> {
             assignments[i] = closest;
         }
 
-        let mut new_centroids = vec![Lab::new(0.0, 0.0, 0.0); k];
+        let mut sums: Vec<Lab> = vec![Lab::new(0.0, 0.0, 0.0); k];
         let mut counts = vec![0; k];
 
-        for (i, pixel) in pixels.iter().enumerate() {
-            let cluster = assignments[i];
-            new_centroids[cluster].l += pixel.l;
-            new_centroids[cluster].a += pixel.a;
-            new_centroids[cluster].b += pixel.b;
+        for (pixel, &cluster) in pixels.iter().zip(&assignments) {
+            sums[cluster].l += pixel.l;
+            sums[cluster].a += pixel.a;
+            sums[cluster].b += pixel.b;
             counts[cluster] += 1;
         }
 
-        for i in 0..k {
-            if counts[i] > 0 {
-                new_centroids[i].l /= counts[i] as f32;
-                new_centroids[i].a /= counts[i] as f32;
-                new_centroids[i].b /= counts[i] as f32;
+        // An empty cluster keeps its centroid rather than collapsing to black
+        for ((centroid, sum), &count) in centroids.iter_mut().zip(&sums).zip(&counts) {
+            if count > 0 {
+                *centroid = Lab::new(
+                    sum.l / count as f32,
+                    sum.a / count as f32,
+                    sum.b / count as f32,
+                );
             }
         }
-
-        centroids = new_centroids;
     }
 
-    let mut cluster_sizes = vec![0; k];
+    let mut cluster_sizes = vec![0usize; k];
     for &assignment in &assignments {
         cluster_sizes[assignment] += 1;
     }
 
-    let largest_cluster = cluster_sizes
-        .iter()
+    let mut clusters: Vec<(usize, usize)> = cluster_sizes
+        .into_iter()
         .enumerate()
-        .max_by_key(|(_, &size)| size)
-        .map(|(idx, _)| idx)?;
-
-    let dominant_lab = centroids[largest_cluster];
-    let dominant_rgb: Srgb = Srgb::from_color(dominant_lab);
-
-    let r = (dominant_rgb.red * 255.0) as u8;
-    let g = (dominant_rgb.green * 255.0) as u8;
-    let b = (dominant_rgb.blue * 255.0) as u8;
-
-    Some(((r as u32) << 16) | ((g as u32) << 8) | (b as u32))
+        .filter(|(_, size)| *size > 0)
+        .collect();
+    clusters.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+
+    Some(
+        clusters
+            .into_iter()
+            .take(top)
+            .map(|(idx, _)| convert_lab_to_rgb(centroids[idx]))
+            .collect(),
+    )
 }
 
 fn find_dominant_by_histogram(img: &DynamicImage) -> u32 {
@@ -200,113 +513,223 @@ fn find_dominant_by_histogram(img: &DynamicImage) -> u32 {
         .unwrap_or(0x5865F2)
 }
 
-fn find_dual_colors_by_kmeans(img: &DynamicImage, k: usize) -> Option<(u32, u32)> {
-    let pixels: Vec<Lab> = img
-        .pixels()
-        .filter_map(|(_, _, rgba)| {
-            let rgba = rgba.0;
-            if rgba[3] < 128 {
-                return None;
-            }
+fn convert_lab_to_rgb(lab: Lab) -> u32 {
+    let rgb: Srgb = Srgb::from_color(lab);
+    let r = (rgb.red * 255.0) as u8;
+    let g = (rgb.green * 255.0) as u8;
+    let b = (rgb.blue * 255.0) as u8;
 
-            let rgb = Srgb::new(
-                rgba[0] as f32 / 255.0,
-                rgba[1] as f32 / 255.0,
-                rgba[2] as f32 / 255.0,
-            );
-            Some(Lab::from_color(rgb))
-        })
-        .collect();
+    ((r as u32) << 16) | ((g as u32) << 8) | (b as u32)
+}
 
-    if pixels.is_empty() {
-        return None;
+fn color_distance(a: &Lab, b: &Lab) -> f32 {
+    let dl = a.l - b.l;
+    let da = a.a - b.a;
+    let db = a.b - b.b;
+    (dl * dl + da * da + db * db).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::gif::GifEncoder;
+    use image::{Delay, Frame, ImageOutputFormat, Rgba, RgbaImage};
+    use std::io::Cursor;
+
+    const RED: u32 = 0xFF0000;
+    const BLUE: u32 = 0x0000FF;
+
+    fn solid(color: u32) -> RgbaImage {
+        let pixel = Rgba([(color >> 16) as u8, (color >> 8) as u8, color as u8, 255]);
+        RgbaImage::from_pixel(32, 32, pixel)
     }
 
-    let mut centroids: Vec<Lab> = pixels
-        .iter()
-        .step_by(pixels.len() / k)
-        .take(k)
-        .cloned()
-        .collect();
+    /// Within the histogram fallback's 16-step quantization
+    fn close_to(actual: u32, expected: u32) -> bool {
+        [16, 8, 0].iter().all(|shift| {
+            let a = ((actual >> shift) & 0xFF) as i32;
+            let e = ((expected >> shift) & 0xFF) as i32;
+            (a - e).abs() < 16
+        })
+    }
 
-    let mut assignments = vec![0; pixels.len()];
+    #[test]
+    fn test_gradient_runs_from_primary_to_secondary() {
+        let png = render_gradient(RED, BLUE).unwrap();
+        let img = image::load_from_memory(&png).unwrap().to_rgba8();
 
-    for _ in 0..20 {
-        for (i, pixel) in pixels.iter().enumerate() {
-            let mut min_dist = f32::MAX;
-            let mut closest = 0;
+        assert_eq!(img.dimensions(), (GRADIENT_WIDTH, GRADIENT_HEIGHT));
+        assert_eq!(*img.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+        assert_eq!(*img.get_pixel(0, GRADIENT_HEIGHT - 1), Rgba([255, 0, 0, 255]));
+        assert_eq!(*img.get_pixel(GRADIENT_WIDTH - 1, 0), Rgba([0, 0, 255, 255]));
 
-            for (j, centroid) in centroids.iter().enumerate() {
-                let dist = color_distance(pixel, centroid);
-                if dist < min_dist {
-                    min_dist = dist;
-                    closest = j;
-                }
-            }
+        let middle = img.get_pixel(GRADIENT_WIDTH / 2, 0);
+        assert!(middle[0] > 100 && middle[2] > 100);
+    }
 
-            assignments[i] = closest;
-        }
+    #[test]
+    fn test_attachment_validation_accepts_images() {
+        assert!(validate_image_attachment(Some("image/png"), "art.png", 1024).is_ok());
+        assert!(validate_image_attachment(Some("image/jpeg; charset=binary"), "a", 1).is_ok());
+        assert!(validate_image_attachment(None, "art.WEBP", 1024).is_ok());
+        assert!(validate_image_attachment(Some("image/gif"), "anim.gif", MAX_ATTACHMENT_BYTES).is_ok());
+    }
 
-        let mut new_centroids = vec![Lab::new(0.0, 0.0, 0.0); k];
-        let mut counts = vec![0; k];
+    #[test]
+    fn test_attachment_validation_rejects_other_files_and_large_uploads() {
+        let err = validate_image_attachment(Some("video/mp4"), "clip.mp4", 1024).unwrap_err();
+        assert!(err.to_string().contains("video/mp4"));
 
-        for (i, pixel) in pixels.iter().enumerate() {
-            let cluster = assignments[i];
-            new_centroids[cluster].l += pixel.l;
-            new_centroids[cluster].a += pixel.a;
-            new_centroids[cluster].b += pixel.b;
-            counts[cluster] += 1;
-        }
+        // Metadata wins over a misleading extension
+        assert!(validate_image_attachment(Some("application/pdf"), "fake.png", 1024).is_err());
+        assert!(validate_image_attachment(None, "notes.txt", 10).is_err());
+        assert!(validate_image_attachment(None, "no_extension", 10).is_err());
 
-        for i in 0..k {
-            if counts[i] > 0 {
-                new_centroids[i].l /= counts[i] as f32;
-                new_centroids[i].a /= counts[i] as f32;
-                new_centroids[i].b /= counts[i] as f32;
+        let err = validate_image_attachment(Some("image/png"), "huge.png", MAX_ATTACHMENT_BYTES + 1)
+            .unwrap_err();
+        assert!(err.to_string().contains("too large"));
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_extracts_colors_from_png() {
+        let mut image = solid(RED);
+        for (x, _, pixel) in image.enumerate_pixels_mut() {
+            if x >= 20 {
+                *pixel = Rgba([0, 0, 255, 255]);
             }
         }
+        let mut png = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut png), ImageOutputFormat::Png)
+            .unwrap();
+
+        let (primary, secondary) = extract_dual_colors_limited(png).await.unwrap();
+        assert!(close_to(primary, RED), "primary #{:06X}", primary);
+        assert!(close_to(secondary, BLUE), "secondary #{:06X}", secondary);
+    }
 
-        centroids = new_centroids;
+    #[tokio::test]
+    async fn test_animated_gif_uses_first_frame() {
+        let mut gif = Vec::new();
+        {
+            let mut encoder = GifEncoder::new(&mut gif);
+            encoder
+                .encode_frames(vec![
+                    Frame::from_parts(solid(RED), 0, 0, Delay::from_numer_denom_ms(100, 1)),
+                    Frame::from_parts(solid(BLUE), 0, 0, Delay::from_numer_denom_ms(100, 1)),
+                ])
+                .unwrap();
+        }
+
+        let (primary, _) = extract_dual_colors_limited(gif).await.unwrap();
+        assert!(close_to(primary, RED), "primary #{:06X}", primary);
     }
 
-    let mut cluster_sizes = vec![0; k];
-    for &assignment in &assignments {
-        cluster_sizes[assignment] += 1;
+    fn encode_png(image: &RgbaImage) -> Vec<u8> {
+        let mut png = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut png), ImageOutputFormat::Png)
+            .unwrap();
+        png
     }
 
-    let mut cluster_size_indices: Vec<(usize, usize)> = cluster_sizes
-        .iter()
-        .enumerate()
-        .map(|(idx, &size)| (idx, size))
-        .collect();
+    #[tokio::test]
+    async fn test_single_pixel_image_falls_back_to_histogram() {
+        let png = encode_png(&RgbaImage::from_pixel(1, 1, Rgba([255, 0, 0, 255])));
 
-    cluster_size_indices.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+        let (primary, secondary) = extract_dual_colors_limited(png.clone()).await.unwrap();
+        assert!(close_to(primary, RED), "primary #{:06X}", primary);
+        assert_eq!(primary, secondary);
 
-    if cluster_size_indices.len() < 2 || cluster_size_indices[1].1 == 0 {
-        return None;
+        let dominant = extract_dominant_color_limited(png).await.unwrap();
+        assert!(close_to(dominant, RED), "dominant #{:06X}", dominant);
     }
 
-    let primary_idx = cluster_size_indices[0].0;
-    let secondary_idx = cluster_size_indices[1].0;
+    #[tokio::test]
+    async fn test_fully_transparent_image_uses_default_color() {
+        let png = encode_png(&RgbaImage::from_pixel(16, 16, Rgba([255, 0, 0, 0])));
 
-    let primary_color = convert_lab_to_rgb(centroids[primary_idx]);
-    let secondary_color = convert_lab_to_rgb(centroids[secondary_idx]);
+        assert_eq!(
+            extract_dual_colors_limited(png.clone()).await.unwrap(),
+            (0x5865F2, 0x5865F2)
+        );
+        assert_eq!(extract_dominant_color_limited(png).await.unwrap(), 0x5865F2);
+    }
 
-    Some((primary_color, secondary_color))
-}
+    #[test]
+    fn test_two_color_image_splits_into_both_colors() {
+        let mut image = solid(BLUE);
+        for (x, _, pixel) in image.enumerate_pixels_mut() {
+            if x < 24 {
+                *pixel = Rgba([255, 0, 0, 255]);
+            }
+        }
+        let png = encode_png(&image);
 
-fn convert_lab_to_rgb(lab: Lab) -> u32 {
-    let rgb: Srgb = Srgb::from_color(lab);
-    let r = (rgb.red * 255.0) as u8;
-    let g = (rgb.green * 255.0) as u8;
-    let b = (rgb.blue * 255.0) as u8;
+        let (primary, secondary) = extract_dual_colors(&png).unwrap();
+        assert!(close_to(primary, RED), "primary #{:06X}", primary);
+        assert!(close_to(secondary, BLUE), "secondary #{:06X}", secondary);
 
-    ((r as u32) << 16) | ((g as u32) << 8) | (b as u32)
-}
+        let dominant = extract_dominant_color(&png).unwrap();
+        assert!(close_to(dominant, RED), "dominant #{:06X}", dominant);
 
-fn color_distance(a: &Lab, b: &Lab) -> f32 {
-    let dl = a.l - b.l;
-    let da = a.a - b.a;
-    let db = a.b - b.b;
-    (dl * dl + da * da + db * db).sqrt()
+        // Fewer opaque pixels than clusters is never a zero step
+        let tiny = DynamicImage::ImageRgba8(RgbaImage::from_fn(2, 1, |x, _| {
+            if x == 0 {
+                Rgba([255, 0, 0, 255])
+            } else {
+                Rgba([0, 0, 255, 255])
+            }
+        }));
+        assert_eq!(find_clusters_by_kmeans(&tiny, 5, 2), None);
+        assert_eq!(find_clusters_by_kmeans(&tiny, 2, 2).map(|c| c.len()), Some(2));
+    }
+
+    #[test]
+    fn test_only_public_addresses_are_fetchable() {
+        for blocked in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:10.0.0.1",
+        ] {
+            assert!(!is_public_ip(blocked.parse().unwrap()), "{}", blocked);
+        }
+        for allowed in ["93.184.216.34", "1.1.1.1", "2606:4700::1111"] {
+            assert!(is_public_ip(allowed.parse().unwrap()), "{}", allowed);
+        }
+    }
+
+    #[test]
+    fn test_image_links_need_http_and_a_host() {
+        assert!(parse_image_url("https://example.com/cat.png").is_ok());
+        assert!(parse_image_url(" http://example.com/cat.png ").is_ok());
+        assert!(parse_image_url("file:///etc/passwd").is_err());
+        assert!(parse_image_url("ftp://example.com/cat.png").is_err());
+        assert!(parse_image_url("not a link").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_private_hosts_are_refused_before_fetching() {
+        for link in [
+            "http://127.0.0.1:9/cat.png",
+            "http://[::1]:9/cat.png",
+            "http://localhost:9/cat.png",
+        ] {
+            let url = parse_image_url(link).unwrap();
+            let error = fetch_remote_image(&url, MAX_ATTACHMENT_BYTES)
+                .await
+                .unwrap_err()
+                .to_string();
+            assert!(error.contains("private or reserved"), "{}: {}", link, error);
+        }
+    }
 }
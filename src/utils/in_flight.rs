@@ -0,0 +1,96 @@
+use serenity::all::{GuildId, UserId};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+type Active = Arc<Mutex<HashSet<(u64, u64)>>>;
+
+/// Members with a booster role change underway, so a second invocation
+/// (say a prefix and a slash command fired together) can't create a
+/// duplicate role before the first one's record is saved
+#[derive(Debug, Clone, Default)]
+pub struct InFlightRoleChanges {
+    active: Active,
+}
+
+/// Held for the length of one role change; dropping it lets the member
+/// run the next one
+#[derive(Debug)]
+pub struct InFlightGuard {
+    active: Active,
+    key: (u64, u64),
+}
+
+impl InFlightRoleChanges {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Claim the member's slot, or `None` if a change is already running
+    pub fn try_begin(&self, guild_id: GuildId, user_id: UserId) -> Option<InFlightGuard> {
+        let key = (guild_id.get(), user_id.get());
+        let mut active = self.active.lock().unwrap_or_else(|e| e.into_inner());
+        active.insert(key).then(|| InFlightGuard {
+            active: self.active.clone(),
+            key,
+        })
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        let mut active = self.active.lock().unwrap_or_else(|e| e.into_inner());
+        active.remove(&self.key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_concurrent_role_creation_runs_once() {
+        let changes = InFlightRoleChanges::new();
+        let created = Arc::new(AtomicUsize::new(0));
+        let (guild, user) = (GuildId::new(1), UserId::new(2));
+
+        let attempts: Vec<_> = (0..2)
+            .map(|_| {
+                let changes = changes.clone();
+                let created = created.clone();
+                tokio::spawn(async move {
+                    let Some(_guard) = changes.try_begin(guild, user) else {
+                        return false;
+                    };
+                    // Stand-in for the role creation and database upsert
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    created.fetch_add(1, Ordering::SeqCst);
+                    true
+                })
+            })
+            .collect();
+
+        let mut proceeded = 0;
+        for attempt in attempts {
+            if attempt.await.unwrap() {
+                proceeded += 1;
+            }
+        }
+        assert_eq!(proceeded, 1);
+        assert_eq!(created.load(Ordering::SeqCst), 1);
+
+        // The slot is free again once the first flow finished
+        assert!(changes.try_begin(guild, user).is_some());
+    }
+
+    #[test]
+    fn test_other_members_are_not_blocked() {
+        let changes = InFlightRoleChanges::new();
+        let _first = changes.try_begin(GuildId::new(1), UserId::new(2)).unwrap();
+
+        assert!(changes.try_begin(GuildId::new(1), UserId::new(2)).is_none());
+        assert!(changes.try_begin(GuildId::new(1), UserId::new(3)).is_some());
+        assert!(changes.try_begin(GuildId::new(4), UserId::new(2)).is_some());
+    }
+}
@@ -0,0 +1,256 @@
+use serenity::all::{GuildId, Http, InviteCreateEvent, RichInvite, UserId};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// An invite's use count when it was last seen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedInvite {
+    pub code: String,
+    pub inviter: Option<UserId>,
+    pub uses: u64,
+    /// 0 means unlimited
+    pub max_uses: u64,
+}
+
+impl From<&RichInvite> for TrackedInvite {
+    fn from(invite: &RichInvite) -> Self {
+        Self {
+            code: invite.code.clone(),
+            inviter: invite.inviter.as_ref().map(|u| u.id),
+            uses: invite.uses,
+            max_uses: u64::from(invite.max_uses),
+        }
+    }
+}
+
+impl From<&InviteCreateEvent> for TrackedInvite {
+    fn from(invite: &InviteCreateEvent) -> Self {
+        Self {
+            code: invite.code.clone(),
+            inviter: invite.inviter.as_ref().map(|u| u.id),
+            uses: invite.uses,
+            max_uses: u64::from(invite.max_uses),
+        }
+    }
+}
+
+/// Which invite brought a member in, as far as the use counts can tell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InviteAttribution {
+    Invite {
+        code: String,
+        inviter: Option<UserId>,
+        uses: u64,
+    },
+    /// Vanity URL, missing Manage Server, or more than one invite moved
+    Unknown,
+}
+
+impl InviteAttribution {
+    /// Text for the "Invited by" field of a join log
+    pub fn describe(&self) -> String {
+        match self {
+            InviteAttribution::Invite {
+                code,
+                inviter: Some(inviter),
+                uses,
+            } => format!("<@{}> (code `{}`, {})", inviter, code, plural_uses(*uses)),
+            InviteAttribution::Invite {
+                code,
+                inviter: None,
+                uses,
+            } => format!("Code `{}` ({})", code, plural_uses(*uses)),
+            InviteAttribution::Unknown => "Unknown".to_string(),
+        }
+    }
+}
+
+fn plural_uses(uses: u64) -> String {
+    format!("{} use{}", uses, if uses == 1 { "" } else { "s" })
+}
+
+/// Work out which invite a member joined with by comparing use counts from
+/// before and after the join. A limited invite is deleted on its last use, so
+/// one that disappeared a single use short of its limit counts as used too.
+/// Anything other than exactly one candidate is ambiguous.
+pub fn find_used_invite(
+    before: &HashMap<String, TrackedInvite>,
+    after: &[TrackedInvite],
+) -> InviteAttribution {
+    let mut candidates: Vec<InviteAttribution> = after
+        .iter()
+        .filter(|invite| {
+            let previous = before.get(&invite.code).map_or(0, |b| b.uses);
+            invite.uses > previous
+        })
+        .map(|invite| InviteAttribution::Invite {
+            code: invite.code.clone(),
+            inviter: invite.inviter,
+            uses: invite.uses,
+        })
+        .collect();
+
+    candidates.extend(
+        before
+            .values()
+            .filter(|old| {
+                old.max_uses > 0
+                    && old.uses + 1 == old.max_uses
+                    && !after.iter().any(|invite| invite.code == old.code)
+            })
+            .map(|old| InviteAttribution::Invite {
+                code: old.code.clone(),
+                inviter: old.inviter,
+                uses: old.max_uses,
+            }),
+    );
+
+    if candidates.len() == 1 {
+        candidates.remove(0)
+    } else {
+        InviteAttribution::Unknown
+    }
+}
+
+/// Cached invite use counts per guild, used to attribute joins. Listing
+/// invites needs Manage Server, so guilds where that fails aren't tracked.
+#[derive(Debug, Clone, Default)]
+pub struct InviteTracker {
+    guilds: Arc<RwLock<HashMap<GuildId, HashMap<String, TrackedInvite>>>>,
+}
+
+impl InviteTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fetch and cache the guild's current invites
+    pub async fn refresh(&self, http: &Http, guild_id: GuildId) {
+        match guild_id.invites(http).await {
+            Ok(invites) => {
+                let invites = invites.iter().map(TrackedInvite::from).collect::<Vec<_>>();
+                self.guilds.write().await.insert(guild_id, by_code(invites));
+            }
+            Err(e) => {
+                tracing::debug!(
+                    guild_id = %guild_id,
+                    error = %e,
+                    "Can't list invites, join attribution disabled"
+                );
+                self.forget(guild_id).await;
+            }
+        }
+    }
+
+    pub async fn invite_created(&self, guild_id: GuildId, invite: TrackedInvite) {
+        if let Some(invites) = self.guilds.write().await.get_mut(&guild_id) {
+            invites.insert(invite.code.clone(), invite);
+        }
+    }
+
+    pub async fn invite_deleted(&self, guild_id: GuildId, code: &str) {
+        if let Some(invites) = self.guilds.write().await.get_mut(&guild_id) {
+            invites.remove(code);
+        }
+    }
+
+    /// Find the invite a new member used and cache the guild's new counts
+    pub async fn attribute_join(&self, http: &Http, guild_id: GuildId) -> InviteAttribution {
+        if !self.guilds.read().await.contains_key(&guild_id) {
+            return InviteAttribution::Unknown;
+        }
+
+        let after = match guild_id.invites(http).await {
+            Ok(invites) => invites.iter().map(TrackedInvite::from).collect::<Vec<_>>(),
+            Err(e) => {
+                tracing::debug!(guild_id = %guild_id, error = %e, "Failed to refresh invites");
+                return InviteAttribution::Unknown;
+            }
+        };
+
+        let attribution = match self.guilds.read().await.get(&guild_id) {
+            Some(before) => find_used_invite(before, &after),
+            None => InviteAttribution::Unknown,
+        };
+        self.guilds.write().await.insert(guild_id, by_code(after));
+
+        attribution
+    }
+
+    pub async fn forget(&self, guild_id: GuildId) {
+        self.guilds.write().await.remove(&guild_id);
+    }
+}
+
+fn by_code(invites: Vec<TrackedInvite>) -> HashMap<String, TrackedInvite> {
+    invites
+        .into_iter()
+        .map(|invite| (invite.code.clone(), invite))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invite(code: &str, uses: u64, max_uses: u64) -> TrackedInvite {
+        TrackedInvite {
+            code: code.to_string(),
+            inviter: Some(UserId::new(7)),
+            uses,
+            max_uses,
+        }
+    }
+
+    #[test]
+    fn test_single_increment_is_attributed() {
+        let before = by_code(vec![invite("abc", 11, 0), invite("xyz", 3, 0)]);
+        let after = [invite("abc", 12, 0), invite("xyz", 3, 0)];
+
+        let attribution = find_used_invite(&before, &after);
+        assert_eq!(
+            attribution,
+            InviteAttribution::Invite {
+                code: "abc".to_string(),
+                inviter: Some(UserId::new(7)),
+                uses: 12,
+            }
+        );
+        assert_eq!(attribution.describe(), "<@7> (code `abc`, 12 uses)");
+    }
+
+    #[test]
+    fn test_exhausted_invite_that_vanished_is_attributed() {
+        let before = by_code(vec![invite("once", 0, 1), invite("abc", 4, 0)]);
+        let after = [invite("abc", 4, 0)];
+
+        assert_eq!(
+            find_used_invite(&before, &after).describe(),
+            "<@7> (code `once`, 1 use)"
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_or_missing_changes_are_unknown() {
+        let before = by_code(vec![invite("abc", 1, 0), invite("xyz", 1, 0)]);
+
+        // Vanity URL joins don't move any invite
+        let unchanged = [invite("abc", 1, 0), invite("xyz", 1, 0)];
+        assert_eq!(
+            find_used_invite(&before, &unchanged),
+            InviteAttribution::Unknown
+        );
+
+        // Two joins landed between refreshes
+        let both = [invite("abc", 2, 0), invite("xyz", 2, 0)];
+        assert_eq!(find_used_invite(&before, &both), InviteAttribution::Unknown);
+
+        // A manually revoked unlimited invite isn't a use
+        let revoked = [invite("abc", 1, 0)];
+        assert_eq!(
+            find_used_invite(&before, &revoked),
+            InviteAttribution::Unknown
+        );
+    }
+}
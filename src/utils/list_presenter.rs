@@ -0,0 +1,440 @@
+use crate::utils::embed_builder::DescriptionBuilder;
+
+/// How list-style embeds are laid out for a user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ListLayout {
+    /// Multi-line entries with full details
+    #[default]
+    Rich,
+    /// One line per entry, for narrow mobile screens
+    Compact,
+}
+
+impl ListLayout {
+    pub fn from_compact(compact: bool) -> Self {
+        if compact {
+            Self::Compact
+        } else {
+            Self::Rich
+        }
+    }
+
+    pub fn page_size(self) -> usize {
+        match self {
+            Self::Rich => 10,
+            Self::Compact => 25,
+        }
+    }
+
+    /// Pages needed for `len` entries; an empty list still has one page
+    pub fn page_count(self, len: usize) -> usize {
+        len.div_ceil(self.page_size()).max(1)
+    }
+}
+
+/// One booster role as shown by `/boosterrole list`
+#[derive(Debug, Clone)]
+pub struct RoleListEntry {
+    pub role_id: i64,
+    pub owner_id: i64,
+    pub color: String,
+    /// Unix timestamp of role creation, when known
+    pub created_at: Option<i64>,
+    pub lock_label: Option<&'static str>,
+    /// Emoji or image URL the role icon was set from
+    pub icon: Option<String>,
+    /// Admin who linked an existing role to the owner, for linked roles
+    pub linked_by: Option<i64>,
+}
+
+impl RoleListEntry {
+    /// Unicode emoji icons are shown as-is, images as a picture marker
+    fn icon_marker(&self) -> Option<&str> {
+        self.icon.as_deref().map(|icon| {
+            if icon.starts_with("http") {
+                "🖼️"
+            } else {
+                icon
+            }
+        })
+    }
+}
+
+/// One booster role and its recipients as shown by `/boosterrole share list`
+#[derive(Debug, Clone)]
+pub struct ShareListEntry {
+    pub role_name: String,
+    pub owner_id: i64,
+    pub recipients: Vec<ShareRecipient>,
+}
+
+/// A member a role is shared with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShareRecipient {
+    pub user_id: i64,
+    /// Unix timestamp the share ends at, if it was given for a duration
+    pub expires_at: Option<i64>,
+}
+
+impl ShareRecipient {
+    fn mention(&self) -> String {
+        match self.expires_at {
+            Some(at) => format!("<@{}> (expires <t:{}:R>)", self.user_id, at),
+            None => format!("<@{}>", self.user_id),
+        }
+    }
+}
+
+/// Rendered list page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListPage {
+    pub description: String,
+    pub footer: String,
+}
+
+/// Nearest colored square emoji for a `#RRGGBB` color
+pub fn color_swatch(hex: &str) -> &'static str {
+    let hex = hex.trim_start_matches('#');
+    let Ok(value) = u32::from_str_radix(hex, 16) else {
+        return "⬜";
+    };
+
+    let (r, g, b) = (
+        ((value >> 16) & 0xFF) as i32,
+        ((value >> 8) & 0xFF) as i32,
+        (value & 0xFF) as i32,
+    );
+
+    const SWATCHES: [(&str, (i32, i32, i32)); 9] = [
+        ("🟥", (221, 46, 68)),
+        ("🟧", (244, 144, 12)),
+        ("🟨", (253, 203, 88)),
+        ("🟩", (120, 177, 89)),
+        ("🟦", (85, 172, 238)),
+        ("🟪", (170, 142, 214)),
+        ("🟫", (193, 105, 79)),
+        ("⬛", (49, 55, 61)),
+        ("⬜", (230, 231, 232)),
+    ];
+
+    SWATCHES
+        .iter()
+        .min_by_key(|(_, (sr, sg, sb))| (r - sr).pow(2) + (g - sg).pow(2) + (b - sb).pow(2))
+        .map(|(emoji, _)| *emoji)
+        .unwrap_or("⬜")
+}
+
+/// Render one page of booster roles; `page` is 1-based
+pub fn render_role_list(
+    entries: &[RoleListEntry],
+    page: usize,
+    layout: ListLayout,
+    requested_by: &str,
+) -> ListPage {
+    let page_size = layout.page_size();
+    let total_pages = layout.page_count(entries.len());
+    let page = page.clamp(1, total_pages);
+    let start = (page - 1) * page_size;
+
+    let lines: Vec<String> = entries
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(page_size)
+        .map(|(i, entry)| match layout {
+            ListLayout::Rich => {
+                let created_at = entry
+                    .created_at
+                    .map(|ts| format!("<t:{}:R>", ts))
+                    .unwrap_or_else(|| "Unknown".to_string());
+                let lock_text = entry
+                    .lock_label
+                    .map(|label| format!(" • 🔒 {}", label))
+                    .unwrap_or_default();
+                let icon_text = entry
+                    .icon_marker()
+                    .map(|marker| format!(" • Icon: {}", marker))
+                    .unwrap_or_default();
+                let link_text = entry
+                    .linked_by
+                    .map(|admin| format!(" • 🔗 linked by <@{}>", admin))
+                    .unwrap_or_default();
+                format!(
+                    "**{}. <@&{}>** by <@{}>\n└ Color: `{}` • Created: {}{}{}{}",
+                    i + 1,
+                    entry.role_id,
+                    entry.owner_id,
+                    entry.color,
+                    created_at,
+                    icon_text,
+                    lock_text,
+                    link_text
+                )
+            }
+            ListLayout::Compact => format!(
+                "{} <@&{}> · <@{}>{}{}{}",
+                color_swatch(&entry.color),
+                entry.role_id,
+                entry.owner_id,
+                entry
+                    .icon_marker()
+                    .map(|marker| format!(" {}", marker))
+                    .unwrap_or_default(),
+                if entry.lock_label.is_some() {
+                    " 🔒"
+                } else {
+                    ""
+                },
+                if entry.linked_by.is_some() {
+                    " 🔗"
+                } else {
+                    ""
+                }
+            ),
+        })
+        .collect();
+
+    let (description, footer) = match layout {
+        ListLayout::Rich => (
+            format!(
+                "**All booster roles ({} total):**\n\n{}",
+                entries.len(),
+                lines.join("\n\n")
+            ),
+            format!(
+                "Page {} of {} • Requested by {}",
+                page, total_pages, requested_by
+            ),
+        ),
+        ListLayout::Compact => (
+            format!("**{} roles**\n{}", entries.len(), lines.join("\n")),
+            format!("{}/{}", page, total_pages),
+        ),
+    };
+
+    ListPage {
+        description,
+        footer,
+    }
+}
+
+/// Render every page of the share overview. Pages hold up to the layout's
+/// page size but start early rather than overflow Discord's limits.
+pub fn render_share_list(entries: &[ShareListEntry], layout: ListLayout) -> Vec<ListPage> {
+    let builder = DescriptionBuilder::new().max_lines(layout.page_size());
+
+    let builder = match layout {
+        ListLayout::Rich => builder
+            .header("👥 **Booster Role Shares**")
+            .separator("\n\n")
+            .lines(entries.iter().map(|entry| {
+                let mut block = format!("**{}**\nOwner: <@{}>\n", entry.role_name, entry.owner_id);
+                if entry.recipients.is_empty() {
+                    block.push_str("No shares");
+                } else {
+                    block.push_str("Shared with: ");
+                    for recipient in entry.recipients.iter().take(5) {
+                        block.push_str(&format!("{} ", recipient.mention()));
+                    }
+                    if entry.recipients.len() > 5 {
+                        block.push_str(&format!("... and {} more", entry.recipients.len() - 5));
+                    }
+                }
+                block.trim_end().to_string()
+            })),
+        ListLayout::Compact => builder.lines(entries.iter().map(|entry| {
+            let recipients = match entry.recipients.len() {
+                0 => "—".to_string(),
+                1..=3 => entry
+                    .recipients
+                    .iter()
+                    .map(ShareRecipient::mention)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                n => format!("{} members", n),
+            };
+            format!(
+                "**{}** · <@{}> → {}",
+                truncate(&entry.role_name, 32),
+                entry.owner_id,
+                recipients
+            )
+        })),
+    };
+
+    let pages = builder.pages();
+    let total_pages = pages.len();
+    pages
+        .into_iter()
+        .enumerate()
+        .map(|(i, description)| ListPage {
+            description,
+            footer: format!(
+                "Page {} of {} • {} booster roles",
+                i + 1,
+                total_pages,
+                entries.len()
+            ),
+        })
+        .collect()
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        let mut truncated: String = text.chars().take(max_chars - 1).collect();
+        truncated.push('…');
+        truncated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::embed_builder::MAX_DESCRIPTION_CHARS;
+
+    // Snowflakes at their longest realistic length
+    const ROLE_BASE: i64 = 1_234_567_890_123_456_000;
+    const USER_BASE: i64 = 1_987_654_321_098_765_000;
+
+    fn role_entries(count: usize) -> Vec<RoleListEntry> {
+        (0..count)
+            .map(|i| RoleListEntry {
+                role_id: ROLE_BASE + i as i64,
+                owner_id: USER_BASE + i as i64,
+                color: "#FF5733".to_string(),
+                created_at: Some(1_700_000_000),
+                lock_label: (i % 2 == 0).then_some("Name & color locked"),
+                icon: (i % 3 == 0).then(|| "https://cdn.discordapp.com/emojis/1.png".to_string()),
+                linked_by: (i % 5 == 0).then_some(USER_BASE + 999),
+            })
+            .collect()
+    }
+
+    fn share_entries(count: usize) -> Vec<ShareListEntry> {
+        (0..count)
+            .map(|i| ShareListEntry {
+                role_name: "A Rather Long Booster Role Name For Testing".to_string(),
+                owner_id: USER_BASE + i as i64,
+                recipients: (0..i % 6)
+                    .map(|r| ShareRecipient {
+                        user_id: USER_BASE + 100 + r as i64,
+                        expires_at: (r % 2 == 1).then_some(1_900_000_000),
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compact_pages_are_larger() {
+        assert!(ListLayout::Compact.page_size() > ListLayout::Rich.page_size());
+        assert_eq!(ListLayout::from_compact(true), ListLayout::Compact);
+        assert_eq!(ListLayout::default(), ListLayout::Rich);
+    }
+
+    #[test]
+    fn test_compact_role_list_within_limits() {
+        let entries = role_entries(100);
+        let page = render_role_list(&entries, 1, ListLayout::Compact, "someone");
+
+        assert!(page.description.chars().count() <= MAX_DESCRIPTION_CHARS);
+        assert_eq!(
+            page.description.lines().count(),
+            ListLayout::Compact.page_size() + 1
+        );
+        assert!(page.description.lines().skip(1).all(|l| !l.contains('\n')));
+        assert_eq!(page.footer, "1/4");
+    }
+
+    #[test]
+    fn test_layouts_carry_same_essentials() {
+        let entries = role_entries(10);
+        let rich = render_role_list(&entries, 1, ListLayout::Rich, "someone");
+        let compact = render_role_list(&entries, 1, ListLayout::Compact, "someone");
+
+        assert!(rich.footer.contains("Requested by someone"));
+        assert!(!compact.footer.contains("Requested by"));
+
+        for entry in &entries {
+            let role = format!("<@&{}>", entry.role_id);
+            let owner = format!("<@{}>", entry.owner_id);
+            assert!(rich.description.contains(&role) && rich.description.contains(&owner));
+            assert!(compact.description.contains(&role) && compact.description.contains(&owner));
+        }
+        assert_eq!(
+            rich.description.matches("🔒").count(),
+            compact.description.matches("🔒").count()
+        );
+        assert_eq!(
+            rich.description.matches("🖼️").count(),
+            compact.description.matches("🖼️").count()
+        );
+        assert_eq!(rich.description.matches("Icon: 🖼️").count(), 4);
+        assert_eq!(rich.description.matches("🔗 linked by").count(), 2);
+        assert_eq!(compact.description.matches("🔗").count(), 2);
+    }
+
+    #[test]
+    fn test_page_is_clamped() {
+        let entries = role_entries(3);
+        let page = render_role_list(&entries, 9, ListLayout::Rich, "someone");
+        assert_eq!(page.footer, "Page 1 of 1 • Requested by someone");
+    }
+
+    #[test]
+    fn test_compact_share_list_within_limits_and_complete() {
+        let entries = share_entries(40);
+        let compact = render_share_list(&entries, ListLayout::Compact);
+        let rich = render_share_list(&entries, ListLayout::Rich);
+
+        assert!(compact[0].description.chars().count() <= MAX_DESCRIPTION_CHARS);
+        assert_eq!(compact[0].footer, "Page 1 of 2 • 40 booster roles");
+        assert_eq!(rich[0].footer, "Page 1 of 4 • 40 booster roles");
+
+        for entry in entries.iter().take(ListLayout::Rich.page_size()) {
+            let owner = format!("<@{}>", entry.owner_id);
+            assert!(rich[0].description.contains(&owner) && compact[0].description.contains(&owner));
+        }
+        assert!(rich[0].description.contains("(expires <t:1900000000:R>)"));
+
+        // Later pages pick up where the previous one stopped
+        let last = &rich[3];
+        assert!(last
+            .description
+            .contains(&format!("<@{}>", entries[39].owner_id)));
+        assert!(!last
+            .description
+            .contains(&format!("<@{}>", entries[0].owner_id)));
+    }
+
+    #[test]
+    fn test_share_list_with_huge_role_names_splits_early() {
+        let mut entries = share_entries(10);
+        for entry in &mut entries {
+            entry.role_name = "N".repeat(1000);
+        }
+
+        let pages = render_share_list(&entries, ListLayout::Rich);
+        assert!(pages.len() > 1);
+        assert!(pages
+            .iter()
+            .all(|page| page.description.chars().count() <= MAX_DESCRIPTION_CHARS));
+        assert_eq!(
+            pages
+                .iter()
+                .map(|page| page.description.matches("Owner: ").count())
+                .sum::<usize>(),
+            10
+        );
+    }
+
+    #[test]
+    fn test_color_swatch() {
+        assert_eq!(color_swatch("#FF0000"), "🟥");
+        assert_eq!(color_swatch("#0000FF"), "🟦");
+        assert_eq!(color_swatch("#000000"), "⬛");
+        assert_eq!(color_swatch("not a color"), "⬜");
+    }
+}
@@ -1,27 +1,93 @@
 pub mod color_generator;
+pub mod color_rotation;
+pub mod announcements;
+pub mod args;
+pub mod audit;
+pub mod boost_states;
+pub mod buffered_counter;
 pub mod color_parser;
+pub mod command_channels;
+pub mod command_cooldowns;
+pub mod command_usage;
+pub mod confirm;
+pub mod config_diff;
 pub mod content_filter;
+pub mod disabled_commands;
+pub mod eligibility;
 pub mod embed_builder;
+pub mod embed_fallback;
+pub mod embed_theme;
 pub mod error;
+pub mod experiments;
+pub mod failure_streak;
+pub mod guild_availability;
+pub mod history_export;
+pub mod i18n;
 pub mod image_processor;
+pub mod in_flight;
+pub mod invite_tracker;
+pub mod list_presenter;
 pub mod moderation;
+pub mod name_severity;
+pub mod next_steps;
+pub mod nickname_template;
+pub mod paginator;
 pub mod performance;
+pub mod progress;
+pub mod permission_audit;
+pub mod prefix_cache;
+pub mod prefix_rules;
+pub mod premium_role;
+pub mod process_stats;
+pub mod query_metrics;
+pub mod quiet_hours;
+pub mod rename_cooldown;
 pub mod response;
+pub mod retry;
+pub mod retention;
+pub mod role_adoption;
+pub mod role_archive;
+pub mod role_export;
+pub mod role_icon;
+pub mod role_lock;
 pub mod role_manager;
+pub mod role_name;
+pub mod role_positions;
+pub mod rules_ack;
+pub mod scheduled_change;
 pub mod settings_error;
 pub mod settings_rate_limiter;
+pub mod share_revalidation;
+pub mod shutdown;
+pub mod spotlight;
+pub mod table_growth;
+pub mod usage_example;
 
+pub use boost_states::BoostStateCache;
 pub use color_generator::ColorGenerator;
 pub use color_parser::ColorParser;
+pub use command_usage::CommandUsageCounters;
 pub use embed_builder::{EmbedBuilder, EmbedColor};
+pub use eligibility::{is_eligible, load_eligibility_config};
+pub use embed_fallback::EmbedPermissionCache;
 pub use error::{BotError, BotResult};
+pub use experiments::ExperimentCounters;
+pub use failure_streak::FailureTracker;
+pub use guild_availability::GuildAvailability;
+pub use in_flight::InFlightRoleChanges;
+pub use invite_tracker::InviteTracker;
 #[allow(unused_imports)] // Re-exports for later moderation command suites
 pub use moderation::{
     case_created_embed, case_view_embed, load_staff_role_ids, member_is_staff,
     moderation_error_embed, moderation_warning_embed, normalize_reason, prepare_reason,
     require_guild_staff, validate_reason, ModerationError, MAX_REASON_LEN,
 };
+pub use next_steps::CommandRegistry;
+pub use performance::PerformanceTracker;
+pub use permission_audit::{record_check_failure, CheckFailure, PermissionFailureLog};
+pub use prefix_cache::PrefixCache;
 pub use response::ResponseHelper;
+pub use role_lock::{check_lock, LockFlags, RequestedChange};
 pub use role_manager::RoleManager;
 pub use settings_error::SettingsError;
 pub use settings_rate_limiter::SettingsRateLimiter;
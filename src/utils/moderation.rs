@@ -325,7 +325,7 @@ mod tests {
             duration_seconds: None,
             active: 1,
             related_case_id: None,
-            created_at: Some("2026-01-01".into()),
+            created_at: Some(chrono::Utc::now()),
             updated_at: None,
         };
 
@@ -0,0 +1,402 @@
+use regex::{Regex, RegexBuilder};
+
+/// How serious a blacklisted word is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, poise::ChoiceParameter)]
+pub enum Severity {
+    #[name = "low"]
+    Low,
+    #[default]
+    #[name = "medium"]
+    Medium,
+    #[name = "high"]
+    High,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+        }
+    }
+
+    /// Unknown values fall back to the default so old rows keep rejecting
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "low" => Severity::Low,
+            "high" => Severity::High,
+            _ => Severity::Medium,
+        }
+    }
+}
+
+/// What happens when a role name matches a word of some severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum FilterAction {
+    /// Allow the name, but caution the member and record the attempt
+    #[name = "warn"]
+    Warn,
+    #[name = "reject"]
+    Reject,
+    /// Reject and post the attempt to the staff channel right away
+    #[name = "reject and alert staff"]
+    Alert,
+}
+
+impl FilterAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilterAction::Warn => "warn",
+            FilterAction::Reject => "reject",
+            FilterAction::Alert => "alert",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "warn" => Some(FilterAction::Warn),
+            "reject" => Some(FilterAction::Reject),
+            "alert" => Some(FilterAction::Alert),
+            _ => None,
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            FilterAction::Warn => "warn but allow",
+            FilterAction::Reject => "reject",
+            FilterAction::Alert => "reject and alert staff",
+        }
+    }
+}
+
+/// A guild's action for each severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnforcementPolicy {
+    pub low: FilterAction,
+    pub medium: FilterAction,
+    pub high: FilterAction,
+}
+
+impl Default for EnforcementPolicy {
+    fn default() -> Self {
+        Self {
+            low: FilterAction::Warn,
+            medium: FilterAction::Reject,
+            high: FilterAction::Alert,
+        }
+    }
+}
+
+impl EnforcementPolicy {
+    pub fn action_for(&self, severity: Severity) -> FilterAction {
+        match severity {
+            Severity::Low => self.low,
+            Severity::Medium => self.medium,
+            Severity::High => self.high,
+        }
+    }
+
+    pub fn set(&mut self, severity: Severity, action: FilterAction) {
+        match severity {
+            Severity::Low => self.low = action,
+            Severity::Medium => self.medium = action,
+            Severity::High => self.high = action,
+        }
+    }
+}
+
+/// Outcome of checking a role name against the blacklist
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NameCheck {
+    Clean,
+    /// Allowed, but the member is cautioned and the attempt recorded
+    Warn {
+        word: String,
+    },
+    Reject {
+        word: String,
+        alert: bool,
+    },
+}
+
+impl NameCheck {
+    /// Note added to the success message when a name was allowed with a warning
+    pub fn caution_note(&self) -> Option<String> {
+        match self {
+            NameCheck::Warn { word } => Some(format!(
+                "⚠️ Your role name contains \"{}\", which this server discourages. It was allowed, but staff can see the attempt.",
+                word
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// How a blacklist entry is compared against role names
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum MatchType {
+    /// Only whole words, so "ass" doesn't catch "class"
+    #[name = "exact word"]
+    Exact,
+    #[default]
+    #[name = "substring"]
+    Substring,
+    /// `*` stands for any run of letters or digits inside one word
+    #[name = "wildcard"]
+    Wildcard,
+    #[name = "regex"]
+    Regex,
+}
+
+impl MatchType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MatchType::Exact => "exact",
+            MatchType::Substring => "substring",
+            MatchType::Wildcard => "wildcard",
+            MatchType::Regex => "regex",
+        }
+    }
+
+    /// Rows from before match types existed were all substring checks
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "exact" => MatchType::Exact,
+            "wildcard" => MatchType::Wildcard,
+            "regex" => MatchType::Regex,
+            _ => MatchType::Substring,
+        }
+    }
+}
+
+/// Compiled size cap for admin-supplied patterns
+const MAX_PATTERN_SIZE: usize = 256 * 1024;
+
+/// Build the case-insensitive matcher for a blacklist pattern, with an
+/// explanation suitable for the admin when the pattern can't be used
+pub fn compile_pattern(pattern: &str, match_type: MatchType) -> Result<Regex, String> {
+    let source = match match_type {
+        MatchType::Substring => regex::escape(pattern),
+        MatchType::Exact => whole_word(&regex::escape(pattern)),
+        MatchType::Wildcard => {
+            if pattern.chars().all(|c| c == '*') {
+                return Err("A wildcard needs at least one letter besides `*`.".to_string());
+            }
+            let body = pattern
+                .split('*')
+                .map(regex::escape)
+                .collect::<Vec<_>>()
+                .join(r"\w*");
+            whole_word(&body)
+        }
+        MatchType::Regex => pattern.to_string(),
+    };
+
+    let compiled = RegexBuilder::new(&source)
+        .case_insensitive(true)
+        .size_limit(MAX_PATTERN_SIZE)
+        .build()
+        .map_err(|e| match e {
+            regex::Error::CompiledTooBig(_) => "That pattern is too complex.".to_string(),
+            other => format!(
+                "That isn't a valid regular expression: {}",
+                other
+                    .to_string()
+                    .lines()
+                    .rev()
+                    .find(|line| !line.trim().is_empty())
+                    .unwrap_or_default()
+                    .trim_start_matches("error: ")
+            ),
+        })?;
+
+    if compiled.is_match("") {
+        return Err(
+            "That pattern matches an empty name, so it would block every role.".to_string(),
+        );
+    }
+
+    Ok(compiled)
+}
+
+/// Anchor a pattern so it only matches between word boundaries
+fn whole_word(body: &str) -> String {
+    format!(r"(?:^|\W)(?:{})(?:\W|$)", body)
+}
+
+/// One blacklist row, as checked against role names
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlacklistEntry {
+    pub word: String,
+    pub severity: Severity,
+    pub match_type: MatchType,
+}
+
+impl BlacklistEntry {
+    pub fn new(word: impl Into<String>, severity: Severity, match_type: MatchType) -> Self {
+        Self {
+            word: word.into(),
+            severity,
+            match_type,
+        }
+    }
+
+    /// Patterns are validated when added, so one that no longer compiles
+    /// simply never matches rather than blocking every name
+    pub fn matches(&self, text: &str) -> bool {
+        compile_pattern(&self.word, self.match_type)
+            .map(|pattern| pattern.is_match(text))
+            .unwrap_or(false)
+    }
+}
+
+/// The most severe blacklist entry matching `text`
+pub fn strongest_match<'a>(
+    text: &str,
+    entries: &'a [BlacklistEntry],
+) -> Option<(&'a str, Severity)> {
+    entries
+        .iter()
+        .filter(|entry| entry.matches(text))
+        .max_by_key(|entry| entry.severity)
+        .map(|entry| (entry.word.as_str(), entry.severity))
+}
+
+/// Decide what to do with a role name under a guild's policy
+pub fn enforce(text: &str, entries: &[BlacklistEntry], policy: EnforcementPolicy) -> NameCheck {
+    let Some((word, severity)) = strongest_match(text, entries) else {
+        return NameCheck::Clean;
+    };
+
+    let word = word.to_string();
+    match policy.action_for(severity) {
+        FilterAction::Warn => NameCheck::Warn { word },
+        FilterAction::Reject => NameCheck::Reject { word, alert: false },
+        FilterAction::Alert => NameCheck::Reject { word, alert: true },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<BlacklistEntry> {
+        vec![
+            BlacklistEntry::new("darn", Severity::Low, MatchType::Substring),
+            BlacklistEntry::new("crud", Severity::Medium, MatchType::Substring),
+            BlacklistEntry::new("slur", Severity::High, MatchType::Substring),
+        ]
+    }
+
+    #[test]
+    fn test_low_warns_but_allows() {
+        let check = enforce("Darn Cool", &entries(), EnforcementPolicy::default());
+        assert_eq!(
+            check,
+            NameCheck::Warn {
+                word: "darn".to_string()
+            }
+        );
+        assert!(check.caution_note().unwrap().contains("darn"));
+    }
+
+    #[test]
+    fn test_medium_rejects_quietly() {
+        let check = enforce("crud", &entries(), EnforcementPolicy::default());
+        assert_eq!(
+            check,
+            NameCheck::Reject {
+                word: "crud".to_string(),
+                alert: false
+            }
+        );
+        assert_eq!(check.caution_note(), None);
+    }
+
+    #[test]
+    fn test_high_rejects_and_alerts() {
+        let check = enforce("a SLUR", &entries(), EnforcementPolicy::default());
+        assert_eq!(
+            check,
+            NameCheck::Reject {
+                word: "slur".to_string(),
+                alert: true
+            }
+        );
+    }
+
+    #[test]
+    fn test_most_severe_match_wins_and_clean_names_pass() {
+        let check = enforce("darn slur", &entries(), EnforcementPolicy::default());
+        assert!(matches!(check, NameCheck::Reject { alert: true, .. }));
+        assert_eq!(
+            enforce("Sparkles", &entries(), EnforcementPolicy::default()),
+            NameCheck::Clean
+        );
+    }
+
+    #[test]
+    fn test_guild_policy_overrides_defaults() {
+        let mut policy = EnforcementPolicy::default();
+        policy.set(Severity::Low, FilterAction::Reject);
+        policy.set(Severity::Medium, FilterAction::Warn);
+
+        assert!(matches!(
+            enforce("darn", &entries(), policy),
+            NameCheck::Reject { .. }
+        ));
+        assert!(matches!(
+            enforce("crud", &entries(), policy),
+            NameCheck::Warn { .. }
+        ));
+        assert_eq!(Severity::parse("bogus"), Severity::Medium);
+    }
+
+    #[test]
+    fn test_exact_word_skips_longer_words() {
+        let entry = BlacklistEntry::new("ass", Severity::Medium, MatchType::Exact);
+        assert!(entry.matches("Kick ASS crew"));
+        assert!(entry.matches("ass"));
+        assert!(!entry.matches("First Class"));
+        assert!(
+            BlacklistEntry::new("ass", Severity::Medium, MatchType::Substring).matches("class")
+        );
+    }
+
+    #[test]
+    fn test_wildcard_spans_one_word() {
+        let entry = BlacklistEntry::new("f*ck", Severity::High, MatchType::Wildcard);
+        assert!(entry.matches("Fuck Yeah"));
+        assert!(entry.matches("what the fck"));
+        assert!(!entry.matches("fun luck"));
+        assert!(compile_pattern("**", MatchType::Wildcard).is_err());
+    }
+
+    #[test]
+    fn test_regex_catches_variants_and_rejects_bad_patterns() {
+        let entry = BlacklistEntry::new(r"n[o0]{2}b", Severity::Low, MatchType::Regex);
+        assert!(entry.matches("Total N00B"));
+        assert!(entry.matches("noob squad"));
+        assert!(!entry.matches("nub"));
+
+        let err = compile_pattern("(unclosed", MatchType::Regex).unwrap_err();
+        assert!(err.starts_with("That isn't a valid regular expression"));
+        assert!(compile_pattern("x*", MatchType::Regex).is_err());
+        assert!(compile_pattern("a.b(c", MatchType::Substring).is_ok());
+    }
+
+    #[test]
+    fn test_match_type_round_trips_and_defaults_to_substring() {
+        for match_type in [
+            MatchType::Exact,
+            MatchType::Substring,
+            MatchType::Wildcard,
+            MatchType::Regex,
+        ] {
+            assert_eq!(MatchType::parse(match_type.as_str()), match_type);
+        }
+        assert_eq!(MatchType::parse(""), MatchType::Substring);
+    }
+}
@@ -0,0 +1,223 @@
+use serenity::all::{Command, CommandId};
+use std::collections::HashMap;
+
+/// Title of the embed field that carries follow-up suggestions
+pub const NEXT_STEPS_FIELD_TITLE: &str = "💡 Next Steps";
+
+/// A follow-up command suggested after a successful command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Suggestion {
+    /// Qualified command name, e.g. `boosterrole share role`
+    pub command: &'static str,
+    pub description: &'static str,
+}
+
+const fn suggest(command: &'static str, description: &'static str) -> Suggestion {
+    Suggestion {
+        command,
+        description,
+    }
+}
+
+/// Static map from qualified command name to at most three follow-ups
+const SUGGESTIONS: &[(&str, &[Suggestion])] = &[
+    (
+        "boosterrole color",
+        &[
+            suggest("boosterrole icon", "Give your role a custom icon"),
+            suggest("boosterrole share role", "Share your role with a friend"),
+            suggest("boosterrole dominant", "Match your avatar's colors"),
+        ],
+    ),
+    (
+        "boosterrole dominant",
+        &[
+            suggest("boosterrole random", "Try a random color instead"),
+            suggest("boosterrole rename set", "Give your role a new name"),
+            suggest("boosterrole share role", "Share your role with a friend"),
+        ],
+    ),
+    (
+        "boosterrole random",
+        &[
+            suggest("boosterrole color", "Pick an exact color"),
+            suggest("boosterrole dominant", "Match your avatar's colors"),
+            suggest("boosterrole icon", "Give your role a custom icon"),
+        ],
+    ),
+    (
+        "boosterrole rename set",
+        &[
+            suggest("boosterrole color", "Change your role color"),
+            suggest("boosterrole icon", "Give your role a custom icon"),
+        ],
+    ),
+    (
+        "boosterrole icon",
+        &[
+            suggest("boosterrole color", "Change your role color"),
+            suggest("boosterrole share role", "Share your role with a friend"),
+        ],
+    ),
+    (
+        "boosterrole share role",
+        &[
+            suggest("boosterrole share mine", "See who your role is shared with"),
+            suggest("boosterrole rename set", "Give your role a new name"),
+        ],
+    ),
+    (
+        "boosterrole filter add",
+        &[
+            suggest("boosterrole filter list", "Review all blacklisted words"),
+            suggest("boosterrole filter remove", "Undo a blacklist entry"),
+        ],
+    ),
+    (
+        "boosterrole filter remove",
+        &[suggest("boosterrole filter list", "Review all blacklisted words")],
+    ),
+    (
+        "boosterrole link",
+        &[
+            suggest("boosterrole list", "View all booster roles"),
+            suggest("boosterrole base set", "Set the hierarchy base role"),
+        ],
+    ),
+    (
+        "boosterrole award set",
+        &[
+            suggest("boosterrole award view", "Check the current award role"),
+            suggest("boosterrole limit", "Cap the number of booster roles"),
+        ],
+    ),
+    (
+        "boosterrole limit",
+        &[
+            suggest("boosterrole list", "View all booster roles"),
+            suggest("boosterrole cleanup", "Remove orphaned booster roles"),
+        ],
+    ),
+];
+
+/// Look up the follow-up suggestions for a qualified command name
+pub fn suggestions_for(command: &str) -> &'static [Suggestion] {
+    SUGGESTIONS
+        .iter()
+        .find(|(name, _)| *name == command)
+        .map(|(_, suggestions)| *suggestions)
+        .unwrap_or(&[])
+}
+
+/// Registered application command IDs, keyed by top-level command name
+#[derive(Debug, Clone, Default)]
+pub struct CommandRegistry {
+    ids: HashMap<String, CommandId>,
+}
+
+impl CommandRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the IDs Discord assigned to the commands we just registered
+    pub fn capture(&mut self, commands: &[Command]) {
+        for command in commands {
+            self.insert(&command.name, command.id);
+        }
+    }
+
+    pub fn insert(&mut self, name: &str, id: CommandId) {
+        self.ids.insert(name.to_string(), id);
+    }
+
+    pub fn get(&self, name: &str) -> Option<CommandId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Render a clickable `</name:id>` mention, or plain `/name` text when the ID is unknown
+    pub fn mention(&self, qualified_name: &str) -> String {
+        let top_level = qualified_name.split(' ').next().unwrap_or(qualified_name);
+        match self.get(top_level) {
+            Some(id) => format!("</{}:{}>", qualified_name, id),
+            None => format!("`/{}`", qualified_name),
+        }
+    }
+}
+
+/// Render the next-steps field value for a command, if it has any suggestions
+pub fn render_next_steps(registry: &CommandRegistry, command: &str) -> Option<String> {
+    let suggestions = suggestions_for(command);
+    if suggestions.is_empty() {
+        return None;
+    }
+
+    Some(
+        suggestions
+            .iter()
+            .map(|s| format!("{} — {}", registry.mention(s.command), s.description))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suggestions_lookup() {
+        let color = suggestions_for("boosterrole color");
+        assert_eq!(color.len(), 3);
+        assert_eq!(color[0].command, "boosterrole icon");
+
+        assert!(suggestions_for("ping").is_empty());
+        assert!(SUGGESTIONS.iter().all(|(_, s)| !s.is_empty() && s.len() <= 3));
+    }
+
+    #[test]
+    fn test_mention_degrades_without_ids() {
+        let registry = CommandRegistry::new();
+        assert_eq!(
+            registry.mention("boosterrole filter list"),
+            "`/boosterrole filter list`"
+        );
+        assert_eq!(
+            render_next_steps(&registry, "boosterrole filter remove").unwrap(),
+            "`/boosterrole filter list` — Review all blacklisted words"
+        );
+        assert!(render_next_steps(&registry, "ping").is_none());
+    }
+
+    #[test]
+    fn test_capture_registered_ids() {
+        let commands: Vec<Command> = serde_json::from_value(serde_json::json!([{
+            "id": "1234",
+            "type": 1,
+            "application_id": "99",
+            "name": "boosterrole",
+            "description": "Booster role management",
+            "version": "1"
+        }]))
+        .unwrap();
+
+        let mut registry = CommandRegistry::new();
+        registry.capture(&commands);
+
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.get("boosterrole"), Some(CommandId::new(1234)));
+        assert_eq!(
+            registry.mention("boosterrole share role"),
+            "</boosterrole share role:1234>"
+        );
+        assert_eq!(registry.mention("settings config"), "`/settings config`");
+    }
+}
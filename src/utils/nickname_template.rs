@@ -0,0 +1,140 @@
+/// Longest nickname Discord accepts, in characters
+pub const MAX_NICKNAME_CHARS: usize = 32;
+
+/// Every placeholder an auto-nickname template can use
+pub const PLACEHOLDERS: &[&str] = &[
+    "{username}",
+    "{displayname}",
+    "{tag}",
+    "{discriminator}",
+    "{member_count}",
+    "{join_position}",
+];
+
+/// What a template is rendered against
+#[derive(Debug, Clone, Default)]
+pub struct NicknameValues<'a> {
+    pub username: &'a str,
+    pub display_name: &'a str,
+    /// `None` for accounts on the new username system
+    pub discriminator: Option<u16>,
+    pub member_count: u64,
+    pub join_position: u64,
+}
+
+impl NicknameValues<'_> {
+    /// `name#1234` for legacy accounts, the plain username otherwise
+    fn tag(&self) -> String {
+        match self.discriminator {
+            Some(disc) => format!("{}#{:04}", self.username, disc),
+            None => self.username.to_string(),
+        }
+    }
+}
+
+/// Fill in a template's placeholders, cut to Discord's nickname limit
+pub fn render(template: &str, values: &NicknameValues) -> String {
+    let discriminator = values
+        .discriminator
+        .map(|d| format!("{:04}", d))
+        .unwrap_or_default();
+
+    let rendered = template
+        .replace("{username}", values.username)
+        .replace("{displayname}", values.display_name)
+        .replace("{tag}", &values.tag())
+        .replace("{discriminator}", &discriminator)
+        .replace("{member_count}", &values.member_count.to_string())
+        .replace("{join_position}", &values.join_position.to_string());
+
+    truncate_chars(rendered.trim(), MAX_NICKNAME_CHARS)
+}
+
+/// The first `max` characters of `text`, never splitting a character
+pub fn truncate_chars(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((end, _)) => text[..end].to_string(),
+        None => text.to_string(),
+    }
+}
+
+/// `{...}` tokens in the template that aren't placeholders, in order
+pub fn unknown_placeholders(template: &str) -> Vec<String> {
+    let mut unknown = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let token = &rest[start..=start + len];
+        if !PLACEHOLDERS.contains(&token) && !unknown.iter().any(|u| u == token) {
+            unknown.push(token.to_string());
+        }
+        rest = &rest[start + len + 1..];
+    }
+
+    unknown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values<'a>(username: &'a str, display_name: &'a str) -> NicknameValues<'a> {
+        NicknameValues {
+            username,
+            display_name,
+            discriminator: None,
+            member_count: 1204,
+            join_position: 1187,
+        }
+    }
+
+    #[test]
+    fn test_every_placeholder_is_filled() {
+        let legacy = NicknameValues {
+            discriminator: Some(42),
+            ..values("alex", "Alex")
+        };
+
+        assert_eq!(render("{username}", &legacy), "alex");
+        assert_eq!(render("{displayname}", &legacy), "Alex");
+        assert_eq!(render("{tag}", &legacy), "alex#0042");
+        assert_eq!(render("{discriminator}", &legacy), "0042");
+        assert_eq!(render("{member_count}", &legacy), "1204");
+        assert_eq!(
+            render("#{join_position} {displayname}", &legacy),
+            "#1187 Alex"
+        );
+
+        // New-style accounts have no discriminator
+        let modern = values("alex", "Alex");
+        assert_eq!(render("{tag}", &modern), "alex");
+        assert_eq!(render("{displayname} {discriminator}", &modern), "Alex");
+    }
+
+    #[test]
+    fn test_unicode_names_truncate_on_char_boundaries() {
+        let name = "ñandú🦤".repeat(10);
+        let rendered = render("{displayname}", &values("x", &name));
+        assert_eq!(rendered.chars().count(), MAX_NICKNAME_CHARS);
+        assert!(name.starts_with(&rendered));
+
+        assert_eq!(truncate_chars("日本語", 2), "日本");
+        assert_eq!(truncate_chars("日本語", 5), "日本語");
+        assert_eq!(
+            render("✨ {username} ✨", &values("ゆき", "ゆき")),
+            "✨ ゆき ✨"
+        );
+    }
+
+    #[test]
+    fn test_unknown_placeholders_are_reported_once() {
+        assert!(unknown_placeholders("{displayname} | #{join_position}").is_empty());
+        assert_eq!(
+            unknown_placeholders("{nick} {user} {nick} {"),
+            vec!["{nick}".to_string(), "{user}".to_string()]
+        );
+    }
+}
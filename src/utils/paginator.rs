@@ -0,0 +1,163 @@
+use crate::bot::{Context, Error};
+use poise::serenity_prelude::{
+    ButtonStyle, ComponentInteractionCollector, CreateActionRow, CreateButton, CreateEmbed,
+    CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use poise::CreateReply;
+use std::time::Duration;
+
+/// How long a menu waits for the next click before its buttons are disabled
+pub const PAGE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// A button press on a paged menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageAction {
+    Previous,
+    Next,
+}
+
+impl PageAction {
+    fn suffix(self) -> &'static str {
+        match self {
+            PageAction::Previous => "prev",
+            PageAction::Next => "next",
+        }
+    }
+
+    /// The action a button's custom ID stands for, if it belongs to `prefix`
+    pub fn from_custom_id(prefix: &str, custom_id: &str) -> Option<Self> {
+        match custom_id.strip_prefix(prefix)?.strip_prefix(':')? {
+            "prev" => Some(PageAction::Previous),
+            "next" => Some(PageAction::Next),
+            _ => None,
+        }
+    }
+}
+
+/// The zero-based page after `action`, staying within `0..total`
+pub fn turn_page(current: usize, total: usize, action: PageAction) -> usize {
+    match action {
+        PageAction::Previous => current.saturating_sub(1),
+        PageAction::Next => (current + 1).min(total.saturating_sub(1)),
+    }
+}
+
+/// Previous/Next buttons for page `current` (zero-based) of `total`
+pub fn nav_buttons(prefix: &str, current: usize, total: usize, expired: bool) -> CreateActionRow {
+    let button = |action: PageAction, label: &str, at_edge: bool| {
+        CreateButton::new(format!("{}:{}", prefix, action.suffix()))
+            .label(label)
+            .style(ButtonStyle::Secondary)
+            .disabled(expired || at_edge)
+    };
+
+    CreateActionRow::Buttons(vec![
+        button(PageAction::Previous, "◀ Previous", current == 0),
+        button(PageAction::Next, "Next ▶", current + 1 >= total),
+    ])
+}
+
+/// Send `pages` with Previous/Next buttons only the invoking user can use.
+/// A single page is sent without buttons.
+pub async fn paginate_embeds(ctx: Context<'_>, pages: Vec<CreateEmbed>) -> Result<(), Error> {
+    let total = pages.len();
+    let Some(first) = pages.first().cloned() else {
+        return Ok(());
+    };
+    if total == 1 {
+        ctx.send(CreateReply::default().embed(first)).await?;
+        return Ok(());
+    }
+
+    let prefix = format!("page:{}", ctx.id());
+    let mut current = 0;
+    let reply = ctx
+        .send(
+            CreateReply::default()
+                .embed(first)
+                .components(vec![nav_buttons(&prefix, current, total, false)]),
+        )
+        .await?;
+    let message_id = reply.message().await?.id;
+
+    loop {
+        let filter_prefix = prefix.clone();
+        let interaction = ComponentInteractionCollector::new(ctx)
+            .message_id(message_id)
+            .filter(move |i| {
+                PageAction::from_custom_id(&filter_prefix, &i.data.custom_id).is_some()
+            })
+            .timeout(PAGE_TIMEOUT)
+            .await;
+
+        let Some(interaction) = interaction else {
+            reply
+                .edit(
+                    ctx,
+                    CreateReply::default()
+                        .embed(pages[current].clone())
+                        .components(vec![nav_buttons(&prefix, current, total, true)]),
+                )
+                .await?;
+            return Ok(());
+        };
+
+        if interaction.user.id != ctx.author().id {
+            interaction
+                .create_response(
+                    ctx,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content("This isn't your menu. Run the command yourself to browse it.")
+                            .ephemeral(true),
+                    ),
+                )
+                .await?;
+            continue;
+        }
+
+        if let Some(action) = PageAction::from_custom_id(&prefix, &interaction.data.custom_id) {
+            current = turn_page(current, total, action);
+        }
+        interaction
+            .create_response(
+                ctx,
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .embed(pages[current].clone())
+                        .components(vec![nav_buttons(&prefix, current, total, false)]),
+                ),
+            )
+            .await?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turn_page_stays_in_range() {
+        assert_eq!(turn_page(0, 3, PageAction::Previous), 0);
+        assert_eq!(turn_page(0, 3, PageAction::Next), 1);
+        assert_eq!(turn_page(2, 3, PageAction::Next), 2);
+        assert_eq!(turn_page(2, 3, PageAction::Previous), 1);
+        assert_eq!(turn_page(0, 1, PageAction::Next), 0);
+    }
+
+    #[test]
+    fn test_custom_ids_belong_to_one_menu() {
+        assert_eq!(
+            PageAction::from_custom_id("page:7", "page:7:next"),
+            Some(PageAction::Next)
+        );
+        assert_eq!(
+            PageAction::from_custom_id("page:7", "page:7:prev"),
+            Some(PageAction::Previous)
+        );
+        // Another invocation's menu, or a prefix that only shares digits
+        assert_eq!(PageAction::from_custom_id("page:7", "page:8:next"), None);
+        assert_eq!(PageAction::from_custom_id("page:7", "page:77:next"), None);
+        assert_eq!(PageAction::from_custom_id("page:7", "page:7:other"), None);
+    }
+}
@@ -1,10 +1,23 @@
-use std::time::Instant;
-use std::collections::HashMap;
+use super::query_metrics::percentile;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
 use tokio::sync::RwLock;
-use serde::{Serialize, Deserialize};
-use std::path::Path;
-use tokio::fs;
+
+/// Where each command execution is appended for the Python scripts to read
+pub const METRICS_LOG_PATH: &str = "test_results/rust_performance_metrics.jsonl";
+
+/// Executions kept in memory for `/metrics`
+const MAX_IN_MEMORY: usize = 1000;
+
+/// Once the log reaches this size it's moved to `<path>.1` and restarted
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Timers older than this belong to invocations that never finished
+const STALE_TIMER: Duration = Duration::from_secs(15 * 60);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommandMetrics {
@@ -18,187 +31,279 @@ pub struct CommandMetrics {
     pub error: Option<String>,
 }
 
-#[derive(Clone)]
-#[allow(dead_code)]
+impl CommandMetrics {
+    /// The qualified command name, e.g. `boosterrole filter add`
+    fn qualified_name(&self) -> String {
+        match &self.subcommand {
+            Some(sub) => format!("{} {}", self.command, sub),
+            None => self.command.clone(),
+        }
+    }
+}
+
+/// Timings for one command over the executions kept in memory
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandStats {
+    pub name: String,
+    pub count: usize,
+    pub successful: usize,
+    pub avg_ms: f64,
+    pub p95_ms: f64,
+}
+
+/// Everything `/metrics` shows, busiest commands first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PerformanceSummary {
+    pub total: usize,
+    pub successful: usize,
+    pub commands: Vec<CommandStats>,
+}
+
+impl PerformanceSummary {
+    /// Percentage of executions that succeeded
+    pub fn success_rate(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.successful as f64 / self.total as f64 * 100.0
+        }
+    }
+}
+
+/// Times every command execution from the framework's hooks
+#[derive(Debug, Clone)]
 pub struct PerformanceTracker {
-    metrics: Arc<RwLock<Vec<CommandMetrics>>>,
-    active_timers: Arc<RwLock<HashMap<String, Instant>>>,
+    metrics: Arc<RwLock<VecDeque<CommandMetrics>>>,
+    active_timers: Arc<RwLock<HashMap<u64, Instant>>>,
+    log_path: Option<PathBuf>,
+}
+
+impl Default for PerformanceTracker {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-#[allow(dead_code)]
 impl PerformanceTracker {
     pub fn new() -> Self {
+        Self::with_log(Some(PathBuf::from(METRICS_LOG_PATH)))
+    }
+
+    /// A tracker appending to `log_path`, or keeping metrics in memory only
+    pub fn with_log(log_path: Option<PathBuf>) -> Self {
         Self {
-            metrics: Arc::new(RwLock::new(Vec::new())),
+            metrics: Arc::new(RwLock::new(VecDeque::new())),
             active_timers: Arc::new(RwLock::new(HashMap::new())),
+            log_path,
         }
     }
 
-    /// Start timing a command execution
-    pub async fn start_timer(&self, command_id: String) {
+    /// Start timing an invocation, keyed by its context ID
+    pub async fn start_timer(&self, invocation_id: u64) {
         let mut timers = self.active_timers.write().await;
-        timers.insert(command_id, Instant::now());
+        // Invocations that failed before running never reach end_timer
+        timers.retain(|_, started| started.elapsed() < STALE_TIMER);
+        timers.insert(invocation_id, Instant::now());
     }
 
-    /// End timing and record metrics
+    /// Stop timing an invocation and record it; `error` is `None` on success.
+    /// Returns the execution time, or `None` if no timer was running.
     pub async fn end_timer(
         &self,
-        command_id: String,
-        command: String,
-        subcommand: Option<String>,
+        invocation_id: u64,
+        qualified_name: &str,
         user_id: u64,
         guild_id: Option<u64>,
-        success: bool,
         error: Option<String>,
     ) -> Option<f64> {
-        let mut timers = self.active_timers.write().await;
-        
-        if let Some(start_time) = timers.remove(&command_id) {
-            let execution_time_ms = start_time.elapsed().as_secs_f64() * 1000.0;
-            
-            let metric = CommandMetrics {
-                command,
-                subcommand,
-                execution_time_ms,
-                timestamp: chrono::Utc::now(),
-                user_id,
-                guild_id,
-                success,
-                error,
-            };
-            
-            let mut metrics = self.metrics.write().await;
-            metrics.push(metric.clone());
-            
-            // Keep only last 1000 metrics in memory
-            if metrics.len() > 1000 {
-                let drain_end = metrics.len() - 1000;
-                metrics.drain(0..drain_end);
+        let started = self.active_timers.write().await.remove(&invocation_id)?;
+        let execution_time_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+        let (command, subcommand) = match qualified_name.split_once(' ') {
+            Some((command, sub)) => (command.to_string(), Some(sub.to_string())),
+            None => (qualified_name.to_string(), None),
+        };
+        let metric = CommandMetrics {
+            command,
+            subcommand,
+            execution_time_ms,
+            timestamp: chrono::Utc::now(),
+            user_id,
+            guild_id,
+            success: error.is_none(),
+            error,
+        };
+
+        if let Some(path) = &self.log_path {
+            if let Err(e) = append_to_log(path, &metric).await {
+                tracing::warn!(
+                    error = %e,
+                    path = %path.display(),
+                    "Failed to append command metric"
+                );
             }
-            
-            // Async save to file
-            let _ = self.save_metrics_to_file(metric).await;
-            
-            Some(execution_time_ms)
-        } else {
-            None
         }
-    }
 
-    /// Save metrics to file for the Python scripts to read
-    async fn save_metrics_to_file(&self, metric: CommandMetrics) -> Result<(), Box<dyn std::error::Error>> {
-        let path = Path::new("test_results/rust_performance_metrics.jsonl");
-        
-        // Ensure directory exists
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent).await?;
+        let mut metrics = self.metrics.write().await;
+        if metrics.len() == MAX_IN_MEMORY {
+            metrics.pop_front();
         }
-        
-        // Append metric as JSON line
-        let json_line = serde_json::to_string(&metric)? + "\n";
-        
-        // Append to file
-        let mut contents = if path.exists() {
-            fs::read_to_string(path).await.unwrap_or_default()
-        } else {
-            String::new()
-        };
-        
-        contents.push_str(&json_line);
-        
-        // Keep only last 10000 lines
-        let lines: Vec<&str> = contents.lines().collect();
-        let keep_lines = if lines.len() > 10000 {
-            &lines[lines.len() - 10000..]
-        } else {
-            &lines[..]
-        };
-        
-        let final_content = keep_lines.join("\n") + "\n";
-        fs::write(path, final_content).await?;
-        
-        Ok(())
+        metrics.push_back(metric);
+
+        Some(execution_time_ms)
     }
 
-    /// Get average response time for a command
-    pub async fn get_average_response_time(&self, command: &str, subcommand: Option<&str>) -> Option<f64> {
-        let metrics = self.metrics.read().await;
-        
-        let relevant_metrics: Vec<&CommandMetrics> = metrics
-            .iter()
-            .filter(|m| m.command == command && m.subcommand.as_deref() == subcommand)
-            .collect();
-        
-        if relevant_metrics.is_empty() {
-            return None;
+    /// Summary of the executions kept in memory
+    pub async fn summary(&self) -> PerformanceSummary {
+        summarize(self.metrics.read().await.iter())
+    }
+}
+
+/// Totals plus average and p95 time per command
+pub fn summarize<'a>(metrics: impl IntoIterator<Item = &'a CommandMetrics>) -> PerformanceSummary {
+    let mut by_command: HashMap<String, (Vec<f64>, usize)> = HashMap::new();
+    let mut summary = PerformanceSummary::default();
+
+    for metric in metrics {
+        summary.total += 1;
+        let (times, successful) = by_command.entry(metric.qualified_name()).or_default();
+        times.push(metric.execution_time_ms);
+        if metric.success {
+            summary.successful += 1;
+            *successful += 1;
         }
-        
-        let sum: f64 = relevant_metrics.iter().map(|m| m.execution_time_ms).sum();
-        Some(sum / relevant_metrics.len() as f64)
     }
 
-    /// Get metrics summary
-    pub async fn get_summary(&self) -> HashMap<String, serde_json::Value> {
-        let metrics = self.metrics.read().await;
-        
-        let total = metrics.len();
-        let successful = metrics.iter().filter(|m| m.success).count();
-        let failed = total - successful;
-        
-        let avg_time = if total > 0 {
-            metrics.iter().map(|m| m.execution_time_ms).sum::<f64>() / total as f64
-        } else {
-            0.0
-        };
-        
-        let mut summary = HashMap::new();
-        summary.insert("total_commands".to_string(), serde_json::json!(total));
-        summary.insert("successful".to_string(), serde_json::json!(successful));
-        summary.insert("failed".to_string(), serde_json::json!(failed));
-        summary.insert("average_response_time_ms".to_string(), serde_json::json!(avg_time));
-        summary.insert("success_rate".to_string(), serde_json::json!(if total > 0 { successful as f64 / total as f64 * 100.0 } else { 0.0 }));
-        
-        summary
+    summary.commands = by_command
+        .into_iter()
+        .map(|(name, (mut times, successful))| {
+            times.sort_by(|a, b| a.total_cmp(b));
+            CommandStats {
+                name,
+                count: times.len(),
+                successful,
+                avg_ms: times.iter().sum::<f64>() / times.len() as f64,
+                p95_ms: percentile(&times, 95.0),
+            }
+        })
+        .collect();
+    summary
+        .commands
+        .sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    summary
+}
+
+/// Append one JSON line, rotating the log first when it's grown too big
+async fn append_to_log(path: &Path, metric: &CommandMetrics) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
     }
+
+    if let Ok(meta) = tokio::fs::metadata(path).await {
+        if meta.len() >= MAX_LOG_BYTES {
+            let mut rotated = path.as_os_str().to_owned();
+            rotated.push(".1");
+            tokio::fs::rename(path, rotated).await?;
+        }
+    }
+
+    let mut line = serde_json::to_string(metric)?;
+    line.push('\n');
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(line.as_bytes()).await?;
+    // tokio finishes writes in the background; flush so the line is on disk
+    // before the file is dropped
+    file.flush().await
 }
 
-// Macro to wrap command execution with performance tracking
-#[macro_export]
-macro_rules! track_performance {
-    ($tracker:expr, $ctx:expr, $command:expr, $subcommand:expr, $body:expr) => {{
-        let command_id = format!("{}_{}_{}_{}", 
-            $command, 
-            $subcommand.as_deref().unwrap_or("none"),
-            $ctx.author().id,
-            chrono::Utc::now().timestamp_millis()
-        );
-        
-        $tracker.start_timer(command_id.clone()).await;
-        
-        let result = $body;
-        
-        let success = result.is_ok();
-        let error = result.as_ref().err().map(|e| e.to_string());
-        
-        let execution_time = $tracker.end_timer(
-            command_id,
-            $command.to_string(),
-            $subcommand,
-            $ctx.author().id.0,
-            $ctx.guild_id().map(|id| id.0),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metric(command: &str, ms: f64, success: bool) -> CommandMetrics {
+        let (command, subcommand) = match command.split_once(' ') {
+            Some((c, s)) => (c.to_string(), Some(s.to_string())),
+            None => (command.to_string(), None),
+        };
+        CommandMetrics {
+            command,
+            subcommand,
+            execution_time_ms: ms,
+            timestamp: chrono::Utc::now(),
+            user_id: 1,
+            guild_id: None,
             success,
-            error,
-        ).await;
-        
-        if let Some(time) = execution_time {
-            tracing::info!(
-                "Command {}/{} executed in {:.2}ms", 
-                $command, 
-                $subcommand.as_deref().unwrap_or("none"),
-                time
-            );
+            error: None,
         }
-        
-        result
-    }};
-}
\ No newline at end of file
+    }
+
+    #[test]
+    fn test_p95_per_command() {
+        let metrics: Vec<_> = (1..=100)
+            .map(|ms| metric("boosterrole color", ms as f64, ms % 10 != 0))
+            .chain([metric("ping", 4.0, true)])
+            .collect();
+
+        let summary = summarize(&metrics);
+        assert_eq!(summary.total, 101);
+        assert_eq!(summary.successful, 91);
+
+        let color = &summary.commands[0];
+        assert_eq!(color.name, "boosterrole color");
+        assert_eq!(color.count, 100);
+        assert_eq!(color.successful, 90);
+        assert_eq!(color.avg_ms, 50.5);
+        assert_eq!(color.p95_ms, 95.0);
+
+        // A single sample is its own p95
+        assert_eq!(summary.commands[1].name, "ping");
+        assert_eq!(summary.commands[1].p95_ms, 4.0);
+    }
+
+    #[test]
+    fn test_p95_ignores_arrival_order() {
+        let metrics: Vec<_> = [30.0, 10.0, 200.0, 20.0]
+            .into_iter()
+            .map(|ms| metric("info", ms, true))
+            .collect();
+        assert_eq!(summarize(&metrics).commands[0].p95_ms, 200.0);
+        assert_eq!(summarize(&[]).success_rate(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_executions_are_appended_to_log() {
+        let path =
+            std::env::temp_dir().join(format!("performance_log_test_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let tracker = PerformanceTracker::with_log(Some(path.clone()));
+
+        tracker.start_timer(1).await;
+        tracker.start_timer(2).await;
+        assert!(tracker.end_timer(1, "ping", 7, None, None).await.is_some());
+        assert!(tracker
+            .end_timer(2, "settings config", 7, Some(3), Some("boom".into()))
+            .await
+            .is_some());
+        // Already finished
+        assert!(tracker.end_timer(1, "ping", 7, None, None).await.is_none());
+
+        let lines: Vec<CommandMetrics> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].subcommand.as_deref(), Some("config"));
+        assert!(!lines[1].success);
+
+        let summary = tracker.summary().await;
+        assert_eq!((summary.total, summary.successful), (2, 1));
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,242 @@
+use crate::bot::Context;
+use chrono::{DateTime, Utc};
+use serenity::all::{ChannelId, GuildId, Permissions, UserId};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Failures kept per guild; the oldest are dropped first
+pub const FAILURES_PER_GUILD: usize = 50;
+
+/// Which permission check stopped a command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckFailure {
+    /// Member lacks Manage Server
+    MissingManageGuild,
+    /// Member lacks other Discord permissions the command requires
+    MissingPermissions(Permissions),
+    /// Member is not the owner, has no Manage Server and holds no staff role
+    NotStaff,
+    /// Member is not one of the bot's owners
+    NotOwner,
+    /// Command group is confined to other channels; holds the one to use
+    WrongChannel(ChannelId),
+    /// Command, or the group holding it, is disabled in the guild
+    Disabled(String),
+    /// Member used the command too recently; holds the cooled-down command
+    /// and the seconds left
+    Cooldown(String, u64),
+}
+
+impl CheckFailure {
+    /// Classify missing Discord permissions reported by the framework
+    pub fn from_missing(missing: Permissions) -> Self {
+        if missing == Permissions::MANAGE_GUILD {
+            Self::MissingManageGuild
+        } else {
+            Self::MissingPermissions(missing)
+        }
+    }
+
+    /// Short name of the failing check
+    pub fn label(&self) -> String {
+        match self {
+            Self::MissingManageGuild => "Missing Manage Server".to_string(),
+            Self::MissingPermissions(missing) => format!("Missing permissions: {}", missing),
+            Self::NotStaff => "Not staff".to_string(),
+            Self::NotOwner => "Not a bot owner".to_string(),
+            Self::WrongChannel(channel) => format!("Used outside <#{}>", channel),
+            Self::Disabled(command) => format!("`/{}` disabled", command),
+            Self::Cooldown(command, _) => format!("`/{}` on cooldown", command),
+        }
+    }
+
+    /// What would let the member through this check
+    pub fn remedy(&self) -> &'static str {
+        match self {
+            Self::MissingManageGuild => "Give one of their roles the **Manage Server** permission.",
+            Self::MissingPermissions(_) => {
+                "Give one of their roles the listed Discord permissions."
+            }
+            Self::NotStaff => {
+                "Add one of their roles with `/settings staff add`, or give them **Manage Server**."
+            }
+            Self::NotOwner => "Only the bot's owners can use this command.",
+            Self::WrongChannel(_) => {
+                "Use the command in an allowed channel, or change them with `/settings commandchannel`."
+            }
+            Self::Disabled(_) => "Re-enable the command with `/settings commands enable`.",
+            Self::Cooldown(_, _) => {
+                "Wait for the cooldown, or change it with `/settings cooldown`."
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for CheckFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// One command that a member was refused
+#[derive(Debug, Clone)]
+pub struct PermissionFailure {
+    pub command: String,
+    pub user_id: UserId,
+    pub reason: CheckFailure,
+    pub at: DateTime<Utc>,
+}
+
+/// Recent permission-check failures, bounded per guild and never persisted
+#[derive(Debug, Clone, Default)]
+pub struct PermissionFailureLog {
+    guilds: Arc<Mutex<HashMap<GuildId, VecDeque<PermissionFailure>>>>,
+}
+
+impl PermissionFailureLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, guild_id: GuildId, failure: PermissionFailure) {
+        tracing::debug!(
+            guild_id = %guild_id,
+            user_id = %failure.user_id,
+            command = %failure.command,
+            reason = %failure.reason,
+            "Permission check failed"
+        );
+
+        let mut guilds = self.guilds.lock().unwrap_or_else(|e| e.into_inner());
+        let failures = guilds.entry(guild_id).or_default();
+        if failures.len() >= FAILURES_PER_GUILD {
+            failures.pop_front();
+        }
+        failures.push_back(failure);
+    }
+
+    /// Newest first, only from `guild_id`, optionally for one member
+    pub fn recent(&self, guild_id: GuildId, user_id: Option<UserId>) -> Vec<PermissionFailure> {
+        let guilds = self.guilds.lock().unwrap_or_else(|e| e.into_inner());
+        guilds
+            .get(&guild_id)
+            .map(|failures| {
+                failures
+                    .iter()
+                    .rev()
+                    .filter(|f| user_id.is_none_or(|id| f.user_id == id))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Record a failed check for the invoking member; ignored outside guilds
+pub fn record_check_failure(ctx: Context<'_>, reason: CheckFailure) {
+    if let Some(guild_id) = ctx.guild_id() {
+        ctx.data().permission_failures.record(
+            guild_id,
+            PermissionFailure {
+                command: ctx.command().qualified_name.clone(),
+                user_id: ctx.author().id,
+                reason,
+                at: Utc::now(),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure(command: &str, user: u64, reason: CheckFailure) -> PermissionFailure {
+        PermissionFailure {
+            command: command.to_string(),
+            user_id: UserId::new(user),
+            reason,
+            at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_buffer_drops_oldest_past_capacity() {
+        let log = PermissionFailureLog::new();
+        let guild = GuildId::new(1);
+
+        for i in 0..FAILURES_PER_GUILD + 5 {
+            log.record(
+                guild,
+                failure(&format!("cmd{}", i), 10, CheckFailure::NotStaff),
+            );
+        }
+
+        let recent = log.recent(guild, None);
+        assert_eq!(recent.len(), FAILURES_PER_GUILD);
+        assert_eq!(recent[0].command, format!("cmd{}", FAILURES_PER_GUILD + 4));
+        assert_eq!(recent.last().unwrap().command, "cmd5");
+    }
+
+    #[test]
+    fn test_recent_is_scoped_to_guild_and_user() {
+        let log = PermissionFailureLog::new();
+        let (ours, theirs) = (GuildId::new(1), GuildId::new(2));
+
+        log.record(
+            ours,
+            failure("boosterrole cleanup", 10, CheckFailure::NotStaff),
+        );
+        log.record(
+            ours,
+            failure("settings staff add", 11, CheckFailure::MissingManageGuild),
+        );
+        log.record(
+            theirs,
+            failure("boosterrole lock", 10, CheckFailure::NotStaff),
+        );
+
+        let all = log.recent(ours, None);
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().all(|f| f.command != "boosterrole lock"));
+
+        let member = log.recent(ours, Some(UserId::new(10)));
+        assert_eq!(member.len(), 1);
+        assert_eq!(member[0].command, "boosterrole cleanup");
+
+        assert!(log.recent(GuildId::new(3), None).is_empty());
+    }
+
+    #[test]
+    fn test_missing_permissions_taxonomy() {
+        assert_eq!(
+            CheckFailure::from_missing(Permissions::MANAGE_GUILD),
+            CheckFailure::MissingManageGuild
+        );
+        let both = Permissions::MANAGE_GUILD | Permissions::MANAGE_ROLES;
+        assert_eq!(
+            CheckFailure::from_missing(both),
+            CheckFailure::MissingPermissions(both)
+        );
+    }
+
+    #[test]
+    fn test_every_reason_names_check_and_remedy() {
+        let reasons = [
+            CheckFailure::MissingManageGuild,
+            CheckFailure::MissingPermissions(Permissions::BAN_MEMBERS),
+            CheckFailure::NotStaff,
+            CheckFailure::NotOwner,
+            CheckFailure::WrongChannel(ChannelId::new(1)),
+            CheckFailure::Disabled("boosterrole share".to_string()),
+            CheckFailure::Cooldown("boosterrole color".to_string(), 30),
+        ];
+        let labels: std::collections::HashSet<String> =
+            reasons.iter().map(CheckFailure::label).collect();
+        assert_eq!(labels.len(), reasons.len());
+        assert!(reasons.iter().all(|r| !r.remedy().is_empty()));
+        assert!(CheckFailure::NotStaff
+            .remedy()
+            .contains("/settings staff add"));
+    }
+}
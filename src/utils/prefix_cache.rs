@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Per-guild custom prefixes, looked up on every message.
+///
+/// `None` is cached too, so guilds without a custom prefix aren't
+/// re-queried; a missing entry means the guild hasn't been looked up yet.
+#[derive(Debug, Clone, Default)]
+pub struct PrefixCache {
+    entries: Arc<RwLock<HashMap<u64, Option<String>>>>,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+}
+
+/// Snapshot of the prefix cache for `cache_status`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrefixCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub custom: usize,
+    pub default: usize,
+}
+
+impl PrefixCacheStats {
+    /// Share of lookups answered without the database, as a percentage
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+        (total > 0).then(|| self.hits as f64 * 100.0 / total as f64)
+    }
+}
+
+impl PrefixCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `Some(prefix)` on a hit, `None` when the guild still needs a lookup
+    pub async fn get(&self, guild_id: u64) -> Option<Option<String>> {
+        let entries = self.entries.read().await;
+        let cached = entries.get(&guild_id).cloned();
+
+        let counter = if cached.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        cached
+    }
+
+    pub async fn store(&self, guild_id: u64, prefix: Option<String>) {
+        self.entries.write().await.insert(guild_id, prefix);
+    }
+
+    /// Forget a guild entirely, so its next message looks it up again
+    pub async fn forget(&self, guild_id: u64) {
+        self.entries.write().await.remove(&guild_id);
+    }
+
+    pub async fn stats(&self) -> PrefixCacheStats {
+        let entries = self.entries.read().await;
+        let custom = entries.values().filter(|prefix| prefix.is_some()).count();
+
+        PrefixCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            custom,
+            default: entries.len() - custom,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_default_prefix_is_cached_distinctly() {
+        let cache = PrefixCache::new();
+
+        assert_eq!(cache.get(1).await, None);
+        cache.store(1, None).await;
+        assert_eq!(cache.get(1).await, Some(None));
+
+        cache.store(2, Some("?".to_string())).await;
+        assert_eq!(cache.get(2).await, Some(Some("?".to_string())));
+
+        let stats = cache.stats().await;
+        assert_eq!(
+            stats,
+            PrefixCacheStats {
+                hits: 2,
+                misses: 1,
+                custom: 1,
+                default: 1,
+            }
+        );
+        assert_eq!(stats.hit_rate().map(|rate| rate.round()), Some(67.0));
+    }
+
+    #[tokio::test]
+    async fn test_forget_requires_a_fresh_lookup() {
+        let cache = PrefixCache::new();
+        cache.store(1, Some("$".to_string())).await;
+        cache.forget(1).await;
+
+        assert_eq!(cache.get(1).await, None);
+        assert_eq!(PrefixCache::new().stats().await.hit_rate(), None);
+    }
+}
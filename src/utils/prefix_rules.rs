@@ -0,0 +1,119 @@
+/// Prefixes every guild accepts on top of its own
+pub const ADDITIONAL_PREFIXES: &[&str] = &["death", "d!"];
+
+/// Longest prefix a guild may set, matching the `guild_prefixes` CHECK
+pub const MAX_PREFIX_LENGTH: usize = 5;
+
+/// Why a prefix can't be used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrefixProblem {
+    Empty,
+    TooLong,
+    /// Would be mistaken for a slash command
+    SlashStart,
+    /// Mentions and backticks break how prefixes are matched and shown
+    ForbiddenCharacter(char),
+}
+
+impl std::fmt::Display for PrefixProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Prefix can't be empty or only whitespace"),
+            Self::TooLong => write!(
+                f,
+                "Prefix must be at most {} characters long",
+                MAX_PREFIX_LENGTH
+            ),
+            Self::SlashStart => write!(
+                f,
+                "Prefix can't start with `/`, since Discord reserves it for slash commands"
+            ),
+            Self::ForbiddenCharacter(_) => write!(
+                f,
+                "Prefix can't contain `@`, `#`, `<`, backticks or spaces"
+            ),
+        }
+    }
+}
+
+/// Trim a requested prefix and check it can be used
+pub fn validate_prefix(input: &str) -> Result<String, PrefixProblem> {
+    let prefix = input.trim();
+
+    if prefix.is_empty() {
+        return Err(PrefixProblem::Empty);
+    }
+    if prefix.chars().count() > MAX_PREFIX_LENGTH {
+        return Err(PrefixProblem::TooLong);
+    }
+    if prefix.starts_with('/') {
+        return Err(PrefixProblem::SlashStart);
+    }
+    if let Some(c) = prefix
+        .chars()
+        .find(|c| matches!(c, '@' | '#' | '<' | '`') || c.is_whitespace())
+    {
+        return Err(PrefixProblem::ForbiddenCharacter(c));
+    }
+
+    Ok(prefix.to_string())
+}
+
+/// Whether a prefix is one every guild already accepts
+pub fn is_additional_prefix(prefix: &str) -> bool {
+    ADDITIONAL_PREFIXES
+        .iter()
+        .any(|additional| additional.eq_ignore_ascii_case(prefix))
+}
+
+/// Every prefix that works in a guild using `prefix`, for display
+pub fn effective_prefixes(prefix: &str) -> String {
+    std::iter::once(prefix)
+        .chain(
+            ADDITIONAL_PREFIXES
+                .iter()
+                .copied()
+                .filter(|additional| *additional != prefix),
+        )
+        .map(|p| format!("`{}`", p))
+        .collect::<Vec<_>>()
+        .join(", ")
+        + " or a mention of the bot"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_prefix() {
+        assert_eq!(validate_prefix("  ?? "), Ok("??".to_string()));
+        assert_eq!(validate_prefix("   "), Err(PrefixProblem::Empty));
+        assert_eq!(validate_prefix("toolong"), Err(PrefixProblem::TooLong));
+        assert_eq!(validate_prefix("/b"), Err(PrefixProblem::SlashStart));
+        assert_eq!(
+            validate_prefix("<@1"),
+            Err(PrefixProblem::ForbiddenCharacter('<'))
+        );
+        assert_eq!(
+            validate_prefix("a`"),
+            Err(PrefixProblem::ForbiddenCharacter('`'))
+        );
+        assert_eq!(
+            validate_prefix("a b"),
+            Err(PrefixProblem::ForbiddenCharacter(' '))
+        );
+        // Length is in characters, like the database CHECK
+        assert_eq!(validate_prefix("ñññññ"), Ok("ñññññ".to_string()));
+    }
+
+    #[test]
+    fn test_additional_prefixes() {
+        assert!(is_additional_prefix("D!"));
+        assert!(!is_additional_prefix("!"));
+        assert_eq!(
+            effective_prefixes("d!"),
+            "`d!`, `death` or a mention of the bot"
+        );
+    }
+}
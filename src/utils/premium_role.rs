@@ -0,0 +1,65 @@
+use serenity::all::{Member, RoleId, UserId};
+
+/// Members whose premium role doesn't match their boost state
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PremiumRoleSync {
+    /// Boosters missing the role
+    pub grant: Vec<UserId>,
+    /// Members holding the role without boosting
+    pub revoke: Vec<UserId>,
+    /// Boosters already holding the role
+    pub in_sync: usize,
+}
+
+impl PremiumRoleSync {
+    /// Compare each member's boost state with whether they hold the role.
+    /// Called once per page of members.
+    pub fn check<'a>(&mut self, members: impl IntoIterator<Item = &'a Member>, role_id: RoleId) {
+        for member in members {
+            let boosting = member.premium_since.is_some();
+            let holds_role = member.roles.contains(&role_id);
+            match (boosting, holds_role) {
+                (true, false) => self.grant.push(member.user.id),
+                (false, true) => self.revoke.push(member.user.id),
+                (true, true) => self.in_sync += 1,
+                (false, false) => {}
+            }
+        }
+    }
+
+    /// Role changes the sync has to make
+    pub fn changes(&self) -> usize {
+        self.grant.len() + self.revoke.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serenity::all::Timestamp;
+
+    const PREMIUM: RoleId = RoleId::new(500);
+
+    fn member(id: u64, boosting: bool, roles: &[u64]) -> Member {
+        let mut member = Member::default();
+        member.user.id = UserId::new(id);
+        member.premium_since = boosting.then(Timestamp::now);
+        member.roles = roles.iter().map(|id| RoleId::new(*id)).collect();
+        member
+    }
+
+    #[test]
+    fn test_sync_matches_role_to_boost_state() {
+        let mut sync = PremiumRoleSync::default();
+        sync.check(&[member(1, true, &[]), member(2, true, &[500])], PREMIUM);
+        sync.check(
+            &[member(3, false, &[500, 7]), member(4, false, &[7])],
+            PREMIUM,
+        );
+
+        assert_eq!(sync.grant, vec![UserId::new(1)]);
+        assert_eq!(sync.revoke, vec![UserId::new(3)]);
+        assert_eq!(sync.in_sync, 1);
+        assert_eq!(sync.changes(), 2);
+    }
+}
@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+/// Page size assumed for `/proc/self/statm`, which counts in pages
+const PAGE_SIZE: u64 = 4096;
+
+/// Resident memory of the bot process, or `None` off Linux
+pub fn resident_memory_bytes() -> Option<u64> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    parse_statm_resident(&statm)
+}
+
+/// Second field of statm is the resident set, in pages
+fn parse_statm_resident(statm: &str) -> Option<u64> {
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    Some(pages * PAGE_SIZE)
+}
+
+/// e.g. "512 B", "3.4 MB"
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// e.g. "3d 4h 12m", "5m 9s"
+pub fn format_uptime(uptime: Duration) -> String {
+    let secs = uptime.as_secs();
+    let (days, hours, minutes) = (secs / 86_400, secs / 3_600 % 24, secs / 60 % 60);
+
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m {}s", minutes, secs % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formats_sizes_and_uptime() {
+        assert_eq!(
+            parse_statm_resident("5000 1024 300 10 0 900 0\n"),
+            Some(4 * 1024 * 1024)
+        );
+        assert_eq!(parse_statm_resident(""), None);
+
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(3 * 1024 * 1024 + 400 * 1024), "3.4 MB");
+
+        assert_eq!(format_uptime(Duration::from_secs(309)), "5m 9s");
+        assert_eq!(
+            format_uptime(Duration::from_secs(3 * 86_400 + 4 * 3_600 + 720)),
+            "3d 4h 12m"
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use crate::bot::{Context, Error};
+use poise::serenity_prelude::{CreateEmbed, CreateMessage, EditMessage, Message};
+use poise::{CreateReply, ReplyHandle};
+use std::time::{Duration, Instant};
+
+/// How long Discord accepts edits to an interaction response
+pub const INTERACTION_TOKEN_LIFETIME: Duration = Duration::from_secs(15 * 60);
+
+/// Switch to channel messages this long before the token expires, so an
+/// edit that's already in flight doesn't race the deadline
+pub const DEADLINE_MARGIN: Duration = Duration::from_secs(60);
+
+/// Where the next progress update should go
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressRoute {
+    /// Send or edit the command's own response
+    Reply,
+    /// Post or edit a regular message in the invocation channel
+    Channel,
+}
+
+/// When an interaction's token stops working. Prefix invocations have no
+/// token, so they always reply normally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InteractionDeadline {
+    expires_at: Option<Instant>,
+}
+
+impl InteractionDeadline {
+    /// An interaction created `elapsed` before `now`
+    pub fn slash(now: Instant, elapsed: Duration) -> Self {
+        let started = now.checked_sub(elapsed).unwrap_or(now);
+        Self {
+            expires_at: Some(started + INTERACTION_TOKEN_LIFETIME),
+        }
+    }
+
+    pub fn prefix() -> Self {
+        Self { expires_at: None }
+    }
+
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.expires_at
+            .map(|expires_at| expires_at.saturating_duration_since(now))
+    }
+
+    pub fn route_at(&self, now: Instant) -> ProgressRoute {
+        match self.remaining(now) {
+            Some(remaining) if remaining <= DEADLINE_MARGIN => ProgressRoute::Channel,
+            _ => ProgressRoute::Reply,
+        }
+    }
+}
+
+/// Shown on the first channel message so members can find the command
+pub fn continuation_note(command: &str, original: Option<&str>) -> String {
+    match original {
+        Some(link) => format!(
+            "⏱️ The response to `/{}` timed out, so updates continue here. Original: {}",
+            command, link
+        ),
+        None => format!(
+            "⏱️ The response to `/{}` timed out, so updates continue here.",
+            command
+        ),
+    }
+}
+
+/// Reports progress for a long-running command, moving to a channel message
+/// before the interaction token expires
+pub struct ProgressReporter<'a> {
+    ctx: Context<'a>,
+    deadline: InteractionDeadline,
+    reply: Option<ReplyHandle<'a>>,
+    original_link: Option<String>,
+    channel_message: Option<Message>,
+}
+
+impl<'a> ProgressReporter<'a> {
+    pub fn new(ctx: Context<'a>) -> Self {
+        let deadline = match ctx {
+            poise::Context::Application(_) => {
+                let elapsed = poise::serenity_prelude::Timestamp::now().unix_timestamp()
+                    - ctx.created_at().unix_timestamp();
+                InteractionDeadline::slash(
+                    Instant::now(),
+                    Duration::from_secs(elapsed.max(0) as u64),
+                )
+            }
+            poise::Context::Prefix(_) => InteractionDeadline::prefix(),
+        };
+
+        Self {
+            ctx,
+            deadline,
+            reply: None,
+            original_link: None,
+            channel_message: None,
+        }
+    }
+
+    /// Show `embed` as the current progress or final result
+    pub async fn update(&mut self, embed: CreateEmbed) -> Result<(), Error> {
+        let http = &self.ctx.serenity_context().http;
+
+        if let Some(message) = &mut self.channel_message {
+            message.edit(http, EditMessage::new().embed(embed)).await?;
+            return Ok(());
+        }
+
+        if self.deadline.route_at(Instant::now()) == ProgressRoute::Reply {
+            let reply = CreateReply::default().embed(embed);
+            match &self.reply {
+                Some(handle) => handle.edit(self.ctx, reply).await?,
+                None => {
+                    let handle = self.ctx.send(reply).await?;
+                    // Fetch the link now; it can't be looked up once the token expires
+                    self.original_link = handle.message().await.ok().map(|m| m.link());
+                    self.reply = Some(handle);
+                }
+            }
+            return Ok(());
+        }
+
+        let note = continuation_note(
+            &self.ctx.command().qualified_name,
+            self.original_link.as_deref(),
+        );
+        let message = self
+            .ctx
+            .channel_id()
+            .send_message(http, CreateMessage::new().content(note).embed(embed))
+            .await?;
+        tracing::info!(
+            command = %self.ctx.command().qualified_name,
+            channel_id = %self.ctx.channel_id(),
+            "Interaction token near expiry; progress moved to a channel message"
+        );
+        self.channel_message = Some(message);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fresh_interaction_replies() {
+        let now = Instant::now();
+        let deadline = InteractionDeadline::slash(now, Duration::from_secs(2));
+        assert_eq!(deadline.route_at(now), ProgressRoute::Reply);
+        assert_eq!(
+            deadline.remaining(now),
+            Some(INTERACTION_TOKEN_LIFETIME - Duration::from_secs(2))
+        );
+    }
+
+    #[test]
+    fn test_switches_before_expiry() {
+        let now = Instant::now();
+        let deadline = InteractionDeadline::slash(now, Duration::ZERO);
+
+        let just_before =
+            now + INTERACTION_TOKEN_LIFETIME - DEADLINE_MARGIN - Duration::from_secs(1);
+        assert_eq!(deadline.route_at(just_before), ProgressRoute::Reply);
+
+        let at_margin = now + INTERACTION_TOKEN_LIFETIME - DEADLINE_MARGIN;
+        assert_eq!(deadline.route_at(at_margin), ProgressRoute::Channel);
+
+        let long_after = now + INTERACTION_TOKEN_LIFETIME * 2;
+        assert_eq!(deadline.route_at(long_after), ProgressRoute::Channel);
+        assert_eq!(deadline.remaining(long_after), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_old_interaction_accounts_for_elapsed_time() {
+        let now = Instant::now();
+        let deadline = InteractionDeadline::slash(now, Duration::from_secs(14 * 60 + 30));
+        assert_eq!(deadline.route_at(now), ProgressRoute::Channel);
+    }
+
+    #[test]
+    fn test_prefix_never_switches() {
+        let now = Instant::now();
+        let deadline = InteractionDeadline::prefix();
+        assert_eq!(deadline.remaining(now), None);
+        assert_eq!(
+            deadline.route_at(now + INTERACTION_TOKEN_LIFETIME * 4),
+            ProgressRoute::Reply
+        );
+    }
+
+    #[test]
+    fn test_continuation_note_links_back() {
+        let note = continuation_note(
+            "boosterrole cleanup",
+            Some("https://discord.com/channels/1/2/3"),
+        );
+        assert!(note.contains("/boosterrole cleanup"));
+        assert!(note.ends_with("https://discord.com/channels/1/2/3"));
+        assert!(!continuation_note("boosterrole cleanup", None).contains("Original"));
+    }
+}
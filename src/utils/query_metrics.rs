@@ -0,0 +1,224 @@
+use serenity::all::GuildId;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Default duration above which a query is logged as slow
+pub const DEFAULT_SLOW_QUERY_THRESHOLD_MS: u64 = 50;
+
+/// Samples kept per query name; older samples are overwritten
+const SAMPLES_PER_QUERY: usize = 512;
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    duration: Duration,
+    recorded_at: Instant,
+}
+
+/// Fixed-size ring buffer of recent durations for one query name
+#[derive(Debug, Default)]
+struct QuerySamples {
+    samples: VecDeque<Sample>,
+}
+
+impl QuerySamples {
+    fn push(&mut self, sample: Sample) {
+        if self.samples.len() == SAMPLES_PER_QUERY {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+}
+
+/// Aggregated latency for one query name over a time window
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuerySummary {
+    pub name: &'static str,
+    pub count: usize,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Per-query latency aggregates with slow-query logging
+#[derive(Debug)]
+pub struct QueryMetrics {
+    queries: Mutex<HashMap<&'static str, QuerySamples>>,
+    slow_threshold_ms: AtomicU64,
+}
+
+impl Default for QueryMetrics {
+    fn default() -> Self {
+        Self {
+            queries: Mutex::new(HashMap::new()),
+            slow_threshold_ms: AtomicU64::new(DEFAULT_SLOW_QUERY_THRESHOLD_MS),
+        }
+    }
+}
+
+impl QueryMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_slow_threshold_ms(&self, threshold_ms: u64) {
+        self.slow_threshold_ms.store(threshold_ms, Ordering::Relaxed);
+    }
+
+    pub fn slow_threshold(&self) -> Duration {
+        Duration::from_millis(self.slow_threshold_ms.load(Ordering::Relaxed))
+    }
+
+    pub fn record(&self, name: &'static str, duration: Duration) {
+        self.record_at(name, duration, Instant::now());
+    }
+
+    fn record_at(&self, name: &'static str, duration: Duration, recorded_at: Instant) {
+        let mut queries = self.queries.lock().unwrap_or_else(|e| e.into_inner());
+        queries.entry(name).or_default().push(Sample {
+            duration,
+            recorded_at,
+        });
+    }
+
+    /// Summaries for samples recorded within `window`, slowest p95 first
+    pub fn summaries(&self, window: Duration) -> Vec<QuerySummary> {
+        let queries = self.queries.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut summaries: Vec<QuerySummary> = queries
+            .iter()
+            .filter_map(|(name, samples)| {
+                let mut durations: Vec<f64> = samples
+                    .samples
+                    .iter()
+                    .filter(|s| s.recorded_at.elapsed() <= window)
+                    .map(|s| s.duration.as_secs_f64() * 1000.0)
+                    .collect();
+
+                if durations.is_empty() {
+                    return None;
+                }
+
+                durations.sort_by(|a, b| a.total_cmp(b));
+
+                Some(QuerySummary {
+                    name,
+                    count: durations.len(),
+                    p50_ms: percentile(&durations, 50.0),
+                    p95_ms: percentile(&durations, 95.0),
+                    max_ms: durations[durations.len() - 1],
+                })
+            })
+            .collect();
+
+        summaries.sort_by(|a, b| b.p95_ms.total_cmp(&a.p95_ms));
+        summaries
+    }
+}
+
+/// Nearest-rank percentile over already sorted values
+pub(crate) fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Process-wide query metrics, shared by all model calls
+pub fn query_metrics() -> &'static QueryMetrics {
+    static METRICS: OnceLock<QueryMetrics> = OnceLock::new();
+    METRICS.get_or_init(QueryMetrics::new)
+}
+
+/// Time a database call, recording it under `name` and warning when it is slow
+pub async fn timed_query<F, T>(name: &'static str, guild_id: GuildId, query: F) -> T
+where
+    F: Future<Output = T>,
+{
+    let start = Instant::now();
+    let result = query.await;
+    let elapsed = start.elapsed();
+
+    let metrics = query_metrics();
+    metrics.record(name, elapsed);
+
+    if elapsed > metrics.slow_threshold() {
+        tracing::warn!(
+            query = name,
+            guild_id = %guild_id,
+            duration_ms = elapsed.as_millis() as u64,
+            threshold_ms = metrics.slow_threshold().as_millis() as u64,
+            "Slow database query"
+        );
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles() {
+        let metrics = QueryMetrics::new();
+        for ms in 1..=100 {
+            metrics.record("get_all_for_guild", Duration::from_millis(ms));
+        }
+
+        let summaries = metrics.summaries(Duration::from_secs(60));
+        assert_eq!(summaries.len(), 1);
+
+        let summary = &summaries[0];
+        assert_eq!(summary.count, 100);
+        assert_eq!(summary.p50_ms, 50.0);
+        assert_eq!(summary.p95_ms, 95.0);
+        assert_eq!(summary.max_ms, 100.0);
+    }
+
+    #[test]
+    fn test_ring_buffer_caps_samples() {
+        let metrics = QueryMetrics::new();
+        for _ in 0..SAMPLES_PER_QUERY {
+            metrics.record("check_limit", Duration::from_millis(500));
+        }
+        for _ in 0..SAMPLES_PER_QUERY {
+            metrics.record("check_limit", Duration::from_millis(1));
+        }
+
+        let summary = &metrics.summaries(Duration::from_secs(60))[0];
+        assert_eq!(summary.count, SAMPLES_PER_QUERY);
+        assert_eq!(summary.max_ms, 1.0);
+    }
+
+    #[test]
+    fn test_sorted_slowest_first_and_windowed() {
+        let metrics = QueryMetrics::new();
+        metrics.record("fast", Duration::from_millis(2));
+        metrics.record("slow", Duration::from_millis(80));
+
+        let names: Vec<_> = metrics
+            .summaries(Duration::from_secs(60))
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec!["slow", "fast"]);
+
+        if let Some(old) = Instant::now().checked_sub(Duration::from_secs(120)) {
+            let metrics = QueryMetrics::new();
+            metrics.record_at("stale", Duration::from_millis(5), old);
+            assert!(metrics.summaries(Duration::from_secs(60)).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_threshold_configurable() {
+        let metrics = QueryMetrics::new();
+        assert_eq!(
+            metrics.slow_threshold(),
+            Duration::from_millis(DEFAULT_SLOW_QUERY_THRESHOLD_MS)
+        );
+        metrics.set_slow_threshold_ms(200);
+        assert_eq!(metrics.slow_threshold(), Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,428 @@
+use chrono::{DateTime, Duration, Timelike, Utc};
+use serenity::all::UserId;
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+/// Furthest UTC offsets in use, in minutes
+const MIN_OFFSET_MINUTES: i32 = -12 * 60;
+const MAX_OFFSET_MINUTES: i32 = 14 * 60;
+
+/// Most member lines in one summary embed before the rest are counted
+pub const MAX_SUMMARY_LINES: usize = 40;
+
+/// Most embeds posted for one quiet period's summary
+pub const MAX_SUMMARY_EMBEDS: usize = 5;
+
+/// A daily window, in the guild's local time, when automated messages wait
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietWindow {
+    /// Minutes after local midnight the window opens
+    pub start_minute: u16,
+    /// Minutes after local midnight the window closes
+    pub end_minute: u16,
+    /// Local time minus UTC, in minutes
+    pub utc_offset_minutes: i32,
+}
+
+impl QuietWindow {
+    /// Minutes after local midnight at `now`
+    fn local_minute(&self, now: DateTime<Utc>) -> i64 {
+        let utc_minute = (now.hour() * 60 + now.minute()) as i64;
+        (utc_minute + self.utc_offset_minutes as i64).rem_euclid(MINUTES_PER_DAY)
+    }
+
+    /// Whether `now` falls inside the window. Windows where the end is
+    /// earlier than the start run across midnight.
+    pub fn contains(&self, now: DateTime<Utc>) -> bool {
+        let minute = self.local_minute(now);
+        let (start, end) = (self.start_minute as i64, self.end_minute as i64);
+
+        if start <= end {
+            (start..end).contains(&minute)
+        } else {
+            minute >= start || minute < end
+        }
+    }
+
+    /// The next moment the window closes after `now`
+    pub fn next_end(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let minute = self.local_minute(now);
+        let until_end = (self.end_minute as i64 - minute).rem_euclid(MINUTES_PER_DAY);
+        let until_end = if until_end == 0 {
+            MINUTES_PER_DAY
+        } else {
+            until_end
+        };
+
+        let start_of_minute = now
+            .with_second(0)
+            .and_then(|t| t.with_nanosecond(0))
+            .unwrap_or(now);
+        start_of_minute + Duration::minutes(until_end)
+    }
+
+    /// `22:00–07:00 (UTC+02:00)`
+    pub fn describe(&self) -> String {
+        format!(
+            "{}–{} ({})",
+            format_minute(self.start_minute),
+            format_minute(self.end_minute),
+            format_offset(self.utc_offset_minutes)
+        )
+    }
+}
+
+/// Automated messages the gate knows about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutomatedMessage {
+    JoinLog,
+    LeaveLog,
+    Spotlight,
+    /// Congratulations for a new booster, posted in the join-log channel
+    BoostAnnouncement,
+}
+
+/// What to do with an automated message right now
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuietDecision {
+    Send,
+    /// Drop the message, or queue it for the end-of-window summary
+    Suppress {
+        queue: bool,
+    },
+    /// Try again once the window closes
+    DelayUntil(DateTime<Utc>),
+}
+
+/// Decide whether an automated message may be sent at `now`.
+/// Command responses never go through this gate.
+pub fn gate(
+    window: Option<&QuietWindow>,
+    queue_logs: bool,
+    message: AutomatedMessage,
+    now: DateTime<Utc>,
+) -> QuietDecision {
+    let Some(window) = window.filter(|w| w.contains(now)) else {
+        return QuietDecision::Send;
+    };
+
+    match message {
+        AutomatedMessage::JoinLog | AutomatedMessage::LeaveLog => {
+            QuietDecision::Suppress { queue: queue_logs }
+        }
+        AutomatedMessage::Spotlight => QuietDecision::DelayUntil(window.next_end(now)),
+        // Only joins and leaves fit the end-of-window summary
+        AutomatedMessage::BoostAnnouncement => QuietDecision::Suppress { queue: false },
+    }
+}
+
+/// Parse `HH:MM` (24-hour) or a bare hour into minutes after midnight
+pub fn parse_time(input: &str) -> Result<u16, String> {
+    let input = input.trim();
+    let (hours, minutes) = match input.split_once(':') {
+        Some((h, m)) => (h, m),
+        None => (input, "0"),
+    };
+
+    let invalid = || {
+        format!(
+            "`{}` isn't a time. Use 24-hour `HH:MM`, like `22:30`",
+            input
+        )
+    };
+    let hours: u16 = hours.parse().map_err(|_| invalid())?;
+    let minutes: u16 = minutes.parse().map_err(|_| invalid())?;
+
+    if hours > 23 || minutes > 59 {
+        return Err(invalid());
+    }
+    Ok(hours * 60 + minutes)
+}
+
+/// Parse `UTC`, `UTC+2`, `GMT-5`, `+05:30` or `-0330` into a UTC offset in minutes
+pub fn parse_utc_offset(input: &str) -> Result<i32, String> {
+    let trimmed = input.trim();
+    let upper = trimmed.to_ascii_uppercase();
+    let rest = upper
+        .strip_prefix("UTC")
+        .or_else(|| upper.strip_prefix("GMT"))
+        .unwrap_or(&upper)
+        .trim();
+
+    if rest.is_empty() || rest == "Z" {
+        return Ok(0);
+    }
+
+    let invalid = || {
+        format!(
+            "`{}` isn't a UTC offset. Use a form like `UTC+2`, `UTC-5` or `+05:30`",
+            trimmed
+        )
+    };
+
+    let (sign, digits) = match rest.as_bytes()[0] {
+        b'+' => (1, &rest[1..]),
+        b'-' => (-1, &rest[1..]),
+        _ => return Err(invalid()),
+    };
+
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((h, m)) => (h, m),
+        None if digits.len() == 4 => digits.split_at(2),
+        None => (digits, "0"),
+    };
+    let hours: i32 = hours.parse().map_err(|_| invalid())?;
+    let minutes: i32 = minutes.parse().map_err(|_| invalid())?;
+    if minutes > 59 {
+        return Err(invalid());
+    }
+
+    let offset = sign * (hours * 60 + minutes);
+    if !(MIN_OFFSET_MINUTES..=MAX_OFFSET_MINUTES).contains(&offset) {
+        return Err(invalid());
+    }
+    Ok(offset)
+}
+
+pub fn format_minute(minute: u16) -> String {
+    format!("{:02}:{:02}", minute / 60, minute % 60)
+}
+
+pub fn format_offset(offset_minutes: i32) -> String {
+    if offset_minutes == 0 {
+        return "UTC".to_string();
+    }
+    let sign = if offset_minutes < 0 { '-' } else { '+' };
+    let abs = offset_minutes.unsigned_abs();
+    format!("UTC{}{:02}:{:02}", sign, abs / 60, abs % 60)
+}
+
+/// A join or leave held back during quiet hours
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedMemberEvent {
+    pub user_id: UserId,
+    pub user_tag: String,
+    pub joined: bool,
+    pub at: DateTime<Utc>,
+}
+
+/// Embed descriptions summarizing held-back joins and leaves, oldest first.
+///
+/// Each description stays under `max_len` characters and holds at most
+/// [`MAX_SUMMARY_LINES`] members; past [`MAX_SUMMARY_EMBEDS`] descriptions
+/// the remaining events are only counted.
+pub fn summarize_member_events(events: &[QueuedMemberEvent], max_len: usize) -> Vec<String> {
+    if events.is_empty() {
+        return Vec::new();
+    }
+
+    let joins = events.iter().filter(|e| e.joined).count();
+    let header = format!(
+        "While quiet hours were on, **{}** member{} joined and **{}** left.\n\n",
+        joins,
+        if joins == 1 { "" } else { "s" },
+        events.len() - joins
+    );
+
+    let tail_room = format!("…and {} more", events.len()).chars().count();
+    let mut pages: Vec<String> = Vec::new();
+    let mut current = header;
+    let mut lines_in_current = 0;
+
+    for (i, event) in events.iter().enumerate() {
+        let line = format!(
+            "{} <@{}> ({}) <t:{}:t>\n",
+            if event.joined { "📥" } else { "📤" },
+            event.user_id,
+            event.user_tag,
+            event.at.timestamp()
+        );
+
+        // Every page keeps room for the "…and N more" tail
+        let full = lines_in_current >= MAX_SUMMARY_LINES
+            || current.chars().count() + line.chars().count() + tail_room > max_len;
+        if full {
+            if pages.len() + 1 >= MAX_SUMMARY_EMBEDS {
+                current.push_str(&format!("…and {} more", events.len() - i));
+                pages.push(current);
+                return pages;
+            }
+            pages.push(current.trim_end().to_string());
+            current = String::new();
+            lines_in_current = 0;
+        }
+
+        current.push_str(&line);
+        lines_in_current += 1;
+    }
+
+    pages.push(current.trim_end().to_string());
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::spotlight::parse_sqlite_timestamp;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        parse_sqlite_timestamp(value).unwrap()
+    }
+
+    fn window(start: &str, end: &str, offset: i32) -> QuietWindow {
+        QuietWindow {
+            start_minute: parse_time(start).unwrap(),
+            end_minute: parse_time(end).unwrap(),
+            utc_offset_minutes: offset,
+        }
+    }
+
+    #[test]
+    fn test_same_day_window() {
+        let w = window("01:00", "06:00", 0);
+        assert!(!w.contains(at("2024-06-01 00:59:00")));
+        assert!(w.contains(at("2024-06-01 01:00:00")));
+        assert!(w.contains(at("2024-06-01 05:59:00")));
+        assert!(!w.contains(at("2024-06-01 06:00:00")));
+    }
+
+    #[test]
+    fn test_window_across_midnight() {
+        let w = window("22:00", "07:00", 0);
+        assert!(w.contains(at("2024-06-01 23:30:00")));
+        assert!(w.contains(at("2024-06-02 00:00:00")));
+        assert!(w.contains(at("2024-06-02 06:59:00")));
+        assert!(!w.contains(at("2024-06-02 07:00:00")));
+        assert!(!w.contains(at("2024-06-01 21:59:00")));
+        assert_eq!(
+            w.next_end(at("2024-06-01 23:30:15")),
+            at("2024-06-02 07:00:00")
+        );
+        assert_eq!(
+            w.next_end(at("2024-06-02 03:00:00")),
+            at("2024-06-02 07:00:00")
+        );
+    }
+
+    #[test]
+    fn test_offsets_shift_the_window() {
+        // 22:00–07:00 at UTC+10 is 12:00–21:00 UTC
+        let east = window("22:00", "07:00", 600);
+        assert!(east.contains(at("2024-06-01 12:00:00")));
+        assert!(!east.contains(at("2024-06-01 21:00:00")));
+        assert_eq!(
+            east.next_end(at("2024-06-01 15:00:00")),
+            at("2024-06-01 21:00:00")
+        );
+
+        // 01:00–05:00 at UTC-05:30 is 06:30–10:30 UTC
+        let west = window("01:00", "05:00", -330);
+        assert!(west.contains(at("2024-06-01 06:30:00")));
+        assert!(!west.contains(at("2024-06-01 06:29:00")));
+        assert!(!west.contains(at("2024-06-01 10:30:00")));
+    }
+
+    #[test]
+    fn test_parse_time_and_offset() {
+        assert_eq!(parse_time("22:30"), Ok(22 * 60 + 30));
+        assert_eq!(parse_time("7"), Ok(7 * 60));
+        assert!(parse_time("24:00").is_err());
+        assert!(parse_time("10pm").is_err());
+
+        assert_eq!(parse_utc_offset("UTC"), Ok(0));
+        assert_eq!(parse_utc_offset("utc+2"), Ok(120));
+        assert_eq!(parse_utc_offset("GMT-5"), Ok(-300));
+        assert_eq!(parse_utc_offset("+05:30"), Ok(330));
+        assert_eq!(parse_utc_offset("-0330"), Ok(-210));
+        assert!(parse_utc_offset("UTC+15").is_err());
+        assert!(parse_utc_offset("Europe/Berlin").is_err());
+
+        assert_eq!(format_offset(-210), "UTC-03:30");
+        assert_eq!(
+            window("22:00", "07:00", 120).describe(),
+            "22:00–07:00 (UTC+02:00)"
+        );
+    }
+
+    #[test]
+    fn test_gate_decisions() {
+        let w = window("22:00", "07:00", 0);
+        let quiet = at("2024-06-01 23:00:00");
+        let awake = at("2024-06-01 12:00:00");
+
+        assert_eq!(
+            gate(Some(&w), true, AutomatedMessage::JoinLog, quiet),
+            QuietDecision::Suppress { queue: true }
+        );
+        assert_eq!(
+            gate(Some(&w), false, AutomatedMessage::LeaveLog, quiet),
+            QuietDecision::Suppress { queue: false }
+        );
+        assert_eq!(
+            gate(Some(&w), true, AutomatedMessage::Spotlight, quiet),
+            QuietDecision::DelayUntil(at("2024-06-02 07:00:00"))
+        );
+        assert_eq!(
+            gate(Some(&w), true, AutomatedMessage::BoostAnnouncement, quiet),
+            QuietDecision::Suppress { queue: false }
+        );
+        assert_eq!(
+            gate(Some(&w), true, AutomatedMessage::JoinLog, awake),
+            QuietDecision::Send
+        );
+        assert_eq!(
+            gate(None, true, AutomatedMessage::Spotlight, quiet),
+            QuietDecision::Send
+        );
+    }
+
+    fn events(count: usize) -> Vec<QueuedMemberEvent> {
+        (0..count)
+            .map(|i| QueuedMemberEvent {
+                user_id: UserId::new(100_000_000_000_000_000 + i as u64),
+                user_tag: format!("member_with_a_long_name_{}", i),
+                joined: i % 3 != 0,
+                at: at("2024-06-01 23:00:00") + Duration::minutes(i as i64),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_summary_counts_joins_and_leaves() {
+        let pages = summarize_member_events(&events(3), 4096);
+        assert_eq!(pages.len(), 1);
+        assert!(
+            pages[0].starts_with("While quiet hours were on, **2** members joined and **1** left.")
+        );
+        assert_eq!(pages[0].matches("<@").count(), 3);
+        assert!(summarize_member_events(&[], 4096).is_empty());
+    }
+
+    #[test]
+    fn test_summary_respects_embed_limits_for_busy_guilds() {
+        let busy = events(2_000);
+        let pages = summarize_member_events(&busy, 4096);
+
+        assert_eq!(pages.len(), MAX_SUMMARY_EMBEDS);
+        assert!(pages.iter().all(|p| p.chars().count() <= 4096));
+        assert!(pages
+            .iter()
+            .all(|p| p.matches("<@").count() <= MAX_SUMMARY_LINES));
+        assert!(pages.last().unwrap().ends_with("more"));
+
+        let listed: usize = pages.iter().map(|p| p.matches("<@").count()).sum();
+        let more: usize = pages
+            .last()
+            .unwrap()
+            .rsplit("…and ")
+            .next()
+            .unwrap()
+            .trim_end_matches(" more")
+            .parse()
+            .unwrap();
+        assert_eq!(listed + more, busy.len());
+
+        let tight = summarize_member_events(&events(50), 500);
+        assert!(tight.iter().all(|p| p.chars().count() <= 500));
+    }
+}
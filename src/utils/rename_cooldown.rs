@@ -0,0 +1,91 @@
+use chrono::{DateTime, Duration, Utc};
+
+/// Cooldown between renames when a guild hasn't set its own
+pub const DEFAULT_RENAME_COOLDOWN_MINUTES: i64 = 60;
+
+/// Longest cooldown a guild can choose (one week)
+pub const MAX_RENAME_COOLDOWN_MINUTES: i64 = 7 * 24 * 60;
+
+/// The guild's cooldown, or the default, never outside the allowed range
+pub fn effective_cooldown(configured: Option<i64>) -> i64 {
+    configured
+        .unwrap_or(DEFAULT_RENAME_COOLDOWN_MINUTES)
+        .clamp(0, MAX_RENAME_COOLDOWN_MINUTES)
+}
+
+/// Time left before a member may rename again, if any
+pub fn cooldown_remaining(
+    last_rename: DateTime<Utc>,
+    cooldown_minutes: i64,
+    now: DateTime<Utc>,
+) -> Option<Duration> {
+    let remaining = last_rename + Duration::minutes(cooldown_minutes) - now;
+    (remaining > Duration::zero()).then_some(remaining)
+}
+
+/// Short human form such as `1h 5m`, `12m` or `40s`; partial minutes round up
+pub fn format_remaining(remaining: Duration) -> String {
+    let seconds = remaining.num_seconds().max(0);
+    if seconds < 60 {
+        return format!("{}s", seconds.max(1));
+    }
+
+    let minutes = (seconds + 59) / 60;
+    let (days, hours, minutes) = (minutes / 1440, minutes % 1440 / 60, minutes % 60);
+    [(days, "d"), (hours, "h"), (minutes, "m")]
+        .iter()
+        .filter(|(amount, _)| *amount > 0)
+        .map(|(amount, unit)| format!("{}{}", amount, unit))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A cooldown for settings views and footers
+pub fn describe_cooldown(minutes: i64) -> String {
+    if minutes == 0 {
+        "no cooldown".to_string()
+    } else {
+        format_remaining(Duration::minutes(minutes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 6, 14, 12, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_default_and_clamp() {
+        assert_eq!(effective_cooldown(None), DEFAULT_RENAME_COOLDOWN_MINUTES);
+        assert_eq!(effective_cooldown(Some(15)), 15);
+        assert_eq!(effective_cooldown(Some(0)), 0);
+        assert_eq!(
+            effective_cooldown(Some(1_000_000)),
+            MAX_RENAME_COOLDOWN_MINUTES
+        );
+    }
+
+    #[test]
+    fn test_remaining_from_last_rename() {
+        assert_eq!(
+            cooldown_remaining(at(0), 30, at(10)),
+            Some(Duration::minutes(20))
+        );
+        assert_eq!(cooldown_remaining(at(0), 30, at(30)), None);
+        assert_eq!(cooldown_remaining(at(0), 0, at(0)), None);
+    }
+
+    #[test]
+    fn test_format_remaining() {
+        assert_eq!(format_remaining(Duration::seconds(40)), "40s");
+        assert_eq!(format_remaining(Duration::seconds(61)), "2m");
+        assert_eq!(format_remaining(Duration::minutes(65)), "1h 5m");
+        assert_eq!(format_remaining(Duration::minutes(60)), "1h");
+        assert_eq!(format_remaining(Duration::minutes(1441)), "1d 1m");
+        assert_eq!(describe_cooldown(0), "no cooldown");
+    }
+}
@@ -1,8 +1,50 @@
 use crate::bot::{Context, Error};
+use crate::data::models::GuildNextStepsSetting;
 use crate::utils::embed_builder::{EmbedBuilder, EmbedColor};
-use poise::serenity_prelude::CreateEmbed;
+use crate::utils::embed_fallback::{embed_to_text, is_missing_permissions};
+use crate::utils::embed_theme::EmbedTheme;
+use crate::utils::next_steps::{render_next_steps, NEXT_STEPS_FIELD_TITLE};
+use poise::serenity_prelude::{CreateEmbed, CreateMessage};
 use poise::{CreateReply, ReplyHandle};
 
+/// Where a private reply goes when the invocation can't take an ephemeral one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PrivateReplyFallback {
+    /// Reply in the channel as usual
+    #[default]
+    Channel,
+    /// DM the member, replying in the channel if their DMs are closed
+    DirectMessage,
+}
+
+impl PrivateReplyFallback {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "channel" => Some(Self::Channel),
+            "dm" => Some(Self::DirectMessage),
+            _ => None,
+        }
+    }
+}
+
+/// How a reply is delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Delivery {
+    Public,
+    Ephemeral,
+    DirectMessage,
+}
+
+/// Only interactions can reply ephemerally; prefix invocations use the fallback
+fn delivery(is_prefix: bool, ephemeral: bool, fallback: PrivateReplyFallback) -> Delivery {
+    match (ephemeral, is_prefix, fallback) {
+        (false, _, _) => Delivery::Public,
+        (true, false, _) => Delivery::Ephemeral,
+        (true, true, PrivateReplyFallback::Channel) => Delivery::Public,
+        (true, true, PrivateReplyFallback::DirectMessage) => Delivery::DirectMessage,
+    }
+}
+
 pub struct ResponseHelper;
 
 impl ResponseHelper {
@@ -12,7 +54,7 @@ impl ResponseHelper {
         title: impl Into<String>,
         description: impl Into<String>,
     ) -> Result<ReplyHandle<'_>, Error> {
-        let embed = EmbedBuilder::success(title, description);
+        let embed = EmbedBuilder::themed_success(&Self::theme(ctx).await, title, description);
         Self::send_embed(ctx, embed).await
     }
 
@@ -21,10 +63,20 @@ impl ResponseHelper {
         title: impl Into<String>,
         description: impl Into<String>,
     ) -> Result<ReplyHandle<'_>, Error> {
-        let embed = EmbedBuilder::error(title, description);
+        let embed = EmbedBuilder::themed_error(&Self::theme(ctx).await, title, description);
         Self::send_embed(ctx, embed).await
     }
 
+    /// [`Self::send_error`], shown only to the invoking member where possible
+    pub async fn send_error_ephemeral(
+        ctx: Context<'_>,
+        title: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Result<(), Error> {
+        let embed = EmbedBuilder::themed_error(&Self::theme(ctx).await, title, description);
+        Self::send_embed_with(ctx, embed, true).await.map(|_| ())
+    }
+
     #[allow(dead_code)]
     pub async fn send_warning(
         ctx: Context<'_>,
@@ -41,7 +93,7 @@ impl ResponseHelper {
         title: impl Into<String>,
         description: impl Into<String>,
     ) -> Result<ReplyHandle<'_>, Error> {
-        let embed = EmbedBuilder::info(title, description);
+        let embed = EmbedBuilder::themed_info(&Self::theme(ctx).await, title, description);
         Self::send_embed(ctx, embed).await
     }
 
@@ -51,16 +103,160 @@ impl ResponseHelper {
         title: impl Into<String>,
         description: impl Into<String>,
     ) -> Result<ReplyHandle<'_>, Error> {
-        let embed = EmbedBuilder::primary(title, description);
+        let embed = EmbedBuilder::themed_primary(&Self::theme(ctx).await, title, description);
+        Self::send_embed(ctx, embed).await
+    }
+
+    /// Send a success embed with the command's next-step suggestions attached
+    pub async fn send_success_with_next_steps(
+        ctx: Context<'_>,
+        title: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Result<ReplyHandle<'_>, Error> {
+        let embed = EmbedBuilder::themed_success(&Self::theme(ctx).await, title, description);
+        let embed = Self::with_next_steps(ctx, embed).await;
         Self::send_embed(ctx, embed).await
     }
 
+    /// The invoking guild's embed theme; DMs use the default palette
+    pub async fn theme(ctx: Context<'_>) -> EmbedTheme {
+        match ctx.guild_id() {
+            Some(guild_id) => ctx.data().embed_theme(guild_id).await,
+            None => EmbedTheme::default(),
+        }
+    }
+
+    /// Append the "next steps" field for the invoked command, unless the guild has turned it off
+    pub async fn with_next_steps(ctx: Context<'_>, embed: CreateEmbed) -> CreateEmbed {
+        if let Some(guild_id) = ctx.guild_id() {
+            match GuildNextStepsSetting::is_enabled(&ctx.data().db_pool, guild_id).await {
+                Ok(true) => {}
+                Ok(false) => return embed,
+                Err(e) => {
+                    tracing::warn!(
+                        guild_id = %guild_id,
+                        error = ?e,
+                        "Failed to load next steps setting, showing suggestions"
+                    );
+                }
+            }
+        }
+
+        let registry = ctx.data().command_registry.read().await;
+        match render_next_steps(&registry, &ctx.command().qualified_name) {
+            Some(value) => embed.field(NEXT_STEPS_FIELD_TITLE, value, false),
+            None => embed,
+        }
+    }
+
+    /// Send an embed, degrading to flattened text where the bot lacks Embed Links
     pub async fn send_embed(ctx: Context<'_>, embed: CreateEmbed) -> Result<ReplyHandle<'_>, Error> {
-        ctx.send(CreateReply::default().embed(embed))
+        if !Self::can_embed(ctx).await {
+            return Self::send_embed_as_text(ctx, &embed).await;
+        }
+
+        match ctx.send(CreateReply::default().embed(embed.clone())).await {
+            Ok(handle) => Ok(handle),
+            Err(e) if is_missing_permissions(&e) => {
+                tracing::warn!(
+                    channel_id = %ctx.channel_id(),
+                    guild_id = ?ctx.guild_id(),
+                    "Embed rejected for missing permissions, falling back to text"
+                );
+                ctx.data()
+                    .embed_permission_cache
+                    .set(ctx.channel_id(), false)
+                    .await;
+                Self::send_embed_as_text(ctx, &embed).await
+            }
+            Err(e) => Err(Error::from(e)),
+        }
+    }
+
+    /// Send an embed, privately when `ephemeral` is set. Slash commands reply
+    /// ephemerally; prefix commands follow `PRIVATE_REPLY_FALLBACK`. Returns
+    /// `None` when the reply went to the member's DMs.
+    pub async fn send_embed_with(
+        ctx: Context<'_>,
+        embed: CreateEmbed,
+        ephemeral: bool,
+    ) -> Result<Option<ReplyHandle<'_>>, Error> {
+        let is_prefix = matches!(ctx, poise::Context::Prefix(_));
+        match delivery(is_prefix, ephemeral, ctx.data().settings.private_reply_fallback) {
+            Delivery::Public => Self::send_embed(ctx, embed).await.map(Some),
+            Delivery::Ephemeral => ctx
+                .send(CreateReply::default().embed(embed).ephemeral(true))
+                .await
+                .map(Some)
+                .map_err(Error::from),
+            Delivery::DirectMessage => {
+                let dm = ctx
+                    .author()
+                    .direct_message(ctx.http(), CreateMessage::new().embed(embed.clone()))
+                    .await;
+                match dm {
+                    Ok(_) => Ok(None),
+                    Err(e) => {
+                        tracing::debug!(
+                            user_id = %ctx.author().id,
+                            error = ?e,
+                            "Private reply DM failed, replying in channel"
+                        );
+                        Self::send_embed(ctx, embed).await.map(Some)
+                    }
+                }
+            }
+        }
+    }
+
+    async fn send_embed_as_text<'a>(
+        ctx: Context<'a>,
+        embed: &CreateEmbed,
+    ) -> Result<ReplyHandle<'a>, Error> {
+        ctx.send(CreateReply::default().content(embed_to_text(embed)))
             .await
             .map_err(Error::from)
     }
 
+    /// Pre-check Embed Links for prefix commands, cached briefly per channel.
+    /// Interaction responses always render embeds, so slash commands skip this.
+    async fn can_embed(ctx: Context<'_>) -> bool {
+        if !matches!(ctx, poise::Context::Prefix(_)) {
+            return true;
+        }
+
+        let Some(guild_id) = ctx.guild_id() else {
+            return true;
+        };
+
+        let channel_id = ctx.channel_id();
+        let cache = &ctx.data().embed_permission_cache;
+
+        if let Some(can_embed) = cache.get(channel_id).await {
+            return can_embed;
+        }
+
+        let checked = {
+            let bot_id = ctx.framework().bot_id;
+            guild_id
+                .to_guild_cached(&ctx.serenity_context().cache)
+                .and_then(|guild| {
+                    let channel = guild.channels.get(&channel_id)?;
+                    let member = guild.members.get(&bot_id)?;
+                    Some(guild.user_permissions_in(channel, member).embed_links())
+                })
+        };
+
+        match checked {
+            Some(can_embed) => {
+                cache.set(channel_id, can_embed).await;
+                can_embed
+            }
+            // Not enough cached state to tell; try the embed and detect the failure instead
+            None => true,
+        }
+    }
+
     #[allow(dead_code)]
     pub async fn send_custom(
         ctx: Context<'_>,
@@ -68,7 +264,8 @@ impl ResponseHelper {
         description: impl Into<String>,
         color: EmbedColor,
     ) -> Result<ReplyHandle<'_>, Error> {
-        let embed = EmbedBuilder::custom(title, description, color);
+        let embed = EmbedBuilder::custom(title, description, color)
+            .color(Self::theme(ctx).await.color(color));
         Self::send_embed(ctx, embed).await
     }
 
@@ -99,7 +296,7 @@ impl ResponseHelper {
         title: impl Into<String>,
         description: impl Into<String>,
     ) -> Result<(), Error> {
-        let embed = EmbedBuilder::success(title, description);
+        let embed = EmbedBuilder::themed_success(&Self::theme(ctx).await, title, description);
         Self::edit_to_embed(reply, ctx, embed).await
     }
 
@@ -110,7 +307,7 @@ impl ResponseHelper {
         title: impl Into<String>,
         description: impl Into<String>,
     ) -> Result<(), Error> {
-        let embed = EmbedBuilder::error(title, description);
+        let embed = EmbedBuilder::themed_error(&Self::theme(ctx).await, title, description);
         Self::edit_to_embed(reply, ctx, embed).await
     }
 
@@ -147,7 +344,8 @@ impl ResponseHelper {
         description: impl Into<String>,
         color: EmbedColor,
     ) -> Result<ReplyHandle<'_>, Error> {
-        let embed = EmbedBuilder::custom(title, description, color);
+        let embed = EmbedBuilder::custom(title, description, color)
+            .color(Self::theme(ctx).await.color(color));
         Self::send_embed_guaranteed(ctx, embed).await
     }
 }
@@ -164,3 +362,23 @@ impl ContextExt for Context<'_> {
         ResponseHelper::send_text_as_embed(*self, text).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_invocations_fall_back_when_ephemeral_requested() {
+        use PrivateReplyFallback::{Channel, DirectMessage};
+
+        assert_eq!(delivery(false, true, Channel), Delivery::Ephemeral);
+        assert_eq!(delivery(true, true, Channel), Delivery::Public);
+        assert_eq!(delivery(true, true, DirectMessage), Delivery::DirectMessage);
+        assert_eq!(delivery(false, false, DirectMessage), Delivery::Public);
+        assert_eq!(delivery(true, false, DirectMessage), Delivery::Public);
+
+        assert_eq!(PrivateReplyFallback::parse(" DM "), Some(DirectMessage));
+        assert_eq!(PrivateReplyFallback::parse("channel"), Some(Channel));
+        assert_eq!(PrivateReplyFallback::parse("pigeon"), None);
+    }
+}
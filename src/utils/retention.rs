@@ -0,0 +1,229 @@
+/// Shortest retention a guild can choose
+pub const MIN_RETENTION_DAYS: i64 = 7;
+
+/// Longest retention a guild can choose
+pub const MAX_RETENTION_DAYS: i64 = 365;
+
+/// Kinds of stored member data with their own retention window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, poise::ChoiceParameter)]
+pub enum RetentionCategory {
+    #[name = "rename_history"]
+    RenameHistory,
+    #[name = "color_history"]
+    ColorHistory,
+    #[name = "audit_log"]
+    AuditLog,
+    #[name = "share_history"]
+    ShareHistory,
+    #[name = "activity"]
+    Activity,
+    #[name = "violations"]
+    Violations,
+    #[name = "role_archive"]
+    RoleArchive,
+}
+
+/// A table pruned for a category, and which rows count as history
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PruneTarget {
+    pub table: &'static str,
+    pub timestamp_column: &'static str,
+    /// Extra condition so live rows (e.g. active shares) are never pruned
+    pub condition: Option<&'static str>,
+}
+
+const RENAMES: PruneTarget = target("booster_rename_history", "renamed_at");
+const AUDIT_LOG: PruneTarget = target("settings_audit_log", "timestamp");
+const SPOTLIGHTS: PruneTarget = target("spotlight_history", "spotlighted_at");
+const MEMBER_EVENTS: PruneTarget = target("quiet_member_events", "occurred_at");
+const VIOLATIONS: PruneTarget = target("name_violations", "attempted_at");
+const ROLE_ARCHIVE: PruneTarget = target("booster_role_archive", "archived_at");
+const ENDED_SHARES: PruneTarget = PruneTarget {
+    table: "booster_role_shares",
+    timestamp_column: "deactivated_at",
+    condition: Some("is_active = 0"),
+};
+
+const fn target(table: &'static str, timestamp_column: &'static str) -> PruneTarget {
+    PruneTarget {
+        table,
+        timestamp_column,
+        condition: None,
+    }
+}
+
+impl RetentionCategory {
+    pub const ALL: [RetentionCategory; 7] = [
+        RetentionCategory::RenameHistory,
+        RetentionCategory::ColorHistory,
+        RetentionCategory::AuditLog,
+        RetentionCategory::ShareHistory,
+        RetentionCategory::Activity,
+        RetentionCategory::Violations,
+        RetentionCategory::RoleArchive,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RetentionCategory::RenameHistory => "rename_history",
+            RetentionCategory::ColorHistory => "color_history",
+            RetentionCategory::AuditLog => "audit_log",
+            RetentionCategory::ShareHistory => "share_history",
+            RetentionCategory::Activity => "activity",
+            RetentionCategory::Violations => "violations",
+            RetentionCategory::RoleArchive => "role_archive",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.as_str() == value)
+    }
+
+    /// Global default when a guild hasn't set its own
+    pub fn default_days(&self) -> i64 {
+        match self {
+            RetentionCategory::RenameHistory => 180,
+            RetentionCategory::ColorHistory => 180,
+            RetentionCategory::AuditLog => 90,
+            RetentionCategory::ShareHistory => 30,
+            RetentionCategory::Activity => 90,
+            RetentionCategory::Violations => 90,
+            RetentionCategory::RoleArchive => 90,
+        }
+    }
+
+    /// Tables holding this category's rows. Color changes aren't stored
+    /// yet, so that override only takes effect once they are.
+    pub fn targets(&self) -> &'static [PruneTarget] {
+        match self {
+            RetentionCategory::RenameHistory => &[RENAMES],
+            RetentionCategory::ColorHistory => &[],
+            RetentionCategory::AuditLog => &[AUDIT_LOG],
+            RetentionCategory::ShareHistory => &[ENDED_SHARES],
+            RetentionCategory::Activity => &[SPOTLIGHTS, MEMBER_EVENTS],
+            RetentionCategory::Violations => &[VIOLATIONS],
+            RetentionCategory::RoleArchive => &[ROLE_ARCHIVE],
+        }
+    }
+}
+
+/// Check a retention a guild asked for
+pub fn validate_days(days: i64) -> Result<i64, String> {
+    if (MIN_RETENTION_DAYS..=MAX_RETENTION_DAYS).contains(&days) {
+        Ok(days)
+    } else {
+        Err(format!(
+            "Retention must be between {} and {} days.",
+            MIN_RETENTION_DAYS, MAX_RETENTION_DAYS
+        ))
+    }
+}
+
+/// Days a category is kept for: the guild's override if it has one,
+/// otherwise the global default, never outside the allowed range
+pub fn effective_days(category: RetentionCategory, override_days: Option<i64>) -> i64 {
+    override_days
+        .unwrap_or_else(|| category.default_days())
+        .clamp(MIN_RETENTION_DAYS, MAX_RETENTION_DAYS)
+}
+
+/// One DELETE for a target across every guild. Rows are joined against the
+/// overrides table so each guild's window applies without a per-guild loop;
+/// the clamp matches [`effective_days`]. Binds: category, default days.
+pub fn prune_statement(target: &PruneTarget) -> String {
+    let condition = target
+        .condition
+        .map(|condition| format!("t.{} AND ", condition))
+        .unwrap_or_default();
+
+    format!(
+        r#"
+        DELETE FROM {table} WHERE rowid IN (
+            SELECT t.rowid FROM {table} t
+            LEFT JOIN guild_retention_overrides o
+                ON o.guild_id = t.guild_id AND o.category = ?
+            WHERE {condition}t.{column} < datetime(
+                'now',
+                '-' || MAX({min}, MIN({max}, COALESCE(o.days, ?))) || ' days'
+            )
+        )
+        "#,
+        table = target.table,
+        column = target.timestamp_column,
+        condition = condition,
+        min = MIN_RETENTION_DAYS,
+        max = MAX_RETENTION_DAYS,
+    )
+}
+
+/// One line per category for settings views
+pub fn describe_effective(overrides: &[(RetentionCategory, i64)]) -> String {
+    RetentionCategory::ALL
+        .iter()
+        .map(|category| {
+            let custom = overrides
+                .iter()
+                .find(|(c, _)| c == category)
+                .map(|(_, days)| *days);
+            format!(
+                "**{}**: {} days{}",
+                category.as_str(),
+                effective_days(*category, custom),
+                if custom.is_some() { "" } else { " (default)" }
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_override_beats_default() {
+        assert_eq!(effective_days(RetentionCategory::RenameHistory, None), 180);
+        assert_eq!(
+            effective_days(RetentionCategory::RenameHistory, Some(14)),
+            14
+        );
+        assert_eq!(effective_days(RetentionCategory::AuditLog, None), 90);
+    }
+
+    #[test]
+    fn test_hard_limits_always_apply() {
+        // A stored value from before the limits can't go below the minimum
+        assert_eq!(
+            effective_days(RetentionCategory::Violations, Some(1)),
+            MIN_RETENTION_DAYS
+        );
+        assert_eq!(
+            effective_days(RetentionCategory::Activity, Some(10_000)),
+            MAX_RETENTION_DAYS
+        );
+
+        assert!(validate_days(6).is_err());
+        assert_eq!(validate_days(7), Ok(7));
+        assert_eq!(validate_days(365), Ok(365));
+        assert!(validate_days(366).is_err());
+    }
+
+    #[test]
+    fn test_categories_round_trip_and_share_live_rows() {
+        for category in RetentionCategory::ALL {
+            assert_eq!(RetentionCategory::parse(category.as_str()), Some(category));
+        }
+
+        let shares = RetentionCategory::ShareHistory.targets()[0];
+        assert!(prune_statement(&shares).contains("t.is_active = 0 AND t.deactivated_at <"));
+        assert!(prune_statement(&shares).contains("LEFT JOIN guild_retention_overrides"));
+    }
+
+    #[test]
+    fn test_describe_marks_defaults() {
+        let text = describe_effective(&[(RetentionCategory::AuditLog, 30)]);
+        assert!(text.contains("**audit_log**: 30 days\n"));
+        assert!(text.contains("**rename_history**: 180 days (default)"));
+        assert_eq!(text.lines().count(), RetentionCategory::ALL.len());
+    }
+}
@@ -0,0 +1,313 @@
+use crate::utils::BotError;
+use rand::Rng;
+use serenity::all::HttpError;
+use std::future::Future;
+use std::time::Duration;
+
+/// Whether a failure is worth trying again; anything else fails immediately
+pub trait Transient {
+    fn is_transient(&self) -> bool;
+
+    /// Transient, and the other side answered, so the request can't have
+    /// gone through unnoticed. Only these are retried for calls that aren't
+    /// idempotent: after a timeout a created role may already exist.
+    fn is_transient_response(&self) -> bool {
+        self.is_transient()
+    }
+}
+
+impl Transient for serenity::Error {
+    /// Discord 5xx responses, rate limits and timeouts pass on their own.
+    /// Refusals such as 403 and 404 won't change on a second attempt.
+    fn is_transient(&self) -> bool {
+        match self {
+            serenity::Error::Http(HttpError::UnsuccessfulRequest(response)) => {
+                response.status_code.is_server_error() || response.status_code.as_u16() == 429
+            }
+            serenity::Error::Http(HttpError::Request(e)) => e.is_timeout() || e.is_connect(),
+            _ => false,
+        }
+    }
+
+    fn is_transient_response(&self) -> bool {
+        matches!(
+            self,
+            serenity::Error::Http(HttpError::UnsuccessfulRequest(_))
+        ) && self.is_transient()
+    }
+}
+
+/// How many times to try and how long to wait in between
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    /// Wait before the second attempt; doubles for each one after
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait after the given failed attempt (1-based): exponential, plus up
+    /// to half again at random so retries from many commands don't line up
+    pub fn delay_after(&self, attempt: u32) -> Duration {
+        let delay = self.base_delay * 2u32.saturating_pow(attempt.saturating_sub(1));
+        let jitter_ms = (delay.as_millis() / 2) as u64;
+        let jitter = match jitter_ms {
+            0 => 0,
+            max => rand::thread_rng().gen_range(0..=max),
+        };
+        delay + Duration::from_millis(jitter)
+    }
+}
+
+/// Why a retried operation gave up
+#[derive(Debug, PartialEq, Eq)]
+pub enum RetryError<E> {
+    /// Failed in a way retrying can't fix
+    Permanent(E),
+    /// Still failing transiently after every attempt; holds the last error
+    Exhausted { attempts: u32, last: E },
+}
+
+/// Run `operation` until it succeeds, fails permanently, or runs out of
+/// attempts under `policy`
+pub async fn retry<T, E, F, Fut>(
+    policy: RetryPolicy,
+    label: &str,
+    operation: F,
+) -> Result<T, RetryError<E>>
+where
+    E: Transient + std::fmt::Display,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_when(policy, label, E::is_transient, operation).await
+}
+
+/// [`retry`] for calls that aren't safe to send twice, such as creating a
+/// role: only failures Discord answered are retried, never timeouts
+pub async fn retry_non_idempotent<T, E, F, Fut>(
+    policy: RetryPolicy,
+    label: &str,
+    operation: F,
+) -> Result<T, RetryError<E>>
+where
+    E: Transient + std::fmt::Display,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_when(policy, label, E::is_transient_response, operation).await
+}
+
+async fn retry_when<T, E, F, Fut>(
+    policy: RetryPolicy,
+    label: &str,
+    retryable: fn(&E) -> bool,
+    mut operation: F,
+) -> Result<T, RetryError<E>>
+where
+    E: std::fmt::Display,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) if !retryable(&e) => return Err(RetryError::Permanent(e)),
+            Err(e) if attempt >= policy.max_attempts => {
+                tracing::error!(
+                    operation = label,
+                    attempts = attempt,
+                    error = %e,
+                    "Giving up after repeated transient failures"
+                );
+                return Err(RetryError::Exhausted {
+                    attempts: attempt,
+                    last: e,
+                });
+            }
+            Err(e) => {
+                let delay = policy.delay_after(attempt);
+                tracing::warn!(
+                    operation = label,
+                    attempt,
+                    delay_ms = delay.as_millis() as u64,
+                    error = %e,
+                    "Transient failure, retrying"
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Retry a Discord API call under the default policy. Exhausted retries
+/// become [`BotError::DiscordUnavailable`] so the member is told to try
+/// again shortly rather than shown the raw API error.
+pub async fn retry_discord<T, F, Fut>(label: &str, operation: F) -> Result<T, BotError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, serenity::Error>>,
+{
+    let result = retry(RetryPolicy::default(), label, operation).await;
+    result.map_err(|e| discord_retry_error(label, e))
+}
+
+/// [`retry_discord`] for calls that aren't safe to send twice; see
+/// [`retry_non_idempotent`]
+pub async fn retry_discord_non_idempotent<T, F, Fut>(
+    label: &str,
+    operation: F,
+) -> Result<T, BotError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, serenity::Error>>,
+{
+    let result = retry_non_idempotent(RetryPolicy::default(), label, operation).await;
+    result.map_err(|e| discord_retry_error(label, e))
+}
+
+fn discord_retry_error(label: &str, error: RetryError<serenity::Error>) -> BotError {
+    match error {
+        RetryError::Permanent(e) => BotError::Discord(e),
+        RetryError::Exhausted { attempts, last } => {
+            BotError::DiscordUnavailable(format!("{} failed {} times: {}", label, attempts, last))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum StubError {
+        Unavailable,
+        TimedOut,
+        Forbidden,
+    }
+
+    impl std::fmt::Display for StubError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self)
+        }
+    }
+
+    impl Transient for StubError {
+        fn is_transient(&self) -> bool {
+            *self != StubError::Forbidden
+        }
+
+        fn is_transient_response(&self) -> bool {
+            *self == StubError::Unavailable
+        }
+    }
+
+    fn fast() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+        }
+    }
+
+    /// Fails with `error` for the first `failures` calls, then succeeds
+    async fn run(
+        failures: u32,
+        error: fn() -> StubError,
+    ) -> (Result<u32, RetryError<StubError>>, u32) {
+        let calls = AtomicU32::new(0);
+        let result = retry(fast(), "stub", || async {
+            let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if call <= failures {
+                Err(error())
+            } else {
+                Ok(call)
+            }
+        })
+        .await;
+        (result, calls.load(Ordering::SeqCst))
+    }
+
+    /// `run` for a call that isn't safe to send twice
+    async fn run_non_idempotent(
+        failures: u32,
+        error: fn() -> StubError,
+    ) -> (Result<u32, RetryError<StubError>>, u32) {
+        let calls = AtomicU32::new(0);
+        let result = retry_non_idempotent(fast(), "stub", || async {
+            let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if call <= failures {
+                Err(error())
+            } else {
+                Ok(call)
+            }
+        })
+        .await;
+        (result, calls.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn test_succeeds_after_transient_failures() {
+        assert_eq!(run(0, || StubError::Unavailable).await, (Ok(1), 1));
+        assert_eq!(run(2, || StubError::Unavailable).await, (Ok(3), 3));
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let (result, calls) = run(5, || StubError::Unavailable).await;
+        assert_eq!(calls, 3);
+        assert_eq!(
+            result,
+            Err(RetryError::Exhausted {
+                attempts: 3,
+                last: StubError::Unavailable
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_permanent_errors_are_not_retried() {
+        let (result, calls) = run(5, || StubError::Forbidden).await;
+        assert_eq!(calls, 1);
+        assert_eq!(result, Err(RetryError::Permanent(StubError::Forbidden)));
+    }
+
+    #[tokio::test]
+    async fn test_non_idempotent_calls_are_not_retried_after_a_timeout() {
+        // A timed-out create may have gone through; sending it again could
+        // leave a second, untracked role
+        assert_eq!(run(1, || StubError::TimedOut).await, (Ok(2), 2));
+        let (result, calls) = run_non_idempotent(1, || StubError::TimedOut).await;
+        assert_eq!(calls, 1);
+        assert_eq!(result, Err(RetryError::Permanent(StubError::TimedOut)));
+
+        // Discord answering with a 5xx or 429 is still retried
+        assert_eq!(
+            run_non_idempotent(2, || StubError::Unavailable).await,
+            (Ok(3), 3)
+        );
+    }
+
+    #[test]
+    fn test_delay_doubles_with_bounded_jitter() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+        };
+        for (attempt, base) in [(1, 100), (2, 200), (3, 400)] {
+            let delay = policy.delay_after(attempt).as_millis() as u64;
+            assert!((base..=base + base / 2).contains(&delay), "{} ms", delay);
+        }
+    }
+}
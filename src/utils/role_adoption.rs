@@ -0,0 +1,231 @@
+use serenity::all::{Permissions, RoleId, UserId};
+use std::collections::HashSet;
+
+/// Permissions a booster role should never carry; roles with any of these are not adopted
+pub const DANGEROUS_PERMISSIONS: Permissions = Permissions::ADMINISTRATOR
+    .union(Permissions::MANAGE_GUILD)
+    .union(Permissions::MANAGE_ROLES)
+    .union(Permissions::MANAGE_CHANNELS)
+    .union(Permissions::MANAGE_MESSAGES)
+    .union(Permissions::MANAGE_WEBHOOKS)
+    .union(Permissions::KICK_MEMBERS)
+    .union(Permissions::BAN_MEMBERS)
+    .union(Permissions::MODERATE_MEMBERS)
+    .union(Permissions::MENTION_EVERYONE);
+
+/// The parts of a guild role the adoption heuristic looks at
+#[derive(Debug, Clone)]
+pub struct RoleSnapshot {
+    pub id: RoleId,
+    pub name: String,
+    pub position: u16,
+    pub managed: bool,
+    pub permissions: Permissions,
+}
+
+/// The parts of a guild member the adoption heuristic looks at
+#[derive(Debug, Clone)]
+pub struct MemberSnapshot {
+    pub user_id: UserId,
+    pub role_ids: Vec<RoleId>,
+    pub is_booster: bool,
+}
+
+/// Roles and owners the bot already tracks
+#[derive(Debug, Clone, Default)]
+pub struct TrackedRecords {
+    pub role_ids: HashSet<RoleId>,
+    pub user_ids: HashSet<UserId>,
+}
+
+/// A hand-made role that looks like it belongs to a single booster
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdoptionCandidate {
+    pub role_id: RoleId,
+    pub role_name: String,
+    pub user_id: UserId,
+}
+
+/// Find untracked roles held by exactly one booster that the bot could safely manage.
+///
+/// A role is disqualified when it is @everyone, already tracked, managed by an
+/// integration, at or above the bot's top role, carries dangerous permissions,
+/// is held by zero or several members, or its holder isn't boosting or already
+/// has a booster role.
+pub fn find_adoption_candidates(
+    everyone_role_id: RoleId,
+    roles: &[RoleSnapshot],
+    members: &[MemberSnapshot],
+    tracked: &TrackedRecords,
+    bot_top_position: u16,
+) -> Vec<AdoptionCandidate> {
+    let mut candidates: Vec<AdoptionCandidate> = roles
+        .iter()
+        .filter(|role| role.id != everyone_role_id)
+        .filter(|role| !tracked.role_ids.contains(&role.id))
+        .filter(|role| !role.managed)
+        .filter(|role| role.position < bot_top_position)
+        .filter(|role| !role.permissions.intersects(DANGEROUS_PERMISSIONS))
+        .filter_map(|role| {
+            let mut holders = members.iter().filter(|m| m.role_ids.contains(&role.id));
+            let holder = holders.next()?;
+            if holders.next().is_some() {
+                return None;
+            }
+
+            if !holder.is_booster || tracked.user_ids.contains(&holder.user_id) {
+                return None;
+            }
+
+            Some(AdoptionCandidate {
+                role_id: role.id,
+                role_name: role.name.clone(),
+                user_id: holder.user_id,
+            })
+        })
+        .collect();
+
+    // A booster holding several qualifying roles is ambiguous; skip them all
+    let mut seen = HashSet::new();
+    let ambiguous: HashSet<UserId> = candidates
+        .iter()
+        .filter(|c| !seen.insert(c.user_id))
+        .map(|c| c.user_id)
+        .collect();
+    candidates.retain(|c| !ambiguous.contains(&c.user_id));
+
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVERYONE: RoleId = RoleId::new(1);
+    const BOT_TOP: u16 = 10;
+
+    fn role(id: u64) -> RoleSnapshot {
+        RoleSnapshot {
+            id: RoleId::new(id),
+            name: format!("role-{}", id),
+            position: 5,
+            managed: false,
+            permissions: Permissions::empty(),
+        }
+    }
+
+    fn booster(user: u64, roles: &[u64]) -> MemberSnapshot {
+        MemberSnapshot {
+            user_id: UserId::new(user),
+            role_ids: roles.iter().map(|r| RoleId::new(*r)).collect(),
+            is_booster: true,
+        }
+    }
+
+    fn scan(roles: &[RoleSnapshot], members: &[MemberSnapshot]) -> Vec<AdoptionCandidate> {
+        find_adoption_candidates(
+            EVERYONE,
+            roles,
+            members,
+            &TrackedRecords::default(),
+            BOT_TOP,
+        )
+    }
+
+    #[test]
+    fn test_single_booster_role_is_candidate() {
+        let candidates = scan(&[role(100)], &[booster(500, &[100])]);
+        assert_eq!(
+            candidates,
+            vec![AdoptionCandidate {
+                role_id: RoleId::new(100),
+                role_name: "role-100".to_string(),
+                user_id: UserId::new(500),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_everyone_and_tracked_excluded() {
+        let mut everyone = role(1);
+        everyone.id = EVERYONE;
+        assert!(scan(&[everyone], &[booster(500, &[1])]).is_empty());
+
+        let tracked = TrackedRecords {
+            role_ids: [RoleId::new(100)].into_iter().collect(),
+            user_ids: HashSet::new(),
+        };
+        let found = find_adoption_candidates(
+            EVERYONE,
+            &[role(100)],
+            &[booster(500, &[100])],
+            &tracked,
+            BOT_TOP,
+        );
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn test_owner_with_existing_record_excluded() {
+        let tracked = TrackedRecords {
+            role_ids: HashSet::new(),
+            user_ids: [UserId::new(500)].into_iter().collect(),
+        };
+        let found = find_adoption_candidates(
+            EVERYONE,
+            &[role(100)],
+            &[booster(500, &[100])],
+            &tracked,
+            BOT_TOP,
+        );
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn test_managed_role_excluded() {
+        let mut managed = role(100);
+        managed.managed = true;
+        assert!(scan(&[managed], &[booster(500, &[100])]).is_empty());
+    }
+
+    #[test]
+    fn test_role_above_bot_excluded() {
+        let mut high = role(100);
+        high.position = BOT_TOP;
+        assert!(scan(&[high], &[booster(500, &[100])]).is_empty());
+    }
+
+    #[test]
+    fn test_dangerous_permissions_excluded() {
+        let mut dangerous = role(100);
+        dangerous.permissions = Permissions::SEND_MESSAGES | Permissions::KICK_MEMBERS;
+        assert!(scan(&[dangerous], &[booster(500, &[100])]).is_empty());
+
+        let mut harmless = role(101);
+        harmless.permissions = Permissions::SEND_MESSAGES;
+        assert_eq!(scan(&[harmless], &[booster(500, &[101])]).len(), 1);
+    }
+
+    #[test]
+    fn test_holder_count_must_be_one() {
+        assert!(scan(&[role(100)], &[]).is_empty());
+        assert!(scan(&[role(100)], &[booster(500, &[100]), booster(501, &[100])]).is_empty());
+    }
+
+    #[test]
+    fn test_non_booster_holder_excluded() {
+        let mut member = booster(500, &[100]);
+        member.is_booster = false;
+        assert!(scan(&[role(100)], &[member]).is_empty());
+    }
+
+    #[test]
+    fn test_booster_with_multiple_candidates_is_ambiguous() {
+        let candidates = scan(
+            &[role(100), role(101), role(102)],
+            &[booster(500, &[100, 101]), booster(501, &[102])],
+        );
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].user_id, UserId::new(501));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::bot::Error;
+use crate::data::models::{BoosterRole, BoosterRoleArchive};
+use crate::utils::role_icon::{guild_supports_role_icons, update_role_icon, IconSource};
+use crate::utils::{BotError, ColorParser, RoleManager};
+use serenity::all::{GuildId, Role, UserId};
+use serenity::prelude::Context as SerenityContext;
+use sqlx::SqlitePool;
+
+/// What became of an archived icon when the role was recreated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IconRestore {
+    /// The archived role had no icon
+    None,
+    Restored(String),
+    Skipped(String),
+}
+
+impl IconRestore {
+    pub fn describe(&self) -> String {
+        match self {
+            IconRestore::None => "None".to_string(),
+            IconRestore::Restored(icon) => format!("Restored ({})", icon),
+            IconRestore::Skipped(reason) => format!("Not restored: {}", reason),
+        }
+    }
+}
+
+/// A recreated booster role and exactly what came back from the archive
+pub struct RestoredRole {
+    pub role: Role,
+    pub name: String,
+    pub primary_color: String,
+    pub secondary_color: Option<String>,
+    pub icon: IconRestore,
+}
+
+/// Recreate a member's role from their archive, give it back to them and
+/// clear the archive. The icon is best effort; anything else failing leaves
+/// the archive in place so the member can try again.
+pub async fn restore_archived_role(
+    ctx: &SerenityContext,
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    user_id: UserId,
+    archive: &BoosterRoleArchive,
+) -> Result<RestoredRole, Error> {
+    let color = ColorParser::parse(&archive.primary_color).map_err(|e| {
+        BotError::InvalidColor(format!(
+            "Archived color {} is invalid: {}",
+            archive.primary_color, e
+        ))
+    })?;
+
+    let role = RoleManager::create_booster_role(
+        ctx,
+        guild_id,
+        user_id,
+        &archive.role_name,
+        color,
+        pool,
+    )
+    .await?;
+
+    if let Err(e) = RoleManager::assign_role_to_member(ctx, guild_id, user_id, role.id).await {
+        if let Err(cleanup_error) = RoleManager::delete_role(ctx, guild_id, role.id).await {
+            tracing::error!(
+                role_id = %role.id,
+                guild_id = %guild_id,
+                error = ?cleanup_error,
+                "Failed to cleanup restored role after assignment failure"
+            );
+        }
+        return Err(e);
+    }
+
+    BoosterRole::create(
+        pool,
+        guild_id,
+        user_id,
+        role.id,
+        &archive.role_name,
+        &archive.primary_color,
+        archive.secondary_color.as_deref(),
+    )
+    .await?;
+
+    let icon = match archive.icon_url.as_deref() {
+        Some(icon) => restore_icon(ctx, pool, guild_id, user_id, &role, icon).await,
+        None => IconRestore::None,
+    };
+
+    BoosterRoleArchive::delete(pool, guild_id, user_id).await?;
+
+    tracing::info!(
+        user_id = %user_id,
+        guild_id = %guild_id,
+        role_id = %role.id,
+        icon = ?icon,
+        "Booster role restored from archive"
+    );
+
+    Ok(RestoredRole {
+        role,
+        name: archive.role_name.clone(),
+        primary_color: archive.primary_color.clone(),
+        secondary_color: archive.secondary_color.clone(),
+        icon,
+    })
+}
+
+async fn restore_icon(
+    ctx: &SerenityContext,
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    user_id: UserId,
+    role: &Role,
+    icon: &str,
+) -> IconRestore {
+    let source = match IconSource::parse(icon) {
+        Ok(source) => source,
+        Err(reason) => return IconRestore::Skipped(reason),
+    };
+
+    let supported = guild_id
+        .to_guild_cached(&ctx.cache)
+        .map(|guild| guild_supports_role_icons(&guild))
+        .unwrap_or(false);
+    if !supported {
+        return IconRestore::Skipped("this server needs boost level 2 for role icons".to_string());
+    }
+
+    if let Err(e) = update_role_icon(&ctx.http, guild_id, role.id, &source).await {
+        tracing::warn!(
+            guild_id = %guild_id,
+            role_id = %role.id,
+            error = ?e,
+            "Failed to restore archived role icon"
+        );
+        return IconRestore::Skipped(e.to_string());
+    }
+
+    let stored = source.stored_value();
+    if let Err(e) = BoosterRole::set_icon(pool, guild_id, user_id, Some(&stored)).await {
+        tracing::error!(error = ?e, "Failed to record restored role icon source");
+    }
+    IconRestore::Restored(stored)
+}
@@ -0,0 +1,164 @@
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// Largest file Discord accepts from a bot without boosts, less room for the
+/// JSON closing bracket and multipart overhead
+pub const MAX_EXPORT_BYTES: usize = 8 * 1024 * 1024 - 4096;
+
+const CSV_HEADER: &str =
+    "user_id,username,role_id,role_name,primary_color,secondary_color,created_at,shared_with_count\n";
+
+/// One booster role as it appears in an export
+#[derive(Debug, Clone, Serialize)]
+pub struct RoleExportRow {
+    /// IDs are strings so spreadsheets and JSON readers don't round them
+    pub user_id: String,
+    /// Empty when the member couldn't be resolved
+    pub username: String,
+    pub role_id: String,
+    pub role_name: String,
+    pub primary_color: String,
+    pub secondary_color: Option<String>,
+    pub created_at: Option<String>,
+    pub shared_with_count: i64,
+}
+
+/// A built export and how many rows had to be left out to fit
+#[derive(Debug)]
+pub struct RoleExport {
+    pub contents: String,
+    pub rows_written: usize,
+    pub rows_dropped: usize,
+}
+
+/// Quote a CSV field when it holds a separator, quote or line break, and
+/// defuse values a spreadsheet would read as a formula
+pub fn csv_field(value: &str) -> Cow<'_, str> {
+    let formula = value.starts_with(['=', '+', '-', '@']);
+    let needs_quotes = value.contains([',', '"', '\n', '\r']);
+    if !formula && !needs_quotes {
+        return Cow::Borrowed(value);
+    }
+
+    let value = if formula {
+        Cow::Owned(format!("'{}", value))
+    } else {
+        Cow::Borrowed(value)
+    };
+    if needs_quotes {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        value
+    }
+}
+
+fn csv_line(row: &RoleExportRow) -> String {
+    let fields = [
+        csv_field(&row.user_id),
+        csv_field(&row.username),
+        csv_field(&row.role_id),
+        csv_field(&row.role_name),
+        csv_field(&row.primary_color),
+        csv_field(row.secondary_color.as_deref().unwrap_or("")),
+        csv_field(row.created_at.as_deref().unwrap_or("")),
+        Cow::Owned(row.shared_with_count.to_string()),
+    ];
+    let mut line = fields.join(",");
+    line.push('\n');
+    line
+}
+
+/// Build a CSV export one row at a time, stopping before `max_bytes`
+pub fn build_csv(rows: &[RoleExportRow], max_bytes: usize) -> RoleExport {
+    let mut contents = String::from(CSV_HEADER);
+    let mut rows_written = 0;
+    for row in rows {
+        let line = csv_line(row);
+        if contents.len() + line.len() > max_bytes {
+            break;
+        }
+        contents.push_str(&line);
+        rows_written += 1;
+    }
+
+    RoleExport {
+        contents,
+        rows_written,
+        rows_dropped: rows.len() - rows_written,
+    }
+}
+
+/// Build a JSON array export one row at a time, stopping before `max_bytes`
+pub fn build_json(rows: &[RoleExportRow], max_bytes: usize) -> RoleExport {
+    const CLOSE: &str = "\n]\n";
+
+    let mut contents = String::from("[");
+    let mut rows_written = 0;
+    for row in rows {
+        let Ok(object) = serde_json::to_string_pretty(row) else {
+            continue;
+        };
+        let separator = if rows_written == 0 { "\n" } else { ",\n" };
+        if contents.len() + separator.len() + object.len() + CLOSE.len() > max_bytes {
+            break;
+        }
+        contents.push_str(separator);
+        contents.push_str(&object);
+        rows_written += 1;
+    }
+    contents.push_str(CLOSE);
+
+    RoleExport {
+        contents,
+        rows_written,
+        rows_dropped: rows.len() - rows_written,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(role_name: &str) -> RoleExportRow {
+        RoleExportRow {
+            user_id: "2".to_string(),
+            username: "booster".to_string(),
+            role_id: "10".to_string(),
+            role_name: role_name.to_string(),
+            primary_color: "#FF0000".to_string(),
+            secondary_color: None,
+            created_at: Some("2024-05-01 09:00:00".to_string()),
+            shared_with_count: 1,
+        }
+    }
+
+    #[test]
+    fn test_csv_escapes_commas_and_quotes() {
+        assert_eq!(csv_field("Plain"), "Plain");
+        assert_eq!(csv_field("Red, White"), "\"Red, White\"");
+        assert_eq!(csv_field("The \"Best\""), "\"The \"\"Best\"\"\"");
+        assert_eq!(csv_field("=SUM(A1)"), "'=SUM(A1)");
+        assert_eq!(csv_field("-a, b"), "\"'-a, b\"");
+
+        let export = build_csv(&[row("Red, \"Hot\"")], MAX_EXPORT_BYTES);
+        assert_eq!(
+            export.contents.lines().nth(1),
+            Some("2,booster,10,\"Red, \"\"Hot\"\"\",#FF0000,,2024-05-01 09:00:00,1")
+        );
+    }
+
+    #[test]
+    fn test_exports_stop_at_size_limit() {
+        let rows: Vec<_> = (0..10).map(|i| row(&format!("Role {}", i))).collect();
+
+        let csv = build_csv(&rows, CSV_HEADER.len() + 3 * csv_line(&rows[0]).len());
+        assert_eq!(csv.rows_written, 3);
+        assert_eq!(csv.rows_dropped, 7);
+
+        let json = build_json(&rows, 600);
+        assert!(json.contents.len() <= 600);
+        assert!(json.rows_dropped > 0);
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&json.contents).unwrap();
+        assert_eq!(parsed.len(), json.rows_written);
+    }
+}
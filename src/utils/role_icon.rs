@@ -0,0 +1,342 @@
+use crate::bot::Error;
+use crate::utils::image_processor::{fetch_image_capped, fetch_remote_image, parse_image_url};
+use crate::utils::BotError;
+use serenity::all::{CreateAttachment, EditRole, Guild, GuildId, Http, PremiumTier, RoleId};
+
+/// Discord's size limit for role icons
+pub const MAX_ICON_BYTES: usize = 256 * 1024;
+
+/// Guild feature Discord grants at boost level 2
+const ROLE_ICONS_FEATURE: &str = "ROLE_ICONS";
+
+/// Where a role icon comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IconSource {
+    /// An uploaded image, already known by its CDN URL
+    Attachment {
+        url: String,
+        content_type: Option<String>,
+        size: u32,
+    },
+    /// A custom emoji, fetched from Discord's CDN
+    CustomEmoji {
+        id: u64,
+        animated: bool,
+    },
+    /// A unicode emoji, which Discord renders itself
+    Unicode(String),
+    Url(String),
+}
+
+impl IconSource {
+    /// Parse the text argument: a custom emoji, a unicode emoji, or a URL
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+
+        if let Some(source) = parse_custom_emoji(input) {
+            return Ok(source);
+        }
+        if input.starts_with("http://") || input.starts_with("https://") {
+            return validate_icon_url(input).map(IconSource::Url);
+        }
+        if is_unicode_emoji(input) {
+            return Ok(IconSource::Unicode(input.to_string()));
+        }
+
+        Err("Give an emoji, an image URL (PNG, JPG, or GIF), or attach an image.".to_string())
+    }
+
+    /// Image URL to download, or `None` for unicode emoji
+    pub fn download_url(&self) -> Option<String> {
+        match self {
+            IconSource::Attachment { url, .. } | IconSource::Url(url) => Some(url.clone()),
+            IconSource::CustomEmoji { id, animated } => Some(format!(
+                "https://cdn.discordapp.com/emojis/{}.{}",
+                id,
+                if *animated { "gif" } else { "png" }
+            )),
+            IconSource::Unicode(_) => None,
+        }
+    }
+
+    /// What gets stored in `booster_roles.icon_url`
+    pub fn stored_value(&self) -> String {
+        match self {
+            IconSource::Unicode(emoji) => emoji.clone(),
+            other => other.download_url().unwrap_or_default(),
+        }
+    }
+
+    /// Best guess before downloading, so the boost-level error can say so
+    pub fn looks_animated(&self) -> bool {
+        match self {
+            IconSource::CustomEmoji { animated, .. } => *animated,
+            IconSource::Attachment { content_type, .. } => {
+                content_type.as_deref() == Some("image/gif")
+            }
+            IconSource::Url(url) => url
+                .to_lowercase()
+                .split('?')
+                .next()
+                .unwrap_or_default()
+                .ends_with(".gif"),
+            IconSource::Unicode(_) => false,
+        }
+    }
+}
+
+/// `<:name:id>` or `<a:name:id>`
+fn parse_custom_emoji(input: &str) -> Option<IconSource> {
+    let inner = input.strip_prefix('<')?.strip_suffix('>')?;
+    let (animated, rest) = match inner.strip_prefix("a:") {
+        Some(rest) => (true, rest),
+        None => (false, inner.strip_prefix(':')?),
+    };
+    let (name, id) = rest.split_once(':')?;
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    Some(IconSource::CustomEmoji {
+        id: id.parse().ok()?,
+        animated,
+    })
+}
+
+/// A short run of non-ASCII symbols, e.g. "🔥" or "👩‍🚀"
+fn is_unicode_emoji(input: &str) -> bool {
+    !input.is_empty()
+        && input.chars().count() <= 10
+        && !input
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_alphabetic())
+        && !input.is_ascii()
+}
+
+pub fn validate_icon_url(url: &str) -> Result<String, String> {
+    if url.len() > 2048 {
+        return Err("URL too long (max 2048 characters)".to_string());
+    }
+
+    let url = url.trim();
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err("URL must start with http:// or https://".to_string());
+    }
+
+    let valid_extensions = [".png", ".jpg", ".jpeg", ".gif"];
+    let url_lower = url.to_lowercase();
+    if !valid_extensions
+        .iter()
+        .any(|ext| url_lower.ends_with(ext) || url_lower.contains(&format!("{}?", ext)))
+    {
+        return Err("URL must point to a PNG, JPG, or GIF image".to_string());
+    }
+
+    Ok(url.to_string())
+}
+
+/// Image formats Discord accepts for role icons
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IconFormat {
+    Png,
+    Jpeg,
+    Gif,
+}
+
+impl IconFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            IconFormat::Png => "png",
+            IconFormat::Jpeg => "jpg",
+            IconFormat::Gif => "gif",
+        }
+    }
+}
+
+/// Check size and format from the bytes themselves rather than trusting
+/// the file name or content type
+pub fn validate_icon_bytes(bytes: &[u8]) -> Result<IconFormat, String> {
+    if bytes.len() > MAX_ICON_BYTES {
+        return Err(format!(
+            "Image is too large ({} KB, max {} KB)",
+            bytes.len().div_ceil(1024),
+            MAX_ICON_BYTES / 1024
+        ));
+    }
+
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Ok(IconFormat::Png)
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Ok(IconFormat::Jpeg)
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Ok(IconFormat::Gif)
+    } else {
+        Err("That file isn't a PNG, JPG, or GIF image".to_string())
+    }
+}
+
+/// Role icons need boost level 2, which Discord reports as a guild feature
+pub fn supports_role_icons(tier: PremiumTier, features: &[String]) -> bool {
+    matches!(tier, PremiumTier::Tier2 | PremiumTier::Tier3)
+        || features.iter().any(|feature| feature == ROLE_ICONS_FEATURE)
+}
+
+pub fn guild_supports_role_icons(guild: &Guild) -> bool {
+    supports_role_icons(guild.premium_tier, &guild.features)
+}
+
+/// Explanation for guilds below boost level 2
+pub fn boost_level_message(animated: bool) -> &'static str {
+    if animated {
+        "Animated icons need this server to reach **boost level 2**, which unlocks role icons."
+    } else {
+        "Role icons need this server to reach **boost level 2**."
+    }
+}
+
+/// Set the role's icon from its source, downloading and checking images first
+pub async fn update_role_icon(
+    http: &Http,
+    guild_id: GuildId,
+    role_id: RoleId,
+    source: &IconSource,
+) -> Result<(), Error> {
+    let Some(url) = source.download_url() else {
+        // Unicode emoji are rendered by Discord, nothing to upload
+        let emoji = source.stored_value();
+        guild_id
+            .edit_role(
+                http,
+                role_id,
+                EditRole::new().unicode_emoji(Some(emoji)),
+            )
+            .await?;
+        return Ok(());
+    };
+
+    if let IconSource::Attachment { size, .. } = source {
+        if *size as usize > MAX_ICON_BYTES {
+            return Err(Error::Command("Image is too large (max 256KB)".to_string()));
+        }
+    }
+
+    // Member-supplied links get the checks for arbitrary hosts; attachments
+    // and custom emoji come from Discord's CDN
+    let image_bytes = match source {
+        IconSource::Url(link) => {
+            let url = parse_image_url(link).map_err(fetch_error)?;
+            fetch_remote_image(&url, MAX_ICON_BYTES as u32).await
+        }
+        _ => fetch_image_capped(&url, MAX_ICON_BYTES as u32).await,
+    }
+    .map_err(fetch_error)?;
+
+    let format = validate_icon_bytes(&image_bytes).map_err(Error::Command)?;
+
+    let attachment =
+        CreateAttachment::bytes(image_bytes, format!("icon.{}", format.extension()));
+    guild_id
+        .edit_role(
+            http,
+            role_id,
+            EditRole::new().icon(Some(&attachment)),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Download failures are the member's to fix, so they're shown as-is
+fn fetch_error(error: BotError) -> Error {
+    match error {
+        BotError::Other(msg) => Error::Command(msg),
+        other => other.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_each_kind_of_input() {
+        assert_eq!(
+            IconSource::parse("<a:party:123456789012345678>"),
+            Ok(IconSource::CustomEmoji {
+                id: 123456789012345678,
+                animated: true
+            })
+        );
+        assert_eq!(
+            IconSource::parse("🔥"),
+            Ok(IconSource::Unicode("🔥".to_string()))
+        );
+        assert_eq!(
+            IconSource::parse("👩‍🚀"),
+            Ok(IconSource::Unicode("👩‍🚀".to_string()))
+        );
+        assert_eq!(
+            IconSource::parse("https://example.com/icon.png?size=64"),
+            Ok(IconSource::Url(
+                "https://example.com/icon.png?size=64".to_string()
+            ))
+        );
+        assert!(IconSource::parse("hello").is_err());
+        assert!(IconSource::parse("<:bad name:12>").is_err());
+        assert!(IconSource::parse("https://example.com/page.html").is_err());
+    }
+
+    #[test]
+    fn test_stored_value_and_animation_hints() {
+        let emoji = IconSource::CustomEmoji {
+            id: 42,
+            animated: false,
+        };
+        assert_eq!(
+            emoji.stored_value(),
+            "https://cdn.discordapp.com/emojis/42.png"
+        );
+        assert!(!emoji.looks_animated());
+        assert!(IconSource::Url("https://x.io/a.GIF".to_string()).looks_animated());
+        assert_eq!(IconSource::Unicode("⭐".to_string()).download_url(), None);
+    }
+
+    #[test]
+    fn test_validates_bytes_by_signature_and_size() {
+        assert_eq!(
+            validate_icon_bytes(b"\x89PNG\r\n\x1a\nrest"),
+            Ok(IconFormat::Png)
+        );
+        assert_eq!(validate_icon_bytes(b"GIF89a..."), Ok(IconFormat::Gif));
+        assert!(validate_icon_bytes(b"<html>").is_err());
+
+        let mut huge = b"\x89PNG\r\n\x1a\n".to_vec();
+        huge.resize(MAX_ICON_BYTES + 1, 0);
+        assert!(validate_icon_bytes(&huge)
+            .unwrap_err()
+            .contains("too large"));
+    }
+
+    #[test]
+    fn test_role_icons_need_boost_level_two() {
+        assert!(!supports_role_icons(PremiumTier::Tier1, &[]));
+        assert!(supports_role_icons(PremiumTier::Tier2, &[]));
+        assert!(supports_role_icons(
+            PremiumTier::Tier0,
+            &["ROLE_ICONS".to_string()]
+        ));
+        assert!(boost_level_message(true).starts_with("Animated"));
+    }
+
+    #[tokio::test]
+    async fn test_icon_links_to_private_hosts_are_refused() {
+        let http = Http::new("");
+        let source = IconSource::Url("http://127.0.0.1:9/icon.png".to_string());
+
+        let error = update_role_icon(&http, GuildId::new(1), RoleId::new(2), &source)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("private or reserved"), "{}", error);
+    }
+}
@@ -0,0 +1,120 @@
+/// Which aspects of a booster role staff have locked
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LockFlags {
+    pub name: bool,
+    pub color: bool,
+}
+
+/// Which aspects of a booster role a command is about to change
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestedChange {
+    pub name: bool,
+    pub color: bool,
+}
+
+impl RequestedChange {
+    pub fn name() -> Self {
+        Self {
+            name: true,
+            color: false,
+        }
+    }
+
+    pub fn color() -> Self {
+        Self {
+            name: false,
+            color: true,
+        }
+    }
+}
+
+/// The locked aspect a requested change collided with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockedAspect {
+    Name,
+    Color,
+    NameAndColor,
+}
+
+impl LockedAspect {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LockedAspect::Name => "name",
+            LockedAspect::Color => "color",
+            LockedAspect::NameAndColor => "name and color",
+        }
+    }
+}
+
+/// Reject a requested change if it touches any locked aspect
+pub fn check_lock(flags: LockFlags, change: RequestedChange) -> Result<(), LockedAspect> {
+    match (flags.name && change.name, flags.color && change.color) {
+        (true, true) => Err(LockedAspect::NameAndColor),
+        (true, false) => Err(LockedAspect::Name),
+        (false, true) => Err(LockedAspect::Color),
+        (false, false) => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAME_LOCKED: LockFlags = LockFlags {
+        name: true,
+        color: false,
+    };
+    const COLOR_LOCKED: LockFlags = LockFlags {
+        name: false,
+        color: true,
+    };
+    const BOTH_LOCKED: LockFlags = LockFlags {
+        name: true,
+        color: true,
+    };
+    const NAME_AND_COLOR: RequestedChange = RequestedChange {
+        name: true,
+        color: true,
+    };
+
+    #[test]
+    fn test_unlocked_allows_everything() {
+        let flags = LockFlags::default();
+        assert!(check_lock(flags, RequestedChange::name()).is_ok());
+        assert!(check_lock(flags, RequestedChange::color()).is_ok());
+        assert!(check_lock(flags, NAME_AND_COLOR).is_ok());
+    }
+
+    #[test]
+    fn test_single_aspect_locks() {
+        assert_eq!(
+            check_lock(NAME_LOCKED, RequestedChange::name()),
+            Err(LockedAspect::Name)
+        );
+        assert!(check_lock(NAME_LOCKED, RequestedChange::color()).is_ok());
+
+        assert_eq!(
+            check_lock(COLOR_LOCKED, RequestedChange::color()),
+            Err(LockedAspect::Color)
+        );
+        assert!(check_lock(COLOR_LOCKED, RequestedChange::name()).is_ok());
+    }
+
+    #[test]
+    fn test_combined_change_reports_overlap() {
+        assert_eq!(
+            check_lock(NAME_LOCKED, NAME_AND_COLOR),
+            Err(LockedAspect::Name)
+        );
+        assert_eq!(
+            check_lock(BOTH_LOCKED, NAME_AND_COLOR),
+            Err(LockedAspect::NameAndColor)
+        );
+        assert_eq!(LockedAspect::NameAndColor.label(), "name and color");
+    }
+
+    #[test]
+    fn test_empty_change_never_blocked() {
+        assert!(check_lock(BOTH_LOCKED, RequestedChange::default()).is_ok());
+    }
+}
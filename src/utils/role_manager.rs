@@ -1,7 +1,10 @@
 use crate::bot::Error;
-use crate::data::models::GuildBoosterBaseRole;
+use crate::data::models::{BoosterRole, GuildBoosterBaseRole};
+use crate::utils::retry::{retry_discord, retry_discord_non_idempotent};
+use crate::utils::role_positions::{clamp_below_bot, plan_stack, RepositionOutcome};
+use crate::utils::role_name::check_role_name;
 use crate::utils::{BotError, ColorParser};
-use serenity::all::{Colour, EditRole, Guild, GuildId, Member, Role, RoleId, UserId};
+use serenity::all::{Colour, EditRole, GuildId, Member, Role, RoleId, UserId};
 use serenity::prelude::Context as SerenityContext;
 use sqlx::SqlitePool;
 
@@ -46,48 +49,30 @@ impl RoleManager {
             .mentionable(false)
             .permissions(serenity::all::Permissions::empty());
 
-        let role = guild_id.create_role(&ctx.http, role_builder).await?;
+        // Creating isn't idempotent, so a timeout isn't retried: the first
+        // attempt may have made the role already
+        let role = retry_discord_non_idempotent("create role", || {
+            guild_id.create_role(&ctx.http, role_builder.clone())
+        })
+        .await?;
 
         // Position the role above base role if configured, otherwise use fallback positioning
-        if let Ok(Some(base_role_id)) = GuildBoosterBaseRole::get(db_pool, guild_id).await {
-            let base_position = guild.roles.get(&base_role_id).map(|r| r.position);
-            if let Some(pos) = base_position {
-                let new_position = pos + 1;
-                if let Err(e) = guild_id
-                    .edit_role(
-                        &ctx.http,
-                        role.id,
-                        EditRole::new().position(new_position as u16),
-                    )
-                    .await
-                {
-                    tracing::warn!(
-                        role_id = %role.id,
-                        target_position = new_position,
-                        error = ?e,
-                        "Failed to position role above base role"
-                    );
-                }
-            }
-        } else {
-            // Fallback: position relative to bot's highest role
-            let position = Self::find_booster_role_position(&guild).await.unwrap_or(1);
-            if position > 0 {
-                if let Err(e) = guild_id
-                    .edit_role(
-                        &ctx.http,
-                        role.id,
-                        EditRole::new().position(position),
-                    )
-                    .await
-                {
-                    tracing::warn!(
-                        role_id = %role.id,
-                        position = position,
-                        error = ?e,
-                        "Failed to move role to desired position, keeping default position"
-                    );
-                }
+        let base_position = match GuildBoosterBaseRole::get(db_pool, guild_id).await {
+            Ok(Some(base_role_id)) => guild.roles.get(&base_role_id).map(|r| r.position),
+            _ => None,
+        };
+        let target_position = match base_position {
+            Some(pos) => Some(pos + 1),
+            None => Self::find_booster_role_position(ctx, guild_id).await.ok(),
+        };
+        if let Some(position) = target_position {
+            if let Err(e) = Self::move_role_to_position(ctx, guild_id, role.id, position).await {
+                tracing::warn!(
+                    role_id = %role.id,
+                    target_position = position,
+                    error = ?e,
+                    "Failed to move role to desired position, keeping default position"
+                );
             }
         }
 
@@ -136,7 +121,10 @@ impl RoleManager {
             .name(role_name)
             .colour(Colour::new(color));
 
-        let role = guild_id.edit_role(&ctx.http, role_id, edit_builder).await?;
+        let role = retry_discord("edit role", || {
+            guild_id.edit_role(&ctx.http, role_id, edit_builder.clone())
+        })
+        .await?;
 
         tracing::info!(
             guild_id = %guild_id,
@@ -174,7 +162,7 @@ impl RoleManager {
             return Ok(());
         }
 
-        member.add_role(&ctx.http, role_id).await?;
+        retry_discord("add role", || member.add_role(&ctx.http, role_id)).await?;
 
         tracing::info!(
             user_id = %user_id,
@@ -213,7 +201,7 @@ impl RoleManager {
             return Ok(());
         }
 
-        member.remove_role(&ctx.http, role_id).await?;
+        retry_discord("remove role", || member.remove_role(&ctx.http, role_id)).await?;
 
         tracing::info!(
             user_id = %user_id,
@@ -237,7 +225,7 @@ impl RoleManager {
             "Deleting role"
         );
 
-        guild_id.delete_role(&ctx.http, role_id).await?;
+        retry_discord("delete role", || guild_id.delete_role(&ctx.http, role_id)).await?;
 
         tracing::info!(
             guild_id = %guild_id,
@@ -249,32 +237,24 @@ impl RoleManager {
     }
 
     /// Checks if a member is a server booster
+    #[allow(dead_code)]
     pub fn is_booster(member: &Member) -> bool {
         member.premium_since.is_some()
     }
 
     /// Finds appropriate position for booster role in hierarchy
     /// Places it above regular members but below important roles
-    async fn find_booster_role_position(guild: &Guild) -> Result<u16, BotError> {
-        let bot_member = guild
-            .members
-            .values()
-            .find(|m| m.user.bot)
-            .ok_or_else(|| BotError::Other("Bot member not found in guild".to_string()))?;
-
-        // Find the bot's highest role position
-        let bot_highest_role = bot_member
-            .roles
-            .iter()
-            .filter_map(|role_id| guild.roles.get(role_id))
-            .max_by_key(|role| role.position)
-            .ok_or_else(|| BotError::Other("Bot has no roles".to_string()))?;
+    async fn find_booster_role_position(
+        ctx: &SerenityContext,
+        guild_id: GuildId,
+    ) -> Result<u16, Error> {
+        let bot_top = Self::bot_top_position(ctx, guild_id).await?;
 
         // Place booster roles a few positions below bot's highest role to avoid conflicts
-        let target_position = bot_highest_role.position.saturating_sub(5);
+        let target_position = bot_top.saturating_sub(5);
 
         tracing::debug!(
-            bot_highest_position = bot_highest_role.position,
+            bot_highest_position = bot_top,
             target_position = target_position,
             "Calculated booster role position"
         );
@@ -282,34 +262,116 @@ impl RoleManager {
         Ok(target_position.max(1)) // Ensure position is at least 1
     }
 
-    /// Validates role name to ensure it meets Discord requirements
-    pub fn validate_role_name(name: &str) -> Result<(), BotError> {
-        let name = name.trim();
+    /// Position of the bot's highest role; it can only move roles below this
+    pub async fn bot_top_position(ctx: &SerenityContext, guild_id: GuildId) -> Result<u16, Error> {
+        let bot_id = ctx.cache.current_user().id;
+        let bot_member = guild_id.member(ctx, bot_id).await?;
 
-        if name.is_empty() {
-            return Err(BotError::Command("Role name cannot be empty".to_string()));
-        }
+        let guild = guild_id
+            .to_guild_cached(&ctx.cache)
+            .ok_or_else(|| BotError::Other("Guild not found in cache".to_string()))?;
 
-        if name.len() > 100 {
-            return Err(BotError::Command(
-                "Role name cannot exceed 100 characters".to_string(),
-            ));
-        }
+        Ok(bot_member
+            .roles
+            .iter()
+            .filter_map(|role_id| guild.roles.get(role_id))
+            .map(|role| role.position)
+            .max()
+            .unwrap_or(0))
+    }
 
-        // Check for forbidden characters or patterns
-        if name.contains('@') || name.contains('#') || name.contains(':') {
-            return Err(BotError::Command(
-                "Role name contains forbidden characters (@, #, :)".to_string(),
-            ));
-        }
+    /// Moves a role to the given position, clamped below the bot's highest
+    /// role, and returns the position it was actually given
+    pub async fn move_role_to_position(
+        ctx: &SerenityContext,
+        guild_id: GuildId,
+        role_id: RoleId,
+        position: u16,
+    ) -> Result<u16, Error> {
+        let bot_top = Self::bot_top_position(ctx, guild_id).await?;
+        let target = clamp_below_bot(position, bot_top);
+
+        guild_id.edit_role_position(&ctx.http, role_id, target).await?;
+
+        tracing::info!(
+            guild_id = %guild_id,
+            role_id = %role_id,
+            requested_position = position,
+            position = target,
+            "Moved role"
+        );
+
+        Ok(target)
+    }
 
-        if name.to_lowercase() == "everyone" || name.to_lowercase() == "here" {
-            return Err(BotError::Command(
-                "Role name cannot be 'everyone' or 'here'".to_string(),
-            ));
+    /// Stacks every booster role directly above the base role, keeping
+    /// their current order; also fixes drift after manual reordering
+    pub async fn reposition_all_booster_roles(
+        ctx: &SerenityContext,
+        guild_id: GuildId,
+        base_role_id: RoleId,
+        db_pool: &SqlitePool,
+    ) -> Result<RepositionOutcome, Error> {
+        let records = BoosterRole::get_all_for_guild(db_pool, guild_id).await?;
+        let bot_top = Self::bot_top_position(ctx, guild_id).await?;
+
+        let (base_position, current) = {
+            let guild = guild_id
+                .to_guild_cached(&ctx.cache)
+                .ok_or_else(|| BotError::Other("Guild not found in cache".to_string()))?;
+            let base_position = guild
+                .roles
+                .get(&base_role_id)
+                .map(|role| role.position)
+                .ok_or_else(|| BotError::Other("Base role not found".to_string()))?;
+            let current: Vec<(RoleId, u16)> = records
+                .iter()
+                .filter_map(|record| {
+                    let role_id = RoleId::new(record.role_id as u64);
+                    guild.roles.get(&role_id).map(|role| (role_id, role.position))
+                })
+                .collect();
+            (base_position, current)
+        };
+
+        let mut outcome = RepositionOutcome::default();
+        for (role_id, target) in plan_stack(base_position, bot_top, &current) {
+            if current.contains(&(role_id, target)) {
+                outcome.unchanged += 1;
+                continue;
+            }
+
+            match guild_id.edit_role_position(&ctx.http, role_id, target).await {
+                Ok(_) => outcome.moved += 1,
+                Err(e) => {
+                    outcome.failed += 1;
+                    tracing::warn!(
+                        guild_id = %guild_id,
+                        role_id = %role_id,
+                        target_position = target,
+                        error = ?e,
+                        "Failed to reposition booster role"
+                    );
+                }
+            }
         }
 
-        Ok(())
+        tracing::info!(
+            guild_id = %guild_id,
+            base_role_id = %base_role_id,
+            moved = outcome.moved,
+            unchanged = outcome.unchanged,
+            failed = outcome.failed,
+            "Booster roles repositioned"
+        );
+
+        Ok(outcome)
+    }
+
+    /// Validates role name to ensure it meets Discord requirements,
+    /// returning the normalized name to create or rename the role with
+    pub fn validate_role_name(name: &str) -> Result<String, BotError> {
+        check_role_name(name).map_err(|e| BotError::Command(e.to_string()))
     }
 
     /// Clean up orphaned roles (roles that exist in database but not in Discord)
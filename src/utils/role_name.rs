@@ -0,0 +1,275 @@
+use std::fmt;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Discord's limit on role names, in characters
+pub const MAX_ROLE_NAME_CHARS: usize = 100;
+
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+
+/// Why a role name was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoleNameError {
+    Empty,
+    /// Nothing was left once invisible characters were removed
+    Invisible,
+    /// Longer than Discord allows; holds the length in characters
+    TooLong(usize),
+    /// Contains a character Discord treats as a mention or emoji marker
+    ForbiddenCharacter(char),
+    Reserved,
+    /// Only emoji, symbols or combining marks
+    NoLettersOrDigits,
+    /// Looks the same as a staff role; holds that role's name
+    ImitatesStaffRole(String),
+}
+
+impl fmt::Display for RoleNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Role name cannot be empty"),
+            Self::Invisible => write!(f, "Role name has no visible characters"),
+            Self::TooLong(chars) => write!(
+                f,
+                "Role name cannot exceed {} characters (this one has {})",
+                MAX_ROLE_NAME_CHARS, chars
+            ),
+            Self::ForbiddenCharacter(c) => write!(
+                f,
+                "Role name cannot contain '{}' (@, # and : are not allowed)",
+                c
+            ),
+            Self::Reserved => write!(f, "Role name cannot be 'everyone' or 'here'"),
+            Self::NoLettersOrDigits => {
+                write!(f, "Role name needs at least one letter or number")
+            }
+            Self::ImitatesStaffRole(role) => {
+                write!(f, "Role name looks too much like the staff role '{}'", role)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RoleNameError {}
+
+/// Characters that render as nothing and are used to make one name pass for
+/// another. Joiners between emoji are handled separately.
+fn is_invisible(c: char) -> bool {
+    c.is_control()
+        || matches!(
+            c,
+            '\u{00AD}'
+                | '\u{034F}'
+                | '\u{061C}'
+                | '\u{115F}'
+                | '\u{1160}'
+                | '\u{17B4}'
+                | '\u{17B5}'
+                | '\u{180B}'..='\u{180F}'
+                | '\u{200B}'
+                | '\u{200C}'
+                | '\u{200E}'
+                | '\u{200F}'
+                | '\u{202A}'..='\u{202E}'
+                | '\u{2060}'..='\u{206F}'
+                | '\u{3164}'
+                | '\u{FEFF}'
+                | '\u{FFA0}'
+                | '\u{FFF9}'..='\u{FFFB}'
+        )
+}
+
+/// The name as Discord will show it: NFC-normalized, invisible characters
+/// removed, whitespace collapsed to single spaces and trimmed. A zero-width
+/// joiner survives only between two emoji, where it builds a combined one.
+pub fn normalize_role_name(name: &str) -> String {
+    let chars: Vec<char> = name.nfc().collect();
+    let mut normalized = String::with_capacity(name.len());
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c == ZERO_WIDTH_JOINER {
+            let joins_emoji = |other: Option<&char>| {
+                other.is_some_and(|o| !o.is_alphanumeric() && !o.is_whitespace())
+            };
+            if joins_emoji(normalized.chars().last().as_ref()) && joins_emoji(chars.get(i + 1)) {
+                normalized.push(c);
+            }
+        } else if c.is_whitespace() {
+            if !normalized.is_empty() && !normalized.ends_with(' ') {
+                normalized.push(' ');
+            }
+        } else if !is_invisible(c) {
+            normalized.push(c);
+        }
+    }
+
+    normalized.truncate(normalized.trim_end().len());
+    normalized
+}
+
+/// Normalize a role name and check it against Discord's rules, returning the
+/// name to use
+pub fn check_role_name(name: &str) -> Result<String, RoleNameError> {
+    if name.trim().is_empty() {
+        return Err(RoleNameError::Empty);
+    }
+
+    let normalized = normalize_role_name(name);
+    if normalized.is_empty() {
+        return Err(RoleNameError::Invisible);
+    }
+
+    let chars = normalized.chars().count();
+    if chars > MAX_ROLE_NAME_CHARS {
+        return Err(RoleNameError::TooLong(chars));
+    }
+
+    if let Some(c) = normalized.chars().find(|c| matches!(c, '@' | '#' | ':')) {
+        return Err(RoleNameError::ForbiddenCharacter(c));
+    }
+
+    let lower = normalized.to_lowercase();
+    if lower == "everyone" || lower == "here" {
+        return Err(RoleNameError::Reserved);
+    }
+
+    if !normalized.chars().any(char::is_alphanumeric) {
+        return Err(RoleNameError::NoLettersOrDigits);
+    }
+
+    Ok(normalized)
+}
+
+/// Latin letters that other scripts and digits are commonly swapped in for
+fn unconfuse(c: char) -> char {
+    match c {
+        '0' | 'о' | 'ο' | 'σ' => 'o',
+        '1' | '|' | 'ӏ' | 'ı' => 'l',
+        '3' | 'е' | 'ε' => 'e',
+        '5' | 'ѕ' => 's',
+        'а' | 'α' => 'a',
+        'р' | 'ρ' => 'p',
+        'с' | 'ϲ' => 'c',
+        'у' | 'γ' => 'y',
+        'х' | 'χ' => 'x',
+        'і' | 'ι' => 'i',
+        'ј' => 'j',
+        'ԁ' => 'd',
+        'һ' => 'h',
+        'к' | 'κ' => 'k',
+        'т' | 'τ' => 't',
+        'ν' => 'v',
+        'υ' => 'u',
+        'ɡ' => 'g',
+        other => other,
+    }
+}
+
+/// What a name looks like with styling, accents, case, lookalike letters
+/// and everything but letters and digits stripped away
+fn skeleton(name: &str) -> String {
+    normalize_role_name(name)
+        .nfkd()
+        .filter(|&c| !is_combining_mark(c))
+        .flat_map(char::to_lowercase)
+        .map(unconfuse)
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .replace("rn", "m")
+}
+
+/// The staff role a name would pass for, if any
+pub fn imitated_role<'a>(
+    name: &str,
+    staff_role_names: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let target = skeleton(name);
+    if target.is_empty() {
+        return None;
+    }
+    staff_role_names
+        .into_iter()
+        .find(|staff| skeleton(staff) == target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_width_and_control_characters_are_stripped() {
+        assert_eq!(check_role_name("Mod\u{200B}erator").unwrap(), "Moderator");
+        assert_eq!(check_role_name("\u{FEFF}Cool\u{2060} Kid\u{0007}").unwrap(), "Cool Kid");
+        assert_eq!(check_role_name("Bold\u{202E}txet").unwrap(), "Boldtxet");
+        assert_eq!(
+            check_role_name("\u{200B}\u{200C}\u{3164}"),
+            Err(RoleNameError::Invisible)
+        );
+    }
+
+    #[test]
+    fn test_whitespace_is_trimmed_and_collapsed() {
+        assert_eq!(check_role_name("  Night \u{00A0}\t Owl  ").unwrap(), "Night Owl");
+        assert_eq!(check_role_name("   "), Err(RoleNameError::Empty));
+    }
+
+    #[test]
+    fn test_names_are_nfc_normalized() {
+        // "e" followed by a combining acute accent becomes a single "é"
+        assert_eq!(check_role_name("Cafe\u{0301}").unwrap(), "Caf\u{00E9}");
+    }
+
+    #[test]
+    fn test_length_counts_characters_not_bytes() {
+        let accented = "é".repeat(MAX_ROLE_NAME_CHARS);
+        assert!(accented.len() > MAX_ROLE_NAME_CHARS);
+        assert_eq!(check_role_name(&accented).unwrap(), accented);
+
+        let too_long = "日".repeat(MAX_ROLE_NAME_CHARS + 1);
+        assert_eq!(
+            check_role_name(&too_long),
+            Err(RoleNameError::TooLong(MAX_ROLE_NAME_CHARS + 1))
+        );
+    }
+
+    #[test]
+    fn test_emoji_and_combining_only_names_are_refused() {
+        assert_eq!(check_role_name("🔥🔥"), Err(RoleNameError::NoLettersOrDigits));
+        assert_eq!(
+            check_role_name("\u{0301}\u{0302}\u{0303}"),
+            Err(RoleNameError::NoLettersOrDigits)
+        );
+        assert_eq!(check_role_name("🔥 Blaze").unwrap(), "🔥 Blaze");
+    }
+
+    #[test]
+    fn test_joiner_is_kept_inside_emoji_sequences_only() {
+        let family = "👨\u{200D}👩\u{200D}👧 Family";
+        assert_eq!(check_role_name(family).unwrap(), family);
+        assert_eq!(check_role_name("Ad\u{200D}min").unwrap(), "Admin");
+    }
+
+    #[test]
+    fn test_discord_rules_still_apply() {
+        assert_eq!(
+            check_role_name("Team #1"),
+            Err(RoleNameError::ForbiddenCharacter('#'))
+        );
+        assert_eq!(check_role_name(" Everyone "), Err(RoleNameError::Reserved));
+        assert_eq!(check_role_name("here\u{200B}"), Err(RoleNameError::Reserved));
+    }
+
+    #[test]
+    fn test_lookalikes_of_staff_roles_are_caught() {
+        let staff = ["Moderator", "Admin"];
+        assert_eq!(imitated_role("moderator", staff), Some("Moderator"));
+        assert_eq!(imitated_role("M0derat0r", staff), Some("Moderator"));
+        // Cyrillic "о" and "а"
+        assert_eq!(imitated_role("Mоderаtor", staff), Some("Moderator"));
+        assert_eq!(imitated_role("Ａｄｍｉｎ", staff), Some("Admin"));
+        assert_eq!(imitated_role("A d m i n ✨", staff), Some("Admin"));
+        assert_eq!(imitated_role("Adrnin", staff), Some("Admin"));
+        assert_eq!(imitated_role("Admirer", staff), None);
+        assert_eq!(imitated_role("🔥", staff), None);
+    }
+}
@@ -0,0 +1,93 @@
+use serenity::all::RoleId;
+
+/// Highest position the bot may give a role: just under its own top role,
+/// and never the @everyone slot at 0
+pub fn clamp_below_bot(target: u16, bot_top: u16) -> u16 {
+    target.min(bot_top.saturating_sub(1)).max(1)
+}
+
+/// Distinct target positions for booster roles stacked directly above the
+/// base role, keeping their current relative order.
+///
+/// When the stack would reach the bot's top role it slides down instead of
+/// piling several roles onto the same position.
+pub fn plan_stack(base_position: u16, bot_top: u16, roles: &[(RoleId, u16)]) -> Vec<(RoleId, u16)> {
+    let mut ordered = roles.to_vec();
+    ordered.sort_by_key(|(role_id, position)| (*position, *role_id));
+
+    let count = ordered.len() as u16;
+    let cap = clamp_below_bot(u16::MAX, bot_top);
+    let start = base_position
+        .saturating_add(1)
+        .min((cap + 1).saturating_sub(count))
+        .max(1);
+
+    ordered
+        .into_iter()
+        .enumerate()
+        .map(|(i, (role_id, _))| (role_id, clamp_below_bot(start + i as u16, bot_top)))
+        .collect()
+}
+
+/// Result of lining booster roles up above the base role
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RepositionOutcome {
+    pub moved: usize,
+    pub unchanged: usize,
+    pub failed: usize,
+}
+
+impl RepositionOutcome {
+    pub fn summary(&self) -> String {
+        let mut parts = vec![format!("{} moved", self.moved)];
+        if self.unchanged > 0 {
+            parts.push(format!("{} already in place", self.unchanged));
+        }
+        if self.failed > 0 {
+            parts.push(format!("{} failed", self.failed));
+        }
+        parts.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role(id: u64, position: u16) -> (RoleId, u16) {
+        (RoleId::new(id), position)
+    }
+
+    #[test]
+    fn test_clamps_below_bot_and_above_everyone() {
+        assert_eq!(clamp_below_bot(8, 10), 8);
+        assert_eq!(clamp_below_bot(10, 10), 9);
+        assert_eq!(clamp_below_bot(25, 10), 9);
+        assert_eq!(clamp_below_bot(0, 10), 1);
+        assert_eq!(clamp_below_bot(3, 0), 1);
+    }
+
+    #[test]
+    fn test_stack_keeps_order_and_distinct_positions() {
+        let plan = plan_stack(4, 20, &[role(1, 12), role(2, 2), role(3, 12)]);
+        assert_eq!(plan, vec![role(2, 5), role(1, 6), role(3, 7)]);
+    }
+
+    #[test]
+    fn test_stack_slides_down_under_the_bot() {
+        let plan = plan_stack(8, 10, &[role(1, 3), role(2, 4), role(3, 5)]);
+        assert_eq!(plan, vec![role(1, 7), role(2, 8), role(3, 9)]);
+        assert!(plan.iter().all(|(_, position)| *position < 10));
+    }
+
+    #[test]
+    fn test_outcome_summary() {
+        let outcome = RepositionOutcome {
+            moved: 2,
+            unchanged: 1,
+            failed: 0,
+        };
+        assert_eq!(outcome.summary(), "2 moved, 1 already in place");
+        assert_eq!(RepositionOutcome::default().summary(), "0 moved");
+    }
+}
@@ -0,0 +1,59 @@
+/// Longest rules text the modal accepts; it has to fit one embed description
+pub const MAX_RULES_LENGTH: usize = 2000;
+
+/// What to do before running a customization command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AckDecision {
+    Proceed,
+    /// Show the rules and wait for the member to agree to `version`
+    Prompt {
+        version: i64,
+    },
+}
+
+/// Whether a member must (re-)acknowledge the guild's naming rules.
+/// `rules_version` is `None` when no rules were ever set, and
+/// `acknowledged` is the last version the member agreed to.
+pub fn ack_decision(
+    required: bool,
+    rules_version: Option<i64>,
+    acknowledged: Option<i64>,
+) -> AckDecision {
+    let Some(version) = rules_version else {
+        return AckDecision::Proceed;
+    };
+    if !required || acknowledged.is_some_and(|acked| acked >= version) {
+        return AckDecision::Proceed;
+    }
+    AckDecision::Prompt { version }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_time_prompts() {
+        assert_eq!(
+            ack_decision(true, Some(1), None),
+            AckDecision::Prompt { version: 1 }
+        );
+        assert_eq!(ack_decision(true, Some(1), Some(1)), AckDecision::Proceed);
+    }
+
+    #[test]
+    fn test_updated_rules_need_reacknowledgment() {
+        assert_eq!(
+            ack_decision(true, Some(3), Some(2)),
+            AckDecision::Prompt { version: 3 }
+        );
+        assert_eq!(ack_decision(true, Some(3), Some(3)), AckDecision::Proceed);
+    }
+
+    #[test]
+    fn test_toggle_off_or_no_rules_proceeds() {
+        assert_eq!(ack_decision(false, Some(2), None), AckDecision::Proceed);
+        assert_eq!(ack_decision(false, Some(2), Some(1)), AckDecision::Proceed);
+        assert_eq!(ack_decision(true, None, None), AckDecision::Proceed);
+    }
+}
@@ -0,0 +1,262 @@
+use chrono::{DateTime, Duration, NaiveDateTime, SecondsFormat, Utc};
+
+/// Furthest ahead a change can be scheduled
+pub const MAX_SCHEDULE_AHEAD_DAYS: i64 = 30;
+
+/// Longest a scheduled change may stay applied before reverting
+pub const MAX_REVERT_AFTER_DAYS: i64 = 30;
+
+/// Changes a member can have waiting at once
+pub const MAX_ACTIVE_PER_USER: i64 = 3;
+
+/// A change missed while the bot was offline still applies if it's at most
+/// this late; after that it expires so nobody gets a stale birthday role
+pub const MISSED_GRACE_HOURS: i64 = 6;
+
+/// Lifecycle of a scheduled change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleStatus {
+    Pending,
+    Applied,
+    Reverted,
+    Expired,
+    Cancelled,
+    Failed,
+}
+
+impl ScheduleStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScheduleStatus::Pending => "pending",
+            ScheduleStatus::Applied => "applied",
+            ScheduleStatus::Reverted => "reverted",
+            ScheduleStatus::Expired => "expired",
+            ScheduleStatus::Cancelled => "cancelled",
+            ScheduleStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(ScheduleStatus::Pending),
+            "applied" => Some(ScheduleStatus::Applied),
+            "reverted" => Some(ScheduleStatus::Reverted),
+            "expired" => Some(ScheduleStatus::Expired),
+            "cancelled" => Some(ScheduleStatus::Cancelled),
+            "failed" => Some(ScheduleStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// What the scheduler should do with a change right now
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleAction {
+    Wait,
+    Apply,
+    Revert,
+    /// The apply time passed more than the grace period ago
+    Expire,
+}
+
+/// Decide the next step for a change. `revert_at` is only known once the
+/// change has been applied, since the revert is timed from application.
+pub fn next_action(
+    status: ScheduleStatus,
+    scheduled_at: DateTime<Utc>,
+    revert_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> ScheduleAction {
+    match status {
+        ScheduleStatus::Pending if now < scheduled_at => ScheduleAction::Wait,
+        ScheduleStatus::Pending if now - scheduled_at <= Duration::hours(MISSED_GRACE_HOURS) => {
+            ScheduleAction::Apply
+        }
+        ScheduleStatus::Pending => ScheduleAction::Expire,
+        // A late revert still restores what the member had before
+        ScheduleStatus::Applied if revert_at.is_some_and(|at| now >= at) => ScheduleAction::Revert,
+        _ => ScheduleAction::Wait,
+    }
+}
+
+/// When an applied change should be reverted
+pub fn revert_time(
+    applied_at: DateTime<Utc>,
+    revert_after_secs: Option<i64>,
+) -> Option<DateTime<Utc>> {
+    revert_after_secs.map(|secs| applied_at + Duration::seconds(secs))
+}
+
+/// Parse a duration like `90m`, `3h`, `2d`, `1w` or `1d12h`
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() {
+        return Err("Duration is empty".to_string());
+    }
+
+    let mut total = Duration::zero();
+    let mut digits = String::new();
+    for c in input.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let amount: i64 = digits
+            .parse()
+            .map_err(|_| format!("Expected a number before `{}`", c))?;
+        digits.clear();
+        total += match c {
+            'm' => Duration::minutes(amount),
+            'h' => Duration::hours(amount),
+            'd' => Duration::days(amount),
+            'w' => Duration::weeks(amount),
+            _ => return Err(format!("Unknown unit `{}`; use m, h, d or w", c)),
+        };
+    }
+    if !digits.is_empty() {
+        return Err("Every number needs a unit (m, h, d or w)".to_string());
+    }
+    if total <= Duration::zero() {
+        return Err("Duration must be longer than zero".to_string());
+    }
+    Ok(total)
+}
+
+/// Parse when a change should happen: a delay such as `3d` or `in 2h`, or a
+/// UTC date and time such as `2025-06-14 18:00`
+pub fn parse_when(input: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    let input = input.trim();
+    let relative = input.strip_prefix("in ").unwrap_or(input);
+
+    let at = if let Ok(delay) = parse_duration(relative) {
+        now + delay
+    } else if let Ok(at) = DateTime::parse_from_rfc3339(input) {
+        at.with_timezone(&Utc)
+    } else if let Ok(at) = NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M") {
+        at.and_utc()
+    } else {
+        return Err(
+            "Use a delay like `3d` or `in 2h`, or a UTC time like `2025-06-14 18:00`".to_string(),
+        );
+    };
+
+    if at <= now {
+        return Err("That time has already passed".to_string());
+    }
+    if at - now > Duration::days(MAX_SCHEDULE_AHEAD_DAYS) {
+        return Err(format!(
+            "Changes can be scheduled at most {} days ahead",
+            MAX_SCHEDULE_AHEAD_DAYS
+        ));
+    }
+    Ok(at)
+}
+
+/// Stored form of a schedule timestamp; sorts correctly as text
+pub fn to_stored(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 6, 14, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_pending_waits_then_applies() {
+        let scheduled = at(12);
+        assert_eq!(
+            next_action(ScheduleStatus::Pending, scheduled, None, at(11)),
+            ScheduleAction::Wait
+        );
+        assert_eq!(
+            next_action(ScheduleStatus::Pending, scheduled, None, at(12)),
+            ScheduleAction::Apply
+        );
+    }
+
+    #[test]
+    fn test_downtime_spanning_the_scheduled_moment() {
+        let scheduled = at(12);
+        // Back up within the grace period: apply late
+        assert_eq!(
+            next_action(ScheduleStatus::Pending, scheduled, None, at(17)),
+            ScheduleAction::Apply
+        );
+        // Down for longer than the grace period: expire instead
+        assert_eq!(
+            next_action(ScheduleStatus::Pending, scheduled, None, at(19)),
+            ScheduleAction::Expire
+        );
+    }
+
+    #[test]
+    fn test_applied_reverts_when_due_even_if_late() {
+        let applied_at = at(12);
+        let revert_at = revert_time(applied_at, Some(3 * 3600));
+        assert_eq!(revert_at, Some(at(15)));
+
+        assert_eq!(
+            next_action(ScheduleStatus::Applied, at(12), revert_at, at(14)),
+            ScheduleAction::Wait
+        );
+        assert_eq!(
+            next_action(ScheduleStatus::Applied, at(12), revert_at, at(15)),
+            ScheduleAction::Revert
+        );
+        // The bot was down over the revert time
+        assert_eq!(
+            next_action(ScheduleStatus::Applied, at(12), revert_at, at(23)),
+            ScheduleAction::Revert
+        );
+        // Without a revert, an applied change is finished
+        assert_eq!(
+            next_action(ScheduleStatus::Applied, at(12), None, at(23)),
+            ScheduleAction::Wait
+        );
+    }
+
+    #[test]
+    fn test_finished_states_never_act() {
+        for status in [
+            ScheduleStatus::Reverted,
+            ScheduleStatus::Expired,
+            ScheduleStatus::Cancelled,
+            ScheduleStatus::Failed,
+        ] {
+            assert_eq!(
+                next_action(status, at(1), Some(at(2)), at(23)),
+                ScheduleAction::Wait
+            );
+            assert_eq!(ScheduleStatus::parse(status.as_str()), Some(status));
+        }
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90m"), Ok(Duration::minutes(90)));
+        assert_eq!(parse_duration("1d12h"), Ok(Duration::hours(36)));
+        assert_eq!(parse_duration("2W"), Ok(Duration::weeks(2)));
+        assert!(parse_duration("12").is_err());
+        assert!(parse_duration("3y").is_err());
+        assert!(parse_duration("0h").is_err());
+    }
+
+    #[test]
+    fn test_parse_when() {
+        let now = at(10);
+        assert_eq!(parse_when("in 2h", now), Ok(at(12)));
+        assert_eq!(parse_when("2025-06-14 18:00", now), Ok(at(18)));
+        assert_eq!(parse_when("2025-06-14T20:00:00Z", now), Ok(at(20)));
+        assert!(parse_when("2025-06-14 09:00", now).is_err());
+        assert!(parse_when("45d", now).is_err());
+        assert!(parse_when("saturday", now).is_err());
+
+        let stored = to_stored(at(12));
+        assert_eq!(stored, "2025-06-14T12:00:00Z");
+    }
+}
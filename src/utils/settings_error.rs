@@ -14,9 +14,6 @@ pub enum SettingsError {
     #[error("Settings limit exceeded: {0}")]
     LimitExceeded(String),
 
-    #[error("Insufficient permissions")]
-    InsufficientPermissions,
-
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
 
@@ -0,0 +1,247 @@
+use crate::utils::scheduled_change::parse_duration;
+use chrono::{DateTime, Duration, Utc};
+use serenity::all::HttpError;
+
+/// Discord JSON error codes for a user who isn't in the guild
+const UNKNOWN_MEMBER_CODE: isize = 10007;
+const UNKNOWN_USER_CODE: isize = 10013;
+
+/// Discord JSON error code for a role that no longer exists
+const UNKNOWN_ROLE_CODE: isize = 10011;
+
+/// Longest a share can be given for before it expires
+pub const MAX_SHARE_DURATION_DAYS: i64 = 365;
+
+/// Most shares revoked in one guild per sweep; the rest wait for the next run
+pub const MAX_REVOCATIONS_PER_RUN: usize = 50;
+
+/// A guild's choice of which share recipients to re-check
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RevalidationPolicy {
+    /// Re-check recipients at all
+    pub enabled: bool,
+    /// Also require recipients to meet the guild's booster role eligibility
+    pub require_eligibility: bool,
+}
+
+/// What the sweep knows about a share's recipient
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecipientState {
+    /// Discord says they aren't in the guild
+    NotMember,
+    /// In the guild; `eligible` is their booster/premium eligibility
+    Member { eligible: bool },
+    /// The lookup failed, so nothing is known either way
+    Unknown,
+}
+
+/// Why a share was revoked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevokeReason {
+    LeftGuild,
+    LostEligibility,
+}
+
+impl RevokeReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::LeftGuild => "left_guild",
+            Self::LostEligibility => "lost_eligibility",
+        }
+    }
+
+    /// Sentence for the recipient's DM and the audit log
+    pub fn describe(self) -> &'static str {
+        match self {
+            Self::LeftGuild => "you are no longer a member of the server",
+            Self::LostEligibility => "you no longer meet the server's booster role requirements",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareVerdict {
+    Keep,
+    Revoke(RevokeReason),
+}
+
+/// Classify a member lookup: a definite "not in the guild" answer versus a
+/// failure that says nothing about membership
+pub fn recipient_state_from_error(error: &serenity::Error) -> RecipientState {
+    match error {
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(response))
+            if matches!(response.error.code, UNKNOWN_MEMBER_CODE | UNKNOWN_USER_CODE) =>
+        {
+            RecipientState::NotMember
+        }
+        _ => RecipientState::Unknown,
+    }
+}
+
+/// Decide whether a share survives revalidation
+pub fn revalidate(state: RecipientState, policy: RevalidationPolicy) -> ShareVerdict {
+    if !policy.enabled {
+        return ShareVerdict::Keep;
+    }
+
+    match state {
+        RecipientState::NotMember => ShareVerdict::Revoke(RevokeReason::LeftGuild),
+        RecipientState::Member { eligible: false } if policy.require_eligibility => {
+            ShareVerdict::Revoke(RevokeReason::LostEligibility)
+        }
+        RecipientState::Member { .. } | RecipientState::Unknown => ShareVerdict::Keep,
+    }
+}
+
+/// When a share given for `input` (e.g. `7d`, `12h`, `30m`) ends
+pub fn share_expiry(input: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    let duration = parse_duration(input)?;
+    if duration > Duration::days(MAX_SHARE_DURATION_DAYS) {
+        return Err(format!(
+            "Shares can last at most {} days",
+            MAX_SHARE_DURATION_DAYS
+        ));
+    }
+    Ok(now + duration)
+}
+
+/// Whether a failed role removal for an expired share needs no retry: the
+/// member, user or role is gone, so there's nothing left to take back
+pub fn removal_settled(error: &serenity::Error) -> bool {
+    matches!(
+        error,
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(response))
+            if matches!(
+                response.error.code,
+                UNKNOWN_MEMBER_CODE | UNKNOWN_USER_CODE | UNKNOWN_ROLE_CODE
+            )
+    )
+}
+
+/// Shares to revoke this run, at most `cap`, and whether the cap was hit
+pub fn plan_revocations<T>(
+    verdicts: impl IntoIterator<Item = (T, ShareVerdict)>,
+    cap: usize,
+) -> (Vec<(T, RevokeReason)>, bool) {
+    let mut planned = Vec::new();
+    let mut capped = false;
+
+    for (share, verdict) in verdicts {
+        let ShareVerdict::Revoke(reason) = verdict else {
+            continue;
+        };
+        if planned.len() == cap {
+            capped = true;
+            break;
+        }
+        planned.push((share, reason));
+    }
+
+    (planned, capped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ON: RevalidationPolicy = RevalidationPolicy {
+        enabled: true,
+        require_eligibility: false,
+    };
+    const STRICT: RevalidationPolicy = RevalidationPolicy {
+        enabled: true,
+        require_eligibility: true,
+    };
+
+    #[test]
+    fn test_disabled_policy_keeps_everything() {
+        let off = RevalidationPolicy::default();
+        for state in [
+            RecipientState::NotMember,
+            RecipientState::Member { eligible: false },
+            RecipientState::Member { eligible: true },
+            RecipientState::Unknown,
+        ] {
+            assert_eq!(revalidate(state, off), ShareVerdict::Keep);
+        }
+    }
+
+    #[test]
+    fn test_membership_only() {
+        assert_eq!(
+            revalidate(RecipientState::NotMember, ON),
+            ShareVerdict::Revoke(RevokeReason::LeftGuild)
+        );
+        assert_eq!(
+            revalidate(RecipientState::Member { eligible: false }, ON),
+            ShareVerdict::Keep
+        );
+        assert_eq!(
+            revalidate(RecipientState::Member { eligible: true }, ON),
+            ShareVerdict::Keep
+        );
+    }
+
+    #[test]
+    fn test_eligibility_required() {
+        assert_eq!(
+            revalidate(RecipientState::NotMember, STRICT),
+            ShareVerdict::Revoke(RevokeReason::LeftGuild)
+        );
+        assert_eq!(
+            revalidate(RecipientState::Member { eligible: false }, STRICT),
+            ShareVerdict::Revoke(RevokeReason::LostEligibility)
+        );
+        assert_eq!(
+            revalidate(RecipientState::Member { eligible: true }, STRICT),
+            ShareVerdict::Keep
+        );
+    }
+
+    #[test]
+    fn test_failed_lookups_never_revoke() {
+        assert_eq!(
+            revalidate(RecipientState::Unknown, STRICT),
+            ShareVerdict::Keep
+        );
+    }
+
+    #[test]
+    fn test_plan_caps_revocations() {
+        let verdicts = (0..120).map(|i| {
+            let verdict = if i % 2 == 0 {
+                ShareVerdict::Revoke(RevokeReason::LeftGuild)
+            } else {
+                ShareVerdict::Keep
+            };
+            (i, verdict)
+        });
+
+        let (planned, capped) = plan_revocations(verdicts, MAX_REVOCATIONS_PER_RUN);
+        assert!(capped);
+        assert_eq!(planned.len(), MAX_REVOCATIONS_PER_RUN);
+        assert!(planned.iter().all(|(i, _)| i % 2 == 0));
+
+        let few = vec![
+            (1, ShareVerdict::Revoke(RevokeReason::LostEligibility)),
+            (2, ShareVerdict::Keep),
+        ];
+        let (planned, capped) = plan_revocations(few, MAX_REVOCATIONS_PER_RUN);
+        assert!(!capped);
+        assert_eq!(planned, vec![(1, RevokeReason::LostEligibility)]);
+
+        // Exactly at the cap isn't reported as capped
+        let exact = (0..3).map(|i| (i, ShareVerdict::Revoke(RevokeReason::LeftGuild)));
+        assert!(!plan_revocations(exact, 3).1);
+    }
+
+    #[test]
+    fn test_share_expiry() {
+        let now = Utc::now();
+        assert_eq!(share_expiry("7d", now), Ok(now + Duration::days(7)));
+        assert_eq!(share_expiry("12h", now), Ok(now + Duration::hours(12)));
+        assert_eq!(share_expiry("30m", now), Ok(now + Duration::minutes(30)));
+        assert!(share_expiry("53w", now).is_err());
+        assert!(share_expiry("soon", now).is_err());
+    }
+}
@@ -0,0 +1,60 @@
+use tokio::time::Interval;
+use tokio_util::sync::CancellationToken;
+
+/// Wait for the next tick of a background loop; `false` once shutdown starts.
+///
+/// Loops written as `while next_tick(&mut interval, &token).await { ... }` stop
+/// between runs, so a sweep that's in progress finishes before the task exits.
+pub async fn next_tick(interval: &mut Interval, shutdown: &CancellationToken) -> bool {
+    tokio::select! {
+        biased;
+        _ = shutdown.cancelled() => false,
+        _ = interval.tick() => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_cancellation_stops_loop_promptly() {
+        let token = CancellationToken::new();
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let handle = tokio::spawn({
+            let token = token.clone();
+            let runs = runs.clone();
+            async move {
+                let mut interval = tokio::time::interval(Duration::from_millis(10));
+                while next_tick(&mut interval, &token).await {
+                    runs.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+        });
+
+        tokio::time::sleep(Duration::from_millis(35)).await;
+        token.cancel();
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("loop didn't stop after cancellation")
+            .unwrap();
+
+        let after_cancel = runs.load(Ordering::SeqCst);
+        assert!(after_cancel > 0);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), after_cancel);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_token_skips_pending_tick() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let mut interval = tokio::time::interval(Duration::from_secs(3600));
+        // The first tick is immediate, but shutdown wins
+        assert!(!next_tick(&mut interval, &token).await);
+    }
+}
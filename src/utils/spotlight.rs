@@ -0,0 +1,166 @@
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use serenity::all::{HttpError, RoleId, UserId};
+use std::collections::HashMap;
+
+/// Discord JSON error code for a deleted or inaccessible channel
+const UNKNOWN_CHANNEL_CODE: isize = 10003;
+
+/// Roles younger than this are not spotlighted yet
+pub const MIN_ROLE_AGE_DAYS: i64 = 7;
+
+/// Roles featured within this window are skipped while others remain
+pub const RECENCY_EXCLUSION_DAYS: i64 = 28;
+
+/// A booster role that could be featured
+#[derive(Debug, Clone)]
+pub struct SpotlightCandidate {
+    pub role_id: RoleId,
+    pub user_id: UserId,
+    pub created_at: DateTime<Utc>,
+    pub locked: bool,
+}
+
+/// Pick the next role to spotlight.
+///
+/// Locked roles and roles younger than a week are ineligible. Among the rest,
+/// roles featured in the last four weeks are skipped unless nothing else is
+/// left, and the role featured least recently (never-featured first, then
+/// oldest role) wins so every booster gets a turn.
+pub fn select_spotlight(
+    candidates: &[SpotlightCandidate],
+    last_spotlighted: &HashMap<RoleId, DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Option<RoleId> {
+    let eligible: Vec<&SpotlightCandidate> = candidates
+        .iter()
+        .filter(|c| !c.locked)
+        .filter(|c| now - c.created_at >= Duration::days(MIN_ROLE_AGE_DAYS))
+        .collect();
+
+    let recency_cutoff = now - Duration::days(RECENCY_EXCLUSION_DAYS);
+    let fresh: Vec<&SpotlightCandidate> = eligible
+        .iter()
+        .copied()
+        .filter(|c| {
+            last_spotlighted
+                .get(&c.role_id)
+                .is_none_or(|at| *at < recency_cutoff)
+        })
+        .collect();
+
+    let pool = if fresh.is_empty() { eligible } else { fresh };
+
+    pool.into_iter()
+        .min_by_key(|c| {
+            (
+                last_spotlighted.get(&c.role_id).copied(),
+                c.created_at,
+                c.role_id,
+            )
+        })
+        .map(|c| c.role_id)
+}
+
+/// Parse SQLite's `CURRENT_TIMESTAMP` format as UTC
+pub fn parse_sqlite_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+/// Whether a Discord API error means the target channel no longer exists
+pub fn is_unknown_channel(error: &serenity::Error) -> bool {
+    matches!(
+        error,
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(response))
+            if response.error.code == UNKNOWN_CHANNEL_CODE
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        parse_sqlite_timestamp("2024-06-01 12:00:00").unwrap()
+    }
+
+    fn candidate(role: u64, age_days: i64) -> SpotlightCandidate {
+        SpotlightCandidate {
+            role_id: RoleId::new(role),
+            user_id: UserId::new(role + 1000),
+            created_at: now() - Duration::days(age_days),
+            locked: false,
+        }
+    }
+
+    #[test]
+    fn test_no_candidates() {
+        assert_eq!(select_spotlight(&[], &HashMap::new(), now()), None);
+    }
+
+    #[test]
+    fn test_young_and_locked_roles_ineligible() {
+        let mut locked = candidate(1, 30);
+        locked.locked = true;
+        let young = candidate(2, 3);
+
+        assert_eq!(
+            select_spotlight(&[locked, young], &HashMap::new(), now()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_never_featured_before_featured() {
+        let history = HashMap::from([(RoleId::new(1), now() - Duration::days(60))]);
+        let picked = select_spotlight(&[candidate(1, 90), candidate(2, 10)], &history, now());
+        assert_eq!(picked, Some(RoleId::new(2)));
+    }
+
+    #[test]
+    fn test_oldest_role_first_among_unfeatured() {
+        let picked = select_spotlight(
+            &[candidate(1, 10), candidate(2, 40), candidate(3, 20)],
+            &HashMap::new(),
+            now(),
+        );
+        assert_eq!(picked, Some(RoleId::new(2)));
+    }
+
+    #[test]
+    fn test_recent_feature_excluded() {
+        let history = HashMap::from([
+            (RoleId::new(1), now() - Duration::days(7)),
+            (RoleId::new(2), now() - Duration::days(35)),
+        ]);
+        let picked = select_spotlight(&[candidate(1, 90), candidate(2, 90)], &history, now());
+        assert_eq!(picked, Some(RoleId::new(2)));
+    }
+
+    #[test]
+    fn test_falls_back_to_least_recent_when_all_recent() {
+        let history = HashMap::from([
+            (RoleId::new(1), now() - Duration::days(7)),
+            (RoleId::new(2), now() - Duration::days(14)),
+        ]);
+        let picked = select_spotlight(&[candidate(1, 90), candidate(2, 90)], &history, now());
+        assert_eq!(picked, Some(RoleId::new(2)));
+    }
+
+    #[test]
+    fn test_round_robin_over_weeks() {
+        let roles = [candidate(1, 90), candidate(2, 90), candidate(3, 90)];
+        let mut history = HashMap::new();
+        let mut picks = Vec::new();
+
+        for week in 0..6 {
+            let at = now() + Duration::weeks(week);
+            let picked = select_spotlight(&roles, &history, at).unwrap();
+            history.insert(picked, at);
+            picks.push(picked.get());
+        }
+
+        assert_eq!(picks, vec![1, 2, 3, 1, 2, 3]);
+    }
+}
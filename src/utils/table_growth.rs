@@ -0,0 +1,125 @@
+/// Tables whose per-guild row counts are sampled every night
+pub const TRACKED_TABLES: &[&str] = &[
+    "booster_roles",
+    "booster_role_shares",
+    "booster_rename_history",
+    "name_violations",
+    "settings_audit_log",
+];
+
+/// Growth this many times the trailing average counts as an anomaly
+pub const DEFAULT_GROWTH_MULTIPLIER: f64 = 5.0;
+
+/// Days of history the trailing average covers
+pub const TRAILING_DAYS: usize = 7;
+
+/// Days of history needed before a table is judged at all
+pub const MIN_HISTORY_DAYS: usize = 3;
+
+/// Rows a table must gain in a day before it can be flagged, so a small
+/// guild going from 2 to 12 rows doesn't look like a 5x spike
+pub const MIN_ROWS_ADDED: i64 = 50;
+
+/// A day of growth well above the table's recent normal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GrowthAnomaly {
+    pub rows_added: i64,
+    pub trailing_average: f64,
+}
+
+impl GrowthAnomaly {
+    pub fn describe(&self) -> String {
+        format!(
+            "+{} rows today vs {:.1}/day over the last {} days",
+            self.rows_added, self.trailing_average, TRAILING_DAYS
+        )
+    }
+}
+
+/// Compare today's growth against the trailing average of daily growth.
+/// `history` is the daily row count, oldest first, not including `current`.
+pub fn detect_anomaly(history: &[i64], current: i64, multiplier: f64) -> Option<GrowthAnomaly> {
+    if history.len() < MIN_HISTORY_DAYS {
+        return None;
+    }
+
+    let window = &history[history.len().saturating_sub(TRAILING_DAYS + 1)..];
+    let deltas: Vec<i64> = window.windows(2).map(|w| (w[1] - w[0]).max(0)).collect();
+    let trailing_average = deltas.iter().sum::<i64>() as f64 / deltas.len() as f64;
+
+    let rows_added = current - history[history.len() - 1];
+    if rows_added < MIN_ROWS_ADDED {
+        return None;
+    }
+
+    // A table that normally doesn't grow is compared against one row a day
+    if rows_added as f64 > trailing_average.max(1.0) * multiplier {
+        Some(GrowthAnomaly {
+            rows_added,
+            trailing_average,
+        })
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(start: i64, daily: i64, days: usize) -> Vec<i64> {
+        (0..days as i64).map(|d| start + d * daily).collect()
+    }
+
+    #[test]
+    fn test_steady_growth_is_not_flagged() {
+        let history = series(1000, 40, 8);
+        let last = *history.last().unwrap();
+        assert_eq!(
+            detect_anomaly(&history, last + 45, DEFAULT_GROWTH_MULTIPLIER),
+            None
+        );
+    }
+
+    #[test]
+    fn test_spike_is_flagged() {
+        let history = series(1000, 20, 8);
+        let last = *history.last().unwrap();
+        let anomaly = detect_anomaly(&history, last + 400, DEFAULT_GROWTH_MULTIPLIER).unwrap();
+        assert_eq!(anomaly.rows_added, 400);
+        assert_eq!(anomaly.trailing_average, 20.0);
+    }
+
+    #[test]
+    fn test_small_guilds_need_an_absolute_jump() {
+        // 10x growth, but only a handful of rows
+        let history = series(5, 1, 8);
+        let last = *history.last().unwrap();
+        assert_eq!(
+            detect_anomaly(&history, last + 10, DEFAULT_GROWTH_MULTIPLIER),
+            None
+        );
+
+        // A quiet table suddenly gaining hundreds of rows is flagged
+        let flat = vec![5; 8];
+        assert!(detect_anomaly(&flat, 305, DEFAULT_GROWTH_MULTIPLIER).is_some());
+    }
+
+    #[test]
+    fn test_needs_enough_history_and_uses_trailing_window() {
+        assert_eq!(
+            detect_anomaly(&[0, 0], 500, DEFAULT_GROWTH_MULTIPLIER),
+            None
+        );
+
+        // An old burst outside the 7-day window doesn't raise the baseline
+        let mut history = vec![0, 10_000];
+        history.extend(series(10_000, 10, 8).into_iter().skip(1));
+        let last = *history.last().unwrap();
+        assert!(detect_anomaly(&history, last + 100, DEFAULT_GROWTH_MULTIPLIER).is_some());
+
+        // Pruning shrinks a table; that isn't negative growth in the average
+        let pruned = vec![1000, 1000, 400, 420, 440];
+        assert!(detect_anomaly(&pruned, 540, 2.0).is_some());
+    }
+}
@@ -0,0 +1,409 @@
+use poise::serenity_prelude::{CommandOption, CommandOptionType};
+
+/// Widest integer range treated as a real constraint rather than the
+/// parameter type's own limits
+const MEANINGFUL_RANGE: f64 = 10_000.0;
+
+/// Most examples listed for one command group in help
+pub const MAX_GROUP_EXAMPLES: usize = 8;
+
+/// What kind of value a parameter takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    Text,
+    Integer,
+    Number,
+    Boolean,
+    User,
+    Channel,
+    Role,
+    Mentionable,
+    Attachment,
+}
+
+/// The parts of a poise parameter an example needs
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamSpec {
+    pub name: String,
+    pub kind: ParamKind,
+    pub choices: Vec<String>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub min_length: Option<u16>,
+    pub max_length: Option<u16>,
+}
+
+impl ParamSpec {
+    pub fn new(name: &str, kind: ParamKind) -> Self {
+        Self {
+            name: name.to_string(),
+            kind,
+            choices: Vec::new(),
+            min: None,
+            max: None,
+            min_length: None,
+            max_length: None,
+        }
+    }
+
+    /// Read the type and constraints poise's type setter puts on the slash
+    /// option, so the example follows the same rules Discord enforces
+    pub fn from_parameter<U, E>(param: &poise::CommandParameter<U, E>) -> Self {
+        let mut spec = Self::new(&param.name, ParamKind::Text);
+        spec.choices = param.choices.iter().map(|c| c.name.clone()).collect();
+
+        let option = param
+            .create_as_slash_command_option()
+            .and_then(|builder| serde_json::to_value(builder).ok())
+            .and_then(|value| serde_json::from_value::<CommandOption>(value).ok());
+        if let Some(option) = option {
+            spec.kind = match option.kind {
+                CommandOptionType::Integer => ParamKind::Integer,
+                CommandOptionType::Number => ParamKind::Number,
+                CommandOptionType::Boolean => ParamKind::Boolean,
+                CommandOptionType::User => ParamKind::User,
+                CommandOptionType::Channel => ParamKind::Channel,
+                CommandOptionType::Role => ParamKind::Role,
+                CommandOptionType::Mentionable => ParamKind::Mentionable,
+                CommandOptionType::Attachment => ParamKind::Attachment,
+                _ => ParamKind::Text,
+            };
+            spec.min = option.min_value.and_then(|n| n.as_f64());
+            spec.max = option.max_value.and_then(|n| n.as_f64());
+            spec.min_length = option.min_length;
+            spec.max_length = option.max_length;
+        }
+        spec
+    }
+}
+
+/// One command invocation, written both ways
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageExample {
+    pub slash: String,
+    /// `None` when the command can't be used with a prefix
+    pub prefix: Option<String>,
+}
+
+/// A plausible value for `spec` that passes its choices, range and length
+pub fn example_value(spec: &ParamSpec) -> String {
+    if let Some(choice) = spec.choices.first() {
+        return choice.clone();
+    }
+
+    match spec.kind {
+        ParamKind::User | ParamKind::Mentionable => "@User".to_string(),
+        ParamKind::Role => "@Role".to_string(),
+        ParamKind::Channel => "#general".to_string(),
+        ParamKind::Boolean => "true".to_string(),
+        ParamKind::Attachment => "[attach a file]".to_string(),
+        ParamKind::Integer => (example_number(spec).floor() as i64).to_string(),
+        ParamKind::Number => example_number(spec).to_string(),
+        ParamKind::Text => fit_length(example_text(&spec.name), spec),
+    }
+}
+
+fn example_number(spec: &ParamSpec) -> f64 {
+    match (spec.min, spec.max) {
+        (Some(min), Some(max)) if max - min <= MEANINGFUL_RANGE => {
+            let mid = min + (max - min) / 2.0;
+            if spec.kind == ParamKind::Integer {
+                mid.floor()
+            } else {
+                mid
+            }
+        }
+        (min, max) => {
+            let value = min.map_or(1.0, |min| f64::max(min, 1.0));
+            max.map_or(value, |max| f64::min(value, max))
+        }
+    }
+}
+
+fn example_text(name: &str) -> String {
+    let name = name.to_lowercase();
+    let value = if name.contains("color") || name.contains("colour") {
+        "#FF8800"
+    } else if name.contains("url") || name.contains("icon") || name.contains("image") {
+        "https://example.com/icon.png"
+    } else if name.contains("name") {
+        "Sunset"
+    } else if name.contains("prefix") {
+        "?"
+    } else if name.contains("when") || name.contains("after") || name.contains("duration") {
+        "2h"
+    } else if name.contains("command") {
+        "help"
+    } else {
+        "example"
+    };
+    value.to_string()
+}
+
+fn fit_length(mut value: String, spec: &ParamSpec) -> String {
+    if let Some(min) = spec.min_length {
+        while value.chars().count() < min as usize {
+            value.push('x');
+        }
+    }
+    if let Some(max) = spec.max_length {
+        value = value.chars().take(max as usize).collect();
+    }
+    value
+}
+
+/// Build the slash and prefix forms of one invocation of `path`
+/// (e.g. `["boosterrole", "filter", "add"]`)
+pub fn build_example(
+    path: &[&str],
+    params: &[ParamSpec],
+    prefix: &str,
+    prefix_enabled: bool,
+) -> UsageExample {
+    let command = path.join(" ");
+
+    let mut slash = format!("/{}", command);
+    for spec in params {
+        slash.push_str(&format!(" {}:{}", spec.name, example_value(spec)));
+    }
+
+    // Attachments can't be typed, and the prefix parser stops at the first
+    // parameter it can't fill, so nothing after one is written out
+    let prefix = prefix_enabled.then(|| {
+        let mut line = format!("{}{}", prefix, command);
+        for spec in params {
+            if spec.kind == ParamKind::Attachment {
+                break;
+            }
+            let value = example_value(spec);
+            if value.contains(char::is_whitespace) {
+                line.push_str(&format!(" \"{}\"", value));
+            } else {
+                line.push_str(&format!(" {}", value));
+            }
+        }
+        line
+    });
+
+    UsageExample { slash, prefix }
+}
+
+/// Examples for a command, or for each runnable subcommand of a group
+pub fn command_examples<U, E>(
+    command: &poise::Command<U, E>,
+    parent: &[&str],
+    prefix: &str,
+) -> Vec<UsageExample> {
+    let mut path = parent.to_vec();
+    path.push(&command.name);
+
+    if command.subcommands.is_empty() {
+        let params: Vec<ParamSpec> = command
+            .parameters
+            .iter()
+            .map(ParamSpec::from_parameter)
+            .collect();
+        return vec![build_example(
+            &path,
+            &params,
+            prefix,
+            command.prefix_action.is_some(),
+        )];
+    }
+
+    command
+        .subcommands
+        .iter()
+        .flat_map(|sub| command_examples(sub, &path, prefix))
+        .collect()
+}
+
+/// Help text for a command's examples: both forms for a single command,
+/// slash forms for a group
+pub fn render_examples(examples: &[UsageExample]) -> String {
+    if let [example] = examples {
+        return match &example.prefix {
+            Some(prefix) => format!("**Slash:** `{}`\n**Prefix:** `{}`", example.slash, prefix),
+            None => format!("**Slash:** `{}`", example.slash),
+        };
+    }
+
+    let mut lines: Vec<String> = examples
+        .iter()
+        .take(MAX_GROUP_EXAMPLES)
+        .map(|example| format!("`{}`", example.slash))
+        .collect();
+    if examples.len() > MAX_GROUP_EXAMPLES {
+        lines.push(format!(
+            "…and {} more, see `/help` on a subcommand",
+            examples.len() - MAX_GROUP_EXAMPLES
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::{Data, Error};
+
+    fn find<'a>(
+        commands: &'a [poise::Command<Data, Error>],
+        path: &[&str],
+    ) -> &'a poise::Command<Data, Error> {
+        let command = commands.iter().find(|c| c.name == path[0]).unwrap();
+        if path.len() == 1 {
+            command
+        } else {
+            find(&command.subcommands, &path[1..])
+        }
+    }
+
+    #[test]
+    fn test_mentions_and_flags() {
+        assert_eq!(
+            example_value(&ParamSpec::new("user", ParamKind::User)),
+            "@User"
+        );
+        assert_eq!(
+            example_value(&ParamSpec::new("role", ParamKind::Role)),
+            "@Role"
+        );
+        assert_eq!(
+            example_value(&ParamSpec::new("channel", ParamKind::Channel)),
+            "#general"
+        );
+        assert_eq!(
+            example_value(&ParamSpec::new("enabled", ParamKind::Boolean)),
+            "true"
+        );
+    }
+
+    #[test]
+    fn test_text_uses_name_and_length_limits() {
+        assert_eq!(
+            example_value(&ParamSpec::new("color", ParamKind::Text)),
+            "#FF8800"
+        );
+        assert_eq!(
+            example_value(&ParamSpec::new("name", ParamKind::Text)),
+            "Sunset"
+        );
+
+        let mut short = ParamSpec::new("word", ParamKind::Text);
+        short.max_length = Some(3);
+        assert_eq!(example_value(&short), "exa");
+
+        let mut long = ParamSpec::new("code", ParamKind::Text);
+        long.min_length = Some(10);
+        assert_eq!(example_value(&long).len(), 10);
+    }
+
+    #[test]
+    fn test_numbers_respect_range() {
+        let mut limit = ParamSpec::new("limit", ParamKind::Integer);
+        limit.min = Some(0.0);
+        limit.max = Some(100.0);
+        assert_eq!(example_value(&limit), "50");
+
+        // Type limits alone aren't a useful range
+        let mut days = ParamSpec::new("days", ParamKind::Integer);
+        days.min = Some(-9007199254740991.0);
+        days.max = Some(365.0);
+        assert_eq!(example_value(&days), "1");
+
+        let mut ratio = ParamSpec::new("ratio", ParamKind::Number);
+        ratio.min = Some(0.0);
+        ratio.max = Some(1.0);
+        assert_eq!(example_value(&ratio), "0.5");
+
+        let mut floor = ParamSpec::new("count", ParamKind::Integer);
+        floor.min = Some(5.0);
+        assert_eq!(example_value(&floor), "5");
+    }
+
+    #[test]
+    fn test_choices_win_and_are_quoted_for_prefix() {
+        let mut action = ParamSpec::new("action", ParamKind::Integer);
+        action.choices = vec!["reject and alert staff".to_string(), "warn".to_string()];
+        let example = build_example(
+            &["boosterrole", "filter", "enforcement"],
+            &[action],
+            "!",
+            true,
+        );
+        assert_eq!(
+            example.slash,
+            "/boosterrole filter enforcement action:reject and alert staff"
+        );
+        assert_eq!(
+            example.prefix.as_deref(),
+            Some("!boosterrole filter enforcement \"reject and alert staff\"")
+        );
+    }
+
+    #[test]
+    fn test_render_single_and_group() {
+        let single = UsageExample {
+            slash: "/ping".to_string(),
+            prefix: Some("!ping".to_string()),
+        };
+        assert_eq!(
+            render_examples(std::slice::from_ref(&single)),
+            "**Slash:** `/ping`\n**Prefix:** `!ping`"
+        );
+
+        let group = vec![single; MAX_GROUP_EXAMPLES + 2];
+        let rendered = render_examples(&group);
+        assert_eq!(rendered.lines().count(), MAX_GROUP_EXAMPLES + 1);
+        assert!(rendered.ends_with("…and 2 more, see `/help` on a subcommand"));
+    }
+
+    #[test]
+    fn test_real_metadata_and_nested_subcommands() {
+        let commands = crate::commands::all();
+
+        let limit = find(&commands, &["boosterrole", "limit"]);
+        let examples = command_examples(limit, &["boosterrole"], "!");
+        assert_eq!(examples.len(), 1);
+        assert!(examples[0].slash.contains(":50"));
+
+        let filter = find(&commands, &["boosterrole", "filter"]);
+        let examples = command_examples(filter, &["boosterrole"], "!");
+        assert_eq!(examples.len(), filter.subcommands.len());
+        assert!(examples
+            .iter()
+            .any(|e| e.slash.starts_with("/boosterrole filter add word:")));
+    }
+
+    #[test]
+    fn test_every_registered_example_is_valid() {
+        fn check(command: &poise::Command<Data, Error>) {
+            for sub in &command.subcommands {
+                check(sub);
+            }
+            for param in &command.parameters {
+                let spec = ParamSpec::from_parameter(param);
+                let value = example_value(&spec);
+                if !spec.choices.is_empty() {
+                    assert!(spec.choices.contains(&value), "{}", spec.name);
+                    continue;
+                }
+                if let ParamKind::Integer | ParamKind::Number = spec.kind {
+                    let number: f64 = value.parse().unwrap();
+                    assert!(spec.min.is_none_or(|min| number >= min), "{}", spec.name);
+                    assert!(spec.max.is_none_or(|max| number <= max), "{}", spec.name);
+                }
+                if spec.kind == ParamKind::Text {
+                    let len = value.chars().count();
+                    assert!(spec.min_length.is_none_or(|min| len >= min as usize));
+                    assert!(spec.max_length.is_none_or(|max| len <= max as usize));
+                }
+            }
+        }
+
+        for command in crate::commands::all() {
+            check(&command);
+        }
+    }
+}